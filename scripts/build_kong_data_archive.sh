@@ -0,0 +1,4 @@
+#!/usr/bin/env bash
+
+# kong_data_archive is not deployed directly. kong_data spawns archives from the wasm uploaded with set_archive_wasm
+cargo build --target wasm32-unknown-unknown --release -p kong_data_archive --locked
@@ -16,8 +16,18 @@ touch "src/${CANISTER_NAME}/${CANISTER_NAME}.did"
 # touch "target/wasm32-unknown-unknown/release/${CANISTER_NAME}-ic.wasm"
 touch "target/wasm32-unknown-unknown/release/${CANISTER_NAME}.wasm"
 
+# Canisters with network specific canister IDs are built with the matching feature
+FEATURES=""
+if grep -q '^\[features\]' "src/${CANISTER_NAME}/Cargo.toml"; then
+        case "$KONG_BUILDENV" in
+                ic) FEATURES="--features prod" ;;
+                staging) FEATURES="--features staging" ;;
+                local) FEATURES="--features local" ;;
+        esac
+fi
+
 # Build the Rust canister
-cargo build -p "$CANISTER_NAME" --release --target wasm32-unknown-unknown
+cargo build -p "$CANISTER_NAME" --release --target wasm32-unknown-unknown ${FEATURES}
 
 # Optimize the Wasm file
 # INPUT_WASM="target/wasm32-unknown-unknown/release/${CANISTER_NAME}.wasm"
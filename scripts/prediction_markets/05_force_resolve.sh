@@ -78,17 +78,17 @@ ALICE_PRINCIPAL=$(dfx identity get-principal)
 
 echo -e "${CMD_COLOR}Creating a new prediction market...${RESET}"
 run_dfx "dfx canister call prediction_markets_backend create_market \
-  \"(\\\"Will ETH price exceed \$ 5000 by end of 2025?\\\", variant { Crypto }, \\\"Standard rules apply\\\", \
-  vec { \\\"Yes\\\"; \\\"No\\\" }, variant { Admin }, \
-  variant { Duration = 120 : nat }, null, opt true, opt 0.1, \
-  opt \\\"\${KONG_LEDGER}\\\")\"" "Creating market with time-weighted rewards"
+  \"(record { question = \\\"Will ETH price exceed \$ 5000 by end of 2025?\\\"; category = variant { Crypto }; rules = \\\"Standard rules apply\\\"; \
+  outcomes = vec { \\\"Yes\\\"; \\\"No\\\" }; resolution_method = variant { Admin }; \
+  end_time = variant { Duration = 120 : nat }; image_url = null; uses_time_weighting = opt true; time_weight_alpha = opt 0.1; \
+  token_id = opt \\\"\${KONG_LEDGER}\\\" })\"" "Creating market with time-weighted rewards"
 
 # Extract market ID and check for success
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will ETH price exceed \$ 5000 by end of 2025?\", variant { Crypto }, \"Standard rules apply\", \
-  vec { \"Yes\"; \"No\" }, variant { Admin }, \
-  variant { Duration = 120 : nat }, null, opt true, opt 0.1, \
-  opt \"${KONG_LEDGER}\")")
+  "(record { question = \"Will ETH price exceed \$ 5000 by end of 2025?\"; category = variant { Crypto }; rules = \"Standard rules apply\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Admin }; \
+  end_time = variant { Duration = 120 : nat }; image_url = null; uses_time_weighting = opt true; time_weight_alpha = opt 0.1; \
+  token_id = opt \"${KONG_LEDGER}\" })")
 
 if [[ $RESULT == *"Ok"* ]]; then
     MARKET_ID=$(echo $RESULT | grep -o '[0-9]\+' | head -1)
@@ -24,11 +24,11 @@ echo "KONG Fee: ${KONG_FEE}"
 # Step 1: Create a market using KONG token (as admin)
 echo "Creating a new market with KONG tokens as admin..."
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will ETH reach $ 5,000 by the end of 2025?\", variant { Crypto }, \
-  \"Prediction on Ethereum price. Market resolves YES if ETH reaches $ 5,000 on any major exchange before the end of 2025.\", \
-  vec { \"Yes\"; \"No\" }, variant { Admin }, \
-  variant { Duration = 120 : nat }, null, null, null, \
-  opt \"o7oak-iyaaa-aaaaq-aadzq-cai\")")
+  "(record { question = \"Will ETH reach $ 5,000 by the end of 2025?\"; category = variant { Crypto }; \
+  rules = \"Prediction on Ethereum price. Market resolves YES if ETH reaches $ 5,000 on any major exchange before the end of 2025.\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Admin }; \
+  end_time = variant { Duration = 120 : nat }; image_url = null; uses_time_weighting = null; time_weight_alpha = null; \
+  token_id = opt \"o7oak-iyaaa-aaaaq-aadzq-cai\" })")
 
 # Extract market ID and check for success
 if [[ $RESULT == *"Ok"* ]]; then
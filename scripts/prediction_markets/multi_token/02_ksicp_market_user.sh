@@ -68,11 +68,11 @@ ACTIVATION_AMOUNT=100000000
 
 echo "Creating a new market with ckUSDT tokens as user..."
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will SOL reach $500 by the end of 2025?\", variant { Crypto }, \
-  \"Prediction on Solana price. Market resolves YES if SOL reaches $500 on any major exchange before the end of 2025.\", \
-  vec { \"Yes\"; \"No\" }, variant { Admin }, \
-  variant { Duration = 120 : nat }, null, null, null, \
-  opt \"${CKUSDT_TOKEN_ID}\")")
+  "(record { question = \"Will SOL reach $500 by the end of 2025?\"; category = variant { Crypto }; \
+  rules = \"Prediction on Solana price. Market resolves YES if SOL reaches $500 on any major exchange before the end of 2025.\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Admin }; \
+  end_time = variant { Duration = 120 : nat }; image_url = null; uses_time_weighting = null; time_weight_alpha = null; \
+  token_id = opt \"${CKUSDT_TOKEN_ID}\" })")
 
 # Extract market ID and check for success
 if [[ $RESULT == *"Ok"* ]]; then
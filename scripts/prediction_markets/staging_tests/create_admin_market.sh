@@ -155,14 +155,14 @@ section "CREATING MARKET"
 echo -e "${CMD_COLOR}Calling create_market on prediction markets canister...${RESET}"
 echo -e "${CMD_COLOR}DEBUG - Selected Token ID: ${RESET}${WARNING_COLOR}$SELECTED_TOKEN_ID${RESET}"
 echo -e "${EXEC_COLOR}dfx canister call $PREDICTION_MARKETS_CANISTER create_market \\${RESET}"
-echo -e "${EXEC_COLOR}  \"(\\\"$MARKET_QUESTION\\\", $MARKET_CATEGORY, \\\"$MARKET_RULES\\\", $MARKET_OUTCOMES, \\${RESET}"
-echo -e "${EXEC_COLOR}   variant { Admin }, variant { Duration = $MARKET_DURATION }, \\${RESET}"
-echo -e "${EXEC_COLOR}   opt \\\"$SELECTED_TOKEN_ID\\\", opt true, opt 0.8, null)\" \\${RESET}"
+echo -e "${EXEC_COLOR}  \"(record { question = \\\"$MARKET_QUESTION\\\"; category = $MARKET_CATEGORY; rules = \\\"$MARKET_RULES\\\"; outcomes = $MARKET_OUTCOMES; \\${RESET}"
+echo -e "${EXEC_COLOR}   resolution_method = variant { Admin }; end_time = variant { Duration = $MARKET_DURATION }; \\${RESET}"
+echo -e "${EXEC_COLOR}   uses_time_weighting = opt true; time_weight_alpha = opt 0.8; token_id = opt \\\"$SELECTED_TOKEN_ID\\\" })\" \\${RESET}"
 echo -e "${EXEC_COLOR}  --network $DFX_NETWORK${RESET}"
 echo ""
 
-# Build the command for debugging (CORRECTED parameter order per .did file)
-COMMAND_TO_EXECUTE="(\"$MARKET_QUESTION\", $MARKET_CATEGORY, \"$MARKET_RULES\", $MARKET_OUTCOMES, variant { Admin }, variant { Duration = $MARKET_DURATION }, null, opt true, opt 0.8, opt \"$SELECTED_TOKEN_ID\")"
+# Build the command for debugging (CreateMarketArgs record per .did file)
+COMMAND_TO_EXECUTE="(record { question = \"$MARKET_QUESTION\"; category = $MARKET_CATEGORY; rules = \"$MARKET_RULES\"; outcomes = $MARKET_OUTCOMES; resolution_method = variant { Admin }; end_time = variant { Duration = $MARKET_DURATION }; image_url = null; uses_time_weighting = opt true; time_weight_alpha = opt 0.8; token_id = opt \"$SELECTED_TOKEN_ID\" })"
 echo -e "${CMD_COLOR}DEBUG - Full command parameters:${RESET}"
 echo -e "${WARNING_COLOR}$COMMAND_TO_EXECUTE${RESET}"
 echo ""
@@ -261,15 +261,15 @@ section "CREATING USER MARKET"
 
 echo -e "${CMD_COLOR}Calling create_market on prediction markets canister...${RESET}"
 echo -e "${EXEC_COLOR}dfx canister call $PREDICTION_MARKETS_CANISTER create_market \\${RESET}"
-echo -e "${EXEC_COLOR}  \"(\\\"$MARKET_QUESTION\\\", $MARKET_CATEGORY, \\\"$MARKET_RULES\\\", $MARKET_OUTCOMES, \\${RESET}"
-echo -e "${EXEC_COLOR}   variant { Decentralized = record { quorum = 2 } }, variant { Duration = $MARKET_DURATION }, \\${RESET}"
-echo -e "${EXEC_COLOR}   opt \\\"$SELECTED_TOKEN_ID\\\", opt true, opt 0.8, null)\" \\${RESET}"
+echo -e "${EXEC_COLOR}  \"(record { question = \\\"$MARKET_QUESTION\\\"; category = $MARKET_CATEGORY; rules = \\\"$MARKET_RULES\\\"; outcomes = $MARKET_OUTCOMES; \\${RESET}"
+echo -e "${EXEC_COLOR}   resolution_method = variant { Decentralized = record { quorum = 2 } }; end_time = variant { Duration = $MARKET_DURATION }; \\${RESET}"
+echo -e "${EXEC_COLOR}   uses_time_weighting = opt true; time_weight_alpha = opt 0.8; token_id = opt \\\"$SELECTED_TOKEN_ID\\\" })\" \\${RESET}"
 echo -e "${EXEC_COLOR}  --network $DFX_NETWORK${RESET}"
 echo ""
 
 # Create the market with Decentralized resolution (dual approval)
 MARKET_CREATION_RESULT=$(dfx canister call $PREDICTION_MARKETS_CANISTER create_market \
-    "(\"$MARKET_QUESTION\", $MARKET_CATEGORY, \"$MARKET_RULES\", $MARKET_OUTCOMES, variant { Decentralized = record { quorum = 2 } }, variant { Duration = $MARKET_DURATION }, opt \"$SELECTED_TOKEN_ID\", opt true, opt 0.8, null)" \
+    "(record { question = \"$MARKET_QUESTION\"; category = $MARKET_CATEGORY; rules = \"$MARKET_RULES\"; outcomes = $MARKET_OUTCOMES; resolution_method = variant { Decentralized = record { quorum = 2 } }; end_time = variant { Duration = $MARKET_DURATION }; image_url = null; uses_time_weighting = opt true; time_weight_alpha = opt 0.8; token_id = opt \"$SELECTED_TOKEN_ID\" })" \
     --network $DFX_NETWORK 2>&1)
 
 echo -e "${CMD_COLOR}Result: ${RESET}${RESULT_COLOR}$MARKET_CREATION_RESULT${RESET}"
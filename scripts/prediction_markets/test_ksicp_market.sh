@@ -16,18 +16,18 @@ echo "ICP Fee: ${ICP_FEE}"
 
 # Step 1: Create a market using ICP token
 echo "Creating a new market with ICP tokens..."
-MARKET_ID=$(dfx canister call prediction_markets_backend create_market '(
-  "Will BTC reach $100,000 by the end of 2025?",
-  variant { Crypto },
-  "Prediction on Bitcoin price. Market resolves YES if BTC reaches $100,000 on any major exchange before the end of 2025.",
-  vec { "Yes"; "No" },
-  variant { Admin },
-  variant { Timestamp = 1766822400000000000 }, 
-  null,
-  null, 
-  null,
-  opt "ulvla-h7777-77774-qaacq-cai"
-)' | grep -oP '\(\s*\K[0-9]+(?=\s*:\s*nat\s*\))')
+MARKET_ID=$(dfx canister call prediction_markets_backend create_market '(record {
+  question = "Will BTC reach $100,000 by the end of 2025?";
+  category = variant { Crypto };
+  rules = "Prediction on Bitcoin price. Market resolves YES if BTC reaches $100,000 on any major exchange before the end of 2025.";
+  outcomes = vec { "Yes"; "No" };
+  resolution_method = variant { Admin };
+  end_time = variant { Timestamp = 1766822400000000000 }; 
+  image_url = null;
+  uses_time_weighting = null; 
+  time_weight_alpha = null;
+  token_id = opt "ulvla-h7777-77774-qaacq-cai"
+})' | grep -oP '\(\s*\K[0-9]+(?=\s*:\s*nat\s*\))')
 
 echo "Created market with ID: ${MARKET_ID}"
 
@@ -22,9 +22,9 @@ ADMIN_PRINCIPAL=$(dfx identity get-principal)
 echo "Using Admin principal: $ADMIN_PRINCIPAL"
 
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will BTC exceed $100k in 2025?\", variant { Crypto }, \"Standard rules apply\", \
-  vec { \"Yes\"; \"No\" }, variant { Admin }, \
-  variant { Duration = 120 : nat }, null, opt false, null)")
+  "(record { question = \"Will BTC exceed $100k in 2025?\"; category = variant { Crypto }; rules = \"Standard rules apply\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Admin }; \
+  end_time = variant { Duration = 120 : nat }; image_url = null; uses_time_weighting = opt false; time_weight_alpha = null })")
 
 # Extract market ID
 if [[ $RESULT == *"Ok"* ]]; then
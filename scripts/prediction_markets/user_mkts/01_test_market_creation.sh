@@ -24,10 +24,10 @@ echo "KONG Fee: $KONG_FEE"
 # Step 1: Create a new market as a regular user (Alice)
 echo -e "\n==== Step 1: Creating market as regular user (Alice) ===="
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will BTC reach $ 100k in 2025?\", variant { Crypto }, \"Standard rules apply\", \
-  vec { \"Yes\"; \"No\" }, variant { Admin }, \
-  variant { Duration = 120 : nat }, null, null, null, \
-  opt \"umunu-kh777-77774-qaaca-cai\")")
+  "(record { question = \"Will BTC reach $ 100k in 2025?\"; category = variant { Crypto }; rules = \"Standard rules apply\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Admin }; \
+  end_time = variant { Duration = 120 : nat }; image_url = null; uses_time_weighting = null; time_weight_alpha = null; \
+  token_id = opt \"umunu-kh777-77774-qaaca-cai\" })")
 # ^ Using Admin resolution method - the dual approval flow is triggered automatically for non-admin creators
 
 # Extract market ID and check for success
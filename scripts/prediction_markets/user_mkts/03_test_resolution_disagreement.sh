@@ -23,10 +23,10 @@ ALICE_PRINCIPAL=$(dfx identity get-principal)
 echo "Using Alice's principal: $ALICE_PRINCIPAL"
 
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will SOL price exceed $ 500 by end of 2025?\", variant { Crypto }, \"Standard rules apply\", \
-  vec { \"Yes\"; \"No\" }, variant { Admin }, \
-  variant { Duration = 61 : nat }, null, opt true, opt 0.1, \
-  opt \"${KONG_LEDGER}\")")
+  "(record { question = \"Will SOL price exceed $ 500 by end of 2025?\"; category = variant { Crypto }; rules = \"Standard rules apply\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Admin }; \
+  end_time = variant { Duration = 61 : nat }; image_url = null; uses_time_weighting = opt true; time_weight_alpha = opt 0.1; \
+  token_id = opt \"${KONG_LEDGER}\" })")
 
 # Extract market ID and check for success
 if [[ $RESULT == *"Ok"* ]]; then
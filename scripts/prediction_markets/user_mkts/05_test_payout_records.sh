@@ -23,10 +23,10 @@ DEFAULT_PRINCIPAL=$(dfx identity get-principal)
 echo "Using Admin's principal: $DEFAULT_PRINCIPAL"
 
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will LINK reach $50 by end of 2025?\", variant { Crypto }, \"Standard rules apply\", \
-  vec { \"Yes\"; \"No\" }, variant { Admin }, \
-  variant { Duration = 300 : nat }, null, opt true, opt 0.15, \
-  opt \"${KONG_LEDGER}\")")
+  "(record { question = \"Will LINK reach $50 by end of 2025?\"; category = variant { Crypto }; rules = \"Standard rules apply\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Admin }; \
+  end_time = variant { Duration = 300 : nat }; image_url = null; uses_time_weighting = opt true; time_weight_alpha = opt 0.15; \
+  token_id = opt \"${KONG_LEDGER}\" })")
 
 # Extract market ID
 MARKET_ID=$(echo $RESULT | grep -o '[0-9]\+' | head -1)
@@ -26,20 +26,20 @@ dfx identity use alice
 
 # Try to create market with Oracle resolution
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will DOT reach $30 by end of 2025?\", variant { Crypto }, \"Standard rules apply\", \
-  vec { \"Yes\"; \"No\" }, variant { Oracle = record { oracle_principals = vec { principal \"$ORACLE_PRINCIPAL\"; principal \"$ADMIN_PRINCIPAL\" }; required_confirmations = 1 : nat } }, \
-  variant { Duration = 300 : nat }, null, null, null, \
-  opt \"${KONG_LEDGER}\")")
+  "(record { question = \"Will DOT reach $30 by end of 2025?\"; category = variant { Crypto }; rules = \"Standard rules apply\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Oracle = record { oracle_principals = vec { principal \"$ORACLE_PRINCIPAL\"; principal \"$ADMIN_PRINCIPAL\" }; required_confirmations = 1 : nat } }; \
+  end_time = variant { Duration = 300 : nat }; image_url = null; uses_time_weighting = null; time_weight_alpha = null; \
+  token_id = opt \"${KONG_LEDGER}\" })")
 
 echo "Result: $RESULT"
 
 # Step 2: Create a market with Admin resolution as a regular user (Alice)
 echo -e "\n==== Step 2: Creating a market with Admin resolution as regular user (Alice) ===="
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will DOT reach $30 by end of 2025?\", variant { Crypto }, \"Standard rules apply\", \
-  vec { \"Yes\"; \"No\" }, variant { Admin }, \
-  variant { Duration = 300 : nat }, null, null, null, \
-  opt \"${KONG_LEDGER}\")")
+  "(record { question = \"Will DOT reach $30 by end of 2025?\"; category = variant { Crypto }; rules = \"Standard rules apply\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Admin }; \
+  end_time = variant { Duration = 300 : nat }; image_url = null; uses_time_weighting = null; time_weight_alpha = null; \
+  token_id = opt \"${KONG_LEDGER}\" })")
 
 # Extract market ID
 MARKET_ID=$(echo $RESULT | grep -o '[0-9]\+' | head -1)
@@ -57,10 +57,10 @@ echo "Using Admin's principal: $DEFAULT_PRINCIPAL"
 
 # Create market with Oracle resolution
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will ATOM reach $20 by end of 2025?\", variant { Crypto }, \"Standard rules apply\", \
-  vec { \"Yes\"; \"No\" }, variant { Oracle = record { oracle_principals = vec { principal \"$ALICE_PRINCIPAL\"; principal \"$DEFAULT_PRINCIPAL\" }; required_confirmations = 1 : nat } }, \
-  variant { Duration = 300 : nat }, null, null, null, \
-  opt \"${KONG_LEDGER}\")")
+  "(record { question = \"Will ATOM reach $20 by end of 2025?\"; category = variant { Crypto }; rules = \"Standard rules apply\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Oracle = record { oracle_principals = vec { principal \"$ALICE_PRINCIPAL\"; principal \"$DEFAULT_PRINCIPAL\" }; required_confirmations = 1 : nat } }; \
+  end_time = variant { Duration = 300 : nat }; image_url = null; uses_time_weighting = null; time_weight_alpha = null; \
+  token_id = opt \"${KONG_LEDGER}\" })")
 
 # Extract market ID
 MARKET_ID_ADMIN=$(echo $RESULT | grep -o '[0-9]\+' | head -1)
@@ -78,10 +78,10 @@ echo "Using Bob's principal: $BOB_PRINCIPAL"
 
 echo "Attempting to create market with Decentralized resolution (should fail)..."
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will ALGO reach $5 by end of 2025?\", variant { Crypto }, \"Standard rules apply\", \
-  vec { \"Yes\"; \"No\" }, variant { Decentralized = record { quorum = 100000 : nat } }, \
-  variant { Duration = 300 : nat }, null, null, null, \
-  opt \"${KONG_LEDGER}\")")
+  "(record { question = \"Will ALGO reach $5 by end of 2025?\"; category = variant { Crypto }; rules = \"Standard rules apply\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Decentralized = record { quorum = 100000 : nat } }; \
+  end_time = variant { Duration = 300 : nat }; image_url = null; uses_time_weighting = null; time_weight_alpha = null; \
+  token_id = opt \"${KONG_LEDGER}\" })")
 
 
 echo "Result: $RESULT"
@@ -93,10 +93,10 @@ echo "Using Admin's principal: $DEFAULT_PRINCIPAL"
 
 # Create market with Decentralized resolution
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will ALGO reach $5 by end of 2025?\", variant { Crypto }, \"Standard rules apply\", \
-  vec { \"Yes\"; \"No\" }, variant { Decentralized = record { quorum = 100000 : nat } }, \
-  variant { Duration = 300 : nat }, null, null, null, \
-  opt \"${KONG_LEDGER}\")")
+  "(record { question = \"Will ALGO reach $5 by end of 2025?\"; category = variant { Crypto }; rules = \"Standard rules apply\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Decentralized = record { quorum = 100000 : nat } }; \
+  end_time = variant { Duration = 300 : nat }; image_url = null; uses_time_weighting = null; time_weight_alpha = null; \
+  token_id = opt \"${KONG_LEDGER}\" })")
 
 
 # Extract market ID
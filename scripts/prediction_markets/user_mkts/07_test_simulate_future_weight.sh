@@ -20,10 +20,10 @@ echo "Using Admin's principal: $DEFAULT_PRINCIPAL"
 
 # Create market with time weighting enabled and alpha = 0.25
 RESULT=$(dfx canister call prediction_markets_backend create_market \
-  "(\"Will XRP reach $2 by end of 2025?\", variant { Crypto }, \"Standard rules apply\", \
-  vec { \"Yes\"; \"No\" }, variant { Admin }, \
-  variant { Duration = 600 : nat }, null, opt true, opt 0.25, \
-  opt \"${KONG_LEDGER}\")")
+  "(record { question = \"Will XRP reach $2 by end of 2025?\"; category = variant { Crypto }; rules = \"Standard rules apply\"; \
+  outcomes = vec { \"Yes\"; \"No\" }; resolution_method = variant { Admin }; \
+  end_time = variant { Duration = 600 : nat }; image_url = null; uses_time_weighting = opt true; time_weight_alpha = opt 0.25; \
+  token_id = opt \"${KONG_LEDGER}\" })")
 
 
 # Extract market ID
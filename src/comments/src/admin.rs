@@ -1,8 +1,10 @@
 use ic_cdk::api::caller;
 use crate::state::*;
+use crate::types::{Comment};
 use candid::Principal;
+use kong_lib::stable_trusted_origin::stable_trusted_origin::StableTrustedOriginLog;
 /// Check if the caller is an admin
-fn caller_is_admin() -> bool {
+pub(crate) fn caller_is_admin() -> bool {
     let caller_principal = caller();
     ADMINS.with(|admins| {
         admins.borrow().contains(&caller_principal)
@@ -10,7 +12,7 @@ fn caller_is_admin() -> bool {
 }
 
 /// Check if the caller is a controller of the canister
-fn caller_is_controller() -> bool {
+pub(crate) fn caller_is_controller() -> bool {
     let caller_principal = caller();
     ic_cdk::api::is_controller(&caller_principal)
 }
@@ -66,9 +68,9 @@ pub fn add_admin(principal: String) -> Result<(), String> {
     Ok(())
 }
 
-/// Allows admins to delete any comment
+/// Allows admins to delete any comment. The comment is replaced with a tombstone showing the reason
 #[ic_cdk::update]
-pub fn delete_comment(comment_id: u64) -> Result<(), String> {
+pub fn delete_comment(comment_id: u64, reason: Option<String>) -> Result<(), String> {
     // Check if caller is an admin
     if !caller_is_admin() {
         return Err("Unauthorized: Only admins can delete comments".to_string());
@@ -80,19 +82,18 @@ pub fn delete_comment(comment_id: u64) -> Result<(), String> {
     });
     
     if let Some(comment) = comment {
-        // Delete the comment
-        COMMENT_STORE.with(|store| {
-            store.borrow_mut().remove(&comment_id);
-        });
+        if comment.deleted.is_some() {
+            return Err(format!("Comment with ID {} is already deleted", comment_id));
+        }
         
-        // Update context comment count
-        CONTEXT_COMMENT_COUNT.with(|counts| {
-            let mut counts = counts.borrow_mut();
-            if let Some(count) = counts.get_mut(&comment.context_id) {
-                *count = count.saturating_sub(1);
-            }
+        // Record the removal in the moderation log so the author can appeal
+        MODERATION.with(|moderation| {
+            moderation.borrow_mut().record_removal(comment_id, comment.author, &comment.content, caller(), reason.clone(), ic_cdk::api::time());
         });
         
+        // Replace the comment with a tombstone and update context comment count
+        tombstone_comment(comment, caller(), true, reason);
+        
         Ok(())
     } else {
         Err(format!("Comment with ID {} not found", comment_id))
@@ -108,14 +109,15 @@ pub fn delete_context_comments(context_id: String) -> Result<u32, String> {
     }
     
     let mut deleted_count = 0;
+    let reason = Some(format!("Context {} deleted", context_id));
     
-    // Collect comment IDs to delete
-    let comment_ids_to_delete: Vec<u64> = COMMENT_STORE.with(|store| {
+    // Collect comments to delete
+    let comments_to_delete: Vec<Comment> = COMMENT_STORE.with(|store| {
         store.borrow()
             .iter()
-            .filter_map(|(id, comment)| {
-                if comment.context_id == context_id {
-                    Some(id)
+            .filter_map(|(_, comment)| {
+                if comment.context_id == context_id && comment.deleted.is_none() {
+                    Some(comment)
                 } else {
                     None
                 }
@@ -123,14 +125,18 @@ pub fn delete_context_comments(context_id: String) -> Result<u32, String> {
             .collect()
     });
     
-    // Delete comments
-    COMMENT_STORE.with(|store| {
-        let mut store = store.borrow_mut();
-        for id in comment_ids_to_delete {
-            store.remove(&id);
-            deleted_count += 1;
+    // Record each removal in the moderation log and replace the comments with tombstones
+    let now = ic_cdk::api::time();
+    MODERATION.with(|moderation| {
+        let mut moderation = moderation.borrow_mut();
+        for comment in &comments_to_delete {
+            moderation.record_removal(comment.id, comment.author, &comment.content, caller(), reason.clone(), now);
         }
     });
+    for comment in comments_to_delete {
+        tombstone_comment(comment, caller(), true, reason.clone());
+        deleted_count += 1;
+    }
     
     // Reset context comment count
     CONTEXT_COMMENT_COUNT.with(|counts| {
@@ -148,17 +154,9 @@ pub fn ban_user(user_principal: candid::Principal, days: u64) -> Result<(), Stri
         return Err("Unauthorized: Only admins can ban users".to_string());
     }
     
-    // Convert days to nanoseconds and add to current time
-    let ban_duration_ns = days * 24 * 60 * 60 * 1_000_000_000;
-    let current_time = ic_cdk::api::time();
-    let ban_expiry = current_time + ban_duration_ns;
-    
-    // Add user to banned list (using stable storage)
-    BANNED_USERS_STORE.with(|banned_users| {
-        banned_users.borrow_mut().insert(
-            PrincipalStorable(user_principal),
-            ExpiryTimeStorable(ban_expiry)
-        );
+    // Add user to banned list and record the ban in the moderation log
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().ban(user_principal, days, caller(), ic_cdk::api::time());
     });
     
     Ok(())
@@ -172,9 +170,9 @@ pub fn unban_user(user_principal: candid::Principal) -> Result<(), String> {
         return Err("Unauthorized: Only admins can unban users".to_string());
     }
     
-    // Remove user from banned list (using stable storage)
-    BANNED_USERS_STORE.with(|banned_users| {
-        banned_users.borrow_mut().remove(&PrincipalStorable(user_principal));
+    // Remove user from banned list and record the unban in the moderation log
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().unban(user_principal, caller(), ic_cdk::api::time());
     });
     
     Ok(())
@@ -184,4 +182,40 @@ pub fn unban_user(user_principal: candid::Principal) -> Result<(), String> {
 #[ic_cdk::query]
 pub fn check_ban_status(user_principal: candid::Principal) -> Option<u64> {
     is_user_banned(&user_principal)
-} 
\ No newline at end of file
+}
+
+/// Allows admins or controllers to add a trusted origin returned by icrc28_trusted_origins
+#[ic_cdk::update]
+pub fn add_trusted_origin(origin: String) -> Result<Vec<String>, String> {
+    if !caller_is_admin() && !caller_is_controller() {
+        return Err("Unauthorized: Only admins or controllers can add trusted origins".to_string());
+    }
+
+    TRUSTED_ORIGINS.with(|store| {
+        store.borrow_mut().add(&origin, &caller().to_text(), ic_cdk::api::time())
+    })
+}
+
+/// Allows admins or controllers to remove a trusted origin
+#[ic_cdk::update]
+pub fn remove_trusted_origin(origin: String) -> Result<Vec<String>, String> {
+    if !caller_is_admin() && !caller_is_controller() {
+        return Err("Unauthorized: Only admins or controllers can remove trusted origins".to_string());
+    }
+
+    TRUSTED_ORIGINS.with(|store| {
+        store.borrow_mut().remove(&origin, &caller().to_text(), ic_cdk::api::time())
+    })
+}
+
+/// Returns the audit log of changes to the trusted origins, newest first
+#[ic_cdk::query]
+pub fn get_trusted_origins_log(limit: Option<u64>) -> Result<Vec<StableTrustedOriginLog>, String> {
+    if !caller_is_admin() && !caller_is_controller() {
+        return Err("Unauthorized: Only admins or controllers can view the trusted origins log".to_string());
+    }
+
+    Ok(TRUSTED_ORIGINS.with(|store| {
+        store.borrow().get_logs(limit.map(|l| l as usize))
+    }))
+}
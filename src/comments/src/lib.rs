@@ -1,6 +1,7 @@
 use icrc_ledger_types::icrc21::requests::ConsentMessageRequest;
 use icrc_ledger_types::icrc21::responses::ConsentInfo;
 use icrc_ledger_types::icrc21::errors::ErrorInfo;
+use kong_lib::stable_trusted_origin::stable_trusted_origin::StableTrustedOriginLog;
 use candid::Principal;
 
 // Module declarations
@@ -10,6 +11,8 @@ mod state;
 mod queries;
 mod updates;
 mod admin;
+mod reports;
+mod notifications;
 
 // Public exports
 pub use authentication::*;
@@ -17,6 +20,8 @@ pub use types::*;
 pub use queries::*;
 pub use updates::*;
 pub use admin::*;
+pub use reports::*;
+pub use notifications::*;
 
 // Initialize the canister
 #[ic_cdk::init]
@@ -0,0 +1,98 @@
+use ic_cdk::api::caller;
+use crate::state::*;
+use crate::types::*;
+
+// Range of the caller's notifications up to but excluding the cursor
+fn inbox_range(cursor: Option<u64>) -> std::ops::Range<NotificationKey> {
+    let principal = caller();
+    NotificationKey { principal, id: 0 }..NotificationKey { principal, id: cursor.unwrap_or(u64::MAX) }
+}
+
+fn count_unread() -> u32 {
+    NOTIFICATION_STORE.with(|store| {
+        store.borrow()
+            .range(inbox_range(None))
+            .filter(|(_, notification)| !notification.read)
+            .count() as u32
+    })
+}
+
+/// Returns the caller's notifications, newest first
+#[ic_cdk::query]
+pub fn get_notifications(request: GetNotificationsRequest) -> NotificationsPage {
+    let params = request.pagination.unwrap_or(PaginationParams {
+        cursor: None,
+        limit: None,
+    });
+
+    // Convert u64 limit to usize, with bounds checking
+    let limit = params.limit
+        .map(|l| l.try_into().unwrap_or(DEFAULT_PAGE_SIZE))
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .min(MAX_NOTIFICATIONS_PAGE_SIZE);
+
+    let notifications: Vec<Notification> = NOTIFICATION_STORE.with(|store| {
+        store.borrow()
+            .range(inbox_range(params.cursor))
+            .rev()
+            .map(|(_, notification)| notification)
+            .filter(|notification| !request.unread_only || !notification.read)
+            .take(limit)
+            .collect()
+    });
+
+    // Set next cursor to the ID of the last notification if we have more notifications
+    let next_cursor = if notifications.len() == limit {
+        notifications.last().map(|notification| notification.id)
+    } else {
+        None
+    };
+
+    NotificationsPage {
+        notifications,
+        next_cursor,
+        unread_count: count_unread(),
+    }
+}
+
+#[ic_cdk::query]
+pub fn get_unread_notification_count() -> u32 {
+    count_unread()
+}
+
+/// Marks the caller's notifications with the given IDs as read and returns the number of unread notifications left
+#[ic_cdk::update]
+pub fn mark_notifications_read(ids: Vec<u64>) -> u32 {
+    let principal = caller();
+    NOTIFICATION_STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        for id in ids {
+            let key = NotificationKey { principal, id };
+            if let Some(mut notification) = store.get(&key) {
+                if !notification.read {
+                    notification.read = true;
+                    store.insert(key, notification);
+                }
+            }
+        }
+    });
+    count_unread()
+}
+
+/// Marks all of the caller's notifications as read and returns the number of notifications marked
+#[ic_cdk::update]
+pub fn mark_all_notifications_read() -> u32 {
+    NOTIFICATION_STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        let unread: Vec<(NotificationKey, Notification)> = store
+            .range(inbox_range(None))
+            .filter(|(_, notification)| !notification.read)
+            .collect();
+        let marked = unread.len() as u32;
+        for (key, mut notification) in unread {
+            notification.read = true;
+            store.insert(key, notification);
+        }
+        marked
+    })
+}
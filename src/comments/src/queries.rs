@@ -22,6 +22,9 @@ fn to_comment_response(comment: Comment, caller: &Principal) -> CommentResponse
         is_edited: comment.is_edited,
         edited_at: comment.edited_at,
         has_liked,
+        reactions: reaction_counts(comment.id, Some(caller)),
+        mentions: comment.mentions,
+        deleted: comment.deleted,
     }
 }
 
@@ -49,16 +52,20 @@ pub fn get_comments_by_context(request: GetCommentsRequest) -> CommentsPage {
             };
         }
 
-        // Collect comments for the specific context
-        let mut comments: Vec<Comment> = store.iter()
-            .filter_map(|(_, comment)| {
-                if comment.context_id == request.context_id {
-                    Some(comment.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // Collect comments for the specific context, leaving out comments hidden by moderation.
+        // Tombstones of deleted comments are kept so replies stay in their thread
+        let mut comments: Vec<Comment> = MODERATION.with(|moderation| {
+            let moderation = moderation.borrow();
+            store.iter()
+                .filter_map(|(_, comment)| {
+                    if comment.context_id == request.context_id && (comment.deleted.is_some() || !moderation.is_hidden(comment.id)) {
+                        Some(comment.clone())
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        });
         
         // Sort by newest first (using timestamp)
         comments.sort_by(|a, b| b.created_at.cmp(&a.created_at));
@@ -93,6 +100,9 @@ pub fn get_comments_by_context(request: GetCommentsRequest) -> CommentsPage {
                         is_edited: comment.is_edited,
                         edited_at: comment.edited_at,
                         has_liked: false,
+                        reactions: reaction_counts(comment.id, None),
+                        mentions: comment.mentions,
+                        deleted: comment.deleted,
                     }
                 }
             })
@@ -114,9 +124,38 @@ pub fn get_comments_by_context(request: GetCommentsRequest) -> CommentsPage {
 
 #[ic_cdk::query]
 pub fn get_comment(id: u64) -> Option<Comment> {
-    COMMENT_STORE.with(|store| {
+    let comment = COMMENT_STORE.with(|store| {
         store.borrow().get(&id)
-    })
+    })?;
+    if comment.deleted.is_none() && MODERATION.with(|moderation| moderation.borrow().is_hidden(id)) {
+        return None;
+    }
+    Some(comment)
+}
+
+/// Returns the revisions of a comment, oldest first and ending with the current content.
+/// Revisions of hidden or deleted comments are only returned to their author and admins
+#[ic_cdk::query]
+pub fn get_comment_history(comment_id: u64) -> Result<Vec<CommentRevision>, String> {
+    let comment = COMMENT_STORE.with(|store| {
+        store.borrow().get(&comment_id)
+    }).ok_or("Comment not found")?;
+
+    let caller = ic_cdk::api::caller();
+    let restricted = comment.deleted.is_some() || MODERATION.with(|moderation| moderation.borrow().is_hidden(comment_id));
+    if restricted && comment.author != caller && !crate::admin::caller_is_admin() && !crate::admin::caller_is_controller() {
+        return Err("Comment not found".to_string());
+    }
+
+    let mut revisions = get_revisions(comment_id);
+    if comment.deleted.is_none() {
+        revisions.push(CommentRevision {
+            revision: revisions.len() as u32,
+            content: comment.content,
+            created_at: comment.edited_at.unwrap_or(comment.created_at),
+        });
+    }
+    Ok(revisions)
 }
 
 #[ic_cdk::query]
@@ -131,16 +170,19 @@ pub fn get_user_comments(principal: Principal, limit: Option<u32>) -> Vec<Commen
     let limit = limit.unwrap_or(50).min(100) as usize;
     
     COMMENT_STORE.with(|store| {
-        let mut comments: Vec<Comment> = store.borrow()
-            .iter()
-            .filter_map(|(_, comment)| {
-                if comment.author == principal {
-                    Some(comment.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let mut comments: Vec<Comment> = MODERATION.with(|moderation| {
+            let moderation = moderation.borrow();
+            store.borrow()
+                .iter()
+                .filter_map(|(_, comment)| {
+                    if comment.author == principal && comment.deleted.is_none() && !moderation.is_hidden(comment.id) {
+                        Some(comment.clone())
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        });
         
         comments.sort_by(|a, b| b.created_at.cmp(&a.created_at));
         comments.truncate(limit);
@@ -148,6 +190,12 @@ pub fn get_user_comments(principal: Principal, limit: Option<u32>) -> Vec<Commen
     })
 }
 
+#[ic_cdk::query]
+pub fn get_reactions(comment_id: u64) -> Vec<ReactionCount> {
+    let caller = ic_cdk::api::caller();
+    reaction_counts(comment_id, Some(&caller))
+}
+
 // New authenticated query to get liked comment IDs for the current user
 #[ic_cdk::query]
 pub fn get_user_liked_comments() -> Vec<u64> {
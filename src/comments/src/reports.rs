@@ -0,0 +1,188 @@
+use candid::Principal;
+use ic_cdk::api::{caller, time};
+use moderation::NewReport;
+use crate::admin::{caller_is_admin, caller_is_controller};
+use crate::state::*;
+use crate::types::*;
+
+fn caller_is_moderator() -> bool {
+    caller_is_admin() || caller_is_controller()
+}
+
+/// Report a comment for review by moderators. Comments are hidden automatically after enough reports
+#[ic_cdk::update]
+pub fn report_comment(comment_id: u64, reason: ReportReason, note: Option<String>) -> Result<Report, String> {
+    let caller = caller();
+    if caller == Principal::anonymous() {
+        return Err("You must be logged in to report comments".to_string());
+    }
+
+    let comment = COMMENT_STORE.with(|store| {
+        store.borrow().get(&comment_id)
+    }).filter(|comment| comment.deleted.is_none()).ok_or("Comment not found")?;
+
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().report(NewReport {
+            content_id: comment_id,
+            author: comment.author,
+            snapshot: comment.content,
+            reporter: caller,
+            reason,
+            note,
+        }, time())
+    })
+}
+
+/// Returns reported comments waiting for review, oldest first
+#[ic_cdk::query]
+pub fn get_moderation_queue(cursor: Option<u64>, limit: Option<u64>) -> Result<ReviewQueuePage, String> {
+    if !caller_is_moderator() {
+        return Err("Unauthorized: Only admins or controllers can view the moderation queue".to_string());
+    }
+
+    Ok(MODERATION.with(|moderation| {
+        moderation.borrow().review_queue(cursor, limit)
+    }))
+}
+
+/// Allows admins or controllers to dismiss reports, hide or remove a reported comment
+#[ic_cdk::update]
+pub fn review_comment(comment_id: u64, decision: ReviewDecision, note: Option<String>) -> Result<ModeratedContent, String> {
+    if !caller_is_moderator() {
+        return Err("Unauthorized: Only admins or controllers can review comments".to_string());
+    }
+
+    let remove = decision == ReviewDecision::Remove;
+    let content = MODERATION.with(|moderation| {
+        moderation.borrow_mut().review(comment_id, decision, caller(), note, time())
+    })?;
+
+    if remove {
+        let comment = COMMENT_STORE.with(|store| store.borrow().get(&comment_id))
+            .filter(|comment| comment.deleted.is_none());
+        if let Some(comment) = comment {
+            tombstone_comment(comment, caller(), true, content.reason.clone());
+        }
+    }
+    Ok(content)
+}
+
+/// Returns the moderation status of a comment to its author or a moderator, including the reason it was hidden or removed
+#[ic_cdk::query]
+pub fn get_comment_moderation(comment_id: u64) -> Result<Option<ModeratedContent>, String> {
+    let content = MODERATION.with(|moderation| {
+        moderation.borrow().content(comment_id)
+    });
+    match content {
+        Some(content) if content.author != caller() && !caller_is_moderator() => {
+            Err("Unauthorized: Only the author or moderators can view the moderation status".to_string())
+        }
+        content => Ok(content),
+    }
+}
+
+/// Appeal against a hidden or removed comment or against a ban
+#[ic_cdk::update]
+pub fn appeal(target: AppealTarget, reason: String) -> Result<Appeal, String> {
+    let caller = caller();
+    if caller == Principal::anonymous() {
+        return Err("You must be logged in to appeal".to_string());
+    }
+
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().appeal(target, caller, reason, time())
+    })
+}
+
+/// Returns the caller's appeals
+#[ic_cdk::query]
+pub fn get_my_appeals() -> Vec<Appeal> {
+    MODERATION.with(|moderation| {
+        moderation.borrow().appeals_by(&caller())
+    })
+}
+
+/// Returns appeals, optionally filtered by status, oldest first
+#[ic_cdk::query]
+pub fn get_appeals(status: Option<AppealStatus>, cursor: Option<u64>, limit: Option<u64>) -> Result<AppealsPage, String> {
+    if !caller_is_moderator() {
+        return Err("Unauthorized: Only admins or controllers can view appeals".to_string());
+    }
+
+    Ok(MODERATION.with(|moderation| {
+        moderation.borrow().appeals(status, cursor, limit)
+    }))
+}
+
+/// Allows admins or controllers to accept or reject an appeal. Accepting restores hidden comments or lifts the ban
+#[ic_cdk::update]
+pub fn resolve_appeal(appeal_id: u64, accept: bool, note: Option<String>) -> Result<Appeal, String> {
+    if !caller_is_moderator() {
+        return Err("Unauthorized: Only admins or controllers can resolve appeals".to_string());
+    }
+
+    let appeal = MODERATION.with(|moderation| {
+        moderation.borrow_mut().resolve_appeal(appeal_id, accept, caller(), note, time())
+    })?;
+
+    // Comments deleted by a moderator are restored from their revision history
+    if let (true, AppealTarget::Content(comment_id)) = (accept, &appeal.target) {
+        restore_comment(*comment_id);
+    }
+    Ok(appeal)
+}
+
+/// Returns the blocked and censored word lists
+#[ic_cdk::query]
+pub fn get_word_lists() -> Result<WordLists, String> {
+    if !caller_is_moderator() {
+        return Err("Unauthorized: Only admins or controllers can view the word lists".to_string());
+    }
+
+    Ok(MODERATION.with(|moderation| {
+        moderation.borrow().settings().word_lists
+    }))
+}
+
+/// Allows admins or controllers to replace the blocked and censored word lists
+#[ic_cdk::update]
+pub fn set_word_lists(word_lists: WordLists) -> Result<WordLists, String> {
+    if !caller_is_moderator() {
+        return Err("Unauthorized: Only admins or controllers can update the word lists".to_string());
+    }
+
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().set_word_lists(word_lists, caller(), time())
+    })
+}
+
+#[ic_cdk::query]
+pub fn get_moderation_config() -> ModerationConfig {
+    MODERATION.with(|moderation| {
+        moderation.borrow().settings().config
+    })
+}
+
+/// Allows admins or controllers to change the auto-hide threshold and report limits
+#[ic_cdk::update]
+pub fn set_moderation_config(config: ModerationConfig) -> Result<(), String> {
+    if !caller_is_moderator() {
+        return Err("Unauthorized: Only admins or controllers can update the moderation config".to_string());
+    }
+
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().set_config(config, caller(), time())
+    })
+}
+
+/// Returns the audit log of moderator actions, newest first
+#[ic_cdk::query]
+pub fn get_moderation_log(cursor: Option<u64>, limit: Option<u64>) -> Result<ModerationLogPage, String> {
+    if !caller_is_moderator() {
+        return Err("Unauthorized: Only admins or controllers can view the moderation log".to_string());
+    }
+
+    Ok(MODERATION.with(|moderation| {
+        moderation.borrow().log_page(cursor, limit)
+    }))
+}
@@ -1,50 +1,17 @@
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
-use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, Storable};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
 use std::cell::RefCell;
-use crate::types::Comment;
+use crate::types::{Comment, CommentRevision, Notification, NotificationKey, NotificationKind, ReactionCount, ReactionKey, RevisionKey, Tombstone, DEFAULT_TRUSTED_ORIGINS};
 use regex::Regex;
 use lazy_static::lazy_static;
-use rustrict::Censor;
 use std::collections::{HashMap, HashSet};
 use candid::Principal;
-use std::borrow::Cow;
+use moderation::{ModerationMemories, ModerationStore};
+use kong_lib::stable_trusted_origin::trusted_origin_store::{TrustedOriginMemories, TrustedOriginStore};
 
 // Type aliases
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
-// Define a storable wrapper for Principal and u64 for stable storage
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct PrincipalStorable(pub Principal);
-
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct ExpiryTimeStorable(pub u64);
-
-impl Storable for PrincipalStorable {
-    fn to_bytes(&self) -> Cow<[u8]> {
-        Cow::Owned(self.0.as_slice().to_vec())
-    }
-
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        Self(Principal::from_slice(&bytes))
-    }
-
-    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
-}
-
-impl Storable for ExpiryTimeStorable {
-    fn to_bytes(&self) -> Cow<[u8]> {
-        Cow::Owned(self.0.to_le_bytes().to_vec())
-    }
-
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        let mut data = [0u8; 8];
-        data.copy_from_slice(&bytes[0..8]);
-        Self(u64::from_le_bytes(data))
-    }
-
-    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
-}
-
 // Spam prevention constants
 pub const MIN_COMMENT_INTERVAL_NS: u64 = 5_000_000_000; // 5 seconds between comments
 
@@ -59,16 +26,70 @@ thread_local! {
         )
     );
 
-    pub static BANNED_USERS_STORE: RefCell<StableBTreeMap<PrincipalStorable, ExpiryTimeStorable, Memory>> = RefCell::new(
+    // Reports, review queue, appeals, bans, word lists and moderation audit log
+    pub static MODERATION: RefCell<ModerationStore<Memory>> = RefCell::new(
+        MEMORY_MANAGER.with(|m| {
+            let m = m.borrow();
+            ModerationStore::init(ModerationMemories {
+                bans: m.get(MemoryId::new(1)), // Banned users store from before moderation was shared
+                reports: m.get(MemoryId::new(9)),
+                contents: m.get(MemoryId::new(10)),
+                appeals: m.get(MemoryId::new(11)),
+                log: m.get(MemoryId::new(12)),
+                settings: m.get(MemoryId::new(13)),
+                pending_reports: m.get(MemoryId::new(17)),
+                pending_contents: m.get(MemoryId::new(18)),
+            })
+        })
+    );
+
+    // ICRC-28 trusted origins, initialized with DEFAULT_TRUSTED_ORIGINS, and the audit log of changes to them
+    pub static TRUSTED_ORIGINS: RefCell<TrustedOriginStore<Memory>> = RefCell::new(
+        MEMORY_MANAGER.with(|m| {
+            let m = m.borrow();
+            TrustedOriginStore::init(
+                TrustedOriginMemories {
+                    origins: m.get(MemoryId::new(4)), // 3 is used by authentication
+                    log: m.get(MemoryId::new(5)),
+                },
+                DEFAULT_TRUSTED_ORIGINS.iter().map(|o| o.to_string()).collect(),
+            )
+        })
+    );
+
+    // Emoji reactions on comments
+    pub static REACTION_STORE: RefCell<StableBTreeMap<ReactionKey, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(14)))
+        )
+    );
+
+    // Notification inbox of every user
+    pub static NOTIFICATION_STORE: RefCell<StableBTreeMap<NotificationKey, Notification, Memory>> = RefCell::new(
         StableBTreeMap::init(
-            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(1))) // Use a different memory ID
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(15)))
+        )
+    );
+
+    // Previous contents of edited and deleted comments
+    pub static REVISION_STORE: RefCell<StableBTreeMap<RevisionKey, CommentRevision, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(16)))
+        )
+    );
+
+    // Reactions that notified the comment author, so removing and adding a reaction again doesn't notify twice
+    pub static NOTIFIED_REACTIONS: RefCell<StableBTreeMap<ReactionKey, (), Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(19)))
         )
     );
 
-    pub static COMMENT_COUNTER: RefCell<u64> = RefCell::new(0);
-    
     // Track last comment timestamp per user for spam prevention
     pub static LAST_COMMENT_TIME: RefCell<HashMap<Principal, u64>> = RefCell::new(HashMap::new());
+
+    // Track last reaction timestamp per user for spam prevention
+    pub static LAST_REACTION_TIME: RefCell<HashMap<Principal, u64>> = RefCell::new(HashMap::new());
     
     // Track comment counts per context for statistics
     pub static CONTEXT_COMMENT_COUNT: RefCell<HashMap<String, u32>> = RefCell::new(HashMap::new());
@@ -106,6 +127,8 @@ lazy_static! {
     static ref SAFE_IMAGE_URL_RE: Regex = Regex::new(r"^https?://[^\s<>]+\.(jpg|jpeg|png|gif|webp|svg)(\?[^\s<>]*)?$").unwrap();
     static ref GIF_SERVICE_RE: Regex = Regex::new(r"^https?://(media\.giphy\.com|i\.giphy\.com|media[0-9]*\.giphy\.com|tenor\.com|c\.tenor\.com|imgur\.com|i\.imgur\.com)").unwrap();
     static ref SAFE_URL_RE: Regex = Regex::new(r"^https?://[^\s<>]+$").unwrap();
+    // Mentions in the form @principal
+    static ref MENTION_RE: Regex = Regex::new(r"@([a-z0-9]{5}(?:-[a-z0-9]{1,5})+)").unwrap();
 }
 
 // Helper function to validate comment content
@@ -169,27 +192,370 @@ pub fn validate_comment(content: &str) -> Result<String, String> {
         }
     }).to_string();
 
-    Ok(sanitized_with_links)
+    // Reject blocked words and replace inappropriate words
+    MODERATION.with(|moderation| moderation.borrow().censor(&sanitized_with_links))
 }
 
-// Helper function to check if a user is banned
+// Helper function to check if a user is banned, returns the remaining ban time in seconds
 pub fn is_user_banned(principal: &Principal) -> Option<u64> {
-    BANNED_USERS_STORE.with(|banned_users| {
-        let store = banned_users.borrow();
-        if let Some(ExpiryTimeStorable(expiry_time)) = store.get(&PrincipalStorable(principal.clone())) {
-            let current_time = ic_cdk::api::time();
-            
-            // If ban has expired, remove the user from the banned list
-            if current_time > expiry_time {
-                drop(store); // Release the borrowed reference before mutating
-                banned_users.borrow_mut().remove(&PrincipalStorable(principal.clone()));
-                None
-            } else {
-                // Return remaining ban time in seconds
-                Some((expiry_time - current_time) / 1_000_000_000)
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().ban_remaining_secs(principal, now())
+    })
+}
+
+// Helper function to add the current content of a comment to its revision history
+pub fn save_revision(comment: &Comment) {
+    REVISION_STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        let revision = store.range(RevisionKey { comment_id: comment.id, revision: 0 }..=RevisionKey { comment_id: comment.id, revision: u32::MAX })
+            .next_back()
+            .map(|(key, _)| key.revision + 1)
+            .unwrap_or(0);
+        store.insert(RevisionKey { comment_id: comment.id, revision }, CommentRevision {
+            revision,
+            content: comment.content.clone(),
+            created_at: comment.edited_at.unwrap_or(comment.created_at),
+        });
+    });
+}
+
+// Helper function to get the revision history of a comment, oldest first
+pub fn get_revisions(comment_id: u64) -> Vec<CommentRevision> {
+    REVISION_STORE.with(|store| {
+        store.borrow()
+            .range(RevisionKey { comment_id, revision: 0 }..=RevisionKey { comment_id, revision: u32::MAX })
+            .map(|(_, revision)| revision)
+            .collect()
+    })
+}
+
+// Helper function to replace a comment with a tombstone so its replies keep their thread.
+// The deleted content is kept in the revision history
+pub fn tombstone_comment(mut comment: Comment, deleted_by: Principal, by_moderator: bool, reason: Option<String>) -> Comment {
+    save_revision(&comment);
+    comment.content = String::new();
+    comment.mentions = Vec::new();
+    comment.deleted = Some(Tombstone {
+        deleted_at: now(),
+        deleted_by,
+        by_moderator,
+        reason,
+    });
+    COMMENT_STORE.with(|store| {
+        store.borrow_mut().insert(comment.id, comment.clone());
+    });
+    CONTEXT_COMMENT_COUNT.with(|counts| {
+        let mut counts = counts.borrow_mut();
+        if let Some(count) = counts.get_mut(&comment.context_id) {
+            *count = count.saturating_sub(1);
+        }
+    });
+    REACTION_STORE.with(|reactions| {
+        let mut reactions = reactions.borrow_mut();
+        let keys: Vec<ReactionKey> = reactions.range(reaction_range_start(comment.id)..reaction_range_start(comment.id + 1))
+            .map(|(key, _)| key)
+            .collect();
+        for key in keys {
+            reactions.remove(&key);
+        }
+    });
+    comment
+}
+
+// Helper function to restore a comment deleted by a moderator from its last revision, e.g. after an accepted appeal
+pub fn restore_comment(comment_id: u64) -> bool {
+    let Some(mut comment) = COMMENT_STORE.with(|store| store.borrow().get(&comment_id)) else {
+        return false;
+    };
+    if !comment.deleted.as_ref().is_some_and(|tombstone| tombstone.by_moderator) {
+        return false;
+    }
+    let Some(revision) = get_revisions(comment_id).pop() else {
+        return false;
+    };
+
+    comment.mentions = parse_mentions(&revision.content, &comment.author);
+    comment.content = revision.content;
+    comment.deleted = None;
+    COMMENT_STORE.with(|store| {
+        store.borrow_mut().insert(comment.id, comment.clone());
+    });
+    CONTEXT_COMMENT_COUNT.with(|counts| {
+        *counts.borrow_mut().entry(comment.context_id).or_insert(0) += 1;
+    });
+    true
+}
+
+// Helper function to parse the principals mentioned in a comment, excluding the author
+pub fn parse_mentions(content: &str, author: &Principal) -> Vec<Principal> {
+    let mut mentions = Vec::new();
+    for caps in MENTION_RE.captures_iter(content) {
+        if let Ok(principal) = Principal::from_text(&caps[1]) {
+            if principal != *author && !mentions.contains(&principal) {
+                mentions.push(principal);
             }
-        } else {
-            None
         }
+        if mentions.len() == crate::types::MAX_MENTIONS_PER_COMMENT {
+            break;
+        }
+    }
+    mentions
+}
+
+// Helper function to store a reaction, returns whether the comment author should be notified.
+// Only the first reaction of a user with an emoji notifies, so removing and adding it again doesn't notify twice
+pub fn insert_reaction(key: ReactionKey, reacted_at: u64) -> Result<bool, String> {
+    let already_reacted = REACTION_STORE.with(|reactions| {
+        reactions.borrow_mut().insert(key.clone(), reacted_at).is_some()
+    });
+    if already_reacted {
+        return Err("You have already reacted with this emoji".to_string());
+    }
+
+    let already_notified = NOTIFIED_REACTIONS.with(|notified| {
+        notified.borrow_mut().insert(key, ()).is_some()
+    });
+    Ok(!already_notified)
+}
+
+// Helper function to remove a reaction
+pub fn delete_reaction(key: &ReactionKey) -> Result<(), String> {
+    let was_reacted = REACTION_STORE.with(|reactions| {
+        reactions.borrow_mut().remove(key).is_some()
+    });
+    if !was_reacted {
+        return Err("You haven't reacted with this emoji".to_string());
+    }
+    Ok(())
+}
+
+// Smallest reaction key of a comment, used to range over its reactions
+fn reaction_range_start(comment_id: u64) -> ReactionKey {
+    ReactionKey {
+        comment_id,
+        emoji: String::new(),
+        principal: Principal::from_slice(&[]),
+    }
+}
+
+// Helper function to count the reactions on a comment per emoji
+pub fn reaction_counts(comment_id: u64, viewer: Option<&Principal>) -> Vec<ReactionCount> {
+    let mut counts: Vec<ReactionCount> = Vec::new();
+    REACTION_STORE.with(|reactions| {
+        for (key, _) in reactions.borrow().range(reaction_range_start(comment_id)..reaction_range_start(comment_id + 1)) {
+            let reacted = viewer == Some(&key.principal);
+            match counts.iter_mut().find(|count| count.emoji == key.emoji) {
+                Some(count) => {
+                    count.count += 1;
+                    count.reacted |= reacted;
+                }
+                None => counts.push(ReactionCount { emoji: key.emoji, count: 1, reacted }),
+            }
+        }
+    });
+    counts
+}
+
+// Helper function to add a notification to a user's inbox, dropping the oldest when the inbox is full
+pub fn notify(recipient: Principal, kind: NotificationKind, comment: &Comment, actor: Principal) {
+    // Users are not notified of their own actions
+    if recipient == actor {
+        return;
+    }
+
+    NOTIFICATION_STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        let start = NotificationKey { principal: recipient, id: 0 };
+        let end = NotificationKey { principal: recipient, id: u64::MAX };
+        let id = store.range(start.clone()..end.clone())
+            .next_back()
+            .map(|(key, _)| key.id + 1)
+            .unwrap_or(0);
+
+        store.insert(NotificationKey { principal: recipient, id }, Notification {
+            id,
+            kind,
+            comment_id: comment.id,
+            context_id: comment.context_id.clone(),
+            actor,
+            created_at: now(),
+            read: false,
+        });
+
+        let excess = store.range(start..end).count().saturating_sub(crate::types::MAX_NOTIFICATIONS_PER_USER);
+        let oldest: Vec<NotificationKey> = store.range(NotificationKey { principal: recipient, id: 0 }..)
+            .take(excess)
+            .map(|(key, _)| key)
+            .collect();
+        for key in oldest {
+            store.remove(&key);
+        }
+    });
+}
+
+// Current time, tests run outside of a canister
+#[cfg(not(test))]
+fn now() -> u64 {
+    ic_cdk::api::time()
+}
+
+#[cfg(test)]
+fn now() -> u64 {
+    1_700_000_000_000_000_000
+}
+
+// Helper function to get the next comment ID, survives upgrades as it is derived from stable memory.
+// Deleted comments are kept as tombstones, so an ID is never reused
+pub fn next_comment_id() -> u64 {
+    COMMENT_STORE.with(|store| {
+        store.borrow().last_key_value().map(|(id, _)| id + 1).unwrap_or(0)
     })
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTHOR: &str = "fmlck-tlm2l-l33tz-qspuz-4omct-54vzm-5ciga-ru3ge-awtjs-jezfa-yqe";
+    const OTHER: &str = "hkxzv-wmenl-q4d3b-j3o5s-yucpn-g5itu-b3zmq-hxggl-s3atg-vryjf-dqe";
+
+    fn principal(text: &str) -> Principal {
+        Principal::from_text(text).unwrap()
+    }
+
+    fn comment(id: u64, parent_id: Option<u64>, content: &str) -> Comment {
+        Comment {
+            id,
+            context_id: "market-1".to_string(),
+            content: content.to_string(),
+            author: principal(AUTHOR),
+            created_at: id,
+            parent_id,
+            likes: 0,
+            is_edited: false,
+            edited_at: None,
+            mentions: Vec::new(),
+            deleted: None,
+        }
+    }
+
+    fn store(comment: &Comment) {
+        COMMENT_STORE.with(|store| {
+            store.borrow_mut().insert(comment.id, comment.clone());
+        });
+    }
+
+    fn reaction(comment_id: u64, emoji: &str, principal: Principal) -> ReactionKey {
+        ReactionKey { comment_id, emoji: emoji.to_string(), principal }
+    }
+
+    #[test]
+    fn test_reaction_toggle_is_idempotent() {
+        let user = principal(OTHER);
+        assert_eq!(insert_reaction(reaction(1, "🔥", user), 10), Ok(true));
+        // reacting twice with the same emoji is rejected and counted once
+        assert!(insert_reaction(reaction(1, "🔥", user), 11).is_err());
+        let counts = reaction_counts(1, Some(&user));
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].count, 1);
+        assert!(counts[0].reacted);
+
+        assert_eq!(delete_reaction(&reaction(1, "🔥", user)), Ok(()));
+        assert!(delete_reaction(&reaction(1, "🔥", user)).is_err());
+        assert!(reaction_counts(1, Some(&user)).is_empty());
+
+        // reacting again counts but doesn't notify the author a second time
+        assert_eq!(insert_reaction(reaction(1, "🔥", user), 12), Ok(false));
+        assert_eq!(reaction_counts(1, None)[0].count, 1);
+        assert!(!reaction_counts(1, None)[0].reacted);
+    }
+
+    #[test]
+    fn test_parse_mentions() {
+        let author = principal(AUTHOR);
+        let other = principal(OTHER);
+        let content = format!("@{} and @{} again @{} @not-a-principal", OTHER, AUTHOR, OTHER);
+        // the author's own mention and duplicates are dropped
+        assert_eq!(parse_mentions(&content, &author), vec![other]);
+        assert_eq!(parse_mentions(&content, &other), vec![author]);
+        assert!(parse_mentions("no mentions here, mail@example.com", &author).is_empty());
+    }
+
+    #[test]
+    fn test_parse_mentions_is_capped() {
+        let author = principal(AUTHOR);
+        let content: String = (0..crate::types::MAX_MENTIONS_PER_COMMENT as u64 + 5)
+            .map(|i| format!("@{} ", Principal::from_slice(&i.to_be_bytes()).to_text()))
+            .collect();
+        assert_eq!(parse_mentions(&content, &author).len(), crate::types::MAX_MENTIONS_PER_COMMENT);
+    }
+
+    #[test]
+    fn test_tombstone_keeps_replies_threaded() {
+        let moderator = principal(OTHER);
+        let parent = comment(0, None, "parent");
+        let reply = comment(1, Some(0), "reply");
+        store(&parent);
+        store(&reply);
+        CONTEXT_COMMENT_COUNT.with(|counts| counts.borrow_mut().insert("market-1".to_string(), 2));
+        insert_reaction(reaction(0, "👍", moderator), 10).unwrap();
+        insert_reaction(reaction(1, "👍", moderator), 10).unwrap();
+
+        let deleted = tombstone_comment(parent, moderator, true, Some("spam".to_string()));
+
+        // the parent stays in place as a tombstone, so the reply keeps its thread
+        let stored = COMMENT_STORE.with(|store| store.borrow().get(&0)).unwrap();
+        assert!(stored.content.is_empty());
+        assert_eq!(stored.deleted.as_ref().unwrap().deleted_by, moderator);
+        assert_eq!(deleted.deleted.unwrap().reason, Some("spam".to_string()));
+        let stored_reply = COMMENT_STORE.with(|store| store.borrow().get(&1)).unwrap();
+        assert_eq!(stored_reply.parent_id, Some(0));
+        assert_eq!(stored_reply.content, "reply");
+
+        // only the deleted comment loses its reactions and count
+        assert!(reaction_counts(0, None).is_empty());
+        assert_eq!(reaction_counts(1, None).len(), 1);
+        assert_eq!(CONTEXT_COMMENT_COUNT.with(|counts| counts.borrow().get("market-1").copied()), Some(1));
+        assert_eq!(get_revisions(0)[0].content, "parent");
+
+        // a moderator deletion can be restored from the revision history
+        assert!(restore_comment(0));
+        let restored = COMMENT_STORE.with(|store| store.borrow().get(&0)).unwrap();
+        assert_eq!(restored.content, "parent");
+        assert!(restored.deleted.is_none());
+    }
+
+    #[test]
+    fn test_revisions_are_kept_per_comment_in_order() {
+        // more than 256 revisions, so the order doesn't depend on the encoding of the key
+        let mut edited = comment(5, None, "v0");
+        for revision in 0..300u32 {
+            edited.content = format!("v{}", revision);
+            save_revision(&edited);
+        }
+        save_revision(&comment(4, None, "before"));
+        save_revision(&comment(6, None, "after"));
+
+        let revisions = get_revisions(5);
+        assert_eq!(revisions.len(), 300);
+        for (i, revision) in revisions.iter().enumerate() {
+            assert_eq!(revision.revision, i as u32);
+            assert_eq!(revision.content, format!("v{}", i));
+        }
+        assert_eq!(get_revisions(4).len(), 1);
+        assert_eq!(get_revisions(6)[0].content, "after");
+        assert!(get_revisions(7).is_empty());
+    }
+
+    #[test]
+    fn test_next_comment_id_continues_after_legacy_ids() {
+        assert_eq!(next_comment_id(), 0);
+        // comments created with the heap COMMENT_COUNTER before an upgrade, with gaps from removed comments
+        for id in [0, 1, 2, 7] {
+            store(&comment(id, None, "legacy"));
+        }
+        assert_eq!(next_comment_id(), 8);
+        store(&comment(next_comment_id(), None, "new"));
+        assert_eq!(next_comment_id(), 9);
+    }
+}
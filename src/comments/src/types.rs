@@ -2,10 +2,22 @@ use candid::{CandidType, Deserialize, Principal};
 use ic_stable_structures::Storable;
 use serde::Serialize;
 
+// Moderation types shared with the trollbox canister
+pub use moderation::{
+    Appeal, AppealStatus, AppealTarget, AppealsPage, ContentStatus, ModeratedContent, ModerationAction, ModerationConfig,
+    ModerationLogEntry, ModerationLogPage, Report, ReportReason, ReportStatus, ReviewDecision, ReviewItem, ReviewQueuePage, WordLists,
+};
+
 // Constants
 pub const MAX_COMMENT_LENGTH: usize = 500; // Longer for detailed content analysis
 pub const MAX_COMMENTS_PER_CONTEXT: usize = 5000; // Keep more comments per context
 pub const DEFAULT_PAGE_SIZE: usize = 20;
+pub const MAX_MENTIONS_PER_COMMENT: usize = 10;
+pub const MAX_NOTIFICATIONS_PER_USER: usize = 200; // Oldest notifications are dropped
+pub const MAX_NOTIFICATIONS_PAGE_SIZE: usize = 100;
+
+// Emoji allowed as comment reactions
+pub const ALLOWED_REACTIONS: [&str; 8] = ["👍", "👎", "❤️", "😂", "🔥", "🚀", "🦍", "👀"];
 
 // Comment structure for generic contexts
 #[derive(Debug, Clone, CandidType, Deserialize, Serialize)]
@@ -19,6 +31,57 @@ pub struct Comment {
     pub likes: u32,
     pub is_edited: bool,
     pub edited_at: Option<u64>,
+    #[serde(default)] // Comments stored before mentions have none
+    pub mentions: Vec<Principal>,  // Principals mentioned with @principal in the content
+    #[serde(default)]
+    pub deleted: Option<Tombstone>,  // Set when the comment is deleted, the comment is kept so replies stay in their thread
+}
+
+// Marker left in place of a deleted comment
+#[derive(Debug, Clone, CandidType, Deserialize, Serialize)]
+pub struct Tombstone {
+    pub deleted_at: u64,
+    pub deleted_by: Principal,
+    pub by_moderator: bool,
+    pub reason: Option<String>,  // Moderation reason shown in place of the comment
+}
+
+// Content of a comment before an edit or deletion
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct CommentRevision {
+    pub revision: u32,
+    pub content: String,
+    pub created_at: u64,  // When this content was posted or last edited
+}
+
+impl Storable for CommentRevision {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        std::borrow::Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, CandidType, Deserialize)]
+pub struct RevisionKey {
+    pub comment_id: u64,
+    pub revision: u32,
+}
+
+impl Storable for RevisionKey {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        std::borrow::Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
 }
 
 impl Storable for Comment { 
@@ -47,6 +110,87 @@ pub struct CommentResponse {
     pub is_edited: bool,
     pub edited_at: Option<u64>,
     pub has_liked: bool,  // Whether the current user has liked this comment
+    pub mentions: Vec<Principal>,
+    pub reactions: Vec<ReactionCount>,
+    pub deleted: Option<Tombstone>,
+}
+
+// Number of reactions with an emoji on a comment
+#[derive(Debug, Clone, CandidType, Deserialize, Serialize)]
+pub struct ReactionCount {
+    pub emoji: String,
+    pub count: u32,
+    pub reacted: bool,  // Whether the current user reacted with this emoji
+}
+
+// Reaction of a user on a comment, mapped to the time of the reaction
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, CandidType, Deserialize)]
+pub struct ReactionKey {
+    pub comment_id: u64,
+    pub emoji: String,
+    pub principal: Principal,
+}
+
+impl Storable for ReactionKey {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        std::borrow::Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+#[derive(Debug, Clone, PartialEq, CandidType, Deserialize)]
+pub enum NotificationKind {
+    Reply,                       // Someone replied to the user's comment
+    Mention,                     // Someone mentioned the user in a comment
+    Reaction { emoji: String },  // Someone reacted to the user's comment
+}
+
+// Notification in a user's inbox
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct Notification {
+    pub id: u64,
+    pub kind: NotificationKind,
+    pub comment_id: u64,  // The reply, the comment with the mention or the comment reacted to
+    pub context_id: String,
+    pub actor: Principal,
+    pub created_at: u64,
+    pub read: bool,
+}
+
+impl Storable for Notification {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        std::borrow::Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+// Notifications are keyed by recipient so a user's inbox is a range of the map
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, CandidType, Deserialize)]
+pub struct NotificationKey {
+    pub principal: Principal,
+    pub id: u64,
+}
+
+impl Storable for NotificationKey {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        std::borrow::Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
 }
 
 #[derive(CandidType, Deserialize)]
@@ -81,6 +225,19 @@ pub struct GetCommentsRequest {
     pub check_likes_for: Option<Principal>,  // Optional principal to check likes for
 }
 
+#[derive(CandidType, Deserialize)]
+pub struct GetNotificationsRequest {
+    pub pagination: Option<PaginationParams>,  // Cursor is a notification ID, newest first
+    pub unread_only: bool,
+}
+
+#[derive(CandidType)]
+pub struct NotificationsPage {
+    pub notifications: Vec<Notification>,
+    pub next_cursor: Option<u64>,
+    pub unread_count: u32,
+}
+
 #[derive(CandidType, Deserialize)]
 pub struct BatchCommentCountRequest {
     pub context_ids: Vec<String>,
@@ -90,4 +247,14 @@ pub struct BatchCommentCountRequest {
 pub struct ContextCommentCount {
     pub context_id: String,
     pub count: u32,
-} 
\ No newline at end of file
+}
+
+// ICRC-28 trusted origins, defaults used until changed with add_trusted_origin / remove_trusted_origin
+pub const DEFAULT_TRUSTED_ORIGINS: [&str; 6] = [
+    "https://edoy4-liaaa-aaaar-qakha-cai.localhost:5173", // svelte FE
+    "http://localhost:5173",
+    "https://kongswap.io",
+    "https://www.kongswap.io",
+    "https://edoy4-liaaa-aaaar-qakha-cai.icp0.io",
+    "https://dev.kongswap.io",
+];
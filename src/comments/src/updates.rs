@@ -2,6 +2,7 @@ use ic_cdk::api::time;
 use crate::state::*;
 use crate::types::*;
 use std::collections::HashSet;
+use candid::Principal;
 
 #[ic_cdk::update]
 pub fn create_comment(request: CreateCommentRequest) -> Result<CommentResponse, String> {
@@ -45,26 +46,21 @@ pub fn create_comment(request: CreateCommentRequest) -> Result<CommentResponse,
     }
     
     // Validate parent comment exists if replying
-    if let Some(parent_id) = request.parent_id {
-        let parent_exists = COMMENT_STORE.with(|store| {
-            store.borrow().contains_key(&parent_id)
-        });
-        if !parent_exists {
-            return Err("Parent comment not found".to_string());
-        }
-    }
+    let parent = match request.parent_id {
+        Some(parent_id) => Some(COMMENT_STORE.with(|store| {
+            store.borrow().get(&parent_id)
+        }).filter(|parent| parent.deleted.is_none()).ok_or("Parent comment not found")?),
+        None => None,
+    };
 
     ic_cdk::println!("Validating comment content...");
     let censored_content = validate_comment(&request.content)?;
     ic_cdk::println!("Comment validated successfully");
 
-    let id = COMMENT_COUNTER.with(|counter| {
-        let next_id = *counter.borrow();
-        ic_cdk::println!("Current comment counter: {}", next_id);
-        *counter.borrow_mut() = next_id + 1;
-        next_id
-    });
+    let id = next_comment_id();
+    ic_cdk::println!("Next comment id: {}", id);
 
+    let mentions = parse_mentions(&censored_content, &caller);
     let comment = Comment {
         id,
         context_id: request.context_id.clone(),
@@ -75,6 +71,8 @@ pub fn create_comment(request: CreateCommentRequest) -> Result<CommentResponse,
         likes: 0,
         is_edited: false,
         edited_at: None,
+        mentions,
+        deleted: None,
     };
 
     // Store in stable memory
@@ -89,6 +87,16 @@ pub fn create_comment(request: CreateCommentRequest) -> Result<CommentResponse,
         counts.insert(request.context_id, current_count + 1);
     });
 
+    // Notify the author of the parent comment and the mentioned users
+    if let Some(parent) = &parent {
+        notify(parent.author, NotificationKind::Reply, &comment, caller);
+    }
+    for mentioned in &comment.mentions {
+        if parent.as_ref().is_none_or(|parent| parent.author != *mentioned) {
+            notify(*mentioned, NotificationKind::Mention, &comment, caller);
+        }
+    }
+
     // Convert to CommentResponse
     Ok(CommentResponse {
         id: comment.id,
@@ -101,6 +109,9 @@ pub fn create_comment(request: CreateCommentRequest) -> Result<CommentResponse,
         is_edited: comment.is_edited,
         edited_at: comment.edited_at,
         has_liked: false,  // New comments are not liked by the creator
+        mentions: comment.mentions,
+        reactions: Vec::new(),
+        deleted: None,
     })
 }
 
@@ -118,19 +129,36 @@ pub fn edit_comment(request: EditCommentRequest) -> Result<CommentResponse, Stri
     if comment.author != caller {
         return Err("You can only edit your own comments".to_string());
     }
+    if comment.deleted.is_some() {
+        return Err("Deleted comments cannot be edited".to_string());
+    }
     
     // Validate new content
     let censored_content = validate_comment(&request.content)?;
     
+    // Keep the previous content in the revision history
+    save_revision(&comment);
+    
     // Update comment
+    let mentions = parse_mentions(&censored_content, &caller);
+    let new_mentions: Vec<Principal> = mentions.iter()
+        .filter(|mentioned| !comment.mentions.contains(mentioned))
+        .copied()
+        .collect();
     comment.content = censored_content;
     comment.is_edited = true;
     comment.edited_at = Some(current_time);
+    comment.mentions = mentions;
     
     // Save updated comment
     COMMENT_STORE.with(|store| {
         store.borrow_mut().insert(comment.id, comment.clone());
     });
+
+    // Only users mentioned for the first time are notified
+    for mentioned in new_mentions {
+        notify(mentioned, NotificationKind::Mention, &comment, caller);
+    }
     
     // Check if the user has liked this comment
     let has_liked = USER_LIKES.with(|likes| {
@@ -152,6 +180,9 @@ pub fn edit_comment(request: EditCommentRequest) -> Result<CommentResponse, Stri
         is_edited: comment.is_edited,
         edited_at: comment.edited_at,
         has_liked,
+        reactions: reaction_counts(comment.id, Some(&caller)),
+        mentions: comment.mentions,
+        deleted: comment.deleted,
     })
 }
 
@@ -162,7 +193,7 @@ pub fn like_comment(comment_id: u64) -> Result<u32, String> {
     // Check if comment exists
     let mut comment = COMMENT_STORE.with(|store| {
         store.borrow().get(&comment_id)
-    }).ok_or("Comment not found")?;
+    }).filter(|comment| comment.deleted.is_none()).ok_or("Comment not found")?;
     
     // Check if user already liked this comment
     let already_liked = USER_LIKES.with(|likes| {
@@ -218,4 +249,61 @@ pub fn unlike_comment(comment_id: u64) -> Result<u32, String> {
     });
     
     Ok(comment.likes)
-} 
\ No newline at end of file
+}
+
+#[ic_cdk::update]
+pub fn add_reaction(comment_id: u64, emoji: String) -> Result<Vec<ReactionCount>, String> {
+    let caller = ic_cdk::api::caller();
+    if caller == Principal::anonymous() {
+        return Err("You must be logged in to react to comments".to_string());
+    }
+    if !ALLOWED_REACTIONS.contains(&emoji.as_str()) {
+        return Err(format!("Unsupported reaction. Allowed reactions are {}", ALLOWED_REACTIONS.join(" ")));
+    }
+    
+    // Check if comment exists
+    let comment = COMMENT_STORE.with(|store| {
+        store.borrow().get(&comment_id)
+    }).filter(|comment| comment.deleted.is_none()).ok_or("Comment not found")?;
+    
+    // Check if user is reacting too quickly
+    let current_time = time();
+    let can_react = LAST_REACTION_TIME.with(|last_time_map| {
+        let mut map = last_time_map.borrow_mut();
+        if let Some(last_time) = map.get(&caller) {
+            if current_time - last_time < MIN_COMMENT_INTERVAL_NS {
+                false
+            } else {
+                map.insert(caller, current_time);
+                true
+            }
+        } else {
+            map.insert(caller, current_time);
+            true
+        }
+    });
+    
+    if !can_react {
+        return Err(format!(
+            "You're reacting too quickly. Please wait at least {} seconds between reactions.",
+            MIN_COMMENT_INTERVAL_NS / 1_000_000_000
+        ));
+    }
+    
+    // Only the first reaction of a user with an emoji notifies the author
+    let key = ReactionKey { comment_id, emoji: emoji.clone(), principal: caller };
+    if insert_reaction(key, current_time)? {
+        notify(comment.author, NotificationKind::Reaction { emoji }, &comment, caller);
+    }
+    
+    Ok(reaction_counts(comment_id, Some(&caller)))
+}
+
+#[ic_cdk::update]
+pub fn remove_reaction(comment_id: u64, emoji: String) -> Result<Vec<ReactionCount>, String> {
+    let caller = ic_cdk::api::caller();
+    
+    delete_reaction(&ReactionKey { comment_id, emoji, principal: caller })?;
+    
+    Ok(reaction_counts(comment_id, Some(&caller)))
+}
@@ -2,21 +2,44 @@ import type { Principal } from '@dfinity/principal';
 import type { ActorMethod } from '@dfinity/agent';
 import type { IDL } from '@dfinity/candid';
 
+export interface Appeal {
+  'id' : bigint,
+  'status' : AppealStatus,
+  'resolution_note' : [] | [string],
+  'created_at' : bigint,
+  'target' : AppealTarget,
+  'resolved_at' : [] | [bigint],
+  'resolved_by' : [] | [Principal],
+  'reason' : string,
+  'appellant' : Principal,
+}
+export type AppealStatus = { 'Rejected' : null } |
+  { 'Accepted' : null } |
+  { 'Pending' : null };
+export type AppealTarget = { 'Ban' : null } |
+  { 'Content' : bigint };
+export interface AppealsPage {
+  'appeals' : Array<Appeal>,
+  'next_cursor' : [] | [bigint],
+}
 export interface BatchCommentCountRequest { 'context_ids' : Array<string> }
 export interface Comment {
   'id' : bigint,
   'context_id' : string,
+  'deleted' : [] | [Tombstone],
   'content' : string,
   'created_at' : bigint,
   'edited_at' : [] | [bigint],
   'author' : Principal,
   'likes' : number,
   'parent_id' : [] | [bigint],
+  'mentions' : Array<Principal>,
   'is_edited' : boolean,
 }
 export interface CommentResponse {
   'id' : bigint,
   'context_id' : string,
+  'deleted' : [] | [Tombstone],
   'content' : string,
   'created_at' : bigint,
   'edited_at' : [] | [bigint],
@@ -24,7 +47,14 @@ export interface CommentResponse {
   'likes' : number,
   'has_liked' : boolean,
   'parent_id' : [] | [bigint],
+  'mentions' : Array<Principal>,
   'is_edited' : boolean,
+  'reactions' : Array<ReactionCount>,
+}
+export interface CommentRevision {
+  'content' : string,
+  'created_at' : bigint,
+  'revision' : number,
 }
 export interface CommentsPage {
   'next_cursor' : [] | [bigint],
@@ -51,6 +81,9 @@ export interface ConsentMessageSpec {
   'metadata' : ConsentMessageMetadata,
   'device_spec' : [] | [DisplayMessageType],
 }
+export type ContentStatus = { 'Visible' : null } |
+  { 'Hidden' : null } |
+  { 'Removed' : null };
 export interface ContextCommentCount { 'context_id' : string, 'count' : number }
 export interface CreateCommentRequest {
   'context_id' : string,
@@ -90,18 +123,117 @@ export interface GetCommentsRequest {
   'pagination' : [] | [PaginationParams],
   'check_likes_for' : [] | [Principal],
 }
+export interface GetNotificationsRequest {
+  'pagination' : [] | [PaginationParams],
+  'unread_only' : boolean,
+}
 export interface Icrc28TrustedOriginsResponse {
   'trusted_origins' : Array<string>,
 }
 export interface LineDisplayPage { 'lines' : Array<string> }
+export interface ModeratedContent {
+  'status' : ContentStatus,
+  'updated_at' : bigint,
+  'snapshot' : string,
+  'content_id' : bigint,
+  'reviewed_by' : [] | [Principal],
+  'author' : Principal,
+  'report_ids' : BigUint64Array | bigint[],
+  'auto_hidden' : boolean,
+  'pending_reports' : number,
+  'reason' : [] | [string],
+}
+export type ModerationAction = { 'Ban' : { 'days' : bigint } } |
+  { 'AutoHide' : null } |
+  { 'UpdateWordLists' : null } |
+  { 'Hide' : null } |
+  { 'Remove' : null } |
+  { 'Unban' : null } |
+  { 'RejectAppeal' : { 'appeal_id' : bigint } } |
+  { 'Dismiss' : null } |
+  { 'UpdateConfig' : null } |
+  { 'AcceptAppeal' : { 'appeal_id' : bigint } };
+export interface ModerationConfig {
+  'auto_hide_threshold' : number,
+  'max_pending_reports_per_user' : number,
+}
+export interface ModerationLogEntry {
+  'id' : bigint,
+  'action' : ModerationAction,
+  'moderator' : [] | [Principal],
+  'content_id' : [] | [bigint],
+  'note' : [] | [string],
+  'user' : [] | [Principal],
+  'created_at' : bigint,
+}
+export interface ModerationLogPage {
+  'entries' : Array<ModerationLogEntry>,
+  'next_cursor' : [] | [bigint],
+}
+export interface Notification {
+  'id' : bigint,
+  'context_id' : string,
+  'actor' : Principal,
+  'kind' : NotificationKind,
+  'read' : boolean,
+  'created_at' : bigint,
+  'comment_id' : bigint,
+}
+export type NotificationKind = { 'Reply' : null } |
+  { 'Mention' : null } |
+  { 'Reaction' : { 'emoji' : string } };
+export interface NotificationsPage {
+  'notifications' : Array<Notification>,
+  'unread_count' : number,
+  'next_cursor' : [] | [bigint],
+}
 export interface PaginationParams {
   'cursor' : [] | [bigint],
   'limit' : [] | [bigint],
 }
+export interface ReactionCount {
+  'reacted' : boolean,
+  'count' : number,
+  'emoji' : string,
+}
+export interface Report {
+  'id' : bigint,
+  'status' : ReportStatus,
+  'content_id' : bigint,
+  'note' : [] | [string],
+  'created_at' : bigint,
+  'reporter' : Principal,
+  'reason' : ReportReason,
+}
+export type ReportReason = { 'Scam' : null } |
+  { 'Spam' : null } |
+  { 'Offensive' : null } |
+  { 'OffTopic' : null } |
+  { 'Harassment' : null } |
+  { 'Other' : null };
+export type ReportStatus = { 'Dismissed' : null } |
+  { 'Upheld' : null } |
+  { 'Pending' : null };
 export type Result = { 'Ok' : null } |
   { 'Err' : string };
 export type Result_1 = { 'Ok' : CommentResponse } |
   { 'Err' : string };
+export type Result_10 = { 'Ok' : [] | [ModeratedContent] } |
+  { 'Err' : string };
+export type Result_11 = { 'Ok' : ModerationLogPage } |
+  { 'Err' : string };
+export type Result_12 = { 'Ok' : ReviewQueuePage } |
+  { 'Err' : string };
+export type Result_13 = { 'Ok' : WordLists } |
+  { 'Err' : string };
+export type Result_14 = { 'Ok' : Report } |
+  { 'Err' : string };
+export type Result_15 = { 'Ok' : ModeratedContent } |
+  { 'Err' : string };
+export type Result_16 = { 'Ok' : Array<ReactionCount> } |
+  { 'Err' : string };
+export type Result_17 = { 'Ok' : Array<CommentRevision> } |
+  { 'Err' : string };
 export type Result_2 = { 'Ok' : number } |
   { 'Err' : string };
 export type Result_3 = { 'Ok' : ConsentInfo } |
@@ -110,24 +242,87 @@ export type Result_4 = { 'Ok' : DelegationResponse } |
   { 'Err' : DelegationError };
 export type Result_5 = { 'Ok' : null } |
   { 'Err' : DelegationError };
+export type Result_6 = { 'Ok' : Array<string> } |
+  { 'Err' : string };
+export type Result_7 = { 'Ok' : Array<StableTrustedOriginLog> } |
+  { 'Err' : string };
+export type Result_8 = { 'Ok' : Appeal } |
+  { 'Err' : string };
+export type Result_9 = { 'Ok' : AppealsPage } |
+  { 'Err' : string };
+export type ReviewDecision = { 'Hide' : null } |
+  { 'Remove' : null } |
+  { 'Dismiss' : null };
+export interface ReviewItem {
+  'content' : ModeratedContent,
+  'reports' : Array<Report>,
+}
+export interface ReviewQueuePage {
+  'next_cursor' : [] | [bigint],
+  'items' : Array<ReviewItem>,
+}
 export interface RevokeDelegationRequest { 'targets' : Array<Principal> }
+export interface StableTrustedOriginLog {
+  'ts' : bigint,
+  'action' : TrustedOriginAction,
+  'origin' : string,
+  'log_id' : bigint,
+  'principal_id' : string,
+}
+export interface Tombstone {
+  'by_moderator' : boolean,
+  'deleted_at' : bigint,
+  'deleted_by' : Principal,
+  'reason' : [] | [string],
+}
+export type TrustedOriginAction = { 'Add' : null } |
+  { 'Remove' : null };
+export interface WordLists {
+  'censored' : Array<string>,
+  'blocked' : Array<string>,
+  'builtin_filter' : boolean,
+}
 export interface _SERVICE {
   'add_admin' : ActorMethod<[string], Result>,
+  'add_reaction' : ActorMethod<[bigint, string], Result_16>,
+  'add_trusted_origin' : ActorMethod<[string], Result_6>,
+  'appeal' : ActorMethod<[AppealTarget, string], Result_8>,
   'ban_user' : ActorMethod<[Principal, bigint], Result>,
   'check_ban_status' : ActorMethod<[Principal], [] | [bigint]>,
   'create_comment' : ActorMethod<[CreateCommentRequest], Result_1>,
-  'delete_comment' : ActorMethod<[bigint], Result>,
+  'delete_comment' : ActorMethod<[bigint, [] | [string]], Result>,
   'delete_context_comments' : ActorMethod<[string], Result_2>,
   'edit_comment' : ActorMethod<[EditCommentRequest], Result_1>,
+  'get_appeals' : ActorMethod<
+    [[] | [AppealStatus], [] | [bigint], [] | [bigint]],
+    Result_9
+  >,
   'get_batch_context_comment_counts' : ActorMethod<
     [BatchCommentCountRequest],
     Array<ContextCommentCount>
   >,
   'get_comment' : ActorMethod<[bigint], [] | [Comment]>,
+  'get_comment_history' : ActorMethod<[bigint], Result_17>,
+  'get_comment_moderation' : ActorMethod<[bigint], Result_10>,
   'get_comments_by_context' : ActorMethod<[GetCommentsRequest], CommentsPage>,
   'get_context_comment_count' : ActorMethod<[string], number>,
+  'get_moderation_config' : ActorMethod<[], ModerationConfig>,
+  'get_moderation_log' : ActorMethod<[[] | [bigint], [] | [bigint]], Result_11>,
+  'get_moderation_queue' : ActorMethod<
+    [[] | [bigint], [] | [bigint]],
+    Result_12
+  >,
+  'get_my_appeals' : ActorMethod<[], Array<Appeal>>,
+  'get_notifications' : ActorMethod<
+    [GetNotificationsRequest],
+    NotificationsPage
+  >,
+  'get_reactions' : ActorMethod<[bigint], Array<ReactionCount>>,
+  'get_trusted_origins_log' : ActorMethod<[[] | [bigint]], Result_7>,
+  'get_unread_notification_count' : ActorMethod<[], number>,
   'get_user_comments' : ActorMethod<[Principal, [] | [number]], Array<Comment>>,
   'get_user_liked_comments' : ActorMethod<[], BigUint64Array | bigint[]>,
+  'get_word_lists' : ActorMethod<[], Result_13>,
   'icrc21_canister_call_consent_message' : ActorMethod<
     [ConsentMessageRequest],
     Result_3
@@ -141,6 +336,21 @@ export interface _SERVICE {
   >,
   'is_admin' : ActorMethod<[string], boolean>,
   'like_comment' : ActorMethod<[bigint], Result_2>,
+  'mark_all_notifications_read' : ActorMethod<[], number>,
+  'mark_notifications_read' : ActorMethod<[BigUint64Array | bigint[]], number>,
+  'remove_reaction' : ActorMethod<[bigint, string], Result_16>,
+  'remove_trusted_origin' : ActorMethod<[string], Result_6>,
+  'report_comment' : ActorMethod<
+    [bigint, ReportReason, [] | [string]],
+    Result_14
+  >,
+  'resolve_appeal' : ActorMethod<[bigint, boolean, [] | [string]], Result_8>,
+  'review_comment' : ActorMethod<
+    [bigint, ReviewDecision, [] | [string]],
+    Result_15
+  >,
+  'set_moderation_config' : ActorMethod<[ModerationConfig], Result>,
+  'set_word_lists' : ActorMethod<[WordLists], Result_13>,
   'unban_user' : ActorMethod<[Principal], Result>,
   'unlike_comment' : ActorMethod<[bigint], Result_2>,
 }
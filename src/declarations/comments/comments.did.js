@@ -1,13 +1,48 @@
 export const idlFactory = ({ IDL }) => {
   const Result = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : IDL.Text });
+  const ReactionCount = IDL.Record({
+    'reacted' : IDL.Bool,
+    'count' : IDL.Nat32,
+    'emoji' : IDL.Text,
+  });
+  const Result_16 = IDL.Variant({
+    'Ok' : IDL.Vec(ReactionCount),
+    'Err' : IDL.Text,
+  });
+  const Result_6 = IDL.Variant({ 'Ok' : IDL.Vec(IDL.Text), 'Err' : IDL.Text });
+  const AppealTarget = IDL.Variant({ 'Ban' : IDL.Null, 'Content' : IDL.Nat64 });
+  const AppealStatus = IDL.Variant({
+    'Rejected' : IDL.Null,
+    'Accepted' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const Appeal = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : AppealStatus,
+    'resolution_note' : IDL.Opt(IDL.Text),
+    'created_at' : IDL.Nat64,
+    'target' : AppealTarget,
+    'resolved_at' : IDL.Opt(IDL.Nat64),
+    'resolved_by' : IDL.Opt(IDL.Principal),
+    'reason' : IDL.Text,
+    'appellant' : IDL.Principal,
+  });
+  const Result_8 = IDL.Variant({ 'Ok' : Appeal, 'Err' : IDL.Text });
   const CreateCommentRequest = IDL.Record({
     'context_id' : IDL.Text,
     'content' : IDL.Text,
     'parent_id' : IDL.Opt(IDL.Nat64),
   });
+  const Tombstone = IDL.Record({
+    'by_moderator' : IDL.Bool,
+    'deleted_at' : IDL.Nat64,
+    'deleted_by' : IDL.Principal,
+    'reason' : IDL.Opt(IDL.Text),
+  });
   const CommentResponse = IDL.Record({
     'id' : IDL.Nat64,
     'context_id' : IDL.Text,
+    'deleted' : IDL.Opt(Tombstone),
     'content' : IDL.Text,
     'created_at' : IDL.Nat64,
     'edited_at' : IDL.Opt(IDL.Nat64),
@@ -15,7 +50,9 @@ export const idlFactory = ({ IDL }) => {
     'likes' : IDL.Nat32,
     'has_liked' : IDL.Bool,
     'parent_id' : IDL.Opt(IDL.Nat64),
+    'mentions' : IDL.Vec(IDL.Principal),
     'is_edited' : IDL.Bool,
+    'reactions' : IDL.Vec(ReactionCount),
   });
   const Result_1 = IDL.Variant({ 'Ok' : CommentResponse, 'Err' : IDL.Text });
   const Result_2 = IDL.Variant({ 'Ok' : IDL.Nat32, 'Err' : IDL.Text });
@@ -23,6 +60,11 @@ export const idlFactory = ({ IDL }) => {
     'content' : IDL.Text,
     'comment_id' : IDL.Nat64,
   });
+  const AppealsPage = IDL.Record({
+    'appeals' : IDL.Vec(Appeal),
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+  });
+  const Result_9 = IDL.Variant({ 'Ok' : AppealsPage, 'Err' : IDL.Text });
   const BatchCommentCountRequest = IDL.Record({
     'context_ids' : IDL.Vec(IDL.Text),
   });
@@ -33,14 +75,46 @@ export const idlFactory = ({ IDL }) => {
   const Comment = IDL.Record({
     'id' : IDL.Nat64,
     'context_id' : IDL.Text,
+    'deleted' : IDL.Opt(Tombstone),
     'content' : IDL.Text,
     'created_at' : IDL.Nat64,
     'edited_at' : IDL.Opt(IDL.Nat64),
     'author' : IDL.Principal,
     'likes' : IDL.Nat32,
     'parent_id' : IDL.Opt(IDL.Nat64),
+    'mentions' : IDL.Vec(IDL.Principal),
     'is_edited' : IDL.Bool,
   });
+  const CommentRevision = IDL.Record({
+    'content' : IDL.Text,
+    'created_at' : IDL.Nat64,
+    'revision' : IDL.Nat32,
+  });
+  const Result_17 = IDL.Variant({
+    'Ok' : IDL.Vec(CommentRevision),
+    'Err' : IDL.Text,
+  });
+  const ContentStatus = IDL.Variant({
+    'Visible' : IDL.Null,
+    'Hidden' : IDL.Null,
+    'Removed' : IDL.Null,
+  });
+  const ModeratedContent = IDL.Record({
+    'status' : ContentStatus,
+    'updated_at' : IDL.Nat64,
+    'snapshot' : IDL.Text,
+    'content_id' : IDL.Nat64,
+    'reviewed_by' : IDL.Opt(IDL.Principal),
+    'author' : IDL.Principal,
+    'report_ids' : IDL.Vec(IDL.Nat64),
+    'auto_hidden' : IDL.Bool,
+    'pending_reports' : IDL.Nat32,
+    'reason' : IDL.Opt(IDL.Text),
+  });
+  const Result_10 = IDL.Variant({
+    'Ok' : IDL.Opt(ModeratedContent),
+    'Err' : IDL.Text,
+  });
   const PaginationParams = IDL.Record({
     'cursor' : IDL.Opt(IDL.Nat64),
     'limit' : IDL.Opt(IDL.Nat64),
@@ -54,6 +128,111 @@ export const idlFactory = ({ IDL }) => {
     'next_cursor' : IDL.Opt(IDL.Nat64),
     'comments' : IDL.Vec(CommentResponse),
   });
+  const ModerationConfig = IDL.Record({
+    'auto_hide_threshold' : IDL.Nat32,
+    'max_pending_reports_per_user' : IDL.Nat32,
+  });
+  const ModerationAction = IDL.Variant({
+    'Ban' : IDL.Record({ 'days' : IDL.Nat64 }),
+    'AutoHide' : IDL.Null,
+    'UpdateWordLists' : IDL.Null,
+    'Hide' : IDL.Null,
+    'Remove' : IDL.Null,
+    'Unban' : IDL.Null,
+    'RejectAppeal' : IDL.Record({ 'appeal_id' : IDL.Nat64 }),
+    'Dismiss' : IDL.Null,
+    'UpdateConfig' : IDL.Null,
+    'AcceptAppeal' : IDL.Record({ 'appeal_id' : IDL.Nat64 }),
+  });
+  const ModerationLogEntry = IDL.Record({
+    'id' : IDL.Nat64,
+    'action' : ModerationAction,
+    'moderator' : IDL.Opt(IDL.Principal),
+    'content_id' : IDL.Opt(IDL.Nat64),
+    'note' : IDL.Opt(IDL.Text),
+    'user' : IDL.Opt(IDL.Principal),
+    'created_at' : IDL.Nat64,
+  });
+  const ModerationLogPage = IDL.Record({
+    'entries' : IDL.Vec(ModerationLogEntry),
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+  });
+  const Result_11 = IDL.Variant({ 'Ok' : ModerationLogPage, 'Err' : IDL.Text });
+  const ReportStatus = IDL.Variant({
+    'Dismissed' : IDL.Null,
+    'Upheld' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const ReportReason = IDL.Variant({
+    'Scam' : IDL.Null,
+    'Spam' : IDL.Null,
+    'Offensive' : IDL.Null,
+    'OffTopic' : IDL.Null,
+    'Harassment' : IDL.Null,
+    'Other' : IDL.Null,
+  });
+  const Report = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : ReportStatus,
+    'content_id' : IDL.Nat64,
+    'note' : IDL.Opt(IDL.Text),
+    'created_at' : IDL.Nat64,
+    'reporter' : IDL.Principal,
+    'reason' : ReportReason,
+  });
+  const ReviewItem = IDL.Record({
+    'content' : ModeratedContent,
+    'reports' : IDL.Vec(Report),
+  });
+  const ReviewQueuePage = IDL.Record({
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+    'items' : IDL.Vec(ReviewItem),
+  });
+  const Result_12 = IDL.Variant({ 'Ok' : ReviewQueuePage, 'Err' : IDL.Text });
+  const GetNotificationsRequest = IDL.Record({
+    'pagination' : IDL.Opt(PaginationParams),
+    'unread_only' : IDL.Bool,
+  });
+  const NotificationKind = IDL.Variant({
+    'Reply' : IDL.Null,
+    'Mention' : IDL.Null,
+    'Reaction' : IDL.Record({ 'emoji' : IDL.Text }),
+  });
+  const Notification = IDL.Record({
+    'id' : IDL.Nat64,
+    'context_id' : IDL.Text,
+    'actor' : IDL.Principal,
+    'kind' : NotificationKind,
+    'read' : IDL.Bool,
+    'created_at' : IDL.Nat64,
+    'comment_id' : IDL.Nat64,
+  });
+  const NotificationsPage = IDL.Record({
+    'notifications' : IDL.Vec(Notification),
+    'unread_count' : IDL.Nat32,
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+  });
+  const TrustedOriginAction = IDL.Variant({
+    'Add' : IDL.Null,
+    'Remove' : IDL.Null,
+  });
+  const StableTrustedOriginLog = IDL.Record({
+    'ts' : IDL.Nat64,
+    'action' : TrustedOriginAction,
+    'origin' : IDL.Text,
+    'log_id' : IDL.Nat64,
+    'principal_id' : IDL.Text,
+  });
+  const Result_7 = IDL.Variant({
+    'Ok' : IDL.Vec(StableTrustedOriginLog),
+    'Err' : IDL.Text,
+  });
+  const WordLists = IDL.Record({
+    'censored' : IDL.Vec(IDL.Text),
+    'blocked' : IDL.Vec(IDL.Text),
+    'builtin_filter' : IDL.Bool,
+  });
+  const Result_13 = IDL.Variant({ 'Ok' : WordLists, 'Err' : IDL.Text });
   const ConsentMessageMetadata = IDL.Record({
     'utc_offset_minutes' : IDL.Opt(IDL.Int16),
     'language' : IDL.Text,
@@ -116,8 +295,18 @@ export const idlFactory = ({ IDL }) => {
     'targets' : IDL.Vec(IDL.Principal),
   });
   const Result_5 = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : DelegationError });
+  const Result_14 = IDL.Variant({ 'Ok' : Report, 'Err' : IDL.Text });
+  const ReviewDecision = IDL.Variant({
+    'Hide' : IDL.Null,
+    'Remove' : IDL.Null,
+    'Dismiss' : IDL.Null,
+  });
+  const Result_15 = IDL.Variant({ 'Ok' : ModeratedContent, 'Err' : IDL.Text });
   return IDL.Service({
     'add_admin' : IDL.Func([IDL.Text], [Result], []),
+    'add_reaction' : IDL.Func([IDL.Nat64, IDL.Text], [Result_16], []),
+    'add_trusted_origin' : IDL.Func([IDL.Text], [Result_6], []),
+    'appeal' : IDL.Func([AppealTarget, IDL.Text], [Result_8], []),
     'ban_user' : IDL.Func([IDL.Principal, IDL.Nat64], [Result], []),
     'check_ban_status' : IDL.Func(
         [IDL.Principal],
@@ -125,27 +314,63 @@ export const idlFactory = ({ IDL }) => {
         ['query'],
       ),
     'create_comment' : IDL.Func([CreateCommentRequest], [Result_1], []),
-    'delete_comment' : IDL.Func([IDL.Nat64], [Result], []),
+    'delete_comment' : IDL.Func([IDL.Nat64, IDL.Opt(IDL.Text)], [Result], []),
     'delete_context_comments' : IDL.Func([IDL.Text], [Result_2], []),
     'edit_comment' : IDL.Func([EditCommentRequest], [Result_1], []),
+    'get_appeals' : IDL.Func(
+        [IDL.Opt(AppealStatus), IDL.Opt(IDL.Nat64), IDL.Opt(IDL.Nat64)],
+        [Result_9],
+        ['query'],
+      ),
     'get_batch_context_comment_counts' : IDL.Func(
         [BatchCommentCountRequest],
         [IDL.Vec(ContextCommentCount)],
         ['query'],
       ),
     'get_comment' : IDL.Func([IDL.Nat64], [IDL.Opt(Comment)], ['query']),
+    'get_comment_history' : IDL.Func([IDL.Nat64], [Result_17], ['query']),
+    'get_comment_moderation' : IDL.Func([IDL.Nat64], [Result_10], ['query']),
     'get_comments_by_context' : IDL.Func(
         [GetCommentsRequest],
         [CommentsPage],
         ['query'],
       ),
     'get_context_comment_count' : IDL.Func([IDL.Text], [IDL.Nat32], ['query']),
+    'get_moderation_config' : IDL.Func([], [ModerationConfig], ['query']),
+    'get_moderation_log' : IDL.Func(
+        [IDL.Opt(IDL.Nat64), IDL.Opt(IDL.Nat64)],
+        [Result_11],
+        ['query'],
+      ),
+    'get_moderation_queue' : IDL.Func(
+        [IDL.Opt(IDL.Nat64), IDL.Opt(IDL.Nat64)],
+        [Result_12],
+        ['query'],
+      ),
+    'get_my_appeals' : IDL.Func([], [IDL.Vec(Appeal)], ['query']),
+    'get_notifications' : IDL.Func(
+        [GetNotificationsRequest],
+        [NotificationsPage],
+        ['query'],
+      ),
+    'get_reactions' : IDL.Func(
+        [IDL.Nat64],
+        [IDL.Vec(ReactionCount)],
+        ['query'],
+      ),
+    'get_trusted_origins_log' : IDL.Func(
+        [IDL.Opt(IDL.Nat64)],
+        [Result_7],
+        ['query'],
+      ),
+    'get_unread_notification_count' : IDL.Func([], [IDL.Nat32], ['query']),
     'get_user_comments' : IDL.Func(
         [IDL.Principal, IDL.Opt(IDL.Nat32)],
         [IDL.Vec(Comment)],
         ['query'],
       ),
     'get_user_liked_comments' : IDL.Func([], [IDL.Vec(IDL.Nat64)], ['query']),
+    'get_word_lists' : IDL.Func([], [Result_13], ['query']),
     'icrc21_canister_call_consent_message' : IDL.Func(
         [ConsentMessageRequest],
         [Result_3],
@@ -169,6 +394,27 @@ export const idlFactory = ({ IDL }) => {
       ),
     'is_admin' : IDL.Func([IDL.Text], [IDL.Bool], ['query']),
     'like_comment' : IDL.Func([IDL.Nat64], [Result_2], []),
+    'mark_all_notifications_read' : IDL.Func([], [IDL.Nat32], []),
+    'mark_notifications_read' : IDL.Func([IDL.Vec(IDL.Nat64)], [IDL.Nat32], []),
+    'remove_reaction' : IDL.Func([IDL.Nat64, IDL.Text], [Result_16], []),
+    'remove_trusted_origin' : IDL.Func([IDL.Text], [Result_6], []),
+    'report_comment' : IDL.Func(
+        [IDL.Nat64, ReportReason, IDL.Opt(IDL.Text)],
+        [Result_14],
+        [],
+      ),
+    'resolve_appeal' : IDL.Func(
+        [IDL.Nat64, IDL.Bool, IDL.Opt(IDL.Text)],
+        [Result_8],
+        [],
+      ),
+    'review_comment' : IDL.Func(
+        [IDL.Nat64, ReviewDecision, IDL.Opt(IDL.Text)],
+        [Result_15],
+        [],
+      ),
+    'set_moderation_config' : IDL.Func([ModerationConfig], [Result], []),
+    'set_word_lists' : IDL.Func([WordLists], [Result_13], []),
     'unban_user' : IDL.Func([IDL.Principal], [Result], []),
     'unlike_comment' : IDL.Func([IDL.Nat64], [Result_2], []),
   });
@@ -59,7 +59,14 @@ export interface _SERVICE {
     [string, Uint8Array | number[], bigint],
     Result_2
   >,
-  'siws_login' : ActorMethod<[string, string, Uint8Array | number[]], Result_3>,
+  'siws_login' : ActorMethod<
+    [string, string, Uint8Array | number[], string],
+    Result_3
+  >,
+  'siws_login_with_transaction' : ActorMethod<
+    [Uint8Array | number[], string, Uint8Array | number[], string],
+    Result_3
+  >,
   'siws_prepare_login' : ActorMethod<[string], Result_4>,
 }
 export declare const idlFactory: IDL.InterfaceFactory;
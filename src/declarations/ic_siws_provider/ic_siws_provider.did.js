@@ -55,7 +55,12 @@ export const idlFactory = ({ IDL }) => {
         ['query'],
       ),
     'siws_login' : IDL.Func(
-        [IDL.Text, IDL.Text, IDL.Vec(IDL.Nat8)],
+        [IDL.Text, IDL.Text, IDL.Vec(IDL.Nat8), IDL.Text],
+        [Result_3],
+        [],
+      ),
+    'siws_login_with_transaction' : IDL.Func(
+        [IDL.Vec(IDL.Nat8), IDL.Text, IDL.Vec(IDL.Nat8), IDL.Text],
         [Result_3],
         [],
       ),
@@ -426,7 +426,10 @@ export interface icrc21_consent_info {
   'consent_message' : icrc21_consent_message,
 }
 export type icrc21_consent_message = {
-    'LineDisplayMessage' : { 'pages' : Array<{ 'lines' : Array<string> }> }
+    'FieldsDisplayMessage' : {
+      'fields' : Array<[string, icrc21_value]>,
+      'intent' : string,
+    }
   } |
   { 'GenericDisplayMessage' : string };
 export interface icrc21_consent_message_metadata {
@@ -444,12 +447,7 @@ export interface icrc21_consent_message_spec {
   'metadata' : icrc21_consent_message_metadata,
   'device_spec' : [] | [
     { 'GenericDisplay' : null } |
-      {
-        'LineDisplay' : {
-          'characters_per_line' : number,
-          'lines_per_page' : number,
-        }
-      }
+      { 'FieldsDisplay' : null }
   ],
 }
 export type icrc21_error = {
@@ -459,6 +457,16 @@ export type icrc21_error = {
   { 'UnsupportedCanisterCall' : icrc21_error_info } |
   { 'ConsentMessageUnavailable' : icrc21_error_info };
 export interface icrc21_error_info { 'description' : string }
+export type icrc21_value = { 'Text' : { 'content' : string } } |
+  {
+    'TokenAmount' : {
+      'decimals' : number,
+      'amount' : bigint,
+      'symbol' : string,
+    }
+  } |
+  { 'TimestampSeconds' : { 'amount' : bigint } } |
+  { 'DurationSeconds' : { 'amount' : bigint } };
 export interface _SERVICE {
   'add_liquidity' : ActorMethod<[AddLiquidityArgs], AddLiquidityResult>,
   'add_liquidity_amounts' : ActorMethod<
@@ -193,13 +193,7 @@ export const idlFactory = ({ IDL }) => {
   const icrc21_consent_message_spec = IDL.Record({
     'metadata' : icrc21_consent_message_metadata,
     'device_spec' : IDL.Opt(
-      IDL.Variant({
-        'GenericDisplay' : IDL.Null,
-        'LineDisplay' : IDL.Record({
-          'characters_per_line' : IDL.Nat16,
-          'lines_per_page' : IDL.Nat16,
-        }),
-      })
+      IDL.Variant({ 'GenericDisplay' : IDL.Null, 'FieldsDisplay' : IDL.Null })
     ),
   });
   const icrc21_consent_message_request = IDL.Record({
@@ -207,9 +201,20 @@ export const idlFactory = ({ IDL }) => {
     'method' : IDL.Text,
     'user_preferences' : icrc21_consent_message_spec,
   });
+  const icrc21_value = IDL.Variant({
+    'Text' : IDL.Record({ 'content' : IDL.Text }),
+    'TokenAmount' : IDL.Record({
+      'decimals' : IDL.Nat8,
+      'amount' : IDL.Nat64,
+      'symbol' : IDL.Text,
+    }),
+    'TimestampSeconds' : IDL.Record({ 'amount' : IDL.Nat64 }),
+    'DurationSeconds' : IDL.Record({ 'amount' : IDL.Nat64 }),
+  });
   const icrc21_consent_message = IDL.Variant({
-    'LineDisplayMessage' : IDL.Record({
-      'pages' : IDL.Vec(IDL.Record({ 'lines' : IDL.Vec(IDL.Text) })),
+    'FieldsDisplayMessage' : IDL.Record({
+      'fields' : IDL.Vec(IDL.Tuple(IDL.Text, icrc21_value)),
+      'intent' : IDL.Text,
     }),
     'GenericDisplayMessage' : IDL.Text,
   });
@@ -60,6 +60,50 @@ export interface AddPoolReply {
   'symbol' : string,
   'lp_fee_bps' : number,
 }
+export type ArchiveKind = { 'Tx' : null } |
+  { 'Request' : null } |
+  { 'Transfer' : null };
+export interface ArchiveReply {
+  'start_id' : bigint,
+  'kind' : ArchiveKind,
+  'canister_id' : string,
+  'num_records' : bigint,
+  'end_id' : bigint,
+}
+export interface ArchivedRangeReply {
+  'start_id' : bigint,
+  'canister_id' : string,
+  'num_ids' : bigint,
+}
+export interface ClaimReply {
+  'ts' : bigint,
+  'fee' : bigint,
+  'status' : string,
+  'claim_id' : bigint,
+  'transfer_ids' : Array<TransferIdReply>,
+  'desc' : string,
+  'chain' : string,
+  'to_address' : string,
+  'amount' : bigint,
+  'symbol' : string,
+}
+export interface ClaimsPageReply {
+  'claims' : Array<ClaimsReply>,
+  'next_cursor' : [] | [bigint],
+}
+export type ClaimsPageResult = { 'Ok' : ClaimsPageReply } |
+  { 'Err' : string };
+export interface ClaimsReply {
+  'ts' : bigint,
+  'fee' : bigint,
+  'status' : string,
+  'claim_id' : bigint,
+  'desc' : string,
+  'chain' : string,
+  'to_address' : string,
+  'amount' : bigint,
+  'symbol' : string,
+}
 export interface ICTokenReply {
   'fee' : bigint,
   'decimals' : number,
@@ -143,6 +187,48 @@ export interface RemoveLiquidityReply {
   'remove_lp_token_amount' : bigint,
   'symbol' : string,
 }
+export type RequestReply = { 'AddLiquidity' : AddLiquidityReply } |
+  { 'Send' : SendReply } |
+  { 'Swap' : SwapReply } |
+  { 'AddPool' : AddPoolReply } |
+  { 'Claim' : ClaimReply } |
+  { 'RemoveLiquidity' : RemoveLiquidityReply } |
+  { 'Pending' : null };
+export type RequestRequest = { 'AddLiquidity' : AddLiquidityArgs } |
+  { 'Send' : SendArgs } |
+  { 'Swap' : SwapArgs } |
+  { 'AddPool' : AddPoolArgs } |
+  { 'Claim' : bigint } |
+  { 'RemoveLiquidity' : RemoveLiquidityArgs };
+export interface RequestsPageReply {
+  'requests' : Array<RequestsReply>,
+  'next_cursor' : [] | [bigint],
+  'archived' : Array<ArchivedRangeReply>,
+}
+export type RequestsPageResult = { 'Ok' : RequestsPageReply } |
+  { 'Err' : string };
+export interface RequestsReply {
+  'ts' : bigint,
+  'request_id' : bigint,
+  'request' : RequestRequest,
+  'statuses' : Array<string>,
+  'reply' : RequestReply,
+}
+export interface SendArgs {
+  'token' : string,
+  'to_address' : string,
+  'amount' : bigint,
+}
+export interface SendReply {
+  'ts' : bigint,
+  'request_id' : bigint,
+  'status' : string,
+  'tx_id' : bigint,
+  'chain' : string,
+  'to_address' : string,
+  'amount' : bigint,
+  'symbol' : string,
+}
 export interface SwapArgs {
   'receive_token' : string,
   'max_slippage' : [] | [number],
@@ -197,15 +283,49 @@ export interface TransferIdReply {
   'transfer' : TransferReply,
 }
 export type TransferReply = { 'IC' : ICTransferReply };
+export interface TransfersPageReply {
+  'transfers' : Array<TransferIdReply>,
+  'next_cursor' : [] | [bigint],
+  'archived' : Array<ArchivedRangeReply>,
+}
+export type TransfersPageResult = { 'Ok' : TransfersPageReply } |
+  { 'Err' : string };
 export type TxId = { 'TransactionId' : string } |
   { 'BlockIndex' : bigint };
+export type TxType = { 'AddLiquidity' : null } |
+  { 'Send' : null } |
+  { 'Swap' : null } |
+  { 'AddPool' : null } |
+  { 'RemoveLiquidity' : null };
+export interface TxsPageReply {
+  'txs' : Array<TxsReply>,
+  'next_cursor' : [] | [bigint],
+  'archived' : Array<ArchivedRangeReply>,
+}
+export type TxsPageResult = { 'Ok' : TxsPageReply } |
+  { 'Err' : string };
+export interface TxsQueryArgs {
+  'cursor' : [] | [bigint],
+  'start_ts' : [] | [bigint],
+  'num_txs' : [] | [number],
+  'principal_id' : [] | [string],
+  'pool_id' : [] | [number],
+  'tx_type' : [] | [TxType],
+  'end_ts' : [] | [bigint],
+}
 export type TxsReply = { 'AddLiquidity' : AddLiquidityReply } |
+  { 'Send' : SendReply } |
   { 'Swap' : SwapReply } |
   { 'AddPool' : AddPoolReply } |
   { 'RemoveLiquidity' : RemoveLiquidityReply };
 export type TxsResult = { 'Ok' : Array<TxsReply> } |
   { 'Err' : string };
 export interface _SERVICE {
+  'archived_ranges' : ActorMethod<
+    [ArchiveKind, bigint, bigint],
+    Array<ArchivedRangeReply>
+  >,
+  'archives' : ActorMethod<[[] | [ArchiveKind]], Array<ArchiveReply>>,
   'icrc10_supported_standards' : ActorMethod<
     [],
     Array<Icrc10SupportedStandards>
@@ -213,11 +333,24 @@ export interface _SERVICE {
   'icrc1_name' : ActorMethod<[], string>,
   'icrc28_trusted_origins' : ActorMethod<[], Icrc28TrustedOriginsResponse>,
   'pools' : ActorMethod<[[] | [string]], PoolsResult>,
+  'query_txs' : ActorMethod<[TxsQueryArgs], TxsPageResult>,
   'tokens' : ActorMethod<[[] | [string]], TokensResult>,
   'txs' : ActorMethod<
     [[] | [string], [] | [bigint], [] | [number], [] | [number]],
     TxsResult
   >,
+  'user_claims' : ActorMethod<
+    [string, [] | [bigint], [] | [number]],
+    ClaimsPageResult
+  >,
+  'user_requests' : ActorMethod<
+    [string, [] | [bigint], [] | [number]],
+    RequestsPageResult
+  >,
+  'user_transfers' : ActorMethod<
+    [string, [] | [bigint], [] | [number]],
+    TransfersPageResult
+  >,
 }
 export declare const idlFactory: IDL.InterfaceFactory;
 export declare const init: (args: { IDL: typeof IDL }) => IDL.Type[];
@@ -1,4 +1,21 @@
 export const idlFactory = ({ IDL }) => {
+  const ArchiveKind = IDL.Variant({
+    'Tx' : IDL.Null,
+    'Request' : IDL.Null,
+    'Transfer' : IDL.Null,
+  });
+  const ArchivedRangeReply = IDL.Record({
+    'start_id' : IDL.Nat64,
+    'canister_id' : IDL.Text,
+    'num_ids' : IDL.Nat64,
+  });
+  const ArchiveReply = IDL.Record({
+    'start_id' : IDL.Nat64,
+    'kind' : ArchiveKind,
+    'canister_id' : IDL.Text,
+    'num_records' : IDL.Nat64,
+    'end_id' : IDL.Nat64,
+  });
   const Icrc10SupportedStandards = IDL.Record({
     'url' : IDL.Text,
     'name' : IDL.Text,
@@ -29,35 +46,21 @@ export const idlFactory = ({ IDL }) => {
     'Ok' : IDL.Vec(PoolReply),
     'Err' : IDL.Text,
   });
-  const ICTokenReply = IDL.Record({
-    'fee' : IDL.Nat,
-    'decimals' : IDL.Nat8,
-    'token_id' : IDL.Nat32,
-    'chain' : IDL.Text,
-    'name' : IDL.Text,
-    'canister_id' : IDL.Text,
-    'icrc1' : IDL.Bool,
-    'icrc2' : IDL.Bool,
-    'icrc3' : IDL.Bool,
-    'is_removed' : IDL.Bool,
-    'symbol' : IDL.Text,
-  });
-  const LPTokenReply = IDL.Record({
-    'fee' : IDL.Nat,
-    'decimals' : IDL.Nat8,
-    'token_id' : IDL.Nat32,
-    'chain' : IDL.Text,
-    'name' : IDL.Text,
-    'address' : IDL.Text,
-    'pool_id_of' : IDL.Nat32,
-    'is_removed' : IDL.Bool,
-    'total_supply' : IDL.Nat,
-    'symbol' : IDL.Text,
+  const TxType = IDL.Variant({
+    'AddLiquidity' : IDL.Null,
+    'Send' : IDL.Null,
+    'Swap' : IDL.Null,
+    'AddPool' : IDL.Null,
+    'RemoveLiquidity' : IDL.Null,
   });
-  const TokenReply = IDL.Variant({ 'IC' : ICTokenReply, 'LP' : LPTokenReply });
-  const TokensResult = IDL.Variant({
-    'Ok' : IDL.Vec(TokenReply),
-    'Err' : IDL.Text,
+  const TxsQueryArgs = IDL.Record({
+    'cursor' : IDL.Opt(IDL.Nat64),
+    'start_ts' : IDL.Opt(IDL.Nat64),
+    'num_txs' : IDL.Opt(IDL.Nat16),
+    'principal_id' : IDL.Opt(IDL.Text),
+    'pool_id' : IDL.Opt(IDL.Nat32),
+    'tx_type' : IDL.Opt(TxType),
+    'end_ts' : IDL.Opt(IDL.Nat64),
   });
   const ICTransferReply = IDL.Record({
     'is_send' : IDL.Bool,
@@ -90,6 +93,16 @@ export const idlFactory = ({ IDL }) => {
     'chain_1' : IDL.Text,
     'symbol' : IDL.Text,
   });
+  const SendReply = IDL.Record({
+    'ts' : IDL.Nat64,
+    'request_id' : IDL.Nat64,
+    'status' : IDL.Text,
+    'tx_id' : IDL.Nat64,
+    'chain' : IDL.Text,
+    'to_address' : IDL.Text,
+    'amount' : IDL.Nat,
+    'symbol' : IDL.Text,
+  });
   const SwapTxReply = IDL.Record({
     'ts' : IDL.Nat64,
     'receive_chain' : IDL.Text,
@@ -170,12 +183,173 @@ export const idlFactory = ({ IDL }) => {
   });
   const TxsReply = IDL.Variant({
     'AddLiquidity' : AddLiquidityReply,
+    'Send' : SendReply,
     'Swap' : SwapReply,
     'AddPool' : AddPoolReply,
     'RemoveLiquidity' : RemoveLiquidityReply,
   });
+  const TxsPageReply = IDL.Record({
+    'txs' : IDL.Vec(TxsReply),
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+    'archived' : IDL.Vec(ArchivedRangeReply),
+  });
+  const TxsPageResult = IDL.Variant({ 'Ok' : TxsPageReply, 'Err' : IDL.Text });
+  const ICTokenReply = IDL.Record({
+    'fee' : IDL.Nat,
+    'decimals' : IDL.Nat8,
+    'token_id' : IDL.Nat32,
+    'chain' : IDL.Text,
+    'name' : IDL.Text,
+    'canister_id' : IDL.Text,
+    'icrc1' : IDL.Bool,
+    'icrc2' : IDL.Bool,
+    'icrc3' : IDL.Bool,
+    'is_removed' : IDL.Bool,
+    'symbol' : IDL.Text,
+  });
+  const LPTokenReply = IDL.Record({
+    'fee' : IDL.Nat,
+    'decimals' : IDL.Nat8,
+    'token_id' : IDL.Nat32,
+    'chain' : IDL.Text,
+    'name' : IDL.Text,
+    'address' : IDL.Text,
+    'pool_id_of' : IDL.Nat32,
+    'is_removed' : IDL.Bool,
+    'total_supply' : IDL.Nat,
+    'symbol' : IDL.Text,
+  });
+  const TokenReply = IDL.Variant({ 'IC' : ICTokenReply, 'LP' : LPTokenReply });
+  const TokensResult = IDL.Variant({
+    'Ok' : IDL.Vec(TokenReply),
+    'Err' : IDL.Text,
+  });
   const TxsResult = IDL.Variant({ 'Ok' : IDL.Vec(TxsReply), 'Err' : IDL.Text });
+  const ClaimsReply = IDL.Record({
+    'ts' : IDL.Nat64,
+    'fee' : IDL.Nat,
+    'status' : IDL.Text,
+    'claim_id' : IDL.Nat64,
+    'desc' : IDL.Text,
+    'chain' : IDL.Text,
+    'to_address' : IDL.Text,
+    'amount' : IDL.Nat,
+    'symbol' : IDL.Text,
+  });
+  const ClaimsPageReply = IDL.Record({
+    'claims' : IDL.Vec(ClaimsReply),
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+  });
+  const ClaimsPageResult = IDL.Variant({
+    'Ok' : ClaimsPageReply,
+    'Err' : IDL.Text,
+  });
+  const TxId = IDL.Variant({
+    'TransactionId' : IDL.Text,
+    'BlockIndex' : IDL.Nat,
+  });
+  const AddLiquidityArgs = IDL.Record({
+    'token_0' : IDL.Text,
+    'token_1' : IDL.Text,
+    'amount_0' : IDL.Nat,
+    'amount_1' : IDL.Nat,
+    'tx_id_0' : IDL.Opt(TxId),
+    'tx_id_1' : IDL.Opt(TxId),
+  });
+  const SendArgs = IDL.Record({
+    'token' : IDL.Text,
+    'to_address' : IDL.Text,
+    'amount' : IDL.Nat,
+  });
+  const SwapArgs = IDL.Record({
+    'receive_token' : IDL.Text,
+    'max_slippage' : IDL.Opt(IDL.Float64),
+    'pay_amount' : IDL.Nat,
+    'referred_by' : IDL.Opt(IDL.Text),
+    'receive_amount' : IDL.Opt(IDL.Nat),
+    'receive_address' : IDL.Opt(IDL.Text),
+    'pay_token' : IDL.Text,
+    'pay_tx_id' : IDL.Opt(TxId),
+  });
+  const AddPoolArgs = IDL.Record({
+    'token_0' : IDL.Text,
+    'token_1' : IDL.Text,
+    'amount_0' : IDL.Nat,
+    'amount_1' : IDL.Nat,
+    'tx_id_0' : IDL.Opt(TxId),
+    'tx_id_1' : IDL.Opt(TxId),
+    'lp_fee_bps' : IDL.Opt(IDL.Nat8),
+  });
+  const RemoveLiquidityArgs = IDL.Record({
+    'token_0' : IDL.Text,
+    'token_1' : IDL.Text,
+    'remove_lp_token_amount' : IDL.Nat,
+  });
+  const RequestRequest = IDL.Variant({
+    'AddLiquidity' : AddLiquidityArgs,
+    'Send' : SendArgs,
+    'Swap' : SwapArgs,
+    'AddPool' : AddPoolArgs,
+    'Claim' : IDL.Nat64,
+    'RemoveLiquidity' : RemoveLiquidityArgs,
+  });
+  const ClaimReply = IDL.Record({
+    'ts' : IDL.Nat64,
+    'fee' : IDL.Nat,
+    'status' : IDL.Text,
+    'claim_id' : IDL.Nat64,
+    'transfer_ids' : IDL.Vec(TransferIdReply),
+    'desc' : IDL.Text,
+    'chain' : IDL.Text,
+    'to_address' : IDL.Text,
+    'amount' : IDL.Nat,
+    'symbol' : IDL.Text,
+  });
+  const RequestReply = IDL.Variant({
+    'AddLiquidity' : AddLiquidityReply,
+    'Send' : SendReply,
+    'Swap' : SwapReply,
+    'AddPool' : AddPoolReply,
+    'Claim' : ClaimReply,
+    'RemoveLiquidity' : RemoveLiquidityReply,
+    'Pending' : IDL.Null,
+  });
+  const RequestsReply = IDL.Record({
+    'ts' : IDL.Nat64,
+    'request_id' : IDL.Nat64,
+    'request' : RequestRequest,
+    'statuses' : IDL.Vec(IDL.Text),
+    'reply' : RequestReply,
+  });
+  const RequestsPageReply = IDL.Record({
+    'requests' : IDL.Vec(RequestsReply),
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+    'archived' : IDL.Vec(ArchivedRangeReply),
+  });
+  const RequestsPageResult = IDL.Variant({
+    'Ok' : RequestsPageReply,
+    'Err' : IDL.Text,
+  });
+  const TransfersPageReply = IDL.Record({
+    'transfers' : IDL.Vec(TransferIdReply),
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+    'archived' : IDL.Vec(ArchivedRangeReply),
+  });
+  const TransfersPageResult = IDL.Variant({
+    'Ok' : TransfersPageReply,
+    'Err' : IDL.Text,
+  });
   return IDL.Service({
+    'archived_ranges' : IDL.Func(
+        [ArchiveKind, IDL.Nat64, IDL.Nat64],
+        [IDL.Vec(ArchivedRangeReply)],
+        ['query'],
+      ),
+    'archives' : IDL.Func(
+        [IDL.Opt(ArchiveKind)],
+        [IDL.Vec(ArchiveReply)],
+        ['query'],
+      ),
     'icrc10_supported_standards' : IDL.Func(
         [],
         [IDL.Vec(Icrc10SupportedStandards)],
@@ -184,6 +358,7 @@ export const idlFactory = ({ IDL }) => {
     'icrc1_name' : IDL.Func([], [IDL.Text], ['query']),
     'icrc28_trusted_origins' : IDL.Func([], [Icrc28TrustedOriginsResponse], []),
     'pools' : IDL.Func([IDL.Opt(IDL.Text)], [PoolsResult], ['query']),
+    'query_txs' : IDL.Func([TxsQueryArgs], [TxsPageResult], ['query']),
     'tokens' : IDL.Func([IDL.Opt(IDL.Text)], [TokensResult], ['query']),
     'txs' : IDL.Func(
         [
@@ -195,6 +370,21 @@ export const idlFactory = ({ IDL }) => {
         [TxsResult],
         ['query'],
       ),
+    'user_claims' : IDL.Func(
+        [IDL.Text, IDL.Opt(IDL.Nat64), IDL.Opt(IDL.Nat16)],
+        [ClaimsPageResult],
+        ['query'],
+      ),
+    'user_requests' : IDL.Func(
+        [IDL.Text, IDL.Opt(IDL.Nat64), IDL.Opt(IDL.Nat16)],
+        [RequestsPageResult],
+        ['query'],
+      ),
+    'user_transfers' : IDL.Func(
+        [IDL.Text, IDL.Opt(IDL.Nat64), IDL.Opt(IDL.Nat16)],
+        [TransfersPageResult],
+        ['query'],
+      ),
   });
 };
 export const init = ({ IDL }) => { return []; };
@@ -2,10 +2,48 @@ import type { Principal } from '@dfinity/principal';
 import type { ActorMethod } from '@dfinity/agent';
 import type { IDL } from '@dfinity/candid';
 
+export interface ClaimArgs {
+  'ledgers' : [] | [Array<string>],
+  'pow_solution' : [] | [bigint],
+}
+export interface ClaimChallenge {
+  'difficulty' : number,
+  'nonce' : bigint,
+  'expires_at' : bigint,
+}
+export type ClaimChallengeResult = { 'Ok' : ClaimChallenge } |
+  { 'Err' : string };
+export interface ClaimReply {
+  'claims' : Array<TokenClaimReply>,
+  'next_claim_at' : [] | [bigint],
+}
 export type ClaimResult = { 'Ok' : string } |
   { 'Err' : string };
+export type ClaimTokensResult = { 'Ok' : ClaimReply } |
+  { 'Err' : string };
+export interface FaucetTokenReply {
+  'balance' : [] | [bigint],
+  'low_balance' : boolean,
+  'ledger' : string,
+  'amount' : bigint,
+  'next_claim_at' : [] | [bigint],
+  'symbol' : string,
+  'cooldown_secs' : bigint,
+}
+export interface TokenClaimReply {
+  'status' : TokenClaimStatus,
+  'ledger' : string,
+  'amount' : bigint,
+  'symbol' : string,
+}
+export type TokenClaimStatus = { 'Failed' : { 'error' : string } } |
+  { 'Claimed' : { 'block_index' : bigint } } |
+  { 'CoolingDown' : { 'next_claim_at' : bigint } };
 export interface _SERVICE {
-  'claim' : ActorMethod<[], ClaimResult>,
+  'claim' : ActorMethod<[[] | [ClaimArgs]], ClaimResult>,
+  'claim_tokens' : ActorMethod<[[] | [ClaimArgs]], ClaimTokensResult>,
+  'get_claim_challenge' : ActorMethod<[], ClaimChallengeResult>,
+  'get_faucet_tokens' : ActorMethod<[], Array<FaucetTokenReply>>,
   'icrc1_name' : ActorMethod<[], string>,
 }
 export declare const idlFactory: IDL.InterfaceFactory;
@@ -1,7 +1,51 @@
 export const idlFactory = ({ IDL }) => {
+  const ClaimArgs = IDL.Record({
+    'ledgers' : IDL.Opt(IDL.Vec(IDL.Text)),
+    'pow_solution' : IDL.Opt(IDL.Nat64),
+  });
   const ClaimResult = IDL.Variant({ 'Ok' : IDL.Text, 'Err' : IDL.Text });
+  const TokenClaimStatus = IDL.Variant({
+    'Failed' : IDL.Record({ 'error' : IDL.Text }),
+    'Claimed' : IDL.Record({ 'block_index' : IDL.Nat }),
+    'CoolingDown' : IDL.Record({ 'next_claim_at' : IDL.Nat64 }),
+  });
+  const TokenClaimReply = IDL.Record({
+    'status' : TokenClaimStatus,
+    'ledger' : IDL.Text,
+    'amount' : IDL.Nat,
+    'symbol' : IDL.Text,
+  });
+  const ClaimReply = IDL.Record({
+    'claims' : IDL.Vec(TokenClaimReply),
+    'next_claim_at' : IDL.Opt(IDL.Nat64),
+  });
+  const ClaimTokensResult = IDL.Variant({
+    'Ok' : ClaimReply,
+    'Err' : IDL.Text,
+  });
+  const ClaimChallenge = IDL.Record({
+    'difficulty' : IDL.Nat8,
+    'nonce' : IDL.Nat64,
+    'expires_at' : IDL.Nat64,
+  });
+  const ClaimChallengeResult = IDL.Variant({
+    'Ok' : ClaimChallenge,
+    'Err' : IDL.Text,
+  });
+  const FaucetTokenReply = IDL.Record({
+    'balance' : IDL.Opt(IDL.Nat),
+    'low_balance' : IDL.Bool,
+    'ledger' : IDL.Text,
+    'amount' : IDL.Nat,
+    'next_claim_at' : IDL.Opt(IDL.Nat64),
+    'symbol' : IDL.Text,
+    'cooldown_secs' : IDL.Nat64,
+  });
   return IDL.Service({
-    'claim' : IDL.Func([], [ClaimResult], []),
+    'claim' : IDL.Func([IDL.Opt(ClaimArgs)], [ClaimResult], []),
+    'claim_tokens' : IDL.Func([IDL.Opt(ClaimArgs)], [ClaimTokensResult], []),
+    'get_claim_challenge' : IDL.Func([], [ClaimChallengeResult], []),
+    'get_faucet_tokens' : IDL.Func([], [IDL.Vec(FaucetTokenReply)], ['query']),
     'icrc1_name' : IDL.Func([], [IDL.Text], ['query']),
   });
 };
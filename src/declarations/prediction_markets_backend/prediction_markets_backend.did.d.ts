@@ -17,6 +17,7 @@ export interface Bet {
   'token_id' : string,
   'market_id' : bigint,
   'user' : Principal,
+  'locked_odds' : [] | [number],
   'timestamp' : bigint,
   'amount' : bigint,
   'outcome_index' : bigint,
@@ -42,7 +43,9 @@ export type BetError = { 'MarketNotFound' : null } |
   { 'InvalidOutcome' : null } |
   { 'MarketNotActive' : null } |
   { 'InsufficientBalance' : null } |
-  { 'BalanceUpdateFailed' : null };
+  { 'BalanceUpdateFailed' : null } |
+  { 'InsufficientReserve' : string } |
+  { 'InvalidCombo' : string };
 export interface BetPayoutRecord {
   'transaction_id' : [] | [bigint],
   'bet_amount' : bigint,
@@ -81,9 +84,8 @@ export type ClaimStatus = { 'Claiming' : null } |
   { 'Failed' : FailureDetails } |
   { 'Processed' : ProcessDetails } |
   { 'Pending' : null };
-export type ClaimType = {
-    'Refund' : { 'bet_amount' : bigint, 'reason' : RefundReason }
-  } |
+export type ClaimType = { 'CreatorFee' : { 'platform_fee' : bigint } } |
+  { 'Refund' : { 'bet_amount' : bigint, 'reason' : RefundReason } } |
   {
     'WinningPayout' : {
       'bet_amount' : bigint,
@@ -91,7 +93,8 @@ export type ClaimType = {
       'platform_fee' : [] | [bigint],
     }
   } |
-  { 'Other' : { 'description' : string } };
+  { 'Other' : { 'description' : string } } |
+  { 'LiquiditySubsidy' : { 'contributed' : bigint } };
 export interface ClaimableSummary {
   'pending_claim_count' : bigint,
   'by_token' : Array<[string, bigint]>,
@@ -103,6 +106,39 @@ export interface ClaimsStats {
   'total_count' : bigint,
   'failed_count' : bigint,
 }
+export interface ComboBet {
+  'id' : bigint,
+  'multiplier' : number,
+  'status' : ComboBetStatus,
+  'token_id' : string,
+  'legs' : Array<ComboLeg>,
+  'user' : Principal,
+  'potential_payout' : bigint,
+  'timestamp' : bigint,
+  'amount' : bigint,
+}
+export type ComboBetStatus = { 'Won' : { 'claim_id' : bigint } } |
+  { 'Lost' : null } |
+  { 'Voided' : { 'claim_id' : [] | [bigint] } } |
+  { 'Pending' : null };
+export interface ComboLeg {
+  'is_condition' : boolean,
+  'market_id' : bigint,
+  'implied_probability' : number,
+  'outcome_index' : bigint,
+}
+export interface ComboLegArgs {
+  'is_condition' : boolean,
+  'market_id' : bigint,
+  'outcome_index' : bigint,
+}
+export interface ComboReserve {
+  'exposure' : bigint,
+  'balance' : bigint,
+  'pending_stakes' : bigint,
+  'max_combo_payout' : bigint,
+  'max_total_exposure' : bigint,
+}
 export interface ConsentInfo {
   'metadata' : ConsentMessageMetadata,
   'consent_message' : ConsentMessage,
@@ -124,6 +160,34 @@ export interface ConsentMessageSpec {
   'metadata' : ConsentMessageMetadata,
   'device_spec' : [] | [DisplayMessageType],
 }
+export interface CreateMarketArgs {
+  'uses_time_weighting' : [] | [boolean],
+  'question' : string,
+  'token_id' : [] | [string],
+  'image_url' : [] | [string],
+  'end_time' : MarketEndTime,
+  'outcomes' : Array<string>,
+  'resolution_method' : ResolutionMethod,
+  'time_weight_alpha' : [] | [number],
+  'category' : MarketCategory,
+  'rules' : string,
+  'payout_model' : [] | [PayoutModel],
+}
+export interface CreateScalarMarketArgs {
+  'uses_time_weighting' : [] | [boolean],
+  'upper_bound' : number,
+  'question' : string,
+  'token_id' : [] | [string],
+  'image_url' : [] | [string],
+  'unit' : [] | [string],
+  'end_time' : MarketEndTime,
+  'time_weight_alpha' : [] | [number],
+  'lower_bound' : number,
+  'resolution_source' : [] | [KongPoolPriceSource],
+  'category' : MarketCategory,
+  'rules' : string,
+  'payout_model' : [] | [PayoutModel],
+}
 export interface Delegation {
   'created' : bigint,
   'targets_list_hash' : Uint8Array | number[],
@@ -165,6 +229,7 @@ export interface EstimatedReturn {
   'scenarios' : Array<EstimatedReturnScenario>,
   'time_weight_alpha' : [] | [number],
   'current_time' : bigint,
+  'payout_model' : [] | [PayoutModel],
   'outcome_index' : bigint,
 }
 export interface EstimatedReturnScenario {
@@ -239,8 +304,21 @@ export interface GetMarketsByStatusResult {
 export interface Icrc28TrustedOriginsResponse {
   'trusted_origins' : Array<string>,
 }
+export interface KongPoolPriceSource { 'pool_symbol' : string }
+export interface KongPriceSnapshot {
+  'balance_0' : bigint,
+  'balance_1' : bigint,
+  'timestamp' : bigint,
+  'pool_symbol' : string,
+  'price' : number,
+}
 export interface LatestBets { 'bet' : Bet, 'market' : Market }
 export interface LineDisplayPage { 'lines' : Array<string> }
+export interface LiquiditySubsidy {
+  'amount_per_outcome' : bigint,
+  'timestamp' : bigint,
+  'sponsor' : Principal,
+}
 export interface Market {
   'id' : bigint,
   'bet_count_percentages' : Array<number>,
@@ -254,6 +332,7 @@ export interface Market {
   'token_id' : string,
   'image_url' : [] | [string],
   'resolution_data' : [] | [string],
+  'market_type' : MarketType,
   'created_at' : bigint,
   'end_time' : bigint,
   'total_pool' : bigint,
@@ -262,8 +341,10 @@ export interface Market {
   'time_weight_alpha' : [] | [number],
   'resolution_proposal' : [] | [ResolutionProposalInfo],
   'category' : MarketCategory,
+  'liquidity_subsidies' : Array<LiquiditySubsidy>,
   'rules' : string,
   'resolved_by' : [] | [Principal],
+  'payout_model' : [] | [PayoutModel],
   'bet_counts' : Array<bigint>,
 }
 export type MarketCategory = { 'AI' : null } |
@@ -278,6 +359,7 @@ export type MarketEndTime = { 'SpecificDate' : bigint } |
 export interface MarketResolutionDetails {
   'total_winning_pool' : bigint,
   'total_market_pool' : bigint,
+  'scalar_resolved_value' : [] | [number],
   'platform_fee_amount' : bigint,
   'token_id' : string,
   'token_symbol' : string,
@@ -285,14 +367,18 @@ export interface MarketResolutionDetails {
   'market_id' : bigint,
   'total_weighted_contribution' : [] | [number],
   'platform_fee_percentage' : bigint,
+  'price_snapshot' : [] | [KongPriceSnapshot],
   'used_time_weighting' : boolean,
   'distribution_details' : Array<BetDistributionDetail>,
   'resolution_timestamp' : bigint,
   'time_weight_alpha' : [] | [number],
+  'subsidy_returned_amount' : [] | [bigint],
+  'creator_fee_amount' : [] | [bigint],
   'winning_bet_count' : bigint,
   'winning_outcomes' : Array<bigint>,
   'distributable_profit' : bigint,
   'fee_transaction_id' : [] | [bigint],
+  'payout_model' : [] | [PayoutModel],
   'total_profit' : bigint,
 }
 export interface MarketResult {
@@ -312,11 +398,21 @@ export type MarketStatus = { 'Disputed' : null } |
   { 'ExpiredUnresolved' : null } |
   { 'Voided' : null } |
   { 'PendingActivation' : null };
+export type MarketType = { 'Scalar' : ScalarMarketConfig } |
+  { 'Categorical' : null };
 export interface MarketsByStatus {
   'resolved' : Array<MarketResult>,
   'active' : Array<Market>,
   'expired_unresolved' : Array<Market>,
 }
+export type PayoutModel = { 'LinearDecay' : { 'floor' : number } } |
+  { 'Flat' : null } |
+  { 'Step' : { 'tiers' : Array<PayoutTier> } } |
+  { 'OddsLocked' : null } |
+  { 'Exponential' : { 'alpha' : number } };
+export interface PayoutTier { 'weight' : number, 'until' : number }
+export type PriceComparison = { 'Below' : null } |
+  { 'Above' : null };
 export interface ProcessDetails {
   'transaction_id' : [] | [bigint],
   'timestamp' : bigint,
@@ -345,6 +441,13 @@ export type ResolutionError = { 'MarketNotFound' : null } |
   { 'VoidingFailed' : null } |
   { 'ResolutionDisagreement' : null };
 export type ResolutionMethod = {
+    'KongPrice' : {
+      'comparison' : PriceComparison,
+      'source' : KongPoolPriceSource,
+      'threshold' : number,
+    }
+  } |
+  {
     'Oracle' : {
       'oracle_principals' : Array<Principal>,
       'required_confirmations' : bigint,
@@ -377,6 +480,12 @@ export type Result = { 'Ok' : null } |
   { 'Err' : string };
 export type Result_1 = { 'Ok' : bigint } |
   { 'Err' : string };
+export type Result_10 = { 'Ok' : bigint } |
+  { 'Err' : ResolutionError };
+export type Result_11 = { 'Ok' : bigint } |
+  { 'Err' : BetError };
+export type Result_12 = { 'Ok' : ComboReserve } |
+  { 'Err' : string };
 export type Result_2 = { 'Ok' : [] | [MarketResolutionDetails] } |
   { 'Err' : string };
 export type Result_3 = { 'Ok' : ConsentInfo } |
@@ -394,6 +503,17 @@ export type Result_8 = { 'Ok' : bigint } |
 export type Result_9 = { 'Ok' : [] | [bigint] } |
   { 'Err' : string };
 export interface RevokeDelegationRequest { 'targets' : Array<Principal> }
+export interface ScalarMarketConfig {
+  'upper_bound' : number,
+  'unit' : [] | [string],
+  'lower_bound' : number,
+  'resolution_source' : [] | [KongPoolPriceSource],
+  'resolved_value' : [] | [number],
+}
+export interface ScalarResolutionArgs {
+  'market_id' : bigint,
+  'resolved_value' : [] | [number],
+}
 export interface SearchMarketsArgs {
   'include_resolved' : boolean,
   'sort_field' : [] | [SortField],
@@ -424,7 +544,9 @@ export interface TimeWeightPoint {
 }
 export interface TokenBalanceBreakdown {
   'disputed_penalty_fees' : bigint,
+  'combo_reserve' : bigint,
   'platform_fees' : bigint,
+  'combo_stakes' : bigint,
   'pending_claims' : bigint,
   'voided_markets_unclaimed' : bigint,
   'pending_markets' : bigint,
@@ -447,6 +569,7 @@ export interface TokenInfo {
   'is_kong' : boolean,
   'decimals' : number,
   'transfer_fee' : bigint,
+  'creator_fee_share' : [] | [bigint],
   'name' : string,
   'fee_percentage' : bigint,
   'activation_fee' : bigint,
@@ -476,21 +599,8 @@ export interface _SERVICE {
     BalanceReconciliationSummary
   >,
   'claim_winnings' : ActorMethod<[BigUint64Array | bigint[]], BatchClaimResult>,
-  'create_market' : ActorMethod<
-    [
-      string,
-      MarketCategory,
-      string,
-      Array<string>,
-      ResolutionMethod,
-      MarketEndTime,
-      [] | [string],
-      [] | [boolean],
-      [] | [number],
-      [] | [string],
-    ],
-    Result_1
-  >,
+  'create_market' : ActorMethod<[CreateMarketArgs], Result_1>,
+  'create_scalar_market' : ActorMethod<[CreateScalarMarketArgs], Result_1>,
   'create_test_claim' : ActorMethod<
     [Principal, bigint, bigint, string],
     bigint
@@ -500,6 +610,7 @@ export interface _SERVICE {
     EstimatedReturn
   >,
   'force_resolve_market' : ActorMethod<[ResolutionArgs], ResolutionResult>,
+  'fund_combo_reserve' : ActorMethod<[string, bigint], Result_12>,
   'generate_time_weight_curve' : ActorMethod<
     [bigint, bigint],
     Array<TimeWeightPoint>
@@ -518,6 +629,8 @@ export interface _SERVICE {
   'get_claim_by_id' : ActorMethod<[bigint], [] | [ClaimRecord]>,
   'get_claimable_summary' : ActorMethod<[], ClaimableSummary>,
   'get_claims_stats' : ActorMethod<[], ClaimsStats>,
+  'get_combo_bet' : ActorMethod<[bigint], [] | [ComboBet]>,
+  'get_combo_reserve' : ActorMethod<[string], ComboReserve>,
   'get_featured_markets' : ActorMethod<
     [GetFeaturedMarketsArgs],
     GetFeaturedMarketsResult
@@ -564,6 +677,7 @@ export interface _SERVICE {
     Array<[bigint, FailedTransaction]>
   >,
   'get_user_claims' : ActorMethod<[string], Array<ClaimRecord>>,
+  'get_user_combo_bets' : ActorMethod<[Principal], Array<ComboBet>>,
   'get_user_history' : ActorMethod<[Principal], UserHistory>,
   'get_user_pending_claims' : ActorMethod<[string], Array<ClaimRecord>>,
   'icrc21_canister_call_consent_message' : ActorMethod<
@@ -581,7 +695,16 @@ export interface _SERVICE {
   'mark_claim_processed' : ActorMethod<[bigint], boolean>,
   'mark_transaction_resolved' : ActorMethod<[bigint], Result>,
   'place_bet' : ActorMethod<[bigint, bigint, bigint, [] | [string]], Result_6>,
+  'place_combo_bet' : ActorMethod<
+    [Array<ComboLegArgs>, bigint, [] | [string]],
+    Result_11
+  >,
   'propose_resolution' : ActorMethod<[ResolutionArgs], ResolutionResult>,
+  'resolve_kong_price_markets' : ActorMethod<[], Result_10>,
+  'resolve_scalar_market' : ActorMethod<
+    [ScalarResolutionArgs],
+    ResolutionResult
+  >,
   'resolve_via_admin' : ActorMethod<[ResolutionArgs], ResolutionResult>,
   'resolve_via_admin_legacy' : ActorMethod<
     [bigint, Array<bigint>],
@@ -595,11 +718,14 @@ export interface _SERVICE {
   'retry_market_transactions' : ActorMethod<[bigint], Array<Result_8>>,
   'retry_transaction' : ActorMethod<[bigint], Result_9>,
   'search_markets' : ActorMethod<[SearchMarketsArgs], GetFeaturedMarketsResult>,
+  'seed_market_liquidity' : ActorMethod<[bigint, bigint], Result_6>,
+  'set_combo_limits' : ActorMethod<[string, bigint, bigint], Result_12>,
   'set_market_featured' : ActorMethod<[bigint, boolean], Result>,
   'simulate_future_weight' : ActorMethod<[bigint, bigint, bigint], number>,
   'update_expired_markets' : ActorMethod<[], bigint>,
   'update_token_config' : ActorMethod<[string, TokenInfo], Result>,
   'void_market' : ActorMethod<[bigint], ResolutionResult>,
+  'withdraw_combo_reserve' : ActorMethod<[string, bigint], Result_12>,
 }
 export declare const idlFactory: IDL.InterfaceFactory;
 export declare const init: (args: { IDL: typeof IDL }) => IDL.Type[];
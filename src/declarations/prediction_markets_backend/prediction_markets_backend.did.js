@@ -4,6 +4,7 @@ export const idlFactory = ({ IDL }) => {
     'is_kong' : IDL.Bool,
     'decimals' : IDL.Nat8,
     'transfer_fee' : IDL.Nat,
+    'creator_fee_share' : IDL.Opt(IDL.Nat64),
     'name' : IDL.Text,
     'fee_percentage' : IDL.Nat64,
     'activation_fee' : IDL.Nat,
@@ -12,7 +13,9 @@ export const idlFactory = ({ IDL }) => {
   const Result = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : IDL.Text });
   const TokenBalanceBreakdown = IDL.Record({
     'disputed_penalty_fees' : IDL.Nat,
+    'combo_reserve' : IDL.Nat,
     'platform_fees' : IDL.Nat,
+    'combo_stakes' : IDL.Nat,
     'pending_claims' : IDL.Nat,
     'voided_markets_unclaimed' : IDL.Nat,
     'pending_markets' : IDL.Nat,
@@ -47,6 +50,28 @@ export const idlFactory = ({ IDL }) => {
     'results' : IDL.Vec(ClaimResult),
     'success_count' : IDL.Nat64,
   });
+  const MarketEndTime = IDL.Variant({
+    'SpecificDate' : IDL.Nat,
+    'Duration' : IDL.Nat,
+  });
+  const PriceComparison = IDL.Variant({
+    'Below' : IDL.Null,
+    'Above' : IDL.Null,
+  });
+  const KongPoolPriceSource = IDL.Record({ 'pool_symbol' : IDL.Text });
+  const ResolutionMethod = IDL.Variant({
+    'KongPrice' : IDL.Record({
+      'comparison' : PriceComparison,
+      'source' : KongPoolPriceSource,
+      'threshold' : IDL.Float64,
+    }),
+    'Oracle' : IDL.Record({
+      'oracle_principals' : IDL.Vec(IDL.Principal),
+      'required_confirmations' : IDL.Nat,
+    }),
+    'Decentralized' : IDL.Record({ 'quorum' : IDL.Nat }),
+    'Admin' : IDL.Null,
+  });
   const MarketCategory = IDL.Variant({
     'AI' : IDL.Null,
     'Memes' : IDL.Null,
@@ -56,19 +81,46 @@ export const idlFactory = ({ IDL }) => {
     'KongMadness' : IDL.Null,
     'Sports' : IDL.Null,
   });
-  const ResolutionMethod = IDL.Variant({
-    'Oracle' : IDL.Record({
-      'oracle_principals' : IDL.Vec(IDL.Principal),
-      'required_confirmations' : IDL.Nat,
-    }),
-    'Decentralized' : IDL.Record({ 'quorum' : IDL.Nat }),
-    'Admin' : IDL.Null,
+  const PayoutTier = IDL.Record({
+    'weight' : IDL.Float64,
+    'until' : IDL.Float64,
   });
-  const MarketEndTime = IDL.Variant({
-    'SpecificDate' : IDL.Nat,
-    'Duration' : IDL.Nat,
+  const PayoutModel = IDL.Variant({
+    'LinearDecay' : IDL.Record({ 'floor' : IDL.Float64 }),
+    'Flat' : IDL.Null,
+    'Step' : IDL.Record({ 'tiers' : IDL.Vec(PayoutTier) }),
+    'OddsLocked' : IDL.Null,
+    'Exponential' : IDL.Record({ 'alpha' : IDL.Float64 }),
+  });
+  const CreateMarketArgs = IDL.Record({
+    'uses_time_weighting' : IDL.Opt(IDL.Bool),
+    'question' : IDL.Text,
+    'token_id' : IDL.Opt(IDL.Text),
+    'image_url' : IDL.Opt(IDL.Text),
+    'end_time' : MarketEndTime,
+    'outcomes' : IDL.Vec(IDL.Text),
+    'resolution_method' : ResolutionMethod,
+    'time_weight_alpha' : IDL.Opt(IDL.Float64),
+    'category' : MarketCategory,
+    'rules' : IDL.Text,
+    'payout_model' : IDL.Opt(PayoutModel),
   });
   const Result_1 = IDL.Variant({ 'Ok' : IDL.Nat, 'Err' : IDL.Text });
+  const CreateScalarMarketArgs = IDL.Record({
+    'uses_time_weighting' : IDL.Opt(IDL.Bool),
+    'upper_bound' : IDL.Float64,
+    'question' : IDL.Text,
+    'token_id' : IDL.Opt(IDL.Text),
+    'image_url' : IDL.Opt(IDL.Text),
+    'unit' : IDL.Opt(IDL.Text),
+    'end_time' : MarketEndTime,
+    'time_weight_alpha' : IDL.Opt(IDL.Float64),
+    'lower_bound' : IDL.Float64,
+    'resolution_source' : IDL.Opt(KongPoolPriceSource),
+    'category' : MarketCategory,
+    'rules' : IDL.Text,
+    'payout_model' : IDL.Opt(PayoutModel),
+  });
   const EstimatedReturnScenario = IDL.Record({
     'probability' : IDL.Float64,
     'max_return' : IDL.Nat,
@@ -89,6 +141,7 @@ export const idlFactory = ({ IDL }) => {
     'scenarios' : IDL.Vec(EstimatedReturnScenario),
     'time_weight_alpha' : IDL.Opt(IDL.Float64),
     'current_time' : IDL.Nat,
+    'payout_model' : IDL.Opt(PayoutModel),
     'outcome_index' : IDL.Nat,
   });
   const ResolutionArgs = IDL.Record({
@@ -118,6 +171,14 @@ export const idlFactory = ({ IDL }) => {
     'Success' : IDL.Null,
     'AwaitingCreatorApproval' : IDL.Null,
   });
+  const ComboReserve = IDL.Record({
+    'exposure' : IDL.Nat,
+    'balance' : IDL.Nat,
+    'pending_stakes' : IDL.Nat,
+    'max_combo_payout' : IDL.Nat,
+    'max_total_exposure' : IDL.Nat,
+  });
+  const Result_12 = IDL.Variant({ 'Ok' : ComboReserve, 'Err' : IDL.Text });
   const TimeWeightPoint = IDL.Record({
     'weight' : IDL.Float64,
     'absolute_time' : IDL.Nat,
@@ -166,6 +227,22 @@ export const idlFactory = ({ IDL }) => {
     'Voided' : IDL.Null,
     'PendingActivation' : IDL.Null,
   });
+  const ScalarMarketConfig = IDL.Record({
+    'upper_bound' : IDL.Float64,
+    'unit' : IDL.Opt(IDL.Text),
+    'lower_bound' : IDL.Float64,
+    'resolution_source' : IDL.Opt(KongPoolPriceSource),
+    'resolved_value' : IDL.Opt(IDL.Float64),
+  });
+  const MarketType = IDL.Variant({
+    'Scalar' : ScalarMarketConfig,
+    'Categorical' : IDL.Null,
+  });
+  const LiquiditySubsidy = IDL.Record({
+    'amount_per_outcome' : IDL.Nat,
+    'timestamp' : IDL.Nat,
+    'sponsor' : IDL.Principal,
+  });
   const Market = IDL.Record({
     'id' : IDL.Nat,
     'bet_count_percentages' : IDL.Vec(IDL.Float64),
@@ -179,6 +256,7 @@ export const idlFactory = ({ IDL }) => {
     'token_id' : IDL.Text,
     'image_url' : IDL.Opt(IDL.Text),
     'resolution_data' : IDL.Opt(IDL.Text),
+    'market_type' : MarketType,
     'created_at' : IDL.Nat,
     'end_time' : IDL.Nat,
     'total_pool' : IDL.Nat,
@@ -187,8 +265,10 @@ export const idlFactory = ({ IDL }) => {
     'time_weight_alpha' : IDL.Opt(IDL.Float64),
     'resolution_proposal' : IDL.Opt(ResolutionProposalInfo),
     'category' : MarketCategory,
+    'liquidity_subsidies' : IDL.Vec(LiquiditySubsidy),
     'rules' : IDL.Text,
     'resolved_by' : IDL.Opt(IDL.Principal),
+    'payout_model' : IDL.Opt(PayoutModel),
     'bet_counts' : IDL.Vec(IDL.Nat),
   });
   const GetActiveUserMarketsResult = IDL.Record({
@@ -237,6 +317,7 @@ export const idlFactory = ({ IDL }) => {
     'VoidedMarket' : IDL.Null,
   });
   const ClaimType = IDL.Variant({
+    'CreatorFee' : IDL.Record({ 'platform_fee' : IDL.Nat }),
     'Refund' : IDL.Record({ 'bet_amount' : IDL.Nat, 'reason' : RefundReason }),
     'WinningPayout' : IDL.Record({
       'bet_amount' : IDL.Nat,
@@ -244,6 +325,7 @@ export const idlFactory = ({ IDL }) => {
       'platform_fee' : IDL.Opt(IDL.Nat),
     }),
     'Other' : IDL.Record({ 'description' : IDL.Text }),
+    'LiquiditySubsidy' : IDL.Record({ 'contributed' : IDL.Nat }),
   });
   const ClaimRecord = IDL.Record({
     'status' : ClaimStatus,
@@ -267,6 +349,29 @@ export const idlFactory = ({ IDL }) => {
     'total_count' : IDL.Nat64,
     'failed_count' : IDL.Nat64,
   });
+  const ComboBetStatus = IDL.Variant({
+    'Won' : IDL.Record({ 'claim_id' : IDL.Nat64 }),
+    'Lost' : IDL.Null,
+    'Voided' : IDL.Record({ 'claim_id' : IDL.Opt(IDL.Nat64) }),
+    'Pending' : IDL.Null,
+  });
+  const ComboLeg = IDL.Record({
+    'is_condition' : IDL.Bool,
+    'market_id' : IDL.Nat,
+    'implied_probability' : IDL.Float64,
+    'outcome_index' : IDL.Nat,
+  });
+  const ComboBet = IDL.Record({
+    'id' : IDL.Nat64,
+    'multiplier' : IDL.Float64,
+    'status' : ComboBetStatus,
+    'token_id' : IDL.Text,
+    'legs' : IDL.Vec(ComboLeg),
+    'user' : IDL.Principal,
+    'potential_payout' : IDL.Nat,
+    'timestamp' : IDL.Nat,
+    'amount' : IDL.Nat,
+  });
   const GetFeaturedMarketsArgs = IDL.Record({
     'start' : IDL.Nat,
     'length' : IDL.Nat,
@@ -279,6 +384,7 @@ export const idlFactory = ({ IDL }) => {
     'token_id' : IDL.Text,
     'market_id' : IDL.Nat,
     'user' : IDL.Principal,
+    'locked_odds' : IDL.Opt(IDL.Float64),
     'timestamp' : IDL.Nat,
     'amount' : IDL.Nat,
     'outcome_index' : IDL.Nat,
@@ -309,6 +415,13 @@ export const idlFactory = ({ IDL }) => {
     'timestamp' : IDL.Opt(IDL.Nat),
     'amount' : IDL.Nat,
   });
+  const KongPriceSnapshot = IDL.Record({
+    'balance_0' : IDL.Nat,
+    'balance_1' : IDL.Nat,
+    'timestamp' : IDL.Nat,
+    'pool_symbol' : IDL.Text,
+    'price' : IDL.Float64,
+  });
   const BetDistributionDetail = IDL.Record({
     'weighted_contribution' : IDL.Opt(IDL.Float64),
     'bet_amount' : IDL.Nat,
@@ -322,6 +435,7 @@ export const idlFactory = ({ IDL }) => {
   const MarketResolutionDetails = IDL.Record({
     'total_winning_pool' : IDL.Nat,
     'total_market_pool' : IDL.Nat,
+    'scalar_resolved_value' : IDL.Opt(IDL.Float64),
     'platform_fee_amount' : IDL.Nat,
     'token_id' : IDL.Text,
     'token_symbol' : IDL.Text,
@@ -329,14 +443,18 @@ export const idlFactory = ({ IDL }) => {
     'market_id' : IDL.Nat,
     'total_weighted_contribution' : IDL.Opt(IDL.Float64),
     'platform_fee_percentage' : IDL.Nat64,
+    'price_snapshot' : IDL.Opt(KongPriceSnapshot),
     'used_time_weighting' : IDL.Bool,
     'distribution_details' : IDL.Vec(BetDistributionDetail),
     'resolution_timestamp' : IDL.Nat,
     'time_weight_alpha' : IDL.Opt(IDL.Float64),
+    'subsidy_returned_amount' : IDL.Opt(IDL.Nat),
+    'creator_fee_amount' : IDL.Opt(IDL.Nat),
     'winning_bet_count' : IDL.Nat64,
     'winning_outcomes' : IDL.Vec(IDL.Nat),
     'distributable_profit' : IDL.Nat,
     'fee_transaction_id' : IDL.Opt(IDL.Nat64),
+    'payout_model' : IDL.Opt(PayoutModel),
     'total_profit' : IDL.Nat,
   });
   const Result_2 = IDL.Variant({
@@ -472,8 +590,21 @@ export const idlFactory = ({ IDL }) => {
     'MarketNotActive' : IDL.Null,
     'InsufficientBalance' : IDL.Null,
     'BalanceUpdateFailed' : IDL.Null,
+    'InsufficientReserve' : IDL.Text,
+    'InvalidCombo' : IDL.Text,
   });
   const Result_6 = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : BetError });
+  const ComboLegArgs = IDL.Record({
+    'is_condition' : IDL.Bool,
+    'market_id' : IDL.Nat,
+    'outcome_index' : IDL.Nat,
+  });
+  const Result_11 = IDL.Variant({ 'Ok' : IDL.Nat64, 'Err' : BetError });
+  const Result_10 = IDL.Variant({ 'Ok' : IDL.Nat64, 'Err' : ResolutionError });
+  const ScalarResolutionArgs = IDL.Record({
+    'market_id' : IDL.Nat,
+    'resolved_value' : IDL.Opt(IDL.Float64),
+  });
   const Result_7 = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : ResolutionError });
   const Result_8 = IDL.Variant({ 'Ok' : IDL.Nat64, 'Err' : IDL.Text });
   const Result_9 = IDL.Variant({ 'Ok' : IDL.Opt(IDL.Nat), 'Err' : IDL.Text });
@@ -500,22 +631,8 @@ export const idlFactory = ({ IDL }) => {
         [],
       ),
     'claim_winnings' : IDL.Func([IDL.Vec(IDL.Nat64)], [BatchClaimResult], []),
-    'create_market' : IDL.Func(
-        [
-          IDL.Text,
-          MarketCategory,
-          IDL.Text,
-          IDL.Vec(IDL.Text),
-          ResolutionMethod,
-          MarketEndTime,
-          IDL.Opt(IDL.Text),
-          IDL.Opt(IDL.Bool),
-          IDL.Opt(IDL.Float64),
-          IDL.Opt(IDL.Text),
-        ],
-        [Result_1],
-        [],
-      ),
+    'create_market' : IDL.Func([CreateMarketArgs], [Result_1], []),
+    'create_scalar_market' : IDL.Func([CreateScalarMarketArgs], [Result_1], []),
     'create_test_claim' : IDL.Func(
         [IDL.Principal, IDL.Nat, IDL.Nat, IDL.Text],
         [IDL.Nat64],
@@ -527,6 +644,7 @@ export const idlFactory = ({ IDL }) => {
         ['query'],
       ),
     'force_resolve_market' : IDL.Func([ResolutionArgs], [ResolutionResult], []),
+    'fund_combo_reserve' : IDL.Func([IDL.Text, IDL.Nat], [Result_12], []),
     'generate_time_weight_curve' : IDL.Func(
         [IDL.Nat64, IDL.Nat64],
         [IDL.Vec(TimeWeightPoint)],
@@ -560,6 +678,8 @@ export const idlFactory = ({ IDL }) => {
       ),
     'get_claimable_summary' : IDL.Func([], [ClaimableSummary], ['query']),
     'get_claims_stats' : IDL.Func([], [ClaimsStats], ['query']),
+    'get_combo_bet' : IDL.Func([IDL.Nat64], [IDL.Opt(ComboBet)], ['query']),
+    'get_combo_reserve' : IDL.Func([IDL.Text], [ComboReserve], ['query']),
     'get_featured_markets' : IDL.Func(
         [GetFeaturedMarketsArgs],
         [GetFeaturedMarketsResult],
@@ -631,6 +751,11 @@ export const idlFactory = ({ IDL }) => {
         ['query'],
       ),
     'get_user_claims' : IDL.Func([IDL.Text], [IDL.Vec(ClaimRecord)], ['query']),
+    'get_user_combo_bets' : IDL.Func(
+        [IDL.Principal],
+        [IDL.Vec(ComboBet)],
+        ['query'],
+      ),
     'get_user_history' : IDL.Func([IDL.Principal], [UserHistory], ['query']),
     'get_user_pending_claims' : IDL.Func(
         [IDL.Text],
@@ -666,7 +791,18 @@ export const idlFactory = ({ IDL }) => {
         [Result_6],
         [],
       ),
+    'place_combo_bet' : IDL.Func(
+        [IDL.Vec(ComboLegArgs), IDL.Nat, IDL.Opt(IDL.Text)],
+        [Result_11],
+        [],
+      ),
     'propose_resolution' : IDL.Func([ResolutionArgs], [ResolutionResult], []),
+    'resolve_kong_price_markets' : IDL.Func([], [Result_10], []),
+    'resolve_scalar_market' : IDL.Func(
+        [ScalarResolutionArgs],
+        [ResolutionResult],
+        [],
+      ),
     'resolve_via_admin' : IDL.Func([ResolutionArgs], [ResolutionResult], []),
     'resolve_via_admin_legacy' : IDL.Func(
         [IDL.Nat, IDL.Vec(IDL.Nat)],
@@ -686,6 +822,12 @@ export const idlFactory = ({ IDL }) => {
         [GetFeaturedMarketsResult],
         ['query'],
       ),
+    'seed_market_liquidity' : IDL.Func([IDL.Nat, IDL.Nat], [Result_6], []),
+    'set_combo_limits' : IDL.Func(
+        [IDL.Text, IDL.Nat, IDL.Nat],
+        [Result_12],
+        [],
+      ),
     'set_market_featured' : IDL.Func([IDL.Nat, IDL.Bool], [Result], []),
     'simulate_future_weight' : IDL.Func(
         [IDL.Nat64, IDL.Nat64, IDL.Nat64],
@@ -695,6 +837,7 @@ export const idlFactory = ({ IDL }) => {
     'update_expired_markets' : IDL.Func([], [IDL.Nat64], []),
     'update_token_config' : IDL.Func([IDL.Text, TokenInfo], [Result], []),
     'void_market' : IDL.Func([IDL.Nat], [ResolutionResult], []),
+    'withdraw_combo_reserve' : IDL.Func([IDL.Text, IDL.Nat], [Result_12], []),
   });
 };
 export const init = ({ IDL }) => { return []; };
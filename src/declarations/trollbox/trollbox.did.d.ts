@@ -2,6 +2,26 @@ import type { Principal } from '@dfinity/principal';
 import type { ActorMethod } from '@dfinity/agent';
 import type { IDL } from '@dfinity/candid';
 
+export interface Appeal {
+  'id' : bigint,
+  'status' : AppealStatus,
+  'resolution_note' : [] | [string],
+  'created_at' : bigint,
+  'target' : AppealTarget,
+  'resolved_at' : [] | [bigint],
+  'resolved_by' : [] | [Principal],
+  'reason' : string,
+  'appellant' : Principal,
+}
+export type AppealStatus = { 'Rejected' : null } |
+  { 'Accepted' : null } |
+  { 'Pending' : null };
+export type AppealTarget = { 'Ban' : null } |
+  { 'Content' : bigint };
+export interface AppealsPage {
+  'appeals' : Array<Appeal>,
+  'next_cursor' : [] | [bigint],
+}
 export interface ConsentInfo {
   'metadata' : ConsentMessageMetadata,
   'consent_message' : ConsentMessage,
@@ -23,6 +43,9 @@ export interface ConsentMessageSpec {
   'metadata' : ConsentMessageMetadata,
   'device_spec' : [] | [DisplayMessageType],
 }
+export type ContentStatus = { 'Visible' : null } |
+  { 'Hidden' : null } |
+  { 'Removed' : null };
 export interface Delegation {
   'created' : bigint,
   'targets_list_hash' : Uint8Array | number[],
@@ -54,6 +77,7 @@ export interface LineDisplayPage { 'lines' : Array<string> }
 export interface Message {
   'id' : bigint,
   'principal' : Principal,
+  'room_id' : string,
   'created_at' : bigint,
   'message' : string,
 }
@@ -61,29 +85,192 @@ export interface MessagesPage {
   'messages' : Array<Message>,
   'next_cursor' : [] | [bigint],
 }
+export interface ModeratedContent {
+  'status' : ContentStatus,
+  'updated_at' : bigint,
+  'snapshot' : string,
+  'content_id' : bigint,
+  'reviewed_by' : [] | [Principal],
+  'author' : Principal,
+  'report_ids' : BigUint64Array | bigint[],
+  'auto_hidden' : boolean,
+  'pending_reports' : number,
+  'reason' : [] | [string],
+}
+export type ModerationAction = { 'Ban' : { 'days' : bigint } } |
+  { 'AutoHide' : null } |
+  { 'UpdateWordLists' : null } |
+  { 'Hide' : null } |
+  { 'Remove' : null } |
+  { 'Unban' : null } |
+  { 'RejectAppeal' : { 'appeal_id' : bigint } } |
+  { 'Dismiss' : null } |
+  { 'UpdateConfig' : null } |
+  { 'AcceptAppeal' : { 'appeal_id' : bigint } };
+export interface ModerationConfig {
+  'auto_hide_threshold' : number,
+  'max_pending_reports_per_user' : number,
+}
+export interface ModerationLogEntry {
+  'id' : bigint,
+  'action' : ModerationAction,
+  'moderator' : [] | [Principal],
+  'content_id' : [] | [bigint],
+  'note' : [] | [string],
+  'user' : [] | [Principal],
+  'created_at' : bigint,
+}
+export interface ModerationLogPage {
+  'entries' : Array<ModerationLogEntry>,
+  'next_cursor' : [] | [bigint],
+}
 export interface PaginationParams {
   'cursor' : [] | [bigint],
   'limit' : [] | [bigint],
 }
+export interface Report {
+  'id' : bigint,
+  'status' : ReportStatus,
+  'content_id' : bigint,
+  'note' : [] | [string],
+  'created_at' : bigint,
+  'reporter' : Principal,
+  'reason' : ReportReason,
+}
+export type ReportReason = { 'Scam' : null } |
+  { 'Spam' : null } |
+  { 'Offensive' : null } |
+  { 'OffTopic' : null } |
+  { 'Harassment' : null } |
+  { 'Other' : null };
+export type ReportStatus = { 'Dismissed' : null } |
+  { 'Upheld' : null } |
+  { 'Pending' : null };
 export type Result = { 'Ok' : null } |
   { 'Err' : string };
 export type Result_1 = { 'Ok' : Message } |
   { 'Err' : string };
+export type Result_10 = { 'Ok' : Appeal } |
+  { 'Err' : string };
+export type Result_11 = { 'Ok' : AppealsPage } |
+  { 'Err' : string };
+export type Result_12 = { 'Ok' : [] | [ModeratedContent] } |
+  { 'Err' : string };
+export type Result_13 = { 'Ok' : ModerationLogPage } |
+  { 'Err' : string };
+export type Result_14 = { 'Ok' : ReviewQueuePage } |
+  { 'Err' : string };
+export type Result_15 = { 'Ok' : WordLists } |
+  { 'Err' : string };
+export type Result_16 = { 'Ok' : Report } |
+  { 'Err' : string };
+export type Result_17 = { 'Ok' : ModeratedContent } |
+  { 'Err' : string };
 export type Result_2 = { 'Ok' : ConsentInfo } |
   { 'Err' : ErrorInfo };
 export type Result_3 = { 'Ok' : DelegationResponse } |
   { 'Err' : DelegationError };
 export type Result_4 = { 'Ok' : null } |
   { 'Err' : DelegationError };
+export type Result_5 = { 'Ok' : Array<string> } |
+  { 'Err' : string };
+export type Result_6 = { 'Ok' : Array<StableTrustedOriginLog> } |
+  { 'Err' : string };
+export type Result_7 = { 'Ok' : Room } |
+  { 'Err' : string };
+export type Result_8 = { 'Ok' : MessagesPage } |
+  { 'Err' : string };
+export type Result_9 = { 'Ok' : [] | [bigint] } |
+  { 'Err' : string };
+export type ReviewDecision = { 'Hide' : null } |
+  { 'Remove' : null } |
+  { 'Dismiss' : null };
+export interface ReviewItem {
+  'content' : ModeratedContent,
+  'reports' : Array<Report>,
+}
+export interface ReviewQueuePage {
+  'next_cursor' : [] | [bigint],
+  'items' : Array<ReviewItem>,
+}
 export interface RevokeDelegationRequest { 'targets' : Array<Principal> }
+export interface Room {
+  'id' : string,
+  'gate' : [] | [RoomGate],
+  'kind' : RoomKind,
+  'name' : string,
+  'max_message_length' : bigint,
+  'created_at' : bigint,
+  'created_by' : Principal,
+  'max_messages' : [] | [bigint],
+  'retention_secs' : [] | [bigint],
+  'moderators' : Array<Principal>,
+}
+export interface RoomArgs {
+  'id' : string,
+  'gate' : [] | [RoomGate],
+  'kind' : RoomKind,
+  'name' : string,
+  'max_message_length' : [] | [bigint],
+  'max_messages' : [] | [bigint],
+  'retention_secs' : [] | [bigint],
+}
+export type RoomGate = {
+    'TokenBalance' : { 'ledger' : Principal, 'min_balance' : bigint }
+  } |
+  { 'LpBalance' : { 'min_balance' : number, 'symbol' : string } };
+export type RoomKind = { 'Pool' : string } |
+  { 'Token' : Principal } |
+  { 'Global' : null } |
+  { 'Market' : bigint };
+export interface StableTrustedOriginLog {
+  'ts' : bigint,
+  'action' : TrustedOriginAction,
+  'origin' : string,
+  'log_id' : bigint,
+  'principal_id' : string,
+}
+export type TrustedOriginAction = { 'Add' : null } |
+  { 'Remove' : null };
+export interface WordLists {
+  'censored' : Array<string>,
+  'blocked' : Array<string>,
+  'builtin_filter' : boolean,
+}
 export interface _SERVICE {
   'add_admin' : ActorMethod<[string], Result>,
+  'add_trusted_origin' : ActorMethod<[string], Result_5>,
+  'appeal' : ActorMethod<[AppealTarget, string], Result_10>,
   'ban_user' : ActorMethod<[Principal, bigint], Result>,
   'check_ban_status' : ActorMethod<[Principal], [] | [bigint]>,
   'create_message' : ActorMethod<[string], Result_1>,
+  'create_room' : ActorMethod<[RoomArgs], Result_7>,
+  'create_room_message' : ActorMethod<[string, string], Result_1>,
   'delete_message' : ActorMethod<[bigint], Result>,
+  'delete_room' : ActorMethod<[string], Result>,
+  'delete_room_message' : ActorMethod<[string, bigint], Result>,
+  'get_appeals' : ActorMethod<
+    [[] | [AppealStatus], [] | [bigint], [] | [bigint]],
+    Result_11
+  >,
   'get_message' : ActorMethod<[bigint], [] | [Message]>,
+  'get_message_moderation' : ActorMethod<[bigint], Result_12>,
   'get_messages' : ActorMethod<[[] | [PaginationParams]], MessagesPage>,
+  'get_moderation_config' : ActorMethod<[], ModerationConfig>,
+  'get_moderation_log' : ActorMethod<[[] | [bigint], [] | [bigint]], Result_13>,
+  'get_moderation_queue' : ActorMethod<
+    [[] | [bigint], [] | [bigint]],
+    Result_14
+  >,
+  'get_my_appeals' : ActorMethod<[], Array<Appeal>>,
+  'get_room' : ActorMethod<[string], [] | [Room]>,
+  'get_room_messages' : ActorMethod<
+    [string, [] | [PaginationParams]],
+    Result_8
+  >,
+  'get_rooms' : ActorMethod<[], Array<Room>>,
+  'get_trusted_origins_log' : ActorMethod<[[] | [bigint]], Result_6>,
+  'get_word_lists' : ActorMethod<[], Result_15>,
   'icrc21_canister_call_consent_message' : ActorMethod<
     [ConsentMessageRequest],
     Result_2
@@ -96,7 +283,22 @@ export interface _SERVICE {
     Result_4
   >,
   'is_admin' : ActorMethod<[string], boolean>,
+  'join_room' : ActorMethod<[string], Result_9>,
+  'remove_trusted_origin' : ActorMethod<[string], Result_5>,
+  'report_message' : ActorMethod<
+    [bigint, ReportReason, [] | [string]],
+    Result_16
+  >,
+  'resolve_appeal' : ActorMethod<[bigint, boolean, [] | [string]], Result_10>,
+  'review_message' : ActorMethod<
+    [bigint, ReviewDecision, [] | [string]],
+    Result_17
+  >,
+  'set_moderation_config' : ActorMethod<[ModerationConfig], Result>,
+  'set_room_moderators' : ActorMethod<[string, Array<Principal>], Result_7>,
+  'set_word_lists' : ActorMethod<[WordLists], Result_15>,
   'unban_user' : ActorMethod<[Principal], Result>,
+  'update_room' : ActorMethod<[RoomArgs], Result_7>,
 }
 export declare const idlFactory: IDL.InterfaceFactory;
 export declare const init: (args: { IDL: typeof IDL }) => IDL.Type[];
@@ -1,12 +1,96 @@
 export const idlFactory = ({ IDL }) => {
   const Result = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : IDL.Text });
+  const Result_5 = IDL.Variant({ 'Ok' : IDL.Vec(IDL.Text), 'Err' : IDL.Text });
+  const AppealTarget = IDL.Variant({ 'Ban' : IDL.Null, 'Content' : IDL.Nat64 });
+  const AppealStatus = IDL.Variant({
+    'Rejected' : IDL.Null,
+    'Accepted' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const Appeal = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : AppealStatus,
+    'resolution_note' : IDL.Opt(IDL.Text),
+    'created_at' : IDL.Nat64,
+    'target' : AppealTarget,
+    'resolved_at' : IDL.Opt(IDL.Nat64),
+    'resolved_by' : IDL.Opt(IDL.Principal),
+    'reason' : IDL.Text,
+    'appellant' : IDL.Principal,
+  });
+  const Result_10 = IDL.Variant({ 'Ok' : Appeal, 'Err' : IDL.Text });
   const Message = IDL.Record({
     'id' : IDL.Nat64,
     'principal' : IDL.Principal,
+    'room_id' : IDL.Text,
     'created_at' : IDL.Nat64,
     'message' : IDL.Text,
   });
   const Result_1 = IDL.Variant({ 'Ok' : Message, 'Err' : IDL.Text });
+  const RoomGate = IDL.Variant({
+    'TokenBalance' : IDL.Record({
+      'ledger' : IDL.Principal,
+      'min_balance' : IDL.Nat,
+    }),
+    'LpBalance' : IDL.Record({
+      'min_balance' : IDL.Float64,
+      'symbol' : IDL.Text,
+    }),
+  });
+  const RoomKind = IDL.Variant({
+    'Pool' : IDL.Text,
+    'Token' : IDL.Principal,
+    'Global' : IDL.Null,
+    'Market' : IDL.Nat64,
+  });
+  const RoomArgs = IDL.Record({
+    'id' : IDL.Text,
+    'gate' : IDL.Opt(RoomGate),
+    'kind' : RoomKind,
+    'name' : IDL.Text,
+    'max_message_length' : IDL.Opt(IDL.Nat64),
+    'max_messages' : IDL.Opt(IDL.Nat64),
+    'retention_secs' : IDL.Opt(IDL.Nat64),
+  });
+  const Room = IDL.Record({
+    'id' : IDL.Text,
+    'gate' : IDL.Opt(RoomGate),
+    'kind' : RoomKind,
+    'name' : IDL.Text,
+    'max_message_length' : IDL.Nat64,
+    'created_at' : IDL.Nat64,
+    'created_by' : IDL.Principal,
+    'max_messages' : IDL.Opt(IDL.Nat64),
+    'retention_secs' : IDL.Opt(IDL.Nat64),
+    'moderators' : IDL.Vec(IDL.Principal),
+  });
+  const Result_7 = IDL.Variant({ 'Ok' : Room, 'Err' : IDL.Text });
+  const AppealsPage = IDL.Record({
+    'appeals' : IDL.Vec(Appeal),
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+  });
+  const Result_11 = IDL.Variant({ 'Ok' : AppealsPage, 'Err' : IDL.Text });
+  const ContentStatus = IDL.Variant({
+    'Visible' : IDL.Null,
+    'Hidden' : IDL.Null,
+    'Removed' : IDL.Null,
+  });
+  const ModeratedContent = IDL.Record({
+    'status' : ContentStatus,
+    'updated_at' : IDL.Nat64,
+    'snapshot' : IDL.Text,
+    'content_id' : IDL.Nat64,
+    'reviewed_by' : IDL.Opt(IDL.Principal),
+    'author' : IDL.Principal,
+    'report_ids' : IDL.Vec(IDL.Nat64),
+    'auto_hidden' : IDL.Bool,
+    'pending_reports' : IDL.Nat32,
+    'reason' : IDL.Opt(IDL.Text),
+  });
+  const Result_12 = IDL.Variant({
+    'Ok' : IDL.Opt(ModeratedContent),
+    'Err' : IDL.Text,
+  });
   const PaginationParams = IDL.Record({
     'cursor' : IDL.Opt(IDL.Nat64),
     'limit' : IDL.Opt(IDL.Nat64),
@@ -15,6 +99,89 @@ export const idlFactory = ({ IDL }) => {
     'messages' : IDL.Vec(Message),
     'next_cursor' : IDL.Opt(IDL.Nat64),
   });
+  const ModerationConfig = IDL.Record({
+    'auto_hide_threshold' : IDL.Nat32,
+    'max_pending_reports_per_user' : IDL.Nat32,
+  });
+  const ModerationAction = IDL.Variant({
+    'Ban' : IDL.Record({ 'days' : IDL.Nat64 }),
+    'AutoHide' : IDL.Null,
+    'UpdateWordLists' : IDL.Null,
+    'Hide' : IDL.Null,
+    'Remove' : IDL.Null,
+    'Unban' : IDL.Null,
+    'RejectAppeal' : IDL.Record({ 'appeal_id' : IDL.Nat64 }),
+    'Dismiss' : IDL.Null,
+    'UpdateConfig' : IDL.Null,
+    'AcceptAppeal' : IDL.Record({ 'appeal_id' : IDL.Nat64 }),
+  });
+  const ModerationLogEntry = IDL.Record({
+    'id' : IDL.Nat64,
+    'action' : ModerationAction,
+    'moderator' : IDL.Opt(IDL.Principal),
+    'content_id' : IDL.Opt(IDL.Nat64),
+    'note' : IDL.Opt(IDL.Text),
+    'user' : IDL.Opt(IDL.Principal),
+    'created_at' : IDL.Nat64,
+  });
+  const ModerationLogPage = IDL.Record({
+    'entries' : IDL.Vec(ModerationLogEntry),
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+  });
+  const Result_13 = IDL.Variant({ 'Ok' : ModerationLogPage, 'Err' : IDL.Text });
+  const ReportStatus = IDL.Variant({
+    'Dismissed' : IDL.Null,
+    'Upheld' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const ReportReason = IDL.Variant({
+    'Scam' : IDL.Null,
+    'Spam' : IDL.Null,
+    'Offensive' : IDL.Null,
+    'OffTopic' : IDL.Null,
+    'Harassment' : IDL.Null,
+    'Other' : IDL.Null,
+  });
+  const Report = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : ReportStatus,
+    'content_id' : IDL.Nat64,
+    'note' : IDL.Opt(IDL.Text),
+    'created_at' : IDL.Nat64,
+    'reporter' : IDL.Principal,
+    'reason' : ReportReason,
+  });
+  const ReviewItem = IDL.Record({
+    'content' : ModeratedContent,
+    'reports' : IDL.Vec(Report),
+  });
+  const ReviewQueuePage = IDL.Record({
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+    'items' : IDL.Vec(ReviewItem),
+  });
+  const Result_14 = IDL.Variant({ 'Ok' : ReviewQueuePage, 'Err' : IDL.Text });
+  const Result_8 = IDL.Variant({ 'Ok' : MessagesPage, 'Err' : IDL.Text });
+  const TrustedOriginAction = IDL.Variant({
+    'Add' : IDL.Null,
+    'Remove' : IDL.Null,
+  });
+  const StableTrustedOriginLog = IDL.Record({
+    'ts' : IDL.Nat64,
+    'action' : TrustedOriginAction,
+    'origin' : IDL.Text,
+    'log_id' : IDL.Nat64,
+    'principal_id' : IDL.Text,
+  });
+  const Result_6 = IDL.Variant({
+    'Ok' : IDL.Vec(StableTrustedOriginLog),
+    'Err' : IDL.Text,
+  });
+  const WordLists = IDL.Record({
+    'censored' : IDL.Vec(IDL.Text),
+    'blocked' : IDL.Vec(IDL.Text),
+    'builtin_filter' : IDL.Bool,
+  });
+  const Result_15 = IDL.Variant({ 'Ok' : WordLists, 'Err' : IDL.Text });
   const ConsentMessageMetadata = IDL.Record({
     'utc_offset_minutes' : IDL.Opt(IDL.Int16),
     'language' : IDL.Text,
@@ -77,8 +244,18 @@ export const idlFactory = ({ IDL }) => {
     'targets' : IDL.Vec(IDL.Principal),
   });
   const Result_4 = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : DelegationError });
+  const Result_9 = IDL.Variant({ 'Ok' : IDL.Opt(IDL.Nat64), 'Err' : IDL.Text });
+  const Result_16 = IDL.Variant({ 'Ok' : Report, 'Err' : IDL.Text });
+  const ReviewDecision = IDL.Variant({
+    'Hide' : IDL.Null,
+    'Remove' : IDL.Null,
+    'Dismiss' : IDL.Null,
+  });
+  const Result_17 = IDL.Variant({ 'Ok' : ModeratedContent, 'Err' : IDL.Text });
   return IDL.Service({
     'add_admin' : IDL.Func([IDL.Text], [Result], []),
+    'add_trusted_origin' : IDL.Func([IDL.Text], [Result_5], []),
+    'appeal' : IDL.Func([AppealTarget, IDL.Text], [Result_10], []),
     'ban_user' : IDL.Func([IDL.Principal, IDL.Nat64], [Result], []),
     'check_ban_status' : IDL.Func(
         [IDL.Principal],
@@ -86,13 +263,48 @@ export const idlFactory = ({ IDL }) => {
         ['query'],
       ),
     'create_message' : IDL.Func([IDL.Text], [Result_1], []),
+    'create_room' : IDL.Func([RoomArgs], [Result_7], []),
+    'create_room_message' : IDL.Func([IDL.Text, IDL.Text], [Result_1], []),
     'delete_message' : IDL.Func([IDL.Nat64], [Result], []),
+    'delete_room' : IDL.Func([IDL.Text], [Result], []),
+    'delete_room_message' : IDL.Func([IDL.Text, IDL.Nat64], [Result], []),
+    'get_appeals' : IDL.Func(
+        [IDL.Opt(AppealStatus), IDL.Opt(IDL.Nat64), IDL.Opt(IDL.Nat64)],
+        [Result_11],
+        ['query'],
+      ),
     'get_message' : IDL.Func([IDL.Nat64], [IDL.Opt(Message)], ['query']),
+    'get_message_moderation' : IDL.Func([IDL.Nat64], [Result_12], ['query']),
     'get_messages' : IDL.Func(
         [IDL.Opt(PaginationParams)],
         [MessagesPage],
         ['query'],
       ),
+    'get_moderation_config' : IDL.Func([], [ModerationConfig], ['query']),
+    'get_moderation_log' : IDL.Func(
+        [IDL.Opt(IDL.Nat64), IDL.Opt(IDL.Nat64)],
+        [Result_13],
+        ['query'],
+      ),
+    'get_moderation_queue' : IDL.Func(
+        [IDL.Opt(IDL.Nat64), IDL.Opt(IDL.Nat64)],
+        [Result_14],
+        ['query'],
+      ),
+    'get_my_appeals' : IDL.Func([], [IDL.Vec(Appeal)], ['query']),
+    'get_room' : IDL.Func([IDL.Text], [IDL.Opt(Room)], ['query']),
+    'get_room_messages' : IDL.Func(
+        [IDL.Text, IDL.Opt(PaginationParams)],
+        [Result_8],
+        ['query'],
+      ),
+    'get_rooms' : IDL.Func([], [IDL.Vec(Room)], ['query']),
+    'get_trusted_origins_log' : IDL.Func(
+        [IDL.Opt(IDL.Nat64)],
+        [Result_6],
+        ['query'],
+      ),
+    'get_word_lists' : IDL.Func([], [Result_15], ['query']),
     'icrc21_canister_call_consent_message' : IDL.Func(
         [ConsentMessageRequest],
         [Result_2],
@@ -115,7 +327,32 @@ export const idlFactory = ({ IDL }) => {
         [],
       ),
     'is_admin' : IDL.Func([IDL.Text], [IDL.Bool], ['query']),
+    'join_room' : IDL.Func([IDL.Text], [Result_9], []),
+    'remove_trusted_origin' : IDL.Func([IDL.Text], [Result_5], []),
+    'report_message' : IDL.Func(
+        [IDL.Nat64, ReportReason, IDL.Opt(IDL.Text)],
+        [Result_16],
+        [],
+      ),
+    'resolve_appeal' : IDL.Func(
+        [IDL.Nat64, IDL.Bool, IDL.Opt(IDL.Text)],
+        [Result_10],
+        [],
+      ),
+    'review_message' : IDL.Func(
+        [IDL.Nat64, ReviewDecision, IDL.Opt(IDL.Text)],
+        [Result_17],
+        [],
+      ),
+    'set_moderation_config' : IDL.Func([ModerationConfig], [Result], []),
+    'set_room_moderators' : IDL.Func(
+        [IDL.Text, IDL.Vec(IDL.Principal)],
+        [Result_7],
+        [],
+      ),
+    'set_word_lists' : IDL.Func([WordLists], [Result_15], []),
     'unban_user' : IDL.Func([IDL.Principal], [Result], []),
+    'update_room' : IDL.Func([RoomArgs], [Result_7], []),
   });
 };
 export const init = ({ IDL }) => { return []; };
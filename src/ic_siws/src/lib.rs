@@ -23,6 +23,7 @@ for Ethereum-based applications.
 - [Login flow](#login-flow)
   - [`siws_prepare_login`](#siws_prepare_login)
   - [`siws_login`](#siws_login)
+  - [`siws_login_with_transaction`](#siws_login_with_transaction)
   - [`siws_get_delegation`](#siws_get_delegation)
 - [Crate features](#crate-features)
 - [Updates](#updates)
@@ -47,7 +48,7 @@ At the core of the protocol is the SIWS message, which is a signed message that
 
 `ic_siws` implements most parts of the Sign In with Solana standard, with some notable exceptions:
 
-- `nonce` - SIWS requires that each sign-in message has a unique nonce. In the context of this implementation, the nonce don't add any additional security to the login flow and are disabled by default. If random nonces are required, the `nonce` feature flag can be enabled. When this feature is enabled, the nonce is generated using a cryptographically secure random number generator.
+- `nonce` - SIWS requires that each sign-in message has a unique nonce. In the context of this implementation, the nonce identifies the pending sign-in message of a login, so that one address can have several logins in progress. The nonce is unique but not random by default. If random nonces are required, the `nonce` feature flag can be enabled. When this feature is enabled, the nonce is generated using a cryptographically secure random number generator.
 
 - `not-before`, `request-id`, `resources` - Not implemented. These fields are marked as OPTIONAL in the SIWS standard and are not currently implemented.
 
@@ -65,6 +66,7 @@ An implementing canister is free to implement these steps in any way it sees fit
 ```text
 type Address = text;
 type CanisterPublicKey = PublicKey;
+type Nonce = text;
 type PublicKey = blob;
 type SessionKey = PublicKey;
 type SiwsSignature = text;
@@ -115,7 +117,8 @@ type PrepareLoginResponse = variant {
 
 service : (settings_input : SettingsInput) -> {
   "siws_prepare_login" : (Address) -> (PrepareLoginResponse);
-  "siws_login" : (SiwsSignature, Address, SessionKey) -> (LoginResponse);
+  "siws_login" : (SiwsSignature, Address, SessionKey, Nonce) -> (LoginResponse);
+  "siws_login_with_transaction" : (blob, Address, SessionKey, Nonce) -> (LoginResponse);
   "siws_get_delegation" : (Address, SessionKey, Timestamp) -> (GetDelegationResponse) query;
 };
 
@@ -126,9 +129,13 @@ service : (settings_input : SettingsInput) -> {
 - See: [`login::prepare_login`]
 
 ## `login`
-- The `login` method is called by the frontend application after the user has signed the SIWS message. The method takes the user's Solana address, signature, and session identity as parameters. The method verifies the signature and Solana address and returns a delegation.
+- The `login` method is called by the frontend application after the user has signed the SIWS message. The method takes the user's Solana address, signature, session identity and the nonce of the signed SIWS message as parameters. The method verifies the signature and Solana address and returns a delegation.
 - See: [`login::login`]
 
+## `siws_login_with_transaction`
+- Hardware wallets such as Ledger can't sign arbitrary off-chain messages. For these wallets, the frontend application instead asks the user to sign a Solana transaction that contains the SIWS message in a memo instruction, with the user's address as fee payer. The transaction is never broadcast. The method takes the serialized signed transaction, the user's Solana address, session identity and the nonce of the SIWS message as parameters. The method verifies the memo content, fee payer and signature and returns a delegation, the same way as `login`.
+- See: [`login::login_with_transaction`]
+
 ## `siws_get_delegation`
 - The `siws_get_delegation` method is called by the frontend application after a successful login. The method takes the delegation expiration time as a parameter and returns a delegation.
 - The `siws_get_delegation` method is not mirrored by one function in the `ic_siws` library. The creation of delegate identities requires setting the certified data of the canister. This should not be done by the library, but by the implementing canister.
@@ -204,7 +211,7 @@ The login flow is illustrated in the following diagram:
 
 The library has one optional feature that is disabled by default.
 
-* `nonce` - Enables the generation of nonces for SIWS messages. This feature initializes a random number generator with a seed from the management canister. The random number generator then is used to generate unique nonces for each generated SIWS message. Nonces don't add any additional security to the SIWS login flow but are required by the SIWS standard. When this feature is disabled, the nonce is always set to the hex encoded string `Not in use`.
+* `nonce` - Enables the generation of nonces for SIWS messages. This feature initializes a random number generator with a seed from the management canister. The random number generator then is used to generate unique nonces for each generated SIWS message. Nonces don't add any additional security to the SIWS login flow but are required by the SIWS standard. When this feature is disabled, the nonce is derived from the current time and a counter.
 
 ## Updates
 
@@ -236,6 +243,7 @@ pub mod signature_map;
 pub mod siws;
 pub mod solana;
 pub(crate) mod time;
+pub mod transaction;
 
 pub use init::init;
 
@@ -254,10 +262,10 @@ thread_local! {
 
     // The settings control the behavior of the SIWS library. The settings must be initialized
     // before any other library functions are called.
-    static SETTINGS: RefCell<Option<Settings>> = RefCell::new(None);
+    static SETTINGS: RefCell<Option<Settings>> = const { RefCell::new(None) };
 
     // SIWS messages are stored in global state during the login process. The key is the
-    // Solana address as a byte array and the value is the SIWS message. After a successful
-    // login, the SIWS message is removed from state.
+    // Solana address as a byte array and the nonce of the message, the value is the SIWS message.
+    // After a successful login, the SIWS message is removed from state.
     static SIWS_MESSAGES: RefCell<SiwsMessageMap> = RefCell::new(SiwsMessageMap::new());
 }
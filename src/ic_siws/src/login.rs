@@ -16,13 +16,15 @@ use crate::{
     siws::{SiwsMessage, SiwsMessageError},
     solana::{verify_sol_signature, SolError, SolPubkey, SolSignature},
     time::get_current_time,
+    transaction::{verify_sol_transaction, SolTransaction},
     with_settings, SIWS_MESSAGES,
 };
 
 const MAX_SIGS_TO_PRUNE: usize = 10;
 
 /// This function is the first step of the user login process. It validates the provided Solana address,
-/// creates a SIWS message, saves it for future use, and returns it.
+/// creates a SIWS message, saves it for future use, and returns it. Each call creates a message with a new
+/// nonce, earlier pending messages of the address stay valid so logins can run in parallel.
 ///
 /// # Example
 /// ```ignore
@@ -111,6 +113,7 @@ impl fmt::Display for LoginError {
 /// * `signature_map`: A mutable reference to `SignatureMap` to which the delegation hash will be added
 ///   after successful validation.
 /// * `canister_id`: The principal of the canister performing the login.
+/// * `nonce`: The nonce of the SIWS message returned by [prepare_login] that was signed.
 ///
 /// # Returns
 /// A `Result` that, on success, contains the [LoginDetails] with session expiration and user canister
@@ -121,6 +124,62 @@ pub fn login(
     session_key: ByteBuf,
     signature_map: &mut SignatureMap,
     canister_id: &Principal,
+    nonce: &str,
+) -> Result<LoginDetails, LoginError> {
+    verify_and_login(
+        address,
+        session_key,
+        signature_map,
+        canister_id,
+        nonce,
+        |message_string| verify_sol_signature(message_string, signature, address),
+    )
+}
+
+/// Alternative to [login] for hardware wallets, which can't sign arbitrary off-chain messages. Instead of
+/// signing the SIWS message, the user signs a Solana transaction that is never broadcast and that contains
+/// the SIWS message in a memo instruction. The transaction is verified using [verify_sol_transaction]:
+/// the fee payer must be `address`, the fee payer signature must be valid and the memo must match the
+/// stored SIWS message. On success, the delegation is created the same way as in [login].
+///
+/// # Parameters
+/// * `transaction`: The signed transaction containing the SIWS message in a memo instruction.
+/// * `address`: The Solana address that signed the transaction as fee payer.
+/// * `session_key`: A unique session key to be used for the delegation.
+/// * `signature_map`: A mutable reference to `SignatureMap` to which the delegation hash will be added
+///   after successful validation.
+/// * `canister_id`: The principal of the canister performing the login.
+/// * `nonce`: The nonce of the SIWS message returned by [prepare_login] that was signed.
+///
+/// # Returns
+/// A `Result` that, on success, contains the [LoginDetails] with session expiration and user canister
+/// public key, or an error string on failure.
+pub fn login_with_transaction(
+    transaction: &SolTransaction,
+    address: &SolPubkey,
+    session_key: ByteBuf,
+    signature_map: &mut SignatureMap,
+    canister_id: &Principal,
+    nonce: &str,
+) -> Result<LoginDetails, LoginError> {
+    verify_and_login(
+        address,
+        session_key,
+        signature_map,
+        canister_id,
+        nonce,
+        |message_string| verify_sol_transaction(message_string, transaction, address),
+    )
+}
+
+// Verifies the stored SIWS message for `address` and `nonce` using `verify`, then creates the delegation.
+fn verify_and_login(
+    address: &SolPubkey,
+    session_key: ByteBuf,
+    signature_map: &mut SignatureMap,
+    canister_id: &Principal,
+    nonce: &str,
+    verify: impl FnOnce(&str) -> Result<bool, SolError>,
 ) -> Result<LoginDetails, LoginError> {
     // Remove expired SIWS messages from the state before proceeding. The init settings determines
     // the time to live for SIWS messages.
@@ -128,18 +187,17 @@ pub fn login(
         // Prune any expired SIWS messages from the state.
         siws_messages.prune_expired();
 
-        // Get the previously created SIWS message for current address. If it has expired or does not
-        // exist, return an error.
-        let message = siws_messages.get(address)?;
+        // Get the previously created SIWS message for current address and nonce. If it has expired or
+        // does not exist, return an error.
+        let message = siws_messages.get(address, nonce)?;
         let message_string: String = message.clone().into();
 
         // Verify the supplied signature and public key against the stored SIWS message.
-        verify_sol_signature(&message_string, signature, address)
-            .map_err(LoginError::SignatureError)?;
+        verify(&message_string).map_err(LoginError::SignatureError)?;
 
         // At this point, the signature has been verified and the SIWS message has been used. Remove
         // the SIWS message from the state.
-        siws_messages.remove(address);
+        siws_messages.remove(address, nonce);
 
         // The delegation is valid for the duration of the session as defined in the settings.
         let expiration = with_settings!(|settings: &Settings| {
@@ -170,3 +228,118 @@ pub fn login(
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::signature::{Keypair, Signer};
+
+    use super::*;
+    use crate::{settings::SettingsBuilder, siws::MAX_PENDING_MESSAGES_PER_ADDRESS, SETTINGS};
+
+    const SESSION_KEY: &[u8] = &[
+        48, 42, 48, 5, 6, 3, 43, 101, 112, 3, 33, 0, 220, 227, 2, 129, 72, 36, 43, 220, 96, 102,
+        225, 92, 98, 163, 114, 182, 117, 181, 51, 15, 219, 197, 104, 55, 123, 245, 74, 181, 35,
+        181, 171, 196,
+    ]; // DER encoded session key
+
+    fn init() -> (Keypair, SolPubkey) {
+        let settings = SettingsBuilder::new("example.com", "http://example.com", "some_salt")
+            .build()
+            .unwrap();
+        SETTINGS.set(Some(settings));
+        let wallet = Keypair::new();
+        let address = SolPubkey::from(wallet.pubkey().to_bytes());
+        (wallet, address)
+    }
+
+    fn sign(wallet: &Keypair, message: &SiwsMessage) -> SolSignature {
+        let message_string: String = message.clone().into();
+        let signature = wallet.sign_message(message_string.as_bytes());
+        SolSignature::try_from(signature.as_ref().to_vec()).unwrap()
+    }
+
+    fn login_with_nonce(
+        signature: &SolSignature,
+        address: &SolPubkey,
+        nonce: &str,
+    ) -> Result<LoginDetails, LoginError> {
+        login(
+            signature,
+            address,
+            ByteBuf::from(SESSION_KEY),
+            &mut SignatureMap::default(),
+            &Principal::anonymous(),
+            nonce,
+        )
+    }
+
+    fn pending_count(address: &SolPubkey) -> usize {
+        SIWS_MESSAGES.with_borrow(|siws_messages| siws_messages.pending_count(address))
+    }
+
+    // Two logins of one address can be prepared and completed in any order
+    #[test]
+    fn test_parallel_logins() {
+        let (wallet, address) = init();
+        let first = prepare_login(&address);
+        let second = prepare_login(&address);
+        assert_ne!(first.nonce, second.nonce);
+        assert_eq!(pending_count(&address), 2);
+
+        assert!(login_with_nonce(&sign(&wallet, &second), &address, &second.nonce).is_ok());
+        assert_eq!(pending_count(&address), 1);
+        assert!(login_with_nonce(&sign(&wallet, &first), &address, &first.nonce).is_ok());
+        assert_eq!(pending_count(&address), 0);
+    }
+
+    // A login with an unknown nonce fails and keeps the pending message
+    #[test]
+    fn test_login_nonce_mismatch() {
+        let (wallet, address) = init();
+        let message = prepare_login(&address);
+        let signature = sign(&wallet, &message);
+
+        let result = login_with_nonce(&signature, &address, "3a54fd98cf1c4a0e2b7d");
+        assert!(matches!(
+            result,
+            Err(LoginError::SiwsMessageError(
+                SiwsMessageError::MessageNotFound
+            ))
+        ));
+        assert_eq!(pending_count(&address), 1);
+        assert!(login_with_nonce(&signature, &address, &message.nonce).is_ok());
+    }
+
+    // The signature of one pending message can't be used to log in with the nonce of another
+    #[test]
+    fn test_login_signature_of_other_message() {
+        let (wallet, address) = init();
+        let first = prepare_login(&address);
+        let second = prepare_login(&address);
+
+        let result = login_with_nonce(&sign(&wallet, &first), &address, &second.nonce);
+        assert!(matches!(result, Err(LoginError::SignatureError(_))));
+        assert_eq!(pending_count(&address), 2);
+    }
+
+    // Preparing more logins than the cap evicts the oldest pending message
+    #[test]
+    fn test_pending_messages_capped() {
+        let (wallet, address) = init();
+        let messages: Vec<SiwsMessage> = (0..=MAX_PENDING_MESSAGES_PER_ADDRESS)
+            .map(|_| prepare_login(&address))
+            .collect();
+        assert_eq!(pending_count(&address), MAX_PENDING_MESSAGES_PER_ADDRESS);
+
+        let oldest = &messages[0];
+        let result = login_with_nonce(&sign(&wallet, oldest), &address, &oldest.nonce);
+        assert!(matches!(
+            result,
+            Err(LoginError::SiwsMessageError(
+                SiwsMessageError::MessageNotFound
+            ))
+        ));
+        let newest = &messages[MAX_PENDING_MESSAGES_PER_ADDRESS];
+        assert!(login_with_nonce(&sign(&wallet, newest), &address, &newest.nonce).is_ok());
+    }
+}
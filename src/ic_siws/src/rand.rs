@@ -10,10 +10,25 @@ pub(crate) fn generate_nonce() -> String {
     hex::encode(buf)
 }
 
+// Without the nonce feature the nonce is not random, but it is unique per message as pending SIWS messages
+// are keyed by address and nonce. Derived from the current time and a counter.
 #[cfg(not(test))]
 #[cfg(not(feature = "nonce"))]
 pub(crate) fn generate_nonce() -> String {
-    hex::encode("Not in use")
+    use std::cell::Cell;
+
+    thread_local! {
+        static NONCE_COUNTER: Cell<u64> = const { Cell::new(0) };
+    }
+
+    let counter = NONCE_COUNTER.with(|counter| {
+        let value = counter.get();
+        counter.set(value.wrapping_add(1));
+        value
+    });
+    let mut bytes = crate::time::get_current_time().to_be_bytes().to_vec();
+    bytes.extend_from_slice(&counter.to_be_bytes());
+    hex::encode(&crate::hash::hash_bytes(bytes)[..10])
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fmt};
+use std::{collections::BTreeMap, fmt};
 use time::{macros::format_description, OffsetDateTime};
 
 use crate::{
@@ -142,17 +142,22 @@ impl From<SiwsMessage> for String {
     }
 }
 
-/// The SiwsMessageMap is a map of SIWS messages keyed by the Solana address of the user. SIWS messages
-/// are stored in the map during the course of the login process and are removed once the login process
-/// is complete. The map is also pruned periodically to remove expired SIWS messages.
+/// The maximum number of pending SIWS messages per Solana address. Preparing another login evicts the
+/// oldest pending message of the address.
+pub const MAX_PENDING_MESSAGES_PER_ADDRESS: usize = 10;
+
+/// The SiwsMessageMap is a map of SIWS messages keyed by the Solana address of the user and the nonce of the
+/// message, so that one address can have several logins in progress, e.g. from different tabs or devices.
+/// SIWS messages are stored in the map during the course of the login process and are removed once the login
+/// process is complete. The map is also pruned periodically to remove expired SIWS messages.
 pub struct SiwsMessageMap {
-    map: HashMap<Vec<u8>, SiwsMessage>,
+    map: BTreeMap<(Vec<u8>, String), SiwsMessage>,
 }
 
 impl SiwsMessageMap {
     pub fn new() -> SiwsMessageMap {
         SiwsMessageMap {
-            map: HashMap::new(),
+            map: BTreeMap::new(),
         }
     }
 
@@ -163,23 +168,52 @@ impl SiwsMessageMap {
             .retain(|_, message| message.expiration_time > current_time);
     }
 
-    /// Adds a SIWS message to the map.
+    /// Adds a SIWS message to the map. If the address already has [`MAX_PENDING_MESSAGES_PER_ADDRESS`]
+    /// pending messages, the oldest ones are removed.
     pub fn insert(&mut self, pubkey: &SolPubkey, message: SiwsMessage) {
-        self.map.insert(pubkey.to_bytes().to_vec(), message);
+        let address = pubkey.to_bytes().to_vec();
+        let mut pending: Vec<((Vec<u8>, String), u64)> = self
+            .pending(&address)
+            .map(|(key, message)| (key.clone(), message.issued_at))
+            .collect();
+        if pending.len() >= MAX_PENDING_MESSAGES_PER_ADDRESS {
+            pending.sort_by_key(|(_, issued_at)| *issued_at);
+            let num_evicted = pending.len() + 1 - MAX_PENDING_MESSAGES_PER_ADDRESS;
+            for (key, _) in pending.into_iter().take(num_evicted) {
+                self.map.remove(&key);
+            }
+        }
+        self.map.insert((address, message.nonce.clone()), message);
     }
 
-    /// Returns a cloned SIWS message associated with the provided address or an error if the message
-    /// does not exist.
-    pub fn get(&self, pubkey: &SolPubkey) -> Result<SiwsMessage, SiwsMessageError> {
+    /// Returns a cloned SIWS message associated with the provided address and nonce or an error if the
+    /// message does not exist.
+    pub fn get(&self, pubkey: &SolPubkey, nonce: &str) -> Result<SiwsMessage, SiwsMessageError> {
         self.map
-            .get(&pubkey.to_bytes().to_vec())
+            .get(&(pubkey.to_bytes().to_vec(), nonce.to_string()))
             .cloned()
             .ok_or(SiwsMessageError::MessageNotFound)
     }
 
-    /// Removes the SIWS message associated with the provided address.
-    pub fn remove(&mut self, pubkey: &SolPubkey) {
-        self.map.remove(&pubkey.to_bytes().to_vec());
+    /// Removes the SIWS message associated with the provided address and nonce.
+    pub fn remove(&mut self, pubkey: &SolPubkey, nonce: &str) {
+        self.map
+            .remove(&(pubkey.to_bytes().to_vec(), nonce.to_string()));
+    }
+
+    /// Returns the number of pending SIWS messages of the provided address.
+    pub fn pending_count(&self, pubkey: &SolPubkey) -> usize {
+        self.pending(&pubkey.to_bytes()).count()
+    }
+
+    /// Iterates the pending SIWS messages of an address, which are adjacent in the map.
+    fn pending<'a>(
+        &'a self,
+        address: &'a [u8],
+    ) -> impl Iterator<Item = (&'a (Vec<u8>, String), &'a SiwsMessage)> + 'a {
+        self.map
+            .range((address.to_vec(), String::new())..)
+            .take_while(move |((a, _), _)| a.as_slice() == address)
     }
 }
 
@@ -188,3 +222,62 @@ impl Default for SiwsMessageMap {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(pubkey: &SolPubkey, issued_at: u64) -> SiwsMessage {
+        SiwsMessage {
+            domain: "example.com".to_string(),
+            address: pubkey.to_string(),
+            statement: "SIWS Fields:".to_string(),
+            uri: "http://example.com".to_string(),
+            version: 1,
+            chain_id: "mainnet".to_string(),
+            nonce: generate_nonce(),
+            issued_at,
+            expiration_time: issued_at + 1_000_000_000,
+        }
+    }
+
+    // Several pending messages of one address are kept apart by their nonce
+    #[test]
+    fn test_insert_same_address_different_nonces() {
+        let pubkey = SolPubkey::from([1u8; 32]);
+        let mut map = SiwsMessageMap::new();
+        let first = message(&pubkey, 1);
+        let second = message(&pubkey, 2);
+        map.insert(&pubkey, first.clone());
+        map.insert(&pubkey, second.clone());
+        assert_eq!(map.pending_count(&pubkey), 2);
+        assert_eq!(map.get(&pubkey, &first.nonce).unwrap().issued_at, 1);
+        assert_eq!(map.get(&pubkey, &second.nonce).unwrap().issued_at, 2);
+
+        map.remove(&pubkey, &second.nonce);
+        assert!(map.get(&pubkey, &second.nonce).is_err());
+        assert!(map.get(&pubkey, &first.nonce).is_ok());
+    }
+
+    // Inserting more than the cap evicts the oldest message of the address only
+    #[test]
+    fn test_insert_evicts_oldest_message() {
+        let pubkey = SolPubkey::from([1u8; 32]);
+        let other_pubkey = SolPubkey::from([2u8; 32]);
+        let mut map = SiwsMessageMap::new();
+        let other = message(&other_pubkey, 0);
+        map.insert(&other_pubkey, other.clone());
+
+        let messages: Vec<SiwsMessage> = (1..=MAX_PENDING_MESSAGES_PER_ADDRESS as u64 + 1)
+            .map(|issued_at| message(&pubkey, issued_at))
+            .collect();
+        for message in &messages {
+            map.insert(&pubkey, message.clone());
+        }
+
+        assert_eq!(map.pending_count(&pubkey), MAX_PENDING_MESSAGES_PER_ADDRESS);
+        assert!(map.get(&pubkey, &messages[0].nonce).is_err());
+        assert!(map.get(&pubkey, &messages[1].nonce).is_ok());
+        assert!(map.get(&other_pubkey, &other.nonce).is_ok());
+    }
+}
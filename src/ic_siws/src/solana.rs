@@ -136,6 +136,9 @@ pub enum SolError {
     InvalidPubkey,
     InvalidSignature,
     VerificationFailure,
+    FeePayerMismatch,
+    MemoMismatch,
+    UnexpectedInstruction,
 }
 
 impl fmt::Display for SolError {
@@ -144,6 +147,11 @@ impl fmt::Display for SolError {
             SolError::InvalidPubkey => write!(f, "Invalid public key"),
             SolError::InvalidSignature => write!(f, "Invalid signature"),
             SolError::VerificationFailure => write!(f, "Signature verification failed"),
+            SolError::FeePayerMismatch => write!(f, "Transaction fee payer does not match"),
+            SolError::MemoMismatch => write!(f, "Transaction memo does not match the SIWS message"),
+            SolError::UnexpectedInstruction => {
+                write!(f, "Transaction contains instructions other than memo")
+            }
         }
     }
 }
@@ -158,10 +166,17 @@ pub fn verify_sol_signature(
     message: &str,
     signature: &SolSignature,
     pubkey: &SolPubkey,
+) -> Result<bool, SolError> {
+    verify_sol_bytes(message.as_bytes(), signature, pubkey)
+}
+
+pub(crate) fn verify_sol_bytes(
+    message: &[u8],
+    signature: &SolSignature,
+    pubkey: &SolPubkey,
 ) -> Result<bool, SolError> {
     // Create a VerifyingKey from the Solana public key bytes
-    let verifying_key = VerifyingKey::from_bytes(&pubkey.0)
-        .map_err(|_| SolError::InvalidPubkey)?;
+    let verifying_key = VerifyingKey::from_bytes(&pubkey.0).map_err(|_| SolError::InvalidPubkey)?;
 
     // Create a Signature from the Solana signature bytes
     let signature = Signature::from_bytes(&signature.0)
@@ -170,7 +185,7 @@ pub fn verify_sol_signature(
 
     // Verify the signature
     verifying_key
-        .verify(message.as_bytes(), &signature)
+        .verify(message, &signature)
         .map(|_| true) // If verification is successful, map Ok(()) to true
         .map_err(|_| SolError::VerificationFailure) // Handle any verification failure
 }
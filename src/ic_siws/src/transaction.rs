@@ -0,0 +1,437 @@
+use {
+    crate::solana::{verify_sol_bytes, SolError, SolPubkey, SolSignature},
+    serde::Serialize,
+    std::fmt,
+    thiserror::Error,
+};
+
+/// The program id of the SPL Memo program, version 2.
+pub const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+
+/// The program id of the SPL Memo program, version 1.
+pub const MEMO_V1_PROGRAM_ID: &str = "Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo";
+
+/// The program id of the Compute Budget program. Wallets commonly add compute budget instructions to
+/// the transactions they sign, these are accepted alongside the memo instruction.
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+
+// Versioned messages have the highest bit of the first byte set, legacy messages never do.
+const MESSAGE_VERSION_PREFIX: u8 = 0x80;
+
+#[derive(Error, Debug, Serialize, Clone, PartialEq, Eq)]
+pub enum ParseSolTransactionError {
+    #[error("Transaction is truncated")]
+    Truncated,
+    #[error("Transaction has trailing bytes")]
+    TrailingBytes,
+    #[error("Unsupported transaction version")]
+    UnsupportedVersion,
+    #[error("Transaction has no signatures")]
+    NoSignatures,
+    #[error("Invalid account index in transaction")]
+    InvalidAccountIndex,
+    #[error("Invalid compact-u16 length in transaction")]
+    InvalidLength,
+}
+
+/// An instruction of a Solana transaction, with the program id resolved from the account keys of
+/// the transaction message.
+pub struct SolInstruction {
+    pub program_id: SolPubkey,
+    pub data: Vec<u8>,
+}
+
+/// A serialized Solana transaction, as returned by the `signTransaction` method of a wallet. Legacy
+/// and version 0 transactions are supported.
+///
+/// Hardware wallets can't sign arbitrary off-chain messages. Instead, the user signs a transaction that
+/// is never broadcast, containing the SIWS message in a memo instruction. See [verify_sol_transaction].
+pub struct SolTransaction {
+    /// The signatures of the transaction, in the order of the signing account keys.
+    pub signatures: Vec<SolSignature>,
+
+    /// The account keys of the transaction message. The first account key is the fee payer.
+    pub account_keys: Vec<SolPubkey>,
+
+    /// The instructions of the transaction message.
+    pub instructions: Vec<SolInstruction>,
+
+    // The serialized transaction message, this is what the signatures sign.
+    message: Vec<u8>,
+}
+
+impl SolTransaction {
+    /// The fee payer of the transaction, the first account key of the transaction message.
+    pub fn fee_payer(&self) -> Option<&SolPubkey> {
+        self.account_keys.first()
+    }
+}
+
+impl TryFrom<&[u8]> for SolTransaction {
+    type Error = ParseSolTransactionError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let mut reader = Reader { bytes, position: 0 };
+
+        // Counts come from untrusted bytes, capacities are bounded by the bytes left to read.
+        let num_signatures = reader.read_compact_u16()?;
+        let mut signatures = Vec::with_capacity(num_signatures.min(reader.remaining() / 64));
+        for _ in 0..num_signatures {
+            let mut signature = [0u8; 64];
+            signature.copy_from_slice(reader.read_bytes(64)?);
+            signatures.push(SolSignature(signature));
+        }
+        let message_start = reader.position;
+
+        // Only version 0 is defined for versioned messages.
+        if reader.peek()? & MESSAGE_VERSION_PREFIX != 0 {
+            let version = reader.read_u8()? & !MESSAGE_VERSION_PREFIX;
+            if version != 0 {
+                return Err(ParseSolTransactionError::UnsupportedVersion);
+            }
+        }
+
+        // Message header: number of required signatures, readonly signed and readonly unsigned accounts.
+        let num_required_signatures = reader.read_u8()? as usize;
+        reader.read_bytes(2)?;
+        if num_required_signatures == 0 || signatures.len() < num_required_signatures {
+            return Err(ParseSolTransactionError::NoSignatures);
+        }
+
+        let num_account_keys = reader.read_compact_u16()?;
+        let mut account_keys = Vec::with_capacity(num_account_keys.min(reader.remaining() / 32));
+        for _ in 0..num_account_keys {
+            let mut key = [0u8; 32];
+            key.copy_from_slice(reader.read_bytes(32)?);
+            account_keys.push(SolPubkey(key));
+        }
+
+        // Recent blockhash
+        reader.read_bytes(32)?;
+
+        let num_instructions = reader.read_compact_u16()?;
+        // Each instruction takes at least three bytes: program id index, accounts and data lengths.
+        let mut instructions = Vec::with_capacity(num_instructions.min(reader.remaining() / 3));
+        for _ in 0..num_instructions {
+            let program_id_index = reader.read_u8()? as usize;
+            let num_accounts = reader.read_compact_u16()?;
+            reader.read_bytes(num_accounts)?;
+            let data_len = reader.read_compact_u16()?;
+            let data = reader.read_bytes(data_len)?.to_vec();
+
+            // Programs can't be loaded from address lookup tables, the program id is always a static key.
+            let program_id = *account_keys
+                .get(program_id_index)
+                .ok_or(ParseSolTransactionError::InvalidAccountIndex)?;
+            instructions.push(SolInstruction { program_id, data });
+        }
+
+        // Address table lookups of version 0 messages, these are part of the signed message but not
+        // needed to verify the memo.
+        if bytes[message_start] & MESSAGE_VERSION_PREFIX != 0 {
+            let num_lookups = reader.read_compact_u16()?;
+            for _ in 0..num_lookups {
+                reader.read_bytes(32)?;
+                let num_writable = reader.read_compact_u16()?;
+                reader.read_bytes(num_writable)?;
+                let num_readonly = reader.read_compact_u16()?;
+                reader.read_bytes(num_readonly)?;
+            }
+        }
+
+        if reader.position != bytes.len() {
+            return Err(ParseSolTransactionError::TrailingBytes);
+        }
+
+        Ok(SolTransaction {
+            signatures,
+            account_keys,
+            instructions,
+            message: bytes[message_start..].to_vec(),
+        })
+    }
+}
+
+impl TryFrom<Vec<u8>> for SolTransaction {
+    type Error = ParseSolTransactionError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        SolTransaction::try_from(bytes.as_slice())
+    }
+}
+
+impl fmt::Debug for SolTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SolTransaction")
+            .field("fee_payer", &self.fee_payer().map(|key| key.to_string()))
+            .field("signatures", &self.signatures.len())
+            .field("instructions", &self.instructions.len())
+            .finish()
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn peek(&self) -> Result<u8, ParseSolTransactionError> {
+        self.bytes
+            .get(self.position)
+            .copied()
+            .ok_or(ParseSolTransactionError::Truncated)
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    fn read_u8(&mut self) -> Result<u8, ParseSolTransactionError> {
+        let byte = self.peek()?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], ParseSolTransactionError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .ok_or(ParseSolTransactionError::Truncated)?;
+        let bytes = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(ParseSolTransactionError::Truncated)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    // Solana's compact-u16 encoding: little endian base 128, at most three bytes. Like Solana,
+    // values above u16::MAX and non-canonical encodings with a trailing zero byte are rejected.
+    fn read_compact_u16(&mut self) -> Result<usize, ParseSolTransactionError> {
+        let mut value = 0usize;
+        for i in 0..3 {
+            let byte = self.read_u8()?;
+            if i > 0 && byte == 0 {
+                return Err(ParseSolTransactionError::InvalidLength);
+            }
+            value |= ((byte & 0x7f) as usize) << (i * 7);
+            if byte & 0x80 == 0 {
+                return u16::try_from(value)
+                    .map(usize::from)
+                    .map_err(|_| ParseSolTransactionError::InvalidLength);
+            }
+        }
+        Err(ParseSolTransactionError::InvalidLength)
+    }
+}
+
+/// Verifies a transaction signed in place of the SIWS message. The transaction is accepted if:
+/// - The fee payer of the transaction is `pubkey`.
+/// - The fee payer signature is a valid signature of the transaction message.
+/// - The transaction has a memo instruction with exactly `message` as content.
+/// - All other instructions are memo or compute budget instructions, so that the transaction does
+///   nothing but pay a fee if it were ever broadcast.
+pub fn verify_sol_transaction(
+    message: &str,
+    transaction: &SolTransaction,
+    pubkey: &SolPubkey,
+) -> Result<bool, SolError> {
+    let memo_program_ids: [SolPubkey; 2] = [
+        MEMO_PROGRAM_ID
+            .parse()
+            .map_err(|_| SolError::InvalidPubkey)?,
+        MEMO_V1_PROGRAM_ID
+            .parse()
+            .map_err(|_| SolError::InvalidPubkey)?,
+    ];
+    let compute_budget_program_id: SolPubkey = COMPUTE_BUDGET_PROGRAM_ID
+        .parse()
+        .map_err(|_| SolError::InvalidPubkey)?;
+
+    let fee_payer = transaction.fee_payer().ok_or(SolError::FeePayerMismatch)?;
+    if fee_payer.0 != pubkey.0 {
+        return Err(SolError::FeePayerMismatch);
+    }
+
+    let is_memo = |instruction: &SolInstruction| {
+        memo_program_ids
+            .iter()
+            .any(|program_id| program_id.0 == instruction.program_id.0)
+    };
+    if transaction.instructions.iter().any(|instruction| {
+        !is_memo(instruction) && instruction.program_id.0 != compute_budget_program_id.0
+    }) {
+        return Err(SolError::UnexpectedInstruction);
+    }
+    if !transaction
+        .instructions
+        .iter()
+        .any(|instruction| is_memo(instruction) && instruction.data == message.as_bytes())
+    {
+        return Err(SolError::MemoMismatch);
+    }
+
+    // The fee payer signature is always the first signature.
+    let signature = transaction
+        .signatures
+        .first()
+        .ok_or(SolError::InvalidSignature)?;
+    verify_sol_bytes(&transaction.message, signature, pubkey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        compute_budget::ComputeBudgetInstruction,
+        hash::Hash,
+        instruction::Instruction,
+        message::{v0, Message, VersionedMessage},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+    use std::str::FromStr;
+
+    const SIWS_MESSAGE: &str = "example.com wants you to sign in with your Solana account:\nAwes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM\n\nSIWS Fields:";
+
+    fn memo_instruction(memo: &str) -> Instruction {
+        Instruction {
+            program_id: Pubkey::from_str(MEMO_PROGRAM_ID).unwrap(),
+            accounts: vec![],
+            data: memo.as_bytes().to_vec(),
+        }
+    }
+
+    // Serializes a signed message the way wallets do: compact-u16 signature count, signatures, message.
+    fn sign(keypair: &Keypair, message_bytes: Vec<u8>) -> Vec<u8> {
+        let signature = keypair.sign_message(&message_bytes);
+        let mut bytes = vec![1u8];
+        bytes.extend_from_slice(signature.as_ref());
+        bytes.extend(message_bytes);
+        bytes
+    }
+
+    fn legacy_transaction(keypair: &Keypair, instructions: &[Instruction]) -> Vec<u8> {
+        let message =
+            Message::new_with_blockhash(instructions, Some(&keypair.pubkey()), &Hash::new_unique());
+        sign(keypair, message.serialize())
+    }
+
+    fn pubkey(keypair: &Keypair) -> SolPubkey {
+        SolPubkey::from(keypair.pubkey().to_bytes())
+    }
+
+    #[test]
+    fn test_verify_legacy_transaction() {
+        let keypair = Keypair::new();
+        let bytes = legacy_transaction(&keypair, &[memo_instruction(SIWS_MESSAGE)]);
+        let transaction = SolTransaction::try_from(bytes.as_slice()).unwrap();
+        assert!(verify_sol_transaction(SIWS_MESSAGE, &transaction, &pubkey(&keypair)).is_ok());
+    }
+
+    #[test]
+    fn test_verify_v0_transaction_with_compute_budget() {
+        let keypair = Keypair::new();
+        let message = v0::Message::try_compile(
+            &keypair.pubkey(),
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(10_000),
+                memo_instruction(SIWS_MESSAGE),
+            ],
+            &[],
+            Hash::new_unique(),
+        )
+        .unwrap();
+        let bytes = sign(&keypair, VersionedMessage::V0(message).serialize());
+        let transaction = SolTransaction::try_from(bytes.as_slice()).unwrap();
+        assert!(verify_sol_transaction(SIWS_MESSAGE, &transaction, &pubkey(&keypair)).is_ok());
+    }
+
+    #[test]
+    fn test_verify_memo_mismatch() {
+        let keypair = Keypair::new();
+        let bytes = legacy_transaction(&keypair, &[memo_instruction("Another message")]);
+        let transaction = SolTransaction::try_from(bytes.as_slice()).unwrap();
+        let result = verify_sol_transaction(SIWS_MESSAGE, &transaction, &pubkey(&keypair));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            SolError::MemoMismatch.to_string()
+        );
+    }
+
+    #[test]
+    fn test_verify_fee_payer_mismatch() {
+        let keypair = Keypair::new();
+        let bytes = legacy_transaction(&keypair, &[memo_instruction(SIWS_MESSAGE)]);
+        let transaction = SolTransaction::try_from(bytes.as_slice()).unwrap();
+        let result = verify_sol_transaction(SIWS_MESSAGE, &transaction, &pubkey(&Keypair::new()));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            SolError::FeePayerMismatch.to_string()
+        );
+    }
+
+    #[test]
+    fn test_verify_unexpected_instruction() {
+        let keypair = Keypair::new();
+        let other = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![],
+            data: vec![1, 2, 3],
+        };
+        let bytes = legacy_transaction(&keypair, &[memo_instruction(SIWS_MESSAGE), other]);
+        let transaction = SolTransaction::try_from(bytes.as_slice()).unwrap();
+        let result = verify_sol_transaction(SIWS_MESSAGE, &transaction, &pubkey(&keypair));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            SolError::UnexpectedInstruction.to_string()
+        );
+    }
+
+    #[test]
+    fn test_verify_manipulated_message() {
+        let keypair = Keypair::new();
+        let mut bytes = legacy_transaction(&keypair, &[memo_instruction(SIWS_MESSAGE)]);
+        // Change the recent blockhash, the signature no longer matches the message.
+        let blockhash_start = bytes.len() - SIWS_MESSAGE.len() - 1 - 1 - 1 - 1 - 32;
+        bytes[blockhash_start] ^= 0xff;
+        let transaction = SolTransaction::try_from(bytes.as_slice()).unwrap();
+        let result = verify_sol_transaction(SIWS_MESSAGE, &transaction, &pubkey(&keypair));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            SolError::VerificationFailure.to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_truncated_transaction() {
+        let keypair = Keypair::new();
+        let bytes = legacy_transaction(&keypair, &[memo_instruction(SIWS_MESSAGE)]);
+        let result = SolTransaction::try_from(&bytes[..bytes.len() - 1]);
+        assert_eq!(result.unwrap_err(), ParseSolTransactionError::Truncated);
+    }
+
+    #[test]
+    fn test_read_compact_u16() {
+        let read = |bytes: &[u8]| Reader { bytes, position: 0 }.read_compact_u16();
+        assert_eq!(read(&[0x00]), Ok(0));
+        assert_eq!(read(&[0x7f]), Ok(0x7f));
+        assert_eq!(read(&[0x80, 0x01]), Ok(0x80));
+        assert_eq!(read(&[0xff, 0xff, 0x03]), Ok(0xffff));
+        // Above u16::MAX
+        assert_eq!(read(&[0xff, 0xff, 0x04]), Err(ParseSolTransactionError::InvalidLength));
+        assert_eq!(read(&[0xff, 0xff, 0x7f]), Err(ParseSolTransactionError::InvalidLength));
+        // More than three bytes
+        assert_eq!(read(&[0x80, 0x80, 0x80]), Err(ParseSolTransactionError::InvalidLength));
+        // Non-canonical encoding of 0
+        assert_eq!(read(&[0x80, 0x00]), Err(ParseSolTransactionError::InvalidLength));
+    }
+
+    #[test]
+    fn test_parse_large_counts_without_bytes() {
+        // 65535 signatures announced but none present fails without allocating for them.
+        let result = SolTransaction::try_from([0xff, 0xff, 0x03].as_slice());
+        assert_eq!(result.unwrap_err(), ParseSolTransactionError::Truncated);
+    }
+}
@@ -91,10 +91,12 @@ thread_local! {
     static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
         RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
 
-    static SETTINGS: RefCell<Settings> = RefCell::new(Settings {
-        disable_sol_to_principal_mapping: false,
-        disable_principal_to_sol_mapping: false,
-    });
+    static SETTINGS: RefCell<Settings> = const {
+        RefCell::new(Settings {
+            disable_sol_to_principal_mapping: false,
+            disable_principal_to_sol_mapping: false,
+        })
+    };
 
     static PRINCIPAL_ADDRESS: RefCell<StableBTreeMap<Blob<29>, [u8;32], VirtualMemory<DefaultMemoryImpl>>> = RefCell::new(
         StableBTreeMap::init(
@@ -4,4 +4,5 @@ pub mod get_principal;
 pub mod init_upgrade;
 pub mod siws_get_delegation;
 pub mod siws_login;
+pub mod siws_login_with_transaction;
 pub mod siws_prepare_login;
@@ -18,6 +18,7 @@ use crate::{update_root_hash, ADDRESS_PRINCIPAL, PRINCIPAL_ADDRESS, SETTINGS, ST
 /// * `signature` (String): The signature of the SIWS message.
 /// * `address` (String): The Solana address of the user.
 /// * `session_key` (ByteBuf): A unique key that identifies the session.
+/// * `nonce` (String): The nonce of the signed SIWS message, as returned by `siws_prepare_login`.
 ///
 /// # Returns
 /// * `Ok(LoginOkResponse)`: Contains the user canister public key and other login response data if the login is successful.
@@ -27,6 +28,7 @@ fn siws_login(
     signature: String,
     pubkey: String,
     session_key: ByteBuf,
+    nonce: String,
 ) -> Result<LoginDetails, String> {
     STATE.with(|state| {
         let signature_map = &mut *state.signature_map.borrow_mut();
@@ -36,13 +38,14 @@ fn siws_login(
         // Create an EthSignature from the string. This validates the signature.
         let signature = SolSignature::from_str(signature.as_str()).map_err(|e| e.to_string())?;
 
-        // Attempt to log in with the provided signature, address, and session key.
+        // Attempt to log in with the provided signature, address, session key and nonce.
         let login_response = ic_siws::login::login(
             &signature,
             &pubkey,
             session_key,
             &mut *signature_map,
             &ic_cdk::api::id(),
+            &nonce,
         )
         .map_err(|e| e.to_string())?;
 
@@ -62,7 +65,7 @@ fn siws_login(
     })
 }
 
-fn manage_principal_address_mappings(principal: &Blob<29>, pubkey: &SolPubkey) {
+pub(crate) fn manage_principal_address_mappings(principal: &Blob<29>, pubkey: &SolPubkey) {
     SETTINGS.with(|s| {
         if !s.borrow().disable_principal_to_sol_mapping {
             PRINCIPAL_ADDRESS.with(|pa| {
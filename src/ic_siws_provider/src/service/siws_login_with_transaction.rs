@@ -0,0 +1,66 @@
+use std::str::FromStr;
+
+use candid::Principal;
+use ic_cdk::update;
+use ic_siws::{login::LoginDetails, solana::SolPubkey, transaction::SolTransaction};
+use ic_stable_structures::storable::Blob;
+use serde_bytes::ByteBuf;
+
+use crate::{service::siws_login::manage_principal_address_mappings, update_root_hash, STATE};
+
+/// Authenticates the user by verifying a signed Solana transaction that contains the SIWS message in a
+/// memo instruction. Use this instead of `siws_login` for wallets that can't sign off-chain messages, such
+/// as Ledger hardware wallets. The transaction is never broadcast. This function also prepares the
+/// delegation to be fetched in the next step, the `siws_get_delegation` function.
+///
+/// # Arguments
+/// * `transaction` (ByteBuf): The serialized, signed transaction. The fee payer must be `address`.
+/// * `address` (String): The Solana address of the user.
+/// * `session_key` (ByteBuf): A unique key that identifies the session.
+/// * `nonce` (String): The nonce of the SIWS message, as returned by `siws_prepare_login`.
+///
+/// # Returns
+/// * `Ok(LoginOkResponse)`: Contains the user canister public key and other login response data if the login is successful.
+/// * `Err(String)`: An error message if the login process fails.
+#[update]
+fn siws_login_with_transaction(
+    transaction: ByteBuf,
+    pubkey: String,
+    session_key: ByteBuf,
+    nonce: String,
+) -> Result<LoginDetails, String> {
+    STATE.with(|state| {
+        let signature_map = &mut *state.signature_map.borrow_mut();
+
+        let pubkey = SolPubkey::from_str(pubkey.as_str()).map_err(|e| e.to_string())?;
+
+        // Parse the serialized transaction. This validates the transaction format.
+        let transaction =
+            SolTransaction::try_from(transaction.as_slice()).map_err(|e| e.to_string())?;
+
+        // Attempt to log in with the provided transaction, address, session key and nonce.
+        let login_response = ic_siws::login::login_with_transaction(
+            &transaction,
+            &pubkey,
+            session_key,
+            &mut *signature_map,
+            &ic_cdk::api::id(),
+            &nonce,
+        )
+        .map_err(|e| e.to_string())?;
+
+        // Update the certified data of the canister due to changes in the signature map.
+        update_root_hash(&state.asset_hashes.borrow(), signature_map);
+
+        // Convert the user canister public key to a principal.
+        let principal: Blob<29> =
+            Principal::self_authenticating(&login_response.user_canister_pubkey).as_slice()[..29]
+                .try_into()
+                .map_err(|_| format!("Invalid principal: {:?}", login_response))?;
+
+        // Store the mapping of principal to Solana address and vice versa if the settings allow it.
+        manage_principal_address_mappings(&principal, &pubkey);
+
+        Ok(login_response)
+    })
+}
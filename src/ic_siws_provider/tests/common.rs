@@ -9,10 +9,16 @@ use ic_agent::{
     Identity,
 };
 use ic_siws::{delegation::SignedDelegation, login::LoginDetails, siws::SiwsMessage};
-use pocket_ic::{PocketIc, WasmResult};
+use pocket_ic::PocketIc;
 use rand::Rng;
 use serde::Deserialize;
-use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
 use std::time::Duration;
 
 #[derive(CandidType, Debug, Clone, PartialEq, Deserialize)]
@@ -40,6 +46,7 @@ pub struct SettingsInput {
 }
 
 pub const VALID_PUBKEY: &str = "Awes4Tr6TX8JDzEhCZY2QVNimT6iD1zWHzf1vNyGvpLM";
+pub const NONCE: &str = "3a54fd98cf1c4a0e2b7d"; // A nonce that no prepared SIWS message has
 pub const SESSION_KEY: &[u8] = &[
     48, 42, 48, 5, 6, 3, 43, 101, 112, 3, 33, 0, 220, 227, 2, 129, 72, 36, 43, 220, 96, 102, 225,
     92, 98, 163, 114, 182, 117, 181, 51, 15, 219, 197, 104, 55, 123, 245, 74, 181, 35, 181, 171,
@@ -106,9 +113,8 @@ pub fn update<T: CandidType + for<'de> Deserialize<'de>>(
     args: Vec<u8>,
 ) -> Result<T, String> {
     match ic.update_call(canister, sender, method, args) {
-        Ok(WasmResult::Reply(data)) => decode_one(&data).unwrap(),
-        Ok(WasmResult::Reject(error_message)) => Err(error_message.to_string()),
-        Err(user_error) => Err(user_error.to_string()),
+        Ok(data) => decode_one(&data).unwrap(),
+        Err(reject_response) => Err(reject_response.to_string()),
     }
 }
 
@@ -120,9 +126,8 @@ pub fn query<T: CandidType + for<'de> Deserialize<'de>>(
     args: Vec<u8>,
 ) -> Result<T, String> {
     match ic.query_call(canister, sender, method, args) {
-        Ok(WasmResult::Reply(data)) => decode_one(&data).unwrap(),
-        Ok(WasmResult::Reject(error_message)) => Err(error_message.to_string()),
-        Err(user_error) => Err(user_error.to_string()),
+        Ok(data) => decode_one(&data).unwrap(),
+        Err(reject_response) => Err(reject_response.to_string()),
     }
 }
 
@@ -158,12 +163,46 @@ pub fn prepare_login_and_sign_message(
     (signature_str, siws_message)
 }
 
+// Hardware wallets sign a transaction with the SIWS message in a memo instruction instead of the message itself.
+pub fn prepare_login_and_sign_transaction(
+    ic: &PocketIc,
+    ic_siws_provider_canister: Principal,
+    wallet: &Keypair,
+) -> (Vec<u8>, SiwsMessage) {
+    let args = encode_one(wallet.pubkey().to_string()).unwrap();
+    let siws_message: SiwsMessage = update(
+        ic,
+        Principal::anonymous(),
+        ic_siws_provider_canister,
+        "siws_prepare_login",
+        args,
+    )
+    .unwrap();
+
+    let message_string: String = siws_message.clone().into();
+    let memo = Instruction {
+        program_id: ic_siws::transaction::MEMO_PROGRAM_ID
+            .parse::<Pubkey>()
+            .unwrap(),
+        accounts: vec![],
+        data: message_string.into_bytes(),
+    };
+    let message = Message::new_with_blockhash(&[memo], Some(&wallet.pubkey()), &Hash::default());
+    let message_bytes = message.serialize();
+    let signature = wallet.sign_message(&message_bytes);
+
+    // Serialized transaction: number of signatures, signatures, message
+    let mut transaction = vec![1u8];
+    transaction.extend_from_slice(signature.as_ref());
+    transaction.extend(message_bytes);
+
+    (transaction, siws_message)
+}
+
 pub fn create_session_identity() -> BasicIdentity {
     let mut ed25519_seed = [0u8; 32];
     rand::thread_rng().fill(&mut ed25519_seed);
-    let ed25519_keypair =
-        ring::signature::Ed25519KeyPair::from_seed_unchecked(&ed25519_seed).unwrap();
-    BasicIdentity::from_key_pair(ed25519_keypair)
+    BasicIdentity::from_signing_key(ed25519_consensus::SigningKey::from(ed25519_seed))
 }
 
 pub fn create_delegated_identity(
@@ -178,11 +217,10 @@ pub fn create_delegated_identity(
             pubkey: identity.public_key().unwrap(),
             expiration: login_response.expiration,
             targets,
-            senders: None,
         },
         signature,
     };
-    DelegatedIdentity::new(
+    DelegatedIdentity::new_unchecked(
         login_response.user_canister_pubkey.to_vec(),
         Box::new(identity),
         vec![signed_delegation],
@@ -195,14 +233,21 @@ pub fn full_login(
     targets: Option<Vec<Principal>>,
 ) -> (String, DelegatedIdentity) {
     let (wallet, address) = create_wallet();
-    let (signature, _) = prepare_login_and_sign_message(ic, ic_siws_provider_canister, &wallet);
+    let (signature, siws_message) =
+        prepare_login_and_sign_message(ic, ic_siws_provider_canister, &wallet);
 
     // Create a session identity
     let session_identity = create_session_identity();
     let session_pubkey = session_identity.public_key().unwrap();
 
     // Login
-    let login_args = encode_args((signature, address.clone(), session_pubkey.clone())).unwrap();
+    let login_args = encode_args((
+        signature,
+        address.clone(),
+        session_pubkey.clone(),
+        siws_message.nonce,
+    ))
+    .unwrap();
     let login_response: LoginDetails = update(
         ic,
         Principal::anonymous(),
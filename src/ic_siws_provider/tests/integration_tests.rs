@@ -11,7 +11,10 @@ use pocket_ic::PocketIc;
 use serde_bytes::ByteBuf;
 use std::time::Duration;
 
-use crate::common::{prepare_login_and_sign_message, SettingsInput, VALID_PUBKEY};
+use crate::common::{
+    prepare_login_and_sign_message, prepare_login_and_sign_transaction, SettingsInput, NONCE,
+    VALID_PUBKEY,
+};
 
 #[test]
 #[should_panic]
@@ -159,7 +162,7 @@ fn test_login_signature_too_short() {
     let ic = PocketIc::new();
     let (ic_siws_provider_canister, _) = init(&ic, None);
     let signature = "4odadvBKw1"; // Too short
-    let args = encode_args((signature, VALID_PUBKEY, SESSION_KEY)).unwrap();
+    let args = encode_args((signature, VALID_PUBKEY, SESSION_KEY, NONCE)).unwrap();
     let response: Result<LoginDetails, String> = update(
         &ic,
         Principal::anonymous(),
@@ -175,7 +178,7 @@ fn test_login_signature_too_long() {
     let ic = PocketIc::new();
     let (ic_siws_provider_canister, _) = init(&ic, None);
     let signature = "4odadvBKw14odadvBKw14odadvBKw14odadvBKw14odadvBKw14odadvBKw14odadvBKw14odadvBKw14odadvBKw14odadvBKw14odadvBKw14odadvBKw14odadvBKw1"; // Too long
-    let args = encode_args((signature, VALID_PUBKEY, SESSION_KEY)).unwrap();
+    let args = encode_args((signature, VALID_PUBKEY, SESSION_KEY, NONCE)).unwrap();
     let response: Result<LoginDetails, String> = update(
         &ic,
         Principal::anonymous(),
@@ -191,7 +194,7 @@ fn test_incorrect_signature_format() {
     let ic = PocketIc::new();
     let (ic_siws_provider_canister, _) = init(&ic, None);
     let signature = "INVALID SIGNATURE FORMAT";
-    let args = encode_args((signature, VALID_PUBKEY, SESSION_KEY)).unwrap();
+    let args = encode_args((signature, VALID_PUBKEY, SESSION_KEY, NONCE)).unwrap();
     let response: Result<LoginDetails, String> = update(
         &ic,
         Principal::anonymous(),
@@ -208,11 +211,12 @@ fn test_sign_in_message_expired() {
     let ic = PocketIc::new();
     let (ic_siws_provider_canister, _) = init(&ic, None);
     let (wallet, pubkey) = create_wallet();
-    let (signature, _) = prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
+    let (signature, siws_message) =
+        prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
 
     ic.advance_time(Duration::from_secs(10));
 
-    let args = encode_args((signature, pubkey, SESSION_KEY)).unwrap();
+    let args = encode_args((signature, pubkey, SESSION_KEY, siws_message.nonce)).unwrap();
     let response: Result<LoginDetails, String> = update(
         &ic,
         Principal::anonymous(),
@@ -229,8 +233,9 @@ fn test_sign_in_address_mismatch() {
     let ic = PocketIc::new();
     let (ic_siws_provider_canister, _) = init(&ic, None);
     let (wallet, _) = create_wallet();
-    let (signature, _) = prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
-    let args = encode_args((signature, VALID_PUBKEY, SESSION_KEY)).unwrap(); // Wrong pubkey
+    let (signature, siws_message) =
+        prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
+    let args = encode_args((signature, VALID_PUBKEY, SESSION_KEY, siws_message.nonce)).unwrap(); // Wrong pubkey
     let response: Result<LoginDetails, String> = update(
         &ic,
         Principal::anonymous(),
@@ -247,10 +252,16 @@ fn test_sign_in_signature_manipulated() {
     let ic = PocketIc::new();
     let (ic_siws_provider_canister, _) = init(&ic, None);
     let (wallet, pubkey) = create_wallet();
-    let (_, _) = prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
+    let (_, siws_message) = prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
     let manipulated_signature =
         "5TgJLdKQZ8UjBZLbVjHHQ9kZigxmAgDKqGdZKdJzF8iMWri93N4d2Q7RfQJHReAqyQzSJf9B4MeqGTPJkH6RcW72";
-    let args = encode_args((manipulated_signature, pubkey, SESSION_KEY)).unwrap();
+    let args = encode_args((
+        manipulated_signature,
+        pubkey,
+        SESSION_KEY,
+        siws_message.nonce,
+    ))
+    .unwrap();
     let response: Result<LoginDetails, String> = update(
         &ic,
         Principal::anonymous(),
@@ -266,8 +277,9 @@ fn test_sign_in_ok() {
     let ic = PocketIc::new();
     let (ic_siws_provider_canister, _) = init(&ic, None);
     let (wallet, pubkey) = create_wallet();
-    let (signature, _) = prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
-    let args = encode_args((signature, pubkey, SESSION_KEY)).unwrap();
+    let (signature, siws_message) =
+        prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
+    let args = encode_args((signature, pubkey, SESSION_KEY, siws_message.nonce)).unwrap();
     let response: Result<LoginDetails, String> = update(
         &ic,
         Principal::anonymous(),
@@ -285,8 +297,9 @@ fn test_sign_in_replay_attack() {
     let ic = PocketIc::new();
     let (ic_siws_provider_canister, _) = init(&ic, None);
     let (wallet, pubkey) = create_wallet();
-    let (signature, _) = prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
-    let args = encode_args((signature, pubkey, SESSION_KEY)).unwrap();
+    let (signature, siws_message) =
+        prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
+    let args = encode_args((signature, pubkey, SESSION_KEY, siws_message.nonce)).unwrap();
     let response: Result<LoginDetails, String> = update(
         &ic,
         Principal::anonymous(),
@@ -305,6 +318,162 @@ fn test_sign_in_replay_attack() {
     assert_eq!(second_response.unwrap_err(), "Message not found");
 }
 
+// Prepare two logins for the same wallet before logging in. Both logins should succeed, regardless of the order.
+#[test]
+fn test_sign_in_parallel_logins() {
+    let ic = PocketIc::new();
+    let (ic_siws_provider_canister, _) = init(&ic, None);
+    let (wallet, pubkey) = create_wallet();
+    let (signature1, siws_message1) =
+        prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
+    let (signature2, siws_message2) =
+        prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
+    assert_ne!(siws_message1.nonce, siws_message2.nonce);
+
+    // Login with the second message first
+    let args = encode_args((signature2, pubkey.clone(), SESSION_KEY, siws_message2.nonce)).unwrap();
+    let response: Result<LoginDetails, String> = update(
+        &ic,
+        Principal::anonymous(),
+        ic_siws_provider_canister,
+        "siws_login",
+        args,
+    );
+    assert!(response.is_ok());
+
+    let args = encode_args((signature1, pubkey, SESSION_KEY, siws_message1.nonce)).unwrap();
+    let response: Result<LoginDetails, String> = update(
+        &ic,
+        Principal::anonymous(),
+        ic_siws_provider_canister,
+        "siws_login",
+        args,
+    );
+    assert!(response.is_ok());
+}
+
+// A valid signature but with a nonce that does not belong to the signed message
+#[test]
+fn test_sign_in_nonce_mismatch() {
+    let ic = PocketIc::new();
+    let (ic_siws_provider_canister, _) = init(&ic, None);
+    let (wallet, pubkey) = create_wallet();
+    let (signature, _) = prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
+    let args = encode_args((signature, pubkey, SESSION_KEY, NONCE)).unwrap(); // Wrong nonce
+    let response: Result<LoginDetails, String> = update(
+        &ic,
+        Principal::anonymous(),
+        ic_siws_provider_canister,
+        "siws_login",
+        args,
+    );
+    assert_eq!(response.unwrap_err(), "Message not found");
+}
+
+// The number of pending messages per address is capped. Preparing more logins than the cap evicts the oldest message.
+#[test]
+fn test_sign_in_pending_messages_capped() {
+    let ic = PocketIc::new();
+    let (ic_siws_provider_canister, _) = init(&ic, None);
+    let (wallet, pubkey) = create_wallet();
+    let (first_signature, first_siws_message) =
+        prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet);
+    let mut last = None;
+    for _ in 0..ic_siws::siws::MAX_PENDING_MESSAGES_PER_ADDRESS {
+        ic.advance_time(Duration::from_millis(1));
+        last = Some(prepare_login_and_sign_message(
+            &ic,
+            ic_siws_provider_canister,
+            &wallet,
+        ));
+    }
+
+    // The first message has been evicted
+    let args = encode_args((
+        first_signature,
+        pubkey.clone(),
+        SESSION_KEY,
+        first_siws_message.nonce,
+    ))
+    .unwrap();
+    let response: Result<LoginDetails, String> = update(
+        &ic,
+        Principal::anonymous(),
+        ic_siws_provider_canister,
+        "siws_login",
+        args,
+    );
+    assert_eq!(response.unwrap_err(), "Message not found");
+
+    // The most recent message is still pending
+    let (last_signature, last_siws_message) = last.unwrap();
+    let args = encode_args((last_signature, pubkey, SESSION_KEY, last_siws_message.nonce)).unwrap();
+    let response: Result<LoginDetails, String> = update(
+        &ic,
+        Principal::anonymous(),
+        ic_siws_provider_canister,
+        "siws_login",
+        args,
+    );
+    assert!(response.is_ok());
+}
+
+#[test]
+fn test_sign_in_with_transaction_ok() {
+    let ic = PocketIc::new();
+    let (ic_siws_provider_canister, _) = init(&ic, None);
+    let (wallet, pubkey) = create_wallet();
+    let (transaction, siws_message) =
+        prepare_login_and_sign_transaction(&ic, ic_siws_provider_canister, &wallet);
+    let args = encode_args((
+        ByteBuf::from(transaction),
+        pubkey,
+        SESSION_KEY,
+        siws_message.nonce,
+    ))
+    .unwrap();
+    let response: Result<LoginDetails, String> = update(
+        &ic,
+        Principal::anonymous(),
+        ic_siws_provider_canister,
+        "siws_login_with_transaction",
+        args,
+    );
+    assert!(response.is_ok());
+    assert!(response.unwrap().user_canister_pubkey.len() == 62);
+}
+
+// A transaction signed by another wallet than the one the SIWS message was prepared for
+#[test]
+fn test_sign_in_with_transaction_fee_payer_mismatch() {
+    let ic = PocketIc::new();
+    let (ic_siws_provider_canister, _) = init(&ic, None);
+    let (wallet, pubkey) = create_wallet();
+    let (other_wallet, _) = create_wallet();
+    let (_, siws_message) =
+        prepare_login_and_sign_transaction(&ic, ic_siws_provider_canister, &wallet);
+    let (other_transaction, _) =
+        prepare_login_and_sign_transaction(&ic, ic_siws_provider_canister, &other_wallet);
+    let args = encode_args((
+        ByteBuf::from(other_transaction),
+        pubkey,
+        SESSION_KEY,
+        siws_message.nonce,
+    ))
+    .unwrap();
+    let response: Result<LoginDetails, String> = update(
+        &ic,
+        Principal::anonymous(),
+        ic_siws_provider_canister,
+        "siws_login_with_transaction",
+        args,
+    );
+    assert_eq!(
+        response.unwrap_err(),
+        "Transaction fee payer does not match"
+    );
+}
+
 #[test]
 fn test_sign_in_siws_get_delegation() {
     let ic = PocketIc::new();
@@ -321,12 +490,19 @@ fn test_sign_in_siws_get_delegation_timeout() {
 
     // Create wallet and session identity
     let (wallet1, pubkey1) = create_wallet();
-    let (signature, _) = prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet1);
+    let (signature, siws_message) =
+        prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet1);
     let session_identity = create_session_identity();
     let session_pubkey = session_identity.public_key().unwrap();
 
     // Login
-    let login_args = encode_args((signature, pubkey1.clone(), session_pubkey.clone())).unwrap();
+    let login_args = encode_args((
+        signature,
+        pubkey1.clone(),
+        session_pubkey.clone(),
+        siws_message.nonce,
+    ))
+    .unwrap();
     let login_response: LoginDetails = update(
         &ic,
         Principal::anonymous(),
@@ -342,12 +518,19 @@ fn test_sign_in_siws_get_delegation_timeout() {
 
     // Create another wallet and session identity
     let (wallet2, pubkey2) = create_wallet();
-    let (signature2, _) = prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet2);
+    let (signature2, siws_message2) =
+        prepare_login_and_sign_message(&ic, ic_siws_provider_canister, &wallet2);
     let session_identity2 = create_session_identity();
     let session_pubkey2 = session_identity2.public_key().unwrap();
 
     // Login pubkey 2, this should cause the delegation signature for pubkey 1 to be pruned
-    let login_args2 = encode_args((signature2, pubkey2.clone(), session_pubkey2.clone())).unwrap();
+    let login_args2 = encode_args((
+        signature2,
+        pubkey2.clone(),
+        session_pubkey2.clone(),
+        siws_message2.nonce,
+    ))
+    .unwrap();
     let _: LoginDetails = update(
         &ic,
         Principal::anonymous(),
@@ -0,0 +1,37 @@
+-- Derived analytics tables, maintained by kong_admin (--db_updates and --analytics_backfill)
+
+-- OHLCV candles per pool and interval (price in token_1 per token_0)
+CREATE TABLE pool_candles (
+    pool_id INT NOT NULL,
+    interval TEXT NOT NULL,
+    bucket TIMESTAMP NOT NULL,
+    open DOUBLE PRECISION NOT NULL,
+    high DOUBLE PRECISION NOT NULL,
+    low DOUBLE PRECISION NOT NULL,
+    close DOUBLE PRECISION NOT NULL,
+    volume_0 DOUBLE PRECISION NOT NULL,
+    volume_1 DOUBLE PRECISION NOT NULL,
+    num_swaps INT NOT NULL,
+    PRIMARY KEY (pool_id, interval, bucket)
+);
+
+-- Rolling 24h pool stats (amounts in token_1)
+CREATE TABLE pool_stats (
+    pool_id INT PRIMARY KEY,
+    volume_24h DOUBLE PRECISION NOT NULL,
+    lp_fee_24h DOUBLE PRECISION NOT NULL,
+    num_swaps_24h INT NOT NULL,
+    tvl DOUBLE PRECISION NOT NULL,
+    lp_apr DOUBLE PRECISION NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+
+-- Rolling 24h token stats (amounts in the token)
+CREATE TABLE token_stats (
+    token_id INT PRIMARY KEY,
+    volume_24h DOUBLE PRECISION NOT NULL,
+    lp_fee_24h DOUBLE PRECISION NOT NULL,
+    num_swaps_24h INT NOT NULL,
+    tvl DOUBLE PRECISION NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,270 @@
+use deadpool_postgres::Pool;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+use tokio_postgres::Client;
+use tracing::{error, info};
+
+// Market data derived from the raw swap_pool_tx and pools tables.
+//
+// - pool_candles: OHLCV candles per pool and interval. Prices are quoted in token_1 per token_0.
+// - pool_stats: rolling 24h volume, LP fees and swap count per pool, TVL and LP APR
+//   (amounts in the pool's token_1).
+// - token_stats: rolling 24h volume, LP fees and swap count per token, and TVL across pools
+//   (amounts in the token itself).
+//
+// Everything is recomputed from the raw tables (idempotent), so re-processing a db_update
+// or running the backfill again never double counts. The stats of all pools and tokens are
+// refreshed every STATS_REFRESH_INTERVAL_SECS so idle pools and tokens age out of the 24h window.
+
+/// How often the 24h stats of all pools and tokens are recomputed
+pub const STATS_REFRESH_INTERVAL_SECS: u64 = 300;
+
+/// Candle intervals maintained for every pool (label, length in seconds)
+pub const CANDLE_INTERVALS: [(&str, i64); 6] = [
+    ("1m", 60),
+    ("5m", 300),
+    ("15m", 900),
+    ("1h", 3_600),
+    ("4h", 14_400),
+    ("1d", 86_400),
+];
+
+// Swap legs of successful swaps with the executed price (token_1 per token_0) and volumes
+// in the pool's token_0 and token_1. $1 = pool_ids (NULL for all), $2..$3 = time range
+const SWAP_LEGS_CTE: &str = "
+    WITH legs AS (
+        SELECT s.id, s.pool_id, s.ts,
+            CASE WHEN s.pay_token_id = p.token_id_0 THEN s.receive_amount / NULLIF(s.pay_amount, 0)
+                ELSE s.pay_amount / NULLIF(s.receive_amount, 0) END AS price,
+            CASE WHEN s.pay_token_id = p.token_id_0 THEN s.pay_amount ELSE s.receive_amount END AS volume_0,
+            CASE WHEN s.pay_token_id = p.token_id_0 THEN s.receive_amount ELSE s.pay_amount END AS volume_1
+        FROM swap_pool_tx s
+        JOIN pools p ON p.pool_id = s.pool_id
+        JOIN txs t ON t.tx_id = s.tx_id AND t.status = 'Success'
+        WHERE ($1::INT[] IS NULL OR s.pool_id = ANY($1))
+            AND s.ts >= to_timestamp($2) AND s.ts < to_timestamp($3)
+    )";
+
+pub async fn create_analytics_tables(db_client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    db_client
+        .batch_execute(
+            "CREATE TABLE IF NOT EXISTS pool_candles (
+                pool_id INT NOT NULL,
+                interval TEXT NOT NULL,
+                bucket TIMESTAMP NOT NULL,
+                open DOUBLE PRECISION NOT NULL,
+                high DOUBLE PRECISION NOT NULL,
+                low DOUBLE PRECISION NOT NULL,
+                close DOUBLE PRECISION NOT NULL,
+                volume_0 DOUBLE PRECISION NOT NULL,
+                volume_1 DOUBLE PRECISION NOT NULL,
+                num_swaps INT NOT NULL,
+                PRIMARY KEY (pool_id, interval, bucket)
+            );
+            CREATE TABLE IF NOT EXISTS pool_stats (
+                pool_id INT PRIMARY KEY,
+                volume_24h DOUBLE PRECISION NOT NULL,
+                lp_fee_24h DOUBLE PRECISION NOT NULL,
+                num_swaps_24h INT NOT NULL,
+                tvl DOUBLE PRECISION NOT NULL,
+                lp_apr DOUBLE PRECISION NOT NULL,
+                updated_at TIMESTAMP NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS token_stats (
+                token_id INT PRIMARY KEY,
+                volume_24h DOUBLE PRECISION NOT NULL,
+                lp_fee_24h DOUBLE PRECISION NOT NULL,
+                num_swaps_24h INT NOT NULL,
+                tvl DOUBLE PRECISION NOT NULL,
+                updated_at TIMESTAMP NOT NULL
+            );",
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Start of the candle bucket containing ts (seconds since epoch)
+pub fn candle_bucket(ts: f64, interval_secs: i64) -> f64 {
+    ((ts as i64).div_euclid(interval_secs) * interval_secs) as f64
+}
+
+/// Rebuilds the candles of the given pools (all pools if None) for one interval over a time range
+async fn rebuild_candles(
+    db_client: &Client,
+    pool_ids: Option<&[i32]>,
+    interval: &str,
+    interval_secs: i64,
+    from_ts: f64,
+    to_ts: f64,
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let sql = format!(
+        "{}
+        INSERT INTO pool_candles (pool_id, interval, bucket, open, high, low, close, volume_0, volume_1, num_swaps)
+        SELECT pool_id, $4,
+            to_timestamp(floor(extract(epoch FROM ts::timestamptz)::FLOAT8 / $5::FLOAT8) * $5::FLOAT8)::timestamp AS bucket,
+            (array_agg(price ORDER BY ts, id))[1],
+            max(price),
+            min(price),
+            (array_agg(price ORDER BY ts DESC, id DESC))[1],
+            sum(volume_0),
+            sum(volume_1),
+            count(*)::INT
+        FROM legs
+        WHERE price IS NOT NULL
+        GROUP BY pool_id, bucket
+        ON CONFLICT (pool_id, interval, bucket) DO UPDATE SET
+            open = EXCLUDED.open,
+            high = EXCLUDED.high,
+            low = EXCLUDED.low,
+            close = EXCLUDED.close,
+            volume_0 = EXCLUDED.volume_0,
+            volume_1 = EXCLUDED.volume_1,
+            num_swaps = EXCLUDED.num_swaps",
+        SWAP_LEGS_CTE
+    );
+
+    let interval_secs = interval_secs as f64;
+    let rows = db_client
+        .execute(&sql, &[&pool_ids, &from_ts, &to_ts, &interval, &interval_secs])
+        .await?;
+
+    Ok(rows)
+}
+
+/// Updates the candles of the swapped pools, given with the time range (seconds since epoch) of their swaps.
+/// One query per interval covers all the pools
+pub async fn update_pool_candles(db_client: &Client, swapped_pools: &BTreeMap<u32, (f64, f64)>) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(from_ts) = swapped_pools.values().map(|(from_ts, _)| *from_ts).reduce(f64::min) else {
+        return Ok(());
+    };
+    let to_ts = swapped_pools.values().map(|(_, to_ts)| *to_ts).fold(from_ts, f64::max);
+    let pool_ids: Vec<i32> = swapped_pools.keys().map(|id| *id as i32).collect();
+    for (interval, interval_secs) in CANDLE_INTERVALS {
+        let from_bucket = candle_bucket(from_ts, interval_secs);
+        let to_bucket = candle_bucket(to_ts, interval_secs) + interval_secs as f64;
+        rebuild_candles(db_client, Some(&pool_ids), interval, interval_secs, from_bucket, to_bucket).await?;
+    }
+
+    Ok(())
+}
+
+/// Recomputes the 24h stats, TVL and LP APR of the given pools (all pools if None)
+pub async fn update_pool_stats(db_client: &Client, pool_ids: Option<&BTreeSet<u32>>) -> Result<(), Box<dyn std::error::Error>> {
+    let pool_ids = pool_ids.map(|ids| ids.iter().map(|id| *id as i32).collect::<Vec<i32>>());
+    db_client
+        .execute(
+            "INSERT INTO pool_stats (pool_id, volume_24h, lp_fee_24h, num_swaps_24h, tvl, lp_apr, updated_at)
+            SELECT p.pool_id, v.volume_1, v.lp_fee_1, v.num_swaps, tvl.amount,
+                CASE WHEN tvl.amount > 0 THEN v.lp_fee_1 * 365 / tvl.amount * 100 ELSE 0 END,
+                LOCALTIMESTAMP
+            FROM pools p
+            CROSS JOIN LATERAL (
+                SELECT
+                    COALESCE(sum(CASE WHEN s.pay_token_id = p.token_id_0 THEN s.receive_amount ELSE s.pay_amount END), 0) AS volume_1,
+                    -- lp_fee is charged in the receive token
+                    COALESCE(sum(CASE WHEN s.receive_token_id = p.token_id_1 THEN s.lp_fee
+                        ELSE s.lp_fee * p.balance_1 / NULLIF(p.balance_0, 0) END), 0) AS lp_fee_1,
+                    count(s.id)::INT AS num_swaps
+                FROM swap_pool_tx s
+                JOIN txs t ON t.tx_id = s.tx_id AND t.status = 'Success'
+                WHERE s.pool_id = p.pool_id AND s.ts >= LOCALTIMESTAMP - INTERVAL '24 hours'
+            ) v
+            -- token_0 side valued at the pool's spot price
+            CROSS JOIN LATERAL (
+                SELECT CASE WHEN p.balance_0 > 0 THEN 2 * p.balance_1 ELSE p.balance_1 END AS amount
+            ) tvl
+            WHERE $1::INT[] IS NULL OR p.pool_id = ANY($1)
+            ON CONFLICT (pool_id) DO UPDATE SET
+                volume_24h = EXCLUDED.volume_24h,
+                lp_fee_24h = EXCLUDED.lp_fee_24h,
+                num_swaps_24h = EXCLUDED.num_swaps_24h,
+                tvl = EXCLUDED.tvl,
+                lp_apr = EXCLUDED.lp_apr,
+                updated_at = EXCLUDED.updated_at",
+            &[&pool_ids],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Recomputes the 24h stats and TVL of the given tokens (all tokens if None)
+pub async fn update_token_stats(db_client: &Client, token_ids: Option<&BTreeSet<u32>>) -> Result<(), Box<dyn std::error::Error>> {
+    let token_ids = token_ids.map(|ids| ids.iter().map(|id| *id as i32).collect::<Vec<i32>>());
+    db_client
+        .execute(
+            "INSERT INTO token_stats (token_id, volume_24h, lp_fee_24h, num_swaps_24h, tvl, updated_at)
+            SELECT tk.token_id, v.volume, v.lp_fee, v.num_swaps, tvl.amount, LOCALTIMESTAMP
+            FROM tokens tk
+            CROSS JOIN LATERAL (
+                SELECT
+                    COALESCE(sum(CASE WHEN s.pay_token_id = tk.token_id THEN s.pay_amount ELSE s.receive_amount END), 0) AS volume,
+                    COALESCE(sum(CASE WHEN s.receive_token_id = tk.token_id THEN s.lp_fee ELSE 0 END), 0) AS lp_fee,
+                    count(s.id)::INT AS num_swaps
+                FROM swap_pool_tx s
+                JOIN txs t ON t.tx_id = s.tx_id AND t.status = 'Success'
+                WHERE (s.pay_token_id = tk.token_id OR s.receive_token_id = tk.token_id)
+                    AND s.ts >= LOCALTIMESTAMP - INTERVAL '24 hours'
+            ) v
+            CROSS JOIN LATERAL (
+                SELECT COALESCE(sum(CASE WHEN p.token_id_0 = tk.token_id THEN p.balance_0 ELSE p.balance_1 END), 0) AS amount
+                FROM pools p
+                WHERE NOT p.is_removed AND (p.token_id_0 = tk.token_id OR p.token_id_1 = tk.token_id)
+            ) tvl
+            WHERE tk.token_type = 'IC' AND ($1::INT[] IS NULL OR tk.token_id = ANY($1))
+            ON CONFLICT (token_id) DO UPDATE SET
+                volume_24h = EXCLUDED.volume_24h,
+                lp_fee_24h = EXCLUDED.lp_fee_24h,
+                num_swaps_24h = EXCLUDED.num_swaps_24h,
+                tvl = EXCLUDED.tvl,
+                updated_at = EXCLUDED.updated_at",
+            &[&token_ids],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Rebuilds all candles and stats from the existing swap history
+pub async fn backfill_analytics(db_client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    create_analytics_tables(db_client).await?;
+
+    for (interval, interval_secs) in CANDLE_INTERVALS {
+        let start = std::time::Instant::now();
+        let rows = rebuild_candles(db_client, None, interval, interval_secs, 0.0, f64::INFINITY).await?;
+        info!("Backfilled {} {} candles in {:?}", rows, interval, start.elapsed());
+    }
+
+    update_pool_stats(db_client, None).await?;
+    update_token_stats(db_client, None).await?;
+    info!("Backfilled pool and token stats");
+
+    Ok(())
+}
+
+/// Recomputes the stats of all pools and tokens every STATS_REFRESH_INTERVAL_SECS, as db_updates
+/// only refresh the pools and tokens they touch
+pub async fn refresh_stats_periodically(pool: Pool) {
+    let mut interval = tokio::time::interval(Duration::from_secs(STATS_REFRESH_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        let db_client = match pool.get().await {
+            Ok(db_client) => db_client,
+            Err(e) => {
+                error!("Stats refresh failed to get a database connection: {}", e);
+                continue;
+            }
+        };
+        if let Err(e) = refresh_all_stats(&db_client).await {
+            error!("Stats refresh failed: {}", e);
+        }
+    }
+}
+
+async fn refresh_all_stats(db_client: &Client) -> Result<(), String> {
+    create_analytics_tables(db_client).await.map_err(|e| e.to_string())?;
+    update_pool_stats(db_client, None).await.map_err(|e| e.to_string())?;
+    update_token_stats(db_client, None).await.map_err(|e| e.to_string())?;
+    Ok(())
+}
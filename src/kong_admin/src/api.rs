@@ -0,0 +1,452 @@
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use deadpool_postgres::Pool;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio_postgres::Row;
+use tracing::{error, info};
+
+use crate::analytics::CANDLE_INTERVALS;
+
+// Read-only REST API over the synced database (--api)
+//
+// All routes are versioned under /api/v1. Timestamps are seconds since epoch, amounts are
+// in token units (as stored in the database). The OpenAPI spec is served at /api/v1/openapi.json.
+
+const OPENAPI_SPEC: &str = include_str!("../openapi.json");
+
+const DEFAULT_PAGE_LIMIT: i64 = 50;
+const MAX_PAGE_LIMIT: i64 = 500;
+const DEFAULT_CANDLE_LIMIT: i64 = 500;
+const MAX_CANDLE_LIMIT: i64 = 5_000;
+
+pub enum ApiError {
+    NotFound(String),
+    BadRequest(String),
+    Internal(String),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            ApiError::Internal(message) => {
+                error!("API error: {}", message);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+            }
+        };
+        (status, Json(json!({ "error": message }))).into_response()
+    }
+}
+
+impl From<deadpool_postgres::PoolError> for ApiError {
+    fn from(e: deadpool_postgres::PoolError) -> Self {
+        ApiError::Internal(e.to_string())
+    }
+}
+
+impl From<tokio_postgres::Error> for ApiError {
+    fn from(e: tokio_postgres::Error) -> Self {
+        ApiError::Internal(e.to_string())
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+/// A page of results. next_cursor is None on the last page
+#[derive(Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct PageParams {
+    pub cursor: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct CandleParams {
+    pub interval: Option<String>,
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct TokenReply {
+    pub token_id: i32,
+    pub token_type: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub canister_id: Option<String>,
+    pub address: Option<String>,
+    pub decimals: i16,
+    pub fee: Option<f64>,
+    pub is_removed: bool,
+    pub volume_24h: Option<f64>,
+    pub lp_fee_24h: Option<f64>,
+    pub num_swaps_24h: Option<i32>,
+    pub tvl: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct PoolReply {
+    pub pool_id: i32,
+    pub symbol: String,
+    pub token_id_0: i32,
+    pub symbol_0: Option<String>,
+    pub balance_0: f64,
+    pub token_id_1: i32,
+    pub symbol_1: Option<String>,
+    pub balance_1: f64,
+    pub price: Option<f64>,
+    pub lp_fee_bps: i16,
+    pub lp_token_id: i32,
+    pub is_removed: bool,
+    pub volume_24h: Option<f64>,
+    pub lp_fee_24h: Option<f64>,
+    pub num_swaps_24h: Option<i32>,
+    pub tvl: Option<f64>,
+    pub lp_apr: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct TxReply {
+    pub tx_id: i64,
+    pub request_id: i64,
+    pub user_id: i32,
+    pub tx_type: String,
+    pub status: String,
+    pub ts: i64,
+    pub tx: Value,
+}
+
+#[derive(Serialize)]
+pub struct CandleReply {
+    pub ts: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume_0: f64,
+    pub volume_1: f64,
+    pub num_swaps: i32,
+}
+
+#[derive(Serialize)]
+pub struct LPPositionReply {
+    pub pool_id: i32,
+    pub symbol: String,
+    pub lp_token_id: i32,
+    pub lp_token_amount: f64,
+    pub share: f64,
+    pub token_id_0: i32,
+    pub amount_0: f64,
+    pub token_id_1: i32,
+    pub amount_1: f64,
+    pub ts: i64,
+}
+
+const TOKEN_SELECT: &str = "
+    SELECT tk.token_id, tk.token_type::TEXT, tk.name, tk.symbol, tk.canister_id, tk.address, tk.decimals, tk.fee,
+        tk.is_removed, ts.volume_24h, ts.lp_fee_24h, ts.num_swaps_24h, ts.tvl
+    FROM tokens tk
+    LEFT JOIN token_stats ts ON ts.token_id = tk.token_id";
+
+const POOL_SELECT: &str = "
+    SELECT p.pool_id, t0.symbol, t1.symbol, p.token_id_0, p.balance_0, p.token_id_1, p.balance_1,
+        p.lp_fee_bps, p.lp_token_id, COALESCE(p.is_removed, false),
+        ps.volume_24h, ps.lp_fee_24h, ps.num_swaps_24h, ps.tvl, ps.lp_apr
+    FROM pools p
+    LEFT JOIN tokens t0 ON t0.token_id = p.token_id_0
+    LEFT JOIN tokens t1 ON t1.token_id = p.token_id_1
+    LEFT JOIN pool_stats ps ON ps.pool_id = p.pool_id";
+
+// $1 = cursor, $2 = limit, $3 = filter value
+const TX_SELECT: &str = "
+    SELECT t.tx_id, t.request_id, t.user_id, t.tx_type::TEXT, t.status::TEXT,
+        EXTRACT(EPOCH FROM t.ts)::BIGINT, t.raw_json
+    FROM txs t
+    WHERE ($1::BIGINT IS NULL OR t.tx_id < $1)";
+
+const TX_BY_USER: &str = "t.user_id = (SELECT user_id FROM users WHERE principal_id = $3)";
+
+const TX_BY_POOL: &str = "t.tx_id IN (
+        SELECT tx_id FROM swap_pool_tx WHERE pool_id = $3
+        UNION SELECT tx_id FROM add_pool_tx WHERE pool_id = $3
+        UNION SELECT tx_id FROM add_liquidity_tx WHERE pool_id = $3
+        UNION SELECT tx_id FROM remove_liquidity_tx WHERE pool_id = $3
+    )";
+
+const TX_BY_TOKEN: &str = "t.tx_id IN (
+        SELECT tx_id FROM swap_pool_tx WHERE pay_token_id = $3 OR receive_token_id = $3
+        UNION SELECT tx_id FROM send_tx WHERE token_id = $3
+        UNION SELECT a.tx_id FROM add_pool_tx a JOIN pools p ON p.pool_id = a.pool_id WHERE p.token_id_0 = $3 OR p.token_id_1 = $3
+        UNION SELECT a.tx_id FROM add_liquidity_tx a JOIN pools p ON p.pool_id = a.pool_id WHERE p.token_id_0 = $3 OR p.token_id_1 = $3
+        UNION SELECT r.tx_id FROM remove_liquidity_tx r JOIN pools p ON p.pool_id = r.pool_id WHERE p.token_id_0 = $3 OR p.token_id_1 = $3
+    )";
+
+pub async fn serve_api(pool: Pool, bind_address: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let app = Router::new()
+        .route("/api/v1/openapi.json", get(get_openapi))
+        .route("/api/v1/tokens", get(get_tokens))
+        .route("/api/v1/tokens/:token_id", get(get_token))
+        .route("/api/v1/tokens/:token_id/txs", get(get_token_txs))
+        .route("/api/v1/pools", get(get_pools))
+        .route("/api/v1/pools/:pool_id", get(get_pool))
+        .route("/api/v1/pools/:pool_id/txs", get(get_pool_txs))
+        .route("/api/v1/pools/:pool_id/candles", get(get_pool_candles))
+        .route("/api/v1/users/:principal_id/txs", get(get_user_txs))
+        .route("/api/v1/users/:principal_id/lp_positions", get(get_user_lp_positions))
+        .with_state(pool);
+
+    let listener = tokio::net::TcpListener::bind(&bind_address).await?;
+    info!("API server listening on {}", bind_address);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    info!("API server stopped");
+
+    Ok(())
+}
+
+async fn get_openapi() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/json")], OPENAPI_SPEC)
+}
+
+async fn get_tokens(State(pool): State<Pool>) -> ApiResult<Vec<TokenReply>> {
+    let db_client = pool.get().await?;
+    let rows = db_client.query(&format!("{} ORDER BY tk.token_id", TOKEN_SELECT), &[]).await?;
+    Ok(Json(rows.iter().map(to_token_reply).collect()))
+}
+
+async fn get_token(State(pool): State<Pool>, Path(token_id): Path<i32>) -> ApiResult<TokenReply> {
+    let db_client = pool.get().await?;
+    let row = db_client
+        .query_opt(&format!("{} WHERE tk.token_id = $1", TOKEN_SELECT), &[&token_id])
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("token_id={} not found", token_id)))?;
+    Ok(Json(to_token_reply(&row)))
+}
+
+async fn get_pools(State(pool): State<Pool>) -> ApiResult<Vec<PoolReply>> {
+    let db_client = pool.get().await?;
+    let rows = db_client.query(&format!("{} ORDER BY p.pool_id", POOL_SELECT), &[]).await?;
+    Ok(Json(rows.iter().map(to_pool_reply).collect()))
+}
+
+async fn get_pool(State(pool): State<Pool>, Path(pool_id): Path<i32>) -> ApiResult<PoolReply> {
+    let db_client = pool.get().await?;
+    let row = db_client
+        .query_opt(&format!("{} WHERE p.pool_id = $1", POOL_SELECT), &[&pool_id])
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("pool_id={} not found", pool_id)))?;
+    Ok(Json(to_pool_reply(&row)))
+}
+
+async fn get_user_txs(
+    State(pool): State<Pool>,
+    Path(principal_id): Path<String>,
+    Query(params): Query<PageParams>,
+) -> ApiResult<Page<TxReply>> {
+    query_txs(&pool, TX_BY_USER, &principal_id, &params).await
+}
+
+async fn get_pool_txs(State(pool): State<Pool>, Path(pool_id): Path<i32>, Query(params): Query<PageParams>) -> ApiResult<Page<TxReply>> {
+    query_txs(&pool, TX_BY_POOL, &pool_id, &params).await
+}
+
+async fn get_token_txs(State(pool): State<Pool>, Path(token_id): Path<i32>, Query(params): Query<PageParams>) -> ApiResult<Page<TxReply>> {
+    query_txs(&pool, TX_BY_TOKEN, &token_id, &params).await
+}
+
+/// Txs matching a filter, newest first. The cursor is the tx_id to continue below
+async fn query_txs(
+    pool: &Pool,
+    filter: &str,
+    value: &(dyn tokio_postgres::types::ToSql + Sync),
+    params: &PageParams,
+) -> ApiResult<Page<TxReply>> {
+    let limit = page_limit(params.limit, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT)?;
+    let db_client = pool.get().await?;
+    let sql = format!("{} AND {} ORDER BY t.tx_id DESC LIMIT $2", TX_SELECT, filter);
+    let rows = db_client.query(&sql, &[&params.cursor, &limit, value]).await?;
+
+    let items: Vec<TxReply> = rows
+        .iter()
+        .map(|row| TxReply {
+            tx_id: row.get(0),
+            request_id: row.get(1),
+            user_id: row.get(2),
+            tx_type: row.get(3),
+            status: row.get(4),
+            ts: row.get(5),
+            tx: row.get(6),
+        })
+        .collect();
+    let next_cursor = if items.len() as i64 == limit {
+        items.last().map(|tx| tx.tx_id)
+    } else {
+        None
+    };
+
+    Ok(Json(Page { items, next_cursor }))
+}
+
+async fn get_pool_candles(
+    State(pool): State<Pool>,
+    Path(pool_id): Path<i32>,
+    Query(params): Query<CandleParams>,
+) -> ApiResult<Vec<CandleReply>> {
+    let interval = params.interval.unwrap_or_else(|| "1h".to_string());
+    if !CANDLE_INTERVALS.iter().any(|(label, _)| *label == interval) {
+        let intervals = CANDLE_INTERVALS.iter().map(|(label, _)| *label).collect::<Vec<_>>().join(", ");
+        return Err(ApiError::BadRequest(format!("interval must be one of: {}", intervals)));
+    }
+    let limit = page_limit(params.limit, DEFAULT_CANDLE_LIMIT, MAX_CANDLE_LIMIT)?;
+    let from = params.from.map(|ts| ts as f64);
+    let to = params.to.map(|ts| ts as f64);
+
+    let db_client = pool.get().await?;
+    // latest candles first, then returned in chronological order
+    let rows = db_client
+        .query(
+            "SELECT * FROM (
+                SELECT EXTRACT(EPOCH FROM bucket)::BIGINT AS ts, open, high, low, close, volume_0, volume_1, num_swaps
+                FROM pool_candles
+                WHERE pool_id = $1 AND interval = $2
+                    AND ($3::FLOAT8 IS NULL OR bucket >= to_timestamp($3))
+                    AND ($4::FLOAT8 IS NULL OR bucket < to_timestamp($4))
+                ORDER BY bucket DESC
+                LIMIT $5
+            ) c ORDER BY ts",
+            &[&pool_id, &interval, &from, &to, &limit],
+        )
+        .await?;
+
+    Ok(Json(
+        rows.iter()
+            .map(|row| CandleReply {
+                ts: row.get(0),
+                open: row.get(1),
+                high: row.get(2),
+                low: row.get(3),
+                close: row.get(4),
+                volume_0: row.get(5),
+                volume_1: row.get(6),
+                num_swaps: row.get(7),
+            })
+            .collect(),
+    ))
+}
+
+async fn get_user_lp_positions(State(pool): State<Pool>, Path(principal_id): Path<String>) -> ApiResult<Vec<LPPositionReply>> {
+    let db_client = pool.get().await?;
+    // the pool share of a position is its amount over the LP token's total supply held by users
+    let rows = db_client
+        .query(
+            "SELECT p.pool_id, t0.symbol, t1.symbol, p.lp_token_id, lp.amount, supply.total,
+                p.token_id_0, p.balance_0, p.token_id_1, p.balance_1, EXTRACT(EPOCH FROM lp.ts)::BIGINT
+            FROM lp_tokens lp
+            JOIN users u ON u.user_id = lp.user_id
+            JOIN pools p ON p.lp_token_id = lp.token_id
+            LEFT JOIN tokens t0 ON t0.token_id = p.token_id_0
+            LEFT JOIN tokens t1 ON t1.token_id = p.token_id_1
+            CROSS JOIN LATERAL (SELECT sum(amount) AS total FROM lp_tokens WHERE token_id = lp.token_id) supply
+            WHERE u.principal_id = $1 AND lp.amount > 0
+            ORDER BY p.pool_id",
+            &[&principal_id],
+        )
+        .await?;
+
+    Ok(Json(
+        rows.iter()
+            .map(|row| {
+                let lp_token_amount: f64 = row.get(4);
+                let total: Option<f64> = row.get(5);
+                let share = match total {
+                    Some(total) if total > 0.0 => lp_token_amount / total,
+                    _ => 0.0,
+                };
+                let balance_0: f64 = row.get(7);
+                let balance_1: f64 = row.get(9);
+                LPPositionReply {
+                    pool_id: row.get(0),
+                    symbol: pool_symbol(row.get(1), row.get(2)),
+                    lp_token_id: row.get(3),
+                    lp_token_amount,
+                    share,
+                    token_id_0: row.get(6),
+                    amount_0: balance_0 * share,
+                    token_id_1: row.get(8),
+                    amount_1: balance_1 * share,
+                    ts: row.get(10),
+                }
+            })
+            .collect(),
+    ))
+}
+
+fn page_limit(limit: Option<i64>, default: i64, max: i64) -> Result<i64, ApiError> {
+    match limit {
+        None => Ok(default),
+        Some(limit) if limit > 0 && limit <= max => Ok(limit),
+        Some(_) => Err(ApiError::BadRequest(format!("limit must be between 1 and {}", max))),
+    }
+}
+
+fn pool_symbol(symbol_0: Option<String>, symbol_1: Option<String>) -> String {
+    format!("{}_{}", symbol_0.unwrap_or_default(), symbol_1.unwrap_or_default())
+}
+
+fn to_token_reply(row: &Row) -> TokenReply {
+    TokenReply {
+        token_id: row.get(0),
+        token_type: row.get(1),
+        name: row.get(2),
+        symbol: row.get(3),
+        canister_id: row.get(4),
+        address: row.get(5),
+        decimals: row.get(6),
+        fee: row.get(7),
+        is_removed: row.get(8),
+        volume_24h: row.get(9),
+        lp_fee_24h: row.get(10),
+        num_swaps_24h: row.get(11),
+        tvl: row.get(12),
+    }
+}
+
+fn to_pool_reply(row: &Row) -> PoolReply {
+    let symbol_0: Option<String> = row.get(1);
+    let symbol_1: Option<String> = row.get(2);
+    let balance_0: f64 = row.get(4);
+    let balance_1: f64 = row.get(6);
+    PoolReply {
+        pool_id: row.get(0),
+        symbol: pool_symbol(symbol_0.clone(), symbol_1.clone()),
+        token_id_0: row.get(3),
+        symbol_0,
+        balance_0,
+        token_id_1: row.get(5),
+        symbol_1,
+        balance_1,
+        price: if balance_0 > 0.0 { Some(balance_1 / balance_0) } else { None },
+        lp_fee_bps: row.get(7),
+        lp_token_id: row.get(8),
+        is_removed: row.get(9),
+        volume_24h: row.get(10),
+        lp_fee_24h: row.get(11),
+        num_swaps_24h: row.get(12),
+        tvl: row.get(13),
+        lp_apr: row.get(14),
+    }
+}
@@ -1,97 +1,166 @@
-use chrono::Local;
-use kong_lib::stable_db_update::stable_db_update::{StableDBUpdate, StableMemory};
-use kong_lib::stable_token::token::Token;
-use std::collections::BTreeMap;
-use tokio_postgres::Client;
-
-use crate::claims::insert_claim_on_database;
-use crate::lp_tokens::insert_lp_token_on_database;
-use crate::pools::{insert_pool_on_database, load_pools_from_database, query_pool_token_ids};
-use crate::requests::insert_request_on_database;
-use crate::tokens::{insert_token_on_database, load_tokens_from_database, query_token_decimals};
-use crate::transfers::insert_transfer_on_database;
-use crate::txs::insert_tx_on_database;
-use crate::users::insert_user_on_database;
+use kong_lib::stable_db_update::stable_db_update::StableDBUpdate;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::{error, info, warn};
+
+use crate::sinks::{DBUpdateBatch, Sink};
 
 use super::kong_data::KongData;
 
+const MAX_RETRY_DELAY_SECS: u64 = 300; // 5 minutes max
+const OPERATION_TIMEOUT_SECS: u64 = 300;
+
+/// Fetches the db_updates after last_db_update_id from kong_data
+///
+/// Returns None if there are no new db_updates.
 pub async fn get_db_updates(
     last_db_update_id: Option<u64>,
     kong_data: &KongData,
-    db_client: &Client,
-    tokens_map: &mut BTreeMap<u32, u8>,
-    pools_map: &mut BTreeMap<u32, (u32, u32)>,
-) -> Result<u64, Box<dyn std::error::Error>> {
+) -> Result<Option<DBUpdateBatch>, Box<dyn std::error::Error>> {
     // start from last_db_update_id + 1 as last_db_update is already processed
     let db_update_id = last_db_update_id.map(|id| id + 1);
     let json = kong_data.backup_db_updates(db_update_id).await?;
     let db_updates: Vec<StableDBUpdate> = serde_json::from_str(&json)?;
-    if db_updates.is_empty() {
-        return Ok(last_db_update_id.unwrap_or(0));
+    Ok(to_batch(last_db_update_id, db_updates))
+}
+
+/// Collects the db_updates after last_db_update_id into a batch
+///
+/// db_update_ids are sequential from 1, so a jump in the sequence means kong_data no longer has the
+/// updates in between; these are reported as gaps. A first sync (no last_db_update_id) starts from 0
+/// so updates kong_data dropped before the first fetched one are reported too.
+fn to_batch(last_db_update_id: Option<u64>, db_updates: Vec<StableDBUpdate>) -> Option<DBUpdateBatch> {
+    let mut batch = DBUpdateBatch {
+        db_updates: Vec::new(),
+        gaps: Vec::new(),
+        last_db_update_id: last_db_update_id.unwrap_or(0),
+    };
+    for db_update in db_updates {
+        // already processed
+        if db_update.db_update_id <= batch.last_db_update_id {
+            continue;
+        }
+        if db_update.db_update_id > batch.last_db_update_id + 1 {
+            let gap = (batch.last_db_update_id + 1, db_update.db_update_id - 1);
+            warn!("Gap detected: db_update_id={}..={} missing", gap.0, gap.1);
+            batch.gaps.push(gap);
+        }
+        batch.last_db_update_id = db_update.db_update_id;
+        batch.db_updates.push(db_update);
+    }
+
+    if batch.db_updates.is_empty() {
+        return None;
     }
+    Some(batch)
+}
 
-    let current_time = Local::now();
-    let formatted_time = current_time.format("%Y-%m-%d %H:%M:%S").to_string();
-    println!("\n--- processing db_update_id={:?} @ {} ---", db_update_id, formatted_time);
-
-    let mut last_update_id = last_db_update_id.unwrap_or(0);
-    for db_update in db_updates.iter() {
-        last_update_id = db_update.db_update_id;
-        let stable_memory = &db_update.stable_memory;
-        match stable_memory {
-            StableMemory::KongSettings(_) => (),
-            StableMemory::UserMap(user) => insert_user_on_database(user, db_client)
-                .await
-                .unwrap_or_else(|e| eprintln!("{}", e)),
-            StableMemory::TokenMap(token) => match insert_token_on_database(token, db_client).await {
-                Ok(()) => {
-                    if !tokens_map.contains_key(&token.token_id()) {
-                        match query_token_decimals(db_client, token.token_id()).await {
-                            Ok(decimals) => {
-                                tokens_map.insert(token.token_id(), decimals);
-                            }
-                            Err(e) => eprintln!("Failed to query token decimals: {}", e),
+/// Polls kong_data for db_updates and writes them to the sink until Ctrl+C
+pub async fn run_db_updates<S: Sink>(sink: &mut S, kong_data: &KongData, base_delay_secs: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let mut retry_delay_secs = base_delay_secs;
+
+    // Load last sync point from the sink, or start from beginning.
+    // Sinks advance their sync point together with each batch, so it is always exact
+    let mut last_db_update_id = sink.load_sync_state().await?;
+    if let Some(id) = last_db_update_id {
+        info!("Resuming {} sink from last sync point: db_update_id={}", sink.name(), id);
+    } else {
+        info!("Starting fresh sync into {} sink (no previous state found)", sink.name());
+    }
+
+    // loop forever and update the sink
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                // an interrupted batch is discarded and written again on restart
+                info!("Shutdown signal received, gracefully stopping db_updates loop");
+                break;
+            }
+            result = timeout(Duration::from_secs(OPERATION_TIMEOUT_SECS), async {
+                let Some(batch) = get_db_updates(last_db_update_id, kong_data).await? else {
+                    return Ok::<_, Box<dyn std::error::Error>>(None);
+                };
+                info!(
+                    "Processing db_update_id={}..={} ({} records)",
+                    batch.db_updates[0].db_update_id,
+                    batch.last_db_update_id,
+                    batch.db_updates.len()
+                );
+                sink.write_batch(&batch).await?;
+                Ok(Some(batch.last_db_update_id))
+            }) => {
+                match result {
+                    Ok(Ok(db_update_id)) => {
+                        if let Some(db_update_id) = db_update_id {
+                            last_db_update_id = Some(db_update_id);
+                            info!("DB update successful, last_id: {}", db_update_id);
                         }
+                        retry_delay_secs = base_delay_secs; // Reset delay on success
                     }
-                }
-                Err(e) => eprintln!("{}", e),
-            },
-            StableMemory::PoolMap(pool) => match insert_pool_on_database(pool, db_client, tokens_map).await {
-                Ok(()) => {
-                    if !pools_map.contains_key(&pool.pool_id) {
-                        match query_pool_token_ids(db_client, pool.pool_id).await {
-                            Ok(token_ids) => {
-                                pools_map.insert(pool.pool_id, token_ids);
-                            }
-                            Err(e) => eprintln!("Failed to query pool token IDs: {}", e),
-                        }
+                    Ok(Err(err)) => {
+                        error!("DB update failed: {}", err);
+                        retry_delay_secs = (retry_delay_secs * 2).min(MAX_RETRY_DELAY_SECS);
+                        warn!("Retrying in {}s (exponential backoff)", retry_delay_secs);
+                    }
+                    Err(_) => {
+                        error!("DB update timed out after {}s", OPERATION_TIMEOUT_SECS);
+                        retry_delay_secs = (retry_delay_secs * 2).min(MAX_RETRY_DELAY_SECS);
+                        warn!("Retrying in {}s (exponential backoff)", retry_delay_secs);
                     }
                 }
-                Err(e) => eprintln!("{}", e),
-            },
-            StableMemory::TxMap(tx) => insert_tx_on_database(tx, db_client, tokens_map, pools_map)
-                .await
-                .unwrap_or_else(|e| eprintln!("{}", e)),
-            StableMemory::RequestMap(request) => insert_request_on_database(request, db_client)
-                .await
-                .unwrap_or_else(|e| eprintln!("{}", e)),
-            StableMemory::TransferMap(transfer) => insert_transfer_on_database(transfer, db_client, tokens_map)
-                .await
-                .unwrap_or_else(|e| eprintln!("{}", e)),
-            StableMemory::ClaimMap(claim) => insert_claim_on_database(claim, db_client, tokens_map)
-                .await
-                .unwrap_or_else(|e| eprintln!("{}", e)),
-            StableMemory::LPTokenMap(lptoken) => insert_lp_token_on_database(lptoken, db_client, tokens_map)
-                .await
-                .unwrap_or_else(|e| eprintln!("{}", e)),
+
+                // Async sleep instead of blocking thread::sleep
+                tokio::time::sleep(Duration::from_secs(retry_delay_secs)).await;
+            }
         }
     }
 
-    println!(
-        "--- processed db_update_id={} - {} records updated ---",
-        last_update_id,
-        db_updates.len()
-    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use kong_lib::stable_db_update::stable_db_update::StableMemory;
+    use kong_lib::stable_kong_settings::stable_kong_settings::StableKongSettings;
+
+    use super::*;
+
+    fn db_updates(db_update_ids: &[u64]) -> Vec<StableDBUpdate> {
+        db_update_ids
+            .iter()
+            .map(|&db_update_id| StableDBUpdate {
+                db_update_id,
+                stable_memory: StableMemory::KongSettings(StableKongSettings::default()),
+                ts: 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_to_batch_skips_processed_db_updates() {
+        let batch = to_batch(Some(3), db_updates(&[2, 3, 4, 5])).unwrap();
+        assert_eq!(batch.db_updates.iter().map(|u| u.db_update_id).collect::<Vec<_>>(), vec![4, 5]);
+        assert!(batch.gaps.is_empty());
+        assert_eq!(batch.last_db_update_id, 5);
+
+        assert!(to_batch(Some(5), db_updates(&[4, 5])).is_none());
+    }
 
-    Ok(last_update_id)
+    #[test]
+    fn test_to_batch_detects_gaps() {
+        let batch = to_batch(Some(3), db_updates(&[6, 7, 10])).unwrap();
+        assert_eq!(batch.gaps, vec![(4, 5), (8, 9)]);
+        assert_eq!(batch.last_db_update_id, 10);
+    }
+
+    #[test]
+    fn test_to_batch_detects_gap_on_first_sync() {
+        let batch = to_batch(None, db_updates(&[1, 2])).unwrap();
+        assert!(batch.gaps.is_empty());
+
+        // kong_data already dropped db_update_ids 1..=4
+        let batch = to_batch(None, db_updates(&[5, 6])).unwrap();
+        assert_eq!(batch.gaps, vec![(1, 4)]);
+        assert_eq!(batch.last_db_update_id, 6);
+    }
 }
@@ -36,6 +36,66 @@ impl KongBackend {
         let call_result = Decode!(result.as_slice(), Result<String, String>)?;
         call_result.map_err(|e| anyhow::anyhow!(e))
     }
+
+    pub async fn backup_kong_settings(&self) -> Result<String> {
+        let result = self
+            .agent
+            .query(&self.canister_id, "backup_kong_settings")
+            .with_arg(Encode!()?)
+            .await?;
+        let call_result = Decode!(result.as_slice(), Result<String, String>)?;
+        call_result.map_err(|e| anyhow::anyhow!(e))
+    }
+
+    pub async fn backup_users(&self, user_id: Option<u32>, num_users: Option<u16>) -> Result<String> {
+        let result = self
+            .agent
+            .query(&self.canister_id, "backup_users")
+            .with_arg(Encode!(&user_id, &num_users)?)
+            .await?;
+        let call_result = Decode!(result.as_slice(), Result<String, String>)?;
+        call_result.map_err(|e| anyhow::anyhow!(e))
+    }
+
+    pub async fn backup_tokens(&self, token_id: Option<u32>, num_tokens: Option<u16>) -> Result<String> {
+        let result = self
+            .agent
+            .query(&self.canister_id, "backup_tokens")
+            .with_arg(Encode!(&token_id, &num_tokens)?)
+            .await?;
+        let call_result = Decode!(result.as_slice(), Result<String, String>)?;
+        call_result.map_err(|e| anyhow::anyhow!(e))
+    }
+
+    pub async fn backup_pools(&self, pool_id: Option<u32>, num_pools: Option<u16>) -> Result<String> {
+        let result = self
+            .agent
+            .query(&self.canister_id, "backup_pools")
+            .with_arg(Encode!(&pool_id, &num_pools)?)
+            .await?;
+        let call_result = Decode!(result.as_slice(), Result<String, String>)?;
+        call_result.map_err(|e| anyhow::anyhow!(e))
+    }
+
+    pub async fn backup_lp_tokens(&self, lp_token_id: Option<u64>, num_lp_tokens: Option<u16>) -> Result<String> {
+        let result = self
+            .agent
+            .query(&self.canister_id, "backup_lp_tokens")
+            .with_arg(Encode!(&lp_token_id, &num_lp_tokens)?)
+            .await?;
+        let call_result = Decode!(result.as_slice(), Result<String, String>)?;
+        call_result.map_err(|e| anyhow::anyhow!(e))
+    }
+
+    pub async fn backup_claims(&self, claim_id: Option<u64>, num_claims: Option<u16>) -> Result<String> {
+        let result = self
+            .agent
+            .query(&self.canister_id, "backup_claims")
+            .with_arg(Encode!(&claim_id, &num_claims)?)
+            .await?;
+        let call_result = Decode!(result.as_slice(), Result<String, String>)?;
+        call_result.map_err(|e| anyhow::anyhow!(e))
+    }
 }
 
 impl KongUpdate for KongBackend {
@@ -26,6 +26,13 @@ impl KongData {
         Ok(Decode!(icrc1_name.as_slice(), String)?)
     }
 
+    /// Canister status including the number of records in each stable map (requires kingkong)
+    pub async fn status(&self) -> Result<String> {
+        let result = self.agent.query(&self.canister_id, "status").with_arg(Encode!()?).await?;
+        let call_result = Decode!(result.as_slice(), Result<String, String>)?;
+        call_result.map_err(|e| anyhow::anyhow!(e))
+    }
+
     #[allow(dead_code)]
     pub async fn backup_db_updates(&self, db_update_id: Option<u64>) -> Result<String> {
         let result = self
@@ -4,19 +4,25 @@ use openssl::ssl::{SslConnector, SslMethod};
 use postgres_openssl::MakeTlsConnector;
 use std::env;
 use std::time::Duration;
-use tokio::time::timeout;
 use tokio_postgres::Config;
-use tracing::{error, info, warn};
+use tracing::info;
 use tracing_subscriber::EnvFilter;
 
 use agent::create_agent_from_identity;
 use agent::{create_anonymous_identity, create_identity_from_pem_file};
-use db_updates::get_db_updates;
+use db_updates::run_db_updates;
 use kong_backend::KongBackend;
 use kong_data::KongData;
-use settings::Settings;
+use settings::{Settings, SinkSettings};
+use sinks::json_lines_sink::JsonLinesSink;
+use sinks::parquet_sink::ParquetSink;
+use sinks::postgres_sink::PostgresSink;
+use sinks::sqlite_sink::SqliteSink;
+use snapshot::RestoreOptions;
 
 mod agent;
+mod analytics;
+mod api;
 mod claims;
 mod db_updates;
 mod kong_backend;
@@ -29,68 +35,19 @@ mod nat_helpers;
 mod pools;
 mod requests;
 mod settings;
+mod sinks;
+mod snapshot;
+mod snapshot_checks;
+mod sync_state;
 mod tokens;
 mod transfers;
 mod txs;
 mod users;
+mod verify;
 
 const LOCAL_REPLICA: &str = "http://localhost:8000";
 const MAINNET_REPLICA: &str = "https://ic0.app";
 
-async fn load_sync_state(pool: &Pool) -> Result<Option<u64>, Box<dyn std::error::Error>> {
-    let client = pool.get().await?;
-
-    // Create sync_state table if it doesn't exist
-    client
-        .execute(
-            "CREATE TABLE IF NOT EXISTS sync_state (
-                id INTEGER PRIMARY KEY DEFAULT 1,
-                last_db_update_id BIGINT NOT NULL,
-                last_updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-                CONSTRAINT single_row CHECK (id = 1)
-            )",
-            &[],
-        )
-        .await?;
-
-    // Load current sync state using prepared statement cache
-    let stmt = client
-        .prepare_cached("SELECT last_db_update_id FROM sync_state WHERE id = 1")
-        .await?;
-    let row = client.query_opt(&stmt, &[]).await?;
-
-    match row {
-        Some(row) => {
-            let last_id: i64 = row.get(0);
-            info!("Loaded sync state from database: last_db_update_id={}", last_id);
-            Ok(Some(last_id as u64))
-        }
-        None => {
-            info!("No previous sync state found in database, starting from beginning");
-            Ok(None)
-        }
-    }
-}
-
-async fn save_sync_state(pool: &Pool, last_db_update_id: u64) -> Result<(), Box<dyn std::error::Error>> {
-    let client = pool.get().await?;
-
-    // Use prepared statement cache for better performance
-    let stmt = client
-        .prepare_cached(
-            "INSERT INTO sync_state (id, last_db_update_id, last_updated_at)
-            VALUES (1, $1, CURRENT_TIMESTAMP)
-            ON CONFLICT (id) DO UPDATE SET
-                last_db_update_id = $1,
-                last_updated_at = CURRENT_TIMESTAMP"
-        )
-        .await?;
-
-    client.execute(&stmt, &[&(last_db_update_id as i64)]).await?;
-
-    Ok(())
-}
-
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing with environment filter
@@ -101,6 +58,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .with_target(false)
         .with_level(true)
+        // keep stdout free for the json_lines sink
+        .with_writer(std::io::stderr)
         .init();
 
     let args = env::args().collect::<Vec<String>>();
@@ -164,10 +123,48 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         info!("Kong backend updates completed in {:?}", start.elapsed());
     }
 
+    // save kong_backend's state into a snapshot file (./snapshots)
+    if args.contains(&"--snapshot".to_string()) {
+        info!("Starting kong_backend snapshot");
+        let dfx_pem_file = settings.dfx_pem_file.as_ref().ok_or("dfx identity required for Kong Backend")?;
+        let identity = create_identity_from_pem_file(dfx_pem_file)?;
+        let agent = create_agent_from_identity(replica_url, identity, is_mainnet).await?;
+        let kong_backend = KongBackend::new(&agent).await;
+        snapshot::create_snapshot(&kong_backend).await?;
+    }
+
+    // validate a snapshot (file or point in time) and restore it into kong_backend
+    if let Some(restore) = args.iter().position(|arg| arg == "--restore").map(|i| args.get(i + 1)) {
+        let restore = restore.ok_or("--restore requires a snapshot file or an RFC 3339 time")?;
+        let path = snapshot::find_snapshot(restore)?;
+        info!("Starting kong_backend restore from {}", path.display());
+        let snapshot = snapshot::load_snapshot(&path)?;
+        let dfx_pem_file = settings.dfx_pem_file.as_ref().ok_or("dfx identity required for Kong Backend")?;
+        let identity = create_identity_from_pem_file(dfx_pem_file)?;
+        let agent = create_agent_from_identity(replica_url, identity, is_mainnet).await?;
+        let kong_backend = KongBackend::new(&agent).await;
+        let options = RestoreOptions {
+            dry_run: args.contains(&"--dry_run".to_string()),
+            skip_ledger_check: args.contains(&"--skip_ledger_check".to_string()),
+        };
+        snapshot::restore_snapshot(&kong_backend, &agent, &snapshot, &options).await?;
+    }
+
+    // db_updates into the Postgres sink needs the database as well
+    let postgres_sink = matches!(settings.sink, SinkSettings::Postgres);
+    let db_updates = args.contains(&"--db_updates".to_string());
+
     // read from flat files (./backups) and update database
-    if args.contains(&"--database".to_string()) || args.contains(&"--db_updates".to_string()) {
-        let mut tokens_map;
-        let mut pools_map;
+    let mut postgres = None;
+    let mut api_server = None;
+    if args.contains(&"--database".to_string())
+        || (db_updates && postgres_sink)
+        || args.contains(&"--analytics_backfill".to_string())
+        || args.contains(&"--api".to_string())
+        || args.contains(&"--verify".to_string())
+    {
+        let tokens_map;
+        let pools_map;
         let pool = create_pool(&settings).await?;
 
         if args.contains(&"--database".to_string()) {
@@ -197,99 +194,78 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             pools_map = pools::load_pools_from_database(&db_client).await?;
         }
 
-        if args.contains(&"--db_updates".to_string()) {
-            info!("Starting db_updates loop with delay of {}s", settings.db_updates_delay_secs.unwrap_or(60));
+        // rebuild candles and pool/token stats from the swap history in the database
+        if args.contains(&"--analytics_backfill".to_string()) {
+            info!("Starting analytics backfill");
+            let start = std::time::Instant::now();
+            let db_client = pool.get().await?;
+            analytics::backfill_analytics(&db_client).await?;
+            info!("Analytics backfill completed in {:?}", start.elapsed());
+        }
 
-            // read from kong_data and update database
-            let identity = create_anonymous_identity();
+        // reconcile the database with kong_data
+        if args.contains(&"--verify".to_string()) {
+            info!("Verifying database against kong_data");
+            let dfx_pem_file = settings.dfx_pem_file.as_ref().ok_or("dfx identity required for Kong Data")?;
+            let identity = create_identity_from_pem_file(dfx_pem_file)?;
             let agent = create_agent_from_identity(replica_url, identity, is_mainnet).await?;
             let kong_data = KongData::new(&agent).await;
-            let base_delay_secs = settings.db_updates_delay_secs.unwrap_or(10);
-            let mut retry_delay_secs = base_delay_secs;
-            const MAX_RETRY_DELAY_SECS: u64 = 300; // 5 minutes max
-            const OPERATION_TIMEOUT_SECS: u64 = 300;
-            const SYNC_STATE_SAVE_INTERVAL: u64 = 10; // Save sync state every 10 updates
-
-            // Load last sync point from database, or start from beginning
-            let mut last_db_update_id = match load_sync_state(&pool).await {
-                Ok(state) => state,
-                Err(e) => {
-                    warn!("Failed to load sync state from database: {}", e);
-                    None
-                }
-            };
-
-            if let Some(id) = last_db_update_id {
-                info!("Resuming from last sync point: db_update_id={}", id);
-            } else {
-                info!("Starting fresh sync (no previous state found)");
-            }
+            let db_client = pool.get().await?;
+            sync_state::create_sync_tables(&db_client).await?;
+            verify::verify_database(&kong_data, &db_client).await?;
+        }
+
+        // keep the 24h stats of idle pools and tokens current while the database is being served or updated
+        if args.contains(&"--api".to_string()) || (db_updates && postgres_sink) {
+            tokio::spawn(analytics::refresh_stats_periodically(pool.clone()));
+        }
+
+        // serve the REST API alongside the db_updates loop (if any) until Ctrl+C
+        if args.contains(&"--api".to_string()) {
+            // pools and tokens are joined with their stats, which may not have been created yet
+            let db_client = pool.get().await?;
+            analytics::create_analytics_tables(&db_client).await?;
+            info!("Starting API server on {}", settings.api_bind_address);
+            api_server = Some(tokio::spawn(api::serve_api(pool.clone(), settings.api_bind_address.clone())));
+        }
+
+        postgres = Some((pool, tokens_map, pools_map));
+    }
 
-            // Counter for batching sync_state saves
-            let mut updates_since_save = 0u64;
-
-            // Get database connection once and reuse it
-            let mut client = pool.get().await?;
-
-            // loop forever and update database
-            loop {
-                tokio::select! {
-                    _ = tokio::signal::ctrl_c() => {
-                        info!("Shutdown signal received, gracefully stopping db_updates loop");
-                        // Final sync state save before exit
-                        if let Some(id) = last_db_update_id {
-                            if let Err(e) = save_sync_state(&pool, id).await {
-                                error!("Failed to save final sync state: {}", e);
-                            } else {
-                                info!("Final sync state saved: db_update_id={}", id);
-                            }
-                        }
-                        break;
-                    }
-                    result = timeout(
-                        Duration::from_secs(OPERATION_TIMEOUT_SECS),
-                        get_db_updates(last_db_update_id, &kong_data, &mut client, &mut tokens_map, &mut pools_map)
-                    ) => {
-                        match result {
-                            Ok(Ok(db_update_id)) => {
-                                last_db_update_id = Some(db_update_id);
-                                retry_delay_secs = base_delay_secs; // Reset delay on success
-                                updates_since_save += 1;
-
-                                // Batch save sync state every N updates to reduce database I/O
-                                if updates_since_save >= SYNC_STATE_SAVE_INTERVAL {
-                                    if let Err(e) = save_sync_state(&pool, db_update_id).await {
-                                        warn!("Failed to save sync state: {}", e);
-                                    } else {
-                                        info!("Sync state saved: db_update_id={}", db_update_id);
-                                    }
-                                    updates_since_save = 0;
-                                }
-
-                                info!("DB update successful, last_id: {}", db_update_id);
-                            }
-                            Ok(Err(err)) => {
-                                error!("DB update failed: {}", err);
-                                retry_delay_secs = (retry_delay_secs * 2).min(MAX_RETRY_DELAY_SECS);
-                                warn!("Retrying in {}s (exponential backoff)", retry_delay_secs);
-                                // Pool's RecyclingMethod::Fast handles connection health automatically
-                            }
-                            Err(_) => {
-                                error!("DB update timed out after {}s", OPERATION_TIMEOUT_SECS);
-                                retry_delay_secs = (retry_delay_secs * 2).min(MAX_RETRY_DELAY_SECS);
-                                warn!("Retrying in {}s (exponential backoff)", retry_delay_secs);
-                                // Pool's RecyclingMethod::Fast handles connection health automatically
-                            }
-                        }
-
-                        // Async sleep instead of blocking thread::sleep
-                        tokio::time::sleep(Duration::from_secs(retry_delay_secs)).await;
-                    }
-                }
+    // read from kong_data and write db_updates to the sink
+    if db_updates {
+        info!("Starting db_updates loop with delay of {}s", settings.db_updates_delay_secs.unwrap_or(60));
+        let identity = create_anonymous_identity();
+        let agent = create_agent_from_identity(replica_url, identity, is_mainnet).await?;
+        let kong_data = KongData::new(&agent).await;
+        let base_delay_secs = settings.db_updates_delay_secs.unwrap_or(10);
+
+        match &settings.sink {
+            SinkSettings::Postgres => {
+                let (pool, tokens_map, pools_map) = postgres.ok_or("database required for Postgres sink")?;
+                // Get database connection once and reuse it
+                let mut sink = PostgresSink::new(pool.get().await?, tokens_map, pools_map).await?;
+                run_db_updates(&mut sink, &kong_data, base_delay_secs).await?;
+            }
+            SinkSettings::Sqlite { path } => {
+                let mut sink = SqliteSink::new(path)?;
+                run_db_updates(&mut sink, &kong_data, base_delay_secs).await?;
+            }
+            SinkSettings::Parquet { dir } => {
+                let mut sink = ParquetSink::new(dir)?;
+                run_db_updates(&mut sink, &kong_data, base_delay_secs).await?;
+            }
+            SinkSettings::JsonLines { path, key_delimiter } => {
+                let mut sink = JsonLinesSink::new(path, key_delimiter.clone());
+                run_db_updates(&mut sink, &kong_data, base_delay_secs).await?;
             }
         }
     }
 
+    if let Some(api_server) = api_server {
+        api_server.await?.map_err(|e| e as Box<dyn std::error::Error>)?;
+    }
+
     Ok(())
 }
 
@@ -28,6 +28,29 @@ pub struct Settings {
     pub dfx_pem_file: Option<String>,
     pub db_updates_delay_secs: Option<u64>,
     pub database: Database,
+    #[serde(default = "default_api_bind_address")]
+    pub api_bind_address: String,
+    #[serde(default)]
+    pub sink: SinkSettings,
+}
+
+/// Destination of the db_updates feed (--db_updates)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkSettings {
+    /// The Postgres schema in `database` (default)
+    #[default]
+    Postgres,
+    /// A local SQLite database file
+    Sqlite { path: String },
+    /// Partitioned Parquet files under a directory
+    Parquet { dir: String },
+    /// Line-delimited JSON to a file, or stdout with "-"
+    JsonLines { path: String, key_delimiter: Option<String> },
+}
+
+fn default_api_bind_address() -> String {
+    "127.0.0.1:8080".to_string()
 }
 
 pub fn read_settings() -> Result<Settings, Box<dyn std::error::Error>> {
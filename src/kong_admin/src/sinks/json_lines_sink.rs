@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::{memory_type, record_id, DBUpdateBatch, Sink};
+
+#[derive(Serialize, Deserialize)]
+struct JsonLinesSyncState {
+    last_db_update_id: u64,
+    /// Length of the output file when the sync point was written
+    offset: u64,
+}
+
+/// Writes db_updates as line-delimited JSON (one StableDBUpdate per line) to a file or stdout ("-")
+///
+/// With a key_delimiter, each line is prefixed with a `<memory_type>:<record_id>` key so the
+/// output can be produced to Kafka as keyed messages, e.g.
+/// `kong_admin --db_updates | kcat -P -b <broker> -t kong_db_updates -K '\t'`
+/// (compacted topics then keep the latest version of every record).
+///
+/// For a file, the sync point is kept in `<path>.sync_state` with the file length, and the file
+/// is truncated back to that length on start, so each db_update is written exactly once. stdout
+/// has no sync point: the stream starts from the oldest db_update in kong_data and consumers
+/// should deduplicate on db_update_id.
+pub struct JsonLinesSink {
+    path: Option<PathBuf>,
+    key_delimiter: Option<String>,
+}
+
+impl JsonLinesSink {
+    pub fn new(path: &str, key_delimiter: Option<String>) -> Self {
+        JsonLinesSink {
+            path: if path == "-" { None } else { Some(PathBuf::from(path)) },
+            key_delimiter,
+        }
+    }
+
+    fn sync_state_path(path: &Path) -> PathBuf {
+        let mut sync_state_path = path.to_path_buf().into_os_string();
+        sync_state_path.push(".sync_state");
+        PathBuf::from(sync_state_path)
+    }
+
+    fn format_batch(&self, batch: &DBUpdateBatch) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut lines = Vec::new();
+        for db_update in batch.db_updates.iter() {
+            if let Some(key_delimiter) = &self.key_delimiter {
+                write!(
+                    lines,
+                    "{}:{}{}",
+                    memory_type(&db_update.stable_memory),
+                    record_id(&db_update.stable_memory),
+                    key_delimiter
+                )?;
+            }
+            serde_json::to_writer(&mut lines, db_update)?;
+            lines.push(b'\n');
+        }
+        Ok(lines)
+    }
+}
+
+impl Sink for JsonLinesSink {
+    fn name(&self) -> &'static str {
+        "json_lines"
+    }
+
+    async fn load_sync_state(&mut self) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+        let Some(path) = &self.path else {
+            return Ok(None);
+        };
+        let sync_state_path = Self::sync_state_path(path);
+        if !sync_state_path.exists() {
+            // nothing committed yet, drop any partial output
+            if path.exists() {
+                File::create(path)?;
+            }
+            return Ok(None);
+        }
+
+        let sync_state: JsonLinesSyncState = serde_json::from_str(&fs::read_to_string(&sync_state_path)?)?;
+        // drop lines of a batch written after the sync point
+        OpenOptions::new().write(true).create(true).truncate(false).open(path)?.set_len(sync_state.offset)?;
+        Ok(Some(sync_state.last_db_update_id))
+    }
+
+    async fn write_batch(&mut self, batch: &DBUpdateBatch) -> Result<(), Box<dyn std::error::Error>> {
+        let lines = self.format_batch(batch)?;
+
+        let Some(path) = &self.path else {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(&lines)?;
+            stdout.flush()?;
+            return Ok(());
+        };
+
+        let mut file = OpenOptions::new().append(true).create(true).open(path)?;
+        file.write_all(&lines)?;
+        file.sync_data()?;
+        let sync_state = JsonLinesSyncState {
+            last_db_update_id: batch.last_db_update_id,
+            offset: file.metadata()?.len(),
+        };
+        let sync_state_path = Self::sync_state_path(path);
+        let tmp_path = sync_state_path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_string(&sync_state)?)?;
+        fs::rename(&tmp_path, &sync_state_path)?;
+
+        Ok(())
+    }
+}
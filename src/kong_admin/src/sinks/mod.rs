@@ -0,0 +1,70 @@
+pub mod json_lines_sink;
+pub mod parquet_sink;
+pub mod postgres_sink;
+pub mod sqlite_sink;
+
+use kong_lib::stable_db_update::stable_db_update::{StableDBUpdate, StableMemory};
+use kong_lib::stable_token::token::Token;
+use kong_lib::stable_tx::tx::Tx;
+
+// Destinations of the kong_data db_updates feed (--db_updates), selected with "sink" in settings.json.
+//
+// A sink persists batches of db_updates together with its sync point (the last db_update_id
+// written), so the loop resumes after the last batch written and never writes a batch twice
+// (see each sink for the guarantees it can give).
+
+/// A batch of db_updates fetched from kong_data
+pub struct DBUpdateBatch {
+    /// New db_updates in db_update_id order
+    pub db_updates: Vec<StableDBUpdate>,
+    /// Ranges of db_update_ids (inclusive) missing before or between the db_updates
+    pub gaps: Vec<(u64, u64)>,
+    /// db_update_id of the last db_update in the batch
+    pub last_db_update_id: u64,
+}
+
+pub trait Sink {
+    fn name(&self) -> &'static str;
+
+    /// Last db_update_id written, None if the sink is empty
+    async fn load_sync_state(&mut self) -> Result<Option<u64>, Box<dyn std::error::Error>>;
+
+    /// Writes a batch and advances the sync point to batch.last_db_update_id
+    async fn write_batch(&mut self, batch: &DBUpdateBatch) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// Name of the stable map a db_update belongs to
+pub fn memory_type(stable_memory: &StableMemory) -> &'static str {
+    match stable_memory {
+        StableMemory::KongSettings(_) => "KongSettings",
+        StableMemory::UserMap(_) => "UserMap",
+        StableMemory::TokenMap(_) => "TokenMap",
+        StableMemory::PoolMap(_) => "PoolMap",
+        StableMemory::TxMap(_) => "TxMap",
+        StableMemory::RequestMap(_) => "RequestMap",
+        StableMemory::TransferMap(_) => "TransferMap",
+        StableMemory::ClaimMap(_) => "ClaimMap",
+        StableMemory::LPTokenMap(_) => "LPTokenMap",
+    }
+}
+
+/// Id of the record a db_update writes, unique within its stable map
+pub fn record_id(stable_memory: &StableMemory) -> u64 {
+    match stable_memory {
+        StableMemory::KongSettings(_) => 0,
+        StableMemory::UserMap(user) => user.user_id as u64,
+        StableMemory::TokenMap(token) => token.token_id() as u64,
+        StableMemory::PoolMap(pool) => pool.pool_id as u64,
+        StableMemory::TxMap(tx) => tx.tx_id(),
+        StableMemory::RequestMap(request) => request.request_id,
+        StableMemory::TransferMap(transfer) => transfer.transfer_id,
+        StableMemory::ClaimMap(claim) => claim.claim_id,
+        StableMemory::LPTokenMap(lp_token) => lp_token.lp_token_id,
+    }
+}
+
+/// The record of a db_update as JSON (e.g. the StableUser of a UserMap update)
+pub fn record_json(stable_memory: &StableMemory) -> Result<String, serde_json::Error> {
+    let json = serde_json::to_value(stable_memory)?;
+    serde_json::to_string(&json[memory_type(stable_memory)])
+}
@@ -0,0 +1,138 @@
+use arrow_array::{ArrayRef, RecordBatch, StringArray, TimestampNanosecondArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use chrono::DateTime;
+use kong_lib::stable_db_update::stable_db_update::StableDBUpdate;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::{memory_type, record_id, record_json, DBUpdateBatch, Sink};
+
+const SYNC_STATE_FILE: &str = "_sync_state.json";
+
+#[derive(Serialize, Deserialize, Default)]
+struct ParquetSyncState {
+    last_db_update_id: Option<u64>,
+    gaps: Vec<(u64, u64)>,
+}
+
+/// Writes db_updates to Parquet files partitioned by stable map and day (hive style):
+///
+/// `<dir>/memory_type=<TxMap|PoolMap|...>/date=<YYYY-MM-DD>/part-<first db_update_id of the batch>.parquet`
+///
+/// with columns db_update_id, ts, record_id and record (JSON). The sync point and detected gaps
+/// are kept in `<dir>/_sync_state.json`, replaced after the batch's files are written. Files
+/// are named after the batch's first db_update_id, so a batch replayed after a crash
+/// overwrites its partial output instead of duplicating it.
+pub struct ParquetSink {
+    dir: PathBuf,
+    schema: Arc<Schema>,
+    sync_state: ParquetSyncState,
+}
+
+impl ParquetSink {
+    pub fn new(dir: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        fs::create_dir_all(dir)?;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("db_update_id", DataType::UInt64, false),
+            Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())), false),
+            Field::new("record_id", DataType::UInt64, false),
+            Field::new("record", DataType::Utf8, false),
+        ]));
+        Ok(ParquetSink {
+            dir: PathBuf::from(dir),
+            schema,
+            sync_state: ParquetSyncState::default(),
+        })
+    }
+
+    fn write_file(&self, path: &Path, db_updates: &[&StableDBUpdate]) -> Result<(), Box<dyn std::error::Error>> {
+        let db_update_ids = UInt64Array::from_iter_values(db_updates.iter().map(|db_update| db_update.db_update_id));
+        let ts = TimestampNanosecondArray::from_iter_values(db_updates.iter().map(|db_update| db_update.ts as i64)).with_timezone("UTC");
+        let record_ids = UInt64Array::from_iter_values(db_updates.iter().map(|db_update| record_id(&db_update.stable_memory)));
+        let records = db_updates
+            .iter()
+            .map(|db_update| record_json(&db_update.stable_memory))
+            .collect::<Result<Vec<String>, _>>()?;
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(db_update_ids),
+            Arc::new(ts),
+            Arc::new(record_ids),
+            Arc::new(StringArray::from(records)),
+        ];
+        let record_batch = RecordBatch::try_new(self.schema.clone(), columns)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("parquet.tmp");
+        let props = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+        let mut writer = ArrowWriter::try_new(File::create(&tmp_path)?, self.schema.clone(), Some(props))?;
+        writer.write(&record_batch)?;
+        writer.close()?;
+        fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+
+    fn save_sync_state(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = self.dir.join(SYNC_STATE_FILE);
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(&self.sync_state)?)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+}
+
+impl Sink for ParquetSink {
+    fn name(&self) -> &'static str {
+        "parquet"
+    }
+
+    async fn load_sync_state(&mut self) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+        let path = self.dir.join(SYNC_STATE_FILE);
+        if path.exists() {
+            self.sync_state = serde_json::from_str(&fs::read_to_string(path)?)?;
+        }
+        Ok(self.sync_state.last_db_update_id)
+    }
+
+    async fn write_batch(&mut self, batch: &DBUpdateBatch) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(first_db_update) = batch.db_updates.first() else {
+            return Ok(());
+        };
+
+        // one file per (memory_type, date) partition
+        let mut partitions: BTreeMap<(&str, String), Vec<&StableDBUpdate>> = BTreeMap::new();
+        for db_update in batch.db_updates.iter() {
+            let date = DateTime::from_timestamp_nanos(db_update.ts as i64).format("%Y-%m-%d").to_string();
+            partitions
+                .entry((memory_type(&db_update.stable_memory), date))
+                .or_default()
+                .push(db_update);
+        }
+        for ((memory_type, date), db_updates) in partitions.iter() {
+            let path = self
+                .dir
+                .join(format!("memory_type={}", memory_type))
+                .join(format!("date={}", date))
+                .join(format!("part-{:020}.parquet", first_db_update.db_update_id));
+            self.write_file(&path, db_updates)?;
+        }
+
+        for gap in batch.gaps.iter() {
+            if !self.sync_state.gaps.contains(gap) {
+                self.sync_state.gaps.push(*gap);
+            }
+        }
+        self.sync_state.last_db_update_id = Some(batch.last_db_update_id);
+        self.save_sync_state()?;
+
+        Ok(())
+    }
+}
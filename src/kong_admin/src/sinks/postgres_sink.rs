@@ -0,0 +1,194 @@
+use deadpool_postgres::Object;
+use kong_lib::stable_db_update::stable_db_update::StableMemory;
+use kong_lib::stable_token::token::Token;
+use kong_lib::stable_tx::stable_tx::StableTx;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+use tokio_postgres::Client;
+use tracing::{error, warn};
+
+use super::{DBUpdateBatch, Sink};
+use crate::analytics::{create_analytics_tables, update_pool_candles, update_pool_stats, update_token_stats};
+use crate::claims::insert_claim_on_database;
+use crate::lp_tokens::insert_lp_token_on_database;
+use crate::pools::{insert_pool_on_database, query_pool_token_ids};
+use crate::requests::insert_request_on_database;
+use crate::sync_state::{create_sync_tables, insert_dead_letter, insert_gap, save_sync_state};
+use crate::tokens::{insert_token_on_database, query_token_decimals};
+use crate::transfers::insert_transfer_on_database;
+use crate::txs::insert_tx_on_database;
+use crate::users::insert_user_on_database;
+
+/// Applies db_updates to the Postgres schema (the default sink)
+///
+/// Each batch, its dead letters, detected gaps and the sync_state advance are committed in a
+/// single transaction, so a crash or failure leaves the database at the previous sync point
+/// and the batch is applied again (exactly once) on retry.
+pub struct PostgresSink {
+    db_client: Object,
+    tokens_map: BTreeMap<u32, u8>,
+    pools_map: BTreeMap<u32, (u32, u32)>,
+}
+
+impl PostgresSink {
+    pub async fn new(
+        db_client: Object,
+        tokens_map: BTreeMap<u32, u8>,
+        pools_map: BTreeMap<u32, (u32, u32)>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        create_sync_tables(&db_client).await?;
+        create_analytics_tables(&db_client).await?;
+        Ok(PostgresSink {
+            db_client,
+            tokens_map,
+            pools_map,
+        })
+    }
+}
+
+impl Sink for PostgresSink {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+
+    async fn load_sync_state(&mut self) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+        let row = self
+            .db_client
+            .query_opt("SELECT last_db_update_id FROM sync_state WHERE id = 1", &[])
+            .await?;
+        Ok(row.map(|row| row.get::<_, i64>(0) as u64))
+    }
+
+    async fn write_batch(&mut self, batch: &DBUpdateBatch) -> Result<(), Box<dyn std::error::Error>> {
+        // maps are only updated once the batch is committed
+        let mut tokens_map = self.tokens_map.clone();
+        let mut pools_map = self.pools_map.clone();
+
+        // a batch interrupted by the caller's timeout may have left its transaction open
+        self.db_client.batch_execute("ROLLBACK").await?;
+        self.db_client.batch_execute("BEGIN").await?;
+        let num_dead_letters = match apply_db_updates(batch, &self.db_client, &mut tokens_map, &mut pools_map).await {
+            Ok(num_dead_letters) => num_dead_letters,
+            Err(e) => {
+                self.db_client.batch_execute("ROLLBACK").await?;
+                return Err(e);
+            }
+        };
+        self.db_client.batch_execute("COMMIT").await?;
+
+        self.tokens_map = tokens_map;
+        self.pools_map = pools_map;
+
+        if num_dead_letters > 0 {
+            warn!("{} db_updates moved to db_update_dead_letters", num_dead_letters);
+        }
+
+        Ok(())
+    }
+}
+
+/// Applies a batch inside the open transaction. Returns the number of dead letters
+async fn apply_db_updates(
+    batch: &DBUpdateBatch,
+    db_client: &Client,
+    tokens_map: &mut BTreeMap<u32, u8>,
+    pools_map: &mut BTreeMap<u32, (u32, u32)>,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    // pools and tokens whose analytics need refreshing after this batch
+    let mut swapped_pools = BTreeMap::new();
+    let mut touched_pools = BTreeSet::new();
+    let mut touched_tokens = BTreeSet::new();
+    let mut num_dead_letters = 0;
+
+    for (from_db_update_id, to_db_update_id) in batch.gaps.iter() {
+        insert_gap(db_client, *from_db_update_id, *to_db_update_id).await?;
+    }
+
+    for db_update in batch.db_updates.iter() {
+        // a failed insert only rolls back its own db_update, which is kept as a dead letter
+        db_client.batch_execute("SAVEPOINT db_update").await?;
+        match apply_db_update(&db_update.stable_memory, db_client, tokens_map, pools_map).await {
+            Ok(()) => {
+                db_client.batch_execute("RELEASE SAVEPOINT db_update").await?;
+                touch_analytics(&db_update.stable_memory, &mut swapped_pools, &mut touched_pools, &mut touched_tokens);
+            }
+            Err(e) => {
+                error!("db_update_id={} failed: {}", db_update.db_update_id, e);
+                db_client.batch_execute("ROLLBACK TO SAVEPOINT db_update").await?;
+                insert_dead_letter(db_client, db_update, &e.to_string()).await?;
+                num_dead_letters += 1;
+            }
+        }
+    }
+
+    update_pool_candles(db_client, &swapped_pools).await?;
+    if !touched_pools.is_empty() {
+        update_pool_stats(db_client, Some(&touched_pools)).await?;
+    }
+    if !touched_tokens.is_empty() {
+        update_token_stats(db_client, Some(&touched_tokens)).await?;
+    }
+
+    save_sync_state(db_client, batch.last_db_update_id).await?;
+
+    Ok(num_dead_letters)
+}
+
+async fn apply_db_update(
+    stable_memory: &StableMemory,
+    db_client: &Client,
+    tokens_map: &mut BTreeMap<u32, u8>,
+    pools_map: &mut BTreeMap<u32, (u32, u32)>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match stable_memory {
+        StableMemory::KongSettings(_) => (),
+        StableMemory::UserMap(user) => insert_user_on_database(user, db_client).await?,
+        StableMemory::TokenMap(token) => {
+            insert_token_on_database(token, db_client).await?;
+            if let Entry::Vacant(entry) = tokens_map.entry(token.token_id()) {
+                entry.insert(query_token_decimals(db_client, token.token_id()).await?);
+            }
+        }
+        StableMemory::PoolMap(pool) => {
+            insert_pool_on_database(pool, db_client, tokens_map).await?;
+            if let Entry::Vacant(entry) = pools_map.entry(pool.pool_id) {
+                entry.insert(query_pool_token_ids(db_client, pool.pool_id).await?);
+            }
+        }
+        StableMemory::TxMap(tx) => insert_tx_on_database(tx, db_client, tokens_map, pools_map).await?,
+        StableMemory::RequestMap(request) => insert_request_on_database(request, db_client).await?,
+        StableMemory::TransferMap(transfer) => insert_transfer_on_database(transfer, db_client, tokens_map).await?,
+        StableMemory::ClaimMap(claim) => insert_claim_on_database(claim, db_client, tokens_map).await?,
+        StableMemory::LPTokenMap(lptoken) => insert_lp_token_on_database(lptoken, db_client, tokens_map).await?,
+    }
+
+    Ok(())
+}
+
+/// Collects the swapped pools with the time range of their swaps, and the pools and tokens whose stats need refreshing
+fn touch_analytics(
+    stable_memory: &StableMemory,
+    swapped_pools: &mut BTreeMap<u32, (f64, f64)>,
+    touched_pools: &mut BTreeSet<u32>,
+    touched_tokens: &mut BTreeSet<u32>,
+) {
+    match stable_memory {
+        StableMemory::PoolMap(pool) => {
+            touched_pools.insert(pool.pool_id);
+            touched_tokens.insert(pool.token_id_0);
+            touched_tokens.insert(pool.token_id_1);
+        }
+        StableMemory::TxMap(StableTx::Swap(swap_tx)) => {
+            let ts = swap_tx.ts as f64 / 1_000_000_000.0;
+            for swap_pool_tx in swap_tx.txs.iter() {
+                let (from_ts, to_ts) = swapped_pools.entry(swap_pool_tx.pool_id).or_insert((ts, ts));
+                *from_ts = from_ts.min(ts);
+                *to_ts = to_ts.max(ts);
+                touched_pools.insert(swap_pool_tx.pool_id);
+                touched_tokens.insert(swap_pool_tx.pay_token_id);
+                touched_tokens.insert(swap_pool_tx.receive_token_id);
+            }
+        }
+        _ => (),
+    }
+}
@@ -0,0 +1,112 @@
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::{memory_type, record_id, record_json, DBUpdateBatch, Sink};
+
+/// Writes db_updates to a local SQLite database
+///
+/// - db_updates: every db_update with its record as JSON
+/// - records: latest version of every record, keyed by (memory_type, record_id)
+/// - db_update_gaps and sync_state: as in Postgres
+///
+/// Each batch is written in a single transaction with the sync point, so it is applied exactly once.
+/// Records can be queried with SQLite's JSON functions, e.g. `json_extract(record, '$.symbol')`.
+pub struct SqliteSink {
+    conn: Connection,
+}
+
+impl SqliteSink {
+    pub fn new(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+            CREATE TABLE IF NOT EXISTS db_updates (
+                db_update_id INTEGER PRIMARY KEY,
+                ts INTEGER NOT NULL,
+                memory_type TEXT NOT NULL,
+                record_id INTEGER NOT NULL,
+                record TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS db_updates_record_idx ON db_updates (memory_type, record_id);
+            CREATE TABLE IF NOT EXISTS records (
+                memory_type TEXT NOT NULL,
+                record_id INTEGER NOT NULL,
+                db_update_id INTEGER NOT NULL,
+                ts INTEGER NOT NULL,
+                record TEXT NOT NULL,
+                PRIMARY KEY (memory_type, record_id)
+            );
+            CREATE TABLE IF NOT EXISTS db_update_gaps (
+                from_db_update_id INTEGER PRIMARY KEY,
+                to_db_update_id INTEGER NOT NULL,
+                detected_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE IF NOT EXISTS sync_state (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                last_db_update_id INTEGER NOT NULL,
+                last_updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );",
+        )?;
+        Ok(SqliteSink { conn })
+    }
+}
+
+impl Sink for SqliteSink {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    async fn load_sync_state(&mut self) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+        let last_db_update_id: Option<i64> = self
+            .conn
+            .query_row("SELECT last_db_update_id FROM sync_state WHERE id = 1", [], |row| row.get(0))
+            .optional()?;
+        Ok(last_db_update_id.map(|id| id as u64))
+    }
+
+    async fn write_batch(&mut self, batch: &DBUpdateBatch) -> Result<(), Box<dyn std::error::Error>> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut insert_db_update = tx.prepare_cached(
+                "INSERT OR REPLACE INTO db_updates (db_update_id, ts, memory_type, record_id, record)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            // db_updates are in order, so the last one of each record wins
+            let mut upsert_record = tx.prepare_cached(
+                "INSERT INTO records (memory_type, record_id, db_update_id, ts, record)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                ON CONFLICT (memory_type, record_id) DO UPDATE SET
+                    db_update_id = excluded.db_update_id,
+                    ts = excluded.ts,
+                    record = excluded.record",
+            )?;
+            for db_update in batch.db_updates.iter() {
+                let db_update_id = db_update.db_update_id as i64;
+                let ts = db_update.ts as i64;
+                let memory_type = memory_type(&db_update.stable_memory);
+                let record_id = record_id(&db_update.stable_memory) as i64;
+                let record = record_json(&db_update.stable_memory)?;
+                insert_db_update.execute(params![db_update_id, ts, memory_type, record_id, record])?;
+                upsert_record.execute(params![memory_type, record_id, db_update_id, ts, record])?;
+            }
+
+            for (from_db_update_id, to_db_update_id) in batch.gaps.iter() {
+                tx.execute(
+                    "INSERT OR IGNORE INTO db_update_gaps (from_db_update_id, to_db_update_id) VALUES (?1, ?2)",
+                    params![*from_db_update_id as i64, *to_db_update_id as i64],
+                )?;
+            }
+
+            tx.execute(
+                "INSERT INTO sync_state (id, last_db_update_id, last_updated_at)
+                VALUES (1, ?1, CURRENT_TIMESTAMP)
+                ON CONFLICT (id) DO UPDATE SET
+                    last_db_update_id = excluded.last_db_update_id,
+                    last_updated_at = CURRENT_TIMESTAMP",
+                params![batch.last_db_update_id as i64],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+}
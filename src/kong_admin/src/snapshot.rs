@@ -0,0 +1,414 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use ic_agent::Agent;
+use kong_lib::ic::canister_address::KONG_BACKEND;
+use kong_lib::stable_claim::stable_claim::StableClaim;
+use kong_lib::stable_kong_settings::stable_kong_settings::StableKongSettings;
+use kong_lib::stable_lp_token::stable_lp_token::StableLPToken;
+use kong_lib::stable_pool::stable_pool::StablePool;
+use kong_lib::stable_token::stable_token::StableToken;
+use kong_lib::stable_token::token::Token;
+use kong_lib::stable_user::stable_user::StableUser;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::future::Future;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tracing::{error, info, warn};
+
+use super::kong_backend::KongBackend;
+use super::kong_update::KongUpdate;
+use super::snapshot_checks::{check_ledger_balances, validate_state};
+
+/// Bump when SnapshotState changes in a way older versions of kong_admin can't read
+pub const SNAPSHOT_VERSION: u32 = 1;
+const SNAPSHOT_DIR: &str = "./snapshots";
+const SNAPSHOT_TS_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const PAGE_SIZE: u16 = 1_000; // max records returned by kong_backend's backup_* endpoints
+const MAX_SNAPSHOT_ATTEMPTS: usize = 3;
+const RESTORE_CHUNK_SIZE: usize = 500; // records per update_* call, keeps the args well below the ingress limit
+
+/// A consistent copy of kong_backend's state
+#[derive(Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    pub canister_id: String,
+    pub created_at: u64,
+    pub checksum: String, // sha256 of the JSON serialized state
+    pub state: SnapshotState,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SnapshotState {
+    pub kong_settings: StableKongSettings,
+    pub users: Vec<StableUser>,
+    pub tokens: Vec<StableToken>,
+    pub pools: Vec<StablePool>,
+    pub lp_tokens: Vec<StableLPToken>,
+    pub claims: Vec<StableClaim>,
+}
+
+impl SnapshotState {
+    fn checksum(&self) -> Result<String, serde_json::Error> {
+        let bytes = serde_json::to_vec(self)?;
+        Ok(hex::encode(Sha256::digest(bytes)))
+    }
+}
+
+pub struct RestoreOptions {
+    pub dry_run: bool,
+    pub skip_ledger_check: bool,
+}
+
+/// counters of every stable map. any operation on kong_backend creates at least a request, so
+/// equal counters before and after reading the maps means nothing was written in between
+fn map_counters(kong_settings: &StableKongSettings) -> [u64; 8] {
+    [
+        kong_settings.user_map_idx as u64,
+        kong_settings.token_map_idx as u64,
+        kong_settings.pool_map_idx as u64,
+        kong_settings.tx_map_idx,
+        kong_settings.request_map_idx,
+        kong_settings.transfer_map_idx,
+        kong_settings.claim_map_idx,
+        kong_settings.lp_token_map_idx,
+    ]
+}
+
+/// maps restored from a snapshot whose live counter is past the snapshot's. a restore would leave those
+/// newer records behind, so they have to be removed before the snapshot can be restored
+fn newer_records(live: &StableKongSettings, snapshot: &StableKongSettings) -> Vec<String> {
+    [
+        ("users", live.user_map_idx as u64, snapshot.user_map_idx as u64),
+        ("tokens", live.token_map_idx as u64, snapshot.token_map_idx as u64),
+        ("pools", live.pool_map_idx as u64, snapshot.pool_map_idx as u64),
+        ("lp_tokens", live.lp_token_map_idx, snapshot.lp_token_map_idx),
+        ("claims", live.claim_map_idx, snapshot.claim_map_idx),
+    ]
+    .into_iter()
+    .filter(|(_, live_idx, snapshot_idx)| live_idx > snapshot_idx)
+    .map(|(name, live_idx, snapshot_idx)| {
+        format!(
+            "kong_backend has {} created after the snapshot (ids {} to {})",
+            name,
+            snapshot_idx + 1,
+            live_idx
+        )
+    })
+    .collect()
+}
+
+/// the snapshot's kong_settings with every map counter at least the live one, so new records never reuse an id
+/// already taken. txs, requests and transfers aren't restored, so their live records are always newer
+fn merge_counters(live: &StableKongSettings, snapshot: &StableKongSettings) -> StableKongSettings {
+    StableKongSettings {
+        user_map_idx: live.user_map_idx.max(snapshot.user_map_idx),
+        token_map_idx: live.token_map_idx.max(snapshot.token_map_idx),
+        pool_map_idx: live.pool_map_idx.max(snapshot.pool_map_idx),
+        tx_map_idx: live.tx_map_idx.max(snapshot.tx_map_idx),
+        request_map_idx: live.request_map_idx.max(snapshot.request_map_idx),
+        transfer_map_idx: live.transfer_map_idx.max(snapshot.transfer_map_idx),
+        claim_map_idx: live.claim_map_idx.max(snapshot.claim_map_idx),
+        lp_token_map_idx: live.lp_token_map_idx.max(snapshot.lp_token_map_idx),
+        ..snapshot.clone()
+    }
+}
+
+/// pages through a backup_* endpoint until all records are read
+async fn backup_all<T, F, Fut>(backup: F, id: fn(&T) -> u64) -> Result<Vec<T>, Box<dyn std::error::Error>>
+where
+    T: DeserializeOwned,
+    F: Fn(Option<u64>, Option<u16>) -> Fut,
+    Fut: Future<Output = anyhow::Result<String>>,
+{
+    let mut records = Vec::new();
+    let mut next_id = 0;
+    loop {
+        let json = backup(Some(next_id), Some(PAGE_SIZE)).await?;
+        let page: BTreeMap<String, T> = serde_json::from_str(&json)?;
+        let num_records = page.len();
+        for record in page.into_values() {
+            next_id = next_id.max(id(&record) + 1);
+            records.push(record);
+        }
+        if num_records < PAGE_SIZE as usize {
+            break;
+        }
+    }
+    records.sort_by_key(id);
+
+    Ok(records)
+}
+
+async fn backup_kong_settings(kong_backend: &KongBackend) -> Result<StableKongSettings, Box<dyn std::error::Error>> {
+    let json = kong_backend.backup_kong_settings().await?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+async fn backup_state(kong_backend: &KongBackend) -> Result<SnapshotState, Box<dyn std::error::Error>> {
+    let (users, tokens, pools, lp_tokens, claims) = tokio::try_join!(
+        backup_all(
+            |id, n| kong_backend.backup_users(id.map(|id| id as u32), n),
+            |user: &StableUser| user.user_id as u64
+        ),
+        backup_all(
+            |id, n| kong_backend.backup_tokens(id.map(|id| id as u32), n),
+            |token: &StableToken| token.token_id() as u64
+        ),
+        backup_all(
+            |id, n| kong_backend.backup_pools(id.map(|id| id as u32), n),
+            |pool: &StablePool| pool.pool_id as u64
+        ),
+        backup_all(
+            |id, n| kong_backend.backup_lp_tokens(id, n),
+            |lp_token: &StableLPToken| lp_token.lp_token_id
+        ),
+        backup_all(|id, n| kong_backend.backup_claims(id, n), |claim: &StableClaim| claim.claim_id),
+    )?;
+    // read last so the counters cover every record above
+    let kong_settings = backup_kong_settings(kong_backend).await?;
+
+    Ok(SnapshotState {
+        kong_settings,
+        users,
+        tokens,
+        pools,
+        lp_tokens,
+        claims,
+    })
+}
+
+/// Reads kong_backend's state into ./snapshots/kong_backend.<YYYYMMDDTHHMMSSZ>.json
+///
+/// The maps are read with several queries, so the snapshot is retried if kong_backend's map
+/// counters moved while reading. Set maintenance_mode on a busy canister to guarantee a snapshot.
+pub async fn create_snapshot(kong_backend: &KongBackend) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    for attempt in 1..=MAX_SNAPSHOT_ATTEMPTS {
+        let counters = map_counters(&backup_kong_settings(kong_backend).await?);
+        let state = backup_state(kong_backend).await?;
+        if counters != map_counters(&state.kong_settings) {
+            warn!("kong_backend changed while taking snapshot (attempt {}/{})", attempt, MAX_SNAPSHOT_ATTEMPTS);
+            continue;
+        }
+
+        // the snapshot records the state as is, problems are only reported
+        for problem in validate_state(&state) {
+            warn!("Snapshot: {}", problem);
+        }
+
+        let created_at = Utc::now();
+        let snapshot = Snapshot {
+            version: SNAPSHOT_VERSION,
+            canister_id: KONG_BACKEND.to_string(),
+            created_at: created_at.timestamp_nanos_opt().unwrap_or_default() as u64,
+            checksum: state.checksum()?,
+            state,
+        };
+
+        fs::create_dir_all(SNAPSHOT_DIR)?;
+        let path = Path::new(SNAPSHOT_DIR).join(format!("kong_backend.{}.json", created_at.format(SNAPSHOT_TS_FORMAT)));
+        let tmp_path = path.with_extension("json.tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        serde_json::to_writer(&mut writer, &snapshot)?;
+        writer.flush()?;
+        fs::rename(&tmp_path, &path)?;
+
+        info!(
+            "Snapshot {} saved: {} users, {} tokens, {} pools, {} lp_tokens, {} claims",
+            path.display(),
+            snapshot.state.users.len(),
+            snapshot.state.tokens.len(),
+            snapshot.state.pools.len(),
+            snapshot.state.lp_tokens.len(),
+            snapshot.state.claims.len()
+        );
+        return Ok(path);
+    }
+
+    Err(format!(
+        "kong_backend kept changing after {} attempts, enable maintenance_mode and try again",
+        MAX_SNAPSHOT_ATTEMPTS
+    )
+    .into())
+}
+
+/// Finds the snapshot to restore: either a path to a snapshot file, or a point in time
+/// (RFC 3339, e.g. 2025-10-18T12:00:00Z) for the latest snapshot in ./snapshots taken at or before it
+pub fn find_snapshot(snapshot: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path = Path::new(snapshot);
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    let point_in_time = DateTime::parse_from_rfc3339(snapshot)
+        .map_err(|_| format!("{} is neither a snapshot file nor an RFC 3339 time", snapshot))?
+        .with_timezone(&Utc);
+    let mut latest: Option<(DateTime<Utc>, PathBuf)> = None;
+    for entry in fs::read_dir(SNAPSHOT_DIR)? {
+        let path = entry?.path();
+        let Some(ts) = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .and_then(|file_name| file_name.strip_prefix("kong_backend.")?.strip_suffix(".json"))
+            .and_then(|ts| NaiveDateTime::parse_from_str(ts, SNAPSHOT_TS_FORMAT).ok())
+        else {
+            continue;
+        };
+        let ts = ts.and_utc();
+        if ts <= point_in_time && latest.as_ref().is_none_or(|(latest_ts, _)| ts > *latest_ts) {
+            latest = Some((ts, path));
+        }
+    }
+
+    latest
+        .map(|(_, path)| path)
+        .ok_or_else(|| format!("No snapshot in {} taken at or before {}", SNAPSHOT_DIR, point_in_time).into())
+}
+
+/// Loads a snapshot, checking its version and checksum
+pub fn load_snapshot(path: &Path) -> Result<Snapshot, Box<dyn std::error::Error>> {
+    let snapshot: Snapshot = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+    if snapshot.version > SNAPSHOT_VERSION {
+        return Err(format!(
+            "Snapshot version {} is newer than supported version {}",
+            snapshot.version, SNAPSHOT_VERSION
+        )
+        .into());
+    }
+    let checksum = snapshot.state.checksum()?;
+    if checksum != snapshot.checksum {
+        return Err(format!("Snapshot checksum mismatch: expected {}, got {}", snapshot.checksum, checksum).into());
+    }
+
+    Ok(snapshot)
+}
+
+/// serializes records as the BTreeMap<id, record> JSON expected by update_* and sends them in chunks
+async fn restore_records<T, F, Fut>(name: &str, records: &[T], id: fn(&T) -> u64, update: F) -> Result<(), Box<dyn std::error::Error>>
+where
+    T: Serialize,
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = anyhow::Result<String>>,
+{
+    for chunk in records.chunks(RESTORE_CHUNK_SIZE) {
+        let map: BTreeMap<u64, &T> = chunk.iter().map(|record| (id(record), record)).collect();
+        update(serde_json::to_string(&map)?).await?;
+    }
+    info!("Restored {} {}", records.len(), name);
+
+    Ok(())
+}
+
+/// Validates the snapshot and writes it to kong_backend. Nothing is written if any check fails
+///
+/// Records created after the snapshot are not removed by the restore, so kong_backend must not have any
+/// users, tokens, pools, LP tokens or claims newer than the snapshot
+pub async fn restore_snapshot(
+    kong_backend: &KongBackend,
+    agent: &Agent,
+    snapshot: &Snapshot,
+    options: &RestoreOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let state = &snapshot.state;
+    if snapshot.canister_id != KONG_BACKEND {
+        warn!("Snapshot taken from {}, restoring into {}", snapshot.canister_id, KONG_BACKEND);
+    }
+
+    let live_kong_settings = backup_kong_settings(kong_backend).await?;
+    let mut problems = validate_state(state);
+    problems.extend(newer_records(&live_kong_settings, &state.kong_settings));
+    if options.skip_ledger_check {
+        warn!("Skipping pool balances vs ledger balances check");
+    } else {
+        problems.extend(check_ledger_balances(agent, state).await?);
+    }
+    if !problems.is_empty() {
+        for problem in problems.iter() {
+            error!("Restore: {}", problem);
+        }
+        return Err(format!("Snapshot failed {} checks, nothing restored", problems.len()).into());
+    }
+    info!("Snapshot passed all checks");
+
+    if options.dry_run {
+        info!("Dry run, nothing restored");
+        return Ok(());
+    }
+
+    // referenced records first, kong_settings (with the map counters) last
+    restore_records("tokens", &state.tokens, |token| token.token_id() as u64, |json| async move {
+        kong_backend.update_tokens(&json).await
+    })
+    .await?;
+    restore_records("users", &state.users, |user| user.user_id as u64, |json| async move {
+        kong_backend.update_users(&json).await
+    })
+    .await?;
+    restore_records("pools", &state.pools, |pool| pool.pool_id as u64, |json| async move {
+        kong_backend.update_pools(&json).await
+    })
+    .await?;
+    restore_records("lp_tokens", &state.lp_tokens, |lp_token| lp_token.lp_token_id, |json| async move {
+        kong_backend.update_lp_tokens(&json).await
+    })
+    .await?;
+    restore_records("claims", &state.claims, |claim| claim.claim_id, |json| async move {
+        kong_backend.update_claims(&json).await
+    })
+    .await?;
+    // re-read the counters, kong_backend may have written txs, requests or transfers during the restore
+    let live_kong_settings = backup_kong_settings(kong_backend).await?;
+    kong_backend
+        .update_kong_settings(&serde_json::to_string(&merge_counters(&live_kong_settings, &state.kong_settings))?)
+        .await?;
+    info!("Restored kong_settings");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kong_settings(user_map_idx: u32, pool_map_idx: u32, tx_map_idx: u64, claim_map_idx: u64) -> StableKongSettings {
+        StableKongSettings {
+            user_map_idx,
+            pool_map_idx,
+            tx_map_idx,
+            claim_map_idx,
+            ..StableKongSettings::default()
+        }
+    }
+
+    #[test]
+    fn restore_refuses_records_newer_than_snapshot() {
+        let snapshot = kong_settings(10, 5, 100, 3);
+        let live = kong_settings(12, 5, 150, 3);
+
+        let problems = newer_records(&live, &snapshot);
+        assert_eq!(
+            problems,
+            vec!["kong_backend has users created after the snapshot (ids 11 to 12)".to_string()]
+        );
+        assert!(newer_records(&snapshot, &snapshot).is_empty());
+    }
+
+    #[test]
+    fn restored_counters_never_go_below_live() {
+        let snapshot = StableKongSettings {
+            maintenance_mode: true,
+            ..kong_settings(10, 7, 100, 3)
+        };
+        let live = kong_settings(8, 9, 150, 3);
+
+        let merged = merge_counters(&live, &snapshot);
+        assert_eq!(merged.user_map_idx, 10);
+        assert_eq!(merged.pool_map_idx, 9);
+        assert_eq!(merged.tx_map_idx, 150);
+        assert_eq!(merged.claim_map_idx, 3);
+        // everything else comes from the snapshot
+        assert!(merged.maintenance_mode);
+    }
+}
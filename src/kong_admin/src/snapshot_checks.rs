@@ -0,0 +1,148 @@
+use candid::{Decode, Encode, Nat, Principal};
+use ic_agent::Agent;
+use icrc_ledger_types::icrc1::account::Account;
+use kong_lib::stable_claim::stable_claim::ClaimStatus;
+use kong_lib::stable_token::stable_token::StableToken;
+use kong_lib::stable_token::token::Token;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::snapshot::SnapshotState;
+
+/// Checks referential integrity and invariants of a snapshot. Returns a description of every problem found
+///
+/// - pools -> tokens (token_0 and token_1 are IC tokens, lp_token_id is an LP token)
+/// - lp_tokens -> users and LP tokens, claims -> users and tokens, kingkong -> users
+/// - map counters are at least the highest id of each map, so restored ids are not handed out again
+/// - a pool holds liquidity if and only if the sum of its LP balances is non-zero
+pub fn validate_state(state: &SnapshotState) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let user_ids: BTreeSet<u32> = state.users.iter().map(|user| user.user_id).collect();
+    let tokens: BTreeMap<u32, &StableToken> = state.tokens.iter().map(|token| (token.token_id(), token)).collect();
+    let is_ic_token = |token_id: u32| matches!(tokens.get(&token_id), Some(StableToken::IC(_)));
+    let is_lp_token = |token_id: u32| matches!(tokens.get(&token_id), Some(StableToken::LP(_)));
+
+    for pool in state.pools.iter() {
+        for token_id in [pool.token_id_0, pool.token_id_1] {
+            if !is_ic_token(token_id) {
+                problems.push(format!("pool_id={} token_id={} is not an IC token", pool.pool_id, token_id));
+            }
+        }
+        if !is_lp_token(pool.lp_token_id) {
+            problems.push(format!("pool_id={} lp_token_id={} is not an LP token", pool.pool_id, pool.lp_token_id));
+        }
+    }
+    for lp_token in state.lp_tokens.iter() {
+        if !user_ids.contains(&lp_token.user_id) {
+            problems.push(format!("lp_token_id={} user_id={} not found", lp_token.lp_token_id, lp_token.user_id));
+        }
+        if !is_lp_token(lp_token.token_id) {
+            problems.push(format!(
+                "lp_token_id={} token_id={} is not an LP token",
+                lp_token.lp_token_id, lp_token.token_id
+            ));
+        }
+    }
+    for claim in state.claims.iter() {
+        if !user_ids.contains(&claim.user_id) {
+            problems.push(format!("claim_id={} user_id={} not found", claim.claim_id, claim.user_id));
+        }
+        if !tokens.contains_key(&claim.token_id) {
+            problems.push(format!("claim_id={} token_id={} not found", claim.claim_id, claim.token_id));
+        }
+    }
+    for user_id in state.kong_settings.kingkong.iter() {
+        if !user_ids.contains(user_id) {
+            problems.push(format!("kingkong user_id={} not found", user_id));
+        }
+    }
+
+    let kong_settings = &state.kong_settings;
+    let counters = [
+        ("user_map_idx", kong_settings.user_map_idx as u64, user_ids.last().map(|id| *id as u64)),
+        ("token_map_idx", kong_settings.token_map_idx as u64, tokens.keys().last().map(|id| *id as u64)),
+        (
+            "pool_map_idx",
+            kong_settings.pool_map_idx as u64,
+            state.pools.iter().map(|pool| pool.pool_id as u64).max(),
+        ),
+        (
+            "lp_token_map_idx",
+            kong_settings.lp_token_map_idx,
+            state.lp_tokens.iter().map(|lp_token| lp_token.lp_token_id).max(),
+        ),
+        (
+            "claim_map_idx",
+            kong_settings.claim_map_idx,
+            state.claims.iter().map(|claim| claim.claim_id).max(),
+        ),
+    ];
+    for (name, counter, max_id) in counters {
+        if let Some(max_id) = max_id {
+            if counter < max_id {
+                problems.push(format!("{}={} is below the highest id {}", name, counter, max_id));
+            }
+        }
+    }
+
+    // LP token supply is the sum of the users' LP balances
+    let mut lp_supply: BTreeMap<u32, Nat> = BTreeMap::new();
+    for lp_token in state.lp_tokens.iter() {
+        *lp_supply.entry(lp_token.token_id).or_default() += lp_token.amount.clone();
+    }
+    let zero = Nat::from(0_u32);
+    for pool in state.pools.iter().filter(|pool| !pool.is_removed) {
+        let has_liquidity = pool.balance_0 > zero || pool.balance_1 > zero;
+        let supply = lp_supply.get(&pool.lp_token_id).unwrap_or(&zero);
+        if has_liquidity != (*supply > zero) {
+            problems.push(format!(
+                "pool_id={} balance_0={} balance_1={} but LP token supply is {}",
+                pool.pool_id, pool.balance_0, pool.balance_1, supply
+            ));
+        }
+    }
+
+    problems
+}
+
+async fn icrc1_balance_of(agent: &Agent, ledger: &Principal, account: &Account) -> Result<Nat, Box<dyn std::error::Error>> {
+    let result = agent.query(ledger, "icrc1_balance_of").with_arg(Encode!(account)?).await?;
+    Ok(Decode!(result.as_slice(), Nat)?)
+}
+
+/// Compares what kong_backend owes for each IC token (pool balances, fees and unclaimed claims)
+/// against its balance on the token's ledger. Returns a description of every shortfall
+pub async fn check_ledger_balances(agent: &Agent, state: &SnapshotState) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut expected_balances: BTreeMap<u32, Nat> = BTreeMap::new();
+    for pool in state.pools.iter() {
+        *expected_balances.entry(pool.token_id_0).or_default() +=
+            pool.balance_0.clone() + pool.lp_fee_0.clone() + pool.kong_fee_0.clone();
+        *expected_balances.entry(pool.token_id_1).or_default() +=
+            pool.balance_1.clone() + pool.lp_fee_1.clone() + pool.kong_fee_1.clone();
+    }
+    for claim in state.claims.iter().filter(|claim| claim.status == ClaimStatus::Unclaimed) {
+        *expected_balances.entry(claim.token_id).or_default() += claim.amount.clone();
+    }
+
+    let mut problems = Vec::new();
+    let kong_backend = &state.kong_settings.kong_backend;
+    for token in state.tokens.iter() {
+        let StableToken::IC(ic_token) = token else {
+            continue;
+        };
+        let Some(expected_balance) = expected_balances.get(&ic_token.token_id) else {
+            continue;
+        };
+        let actual_balance = icrc1_balance_of(agent, &ic_token.canister_id, kong_backend)
+            .await
+            .map_err(|e| format!("{} balance on ledger {} failed: {}", ic_token.symbol, ic_token.canister_id, e))?;
+        if actual_balance < *expected_balance {
+            problems.push(format!(
+                "{} ledger balance {} is below pool balances and unclaimed claims {}",
+                ic_token.symbol, actual_balance, expected_balance
+            ));
+        }
+    }
+
+    Ok(problems)
+}
@@ -0,0 +1,90 @@
+use kong_lib::stable_db_update::stable_db_update::StableDBUpdate;
+use tokio_postgres::Client;
+
+// Bookkeeping of the db_updates sync
+//
+// - sync_state: last db_update_id applied. Advanced in the same transaction as the batch it
+//   covers, so the database never has a batch applied without its sync point (or vice versa).
+// - db_update_dead_letters: db_updates that failed to insert, with the error, for replay.
+// - db_update_gaps: ranges of db_update_ids missing from the kong_data sequence.
+
+pub async fn create_sync_tables(db_client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    db_client
+        .batch_execute(
+            "CREATE TABLE IF NOT EXISTS sync_state (
+                id INTEGER PRIMARY KEY DEFAULT 1,
+                last_db_update_id BIGINT NOT NULL,
+                last_updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                CONSTRAINT single_row CHECK (id = 1)
+            );
+            CREATE TABLE IF NOT EXISTS db_update_dead_letters (
+                db_update_id BIGINT PRIMARY KEY,
+                memory_type TEXT NOT NULL,
+                db_update JSONB NOT NULL,
+                error TEXT NOT NULL,
+                failed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE IF NOT EXISTS db_update_gaps (
+                from_db_update_id BIGINT PRIMARY KEY,
+                to_db_update_id BIGINT NOT NULL,
+                detected_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );",
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Advances the sync point. Called inside the transaction of the batch being applied
+pub async fn save_sync_state(db_client: &Client, last_db_update_id: u64) -> Result<(), Box<dyn std::error::Error>> {
+    db_client
+        .execute(
+            "INSERT INTO sync_state (id, last_db_update_id, last_updated_at)
+            VALUES (1, $1, CURRENT_TIMESTAMP)
+            ON CONFLICT (id) DO UPDATE SET
+                last_db_update_id = $1,
+                last_updated_at = CURRENT_TIMESTAMP",
+            &[&(last_db_update_id as i64)],
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn insert_dead_letter(db_client: &Client, db_update: &StableDBUpdate, error: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let db_update_id = db_update.db_update_id as i64;
+    let json = serde_json::to_value(db_update)?;
+    let memory_type = json["stable_memory"]
+        .as_object()
+        .and_then(|memory| memory.keys().next().cloned())
+        .unwrap_or_default();
+
+    db_client
+        .execute(
+            "INSERT INTO db_update_dead_letters (db_update_id, memory_type, db_update, error, failed_at)
+            VALUES ($1, $2, $3, $4, CURRENT_TIMESTAMP)
+            ON CONFLICT (db_update_id) DO UPDATE SET
+                memory_type = $2,
+                db_update = $3,
+                error = $4,
+                failed_at = CURRENT_TIMESTAMP",
+            &[&db_update_id, &memory_type, &json, &error],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Records db_update_ids from_db_update_id..=to_db_update_id as missing
+pub async fn insert_gap(db_client: &Client, from_db_update_id: u64, to_db_update_id: u64) -> Result<(), Box<dyn std::error::Error>> {
+    db_client
+        .execute(
+            "INSERT INTO db_update_gaps (from_db_update_id, to_db_update_id, detected_at)
+            VALUES ($1, $2, CURRENT_TIMESTAMP)
+            ON CONFLICT (from_db_update_id) DO NOTHING",
+            &[&(from_db_update_id as i64), &(to_db_update_id as i64)],
+        )
+        .await?;
+
+    Ok(())
+}
@@ -484,6 +484,9 @@ pub async fn insert_tx_on_database(
                 )
                 .await?;
 
+            // replace the legs of a re-applied swap instead of duplicating them
+            db_client.execute("DELETE FROM swap_pool_tx WHERE tx_id = $1", &[&tx_id]).await?;
+
             for swap in v.txs.iter() {
                 let pool_id = swap.pool_id as i32;
                 let pay_token_id = swap.pay_token_id as i32;
@@ -0,0 +1,82 @@
+use serde_json::Value;
+use tokio_postgres::Client;
+
+use super::kong_data::KongData;
+
+// kong_data status counters and the tables holding the same records
+const RECONCILED_TABLES: [(&str, &str); 8] = [
+    ("# of users", "users"),
+    ("# of tokens", "tokens"),
+    ("# of pools", "pools"),
+    ("# of requests", "requests"),
+    ("# of txs", "txs"),
+    ("# of transfers", "transfers"),
+    ("# of claims", "claims"),
+    ("# of LP positions", "lp_tokens"),
+];
+
+/// Reconciles the row counts of the synced tables with kong_data and reports dead letters and gaps
+///
+/// Returns an error if any table is out of sync, so it can be used in scripts.
+pub async fn verify_database(kong_data: &KongData, db_client: &Client) -> Result<(), Box<dyn std::error::Error>> {
+    let status: Value = serde_json::from_str(&kong_data.status().await?)?;
+
+    println!("{:<12} {:>12} {:>12} {:>10}", "table", "kong_data", "database", "diff");
+    let mut num_mismatches = 0;
+    for (counter, table) in RECONCILED_TABLES {
+        let kong_data_count = status[counter].as_i64().ok_or(format!("{} missing from kong_data status", counter))?;
+        let row = db_client.query_one(&format!("SELECT COUNT(*) FROM {}", table), &[]).await?;
+        let database_count: i64 = row.get(0);
+        let diff = database_count - kong_data_count;
+        if diff != 0 {
+            num_mismatches += 1;
+        }
+        println!(
+            "{:<12} {:>12} {:>12} {:>10}{}",
+            table,
+            kong_data_count,
+            database_count,
+            diff,
+            if diff != 0 { "  MISMATCH" } else { "" }
+        );
+    }
+
+    let row = db_client
+        .query_opt("SELECT last_db_update_id FROM sync_state WHERE id = 1", &[])
+        .await?;
+    let last_db_update_id: Option<i64> = row.map(|row| row.get(0));
+    println!("\nlast db_update_id: {:?}", last_db_update_id);
+
+    let rows = db_client
+        .query(
+            "SELECT memory_type, COUNT(*) FROM db_update_dead_letters GROUP BY memory_type ORDER BY memory_type",
+            &[],
+        )
+        .await?;
+    println!("dead letters: {}", rows.iter().map(|row| row.get::<_, i64>(1)).sum::<i64>());
+    for row in rows {
+        let memory_type: String = row.get(0);
+        let count: i64 = row.get(1);
+        println!("  {}: {}", memory_type, count);
+    }
+
+    let rows = db_client
+        .query(
+            "SELECT from_db_update_id, to_db_update_id FROM db_update_gaps ORDER BY from_db_update_id",
+            &[],
+        )
+        .await?;
+    println!("gaps: {}", rows.len());
+    for row in rows {
+        let from_db_update_id: i64 = row.get(0);
+        let to_db_update_id: i64 = row.get(1);
+        println!("  db_update_id={}..={}", from_db_update_id, to_db_update_id);
+    }
+
+    if num_mismatches > 0 {
+        return Err(format!("{} tables out of sync with kong_data", num_mismatches).into());
+    }
+    println!("\nAll tables in sync with kong_data");
+
+    Ok(())
+}
@@ -1,14 +1,13 @@
 use candid::Nat;
 use icrc_ledger_types::icrc1::account::Account;
+use kong_lib::helpers::pool_math::{self, PoolReserves};
 
 use super::add_liquidity::TokenIndex;
 use super::add_liquidity_args::AddLiquidityArgs;
 use super::add_liquidity_reply::AddLiquidityReply;
 use super::add_liquidity_reply_helpers::{to_add_liquidity_reply, to_add_liquidity_reply_failed};
 
-use crate::helpers::nat_helpers::{
-    nat_add, nat_divide, nat_is_zero, nat_multiply, nat_sqrt, nat_subtract, nat_to_decimal_precision, nat_zero,
-};
+use crate::helpers::nat_helpers::{nat_add, nat_is_zero, nat_subtract, nat_zero};
 use crate::ic::{
     address::Address,
     get_time::get_time,
@@ -105,84 +104,23 @@ async fn check_arguments(args: &AddLiquidityArgs) -> Result<(u32, StablePool, Na
 pub fn calculate_amounts(token_0: &str, amount_0: &Nat, token_1: &str, amount_1: &Nat) -> Result<(StablePool, Nat, Nat, Nat), String> {
     // Pool - make sure pool exists, refresh balances of the pool to make sure we have the latest state
     let pool = pool_map::get_by_tokens(token_0, token_1)?;
-    // Token0
-    let token_0 = pool.token_0();
     // reserve_0 is the total balance of token_0 in the pool = balance_0 + lp_fee_0
-    let reserve_0 = nat_add(&pool.balance_0, &pool.lp_fee_0);
-    // Token1
-    let token_1 = pool.token_1();
-    let reserve_1 = nat_add(&pool.balance_1, &pool.lp_fee_1);
+    let reserves = PoolReserves::new(
+        &pool.balance_0,
+        &pool.lp_fee_0,
+        pool.token_0().decimals(),
+        &pool.balance_1,
+        &pool.lp_fee_1,
+        pool.token_1().decimals(),
+    );
     // LP token
     let lp_token = pool.lp_token();
     let lp_token_id = lp_token.token_id();
     let lp_total_supply = lp_token_map::get_total_supply(lp_token_id);
 
-    if nat_is_zero(&reserve_0) || nat_is_zero(&reserve_1) {
-        // new pool as there are no balances - take user amounts as initial ratio
-        // initialize LP tokens as sqrt(amount_0 * amount_1)
-        // convert the amounts to the same decimal precision as the LP token
-        let amount_0_in_lp_token_decimals = nat_to_decimal_precision(amount_0, token_0.decimals(), lp_token.decimals());
-        let amount_1_in_lp_token_decimals = nat_to_decimal_precision(amount_1, token_1.decimals(), lp_token.decimals());
-        let add_lp_token_amount = nat_sqrt(&nat_multiply(&amount_0_in_lp_token_decimals, &amount_1_in_lp_token_decimals));
-        return Ok((pool, amount_0.clone(), amount_1.clone(), add_lp_token_amount));
-    }
-
-    // amount_0 * reserve_1 = amount_1 * reserve_0 for constant K
-    let amount_0_reserve_1 = nat_multiply(amount_0, &reserve_1);
-    let amount_1_reserve_0 = nat_multiply(amount_1, &reserve_0);
-    // if the ratio of the user amounts is the same as the pool ratio, then the amounts are correct
-    // rarely happens as there are rounding precision errors
-    if amount_0_reserve_1 == amount_1_reserve_0 {
-        // calculate the LP token amount for the user
-        // add_lp_token_amount = lp_total_supply * amount_0 / reserve_0
-        let amount_0_in_lp_token_decimals = nat_to_decimal_precision(amount_0, token_0.decimals(), lp_token.decimals());
-        let reserve_0_in_lp_token_decimals = nat_to_decimal_precision(&reserve_0, token_0.decimals(), lp_token.decimals());
-        let numerator_in_lp_token_decimals = nat_multiply(&lp_total_supply, &amount_0_in_lp_token_decimals);
-        let add_lp_token_amount =
-            nat_divide(&numerator_in_lp_token_decimals, &reserve_0_in_lp_token_decimals).ok_or("Invalid LP token amount")?;
-        return Ok((pool, amount_0.clone(), amount_1.clone(), add_lp_token_amount));
-    }
-
-    // determine if the ratio of the user amounts is same or greater than the pool ratio (reserve_1 / reserve_0)
-    // using amount_0 to calculate the amount_1 that should be added to the pool
-    // amount_1 = amount_0 * reserve_1 / reserve_0
-    // convert amount_0 and reserve_0 to token_1 decimal precision
-    let amount_0_in_token_1_decimals = nat_to_decimal_precision(amount_0, token_0.decimals(), token_1.decimals());
-    let reserve_0_in_token_1_decimals = nat_to_decimal_precision(&reserve_0, token_0.decimals(), token_1.decimals());
-    // amount_0 * reserve_1 - do the multiplication first before divison to avoid loss of precision
-    let numerator_in_token_1_decimals = nat_multiply(&amount_0_in_token_1_decimals, &reserve_1);
-    let amount_1_in_token_1_decimals =
-        nat_divide(&numerator_in_token_1_decimals, &reserve_0_in_token_1_decimals).ok_or("Invalid amount_1")?;
-    // if amount_1 is equal or greater than calculated by the pool ratio, then use amount_0 and amount_1
-    if *amount_1 >= amount_1_in_token_1_decimals {
-        // calculate the LP token amount for the user
-        // add_lp_token_amount = lp_total_supply * amount_0 / reserve_0
-        let amount_0_in_lp_token_decimals = nat_to_decimal_precision(amount_0, token_0.decimals(), lp_token.decimals());
-        let reserve_0_in_lp_token_decimals = nat_to_decimal_precision(&reserve_0, token_0.decimals(), lp_token.decimals());
-        let numerator_in_lp_token_decimals = nat_multiply(&lp_total_supply, &amount_0_in_lp_token_decimals);
-        let add_lp_token_amount =
-            nat_divide(&numerator_in_lp_token_decimals, &reserve_0_in_lp_token_decimals).ok_or("Invalid LP token amount")?;
-        return Ok((pool, amount_0.clone(), amount_1_in_token_1_decimals, add_lp_token_amount));
-    }
-
-    // using amount_1 to calculate the amount_0 that should be added to the pool
-    // amount_0 = amount_1 * reserve_0 / reserve_1
-    let amount_1_in_token_0_decimals = nat_to_decimal_precision(amount_1, token_1.decimals(), token_0.decimals());
-    let reserve_1_in_token_0_decimals = nat_to_decimal_precision(&reserve_1, token_1.decimals(), token_0.decimals());
-    let numerator_in_token_0_decimals = nat_multiply(&amount_1_in_token_0_decimals, &reserve_0);
-    let amount_0_in_token_0_decimals =
-        nat_divide(&numerator_in_token_0_decimals, &reserve_1_in_token_0_decimals).ok_or("Invalid amount_0")?;
-    if *amount_0 >= amount_0_in_token_0_decimals {
-        let amount_1_in_lp_token_decimals = nat_to_decimal_precision(amount_1, token_1.decimals(), lp_token.decimals());
-        let reserve_1_in_lp_token_decimals = nat_to_decimal_precision(&reserve_1, token_1.decimals(), lp_token.decimals());
-        let numerator_in_lp_token_decimals = nat_multiply(&lp_total_supply, &amount_1_in_lp_token_decimals);
-        let add_lp_token_amount =
-            nat_divide(&numerator_in_lp_token_decimals, &reserve_1_in_lp_token_decimals).ok_or("Invalid LP token amount")?;
-        return Ok((pool, amount_0_in_token_0_decimals, amount_1.clone(), add_lp_token_amount));
-    }
-
-    // pool ratio must have changed from initial calculation and amount_0 and amount_1 are not enough now
-    Err("Incorrect ratio of amount_0 and amount_1".to_string())
+    let (amount_0, amount_1, add_lp_token_amount) =
+        pool_math::add_liquidity_amounts(&reserves, lp_token.decimals(), &lp_total_supply, amount_0, amount_1)?;
+    Ok((pool, amount_0, amount_1, add_lp_token_amount))
 }
 
 async fn process_add_liquidity(
@@ -1,11 +1,11 @@
-use candid::{decode_one, CandidType, Nat};
+use candid::{CandidType, Nat};
 use ic_cdk::api::call::{accept_message, method_name};
 use ic_cdk::{init, post_upgrade, pre_upgrade, query, update};
 use ic_cdk_macros::inspect_message;
 use ic_cdk_timers::set_timer_interval;
 use icrc_ledger_types::icrc21::errors::ErrorInfo;
-use icrc_ledger_types::icrc21::requests::{ConsentMessageMetadata, ConsentMessageRequest};
-use icrc_ledger_types::icrc21::responses::{ConsentInfo, ConsentMessage};
+use icrc_ledger_types::icrc21::requests::ConsentMessageRequest;
+use icrc_ledger_types::icrc21::responses::ConsentInfo;
 use serde::Deserialize;
 use std::time::Duration;
 
@@ -21,18 +21,18 @@ use crate::add_token::add_token_reply::AddTokenReply;
 use crate::add_token::update_token_args::UpdateTokenArgs;
 use crate::add_token::update_token_reply::UpdateTokenReply;
 use crate::claims::claims_timer::process_claims_timer;
-use crate::helpers::nat_helpers::{nat_to_decimals_f64, nat_to_f64};
-use crate::ic::canister_address::KONG_BACKEND;
 use crate::ic::id::caller_principal_id;
 use crate::ic::logging::info_log;
+use crate::icrc21::consent_message::consent_message;
 use crate::stable_kong_settings::kong_settings_map;
+use crate::stable_lp_token::lp_token_index::create_lp_token_index;
+use crate::stable_pool::pool_index::create_pool_index;
 use crate::stable_request::request_archive::archive_request_map;
-use crate::stable_token::token::Token;
-use crate::stable_token::token_map;
 use crate::stable_transfer::transfer_archive::archive_transfer_map;
+use crate::stable_trusted_origin::trusted_origin_map;
 use crate::stable_tx::tx_archive::archive_tx_map;
 use crate::stable_user::principal_id_map::create_principal_id_map;
-use crate::swap::swap_args::SwapArgs;
+use crate::stable_user::referral_code::create_referral_code_map;
 
 // list of query calls
 // a bit hard-coded but shouldn't change often
@@ -55,6 +55,7 @@ async fn init() {
     info_log(&format!("{} canister has been initialized", APP_NAME));
 
     create_principal_id_map();
+    create_secondary_indexes();
 
     set_timer_processes().await;
 }
@@ -67,12 +68,20 @@ fn pre_upgrade() {
 #[post_upgrade]
 async fn post_upgrade() {
     create_principal_id_map();
+    create_secondary_indexes();
 
     set_timer_processes().await;
 
     info_log(&format!("{} canister is upgraded", APP_NAME));
 }
 
+// secondary indexes are kept in heap memory and need to be rebuilt from stable memory
+fn create_secondary_indexes() {
+    create_pool_index();
+    create_lp_token_index();
+    create_referral_code_map();
+}
+
 async fn set_timer_processes() {
     // start the background timer to process claims
     let _ = set_timer_interval(Duration::from_secs(kong_settings_map::get().claims_interval_secs), || {
@@ -150,132 +159,7 @@ fn icrc10_supported_standards() -> Vec<SupportedStandard> {
 
 #[update]
 fn icrc21_canister_call_consent_message(consent_msg_request: ConsentMessageRequest) -> Result<ConsentInfo, ErrorInfo> {
-    let consent_message = match consent_msg_request.method.as_str() {
-        "swap" | "swap_async" => {
-            let Ok(swap_args) = decode_one::<SwapArgs>(&consent_msg_request.arg) else {
-                Err(ErrorInfo {
-                    description: "Failed to decode SwapArgs".to_string(),
-                })?
-            };
-            let Ok(token) = token_map::get_by_token(swap_args.pay_token.as_str()) else {
-                Err(ErrorInfo {
-                    description: "Failed to get token".to_string(),
-                })?
-            };
-            let decimals = token.decimals();
-            let pay_amount = nat_to_decimals_f64(decimals, &swap_args.pay_amount).ok_or_else(|| ErrorInfo {
-                description: "Failed to convert pay amount to f64".to_string(),
-            })?;
-            let to_address = match swap_args.receive_address {
-                Some(address) => address,
-                None => caller_principal_id(),
-            };
-            let receive_token = match swap_args.receive_amount {
-                Some(amount) => {
-                    let receive_amount = nat_to_f64(&amount).ok_or_else(|| ErrorInfo {
-                        description: "Failed to convert receive amount to f64".to_string(),
-                    })?;
-                    format!("Min. amount {} {}", receive_amount, swap_args.receive_token)
-                }
-                None => {
-                    let max_slippage = swap_args.max_slippage.unwrap_or(kong_settings_map::get().default_max_slippage);
-                    format!("{} (max. slippage {}%)", swap_args.receive_token, max_slippage)
-                }
-            };
-
-            ConsentMessage::GenericDisplayMessage(format!(
-                "# Approve KongSwap swap
-                
-**Pay token:**
-{} {}
-
-**Receive token:**
-{}
-
-**Receive address:**
-{}",
-                pay_amount, swap_args.pay_token, receive_token, to_address
-            ))
-        }
-        "add_liquidity" | "add_liquidity_async" => {
-            let Ok(add_liquidity_args) = decode_one::<AddLiquidityArgs>(&consent_msg_request.arg) else {
-                Err(ErrorInfo {
-                    description: "Failed to decode AddLiquidityArgs".to_string(),
-                })?
-            };
-            let Ok(token_0) = token_map::get_by_token(add_liquidity_args.token_0.as_str()) else {
-                Err(ErrorInfo {
-                    description: "Failed to get token_0".to_string(),
-                })?
-            };
-            let decimals_0 = token_0.decimals();
-            let amount_0 = nat_to_decimals_f64(decimals_0, &add_liquidity_args.amount_0).ok_or_else(|| ErrorInfo {
-                description: "Failed to convert token_0 amount to f64".to_string(),
-            })?;
-            let Ok(token_1) = token_map::get_by_token(add_liquidity_args.token_1.as_str()) else {
-                Err(ErrorInfo {
-                    description: "Failed to get token_1".to_string(),
-                })?
-            };
-            let decimals_1 = token_1.decimals();
-            let amount_1 = nat_to_decimals_f64(decimals_1, &add_liquidity_args.amount_1).ok_or_else(|| ErrorInfo {
-                description: "Failed to convert token_1 amount to f64".to_string(),
-            })?;
-            ConsentMessage::GenericDisplayMessage(format!(
-                "# Approve KongSwap add liquidity
-
-**Token 0:**
-{} {}
-
-**Token 1:**
-{} {}",
-                amount_0, add_liquidity_args.token_0, amount_1, add_liquidity_args.token_1
-            ))
-        }
-        "add_pool" => {
-            let Ok(add_pool_args) = decode_one::<AddPoolArgs>(&consent_msg_request.arg) else {
-                Err(ErrorInfo {
-                    description: "Failed to decode AddPoolArgs".to_string(),
-                })?
-            };
-            let Ok(token_0) = token_map::get_by_token(add_pool_args.token_0.as_str()) else {
-                Err(ErrorInfo {
-                    description: "Failed to get token_0".to_string(),
-                })?
-            };
-            let decimals_0 = token_0.decimals();
-            let amount_0 = nat_to_decimals_f64(decimals_0, &add_pool_args.amount_0).ok_or_else(|| ErrorInfo {
-                description: "Failed to convert token_0 amount to f64".to_string(),
-            })?;
-            let Ok(token_1) = token_map::get_by_token(add_pool_args.token_1.as_str()) else {
-                Err(ErrorInfo {
-                    description: "Failed to get token_1".to_string(),
-                })?
-            };
-            let decimals_1 = token_1.decimals();
-            let amount_1 = nat_to_decimals_f64(decimals_1, &add_pool_args.amount_1).ok_or_else(|| ErrorInfo {
-                description: "Failed to convert token_1 amount to f64".to_string(),
-            })?;
-            ConsentMessage::GenericDisplayMessage(format!(
-                "# Approve KongSwap add pool
-
-**Token 0:**
-{} {}
-
-**Token 1:**
-{} {}",
-                amount_0, add_pool_args.token_0, amount_1, add_pool_args.token_1
-            ))
-        }
-        _ => ConsentMessage::GenericDisplayMessage(format!("Approve KongSwap to execute {}", consent_msg_request.method)),
-    };
-
-    let metadata = ConsentMessageMetadata {
-        language: "en".to_string(),
-        utc_offset_minutes: None,
-    };
-
-    Ok(ConsentInfo { metadata, consent_message })
+    consent_message(&consent_msg_request)
 }
 
 #[derive(CandidType, Clone, Debug, Deserialize)]
@@ -284,27 +168,12 @@ pub struct Icrc28TrustedOriginsResponse {
 }
 
 // list every base URL that users will authenticate to your app from
+// stored in stable memory and maintained with add_trusted_origin() and remove_trusted_origin()
 #[update]
 fn icrc28_trusted_origins() -> Icrc28TrustedOriginsResponse {
-    let trusted_origins = vec![
-        format!("https://{}.icp0.io", KONG_BACKEND),
-        #[cfg(not(feature = "prod"))]
-        format!("http://{}.localhost:4943", KONG_BACKEND),
-        #[cfg(not(feature = "prod"))]
-        format!("https://edoy4-liaaa-aaaar-qakha-cai.localhost:5173"), // svelte FE
-        #[cfg(not(feature = "prod"))]
-        format!("http://localhost:5173"),
-        #[cfg(feature = "prod")]
-        String::from("https://kongswap.io"),
-        #[cfg(feature = "prod")]
-        String::from("https://www.kongswap.io"),
-        #[cfg(feature = "prod")]
-        String::from("https://edoy4-liaaa-aaaar-qakha-cai.icp0.io"),
-        #[cfg(feature = "prod")]
-        String::from("https://dev.kongswap.io"),
-    ];
-
-    Icrc28TrustedOriginsResponse { trusted_origins }
+    Icrc28TrustedOriginsResponse {
+        trusted_origins: trusted_origin_map::get(),
+    }
 }
 
 ic_cdk::export_candid!();
@@ -3,6 +3,7 @@ use std::collections::BTreeMap;
 
 use crate::helpers::nat_helpers::nat_zero;
 use crate::ic::guards::caller_is_kingkong;
+use crate::stable_lp_token::lp_token_index::create_lp_token_index;
 use crate::stable_lp_token::lp_token_map;
 use crate::stable_lp_token::stable_lp_token::{StableLPToken, StableLPTokenId};
 use crate::stable_memory::LP_TOKEN_MAP;
@@ -62,5 +63,7 @@ fn remove_zero_lp_tokens() -> Result<String, String> {
         });
     });
 
+    create_lp_token_index();
+
     Ok("Zero LP tokens removed".to_string())
 }
@@ -8,5 +8,6 @@ mod requests;
 mod status;
 mod tokens;
 mod transfers;
+mod trusted_origins;
 mod txs;
 mod users;
@@ -0,0 +1,21 @@
+use ic_cdk::{query, update};
+use kong_lib::stable_trusted_origin::stable_trusted_origin::StableTrustedOriginLog;
+
+use crate::ic::guards::caller_is_kingkong;
+use crate::stable_trusted_origin::trusted_origin_map;
+
+#[update(hidden = true, guard = "caller_is_kingkong")]
+fn add_trusted_origin(origin: String) -> Result<Vec<String>, String> {
+    trusted_origin_map::add(&origin)
+}
+
+#[update(hidden = true, guard = "caller_is_kingkong")]
+fn remove_trusted_origin(origin: String) -> Result<Vec<String>, String> {
+    trusted_origin_map::remove(&origin)
+}
+
+/// list the audit log of changes to the trusted origins, newest first
+#[query(hidden = true, guard = "caller_is_kingkong")]
+fn trusted_origins_log(num: Option<u16>) -> Result<Vec<StableTrustedOriginLog>, String> {
+    Ok(trusted_origin_map::get_logs(num.map(|n| n as usize)))
+}
@@ -4,6 +4,7 @@ use std::collections::BTreeMap;
 use crate::ic::guards::caller_is_kingkong;
 use crate::stable_memory::{PRINCIPAL_ID_MAP, USER_MAP};
 use crate::stable_user::principal_id_map::create_principal_id_map;
+use crate::stable_user::referral_code::create_referral_code_map;
 use crate::stable_user::stable_user::{StableUser, StableUserId};
 
 const MAX_USERS: usize = 1_000;
@@ -63,6 +64,7 @@ fn update_users(stable_users_json: String) -> Result<String, String> {
     });
 
     create_principal_id_map();
+    create_referral_code_map();
 
     Ok("Users updated".to_string())
 }
@@ -80,6 +82,7 @@ fn update_user(stable_user_json: String) -> Result<String, String> {
     });
 
     create_principal_id_map();
+    create_referral_code_map();
 
     Ok("User updated".to_string())
 }
@@ -92,6 +95,7 @@ fn remove_user(user_id: u32) -> Result<String, String> {
     });
 
     create_principal_id_map();
+    create_referral_code_map();
 
     Ok("User removed".to_string())
 }
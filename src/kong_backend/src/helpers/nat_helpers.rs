@@ -49,6 +49,23 @@ pub fn nat_to_decimals_f64(decimals: u8, amount: &Nat) -> Option<f64> {
     Some(round_f64(real_amount, decimals))
 }
 
+/// Convert Nat to a decimal string with decimals without going through f64. ie. 150_000_000 with 8 decimals is "1.5"
+pub fn nat_to_decimals_string(decimals: u8, amount: &Nat) -> String {
+    let digits = amount.0.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
 // convert Nat from one decimal precision to another
 // to convert from BTC (8 digit precision) to ETH (18 digit precision), call nat_to_decimals(n, 8, 18)
 pub fn nat_to_decimal_precision(n: &Nat, from_decimal_precision: u8, to_decimal_precision: u8) -> Nat {
@@ -141,6 +158,16 @@ mod tests {
         println!("x: {}", x);
     }
 
+    #[test]
+    fn test_nat_to_decimals_string() {
+        assert_eq!(nat_to_decimals_string(8, &Nat::from(150_000_000_u64)), "1.5");
+        assert_eq!(nat_to_decimals_string(8, &Nat::from(1_u64)), "0.00000001");
+        assert_eq!(nat_to_decimals_string(8, &Nat::from(0_u64)), "0");
+        assert_eq!(nat_to_decimals_string(0, &Nat::from(42_u64)), "42");
+        let n = Nat::from(123_456_789_000_000_000_000_000_u128);
+        assert_eq!(nat_to_decimals_string(18, &n), "123456.789");
+    }
+
     #[test]
     fn test_nat_to_decimal() {
         let n = Nat::from(1_000_000_000_000_000_000_u128);
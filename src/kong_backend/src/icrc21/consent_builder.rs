@@ -0,0 +1,109 @@
+use candid::Nat;
+use icrc_ledger_types::icrc21::requests::DisplayMessageType;
+use icrc_ledger_types::icrc21::responses::{ConsentMessage, FieldsDisplay, Value};
+
+use super::consent_language::Language;
+
+use crate::helpers::nat_helpers::{nat_to_decimals_string, nat_to_u64};
+
+/// builds a consent message as a list of labelled fields so the same content can be rendered
+/// as markdown for generic displays or as ICRC-21 fields for constrained signers (ie. hardware wallets)
+pub struct ConsentBuilder {
+    language: Language,
+    intent: String,
+    fields: Vec<(String, Value)>,
+}
+
+impl ConsentBuilder {
+    pub fn new(language: Language, intent: &'static str, args: &[&str]) -> Self {
+        Self {
+            language,
+            intent: language.fill(intent, args),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn token_amount(mut self, label: &'static str, amount: &Nat, decimals: u8, symbol: &str) -> Self {
+        let value = match nat_to_u64(amount) {
+            Some(amount) => Value::TokenAmount {
+                decimals,
+                amount,
+                symbol: symbol.to_string(),
+            },
+            // TokenAmount only holds a u64. larger amounts are shown as text
+            None => Value::Text {
+                content: format!("{} {}", nat_to_decimals_string(decimals, amount), symbol),
+            },
+        };
+        self.fields.push((self.language.translate(label).to_string(), value));
+        self
+    }
+
+    pub fn text(mut self, label: &'static str, content: &str) -> Self {
+        self.fields.push((
+            self.language.translate(label).to_string(),
+            Value::Text {
+                content: content.to_string(),
+            },
+        ));
+        self
+    }
+
+    pub fn build(self, display_type: Option<&DisplayMessageType>) -> ConsentMessage {
+        match display_type {
+            Some(DisplayMessageType::FieldsDisplay) => ConsentMessage::FieldsDisplayMessage(FieldsDisplay {
+                intent: self.intent,
+                fields: self.fields,
+            }),
+            Some(DisplayMessageType::GenericDisplay) | None => ConsentMessage::GenericDisplayMessage(self.to_markdown()),
+        }
+    }
+
+    fn to_markdown(&self) -> String {
+        self.fields.iter().fold(format!("# {}", self.intent), |message, (label, value)| {
+            let value = match value {
+                Value::TokenAmount { decimals, amount, symbol } => {
+                    format!("{} {}", nat_to_decimals_string(*decimals, &Nat::from(*amount)), symbol)
+                }
+                Value::TimestampSeconds { amount } | Value::DurationSeconds { amount } => amount.to_string(),
+                Value::Text { content } => content.clone(),
+            };
+            format!("{}\n\n**{}:**\n{}", message, label, value)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consent_builder_renders_markdown_and_fields() {
+        let builder = || {
+            ConsentBuilder::new(Language::En, "Send {0}", &["ICP"])
+                .token_amount("Amount", &Nat::from(150_000_000_u64), 8, "ICP")
+                .text("To address", "aaaaa-aa")
+        };
+
+        let ConsentMessage::GenericDisplayMessage(markdown) = builder().build(None) else {
+            panic!("Expected GenericDisplayMessage");
+        };
+        assert_eq!(markdown, "# Send ICP\n\n**Amount:**\n1.5 ICP\n\n**To address:**\naaaaa-aa");
+
+        let ConsentMessage::FieldsDisplayMessage(fields) = builder().build(Some(&DisplayMessageType::FieldsDisplay)) else {
+            panic!("Expected FieldsDisplayMessage");
+        };
+        assert_eq!(fields.intent, "Send ICP");
+        assert_eq!(
+            fields.fields[0],
+            (
+                "Amount".to_string(),
+                Value::TokenAmount {
+                    decimals: 8,
+                    amount: 150_000_000,
+                    symbol: "ICP".to_string()
+                }
+            )
+        );
+    }
+}
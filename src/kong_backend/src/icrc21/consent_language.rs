@@ -0,0 +1,144 @@
+/// languages consent messages are translated to. anything else falls back to English
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    En,
+    Es,
+    Fr,
+    De,
+}
+
+impl Language {
+    /// match a BCP-47 language tag on its primary subtag. ie. "es-MX" is Spanish
+    pub fn from_tag(tag: &str) -> Language {
+        let primary = tag.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+        match primary.as_str() {
+            "es" => Language::Es,
+            "fr" => Language::Fr,
+            "de" => Language::De,
+            _ => Language::En,
+        }
+    }
+
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Es => "es",
+            Language::Fr => "fr",
+            Language::De => "de",
+        }
+    }
+
+    /// translate an English message. {0}, {1} placeholders are kept for fill()
+    pub fn translate(&self, message: &'static str) -> &'static str {
+        let translated = match self {
+            Language::En => None,
+            Language::Es => match message {
+                "Swap {0} for {1}" => Some("Intercambiar {0} por {1}"),
+                "Add liquidity to {0}" => Some("Añadir liquidez a {0}"),
+                "Create pool {0}" => Some("Crear pool {0}"),
+                "Remove liquidity from {0}" => Some("Retirar liquidez de {0}"),
+                "Send {0}" => Some("Enviar {0}"),
+                "Claim {0}" => Some("Reclamar {0}"),
+                "Add token {0}" => Some("Añadir token {0}"),
+                "Update token {0}" => Some("Actualizar token {0}"),
+                "Approve KongSwap to execute {0}" => Some("Aprobar que KongSwap ejecute {0}"),
+                "Pay amount" => Some("Cantidad a pagar"),
+                "Minimum receive amount" => Some("Cantidad mínima a recibir"),
+                "Maximum slippage" => Some("Deslizamiento máximo"),
+                "Estimated receive amount" => Some("Cantidad estimada a recibir"),
+                "Estimated slippage" => Some("Deslizamiento estimado"),
+                "Receive address" => Some("Dirección de recepción"),
+                "Amount" => Some("Cantidad"),
+                "LP fee" => Some("Comisión LP"),
+                "LP token amount" => Some("Cantidad de tokens LP"),
+                "Estimated amount" => Some("Cantidad estimada"),
+                "To address" => Some("Dirección de destino"),
+                "Claim ID" => Some("ID de reclamo"),
+                "Token" => Some("Token"),
+                _ => None,
+            },
+            Language::Fr => match message {
+                "Swap {0} for {1}" => Some("Échanger {0} contre {1}"),
+                "Add liquidity to {0}" => Some("Ajouter de la liquidité à {0}"),
+                "Create pool {0}" => Some("Créer le pool {0}"),
+                "Remove liquidity from {0}" => Some("Retirer de la liquidité de {0}"),
+                "Send {0}" => Some("Envoyer {0}"),
+                "Claim {0}" => Some("Réclamer {0}"),
+                "Add token {0}" => Some("Ajouter le jeton {0}"),
+                "Update token {0}" => Some("Mettre à jour le jeton {0}"),
+                "Approve KongSwap to execute {0}" => Some("Autoriser KongSwap à exécuter {0}"),
+                "Pay amount" => Some("Montant à payer"),
+                "Minimum receive amount" => Some("Montant minimum à recevoir"),
+                "Maximum slippage" => Some("Glissement maximum"),
+                "Estimated receive amount" => Some("Montant estimé à recevoir"),
+                "Estimated slippage" => Some("Glissement estimé"),
+                "Receive address" => Some("Adresse de réception"),
+                "Amount" => Some("Montant"),
+                "LP fee" => Some("Frais LP"),
+                "LP token amount" => Some("Montant de jetons LP"),
+                "Estimated amount" => Some("Montant estimé"),
+                "To address" => Some("Adresse de destination"),
+                "Claim ID" => Some("ID de réclamation"),
+                "Token" => Some("Jeton"),
+                _ => None,
+            },
+            Language::De => match message {
+                "Swap {0} for {1}" => Some("{0} gegen {1} tauschen"),
+                "Add liquidity to {0}" => Some("Liquidität zu {0} hinzufügen"),
+                "Create pool {0}" => Some("Pool {0} erstellen"),
+                "Remove liquidity from {0}" => Some("Liquidität aus {0} entfernen"),
+                "Send {0}" => Some("{0} senden"),
+                "Claim {0}" => Some("{0} beanspruchen"),
+                "Add token {0}" => Some("Token {0} hinzufügen"),
+                "Update token {0}" => Some("Token {0} aktualisieren"),
+                "Approve KongSwap to execute {0}" => Some("KongSwap erlauben, {0} auszuführen"),
+                "Pay amount" => Some("Zahlungsbetrag"),
+                "Minimum receive amount" => Some("Mindestbetrag"),
+                "Maximum slippage" => Some("Maximaler Slippage"),
+                "Estimated receive amount" => Some("Geschätzter Erhaltbetrag"),
+                "Estimated slippage" => Some("Geschätzter Slippage"),
+                "Receive address" => Some("Empfangsadresse"),
+                "Amount" => Some("Betrag"),
+                "LP fee" => Some("LP-Gebühr"),
+                "LP token amount" => Some("LP-Token-Betrag"),
+                "Estimated amount" => Some("Geschätzter Betrag"),
+                "To address" => Some("Zieladresse"),
+                "Claim ID" => Some("Claim-ID"),
+                "Token" => Some("Token"),
+                _ => None,
+            },
+        };
+        translated.unwrap_or(message)
+    }
+
+    /// translate message and replace {0}, {1}, ... with args
+    pub fn fill(&self, message: &'static str, args: &[&str]) -> String {
+        args.iter()
+            .enumerate()
+            .fold(self.translate(message).to_string(), |message, (i, arg)| {
+                message.replace(&format!("{{{}}}", i), arg)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_fallback_and_fill() {
+        assert_eq!(Language::from_tag("es-MX"), Language::Es);
+        assert_eq!(Language::from_tag("DE"), Language::De);
+        assert_eq!(Language::from_tag("ja"), Language::En);
+        assert_eq!(
+            Language::Es.fill("Swap {0} for {1}", &["ICP", "ckUSDT"]),
+            "Intercambiar ICP por ckUSDT"
+        );
+        assert_eq!(
+            Language::De.fill("Swap {0} for {1}", &["ICP", "ckUSDT"]),
+            "ICP gegen ckUSDT tauschen"
+        );
+        // untranslated messages stay in English
+        assert_eq!(Language::Fr.translate("Unknown label"), "Unknown label");
+    }
+}
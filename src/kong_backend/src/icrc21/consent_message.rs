@@ -0,0 +1,180 @@
+use candid::{decode_one, CandidType};
+use icrc_ledger_types::icrc21::errors::ErrorInfo;
+use icrc_ledger_types::icrc21::requests::{ConsentMessageMetadata, ConsentMessageRequest};
+use icrc_ledger_types::icrc21::responses::ConsentInfo;
+use serde::de::DeserializeOwned;
+
+use super::consent_builder::ConsentBuilder;
+use super::consent_language::Language;
+
+use crate::add_liquidity::add_liquidity_args::AddLiquidityArgs;
+use crate::add_pool::add_pool_args::AddPoolArgs;
+use crate::add_token::add_token_args::AddTokenArgs;
+use crate::add_token::update_token_args::UpdateTokenArgs;
+use crate::helpers::nat_helpers::nat_add;
+use crate::ic::id::caller_principal_id;
+use crate::remove_liquidity::remove_liquidity::calculate_amounts;
+use crate::remove_liquidity::remove_liquidity_args::RemoveLiquidityArgs;
+use crate::send::send_args::SendArgs;
+use crate::stable_claim::claim_map;
+use crate::stable_kong_settings::kong_settings_map;
+use crate::stable_pool::pool_map;
+use crate::stable_token::stable_token::StableToken;
+use crate::stable_token::token::Token;
+use crate::stable_token::token_map;
+use crate::swap::swap_amounts::swap_amounts;
+use crate::swap::swap_args::SwapArgs;
+
+/// ICRC-21 consent message for a call to kong_backend
+/// the message is rendered in the requested language and display type, falling back to English and markdown
+pub fn consent_message(request: &ConsentMessageRequest) -> Result<ConsentInfo, ErrorInfo> {
+    let language = Language::from_tag(&request.user_preferences.metadata.language);
+    let builder = match request.method.as_str() {
+        "swap" | "swap_async" => swap_consent(language, decode_arg(request)?),
+        "add_liquidity" | "add_liquidity_async" => add_liquidity_consent(language, decode_arg(request)?),
+        "add_pool" => add_pool_consent(language, decode_arg(request)?),
+        "remove_liquidity" | "remove_liquidity_async" => remove_liquidity_consent(language, decode_arg(request)?),
+        "send" => send_consent(language, decode_arg(request)?),
+        "claim" => claim_consent(language, decode_arg(request)?),
+        "add_token" => {
+            let args: AddTokenArgs = decode_arg(request)?;
+            Ok(ConsentBuilder::new(language, "Add token {0}", &[&args.token]).text("Token", &args.token))
+        }
+        "update_token" => {
+            let args: UpdateTokenArgs = decode_arg(request)?;
+            Ok(ConsentBuilder::new(language, "Update token {0}", &[&args.token]).text("Token", &args.token))
+        }
+        // admin methods guarded by caller_is_kingkong
+        method => Ok(ConsentBuilder::new(language, "Approve KongSwap to execute {0}", &[method])),
+    }?;
+
+    let consent_message = builder.build(request.user_preferences.device_spec.as_ref());
+    let metadata = ConsentMessageMetadata {
+        language: language.tag().to_string(),
+        utc_offset_minutes: None,
+    };
+
+    Ok(ConsentInfo { metadata, consent_message })
+}
+
+fn decode_arg<T: CandidType + DeserializeOwned>(request: &ConsentMessageRequest) -> Result<T, ErrorInfo> {
+    decode_one::<T>(&request.arg).map_err(|e| ErrorInfo {
+        description: format!("Failed to decode {} arguments: {}", request.method, e),
+    })
+}
+
+fn get_token(token: &str) -> Result<StableToken, ErrorInfo> {
+    token_map::get_by_token(token).map_err(|e| ErrorInfo { description: e })
+}
+
+fn swap_consent(language: Language, args: SwapArgs) -> Result<ConsentBuilder, ErrorInfo> {
+    let pay_token = get_token(&args.pay_token)?;
+    let receive_token = get_token(&args.receive_token)?;
+    let (pay_symbol, receive_symbol) = (pay_token.symbol(), receive_token.symbol());
+    let receive_address = args.receive_address.unwrap_or_else(caller_principal_id);
+
+    let mut builder = ConsentBuilder::new(language, "Swap {0} for {1}", &[&pay_symbol, &receive_symbol]).token_amount(
+        "Pay amount",
+        &args.pay_amount,
+        pay_token.decimals(),
+        &pay_symbol,
+    );
+    builder = match args.receive_amount {
+        Some(receive_amount) => builder.token_amount("Minimum receive amount", &receive_amount, receive_token.decimals(), &receive_symbol),
+        None => {
+            let max_slippage = args.max_slippage.unwrap_or(kong_settings_map::get().default_max_slippage);
+            builder.text("Maximum slippage", &format!("{}%", max_slippage))
+        }
+    };
+    // live quote from the pools. the swap itself is still protected by the minimum amount or max slippage
+    if let Ok((receive_amount, _, _, slippage, _)) = swap_amounts(&pay_token, Some(&args.pay_amount), &receive_token) {
+        builder = builder
+            .token_amount(
+                "Estimated receive amount",
+                &receive_amount,
+                receive_token.decimals(),
+                &receive_symbol,
+            )
+            .text("Estimated slippage", &format!("{:.2}%", slippage));
+    }
+
+    Ok(builder.text("Receive address", &receive_address))
+}
+
+fn add_liquidity_consent(language: Language, args: AddLiquidityArgs) -> Result<ConsentBuilder, ErrorInfo> {
+    let token_0 = get_token(&args.token_0)?;
+    let token_1 = get_token(&args.token_1)?;
+    let pool = format!("{}/{}", token_0.symbol(), token_1.symbol());
+
+    Ok(ConsentBuilder::new(language, "Add liquidity to {0}", &[&pool])
+        .token_amount("Amount", &args.amount_0, token_0.decimals(), &token_0.symbol())
+        .token_amount("Amount", &args.amount_1, token_1.decimals(), &token_1.symbol()))
+}
+
+fn add_pool_consent(language: Language, args: AddPoolArgs) -> Result<ConsentBuilder, ErrorInfo> {
+    let token_0 = get_token(&args.token_0)?;
+    let token_1 = get_token(&args.token_1)?;
+    let pool = format!("{}/{}", token_0.symbol(), token_1.symbol());
+    let lp_fee_bps = args.lp_fee_bps.unwrap_or(kong_settings_map::get().default_lp_fee_bps);
+
+    Ok(ConsentBuilder::new(language, "Create pool {0}", &[&pool])
+        .token_amount("Amount", &args.amount_0, token_0.decimals(), &token_0.symbol())
+        .token_amount("Amount", &args.amount_1, token_1.decimals(), &token_1.symbol())
+        .text("LP fee", &format!("{}%", lp_fee_bps as f64 / 100.0)))
+}
+
+fn remove_liquidity_consent(language: Language, args: RemoveLiquidityArgs) -> Result<ConsentBuilder, ErrorInfo> {
+    let pool = pool_map::get_by_tokens(&args.token_0, &args.token_1).map_err(|e| ErrorInfo { description: e })?;
+    let lp_token = pool.lp_token();
+
+    let mut builder = ConsentBuilder::new(language, "Remove liquidity from {0}", &[&pool.symbol()]).token_amount(
+        "LP token amount",
+        &args.remove_lp_token_amount,
+        lp_token.decimals(),
+        &lp_token.symbol(),
+    );
+    if let Ok((amount_0, lp_fee_0, amount_1, lp_fee_1)) = calculate_amounts(&pool, &args.remove_lp_token_amount) {
+        let (token_0, token_1) = (pool.token_0(), pool.token_1());
+        builder = builder
+            .token_amount(
+                "Estimated amount",
+                &nat_add(&amount_0, &lp_fee_0),
+                token_0.decimals(),
+                &token_0.symbol(),
+            )
+            .token_amount(
+                "Estimated amount",
+                &nat_add(&amount_1, &lp_fee_1),
+                token_1.decimals(),
+                &token_1.symbol(),
+            );
+    }
+
+    Ok(builder)
+}
+
+fn send_consent(language: Language, args: SendArgs) -> Result<ConsentBuilder, ErrorInfo> {
+    let token = get_token(&args.token)?;
+    let symbol = token.symbol();
+
+    Ok(ConsentBuilder::new(language, "Send {0}", &[&symbol])
+        .token_amount("Amount", &args.amount, token.decimals(), &symbol)
+        .text("To address", &args.to_address))
+}
+
+fn claim_consent(language: Language, claim_id: u64) -> Result<ConsentBuilder, ErrorInfo> {
+    let claim = claim_map::get_by_claim_id(claim_id).ok_or_else(|| ErrorInfo {
+        description: format!("Claim #{} not found", claim_id),
+    })?;
+    let token = claim_map::get_token(&claim);
+    let symbol = token.symbol();
+    let to_address = claim
+        .to_address
+        .as_ref()
+        .map_or_else(caller_principal_id, |address| address.to_string());
+
+    Ok(ConsentBuilder::new(language, "Claim {0}", &[&symbol])
+        .text("Claim ID", &claim_id.to_string())
+        .token_amount("Amount", &claim.amount, token.decimals(), &symbol)
+        .text("To address", &to_address))
+}
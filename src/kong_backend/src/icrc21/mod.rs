@@ -0,0 +1,3 @@
+pub mod consent_builder;
+pub mod consent_language;
+pub mod consent_message;
@@ -8,6 +8,7 @@ pub mod claims;
 pub mod controllers;
 pub mod helpers;
 pub mod ic;
+pub mod icrc21;
 pub mod pools;
 pub mod remove_liquidity;
 pub mod remove_liquidity_amounts;
@@ -21,6 +22,7 @@ pub mod stable_pool;
 pub mod stable_request;
 pub mod stable_token;
 pub mod stable_transfer;
+pub mod stable_trusted_origin;
 pub mod stable_tx;
 pub mod stable_user;
 pub mod swap;
@@ -1,12 +1,13 @@
 use candid::Nat;
 use ic_cdk::update;
 use icrc_ledger_types::icrc1::account::Account;
+use kong_lib::helpers::pool_math;
 
 use super::remove_liquidity_args::RemoveLiquidityArgs;
 use super::remove_liquidity_reply::RemoveLiquidityReply;
 use super::remove_liquidity_reply_helpers::{to_remove_liquidity_reply, to_remove_liquidity_reply_failed};
 
-use crate::helpers::nat_helpers::{nat_add, nat_divide, nat_is_zero, nat_multiply, nat_subtract, nat_zero};
+use crate::helpers::nat_helpers::{nat_add, nat_is_zero, nat_subtract, nat_zero};
 use crate::ic::{address::Address, get_time::get_time, guards::not_in_maintenance_mode, id::caller_id, transfer::icrc1_transfer};
 use crate::stable_claim::{claim_map, stable_claim::StableClaim};
 use crate::stable_kong_settings::kong_settings_map;
@@ -197,35 +198,20 @@ async fn check_arguments_with_user(args: &RemoveLiquidityArgs, user_id: u32) ->
 }
 
 pub fn calculate_amounts(pool: &StablePool, remove_lp_token_amount: &Nat) -> Result<(Nat, Nat, Nat, Nat), String> {
-    // Token0
-    let balance_0 = &pool.balance_0;
-    let lp_fee_0 = &pool.lp_fee_0;
-    // Token1
-    let balance_1 = &pool.balance_1;
-    let lp_fee_1 = &pool.lp_fee_1;
     // LP token
     let lp_token = pool.lp_token();
     let lp_token_id = lp_token.token_id();
     let lp_total_supply = lp_token_map::get_total_supply(lp_token_id);
 
-    // calculate user's payout in token_0
-    // we split the calculations for balance and fees
-    // amount_0 = balance_0 * remove_lp_token_amount / lp_total_supply
-    let numerator = nat_multiply(balance_0, remove_lp_token_amount);
-    let payout_amount_0 = nat_divide(&numerator, &lp_total_supply).ok_or("Invalid LP token amount_0")?;
-    // payout_lp_fee_0 = lp_fee_0 * remove_lp_token_amount / lp_total_supply
-    let numerator = nat_multiply(lp_fee_0, remove_lp_token_amount);
-    let payout_lp_fee_0 = nat_divide(&numerator, &lp_total_supply).ok_or("Invalid LP lp_fee_0")?;
-
-    // calculate user's payout in token_1
-    // amount_1 = balance_1 * remove_lp_token_amount / lp_total_supply
-    let numerator = nat_multiply(balance_1, remove_lp_token_amount);
-    let payout_amount_1 = nat_divide(&numerator, &lp_total_supply).ok_or("Invalid LP token amount_1")?;
-    // payout_lp_fee_1 = lp_fee_1 * remove_lp_token_amount / lp_total_supply
-    let numerator = nat_multiply(lp_fee_1, remove_lp_token_amount);
-    let payout_lp_fee_1 = nat_divide(&numerator, &lp_total_supply).ok_or("Invalid LP lp_fee_1")?;
-
-    Ok((payout_amount_0, payout_lp_fee_0, payout_amount_1, payout_lp_fee_1))
+    // calculate user's payout in token_0 and token_1, split in balance and fees
+    pool_math::remove_liquidity_amounts(
+        &pool.balance_0,
+        &pool.lp_fee_0,
+        &pool.balance_1,
+        &pool.lp_fee_1,
+        &lp_total_supply,
+        remove_lp_token_amount,
+    )
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -0,0 +1,178 @@
+use candid::Nat;
+
+use crate::helpers::nat_helpers::{nat_add, nat_subtract, nat_zero};
+use crate::stable_memory::{LP_TOKEN_MAP, LP_TOKEN_TOTAL_SUPPLY_MAP, LP_TOKEN_USER_MAP};
+
+use super::stable_lp_token::StableLPToken;
+
+/// rebuild the (user_id, token_id) to lp_token_ids index and the LP token total supplies from LP_TOKEN_MAP
+pub fn create_lp_token_index() {
+    LP_TOKEN_USER_MAP.with(|m| m.borrow_mut().clear());
+    LP_TOKEN_TOTAL_SUPPLY_MAP.with(|m| m.borrow_mut().clear());
+    LP_TOKEN_MAP.with(|m| {
+        let lp_token_map = m.borrow();
+        lp_token_map.iter().for_each(|(_, lp_token)| update_lp_token_index(&lp_token, None));
+    });
+}
+
+/// lowest lp_token_id of the user for token_id, as a scan of LP_TOKEN_MAP would return
+pub fn get_lp_token_id(user_id: u32, token_id: u32) -> Option<u64> {
+    LP_TOKEN_USER_MAP.with(|m| {
+        m.borrow()
+            .get(&(user_id, token_id))
+            .and_then(|lp_token_ids| lp_token_ids.first().copied())
+    })
+}
+
+/// all lp_token_ids of the user in LP_TOKEN_MAP order
+pub fn get_lp_token_ids_by_user_id(user_id: u32) -> Vec<u64> {
+    let mut lp_token_ids: Vec<u64> = LP_TOKEN_USER_MAP.with(|m| {
+        m.borrow()
+            .range((user_id, u32::MIN)..=(user_id, u32::MAX))
+            .flat_map(|(_, lp_token_ids)| lp_token_ids.iter().copied())
+            .collect()
+    });
+    lp_token_ids.sort_unstable();
+    lp_token_ids
+}
+
+pub fn get_total_supply(token_id: u32) -> Nat {
+    LP_TOKEN_TOTAL_SUPPLY_MAP.with(|m| m.borrow().get(&token_id).cloned().unwrap_or_else(nat_zero))
+}
+
+/// update the index after an LP token has been written to LP_TOKEN_MAP
+/// old_lp_token is the LP token previously stored under the same lp_token_id
+pub fn update_lp_token_index(lp_token: &StableLPToken, old_lp_token: Option<&StableLPToken>) {
+    if let Some(old_lp_token) = old_lp_token {
+        if (old_lp_token.user_id, old_lp_token.token_id) == (lp_token.user_id, lp_token.token_id) {
+            // only the amount can have changed
+            subtract_total_supply(old_lp_token);
+        } else {
+            remove_lp_token_index(old_lp_token);
+        }
+    }
+
+    // a user can have several LP tokens with the same token_id, keep their ids sorted
+    LP_TOKEN_USER_MAP.with(|m| {
+        let mut user_map = m.borrow_mut();
+        let lp_token_ids = user_map.entry((lp_token.user_id, lp_token.token_id)).or_default();
+        if let Err(pos) = lp_token_ids.binary_search(&lp_token.lp_token_id) {
+            lp_token_ids.insert(pos, lp_token.lp_token_id);
+        }
+    });
+    LP_TOKEN_TOTAL_SUPPLY_MAP.with(|m| {
+        let mut total_supply_map = m.borrow_mut();
+        let total_supply = total_supply_map.entry(lp_token.token_id).or_insert_with(nat_zero);
+        *total_supply = nat_add(total_supply, &lp_token.amount);
+    });
+}
+
+/// update the index after an LP token has been removed from LP_TOKEN_MAP
+pub fn remove_lp_token_index(lp_token: &StableLPToken) {
+    let key = (lp_token.user_id, lp_token.token_id);
+    LP_TOKEN_USER_MAP.with(|m| {
+        let mut user_map = m.borrow_mut();
+        if let Some(lp_token_ids) = user_map.get_mut(&key) {
+            lp_token_ids.retain(|lp_token_id| *lp_token_id != lp_token.lp_token_id);
+            if lp_token_ids.is_empty() {
+                user_map.remove(&key);
+            }
+        }
+    });
+    subtract_total_supply(lp_token);
+}
+
+fn subtract_total_supply(lp_token: &StableLPToken) {
+    LP_TOKEN_TOTAL_SUPPLY_MAP.with(|m| {
+        let mut total_supply_map = m.borrow_mut();
+        if let Some(total_supply) = total_supply_map.get_mut(&lp_token.token_id) {
+            *total_supply = nat_subtract(total_supply, &lp_token.amount).unwrap_or_else(nat_zero);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_lp_token_index() {
+        let lp_token_1 = StableLPToken {
+            lp_token_id: 1,
+            ..StableLPToken::new(10, 3, Nat::from(100_u32), 0)
+        };
+        let lp_token_2 = StableLPToken {
+            lp_token_id: 2,
+            ..StableLPToken::new(11, 3, Nat::from(50_u32), 0)
+        };
+        let lp_token_3 = StableLPToken {
+            lp_token_id: 3,
+            ..StableLPToken::new(10, 4, Nat::from(7_u32), 0)
+        };
+        update_lp_token_index(&lp_token_1, None);
+        update_lp_token_index(&lp_token_2, None);
+        update_lp_token_index(&lp_token_3, None);
+
+        assert_eq!(get_lp_token_id(10, 3), Some(1));
+        assert_eq!(get_lp_token_id(11, 4), None);
+        assert_eq!(get_lp_token_ids_by_user_id(10), vec![1, 3]);
+        assert_eq!(get_total_supply(3), Nat::from(150_u32));
+        assert_eq!(get_total_supply(5), nat_zero());
+
+        // updating the amount adjusts the total supply
+        let updated_lp_token_1 = StableLPToken {
+            amount: Nat::from(40_u32),
+            ..lp_token_1.clone()
+        };
+        update_lp_token_index(&updated_lp_token_1, Some(&lp_token_1));
+        assert_eq!(get_lp_token_id(10, 3), Some(1));
+        assert_eq!(get_total_supply(3), Nat::from(90_u32));
+
+        remove_lp_token_index(&lp_token_2);
+        assert_eq!(get_lp_token_id(11, 3), None);
+        assert_eq!(get_total_supply(3), Nat::from(40_u32));
+    }
+
+    #[test]
+    fn test_lp_token_index_with_several_lp_tokens_for_same_token() {
+        let lp_token_1 = StableLPToken {
+            lp_token_id: 1,
+            ..StableLPToken::new(10, 3, Nat::from(100_u32), 0)
+        };
+        let lp_token_2 = StableLPToken {
+            lp_token_id: 2,
+            ..StableLPToken::new(10, 4, Nat::from(20_u32), 0)
+        };
+        let lp_token_3 = StableLPToken {
+            lp_token_id: 3,
+            ..StableLPToken::new(10, 3, Nat::from(5_u32), 0)
+        };
+        // indexed out of order, as when rebuilding from an update
+        update_lp_token_index(&lp_token_3, None);
+        update_lp_token_index(&lp_token_1, None);
+        update_lp_token_index(&lp_token_2, None);
+
+        // every row of the user is returned, in lp_token_id order as a scan of LP_TOKEN_MAP
+        assert_eq!(get_lp_token_ids_by_user_id(10), vec![1, 2, 3]);
+        assert_eq!(get_lp_token_id(10, 3), Some(1));
+        assert_eq!(get_total_supply(3), Nat::from(105_u32));
+
+        // updating a row keeps it indexed once
+        let updated_lp_token_3 = StableLPToken {
+            amount: Nat::from(15_u32),
+            ..lp_token_3.clone()
+        };
+        update_lp_token_index(&updated_lp_token_3, Some(&lp_token_3));
+        assert_eq!(get_lp_token_ids_by_user_id(10), vec![1, 2, 3]);
+        assert_eq!(get_total_supply(3), Nat::from(115_u32));
+
+        // removing the lowest id falls back to the next row with the same token_id
+        remove_lp_token_index(&lp_token_1);
+        assert_eq!(get_lp_token_id(10, 3), Some(3));
+        assert_eq!(get_lp_token_ids_by_user_id(10), vec![2, 3]);
+
+        remove_lp_token_index(&updated_lp_token_3);
+        assert_eq!(get_lp_token_id(10, 3), None);
+        assert_eq!(get_lp_token_ids_by_user_id(10), vec![2]);
+    }
+}
@@ -1,8 +1,8 @@
 use candid::Nat;
 
+use super::lp_token_index;
 use super::stable_lp_token::{StableLPToken, StableLPTokenId};
 
-use crate::helpers::nat_helpers::{nat_add, nat_zero};
 use crate::ic::logging::error_log;
 use crate::stable_kong_settings::kong_settings_map;
 use crate::stable_memory::LP_TOKEN_MAP;
@@ -16,33 +16,24 @@ pub fn get_by_token_id(token_id: u32) -> Option<StableLPToken> {
 
 /// get lp_token for specific user and token
 pub fn get_by_token_id_by_user_id(token_id: u32, user_id: u32) -> Option<StableLPToken> {
-    LP_TOKEN_MAP.with(|m| {
-        m.borrow().iter().find_map(|(_, v)| {
-            if v.user_id == user_id && v.token_id == token_id {
-                return Some(v);
-            }
-            None
-        })
-    })
+    let lp_token_id = lp_token_index::get_lp_token_id(user_id, token_id)?;
+    LP_TOKEN_MAP.with(|m| m.borrow().get(&StableLPTokenId(lp_token_id)))
 }
 
 /// get lp_token for specific user
 pub fn get_by_user_id(user_id: u32) -> Vec<StableLPToken> {
+    let lp_token_ids = lp_token_index::get_lp_token_ids_by_user_id(user_id);
     LP_TOKEN_MAP.with(|m| {
-        m.borrow()
-            .iter()
-            .filter_map(|(_, v)| if v.user_id == user_id { Some(v) } else { None })
+        let map = m.borrow();
+        lp_token_ids
+            .into_iter()
+            .filter_map(|lp_token_id| map.get(&StableLPTokenId(lp_token_id)))
             .collect()
     })
 }
 
 pub fn get_total_supply(token_id: u32) -> Nat {
-    LP_TOKEN_MAP.with(|m| {
-        m.borrow()
-            .iter()
-            .filter_map(|(_, v)| if v.token_id == token_id { Some(v.amount) } else { None })
-            .fold(nat_zero(), |acc, x| nat_add(&acc, &x))
-    })
+    lp_token_index::get_total_supply(token_id)
 }
 
 pub fn insert(lp_token: &StableLPToken) -> Result<u64, String> {
@@ -56,13 +47,15 @@ pub fn insert(lp_token: &StableLPToken) -> Result<u64, String> {
         map.insert(StableLPTokenId(lp_token_id), insert_lp_token.clone());
         insert_lp_token
     });
+    lp_token_index::update_lp_token_index(&insert_lp_token, None);
 
     let _ = archive_to_kong_data(&insert_lp_token);
     Ok(insert_lp_token.lp_token_id)
 }
 
 pub fn update(lp_token: &StableLPToken) {
-    LP_TOKEN_MAP.with(|m| m.borrow_mut().insert(StableLPTokenId(lp_token.lp_token_id), lp_token.clone()));
+    let old_lp_token = LP_TOKEN_MAP.with(|m| m.borrow_mut().insert(StableLPTokenId(lp_token.lp_token_id), lp_token.clone()));
+    lp_token_index::update_lp_token_index(lp_token, old_lp_token.as_ref());
     _ = archive_to_kong_data(lp_token);
 }
 
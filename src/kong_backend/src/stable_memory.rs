@@ -1,5 +1,7 @@
+use candid::Nat;
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
 use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell};
+use kong_lib::stable_trusted_origin::trusted_origin_store::{TrustedOriginMemories, TrustedOriginStore};
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 
@@ -10,6 +12,7 @@ use crate::stable_pool::stable_pool::{StablePool, StablePoolId};
 use crate::stable_request::stable_request::{StableRequest, StableRequestId};
 use crate::stable_token::stable_token::{StableToken, StableTokenId};
 use crate::stable_transfer::stable_transfer::{StableTransfer, StableTransferId};
+use crate::stable_trusted_origin::trusted_origin_map;
 use crate::stable_tx::stable_tx::{StableTx, StableTxId};
 use crate::stable_user::banned_user_map::BannedUser;
 use crate::stable_user::stable_user::{StableUser, StableUserId};
@@ -26,6 +29,8 @@ pub const REQUEST_MEMORY_ID: MemoryId = MemoryId::new(26);
 pub const TRANSFER_MEMORY_ID: MemoryId = MemoryId::new(27);
 pub const CLAIM_MEMORY_ID: MemoryId = MemoryId::new(28);
 pub const LP_TOKEN_MEMORY_ID: MemoryId = MemoryId::new(29);
+pub const TRUSTED_ORIGINS_MEMORY_ID: MemoryId = MemoryId::new(30);
+pub const TRUSTED_ORIGIN_LOG_MEMORY_ID: MemoryId = MemoryId::new(31);
 // archives
 pub const TX_ARCHIVE_MEMORY_ID: MemoryId = MemoryId::new(204);
 pub const REQUEST_ARCHIVE_MEMORY_ID: MemoryId = MemoryId::new(205);
@@ -38,6 +43,22 @@ thread_local! {
     // static variable to list of temporary banned users
     pub static BANNED_USERS: RefCell<BTreeMap<u32, BannedUser>> = RefCell::default();
 
+    //
+    // Secondary indexes. Kept in heap memory, updated on every insert/update of the stable maps and rebuilt on init and post_upgrade
+    //
+
+    // static variable to store the map of (token_id_0, token_id_1) to pool_id
+    pub static POOL_TOKEN_IDS_MAP: RefCell<BTreeMap<(u32, u32), u32>> = RefCell::default();
+
+    // static variable to store the map of (user_id, token_id) to the sorted lp_token_ids
+    pub static LP_TOKEN_USER_MAP: RefCell<BTreeMap<(u32, u32), Vec<u64>>> = RefCell::default();
+
+    // static variable to store the total supply of each LP token by token_id
+    pub static LP_TOKEN_TOTAL_SUPPLY_MAP: RefCell<BTreeMap<u32, Nat>> = RefCell::default();
+
+    // static variable to store the map of referral code to user_id
+    pub static REFERRAL_CODE_MAP: RefCell<BTreeMap<String, u32>> = RefCell::default();
+
     // MEMORY_MANAGER is given management of the entire stable memory. Given a 'MemoryId', it can
     // return a memory that can be used by stable structures
     pub static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
@@ -88,6 +109,18 @@ thread_local! {
         RefCell::new(StableBTreeMap::init(memory_manager.get(LP_TOKEN_MEMORY_ID)))
     });
 
+    // stable memory for storing ICRC-28 trusted origins
+    // and the audit log of changes to them
+    pub static TRUSTED_ORIGINS: RefCell<TrustedOriginStore<Memory>> = with_memory_manager(|memory_manager| {
+        RefCell::new(TrustedOriginStore::init(
+            TrustedOriginMemories {
+                origins: memory_manager.get(TRUSTED_ORIGINS_MEMORY_ID),
+                log: memory_manager.get(TRUSTED_ORIGIN_LOG_MEMORY_ID),
+            },
+            trusted_origin_map::default_origins(),
+        ))
+    });
+
     //
     // Archive Stable Memory
     //
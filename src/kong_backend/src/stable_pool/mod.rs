@@ -1,4 +1,5 @@
 pub mod check_token_balance;
+pub mod pool_index;
 pub mod pool_map;
 #[allow(clippy::module_inception)]
 pub mod stable_pool;
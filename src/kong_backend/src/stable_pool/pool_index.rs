@@ -0,0 +1,97 @@
+use crate::stable_memory::{POOL_MAP, POOL_TOKEN_IDS_MAP};
+
+use super::stable_pool::StablePool;
+
+/// rebuild the (token_id_0, token_id_1) to pool_id index from POOL_MAP
+pub fn create_pool_index() {
+    POOL_TOKEN_IDS_MAP.with(|m| m.borrow_mut().clear());
+    POOL_MAP.with(|m| {
+        let pool_map = m.borrow();
+        pool_map.iter().for_each(|(_, pool)| insert_pool_id(&pool));
+    });
+}
+
+pub fn get_pool_id(token_id_0: u32, token_id_1: u32) -> Option<u32> {
+    POOL_TOKEN_IDS_MAP.with(|m| m.borrow().get(&(token_id_0, token_id_1)).copied())
+}
+
+/// update the index after a pool has been written to POOL_MAP
+/// old_pool is the pool previously stored under the same pool_id
+pub fn update_pool_index(pool: &StablePool, old_pool: Option<&StablePool>) {
+    if let Some(old_pool) = old_pool {
+        let old_token_ids = (old_pool.token_id_0, old_pool.token_id_1);
+        if old_token_ids != (pool.token_id_0, pool.token_id_1) && get_pool_id(old_token_ids.0, old_token_ids.1) == Some(pool.pool_id) {
+            // token pair of the pool has changed, point the old token pair to the next pool with the same tokens if any
+            POOL_TOKEN_IDS_MAP.with(|m| m.borrow_mut().remove(&old_token_ids));
+            let next_pool = POOL_MAP.with(|m| {
+                m.borrow().iter().find_map(|(_, v)| {
+                    if (v.token_id_0, v.token_id_1) == old_token_ids {
+                        Some(v)
+                    } else {
+                        None
+                    }
+                })
+            });
+            if let Some(next_pool) = next_pool {
+                insert_pool_id(&next_pool);
+            }
+        }
+    }
+    insert_pool_id(pool);
+}
+
+// if several pools have the same token pair, the index keeps the lowest pool_id as a scan of POOL_MAP would return
+fn insert_pool_id(pool: &StablePool) {
+    POOL_TOKEN_IDS_MAP.with(|m| {
+        m.borrow_mut()
+            .entry((pool.token_id_0, pool.token_id_1))
+            .and_modify(|pool_id| *pool_id = (*pool_id).min(pool.pool_id))
+            .or_insert(pool.pool_id);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_pool_index() {
+        let pool = StablePool {
+            pool_id: 2,
+            ..StablePool::new(1, 3, 30, 10, 4)
+        };
+        update_pool_index(&pool, None);
+        assert_eq!(get_pool_id(1, 3), Some(2));
+        assert_eq!(get_pool_id(3, 1), None);
+
+        // a pool with the same token pair and a higher pool_id does not replace the index entry
+        update_pool_index(
+            &StablePool {
+                pool_id: 5,
+                ..pool.clone()
+            },
+            None,
+        );
+        assert_eq!(get_pool_id(1, 3), Some(2));
+
+        // updating a pool with the same token pair keeps the index entry
+        update_pool_index(&pool, Some(&pool));
+        assert_eq!(get_pool_id(1, 3), Some(2));
+
+        // changing the token pair moves the index entry
+        let updated_pool = StablePool {
+            pool_id: 7,
+            token_id_1: 6,
+            ..pool.clone()
+        };
+        update_pool_index(
+            &updated_pool,
+            Some(&StablePool {
+                pool_id: 7,
+                ..pool.clone()
+            }),
+        );
+        assert_eq!(get_pool_id(1, 6), Some(7));
+        assert_eq!(get_pool_id(1, 3), Some(2));
+    }
+}
@@ -3,6 +3,7 @@ use wildmatch::WildMatch;
 use crate::ic::logging::error_log;
 use crate::stable_kong_settings::kong_settings_map;
 use crate::stable_memory::POOL_MAP;
+use crate::stable_pool::pool_index;
 use crate::stable_pool::stable_pool::{StablePool, StablePoolId};
 use crate::stable_token::stable_token::StableToken;
 use crate::stable_token::token::Token;
@@ -109,14 +110,7 @@ fn get_by_address(address: &str) -> Result<StablePool, String> {
 }
 
 pub fn get_by_token_ids(token_id_0: u32, token_id_1: u32) -> Option<StablePool> {
-    POOL_MAP.with(|m| {
-        m.borrow().iter().find_map(|(_, v)| {
-            if v.token_id_0 == token_id_0 && v.token_id_1 == token_id_1 {
-                return Some(v);
-            }
-            None
-        })
-    })
+    pool_index::get_pool_id(token_id_0, token_id_1).and_then(get_by_pool_id)
 }
 
 pub fn get_by_tokens(token_0: &str, token_1: &str) -> Result<StablePool, String> {
@@ -146,12 +140,8 @@ pub fn get() -> Vec<StablePool> {
 
 /// check if pool exists
 pub fn exists(token_0: &StableToken, token_1: &StableToken) -> bool {
-    POOL_MAP.with(|m| {
-        m.borrow().iter().any(|(_, v)| {
-            v.token_id_0 == token_0.token_id() && v.token_id_1 == token_1.token_id()
-                || v.token_id_0 == token_1.token_id() && v.token_id_1 == token_0.token_id()
-        })
-    })
+    pool_index::get_pool_id(token_0.token_id(), token_1.token_id()).is_some()
+        || pool_index::get_pool_id(token_1.token_id(), token_0.token_id()).is_some()
 }
 
 pub fn insert(pool: &StablePool) -> Result<u32, String> {
@@ -166,13 +156,15 @@ pub fn insert(pool: &StablePool) -> Result<u32, String> {
         map.insert(StablePoolId(pool_id), insert_pool.clone());
         insert_pool
     });
+    pool_index::update_pool_index(&insert_pool, None);
 
     let _ = archive_to_kong_data(&insert_pool);
     Ok(insert_pool.pool_id)
 }
 
 pub fn update(pool: &StablePool) {
-    POOL_MAP.with(|m| m.borrow_mut().insert(StablePoolId(pool.pool_id), pool.clone()));
+    let old_pool = POOL_MAP.with(|m| m.borrow_mut().insert(StablePoolId(pool.pool_id), pool.clone()));
+    pool_index::update_pool_index(pool, old_pool.as_ref());
     let _ = archive_to_kong_data(pool);
 }
 
@@ -0,0 +1 @@
+pub mod trusted_origin_map;
@@ -0,0 +1,46 @@
+use kong_lib::stable_trusted_origin::stable_trusted_origin::StableTrustedOriginLog;
+
+use crate::ic::canister_address::KONG_BACKEND;
+use crate::ic::get_time::get_time;
+use crate::ic::id::caller_principal_id;
+use crate::stable_memory::TRUSTED_ORIGINS;
+
+/// default list used on first install and on upgrade of a canister without trusted origins in stable memory
+pub fn default_origins() -> Vec<String> {
+    vec![
+        format!("https://{}.icp0.io", KONG_BACKEND),
+        #[cfg(not(feature = "prod"))]
+        format!("http://{}.localhost:4943", KONG_BACKEND),
+        #[cfg(not(feature = "prod"))]
+        String::from("https://edoy4-liaaa-aaaar-qakha-cai.localhost:5173"), // svelte FE
+        #[cfg(not(feature = "prod"))]
+        String::from("http://localhost:5173"),
+        #[cfg(feature = "prod")]
+        String::from("https://kongswap.io"),
+        #[cfg(feature = "prod")]
+        String::from("https://www.kongswap.io"),
+        #[cfg(feature = "prod")]
+        String::from("https://edoy4-liaaa-aaaar-qakha-cai.icp0.io"),
+        #[cfg(feature = "prod")]
+        String::from("https://dev.kongswap.io"),
+    ]
+}
+
+pub fn get() -> Vec<String> {
+    TRUSTED_ORIGINS.with(|s| s.borrow().get())
+}
+
+/// add origin to the trusted origins. returns the updated list
+pub fn add(origin: &str) -> Result<Vec<String>, String> {
+    TRUSTED_ORIGINS.with(|s| s.borrow_mut().add(origin, &caller_principal_id(), get_time()))
+}
+
+/// remove origin from the trusted origins. returns the updated list
+pub fn remove(origin: &str) -> Result<Vec<String>, String> {
+    TRUSTED_ORIGINS.with(|s| s.borrow_mut().remove(origin, &caller_principal_id(), get_time()))
+}
+
+/// returns the latest num audit log entries, newest first
+pub fn get_logs(num: Option<usize>) -> Vec<StableTrustedOriginLog> {
+    TRUSTED_ORIGINS.with(|s| s.borrow().get_logs(num))
+}
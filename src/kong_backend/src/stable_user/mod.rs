@@ -1,6 +1,6 @@
 pub mod banned_user_map;
 pub mod principal_id_map;
-mod referral_code;
+pub mod referral_code;
 #[allow(clippy::module_inception)]
 pub mod stable_user;
 pub mod user_map;
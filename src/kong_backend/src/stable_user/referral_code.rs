@@ -1,7 +1,9 @@
 use rand::distributions::{Alphanumeric, DistString};
 use rand::rngs::StdRng;
 
-use crate::stable_memory::USER_MAP;
+use crate::stable_memory::{REFERRAL_CODE_MAP, USER_MAP};
+
+use super::stable_user::StableUser;
 
 // default referral interval is 180 days
 // 180 days = 24 * 60 * 60 * 1_000_000_000
@@ -12,9 +14,34 @@ const REFERRAL_LENGTH: usize = 8;
 pub fn generate_referral_code(rng: &mut StdRng) -> String {
     loop {
         let referral_code = Alphanumeric.sample_string(rng, REFERRAL_LENGTH);
-        let code_exists = USER_MAP.with(|m| m.borrow().iter().any(|(_, v)| v.my_referral_code == referral_code));
-        if !code_exists {
+        if get_user_id(&referral_code).is_none() {
             return referral_code;
         }
     }
 }
+
+/// rebuild the referral code to user_id index from USER_MAP
+pub fn create_referral_code_map() {
+    REFERRAL_CODE_MAP.with(|m| m.borrow_mut().clear());
+    USER_MAP.with(|m| {
+        let user_map = m.borrow();
+        user_map.iter().for_each(|(_, user)| insert_referral_code(&user));
+    });
+}
+
+pub fn get_user_id(referral_code: &str) -> Option<u32> {
+    REFERRAL_CODE_MAP.with(|m| m.borrow().get(referral_code).copied())
+}
+
+pub fn insert_referral_code(user: &StableUser) {
+    if user.my_referral_code.is_empty() {
+        return;
+    }
+    REFERRAL_CODE_MAP.with(|m| {
+        // if several users have the same referral code, the index keeps the lowest user_id as a scan of USER_MAP would return
+        m.borrow_mut()
+            .entry(user.my_referral_code.clone())
+            .and_modify(|user_id| *user_id = (*user_id).min(user.user_id))
+            .or_insert(user.user_id);
+    });
+}
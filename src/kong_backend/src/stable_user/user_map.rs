@@ -1,5 +1,5 @@
 use super::principal_id_map;
-use super::referral_code::{generate_referral_code, REFERRAL_INTERVAL};
+use super::referral_code::{self, generate_referral_code, REFERRAL_INTERVAL};
 use super::stable_user::{StableUser, StableUserId};
 
 use crate::ic::id::{caller_principal_id, principal_id_is_not_anonymous};
@@ -61,11 +61,7 @@ pub fn get_by_caller() -> Result<Option<StableUser>, String> {
 /// * `Some(StableUser)` if user with referral code exists
 /// * `None` if user with referral code does not exist
 pub fn get_user_by_referral_code(referral_code: &str) -> Option<StableUser> {
-    USER_MAP.with(|m| {
-        m.borrow()
-            .iter()
-            .find_map(|(_, v)| if v.my_referral_code == referral_code { Some(v) } else { None })
-    })
+    referral_code::get_user_id(referral_code).and_then(get_by_user_id)
 }
 
 pub fn insert(referred_by: Option<&str>) -> Result<u32, String> {
@@ -112,8 +108,9 @@ pub fn insert(referred_by: Option<&str>) -> Result<u32, String> {
                 referred_by_expires_at,
                 ..Default::default()
             };
-            // insert to principal_id_map
+            // insert to principal_id_map and referral code index
             principal_id_map::insert_principal_id(&user);
+            referral_code::insert_referral_code(&user);
             update = true;
             user
         }
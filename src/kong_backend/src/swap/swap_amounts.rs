@@ -1,4 +1,5 @@
 use candid::Nat;
+use kong_lib::helpers::pool_math::{self, PoolReserves};
 use num::rational::BigRational;
 use num::{FromPrimitive, One, Zero};
 use num_traits::ToPrimitive;
@@ -8,9 +9,7 @@ use super::swap_calc::SwapCalc;
 use crate::helpers::math_helpers::price_rounded;
 use crate::helpers::math_helpers::round_f64;
 use crate::helpers::nat_helpers::nat_zero;
-use crate::helpers::nat_helpers::{
-    nat_add, nat_divide, nat_is_zero, nat_multiply, nat_multiply_f64, nat_subtract, nat_to_decimal_precision,
-};
+use crate::helpers::nat_helpers::{nat_multiply_f64, nat_to_decimal_precision};
 use crate::stable_pool::pool_map;
 use crate::stable_pool::stable_pool::StablePool;
 use crate::stable_token::stable_token::StableToken;
@@ -474,10 +473,16 @@ fn swap_amount_0(
     let token_1 = pool.token_1();
     let token_id_1 = token_1.token_id();
 
-    let reserve_0 = nat_add(&pool.balance_0, &pool.lp_fee_0);
-    let reserve_1 = nat_add(&pool.balance_1, &pool.lp_fee_1);
+    let reserves = PoolReserves::new(
+        &pool.balance_0,
+        &pool.lp_fee_0,
+        token_0.decimals(),
+        &pool.balance_1,
+        &pool.lp_fee_1,
+        token_1.decimals(),
+    );
 
-    if nat_is_zero(&reserve_0) || nat_is_zero(&reserve_1) {
+    if reserves.is_empty() {
         return Ok(SwapCalc {
             pool_id: pool.pool_id,
             pay_token_id: token_id_0,
@@ -505,37 +510,11 @@ fn swap_amount_0(
         Some(amount) => amount,
     };
 
-    // convert amount_0 and pool balances to the max_decimals precision
-    let max_decimals = std::cmp::max(token_0.decimals(), token_1.decimals());
-    let reserve_0_in_max_decimals = nat_to_decimal_precision(&reserve_0, token_0.decimals(), max_decimals);
-    let reserve_1_in_max_decimals = nat_to_decimal_precision(&reserve_1, token_1.decimals(), max_decimals);
-    let amount_0_in_max_decimals = nat_to_decimal_precision(amount_0, token_0.decimals(), max_decimals);
-
-    // amount_1 = (amount_0 * reserve_1) / (reserve_0 + amount_0)
-    let numerator_in_max_decimals = nat_multiply(&amount_0_in_max_decimals, &reserve_1_in_max_decimals);
-    let denominator_in_max_decimals = nat_add(&reserve_0_in_max_decimals, &amount_0_in_max_decimals);
-    let amount_1_in_max_decimals = nat_divide(&numerator_in_max_decimals, &denominator_in_max_decimals).ok_or("Invalid amount_1")?;
-
-    // calculate the LP fees
-    // any user fee discount. user.fee_level is 0 = 100% fee (no discount), 100 = 0% fee (max discount)
-    // user_lp_fee_pct = 100 - user.fee_level
-    let user_lp_fee_pct = nat_subtract(&Nat::from(100_u8), &Nat::from(user_fee_level.unwrap_or(0_u8))).unwrap_or(Nat::from(100_u8));
-    // user_lp_fee_bps = (user_lp_fee * user_lp_fee_pct) / 100 - user's fee level in bps with discount
-    let user_lp_fee_bps = nat_divide(
-        &nat_multiply(&user_lp_fee_pct, &Nat::from(use_lp_fee.unwrap_or(pool.lp_fee_bps))),
-        &Nat::from(100_u8),
-    )
-    .ok_or("Invalid LP fee")?;
-    // lp_fee_1 = (amount_1 * user_lp_fee_bps) / 10_000
-    let numerator_in_max_decimals = nat_multiply(&amount_1_in_max_decimals, &user_lp_fee_bps);
-    let lp_fee_1_in_max_decimals = nat_divide(&numerator_in_max_decimals, &Nat::from(10_000_u128)).ok_or("Invalid LP fee")?;
-
-    // convert amount_1 and lp_fee_1 from max_decimals to token_1 precision
-    let amount_1 = nat_to_decimal_precision(&amount_1_in_max_decimals, max_decimals, token_1.decimals());
-    let lp_fee = nat_to_decimal_precision(&lp_fee_1_in_max_decimals, max_decimals, token_1.decimals());
+    let lp_fee_bps = use_lp_fee.unwrap_or(pool.lp_fee_bps);
+    let (amount_1, lp_fee) = pool_math::swap_amount_0(&reserves, amount_0, lp_fee_bps, user_fee_level)?;
     let gas_fee = use_gas_fee.map_or_else(|| token_1.fee(), |fee| fee.clone());
 
-    if amount_1 > reserve_1 {
+    if amount_1 > reserves.reserve_1 {
         Err(format!("Insufficient {} in pool", token_1.symbol()))?
     }
 
@@ -566,10 +545,16 @@ fn swap_amount_1(
     let token_1 = pool.token_1();
     let token_id_1 = token_1.token_id();
 
-    let reserve_0 = nat_add(&pool.balance_0, &pool.lp_fee_0);
-    let reserve_1 = nat_add(&pool.balance_1, &pool.lp_fee_1);
+    let reserves = PoolReserves::new(
+        &pool.balance_0,
+        &pool.lp_fee_0,
+        token_0.decimals(),
+        &pool.balance_1,
+        &pool.lp_fee_1,
+        token_1.decimals(),
+    );
 
-    if nat_is_zero(&reserve_0) || nat_is_zero(&reserve_1) {
+    if reserves.is_empty() {
         return Ok(SwapCalc {
             pool_id: pool.pool_id,
             pay_token_id: token_id_1,
@@ -597,34 +582,11 @@ fn swap_amount_1(
         Some(amount) => amount,
     };
 
-    // convert amount_1 and pool balances to the max_decimals precision
-    let max_decimals = std::cmp::max(token_0.decimals(), token_1.decimals());
-    let reserve_0_in_max_decimals = nat_to_decimal_precision(&reserve_0, token_0.decimals(), max_decimals);
-    let reserve_1_in_max_decimals = nat_to_decimal_precision(&reserve_1, token_1.decimals(), max_decimals);
-    let amount_1_in_max_decimals = nat_to_decimal_precision(amount_1, token_1.decimals(), max_decimals);
-
-    // amount_0 = (amount_1 * reserve_0) / (reserve_1 + amount_1)
-    let numerator_in_max_decimals = nat_multiply(&amount_1_in_max_decimals, &reserve_0_in_max_decimals);
-    let denominator_in_max_decimals = nat_add(&reserve_1_in_max_decimals, &amount_1_in_max_decimals);
-    let amount_0_in_max_decimals = nat_divide(&numerator_in_max_decimals, &denominator_in_max_decimals).ok_or("Invalid amount_0")?;
-
-    // calculate the LP fees
-    // user_lp_fee_pct = 100 - user.fee_level
-    let user_lp_fee_pct = nat_subtract(&Nat::from(100_u8), &Nat::from(user_fee_level.unwrap_or(0_u8))).unwrap_or(Nat::from(100_u8));
-    let user_lp_fee_bps = nat_divide(
-        &nat_multiply(&user_lp_fee_pct, &Nat::from(use_lp_fee.unwrap_or(pool.lp_fee_bps))),
-        &Nat::from(100_u8),
-    )
-    .ok_or("Invalid LP fee")?;
-    let numerator_in_max_decimals = nat_multiply(&amount_0_in_max_decimals, &user_lp_fee_bps);
-    let lp_fee_0_in_max_decimals = nat_divide(&numerator_in_max_decimals, &Nat::from(10_000_u128)).ok_or("Invalid LP fee")?;
-
-    // convert amount_0 and lp_fee_0 to token_0 precision
-    let amount_0 = nat_to_decimal_precision(&amount_0_in_max_decimals, max_decimals, token_0.decimals());
-    let lp_fee = nat_to_decimal_precision(&lp_fee_0_in_max_decimals, max_decimals, token_0.decimals());
+    let lp_fee_bps = use_lp_fee.unwrap_or(pool.lp_fee_bps);
+    let (amount_0, lp_fee) = pool_math::swap_amount_1(&reserves, amount_1, lp_fee_bps, user_fee_level)?;
     let gas_fee = use_gas_fee.map_or_else(|| token_0.fee(), |fee| fee.clone());
 
-    if amount_0 > reserve_0 {
+    if amount_0 > reserves.reserve_0 {
         Err(format!("Insufficient {} in pool", token_0.symbol()))?
     }
 
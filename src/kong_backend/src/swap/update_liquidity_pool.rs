@@ -1,9 +1,10 @@
 use candid::Nat;
+use kong_lib::helpers::pool_math::split_lp_fee;
 
 use super::calculate_amounts::calculate_amounts;
 use super::swap_calc::SwapCalc;
 
-use crate::helpers::nat_helpers::{nat_add, nat_divide, nat_multiply, nat_subtract, nat_zero};
+use crate::helpers::nat_helpers::{nat_add, nat_subtract};
 use crate::stable_pool::pool_map;
 use crate::stable_request::request_map;
 use crate::stable_request::status::StatusCode;
@@ -41,24 +42,18 @@ pub fn update_liquidity_pool(
                     // user pays token_0 and receives token_1
                     pool.balance_0 = nat_add(&pool.balance_0, &swap.pay_amount); // pay_amount is in token_0
                     pool.balance_1 = subtract_liquid_balance(&pool.balance_1, &swap.receive_amount, &receive_token.symbol())?; // receive_amount is in token_1
-                                                                                                                // fees are in token_1. take out Kong's fee
-                                                                                                                // kong_fee_1 = lp_fee * kong_fee_bps / lp_fee_bps
-                                                                                                                // lp_fee_1 = lp_fee - kong_fee_1
-                    let numerator = nat_multiply(&swap.lp_fee, &Nat::from(pool.kong_fee_bps)); //swap.lp_fee is in token_1
-                    let kong_fee_1 = nat_divide(&numerator, &Nat::from(pool.lp_fee_bps)).unwrap_or(nat_zero());
-                    let lp_fee_1 = nat_subtract(&swap.lp_fee, &kong_fee_1).unwrap_or(nat_zero());
+
+                    // fees are in token_1. take out Kong's fee
+                    let (lp_fee_1, kong_fee_1) = split_lp_fee(&swap.lp_fee, pool.lp_fee_bps, pool.kong_fee_bps);
                     pool.lp_fee_1 = nat_add(&pool.lp_fee_1, &lp_fee_1);
                     pool.kong_fee_1 = nat_add(&pool.kong_fee_1, &kong_fee_1);
                 } else {
                     // user pays token_1 and receives token_0
                     pool.balance_1 = nat_add(&pool.balance_1, &swap.pay_amount); // pay_amount is in token_1
                     pool.balance_0 = subtract_liquid_balance(&pool.balance_0, &swap.receive_amount, &receive_token.symbol())?; // receive_amount is in token_0
-                                                                                                                // fees are in token_0. take out Kong's fee
-                                                                                                                // kong_fee_0 = lp_fee * kong_fee_bps / lp_fee_bps
-                                                                                                                // lp_fee_0 = lp_fee - kong_fee_0
-                    let numerator = nat_multiply(&swap.lp_fee, &Nat::from(pool.kong_fee_bps)); //swap.lp_fee is in token_0
-                    let kong_fee_0 = nat_divide(&numerator, &Nat::from(pool.lp_fee_bps)).unwrap_or(nat_zero());
-                    let lp_fee_0 = nat_subtract(&swap.lp_fee, &kong_fee_0).unwrap_or(nat_zero());
+
+                    // fees are in token_0. take out Kong's fee
+                    let (lp_fee_0, kong_fee_0) = split_lp_fee(&swap.lp_fee, pool.lp_fee_bps, pool.kong_fee_bps);
                     pool.lp_fee_0 = nat_add(&pool.lp_fee_0, &lp_fee_0);
                     pool.kong_fee_0 = nat_add(&pool.kong_fee_0, &kong_fee_0);
                 }
@@ -0,0 +1,18 @@
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+
+/// a record sent to an archive canister. data is the CBOR encoding of the record as stored in kong_data
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveRecord {
+    pub id: u64,
+    pub ts: u64,
+    #[serde(with = "serde_bytes")]
+    pub data: Vec<u8>,
+}
+
+/// init arguments of kong_data_archive
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveInitArgs {
+    pub kong_data: Principal,
+    pub max_records: u64,
+}
@@ -0,0 +1,228 @@
+use ic_cdk::api::management_canister::main::CanisterInstallMode;
+use ic_stable_structures::Storable;
+use std::cell::Cell;
+
+use super::archive_record::ArchiveRecord;
+use super::spawn_archive::{install_archive, spawn_archive};
+
+use crate::ic::get_time::get_time;
+use crate::ic::logging::error_log;
+use crate::stable_archive::archive_map;
+use crate::stable_archive::stable_archive::ArchiveKind;
+use crate::stable_index::{request_index, transfer_index, tx_index};
+use crate::stable_memory::{REQUEST_MAP, TRANSFER_MAP, TX_MAP};
+use crate::stable_request::stable_request::StableRequestId;
+use crate::stable_transfer::stable_transfer::StableTransferId;
+use crate::stable_tx::stable_tx::StableTxId;
+use crate::stable_tx::tx::Tx;
+
+// keep append_records calls below the 2MB inter-canister message limit
+const MAX_ARCHIVE_BATCH_BYTES: usize = 1_500_000;
+
+// a run older than this is considered abandoned (e.g. a callback trapped) and no longer blocks archiving
+const ARCHIVING_TIMEOUT_NS: u64 = 10 * 60 * 1_000_000_000;
+
+thread_local! {
+    // start of the running archive run (0 if none) so overlapping timer runs don't archive the same records twice
+    static ARCHIVING_STARTED_AT: Cell<u64> = const { Cell::new(0) };
+}
+
+/// move the oldest records of each kind to archive canisters once more than trigger_threshold are held locally
+pub async fn archive_records() {
+    let now = get_time();
+    let started_at = ARCHIVING_STARTED_AT.with(|f| f.get());
+    if started_at != 0 && now.saturating_sub(started_at) < ARCHIVING_TIMEOUT_NS {
+        return;
+    }
+    ARCHIVING_STARTED_AT.with(|f| f.set(now));
+
+    // transfers before requests so transfers can still be unlinked from their user
+    for kind in [ArchiveKind::Tx, ArchiveKind::Transfer, ArchiveKind::Request] {
+        if let Err(e) = archive_kind(kind).await {
+            error_log(&format!("Failed to archive {:?} records. {}", kind, e));
+        }
+    }
+
+    ARCHIVING_STARTED_AT.with(|f| f.set(0));
+}
+
+async fn archive_kind(kind: ArchiveKind) -> Result<(), String> {
+    let settings = archive_map::get_settings();
+    let num_local_records = get_num_records(kind);
+    if num_local_records <= settings.trigger_threshold {
+        return Ok(());
+    }
+
+    let archive = match archive_map::get_current_archive(kind) {
+        Some(archive) if !archive.installed => install_archive(&archive, CanisterInstallMode::Reinstall).await?,
+        Some(archive) if archive.num_records < settings.max_records_per_archive => archive,
+        _ => spawn_archive(kind).await?,
+    };
+    let num_records = settings
+        .num_records_to_archive
+        .min(num_local_records - settings.trigger_threshold)
+        .min(settings.max_records_per_archive.saturating_sub(archive.num_records));
+    let records = get_oldest_records(kind, num_records as usize);
+    if records.is_empty() {
+        return Ok(());
+    }
+
+    ic_cdk::call::<(ArchiveKind, &Vec<ArchiveRecord>), (Result<u64, String>,)>(archive.canister_id, "append_records", (kind, &records))
+        .await
+        .map_err(|e| e.1)?
+        .0?;
+
+    // only remove locally once the archive has accepted the records
+    let num_removed = remove_records(kind, &records);
+    if let Some(last_removed) = num_removed.checked_sub(1).map(|i| &records[i]) {
+        archive_map::extend_archive(archive.canister_id, last_removed.id + 1, num_removed as u64)?;
+    }
+
+    Ok(())
+}
+
+fn get_num_records(kind: ArchiveKind) -> u64 {
+    match kind {
+        ArchiveKind::Tx => TX_MAP.with(|m| m.borrow().len()),
+        ArchiveKind::Request => REQUEST_MAP.with(|m| m.borrow().len()),
+        ArchiveKind::Transfer => TRANSFER_MAP.with(|m| m.borrow().len()),
+    }
+}
+
+fn get_oldest_records(kind: ArchiveKind, num_records: usize) -> Vec<ArchiveRecord> {
+    match kind {
+        ArchiveKind::Tx => TX_MAP.with(|m| {
+            to_batch(m.borrow().iter().take(num_records).map(|(k, v)| ArchiveRecord {
+                id: k.0,
+                ts: v.ts(),
+                data: v.to_bytes().into_owned(),
+            }))
+        }),
+        ArchiveKind::Request => REQUEST_MAP.with(|m| {
+            to_batch(m.borrow().iter().take(num_records).map(|(k, v)| ArchiveRecord {
+                id: k.0,
+                ts: v.ts,
+                data: v.to_bytes().into_owned(),
+            }))
+        }),
+        ArchiveKind::Transfer => TRANSFER_MAP.with(|m| {
+            to_batch(m.borrow().iter().take(num_records).map(|(k, v)| ArchiveRecord {
+                id: k.0,
+                ts: v.ts,
+                data: v.to_bytes().into_owned(),
+            }))
+        }),
+    }
+}
+
+/// take records until the batch would exceed MAX_ARCHIVE_BATCH_BYTES
+fn to_batch(records: impl Iterator<Item = ArchiveRecord>) -> Vec<ArchiveRecord> {
+    let mut batch_bytes = 0;
+    records
+        .take_while(|record| {
+            batch_bytes += record.data.len();
+            batch_bytes <= MAX_ARCHIVE_BATCH_BYTES
+        })
+        .collect()
+}
+
+/// remove archived records, stopping at the first record updated while append_records was awaited so the archived
+/// range stays contiguous. the archive's stale copies past it are overwritten by the next run. returns the number removed
+fn remove_records(kind: ArchiveKind, records: &[ArchiveRecord]) -> usize {
+    let mut num_removed = 0;
+    match kind {
+        ArchiveKind::Tx => TX_MAP.with(|m| {
+            let mut map = m.borrow_mut();
+            for record in records {
+                match map.get(&StableTxId(record.id)) {
+                    Some(tx) if tx.to_bytes() == record.data => {
+                        map.remove(&StableTxId(record.id));
+                        tx_index::remove(&tx);
+                    }
+                    _ => break,
+                }
+                num_removed += 1;
+            }
+        }),
+        ArchiveKind::Request => REQUEST_MAP.with(|m| {
+            let mut map = m.borrow_mut();
+            for record in records {
+                match map.get(&StableRequestId(record.id)) {
+                    Some(request) if request.to_bytes() == record.data => {
+                        map.remove(&StableRequestId(record.id));
+                        request_index::remove_archived(&request);
+                    }
+                    _ => break,
+                }
+                num_removed += 1;
+            }
+        }),
+        ArchiveKind::Transfer => TRANSFER_MAP.with(|m| {
+            let mut map = m.borrow_mut();
+            for record in records {
+                match map.get(&StableTransferId(record.id)) {
+                    Some(transfer) if transfer.to_bytes() == record.data => {
+                        map.remove(&StableTransferId(record.id));
+                        transfer_index::remove(&transfer);
+                    }
+                    _ => break,
+                }
+                num_removed += 1;
+            }
+        }),
+    }
+    num_removed
+}
+
+#[cfg(test)]
+mod tests {
+    use candid::Nat;
+
+    use super::*;
+    use crate::stable_transfer::stable_transfer::StableTransfer;
+    use crate::stable_transfer::tx_id::TxId;
+
+    fn insert_transfer(transfer_id: u64, amount: u64) {
+        let transfer = StableTransfer {
+            transfer_id,
+            request_id: transfer_id,
+            is_send: true,
+            amount: Nat::from(amount),
+            token_id: 1,
+            tx_id: TxId::BlockIndex(Nat::from(transfer_id)),
+            ts: transfer_id,
+        };
+        TRANSFER_MAP.with(|m| m.borrow_mut().insert(StableTransferId(transfer_id), transfer));
+    }
+
+    #[test]
+    fn test_remove_records() {
+        for transfer_id in 0..3 {
+            insert_transfer(transfer_id, 100);
+        }
+        let records = get_oldest_records(ArchiveKind::Transfer, 2);
+        assert_eq!(records.iter().map(|record| record.id).collect::<Vec<_>>(), vec![0, 1]);
+
+        assert_eq!(remove_records(ArchiveKind::Transfer, &records), 2);
+        assert_eq!(get_num_records(ArchiveKind::Transfer), 1);
+    }
+
+    #[test]
+    fn test_remove_records_keeps_records_updated_while_archiving() {
+        for transfer_id in 0..3 {
+            insert_transfer(transfer_id, 100);
+        }
+        let records = get_oldest_records(ArchiveKind::Transfer, 3);
+        // updated during the append_records call
+        insert_transfer(1, 200);
+
+        // removal stops at the updated record so the archived range stays contiguous
+        assert_eq!(remove_records(ArchiveKind::Transfer, &records), 1);
+        TRANSFER_MAP.with(|m| {
+            let map = m.borrow();
+            assert!(map.get(&StableTransferId(0)).is_none());
+            assert_eq!(map.get(&StableTransferId(1)).unwrap().amount, Nat::from(200_u64));
+            assert!(map.get(&StableTransferId(2)).is_some());
+        });
+    }
+}
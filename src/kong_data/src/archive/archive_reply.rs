@@ -0,0 +1,21 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::stable_archive::stable_archive::ArchiveKind;
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveReply {
+    pub canister_id: String,
+    pub kind: ArchiveKind,
+    pub start_id: u64,
+    pub end_id: u64, // exclusive
+    pub num_records: u64,
+}
+
+/// part of a requested id range held by an archive. fetch with the archive's get_records
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedRangeReply {
+    pub canister_id: String,
+    pub start_id: u64,
+    pub num_ids: u64,
+}
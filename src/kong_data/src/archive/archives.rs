@@ -0,0 +1,55 @@
+use ic_cdk::query;
+
+use super::archive_reply::{ArchiveReply, ArchivedRangeReply};
+
+use crate::stable_archive::archive_map;
+use crate::stable_archive::stable_archive::{ArchiveKind, StableArchive};
+
+fn to_archive_reply(archive: &StableArchive) -> ArchiveReply {
+    ArchiveReply {
+        canister_id: archive.canister_id.to_text(),
+        kind: archive.kind,
+        start_id: archive.start_id,
+        end_id: archive.end_id,
+        num_records: archive.num_records,
+    }
+}
+
+/// list of archive canisters, oldest first
+#[query]
+fn archives(kind: Option<ArchiveKind>) -> Vec<ArchiveReply> {
+    archive_map::get_archives()
+        .iter()
+        .filter(|archive| kind.is_none_or(|kind| archive.kind == kind))
+        .map(to_archive_reply)
+        .collect()
+}
+
+/// archives holding ids in [start_id, start_id + num_ids) of kind. ids not covered are held by kong_data
+#[query]
+fn archived_ranges(kind: ArchiveKind, start_id: u64, num_ids: u64) -> Vec<ArchivedRangeReply> {
+    get_archived_ranges(kind, start_id, start_id.saturating_add(num_ids))
+}
+
+/// archived id ranges of kind below cursor (exclusive), or all of them if cursor is None. oldest first
+///
+/// archived records are not indexed by user or pool, so paged history queries return these ranges
+/// once the records held by kong_data are exhausted. the caller fetches and filters them
+pub fn get_archived_ranges_before(kind: ArchiveKind, cursor: Option<u64>) -> Vec<ArchivedRangeReply> {
+    get_archived_ranges(kind, 0, cursor.unwrap_or(u64::MAX))
+}
+
+fn get_archived_ranges(kind: ArchiveKind, start_id: u64, end_id: u64) -> Vec<ArchivedRangeReply> {
+    archive_map::get_archives()
+        .iter()
+        .filter(|archive| archive.kind == kind && archive.start_id < end_id && start_id < archive.end_id)
+        .map(|archive| {
+            let range_start_id = start_id.max(archive.start_id);
+            ArchivedRangeReply {
+                canister_id: archive.canister_id.to_text(),
+                start_id: range_start_id,
+                num_ids: end_id.min(archive.end_id) - range_start_id,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,6 @@
+pub mod archive_record;
+pub mod archive_records;
+pub mod archive_reply;
+#[allow(clippy::module_inception)]
+pub mod archives;
+pub mod spawn_archive;
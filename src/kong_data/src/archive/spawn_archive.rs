@@ -0,0 +1,65 @@
+use ic_cdk::api::management_canister::main::{
+    create_canister, install_code, CanisterInstallMode, CanisterSettings, CreateCanisterArgument, InstallCodeArgument,
+};
+
+use super::archive_record::ArchiveInitArgs;
+
+use crate::ic::logging::{error_log, info_log};
+use crate::stable_archive::archive_map;
+use crate::stable_archive::stable_archive::{ArchiveKind, StableArchive};
+
+/// create a new archive canister for kind controlled by kong_data and install kong_data_archive
+pub async fn spawn_archive(kind: ArchiveKind) -> Result<StableArchive, String> {
+    if archive_map::get_wasm().is_empty() {
+        return Err("Archive wasm not set".to_string());
+    }
+    let settings = archive_map::get_settings();
+
+    let create_args = CreateCanisterArgument {
+        settings: Some(CanisterSettings {
+            controllers: Some(vec![ic_cdk::id()]),
+            ..Default::default()
+        }),
+    };
+    let (canister_id_record,) = create_canister(create_args, settings.cycles_for_archive_creation as u128)
+        .await
+        .map_err(|e| format!("Failed to create archive: {}", e.1))?;
+    let canister_id = canister_id_record.canister_id;
+
+    // record the canister before installing so a failed install is retried on it instead of creating another
+    let archive = archive_map::insert_archive(canister_id, kind)?;
+    info_log(&format!("Created {:?} archive {}", kind, canister_id));
+
+    install_archive(&archive, CanisterInstallMode::Install).await
+}
+
+/// install kong_data_archive on an archive canister. use Reinstall to retry a failed install, the archive holds no records yet
+pub async fn install_archive(archive: &StableArchive, mode: CanisterInstallMode) -> Result<StableArchive, String> {
+    let wasm_module = archive_map::get_wasm();
+    if wasm_module.is_empty() {
+        return Err("Archive wasm not set".to_string());
+    }
+    let settings = archive_map::get_settings();
+
+    let init_args = ArchiveInitArgs {
+        kong_data: ic_cdk::id(),
+        max_records: settings.max_records_per_archive,
+    };
+    let arg = candid::encode_one(init_args).map_err(|e| format!("Failed to encode archive init args: {}", e))?;
+    if let Err(e) = install_code(InstallCodeArgument {
+        mode,
+        canister_id: archive.canister_id,
+        wasm_module,
+        arg,
+    })
+    .await
+    {
+        let message = format!("Failed to install archive {}: {}", archive.canister_id, e.1);
+        error_log(&message);
+        return Err(message);
+    }
+
+    let archive = archive_map::set_installed(archive.canister_id)?;
+    info_log(&format!("Installed {:?} archive {}", archive.kind, archive.canister_id));
+    Ok(archive)
+}
@@ -2,18 +2,37 @@ use candid::CandidType;
 use ic_cdk::api::call::{accept_message, method_name};
 use ic_cdk::{init, post_upgrade, pre_upgrade, query, update};
 use ic_cdk_macros::inspect_message;
+use ic_cdk_timers::set_timer_interval;
 use serde::Deserialize;
+use std::time::Duration;
 
 use super::{APP_NAME, APP_VERSION};
 
+use crate::archive::archive_records::archive_records;
+use crate::archive::archive_reply::{ArchiveReply, ArchivedRangeReply};
 use crate::ic::id::caller_principal_id;
 use crate::ic::logging::info_log;
+use crate::stable_archive::archive_map;
+use crate::stable_archive::stable_archive::ArchiveKind;
 use crate::stable_db_update::db_update_map::{max_db_update_id, DB_UPDATE_ID};
+use crate::stable_trusted_origin::trusted_origin_map;
 use crate::stable_user::principal_id_map::create_principal_id_map;
 
 // list of query calls
 // a bit hard-coded but shouldn't change often
-static QUERY_METHODS: [&str; 5] = ["icrc1_name", "icrc10_supported_standards", "tokens", "pools", "txs"];
+static QUERY_METHODS: [&str; 11] = [
+    "icrc1_name",
+    "icrc10_supported_standards",
+    "tokens",
+    "pools",
+    "txs",
+    "query_txs",
+    "user_requests",
+    "user_claims",
+    "user_transfers",
+    "archives",
+    "archived_ranges",
+];
 
 #[init]
 fn init() {
@@ -21,6 +40,8 @@ fn init() {
 
     create_principal_id_map();
     DB_UPDATE_ID.store(max_db_update_id(), std::sync::atomic::Ordering::SeqCst);
+
+    set_timer_processes();
 }
 
 #[pre_upgrade]
@@ -33,9 +54,20 @@ fn post_upgrade() {
     create_principal_id_map();
     DB_UPDATE_ID.store(max_db_update_id(), std::sync::atomic::Ordering::SeqCst);
 
+    set_timer_processes();
+
     info_log(&format!("{} canister is upgraded", APP_NAME));
 }
 
+fn set_timer_processes() {
+    // start the background timer to move old records to archive canisters
+    let _ = set_timer_interval(Duration::from_secs(archive_map::get_settings().archive_interval_secs), || {
+        ic_cdk::spawn(async {
+            archive_records().await;
+        });
+    });
+}
+
 /// inspect all ingress messages to the canister that are called as updates
 /// calling accept_message() will allow the message to be processed
 #[inspect_message]
@@ -80,24 +112,12 @@ pub struct Icrc28TrustedOriginsResponse {
 }
 
 // list every base URL that users will authenticate to your app from
+// stored in stable memory and maintained with add_trusted_origin() and remove_trusted_origin()
 #[update]
 fn icrc28_trusted_origins() -> Icrc28TrustedOriginsResponse {
-    let trusted_origins = vec![
-        #[cfg(not(feature = "prod"))]
-        format!("https://edoy4-liaaa-aaaar-qakha-cai.localhost:5173"), // svelte FE
-        #[cfg(not(feature = "prod"))]
-        format!("http://localhost:5173"),
-        #[cfg(feature = "prod")]
-        String::from("https://kongswap.io"),
-        #[cfg(feature = "prod")]
-        String::from("https://www.kongswap.io"),
-        #[cfg(feature = "prod")]
-        String::from("https://edoy4-liaaa-aaaar-qakha-cai.icp0.io"),
-        #[cfg(feature = "prod")]
-        String::from("https://dev.kongswap.io"),
-    ];
-
-    Icrc28TrustedOriginsResponse { trusted_origins }
+    Icrc28TrustedOriginsResponse {
+        trusted_origins: trusted_origin_map::get(),
+    }
 }
 
 ic_cdk::export_candid!();
@@ -0,0 +1,35 @@
+use ic_cdk::query;
+
+use super::claims_page_reply::ClaimsPageReply;
+use super::claims_reply_helpers::to_claims_reply;
+
+use crate::stable_claim::stable_claim::StableClaimId;
+use crate::stable_index::claim_index;
+use crate::stable_index::stable_index::page_size;
+use crate::stable_memory::CLAIM_MAP;
+use crate::stable_user::user_map;
+
+/// claims of a user, newest first. pass next_cursor back as cursor to get the next page
+#[query]
+fn user_claims(principal_id: String, cursor: Option<u64>, num_claims: Option<u16>) -> Result<ClaimsPageReply, String> {
+    let user_id = match user_map::get_by_principal_id(&principal_id)? {
+        Some(user) => user.user_id,
+        None => {
+            return Ok(ClaimsPageReply {
+                claims: Vec::new(),
+                next_cursor: None,
+            })
+        }
+    };
+    let (claim_ids, next_cursor) = claim_index::get_claim_ids_by_user_id(user_id, cursor, page_size(num_claims));
+    let claims = CLAIM_MAP.with(|m| {
+        let map = m.borrow();
+        claim_ids
+            .iter()
+            .filter_map(|&claim_id| map.get(&StableClaimId(claim_id)))
+            .map(|claim| to_claims_reply(&claim))
+            .collect()
+    });
+
+    Ok(ClaimsPageReply { claims, next_cursor })
+}
@@ -0,0 +1,10 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use super::claims_reply::ClaimsReply;
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimsPageReply {
+    pub claims: Vec<ClaimsReply>,
+    pub next_cursor: Option<u64>,
+}
@@ -1,2 +1,6 @@
 pub mod claim_reply;
+#[allow(clippy::module_inception)]
+pub mod claims;
+pub mod claims_page_reply;
 pub mod claims_reply;
+pub mod claims_reply_helpers;
@@ -0,0 +1,53 @@
+use ic_cdk::{query, update};
+use serde_bytes::ByteBuf;
+
+use crate::archive::archive_records::archive_records;
+use crate::helpers::json_helpers;
+use crate::ic::guards::caller_is_kingkong;
+use crate::stable_archive::archive_map;
+use crate::stable_archive::stable_archive::{StableArchiveSettings, StableArchiveWasm};
+use crate::stable_memory::ARCHIVE_WASM;
+
+#[query(hidden = true, guard = "caller_is_kingkong")]
+fn backup_archive_settings() -> Result<String, String> {
+    serde_json::to_string(&archive_map::get_settings()).map_err(|e| format!("Failed to serialize: {}", e))
+}
+
+/// merge update_settings into the archive settings. the archive list is managed by kong_data and cannot be changed
+#[update(hidden = true, guard = "caller_is_kingkong")]
+fn set_archive_settings(update_settings: String) -> Result<String, String> {
+    let current_settings = archive_map::get_settings();
+    let mut archive_settings_value =
+        serde_json::to_value(&current_settings).map_err(|e| format!("Failed to serialize archive settings: {}", e))?;
+
+    let updates = serde_json::from_str(&update_settings).map_err(|e| format!("Failed to parse update archive settings: {}", e))?;
+    json_helpers::merge(&mut archive_settings_value, &updates);
+
+    let archive_settings: StableArchiveSettings =
+        serde_json::from_value(archive_settings_value).map_err(|e| format!("Failed to parse updated archive settings: {}", e))?;
+    let archive_settings = StableArchiveSettings {
+        archives: current_settings.archives,
+        ..archive_settings
+    };
+    archive_map::set_settings(archive_settings.clone())?;
+
+    serde_json::to_string(&archive_settings).map_err(|e| format!("Failed to serialize: {}", e))
+}
+
+/// upload the kong_data_archive wasm used to spawn new archives
+#[update(hidden = true, guard = "caller_is_kingkong")]
+fn set_archive_wasm(wasm_module: ByteBuf) -> Result<String, String> {
+    ARCHIVE_WASM
+        .with(|w| w.borrow_mut().set(StableArchiveWasm(wasm_module.into_vec())))
+        .map_err(|e| format!("Failed to set archive wasm: {:?}", e))?;
+
+    Ok("Archive wasm updated".to_string())
+}
+
+/// run archiving now instead of waiting for the timer
+#[update(hidden = true, guard = "caller_is_kingkong")]
+async fn archive_now() -> Result<String, String> {
+    archive_records().await;
+
+    Ok("Archiving completed".to_string())
+}
@@ -6,6 +6,7 @@ use crate::ic::guards::{caller_is_kingkong, caller_is_kong_backend};
 use crate::stable_claim::stable_claim::{StableClaim, StableClaimId};
 use crate::stable_db_update::db_update_map;
 use crate::stable_db_update::stable_db_update::{StableDBUpdate, StableMemory};
+use crate::stable_index::claim_index;
 use crate::stable_memory::CLAIM_MAP;
 
 const MAX_CLAIMS: usize = 1_000;
@@ -46,7 +47,10 @@ fn update_claims(stable_claims: String) -> Result<String, String> {
     CLAIM_MAP.with(|claim_map| {
         let mut map = claim_map.borrow_mut();
         for (k, v) in claims {
-            map.insert(k, v);
+            if let Some(old_claim) = map.insert(k, v.clone()) {
+                claim_index::remove(&old_claim);
+            }
+            claim_index::insert(&v);
         }
     });
 
@@ -62,7 +66,10 @@ fn update_claim(stable_claim_json: String) -> Result<String, String> {
 
     CLAIM_MAP.with(|claim_map| {
         let mut map = claim_map.borrow_mut();
-        map.insert(StableClaimId(claim.claim_id), claim.clone());
+        if let Some(old_claim) = map.insert(StableClaimId(claim.claim_id), claim.clone()) {
+            claim_index::remove(&old_claim);
+        }
+        claim_index::insert(&claim);
     });
 
     // add to UpdateMap for archiving to database
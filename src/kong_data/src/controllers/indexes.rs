@@ -0,0 +1,61 @@
+use ic_cdk::update;
+use std::ops::Range;
+
+use crate::ic::guards::caller_is_kingkong;
+use crate::stable_claim::stable_claim::StableClaimId;
+use crate::stable_index::{claim_index, request_index, transfer_index, tx_index};
+use crate::stable_memory::{CLAIM_MAP, REQUEST_MAP, TRANSFER_MAP, TX_MAP};
+use crate::stable_request::stable_request::StableRequestId;
+use crate::stable_transfer::stable_transfer::StableTransferId;
+use crate::stable_tx::stable_tx::StableTxId;
+
+const MAX_REBUILD_IDS: u64 = 1_000;
+
+/// rebuild the secondary indexes for ids in [start_id, start_id + num_ids) of TX_MAP, REQUEST_MAP, CLAIM_MAP and TRANSFER_MAP
+/// used to index records archived before the indexes existed. call repeatedly with the returned id until all maps are covered
+#[update(hidden = true, guard = "caller_is_kingkong")]
+fn rebuild_indexes(start_id: Option<u64>, num_ids: Option<u16>) -> Result<u64, String> {
+    let start_id = start_id.unwrap_or(0);
+    let num_ids = num_ids.map_or(MAX_REBUILD_IDS, |n| n as u64);
+    let ids = start_id..start_id.saturating_add(num_ids);
+
+    rebuild_tx_index(&ids);
+    // requests before transfers so transfers can be linked to their user
+    rebuild_request_index(&ids);
+    rebuild_claim_index(&ids);
+    rebuild_transfer_index(&ids);
+
+    Ok(ids.end)
+}
+
+fn rebuild_tx_index(ids: &Range<u64>) {
+    TX_MAP.with(|m| {
+        for (_, tx) in m.borrow().range(StableTxId(ids.start)..StableTxId(ids.end)) {
+            tx_index::insert(&tx);
+        }
+    });
+}
+
+fn rebuild_request_index(ids: &Range<u64>) {
+    REQUEST_MAP.with(|m| {
+        for (_, request) in m.borrow().range(StableRequestId(ids.start)..StableRequestId(ids.end)) {
+            request_index::insert(&request);
+        }
+    });
+}
+
+fn rebuild_claim_index(ids: &Range<u64>) {
+    CLAIM_MAP.with(|m| {
+        for (_, claim) in m.borrow().range(StableClaimId(ids.start)..StableClaimId(ids.end)) {
+            claim_index::insert(&claim);
+        }
+    });
+}
+
+fn rebuild_transfer_index(ids: &Range<u64>) {
+    TRANSFER_MAP.with(|m| {
+        for (_, transfer) in m.borrow().range(StableTransferId(ids.start)..StableTransferId(ids.end)) {
+            transfer_index::insert(&transfer);
+        }
+    });
+}
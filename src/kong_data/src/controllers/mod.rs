@@ -1,6 +1,8 @@
+mod archives;
 mod claims;
 mod db_updates;
 mod event_store;
+mod indexes;
 mod kong_settings;
 mod lp_tokens;
 mod pools;
@@ -8,5 +10,6 @@ mod requests;
 mod status;
 mod tokens;
 mod transfers;
+mod trusted_origins;
 mod txs;
 mod users;
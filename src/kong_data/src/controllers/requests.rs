@@ -5,6 +5,7 @@ use crate::ic::get_time::get_time;
 use crate::ic::guards::{caller_is_kingkong, caller_is_kong_backend};
 use crate::stable_db_update::db_update_map;
 use crate::stable_db_update::stable_db_update::{StableDBUpdate, StableMemory};
+use crate::stable_index::request_index;
 use crate::stable_memory::REQUEST_MAP;
 use crate::stable_request::stable_request::{StableRequest, StableRequestId};
 
@@ -45,7 +46,10 @@ fn update_requests(stable_requests_json: String) -> Result<String, String> {
     REQUEST_MAP.with(|request_map| {
         let mut map = request_map.borrow_mut();
         for (k, v) in requests {
-            map.insert(k, v);
+            if let Some(old_request) = map.insert(k, v.clone()) {
+                request_index::remove(&old_request);
+            }
+            request_index::insert(&v);
         }
     });
 
@@ -61,7 +65,10 @@ fn update_request(stable_request_json: String) -> Result<String, String> {
 
     REQUEST_MAP.with(|request_map| {
         let mut map = request_map.borrow_mut();
-        map.insert(StableRequestId(request.request_id), request.clone());
+        if let Some(old_request) = map.insert(StableRequestId(request.request_id), request.clone()) {
+            request_index::remove(&old_request);
+        }
+        request_index::insert(&request);
     });
 
     // add to UpdateMap for archiving to database
@@ -4,6 +4,8 @@ use serde_json::json;
 
 use crate::helpers::math_helpers::{bytes_to_megabytes, to_trillions};
 use crate::ic::guards::caller_is_kingkong;
+use crate::stable_archive::archive_map;
+use crate::stable_archive::stable_archive::ArchiveKind;
 
 use crate::stable_memory::{
     CLAIM_MAP, CLAIM_MEMORY_ID, DB_UPDATE_MAP, DB_UPDATE_MEMORY_ID, KONG_SETTINGS_MEMORY_ID, LP_TOKEN_MAP, LP_TOKEN_MEMORY_ID,
@@ -90,16 +92,17 @@ pub fn get_number_of_pools() -> u64 {
     POOL_MAP.with(|m| m.borrow().len())
 }
 
+// txs, requests and transfers include the records moved to archive canisters, to match the rows synced to the database
 pub fn get_number_of_txs() -> u64 {
-    TX_MAP.with(|m| m.borrow().len())
+    TX_MAP.with(|m| m.borrow().len()) + archive_map::get_num_archived(ArchiveKind::Tx)
 }
 
 pub fn get_number_of_requests() -> u64 {
-    REQUEST_MAP.with(|m| m.borrow().len())
+    REQUEST_MAP.with(|m| m.borrow().len()) + archive_map::get_num_archived(ArchiveKind::Request)
 }
 
 pub fn get_number_of_transfers() -> u64 {
-    TRANSFER_MAP.with(|m| m.borrow().len())
+    TRANSFER_MAP.with(|m| m.borrow().len()) + archive_map::get_num_archived(ArchiveKind::Transfer)
 }
 
 pub fn get_number_of_claims() -> u64 {
@@ -5,6 +5,7 @@ use crate::ic::get_time::get_time;
 use crate::ic::guards::{caller_is_kingkong, caller_is_kong_backend};
 use crate::stable_db_update::db_update_map;
 use crate::stable_db_update::stable_db_update::{StableDBUpdate, StableMemory};
+use crate::stable_index::transfer_index;
 use crate::stable_memory::TRANSFER_MAP;
 use crate::stable_transfer::stable_transfer::{StableTransfer, StableTransferId};
 
@@ -46,7 +47,10 @@ fn update_transfers(stable_transfers_json: String) -> Result<String, String> {
     TRANSFER_MAP.with(|transfer_map| {
         let mut map = transfer_map.borrow_mut();
         for (k, v) in transfers {
-            map.insert(k, v);
+            if let Some(old_transfer) = map.insert(k, v.clone()) {
+                transfer_index::remove(&old_transfer);
+            }
+            transfer_index::insert(&v);
         }
     });
 
@@ -62,7 +66,10 @@ fn update_transfer(stable_transfer_json: String) -> Result<String, String> {
 
     TRANSFER_MAP.with(|transfer_map| {
         let mut map = transfer_map.borrow_mut();
-        map.insert(StableTransferId(transfer.transfer_id), transfer.clone());
+        if let Some(old_transfer) = map.insert(StableTransferId(transfer.transfer_id), transfer.clone()) {
+            transfer_index::remove(&old_transfer);
+        }
+        transfer_index::insert(&transfer);
     });
 
     // add to UpdateMap for archiving to database
@@ -5,6 +5,7 @@ use crate::ic::get_time::get_time;
 use crate::ic::guards::{caller_is_kingkong, caller_is_kong_backend};
 use crate::stable_db_update::db_update_map;
 use crate::stable_db_update::stable_db_update::{StableDBUpdate, StableMemory};
+use crate::stable_index::tx_index;
 use crate::stable_memory::TX_MAP;
 use crate::stable_tx::stable_tx::{StableTx, StableTxId};
 use crate::stable_tx::tx::Tx;
@@ -45,7 +46,10 @@ fn update_txs(stable_txs_json: String) -> Result<String, String> {
     TX_MAP.with(|tx_map| {
         let mut map = tx_map.borrow_mut();
         for (k, v) in txs {
-            map.insert(k, v);
+            if let Some(old_tx) = map.insert(k, v.clone()) {
+                tx_index::remove(&old_tx);
+            }
+            tx_index::insert(&v);
         }
     });
 
@@ -61,7 +65,10 @@ fn update_tx(stable_tx_json: String) -> Result<String, String> {
 
     TX_MAP.with(|tx_map| {
         let mut map = tx_map.borrow_mut();
-        map.insert(StableTxId(tx.tx_id()), tx.clone());
+        if let Some(old_tx) = map.insert(StableTxId(tx.tx_id()), tx.clone()) {
+            tx_index::remove(&old_tx);
+        }
+        tx_index::insert(&tx);
     });
 
     // add to UpdateMap for archiving to database
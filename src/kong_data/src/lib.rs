@@ -1,5 +1,6 @@
 mod add_liquidity;
 mod add_pool;
+mod archive;
 mod canister;
 mod chains;
 mod claims;
@@ -10,8 +11,10 @@ mod pools;
 mod remove_liquidity;
 mod requests;
 mod send;
+mod stable_archive;
 mod stable_claim;
 mod stable_db_update;
+mod stable_index;
 mod stable_kong_settings;
 mod stable_lp_token;
 mod stable_memory;
@@ -19,6 +22,7 @@ mod stable_pool;
 mod stable_request;
 mod stable_token;
 mod stable_transfer;
+mod stable_trusted_origin;
 mod stable_tx;
 mod stable_user;
 mod swap;
@@ -1 +1,5 @@
 pub mod request_reply;
+pub mod request_reply_helpers;
+#[allow(clippy::module_inception)]
+pub mod requests;
+pub mod requests_page_reply;
@@ -0,0 +1,48 @@
+use ic_cdk::query;
+
+use super::request_reply_helpers::to_request_reply;
+use super::requests_page_reply::RequestsPageReply;
+
+use crate::archive::archives::get_archived_ranges_before;
+use crate::stable_archive::stable_archive::ArchiveKind;
+use crate::stable_index::request_index;
+use crate::stable_index::stable_index::page_size;
+use crate::stable_memory::REQUEST_MAP;
+use crate::stable_request::stable_request::StableRequestId;
+use crate::stable_user::user_map;
+
+/// requests of a user, newest first. pass next_cursor back as cursor to get the next page.
+/// older requests moved to archive canisters are not indexed by user, their id ranges are returned in archived with the last page
+#[query]
+fn user_requests(principal_id: String, cursor: Option<u64>, num_requests: Option<u16>) -> Result<RequestsPageReply, String> {
+    let user_id = match user_map::get_by_principal_id(&principal_id)? {
+        Some(user) => user.user_id,
+        None => {
+            return Ok(RequestsPageReply {
+                requests: Vec::new(),
+                next_cursor: None,
+                archived: Vec::new(),
+            })
+        }
+    };
+    let (request_ids, next_cursor) = request_index::get_request_ids_by_user_id(user_id, cursor, page_size(num_requests));
+    let requests = REQUEST_MAP.with(|m| {
+        let map = m.borrow();
+        request_ids
+            .iter()
+            .filter_map(|&request_id| map.get(&StableRequestId(request_id)))
+            .map(|request| to_request_reply(&request))
+            .collect()
+    });
+
+    let archived = match next_cursor {
+        Some(_) => Vec::new(),
+        None => get_archived_ranges_before(ArchiveKind::Request, cursor),
+    };
+
+    Ok(RequestsPageReply {
+        requests,
+        next_cursor,
+        archived,
+    })
+}
@@ -0,0 +1,87 @@
+use candid::Principal;
+
+use super::stable_archive::{ArchiveKind, StableArchive, StableArchiveSettings};
+
+use crate::stable_memory::{ARCHIVE_SETTINGS, ARCHIVE_WASM};
+
+pub fn get_settings() -> StableArchiveSettings {
+    ARCHIVE_SETTINGS.with(|s| s.borrow().get().clone())
+}
+
+pub fn set_settings(settings: StableArchiveSettings) -> Result<(), String> {
+    ARCHIVE_SETTINGS
+        .with(|s| s.borrow_mut().set(settings))
+        .map(|_| ())
+        .map_err(|e| format!("Failed to update archive settings: {:?}", e))
+}
+
+pub fn get_archives() -> Vec<StableArchive> {
+    get_settings().archives
+}
+
+/// number of records of kind moved to archives
+pub fn get_num_archived(kind: ArchiveKind) -> u64 {
+    get_archives()
+        .iter()
+        .filter(|archive| archive.kind == kind)
+        .map(|archive| archive.num_records)
+        .sum()
+}
+
+/// archive currently being filled for kind
+pub fn get_current_archive(kind: ArchiveKind) -> Option<StableArchive> {
+    get_archives().into_iter().rev().find(|archive| archive.kind == kind)
+}
+
+/// add a newly created archive before its code is installed. it starts where the previous archive of the same kind ended
+pub fn insert_archive(canister_id: Principal, kind: ArchiveKind) -> Result<StableArchive, String> {
+    let mut settings = get_settings();
+    let start_id = settings
+        .archives
+        .iter()
+        .rev()
+        .find(|archive| archive.kind == kind)
+        .map_or(0, |archive| archive.end_id);
+    let archive = StableArchive {
+        canister_id,
+        kind,
+        start_id,
+        end_id: start_id,
+        num_records: 0,
+        installed: false,
+    };
+    settings.archives.push(archive.clone());
+    set_settings(settings)?;
+    Ok(archive)
+}
+
+/// record that kong_data_archive was installed on the archive
+pub fn set_installed(canister_id: Principal) -> Result<StableArchive, String> {
+    let mut settings = get_settings();
+    let archive = settings
+        .archives
+        .iter_mut()
+        .find(|archive| archive.canister_id == canister_id)
+        .ok_or(format!("Archive {} not found", canister_id))?;
+    archive.installed = true;
+    let archive = archive.clone();
+    set_settings(settings)?;
+    Ok(archive)
+}
+
+/// record that ids up to end_id (exclusive) were appended to the archive
+pub fn extend_archive(canister_id: Principal, end_id: u64, num_records: u64) -> Result<(), String> {
+    let mut settings = get_settings();
+    let archive = settings
+        .archives
+        .iter_mut()
+        .find(|archive| archive.canister_id == canister_id)
+        .ok_or(format!("Archive {} not found", canister_id))?;
+    archive.end_id = end_id;
+    archive.num_records += num_records;
+    set_settings(settings)
+}
+
+pub fn get_wasm() -> Vec<u8> {
+    ARCHIVE_WASM.with(|w| w.borrow().get().0.clone())
+}
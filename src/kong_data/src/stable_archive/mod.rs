@@ -0,0 +1,3 @@
+pub mod archive_map;
+#[allow(clippy::module_inception)]
+pub mod stable_archive;
@@ -0,0 +1,78 @@
+use candid::{CandidType, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::{Deserialize, Serialize};
+
+/// kind of records that are moved to archive canisters. must match kong_data_archive's ArchiveKind
+#[derive(CandidType, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ArchiveKind {
+    Tx,
+    Request,
+    Transfer,
+}
+
+/// an archive canister spawned by kong_data. holds ids in [start_id, end_id) of one kind
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct StableArchive {
+    pub canister_id: Principal,
+    pub kind: ArchiveKind,
+    pub start_id: u64,
+    pub end_id: u64,
+    pub num_records: u64,
+    #[serde(default = "default_installed")]
+    pub installed: bool, // false until kong_data_archive is installed. the install is retried on the next run
+}
+
+fn default_installed() -> bool {
+    true
+}
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct StableArchiveSettings {
+    pub archive_interval_secs: u64,
+    pub trigger_threshold: u64,       // archive when more than this many records of a kind are held locally
+    pub num_records_to_archive: u64,  // records moved per archive run
+    pub max_records_per_archive: u64, // spawn a new archive when the current one is full
+    pub cycles_for_archive_creation: u64,
+    pub archives: Vec<StableArchive>, // in spawn order
+}
+
+impl Default for StableArchiveSettings {
+    fn default() -> Self {
+        Self {
+            archive_interval_secs: 3600, // check every hour
+            trigger_threshold: 2_000_000,
+            num_records_to_archive: 1_000,
+            max_records_per_archive: 10_000_000,
+            cycles_for_archive_creation: 2_000_000_000_000, // 2T cycles
+            archives: Vec::new(),
+        }
+    }
+}
+
+impl Storable for StableArchiveSettings {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        serde_cbor::to_vec(self).unwrap().into()
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        serde_cbor::from_slice(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// wasm module of kong_data_archive used to spawn archives
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StableArchiveWasm(#[serde(with = "serde_bytes")] pub Vec<u8>);
+
+impl Storable for StableArchiveWasm {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        std::borrow::Cow::Borrowed(&self.0)
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        StableArchiveWasm(bytes.into_owned())
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
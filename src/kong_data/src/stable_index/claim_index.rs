@@ -0,0 +1,17 @@
+use super::stable_index::get_ids;
+
+use crate::stable_claim::stable_claim::StableClaim;
+use crate::stable_memory::CLAIM_USER_INDEX_MAP;
+
+pub fn insert(claim: &StableClaim) {
+    CLAIM_USER_INDEX_MAP.with(|m| m.borrow_mut().insert((claim.user_id as u64, claim.claim_id), ()));
+}
+
+pub fn remove(claim: &StableClaim) {
+    CLAIM_USER_INDEX_MAP.with(|m| m.borrow_mut().remove(&(claim.user_id as u64, claim.claim_id)));
+}
+
+/// claim_ids of a user, newest first
+pub fn get_claim_ids_by_user_id(user_id: u32, cursor: Option<u64>, num: usize) -> (Vec<u64>, Option<u64>) {
+    CLAIM_USER_INDEX_MAP.with(|m| get_ids(&m.borrow(), user_id as u64, cursor, num))
+}
@@ -0,0 +1,6 @@
+pub mod claim_index;
+pub mod request_index;
+#[allow(clippy::module_inception)]
+pub mod stable_index;
+pub mod transfer_index;
+pub mod tx_index;
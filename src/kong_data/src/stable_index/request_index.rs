@@ -0,0 +1,50 @@
+use std::ops::Bound;
+
+use super::stable_index::get_ids;
+
+use crate::stable_memory::{REQUEST_USER_INDEX_MAP, TRANSFER_REQUEST_INDEX_MAP, TRANSFER_USER_INDEX_MAP};
+use crate::stable_request::stable_request::StableRequest;
+
+/// index the request by user. transfers archived before their request are linked to the user here
+pub fn insert(request: &StableRequest) {
+    let user_id = request.user_id as u64;
+    REQUEST_USER_INDEX_MAP.with(|m| m.borrow_mut().insert((user_id, request.request_id), ()));
+    let transfer_ids = get_transfer_ids_by_request_id(request.request_id);
+    TRANSFER_USER_INDEX_MAP.with(|m| {
+        let mut map = m.borrow_mut();
+        for transfer_id in transfer_ids {
+            map.insert((user_id, transfer_id), ());
+        }
+    });
+}
+
+pub fn remove(request: &StableRequest) {
+    let user_id = request.user_id as u64;
+    REQUEST_USER_INDEX_MAP.with(|m| m.borrow_mut().remove(&(user_id, request.request_id)));
+    let transfer_ids = get_transfer_ids_by_request_id(request.request_id);
+    TRANSFER_USER_INDEX_MAP.with(|m| {
+        let mut map = m.borrow_mut();
+        for transfer_id in transfer_ids {
+            map.remove(&(user_id, transfer_id));
+        }
+    });
+}
+
+/// remove an archived request from the user index. its transfers stay linked to the user
+pub fn remove_archived(request: &StableRequest) {
+    REQUEST_USER_INDEX_MAP.with(|m| m.borrow_mut().remove(&(request.user_id as u64, request.request_id)));
+}
+
+/// request_ids of a user, newest first
+pub fn get_request_ids_by_user_id(user_id: u32, cursor: Option<u64>, num: usize) -> (Vec<u64>, Option<u64>) {
+    REQUEST_USER_INDEX_MAP.with(|m| get_ids(&m.borrow(), user_id as u64, cursor, num))
+}
+
+fn get_transfer_ids_by_request_id(request_id: u64) -> Vec<u64> {
+    TRANSFER_REQUEST_INDEX_MAP.with(|m| {
+        m.borrow()
+            .range((Bound::Included((request_id, 0)), Bound::Included((request_id, u64::MAX))))
+            .map(|((_, transfer_id), _)| transfer_id)
+            .collect()
+    })
+}
@@ -0,0 +1,68 @@
+use std::ops::Bound;
+
+use crate::stable_memory::StableIndexMap;
+
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// return ids stored under key, newest first
+///
+/// # Arguments
+///
+/// * `map` - index map of (key, id) pairs
+/// * `key` - key to look up. ie. user_id
+/// * `cursor` - exclusive upper bound of the ids to return. None starts from the newest id
+/// * `num` - maximum number of ids to return
+///
+/// # Returns
+///
+/// * `(ids, next_cursor)` - next_cursor is Some if there may be more ids to page through
+pub fn get_ids(map: &StableIndexMap, key: u64, cursor: Option<u64>, num: usize) -> (Vec<u64>, Option<u64>) {
+    let end = match cursor {
+        Some(cursor) => Bound::Excluded((key, cursor)),
+        None => Bound::Included((key, u64::MAX)),
+    };
+    let ids: Vec<u64> = map
+        .range((Bound::Included((key, 0)), end))
+        .rev()
+        .take(num)
+        .map(|((_, id), _)| id)
+        .collect();
+    let next_cursor = if ids.len() == num { ids.last().copied() } else { None };
+    (ids, next_cursor)
+}
+
+/// page size requested by the caller, capped at MAX_PAGE_SIZE
+pub fn page_size(num: Option<u16>) -> usize {
+    num.map_or(MAX_PAGE_SIZE, |n| n as usize).min(MAX_PAGE_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stable_memory::CLAIM_USER_INDEX_MAP;
+
+    #[test]
+    fn test_get_ids_pages_newest_first() {
+        CLAIM_USER_INDEX_MAP.with(|m| {
+            let mut map = m.borrow_mut();
+            for id in [1, 3, 5, 7] {
+                map.insert((1, id), ());
+            }
+            map.insert((2, 4), ());
+            map.insert((0, 9), ());
+        });
+
+        CLAIM_USER_INDEX_MAP.with(|m| {
+            let map = m.borrow();
+            let (ids, next_cursor) = get_ids(&map, 1, None, 3);
+            assert_eq!(ids, vec![7, 5, 3]);
+            assert_eq!(next_cursor, Some(3));
+
+            let (ids, next_cursor) = get_ids(&map, 1, next_cursor, 3);
+            assert_eq!(ids, vec![1]);
+            assert_eq!(next_cursor, None);
+
+            assert_eq!(get_ids(&map, 3, None, 3), (Vec::new(), None));
+        });
+    }
+}
@@ -0,0 +1,29 @@
+use super::stable_index::get_ids;
+
+use crate::stable_memory::{REQUEST_MAP, TRANSFER_REQUEST_INDEX_MAP, TRANSFER_USER_INDEX_MAP};
+use crate::stable_request::stable_request::StableRequestId;
+use crate::stable_transfer::stable_transfer::StableTransfer;
+
+/// index the transfer by request and, if the request has been archived already, by user
+pub fn insert(transfer: &StableTransfer) {
+    TRANSFER_REQUEST_INDEX_MAP.with(|m| m.borrow_mut().insert((transfer.request_id, transfer.transfer_id), ()));
+    if let Some(user_id) = get_user_id_by_request_id(transfer.request_id) {
+        TRANSFER_USER_INDEX_MAP.with(|m| m.borrow_mut().insert((user_id as u64, transfer.transfer_id), ()));
+    }
+}
+
+pub fn remove(transfer: &StableTransfer) {
+    TRANSFER_REQUEST_INDEX_MAP.with(|m| m.borrow_mut().remove(&(transfer.request_id, transfer.transfer_id)));
+    if let Some(user_id) = get_user_id_by_request_id(transfer.request_id) {
+        TRANSFER_USER_INDEX_MAP.with(|m| m.borrow_mut().remove(&(user_id as u64, transfer.transfer_id)));
+    }
+}
+
+/// transfer_ids of a user, newest first
+pub fn get_transfer_ids_by_user_id(user_id: u32, cursor: Option<u64>, num: usize) -> (Vec<u64>, Option<u64>) {
+    TRANSFER_USER_INDEX_MAP.with(|m| get_ids(&m.borrow(), user_id as u64, cursor, num))
+}
+
+fn get_user_id_by_request_id(request_id: u64) -> Option<u32> {
+    REQUEST_MAP.with(|m| m.borrow().get(&StableRequestId(request_id)).map(|request| request.user_id))
+}
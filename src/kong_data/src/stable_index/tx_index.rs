@@ -0,0 +1,86 @@
+use std::cell::RefCell;
+use std::ops::Bound;
+use std::thread::LocalKey;
+
+use crate::stable_memory::{StableIndexMap, TX_POOL_INDEX_MAP, TX_TS_INDEX_MAP, TX_TYPE_INDEX_MAP, TX_USER_INDEX_MAP};
+use crate::stable_tx::stable_tx::StableTx;
+use crate::stable_tx::tx::Tx;
+
+/// secondary indexes on TX_MAP that can drive a query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxIndex {
+    Pool,
+    User,
+    Type,
+}
+
+impl TxIndex {
+    fn map(&self) -> &'static LocalKey<RefCell<StableIndexMap>> {
+        match self {
+            TxIndex::Pool => &TX_POOL_INDEX_MAP,
+            TxIndex::User => &TX_USER_INDEX_MAP,
+            TxIndex::Type => &TX_TYPE_INDEX_MAP,
+        }
+    }
+}
+
+/// (index, key) pairs for a tx. a swap is indexed under every pool it touched
+fn index_keys(tx: &StableTx) -> Vec<(TxIndex, u64)> {
+    let mut keys: Vec<(TxIndex, u64)> = tx.pool_ids().into_iter().map(|pool_id| (TxIndex::Pool, pool_id as u64)).collect();
+    keys.push((TxIndex::User, tx.user_id() as u64));
+    keys.push((TxIndex::Type, tx.tx_type().index_key()));
+    keys
+}
+
+pub fn insert(tx: &StableTx) {
+    let tx_id = tx.tx_id();
+    for (index, key) in index_keys(tx) {
+        index.map().with(|m| m.borrow_mut().insert((key, tx_id), ()));
+    }
+    TX_TS_INDEX_MAP.with(|m| m.borrow_mut().insert((tx.ts(), tx_id), ()));
+}
+
+pub fn remove(tx: &StableTx) {
+    let tx_id = tx.tx_id();
+    for (index, key) in index_keys(tx) {
+        index.map().with(|m| m.borrow_mut().remove(&(key, tx_id)));
+    }
+    TX_TS_INDEX_MAP.with(|m| m.borrow_mut().remove(&(tx.ts(), tx_id)));
+}
+
+/// visit tx_ids stored under key in [lower, upper), newest first, until f returns false
+pub fn for_each_tx_id(index: TxIndex, key: u64, lower: u64, upper: u64, mut f: impl FnMut(u64) -> bool) {
+    index.map().with(|m| {
+        for ((_, tx_id), _) in m.borrow().range((key, lower)..(key, upper)).rev() {
+            if !f(tx_id) {
+                break;
+            }
+        }
+    });
+}
+
+/// convert a timestamp range to a tx_id range [lower, upper)
+/// tx_ids are allocated in timestamp order so the first tx at or after start_ts and
+/// the last tx at or before end_ts bound the ids. txs are still filtered by ts when loaded
+///
+/// # Returns
+///
+/// * `Some((lower, upper))` - tx_id range to scan
+/// * `None` if no tx falls in the time range
+pub fn get_tx_id_range(start_ts: Option<u64>, end_ts: Option<u64>) -> Option<(u64, u64)> {
+    TX_TS_INDEX_MAP.with(|m| {
+        let map = m.borrow();
+        let lower = match start_ts {
+            Some(start_ts) => map.range((start_ts, 0)..).next().map(|((_, tx_id), _)| tx_id)?,
+            None => 0,
+        };
+        let upper = match end_ts {
+            Some(end_ts) => map
+                .range((Bound::Unbounded, Bound::Included((end_ts, u64::MAX))))
+                .next_back()
+                .map(|((_, tx_id), _)| tx_id.saturating_add(1))?,
+            None => u64::MAX,
+        };
+        Some((lower, upper))
+    })
+}
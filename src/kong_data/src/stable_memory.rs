@@ -1,8 +1,10 @@
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
 use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell};
+use kong_lib::stable_trusted_origin::trusted_origin_store::{TrustedOriginMemories, TrustedOriginStore};
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 
+use crate::stable_archive::stable_archive::{StableArchiveSettings, StableArchiveWasm};
 use crate::stable_claim::stable_claim::{StableClaim, StableClaimId};
 use crate::stable_db_update::stable_db_update::{StableDBUpdate, StableDBUpdateId};
 use crate::stable_kong_settings::stable_kong_settings::StableKongSettings;
@@ -11,10 +13,13 @@ use crate::stable_pool::stable_pool::{StablePool, StablePoolId};
 use crate::stable_request::stable_request::{StableRequest, StableRequestId};
 use crate::stable_token::stable_token::{StableToken, StableTokenId};
 use crate::stable_transfer::stable_transfer::{StableTransfer, StableTransferId};
+use crate::stable_trusted_origin::trusted_origin_map;
 use crate::stable_tx::stable_tx::{StableTx, StableTxId};
 use crate::stable_user::stable_user::{StableUser, StableUserId};
 
 type Memory = VirtualMemory<DefaultMemoryImpl>;
+// secondary index of (key, id) pairs, e.g. (pool_id, tx_id)
+pub type StableIndexMap = StableBTreeMap<(u64, u64), (), Memory>;
 
 pub const KONG_SETTINGS_MEMORY_ID: MemoryId = MemoryId::new(0);
 pub const USER_MEMORY_ID: MemoryId = MemoryId::new(1);
@@ -25,6 +30,21 @@ pub const REQUEST_MEMORY_ID: MemoryId = MemoryId::new(5);
 pub const TRANSFER_MEMORY_ID: MemoryId = MemoryId::new(6);
 pub const CLAIM_MEMORY_ID: MemoryId = MemoryId::new(7);
 pub const LP_TOKEN_MEMORY_ID: MemoryId = MemoryId::new(8);
+// secondary indexes
+pub const TX_POOL_INDEX_MEMORY_ID: MemoryId = MemoryId::new(10);
+pub const TX_USER_INDEX_MEMORY_ID: MemoryId = MemoryId::new(11);
+pub const TX_TYPE_INDEX_MEMORY_ID: MemoryId = MemoryId::new(12);
+pub const TX_TS_INDEX_MEMORY_ID: MemoryId = MemoryId::new(13);
+pub const REQUEST_USER_INDEX_MEMORY_ID: MemoryId = MemoryId::new(14);
+pub const CLAIM_USER_INDEX_MEMORY_ID: MemoryId = MemoryId::new(15);
+pub const TRANSFER_REQUEST_INDEX_MEMORY_ID: MemoryId = MemoryId::new(16);
+pub const TRANSFER_USER_INDEX_MEMORY_ID: MemoryId = MemoryId::new(17);
+// archive canisters
+pub const ARCHIVE_SETTINGS_MEMORY_ID: MemoryId = MemoryId::new(20);
+pub const ARCHIVE_WASM_MEMORY_ID: MemoryId = MemoryId::new(21);
+// ICRC-28 trusted origins
+pub const TRUSTED_ORIGINS_MEMORY_ID: MemoryId = MemoryId::new(30);
+pub const TRUSTED_ORIGIN_LOG_MEMORY_ID: MemoryId = MemoryId::new(31);
 
 pub const DB_UPDATE_MEMORY_ID: MemoryId = MemoryId::new(50);
 
@@ -82,6 +102,72 @@ thread_local! {
         RefCell::new(StableBTreeMap::init(memory_manager.get(LP_TOKEN_MEMORY_ID)))
     });
 
+    //
+    // Secondary indexes. Maintained when kong_backend archives records into kong_data
+    //
+
+    // index of txs by pool: (pool_id, tx_id)
+    pub static TX_POOL_INDEX_MAP: RefCell<StableIndexMap> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableBTreeMap::init(memory_manager.get(TX_POOL_INDEX_MEMORY_ID)))
+    });
+
+    // index of txs by user: (user_id, tx_id)
+    pub static TX_USER_INDEX_MAP: RefCell<StableIndexMap> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableBTreeMap::init(memory_manager.get(TX_USER_INDEX_MEMORY_ID)))
+    });
+
+    // index of txs by tx type: (tx_type, tx_id)
+    pub static TX_TYPE_INDEX_MAP: RefCell<StableIndexMap> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableBTreeMap::init(memory_manager.get(TX_TYPE_INDEX_MEMORY_ID)))
+    });
+
+    // index of txs by timestamp: (ts, tx_id)
+    pub static TX_TS_INDEX_MAP: RefCell<StableIndexMap> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableBTreeMap::init(memory_manager.get(TX_TS_INDEX_MEMORY_ID)))
+    });
+
+    // index of requests by user: (user_id, request_id)
+    pub static REQUEST_USER_INDEX_MAP: RefCell<StableIndexMap> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableBTreeMap::init(memory_manager.get(REQUEST_USER_INDEX_MEMORY_ID)))
+    });
+
+    // index of claims by user: (user_id, claim_id)
+    pub static CLAIM_USER_INDEX_MAP: RefCell<StableIndexMap> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableBTreeMap::init(memory_manager.get(CLAIM_USER_INDEX_MEMORY_ID)))
+    });
+
+    // index of transfers by request: (request_id, transfer_id)
+    pub static TRANSFER_REQUEST_INDEX_MAP: RefCell<StableIndexMap> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableBTreeMap::init(memory_manager.get(TRANSFER_REQUEST_INDEX_MEMORY_ID)))
+    });
+
+    // index of transfers by user: (user_id, transfer_id)
+    pub static TRANSFER_USER_INDEX_MAP: RefCell<StableIndexMap> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableBTreeMap::init(memory_manager.get(TRANSFER_USER_INDEX_MEMORY_ID)))
+    });
+
+    // stable memory for storing archive settings and the list of spawned archive canisters
+    pub static ARCHIVE_SETTINGS: RefCell<StableCell<StableArchiveSettings, Memory>> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableCell::init(memory_manager.get(ARCHIVE_SETTINGS_MEMORY_ID), StableArchiveSettings::default()).expect("Failed to initialize archive settings"))
+    });
+
+    // stable memory for storing the kong_data_archive wasm used to spawn archives
+    pub static ARCHIVE_WASM: RefCell<StableCell<StableArchiveWasm, Memory>> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableCell::init(memory_manager.get(ARCHIVE_WASM_MEMORY_ID), StableArchiveWasm::default()).expect("Failed to initialize archive wasm"))
+    });
+
+    // stable memory for storing ICRC-28 trusted origins
+    // and the audit log of changes to them
+    pub static TRUSTED_ORIGINS: RefCell<TrustedOriginStore<Memory>> = with_memory_manager(|memory_manager| {
+        RefCell::new(TrustedOriginStore::init(
+            TrustedOriginMemories {
+                origins: memory_manager.get(TRUSTED_ORIGINS_MEMORY_ID),
+                log: memory_manager.get(TRUSTED_ORIGIN_LOG_MEMORY_ID),
+            },
+            trusted_origin_map::default_origins(),
+        ))
+    });
+
     // stable memory for storing stable memory updates
     pub static DB_UPDATE_MAP: RefCell<StableBTreeMap<StableDBUpdateId, StableDBUpdate, Memory>> = with_memory_manager(|memory_manager| {
         RefCell::new(StableBTreeMap::init(memory_manager.get(DB_UPDATE_MEMORY_ID)))
@@ -8,3 +8,4 @@ pub mod status_tx;
 pub mod swap_tx;
 pub mod tx;
 pub mod tx_map;
+pub mod tx_type;
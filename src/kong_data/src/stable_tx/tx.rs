@@ -1,8 +1,12 @@
 use super::stable_tx::StableTx;
+use super::tx_type::TxType;
 
 pub trait Tx {
     fn tx_id(&self) -> u64;
     fn user_id(&self) -> u32;
+    fn tx_type(&self) -> TxType;
+    fn pool_ids(&self) -> Vec<u32>;
+    fn ts(&self) -> u64;
 }
 
 impl Tx for StableTx {
@@ -25,4 +29,39 @@ impl Tx for StableTx {
             StableTx::Send(tx) => tx.user_id,
         }
     }
+
+    fn tx_type(&self) -> TxType {
+        match self {
+            StableTx::AddPool(_) => TxType::AddPool,
+            StableTx::AddLiquidity(_) => TxType::AddLiquidity,
+            StableTx::RemoveLiquidity(_) => TxType::RemoveLiquidity,
+            StableTx::Swap(_) => TxType::Swap,
+            StableTx::Send(_) => TxType::Send,
+        }
+    }
+
+    /// pools touched by the tx. a multi-hop swap touches more than one pool
+    fn pool_ids(&self) -> Vec<u32> {
+        match self {
+            StableTx::AddPool(tx) => vec![tx.pool_id],
+            StableTx::AddLiquidity(tx) => vec![tx.pool_id],
+            StableTx::RemoveLiquidity(tx) => vec![tx.pool_id],
+            StableTx::Swap(tx) => {
+                let mut pool_ids: Vec<u32> = tx.txs.iter().map(|swap| swap.pool_id).collect();
+                pool_ids.dedup();
+                pool_ids
+            }
+            StableTx::Send(_) => Vec::new(),
+        }
+    }
+
+    fn ts(&self) -> u64 {
+        match self {
+            StableTx::AddPool(tx) => tx.ts,
+            StableTx::AddLiquidity(tx) => tx.ts,
+            StableTx::RemoveLiquidity(tx) => tx.ts,
+            StableTx::Swap(tx) => tx.ts,
+            StableTx::Send(tx) => tx.ts,
+        }
+    }
 }
@@ -1,12 +1,101 @@
 use super::stable_tx::{StableTx, StableTxId};
 use super::tx::Tx;
+use super::tx_type::TxType;
 use std::cmp::min;
 use std::ops::Bound;
 
+use crate::stable_index::tx_index::{self, TxIndex};
 use crate::stable_memory::TX_MAP;
 use crate::stable_pool::pool_map;
 
 const MAX_TXS: usize = 100;
+// maximum number of txs examined by a single filtered query before returning a cursor
+const MAX_SCANNED_TXS: usize = 10_000;
+
+/// filters for get_by_filter. all filters are optional and combined with AND
+#[derive(Debug, Clone, Default)]
+pub struct TxFilter {
+    pub user_id: Option<u32>,
+    pub pool_id: Option<u32>,
+    pub tx_type: Option<TxType>,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+}
+
+impl TxFilter {
+    /// most selective secondary index for the filter. None means scan TX_MAP
+    fn index(&self) -> Option<(TxIndex, u64)> {
+        if let Some(user_id) = self.user_id {
+            return Some((TxIndex::User, user_id as u64));
+        }
+        if let Some(pool_id) = self.pool_id {
+            return Some((TxIndex::Pool, pool_id as u64));
+        }
+        self.tx_type.map(|tx_type| (TxIndex::Type, tx_type.index_key()))
+    }
+
+    fn matches(&self, tx: &StableTx) -> bool {
+        self.user_id.is_none_or(|user_id| tx.user_id() == user_id)
+            && self.pool_id.is_none_or(|pool_id| tx.pool_ids().contains(&pool_id))
+            && self.tx_type.is_none_or(|tx_type| tx.tx_type() == tx_type)
+            && self.start_ts.is_none_or(|start_ts| tx.ts() >= start_ts)
+            && self.end_ts.is_none_or(|end_ts| tx.ts() <= end_ts)
+    }
+}
+
+pub fn get_by_tx_id(tx_id: u64) -> Option<StableTx> {
+    TX_MAP.with(|m| m.borrow().get(&StableTxId(tx_id)))
+}
+
+/// get txs matching filter, newest first
+///
+/// # Arguments
+///
+/// * `filter` - filters to apply
+/// * `cursor` - exclusive upper bound tx_id. None starts from the latest tx
+/// * `num_txs` - maximum number of txs to return, capped at MAX_TXS
+///
+/// # Returns
+///
+/// * `(txs, next_cursor)` - next_cursor is Some if there may be more txs. a page can be
+///   shorter than num_txs, or even empty, if the scan limit was reached first
+pub fn get_by_filter(filter: &TxFilter, cursor: Option<u64>, num_txs: usize) -> (Vec<StableTx>, Option<u64>) {
+    let num_txs = min(num_txs, MAX_TXS);
+    let (lower, upper) = match tx_index::get_tx_id_range(filter.start_ts, filter.end_ts) {
+        Some((lower, upper)) => (lower, cursor.map_or(upper, |cursor| min(cursor, upper))),
+        None => return (Vec::new(), None),
+    };
+    if num_txs == 0 || lower >= upper {
+        return (Vec::new(), None);
+    }
+
+    let mut txs = Vec::new();
+    let mut num_scanned = 0;
+    let mut next_cursor = None;
+    let mut visit = |tx_id: u64, tx: Option<StableTx>| {
+        num_scanned += 1;
+        if let Some(tx) = tx.filter(|tx| filter.matches(tx)) {
+            txs.push(tx);
+        }
+        if txs.len() >= num_txs || num_scanned >= MAX_SCANNED_TXS {
+            next_cursor = Some(tx_id);
+            return false;
+        }
+        true
+    };
+    match filter.index() {
+        Some((index, key)) => tx_index::for_each_tx_id(index, key, lower, upper, |tx_id| visit(tx_id, get_by_tx_id(tx_id))),
+        None => TX_MAP.with(|m| {
+            for (k, v) in m.borrow().range(StableTxId(lower)..StableTxId(upper)).rev() {
+                if !visit(k.0, Some(v)) {
+                    break;
+                }
+            }
+        }),
+    }
+
+    (txs, next_cursor)
+}
 
 /// get txs filtered by user_id and token_id
 /// if you call get_by_user_and_token_id(None, None, None) it will return all txs
@@ -0,0 +1,24 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TxType {
+    AddPool,
+    AddLiquidity,
+    RemoveLiquidity,
+    Swap,
+    Send,
+}
+
+impl TxType {
+    /// key used in TX_TYPE_INDEX_MAP
+    pub fn index_key(&self) -> u64 {
+        match self {
+            TxType::AddPool => 0,
+            TxType::AddLiquidity => 1,
+            TxType::RemoveLiquidity => 2,
+            TxType::Swap => 3,
+            TxType::Send => 4,
+        }
+    }
+}
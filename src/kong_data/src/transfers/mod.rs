@@ -1,2 +1,5 @@
 pub mod transfer_reply;
 pub mod transfer_reply_helpers;
+#[allow(clippy::module_inception)]
+pub mod transfers;
+pub mod transfers_page_reply;
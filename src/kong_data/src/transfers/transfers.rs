@@ -0,0 +1,38 @@
+use ic_cdk::query;
+
+use super::transfer_reply_helpers::to_transfer_ids;
+use super::transfers_page_reply::TransfersPageReply;
+
+use crate::archive::archives::get_archived_ranges_before;
+use crate::stable_archive::stable_archive::ArchiveKind;
+use crate::stable_index::stable_index::page_size;
+use crate::stable_index::transfer_index;
+use crate::stable_user::user_map;
+
+/// transfers of a user, newest first. pass next_cursor back as cursor to get the next page.
+/// older transfers moved to archive canisters are not indexed by user, their id ranges are returned in archived with the last page
+#[query]
+fn user_transfers(principal_id: String, cursor: Option<u64>, num_transfers: Option<u16>) -> Result<TransfersPageReply, String> {
+    let user_id = match user_map::get_by_principal_id(&principal_id)? {
+        Some(user) => user.user_id,
+        None => {
+            return Ok(TransfersPageReply {
+                transfers: Vec::new(),
+                next_cursor: None,
+                archived: Vec::new(),
+            })
+        }
+    };
+    let (transfer_ids, next_cursor) = transfer_index::get_transfer_ids_by_user_id(user_id, cursor, page_size(num_transfers));
+
+    let archived = match next_cursor {
+        Some(_) => Vec::new(),
+        None => get_archived_ranges_before(ArchiveKind::Transfer, cursor),
+    };
+
+    Ok(TransfersPageReply {
+        transfers: to_transfer_ids(&transfer_ids),
+        next_cursor,
+        archived,
+    })
+}
@@ -0,0 +1,15 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use super::transfer_reply::TransferIdReply;
+
+use crate::archive::archive_reply::ArchivedRangeReply;
+
+/// archived holds the id ranges moved to archive canisters, returned with the last page of local records.
+/// history filtered by user or pool only covers local records, archived records must be fetched and filtered by the caller
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct TransfersPageReply {
+    pub transfers: Vec<TransferIdReply>,
+    pub next_cursor: Option<u64>,
+    pub archived: Vec<ArchivedRangeReply>,
+}
@@ -1,4 +1,6 @@
 #[allow(clippy::module_inception)]
 pub mod txs;
+pub mod txs_page_reply;
+pub mod txs_query_args;
 pub mod txs_reply;
 pub mod txs_reply_helpers;
@@ -1,9 +1,14 @@
 use ic_cdk::query;
 
+use super::txs_page_reply::TxsPageReply;
+use super::txs_query_args::TxsQueryArgs;
 use super::txs_reply::TxsReply;
 use super::txs_reply_helpers::to_txs_reply;
 
-use crate::stable_tx::tx_map;
+use crate::archive::archives::get_archived_ranges_before;
+use crate::stable_archive::stable_archive::ArchiveKind;
+use crate::stable_index::stable_index::page_size;
+use crate::stable_tx::tx_map::{self, TxFilter};
 use crate::stable_user::user_map;
 
 #[query]
@@ -25,3 +30,41 @@ fn txs(principal_id: Option<String>, tx_id: Option<u64>, token_id: Option<u32>,
 
     Ok(txs)
 }
+
+/// txs filtered by user, pool, tx type and time range, newest first. pass next_cursor back as cursor to get the next page.
+/// older txs moved to archive canisters are not filtered, their id ranges are returned in archived with the last page
+#[query]
+fn query_txs(args: TxsQueryArgs) -> Result<TxsPageReply, String> {
+    let user_id = match args.principal_id {
+        Some(principal_id) => match user_map::get_by_principal_id(&principal_id) {
+            Ok(Some(user)) => Some(user.user_id),
+            Ok(None) | Err(_) => {
+                return Ok(TxsPageReply {
+                    txs: Vec::new(),
+                    next_cursor: None,
+                    archived: Vec::new(),
+                })
+            }
+        },
+        None => None,
+    };
+    let filter = TxFilter {
+        user_id,
+        pool_id: args.pool_id,
+        tx_type: args.tx_type,
+        start_ts: args.start_ts,
+        end_ts: args.end_ts,
+    };
+    let (txs, next_cursor) = tx_map::get_by_filter(&filter, args.cursor, page_size(args.num_txs));
+
+    let archived = match next_cursor {
+        Some(_) => Vec::new(),
+        None => get_archived_ranges_before(ArchiveKind::Tx, args.cursor),
+    };
+
+    Ok(TxsPageReply {
+        txs: txs.iter().map(to_txs_reply).collect(),
+        next_cursor,
+        archived,
+    })
+}
@@ -0,0 +1,17 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::stable_tx::tx_type::TxType;
+
+/// Data structure for the arguments of the `query_txs` function.
+/// All filters are optional and combined with AND.
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct TxsQueryArgs {
+    pub principal_id: Option<String>,
+    pub pool_id: Option<u32>,
+    pub tx_type: Option<TxType>,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+    pub cursor: Option<u64>, // next_cursor of the previous page
+    pub num_txs: Option<u16>,
+}
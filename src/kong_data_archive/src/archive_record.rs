@@ -0,0 +1,79 @@
+use candid::{CandidType, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::{Deserialize, Serialize};
+
+/// kind of records stored in the archive. must match kong_data's ArchiveKind
+#[derive(CandidType, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ArchiveKind {
+    Tx,
+    Request,
+    Transfer,
+}
+
+#[derive(CandidType, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct StableArchiveRecordId {
+    pub kind: ArchiveKind,
+    pub id: u64,
+}
+
+impl Storable for StableArchiveRecordId {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        serde_cbor::to_vec(self).unwrap().into()
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        serde_cbor::from_slice(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// a record archived by kong_data. data is the CBOR encoding of the record as stored in kong_data
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveRecord {
+    pub id: u64,
+    pub ts: u64,
+    #[serde(with = "serde_bytes")]
+    pub data: Vec<u8>,
+}
+
+impl Storable for ArchiveRecord {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        serde_cbor::to_vec(self).unwrap().into()
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        serde_cbor::from_slice(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct StableArchiveSettings {
+    pub kong_data: Principal, // only kong_data can append records
+    pub max_records: u64,
+    pub num_records: u64,
+}
+
+impl Default for StableArchiveSettings {
+    fn default() -> Self {
+        Self {
+            kong_data: Principal::anonymous(),
+            max_records: 0,
+            num_records: 0,
+        }
+    }
+}
+
+impl Storable for StableArchiveSettings {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        serde_cbor::to_vec(self).unwrap().into()
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        serde_cbor::from_slice(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -0,0 +1,97 @@
+use candid::{CandidType, Principal};
+use ic_cdk::{init, query, update};
+use serde::Deserialize;
+
+use super::{APP_NAME, APP_VERSION};
+
+use crate::archive_record::{ArchiveKind, ArchiveRecord, StableArchiveRecordId, StableArchiveSettings};
+use crate::stable_memory::{ARCHIVE_SETTINGS, RECORD_MAP};
+
+const MAX_RECORDS_PER_QUERY: usize = 1_000;
+
+#[derive(CandidType, Debug, Clone, Deserialize)]
+pub struct ArchiveInitArgs {
+    pub kong_data: Principal,
+    pub max_records: u64,
+}
+
+#[init]
+fn init(args: ArchiveInitArgs) {
+    ARCHIVE_SETTINGS.with(|s| {
+        s.borrow_mut()
+            .set(StableArchiveSettings {
+                kong_data: args.kong_data,
+                max_records: args.max_records,
+                num_records: 0,
+            })
+            .expect("Failed to set archive settings")
+    });
+}
+
+/// Guard to ensure caller is the kong_data canister that spawned this archive
+fn caller_is_kong_data() -> Result<(), String> {
+    if ic_cdk::caller() != ARCHIVE_SETTINGS.with(|s| s.borrow().get().kong_data) {
+        return Err("Caller is not Kong Data".to_string());
+    }
+    Ok(())
+}
+
+#[query]
+fn icrc1_name() -> String {
+    format!("{} {}", APP_NAME, APP_VERSION)
+}
+
+/// append records to the archive. returns the remaining capacity
+#[update(guard = "caller_is_kong_data")]
+fn append_records(kind: ArchiveKind, records: Vec<ArchiveRecord>) -> Result<u64, String> {
+    let settings = ARCHIVE_SETTINGS.with(|s| s.borrow().get().clone());
+    let num_records = settings.num_records + records.len() as u64;
+    if num_records > settings.max_records {
+        return Err(format!(
+            "Archive full. {} records would exceed max of {}",
+            num_records, settings.max_records
+        ));
+    }
+
+    RECORD_MAP.with(|m| {
+        let mut map = m.borrow_mut();
+        for record in records {
+            map.insert(StableArchiveRecordId { kind, id: record.id }, record);
+        }
+    });
+    ARCHIVE_SETTINGS.with(|s| {
+        s.borrow_mut()
+            .set(StableArchiveSettings {
+                num_records,
+                ..settings.clone()
+            })
+            .map_err(|_| "Failed to update archive settings".to_string())
+    })?;
+
+    Ok(settings.max_records - num_records)
+}
+
+/// archived records of kind starting at start_id in ascending id order
+#[query]
+fn get_records(kind: ArchiveKind, start_id: u64, num_records: Option<u16>) -> Vec<ArchiveRecord> {
+    let num_records = num_records.map_or(MAX_RECORDS_PER_QUERY, |n| n as usize).min(MAX_RECORDS_PER_QUERY);
+    RECORD_MAP.with(|m| {
+        m.borrow()
+            .range(StableArchiveRecordId { kind, id: start_id }..)
+            .take_while(|(k, _)| k.kind == kind)
+            .take(num_records)
+            .map(|(_, v)| v)
+            .collect()
+    })
+}
+
+#[query]
+fn remaining_capacity() -> u64 {
+    ARCHIVE_SETTINGS.with(|s| {
+        let settings = s.borrow();
+        let settings = settings.get();
+        settings.max_records.saturating_sub(settings.num_records)
+    })
+}
+
+ic_cdk::export_candid!();
@@ -0,0 +1,6 @@
+mod archive_record;
+mod canister;
+mod stable_memory;
+
+pub const APP_NAME: &str = "Kong Data Archive";
+pub const APP_VERSION: &str = "v0.0.20";
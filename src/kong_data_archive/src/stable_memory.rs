@@ -0,0 +1,32 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell};
+use std::cell::RefCell;
+
+use crate::archive_record::{ArchiveRecord, StableArchiveRecordId, StableArchiveSettings};
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+const ARCHIVE_SETTINGS_MEMORY_ID: MemoryId = MemoryId::new(0);
+const RECORD_MEMORY_ID: MemoryId = MemoryId::new(1);
+
+thread_local! {
+    // MEMORY_MANAGER is given management of the entire stable memory. Given a 'MemoryId', it can
+    // return a memory that can be used by stable structures
+    pub static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    // stable memory for storing archive settings
+    pub static ARCHIVE_SETTINGS: RefCell<StableCell<StableArchiveSettings, Memory>> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableCell::init(memory_manager.get(ARCHIVE_SETTINGS_MEMORY_ID), StableArchiveSettings::default()).expect("Failed to initialize archive settings"))
+    });
+
+    // stable memory for storing archived records
+    pub static RECORD_MAP: RefCell<StableBTreeMap<StableArchiveRecordId, ArchiveRecord, Memory>> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableBTreeMap::init(memory_manager.get(RECORD_MEMORY_ID)))
+    });
+}
+
+/// A helper function to access the memory manager.
+fn with_memory_manager<R>(f: impl FnOnce(&MemoryManager<DefaultMemoryImpl>) -> R) -> R {
+    MEMORY_MANAGER.with(|cell| f(&cell.borrow()))
+}
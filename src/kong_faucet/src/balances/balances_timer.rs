@@ -0,0 +1,40 @@
+use candid::Principal;
+use futures::future::join_all;
+use icrc_ledger_types::icrc1::account::Account;
+use kong_lib::ic::logging::error_log;
+
+use crate::ic::ledger::get_balance;
+use crate::stable_faucet_token::faucet_token_map;
+use crate::stable_faucet_token::stable_faucet_token::StableFaucetToken;
+
+/// refresh the faucet balance of every faucet token and log an alert for tokens running low
+pub async fn refresh_balances() -> Vec<StableFaucetToken> {
+    let faucet = Account::from(ic_cdk::api::id());
+    let tokens = faucet_token_map::get();
+    let balances = join_all(tokens.iter().map(|token| async move {
+        let ledger = Principal::from_text(&token.ledger).map_err(|e| e.to_string())?;
+        get_balance(faucet, &ledger).await
+    }))
+    .await;
+
+    let mut refreshed = Vec::new();
+    for (token, balance) in tokens.iter().zip(balances) {
+        match balance {
+            Ok(balance) => {
+                if let Some(token) = faucet_token_map::update_balance(&token.ledger, balance) {
+                    if token.enabled && token.is_low_balance() {
+                        error_log(&format!(
+                            "Faucet balance of {} is low: {} (threshold {})",
+                            token.symbol,
+                            token.balance.clone().unwrap_or_default(),
+                            token.low_balance_threshold
+                        ));
+                    }
+                    refreshed.push(token);
+                }
+            }
+            Err(e) => error_log(&format!("Failed to get faucet balance of {}: {}", token.symbol, e)),
+        }
+    }
+    refreshed
+}
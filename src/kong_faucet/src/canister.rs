@@ -1,16 +1,27 @@
 use ic_cdk::api::call::{accept_message, method_name};
 use ic_cdk::{init, post_upgrade, pre_upgrade, query};
 use ic_cdk_macros::inspect_message;
+use ic_cdk_timers::set_timer_interval;
+use std::time::Duration;
 
 use kong_lib::ic::id::caller_principal_id;
 use kong_lib::ic::logging::info_log;
 
 use super::{APP_NAME, APP_VERSION};
 
-static QUERY_METHODS: [&str; 1] = ["icrc1_name"];
+use crate::balances::balances_timer::refresh_balances;
+use crate::stable_faucet_settings::faucet_settings_map;
+use crate::stable_faucet_token::faucet_token_map;
+use crate::stable_memory;
+
+static QUERY_METHODS: [&str; 3] = ["icrc1_name", "get_faucet_tokens", "get_faucet_settings"];
 
 #[init]
 async fn init() {
+    faucet_token_map::init_default_tokens();
+
+    set_timer_processes().await;
+
     info_log(&format!("{} canister has been initialized", APP_NAME));
 }
 
@@ -21,9 +32,25 @@ fn pre_upgrade() {
 
 #[post_upgrade]
 async fn post_upgrade() {
+    // seed the registry once when upgrading from before it existed. tokens removed by admins are not seeded again
+    if !stable_memory::has_faucet_token_map() {
+        faucet_token_map::init_default_tokens();
+    }
+
+    set_timer_processes().await;
+
     info_log(&format!("{} canister is upgraded", APP_NAME));
 }
 
+async fn set_timer_processes() {
+    // start the background timer to monitor faucet balances
+    let _ = set_timer_interval(Duration::from_secs(faucet_settings_map::get().balance_check_interval_secs), || {
+        ic_cdk::spawn(async {
+            refresh_balances().await;
+        });
+    });
+}
+
 /// inspect all ingress messages to the canister that are called as updates
 /// calling accept_message() will allow the message to be processed
 #[inspect_message]
@@ -0,0 +1,187 @@
+use candid::Principal;
+use ic_cdk::api::management_canister::main::raw_rand;
+use ic_cdk::update;
+use kong_lib::ic::get_time::get_time;
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+
+use super::claim_reply::ClaimChallenge;
+
+use crate::ic::id::caller;
+use crate::stable_faucet_settings::faucet_settings_map;
+use crate::stable_user::stable_user::{PowChallenge, StableUser};
+use crate::stable_user::user_map::{get_user, update_user};
+
+const HOUR_NANOSECS: u64 = 3_600_000_000_000;
+
+thread_local! {
+    // start of the current hour and the number of claims in it, for max_claims_per_hour
+    static CLAIM_WINDOW: RefCell<(u64, u32)> = const { RefCell::new((0, 0)) };
+}
+
+/// issue a proof-of-work challenge the caller must solve before claiming
+#[update]
+async fn get_claim_challenge() -> Result<ClaimChallenge, String> {
+    let settings = faucet_settings_map::get();
+    let difficulty = settings.pow_difficulty.ok_or("Proof-of-work is not required")?;
+    let user = get_user()?;
+    if let Some(provider) = settings.siws_provider {
+        if siws_address(&user, provider).await.is_some() {
+            Err("Proof-of-work is not required for Sign-In With Solana identities")?
+        }
+    }
+
+    let (random_bytes,) = raw_rand().await.map_err(|(_, e)| format!("Failed to generate challenge: {}", e))?;
+    let nonce = u64::from_le_bytes(random_bytes[..8].try_into().map_err(|_| "Failed to generate challenge")?);
+    let challenge = PowChallenge {
+        nonce,
+        difficulty,
+        expires_at: get_time() + settings.pow_challenge_expiry_secs * 1_000_000_000,
+    };
+    update_user(StableUser {
+        pow_challenge: Some(challenge.clone()),
+        ..get_user()?
+    })?;
+
+    Ok(ClaimChallenge {
+        nonce: challenge.nonce,
+        difficulty: challenge.difficulty,
+        expires_at: challenge.expires_at,
+    })
+}
+
+/// Solana address of the caller if the caller signed in with the SIWS provider
+pub async fn siws_address(user: &StableUser, provider: Principal) -> Option<String> {
+    if user.siws_address.is_some() {
+        return user.siws_address.clone();
+    }
+    match ic_cdk::call::<(Vec<u8>,), (Result<String, String>,)>(provider, "get_address", (caller().as_slice().to_vec(),)).await {
+        Ok((Ok(address),)) => Some(address),
+        _ => None,
+    }
+}
+
+/// verify the solution of the user's proof-of-work challenge. The challenge can only be used once
+pub fn verify_pow(user: &mut StableUser, principal: &Principal, solution: Option<u64>, now: u64) -> Result<(), String> {
+    let challenge = user
+        .pow_challenge
+        .take()
+        .ok_or("Proof-of-work required. Request a claim challenge first")?;
+    let solution = solution.ok_or("Proof-of-work required. Solve the claim challenge first")?;
+    if now > challenge.expires_at {
+        Err("Claim challenge expired. Request a new claim challenge")?
+    }
+    if pow_leading_zeros(principal, challenge.nonce, solution) < challenge.difficulty as u32 {
+        Err("Invalid proof-of-work solution")?
+    }
+    Ok(())
+}
+
+fn pow_leading_zeros(principal: &Principal, nonce: u64, solution: u64) -> u32 {
+    let mut hasher = Sha256::new();
+    hasher.update(principal.as_slice());
+    hasher.update(nonce.to_le_bytes());
+    hasher.update(solution.to_le_bytes());
+    let hash = hasher.finalize();
+
+    let mut zeros = 0;
+    for byte in hash.iter() {
+        zeros += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    zeros
+}
+
+/// reserve a claim in the faucet-wide hourly limit
+pub fn reserve_claim_rate(max_claims_per_hour: Option<u32>, now: u64) -> Result<(), String> {
+    let Some(max_claims_per_hour) = max_claims_per_hour else {
+        return Ok(());
+    };
+    CLAIM_WINDOW.with(|window| {
+        let mut window = window.borrow_mut();
+        if now - window.0 >= HOUR_NANOSECS {
+            *window = (now, 0);
+        }
+        if window.1 >= max_claims_per_hour {
+            Err("The faucet is busy. Please try again later".to_string())?
+        }
+        window.1 += 1;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(principal: &Principal, nonce: u64, difficulty: u8) -> u64 {
+        (0..)
+            .find(|solution| pow_leading_zeros(principal, nonce, *solution) >= difficulty as u32)
+            .unwrap()
+    }
+
+    fn user_with_challenge(nonce: u64, difficulty: u8, expires_at: u64) -> StableUser {
+        StableUser {
+            pow_challenge: Some(PowChallenge {
+                nonce,
+                difficulty,
+                expires_at,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_verify_pow() {
+        let principal = Principal::from_slice(&[1, 2, 3]);
+        let solution = solve(&principal, 42, 8);
+
+        let mut user = user_with_challenge(42, 8, 1_000);
+        assert!(verify_pow(&mut user, &principal, Some(solution), 500).is_ok());
+        // the challenge can only be used once
+        assert!(user.pow_challenge.is_none());
+        assert!(verify_pow(&mut user, &principal, Some(solution), 500).is_err());
+    }
+
+    #[test]
+    fn test_verify_pow_rejects_invalid_solutions() {
+        let principal = Principal::from_slice(&[1, 2, 3]);
+        let solution = solve(&principal, 42, 8);
+
+        // expired
+        let mut user = user_with_challenge(42, 8, 1_000);
+        assert!(verify_pow(&mut user, &principal, Some(solution), 1_001).is_err());
+        assert!(user.pow_challenge.is_none());
+
+        // missing solution
+        let mut user = user_with_challenge(42, 8, 1_000);
+        assert!(verify_pow(&mut user, &principal, None, 500).is_err());
+
+        // solved for another principal
+        let other = Principal::from_slice(&[4, 5, 6]);
+        let other_solution = (0..).find(|s| pow_leading_zeros(&other, 42, *s) >= 8 && pow_leading_zeros(&principal, 42, *s) < 8);
+        let mut user = user_with_challenge(42, 8, 1_000);
+        assert!(verify_pow(&mut user, &principal, other_solution, 500).is_err());
+    }
+
+    #[test]
+    fn test_pow_leading_zeros_counts_across_bytes() {
+        let principal = Principal::from_slice(&[1, 2, 3]);
+        let solution = solve(&principal, 7, 12);
+        assert!(pow_leading_zeros(&principal, 7, solution) >= 12);
+    }
+
+    #[test]
+    fn test_reserve_claim_rate() {
+        assert!(reserve_claim_rate(None, 0).is_ok());
+
+        let now = 10 * HOUR_NANOSECS;
+        assert!(reserve_claim_rate(Some(2), now).is_ok());
+        assert!(reserve_claim_rate(Some(2), now + 1).is_ok());
+        assert!(reserve_claim_rate(Some(2), now + 2).is_err());
+        // a new hour resets the window
+        assert!(reserve_claim_rate(Some(2), now + HOUR_NANOSECS).is_ok());
+    }
+}
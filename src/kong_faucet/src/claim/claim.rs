@@ -0,0 +1,145 @@
+use candid::{Nat, Principal};
+use futures::future::join_all;
+use ic_cdk::update;
+use icrc_ledger_types::icrc1::account::Account;
+use kong_lib::ic::get_time::get_time;
+use kong_lib::ic::id::caller_id;
+use kong_lib::ic::logging::error_log;
+
+use super::anti_abuse::{reserve_claim_rate, siws_address, verify_pow};
+use super::claim_args::ClaimArgs;
+use super::claim_reply::{ClaimReply, TokenClaimReply, TokenClaimStatus};
+
+use crate::ic::id::caller;
+use crate::ic::transfer::icrc1_transfer;
+use crate::stable_faucet_settings::faucet_settings_map;
+use crate::stable_faucet_token::faucet_token_map;
+use crate::stable_faucet_token::stable_faucet_token::StableFaucetToken;
+use crate::stable_user::user_map::{get_user, update_user};
+
+/// claim all enabled faucet tokens. Kept for existing clients, use claim_tokens for per-token results
+#[update]
+pub async fn claim(args: Option<ClaimArgs>) -> Result<String, String> {
+    let reply = claim_tokens(args).await?;
+    let failed: Vec<String> = reply
+        .claims
+        .iter()
+        .filter_map(|claim| match &claim.status {
+            TokenClaimStatus::Failed { error } => Some(format!("{}: {}", claim.symbol, error)),
+            _ => None,
+        })
+        .collect();
+
+    match (reply.num_claimed(), failed.is_empty()) {
+        (0, true) => Err("You have already claimed all tokens. Please wait before claiming more.".to_string()),
+        (0, false) => Err(format!("Failed to claim tokens. {}", failed.join(", "))),
+        (_, true) => Ok("Tokens successfully claimed! Please wait before claiming more.".to_string()),
+        (_, false) => Ok(format!("Some tokens were claimed. Failed: {}", failed.join(", "))),
+    }
+}
+
+/// claim faucet tokens that are not cooling down, reporting the result of each token
+#[update]
+pub async fn claim_tokens(args: Option<ClaimArgs>) -> Result<ClaimReply, String> {
+    let args = args.unwrap_or_default();
+    let settings = faucet_settings_map::get();
+
+    // Sign-In With Solana identities skip proof-of-work, or are required when proof-of-work is disabled
+    let mut siws_verified = None;
+    if let Some(provider) = settings.siws_provider {
+        siws_verified = siws_address(&get_user()?, provider).await;
+        if siws_verified.is_none() && settings.pow_difficulty.is_none() {
+            Err("Sign in with Solana to claim from the faucet")?
+        }
+    }
+
+    // from here to the transfers nothing is awaited so claims are reserved atomically
+    let mut user = get_user()?;
+    let now = get_time();
+    if siws_verified.is_some() {
+        user.siws_address = siws_verified;
+    } else if settings.pow_difficulty.is_some() {
+        let pow = verify_pow(&mut user, &caller(), args.pow_solution, now);
+        // a failed solution uses up the challenge
+        update_user(user.clone())?;
+        pow?;
+    }
+
+    let tokens: Vec<StableFaucetToken> = faucet_token_map::get()
+        .into_iter()
+        .filter(|token| token.enabled)
+        .filter(|token| args.ledgers.as_ref().is_none_or(|ledgers| ledgers.contains(&token.ledger)))
+        .collect();
+    if tokens.is_empty() {
+        Err("No faucet tokens to claim")?
+    }
+
+    let mut claims = Vec::new();
+    let mut to_claim = Vec::new();
+    for token in tokens {
+        let next_claim_at = user.next_token_claim_at(&token.ledger, token.cooldown_secs);
+        if now < next_claim_at {
+            claims.push(token_claim_reply(&token, TokenClaimStatus::CoolingDown { next_claim_at }));
+        } else {
+            to_claim.push(token);
+        }
+    }
+    if !to_claim.is_empty() {
+        reserve_claim_rate(settings.max_claims_per_hour, now)?;
+    }
+
+    // reserve the claims before transferring so concurrent calls can not claim twice
+    let previous_claimed_at = user.last_claimed_at;
+    let previous_claims: Vec<Option<u64>> = to_claim.iter().map(|token| user.reserve_token_claim(&token.ledger, now)).collect();
+    update_user(user)?;
+
+    let caller_id = caller_id();
+    let results = join_all(to_claim.iter().map(|token| transfer_token(token, &caller_id))).await;
+
+    // release the reservation of tokens that failed to transfer
+    let mut user = get_user()?;
+    for ((token, result), previous_claim) in to_claim.iter().zip(results).zip(previous_claims) {
+        let status = match result {
+            Ok(block_index) => TokenClaimStatus::Claimed { block_index },
+            Err(error) => {
+                user.release_token_claim(&token.ledger, previous_claim);
+                TokenClaimStatus::Failed { error }
+            }
+        };
+        claims.push(token_claim_reply(token, status));
+    }
+    // last_claimed_at is the cooldown of users without per-token claims, so it only moves if something was claimed
+    if !claims.iter().any(|claim| matches!(claim.status, TokenClaimStatus::Claimed { .. })) {
+        user.last_claimed_at = previous_claimed_at;
+    }
+    update_user(user.clone())?;
+
+    let next_claim_at = faucet_token_map::get()
+        .iter()
+        .filter(|token| token.enabled)
+        .map(|token| user.next_token_claim_at(&token.ledger, token.cooldown_secs))
+        .min();
+    Ok(ClaimReply { claims, next_claim_at })
+}
+
+fn token_claim_reply(token: &StableFaucetToken, status: TokenClaimStatus) -> TokenClaimReply {
+    TokenClaimReply {
+        symbol: token.symbol.clone(),
+        ledger: token.ledger.clone(),
+        amount: token.amount.clone(),
+        status,
+    }
+}
+
+async fn transfer_token(token: &StableFaucetToken, to_address: &Account) -> Result<Nat, String> {
+    let ledger = Principal::from_text(&token.ledger).map_err(|e| log_transfer_error(&token.symbol, &e.to_string()))?;
+    icrc1_transfer(&token.amount, to_address, &ledger)
+        .await
+        .map_err(|e| log_transfer_error(&token.symbol, e.as_str()))
+}
+
+pub fn log_transfer_error(token: &str, e: &str) -> String {
+    let error = format!("{} claim failed: {}", token, e);
+    error_log(&error);
+    error
+}
@@ -0,0 +1,8 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClaimArgs {
+    pub pow_solution: Option<u64>,    // solution to the caller's proof-of-work challenge, see get_claim_challenge
+    pub ledgers: Option<Vec<String>>, // tokens to claim. None claims all enabled tokens
+}
@@ -0,0 +1,50 @@
+use candid::{CandidType, Nat};
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub enum TokenClaimStatus {
+    Claimed { block_index: Nat },
+    Failed { error: String },
+    CoolingDown { next_claim_at: u64 },
+}
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct TokenClaimReply {
+    pub symbol: String,
+    pub ledger: String,
+    pub amount: Nat,
+    pub status: TokenClaimStatus,
+}
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimReply {
+    pub claims: Vec<TokenClaimReply>,
+    pub next_claim_at: Option<u64>, // earliest time another token can be claimed
+}
+
+impl ClaimReply {
+    pub fn num_claimed(&self) -> usize {
+        self.claims
+            .iter()
+            .filter(|claim| matches!(claim.status, TokenClaimStatus::Claimed { .. }))
+            .count()
+    }
+}
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimChallenge {
+    pub nonce: u64,
+    pub difficulty: u8, // sha256(caller principal bytes ++ nonce ++ solution), little endian, must start with this many zero bits
+    pub expires_at: u64,
+}
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct FaucetTokenReply {
+    pub symbol: String,
+    pub ledger: String,
+    pub amount: Nat,
+    pub cooldown_secs: u64,
+    pub balance: Option<Nat>,
+    pub low_balance: bool,
+    pub next_claim_at: Option<u64>, // None if the caller can claim now
+}
@@ -0,0 +1,34 @@
+use ic_cdk::query;
+use kong_lib::ic::get_time::get_time;
+
+use super::claim_reply::FaucetTokenReply;
+
+use crate::ic::id::is_caller_anonymous;
+use crate::stable_faucet_token::faucet_token_map;
+use crate::stable_user::user_map::get_user;
+
+/// enabled faucet tokens with their faucet balance and when the caller can claim them next
+#[query]
+fn get_faucet_tokens() -> Vec<FaucetTokenReply> {
+    let user = if is_caller_anonymous() { None } else { get_user().ok() };
+    let now = get_time();
+    faucet_token_map::get()
+        .into_iter()
+        .filter(|token| token.enabled)
+        .map(|token| {
+            let next_claim_at = user
+                .as_ref()
+                .map(|user| user.next_token_claim_at(&token.ledger, token.cooldown_secs))
+                .filter(|next_claim_at| *next_claim_at > now);
+            FaucetTokenReply {
+                low_balance: token.is_low_balance(),
+                symbol: token.symbol,
+                ledger: token.ledger,
+                amount: token.amount,
+                cooldown_secs: token.cooldown_secs,
+                balance: token.balance,
+                next_claim_at,
+            }
+        })
+        .collect()
+}
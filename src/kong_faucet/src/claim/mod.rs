@@ -0,0 +1,6 @@
+pub mod anti_abuse;
+#[allow(clippy::module_inception)]
+pub mod claim;
+pub mod claim_args;
+pub mod claim_reply;
+pub mod claim_status;
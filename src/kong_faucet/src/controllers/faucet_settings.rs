@@ -0,0 +1,16 @@
+use ic_cdk::{query, update};
+
+use crate::ic::guards::caller_is_controller;
+use crate::stable_faucet_settings::faucet_settings_map;
+use crate::stable_faucet_settings::stable_faucet_settings::StableFaucetSettings;
+
+#[query(hidden = true, guard = "caller_is_controller")]
+fn get_faucet_settings() -> Result<StableFaucetSettings, String> {
+    Ok(faucet_settings_map::get())
+}
+
+/// update the anti-abuse settings and balance check interval. A new interval is used after the next upgrade
+#[update(hidden = true, guard = "caller_is_controller")]
+fn set_faucet_settings(settings: StableFaucetSettings) -> Result<StableFaucetSettings, String> {
+    faucet_settings_map::set(settings)
+}
@@ -0,0 +1,23 @@
+use ic_cdk::update;
+
+use crate::balances::balances_timer::refresh_balances;
+use crate::ic::guards::caller_is_controller;
+use crate::stable_faucet_token::faucet_token_map;
+use crate::stable_faucet_token::stable_faucet_token::StableFaucetToken;
+
+/// add a faucet token or update its claim amount, cooldown and low balance threshold
+#[update(hidden = true, guard = "caller_is_controller")]
+fn set_faucet_token(token: StableFaucetToken) -> Result<StableFaucetToken, String> {
+    faucet_token_map::insert(token)
+}
+
+#[update(hidden = true, guard = "caller_is_controller")]
+fn remove_faucet_token(ledger: String) -> Result<StableFaucetToken, String> {
+    faucet_token_map::remove(&ledger)
+}
+
+/// refresh faucet balances now instead of waiting for the balance timer
+#[update(hidden = true, guard = "caller_is_controller")]
+async fn refresh_faucet_balances() -> Result<Vec<StableFaucetToken>, String> {
+    Ok(refresh_balances().await)
+}
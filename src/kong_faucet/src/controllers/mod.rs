@@ -0,0 +1,2 @@
+mod faucet_settings;
+mod faucet_tokens;
@@ -1,15 +1,22 @@
 // Tokens
 pub const CKUSDT: &str = "ckUSDT";
 pub const CKUSDT_LEDGER: &str = "cngnf-vqaaa-aaaar-qag4q-cai";
+pub const CKUSDT_CLAIM_AMOUNT: u128 = 100_000_000; // 100 ckUSDT
 
 pub const ICP: &str = "ICP";
 pub const ICP_LEDGER: &str = "ryjl3-tyaaa-aaaaa-aaaba-cai";
+pub const ICP_CLAIM_AMOUNT: u128 = 1_000_000_000; // 10 ICP
 
 pub const CKBTC: &str = "ckBTC";
 pub const CKBTC_LEDGER: &str = "mxzaz-hqaaa-aaaar-qaada-cai";
+pub const CKBTC_CLAIM_AMOUNT: u128 = 200_000; // 0.002 ckBTC
 
 pub const CKETH: &str = "ckETH";
 pub const CKETH_LEDGER: &str = "ss2fx-dyaaa-aaaar-qacoq-cai";
+pub const CKETH_CLAIM_AMOUNT: u128 = 50_000_000_000_000_000; // 0.05 ckETH
 
 pub const KONG: &str = "KONG";
 pub const KONG_LEDGER: &str = "o7oak-iyaaa-aaaaq-aadzq-cai";
+pub const KONG_CLAIM_AMOUNT: u128 = 100_000_000_000; // 1000 KONG
+
+pub const DEFAULT_CLAIM_COOLDOWN_SECS: u64 = 86_400; // 24 hours
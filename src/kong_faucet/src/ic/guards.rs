@@ -0,0 +1,9 @@
+use kong_lib::ic::id::is_caller_controller;
+
+/// guard to make sure caller is a controller of the faucet
+pub fn caller_is_controller() -> Result<(), String> {
+    if !is_caller_controller() {
+        return Err("Caller is not a controller".to_string());
+    }
+    Ok(())
+}
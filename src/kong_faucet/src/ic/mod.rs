@@ -1,3 +1,5 @@
 pub mod constants;
+pub mod guards;
 pub mod id;
+pub mod ledger;
 pub mod transfer;
@@ -1,8 +1,12 @@
+mod balances;
 mod canister;
 mod claim;
+mod controllers;
 mod ic;
+mod stable_faucet_settings;
+mod stable_faucet_token;
 mod stable_memory;
 mod stable_user;
 
 pub const APP_NAME: &str = "Kong Swap Faucet";
-pub const APP_VERSION: &str = "v0.0.19";
+pub const APP_VERSION: &str = "v0.0.20";
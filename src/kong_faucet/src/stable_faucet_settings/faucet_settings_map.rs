@@ -0,0 +1,22 @@
+use crate::stable_memory::FAUCET_SETTINGS;
+
+use super::stable_faucet_settings::StableFaucetSettings;
+
+pub fn get() -> StableFaucetSettings {
+    FAUCET_SETTINGS.with(|s| s.borrow().get().clone())
+}
+
+pub fn set(settings: StableFaucetSettings) -> Result<StableFaucetSettings, String> {
+    if settings.pow_difficulty.is_some_and(|difficulty| difficulty > 32) {
+        Err("Proof-of-work difficulty can be at most 32 bits")?
+    }
+    if settings.balance_check_interval_secs < 60 {
+        Err("Balance check interval must be at least 60 seconds")?
+    }
+    FAUCET_SETTINGS.with(|s| {
+        s.borrow_mut()
+            .set(settings.clone())
+            .map_err(|_| "Failed to update faucet settings".to_string())
+    })?;
+    Ok(settings)
+}
@@ -0,0 +1,3 @@
+pub mod faucet_settings_map;
+#[allow(clippy::module_inception)]
+pub mod stable_faucet_settings;
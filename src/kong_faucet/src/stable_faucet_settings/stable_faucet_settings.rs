@@ -0,0 +1,37 @@
+use candid::{CandidType, Decode, Deserialize, Encode, Principal};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::Serialize;
+use std::borrow::Cow;
+
+#[derive(CandidType, Debug, Clone, Deserialize, Serialize)]
+pub struct StableFaucetSettings {
+    pub pow_difficulty: Option<u8>, // leading zero bits required for proof-of-work, None disables proof-of-work
+    pub pow_challenge_expiry_secs: u64, // how long a proof-of-work challenge can be solved
+    pub siws_provider: Option<Principal>, // SIWS provider. Callers with a Sign-In With Solana identity skip proof-of-work
+    pub max_claims_per_hour: Option<u32>, // claims across all users per hour
+    pub balance_check_interval_secs: u64, // interval of the faucet balance monitoring timer
+}
+
+impl Default for StableFaucetSettings {
+    fn default() -> Self {
+        Self {
+            pow_difficulty: None,
+            pow_challenge_expiry_secs: 300,
+            siws_provider: None,
+            max_claims_per_hour: None,
+            balance_check_interval_secs: 3_600,
+        }
+    }
+}
+
+impl Storable for StableFaucetSettings {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -0,0 +1,143 @@
+use candid::{Nat, Principal};
+use kong_lib::ic::get_time::get_time;
+
+use super::stable_faucet_token::{StableFaucetToken, StableFaucetTokenId};
+
+use crate::ic::constants::{
+    CKBTC, CKBTC_CLAIM_AMOUNT, CKBTC_LEDGER, CKETH, CKETH_CLAIM_AMOUNT, CKETH_LEDGER, CKUSDT, CKUSDT_CLAIM_AMOUNT, CKUSDT_LEDGER,
+    DEFAULT_CLAIM_COOLDOWN_SECS, ICP, ICP_CLAIM_AMOUNT, ICP_LEDGER, KONG, KONG_CLAIM_AMOUNT, KONG_LEDGER,
+};
+use crate::stable_memory::FAUCET_TOKEN_MAP;
+
+pub fn get() -> Vec<StableFaucetToken> {
+    FAUCET_TOKEN_MAP.with(|m| m.borrow().iter().map(|(_, v)| v).collect())
+}
+
+pub fn get_by_ledger(ledger: &str) -> Option<StableFaucetToken> {
+    FAUCET_TOKEN_MAP.with(|m| m.borrow().get(&StableFaucetTokenId(ledger.to_string())))
+}
+
+/// add or replace a faucet token. Cached balance of an existing token is kept
+pub fn insert(token: StableFaucetToken) -> Result<StableFaucetToken, String> {
+    let ledger = Principal::from_text(&token.ledger).map_err(|e| format!("Invalid ledger {}: {}", token.ledger, e))?;
+    if token.symbol.trim().is_empty() {
+        Err("Symbol is required")?
+    }
+    if token.amount == 0_u8 {
+        Err("Claim amount must be greater than 0")?
+    }
+
+    let token = match get_by_ledger(&token.ledger) {
+        Some(existing) => StableFaucetToken {
+            balance: existing.balance,
+            balance_checked_at: existing.balance_checked_at,
+            ..token
+        },
+        None => token,
+    };
+    FAUCET_TOKEN_MAP.with(|m| m.borrow_mut().insert(StableFaucetTokenId(ledger.to_text()), token.clone()));
+    Ok(token)
+}
+
+pub fn remove(ledger: &str) -> Result<StableFaucetToken, String> {
+    FAUCET_TOKEN_MAP
+        .with(|m| m.borrow_mut().remove(&StableFaucetTokenId(ledger.to_string())))
+        .ok_or(format!("Faucet token {} not found", ledger))
+}
+
+pub fn update_balance(ledger: &str, balance: Nat) -> Option<StableFaucetToken> {
+    FAUCET_TOKEN_MAP.with(|m| {
+        let mut map = m.borrow_mut();
+        let key = StableFaucetTokenId(ledger.to_string());
+        let token = StableFaucetToken {
+            balance: Some(balance),
+            balance_checked_at: Some(get_time()),
+            ..map.get(&key)?
+        };
+        map.insert(key, token.clone());
+        Some(token)
+    })
+}
+
+/// seed the registry with the tokens the faucet was originally hardcoded with. called on init and on the upgrade that creates the registry
+pub fn init_default_tokens() {
+    if FAUCET_TOKEN_MAP.with(|m| !m.borrow().is_empty()) {
+        return;
+    }
+    for (symbol, ledger, amount) in [
+        (CKUSDT, CKUSDT_LEDGER, CKUSDT_CLAIM_AMOUNT),
+        (ICP, ICP_LEDGER, ICP_CLAIM_AMOUNT),
+        (CKBTC, CKBTC_LEDGER, CKBTC_CLAIM_AMOUNT),
+        (CKETH, CKETH_LEDGER, CKETH_CLAIM_AMOUNT),
+        (KONG, KONG_LEDGER, KONG_CLAIM_AMOUNT),
+    ] {
+        _ = insert(StableFaucetToken {
+            symbol: symbol.to_string(),
+            ledger: ledger.to_string(),
+            amount: Nat::from(amount),
+            cooldown_secs: DEFAULT_CLAIM_COOLDOWN_SECS,
+            enabled: true,
+            low_balance_threshold: Nat::from(amount * 10),
+            balance: None,
+            balance_checked_at: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(symbol: &str, ledger: &str, amount: u64) -> StableFaucetToken {
+        StableFaucetToken {
+            symbol: symbol.to_string(),
+            ledger: ledger.to_string(),
+            amount: Nat::from(amount),
+            cooldown_secs: 60,
+            enabled: true,
+            low_balance_threshold: Nat::from(amount * 10),
+            balance: None,
+            balance_checked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_insert_validates_token() {
+        assert!(insert(token("ICP", "not a principal", 1)).is_err());
+        assert!(insert(token(" ", ICP_LEDGER, 1)).is_err());
+        assert!(insert(token("ICP", ICP_LEDGER, 0)).is_err());
+        assert!(get_by_ledger(ICP_LEDGER).is_none());
+    }
+
+    #[test]
+    fn test_insert_keeps_cached_balance() {
+        insert(token("ckBTC", CKBTC_LEDGER, 100)).unwrap();
+        FAUCET_TOKEN_MAP.with(|m| {
+            let key = StableFaucetTokenId(CKBTC_LEDGER.to_string());
+            let cached = StableFaucetToken {
+                balance: Some(Nat::from(500_u64)),
+                balance_checked_at: Some(1),
+                ..m.borrow().get(&key).unwrap()
+            };
+            m.borrow_mut().insert(key, cached);
+        });
+
+        let replaced = insert(token("ckBTC", CKBTC_LEDGER, 200)).unwrap();
+        assert_eq!(replaced.amount, Nat::from(200_u64));
+        assert_eq!(replaced.balance, Some(Nat::from(500_u64)));
+        assert!(replaced.is_low_balance());
+
+        assert!(remove(CKBTC_LEDGER).is_ok());
+        assert!(remove(CKBTC_LEDGER).is_err());
+    }
+
+    #[test]
+    fn test_init_default_tokens_only_seeds_empty_registry() {
+        init_default_tokens();
+        assert_eq!(get().len(), 5);
+
+        remove(KONG_LEDGER).unwrap();
+        init_default_tokens();
+        assert!(get_by_ledger(KONG_LEDGER).is_none());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod faucet_token_map;
+#[allow(clippy::module_inception)]
+pub mod stable_faucet_token;
@@ -0,0 +1,55 @@
+use candid::{CandidType, Decode, Deserialize, Encode, Nat};
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::Serialize;
+use std::borrow::Cow;
+
+const FAUCET_TOKEN_ID_SIZE: u32 = 256; // 64 characters * 4 bytes per character = 256 bytes
+
+/// ledger canister id of the faucet token
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Serialize)]
+pub struct StableFaucetTokenId(pub String);
+
+impl Storable for StableFaucetTokenId {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        self.0.to_bytes() // String is already Storable
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(String::from_bytes(bytes))
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: FAUCET_TOKEN_ID_SIZE,
+        is_fixed_size: false,
+    };
+}
+
+#[derive(CandidType, Debug, Clone, Deserialize, Serialize)]
+pub struct StableFaucetToken {
+    pub symbol: String,
+    pub ledger: String,
+    pub amount: Nat, // amount per claim
+    pub cooldown_secs: u64,
+    pub enabled: bool,
+    pub low_balance_threshold: Nat, // faucet balance below which an alert is logged
+    pub balance: Option<Nat>,       // faucet balance at the last balance check
+    pub balance_checked_at: Option<u64>,
+}
+
+impl StableFaucetToken {
+    pub fn is_low_balance(&self) -> bool {
+        self.balance.as_ref().is_some_and(|balance| *balance < self.low_balance_threshold)
+    }
+}
+
+impl Storable for StableFaucetToken {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -1,12 +1,16 @@
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
-use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use ic_stable_structures::{DefaultMemoryImpl, Memory as _, StableBTreeMap, StableCell};
 use std::cell::RefCell;
 
+use crate::stable_faucet_settings::stable_faucet_settings::StableFaucetSettings;
+use crate::stable_faucet_token::stable_faucet_token::{StableFaucetToken, StableFaucetTokenId};
 use crate::stable_user::stable_user::{StableUser, StableUserId};
 
 type Memory = VirtualMemory<DefaultMemoryImpl>;
 
 const USER_MEMORY_ID: MemoryId = MemoryId::new(0);
+const FAUCET_TOKEN_MEMORY_ID: MemoryId = MemoryId::new(1);
+const FAUCET_SETTINGS_MEMORY_ID: MemoryId = MemoryId::new(2);
 
 thread_local! {
     // MEMORY_MANAGER is given management of the entire stable memory. Given a 'MemoryId', it can
@@ -17,6 +21,23 @@ thread_local! {
     pub static USER_MAP: RefCell<StableBTreeMap<StableUserId, StableUser, Memory>> = with_memory_manager(|memory_manager| {
         RefCell::new(StableBTreeMap::init(memory_manager.get(USER_MEMORY_ID)))
     });
+
+    // registry of tokens given out by the faucet
+    pub static FAUCET_TOKEN_MAP: RefCell<StableBTreeMap<StableFaucetTokenId, StableFaucetToken, Memory>> = with_memory_manager(|memory_manager| {
+        RefCell::new(StableBTreeMap::init(memory_manager.get(FAUCET_TOKEN_MEMORY_ID)))
+    });
+
+    pub static FAUCET_SETTINGS: RefCell<StableCell<StableFaucetSettings, Memory>> = with_memory_manager(|memory_manager| {
+        RefCell::new(
+            StableCell::init(memory_manager.get(FAUCET_SETTINGS_MEMORY_ID), StableFaucetSettings::default())
+                .expect("Failed to initialize faucet settings"),
+        )
+    });
+}
+
+/// whether the faucet token registry was ever created. must be checked before FAUCET_TOKEN_MAP is first used
+pub fn has_faucet_token_map() -> bool {
+    with_memory_manager(|memory_manager| memory_manager.get(FAUCET_TOKEN_MEMORY_ID).size() > 0)
 }
 
 /// A helper function to access the memory manager.
@@ -2,9 +2,9 @@ use candid::{CandidType, Decode, Deserialize, Encode};
 use ic_stable_structures::{storable::Bound, Storable};
 use serde::Serialize;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 
 const USER_ID_SIZE: u32 = 256; // 64 characters * 4 bytes per character = 256 bytes
-const USER_VALUE_SIZE: u32 = 32;
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Serialize)]
 pub struct StableUserId(pub String);
@@ -24,9 +24,50 @@ impl Storable for StableUserId {
     };
 }
 
+#[derive(CandidType, Debug, Clone, Deserialize, Serialize)]
+pub struct PowChallenge {
+    pub nonce: u64,
+    pub difficulty: u8,
+    pub expires_at: u64,
+}
+
 #[derive(CandidType, Default, Clone, Deserialize, Serialize)]
 pub struct StableUser {
-    pub last_claimed_at: u64,
+    pub last_claimed_at: u64, // last claim of any token
+    // ledger -> last claimed at. opt so users stored before per-token tracking still decode
+    pub token_claims: Option<BTreeMap<String, u64>>,
+    pub pow_challenge: Option<PowChallenge>, // outstanding proof-of-work challenge
+    pub siws_address: Option<String>,        // Solana address of a Sign-In With Solana identity
+}
+
+impl StableUser {
+    /// last claim of a token. Users who claimed before per-token tracking fall back to last_claimed_at
+    pub fn last_token_claim(&self, ledger: &str) -> u64 {
+        match &self.token_claims {
+            Some(token_claims) if !token_claims.is_empty() => token_claims.get(ledger).copied().unwrap_or(0),
+            _ => self.last_claimed_at,
+        }
+    }
+
+    /// time the token can be claimed again
+    pub fn next_token_claim_at(&self, ledger: &str, cooldown_secs: u64) -> u64 {
+        self.last_token_claim(ledger) + cooldown_secs * 1_000_000_000
+    }
+
+    /// reserve a claim of the token, returning the previous claim to restore if the transfer fails
+    pub fn reserve_token_claim(&mut self, ledger: &str, now: u64) -> Option<u64> {
+        self.last_claimed_at = now;
+        self.token_claims.get_or_insert_with(BTreeMap::new).insert(ledger.to_string(), now)
+    }
+
+    /// release a claim reserved by reserve_token_claim
+    pub fn release_token_claim(&mut self, ledger: &str, previous_claim: Option<u64>) {
+        let token_claims = self.token_claims.get_or_insert_with(BTreeMap::new);
+        match previous_claim {
+            Some(claimed_at) => token_claims.insert(ledger.to_string(), claimed_at),
+            None => token_claims.remove(ledger),
+        };
+    }
 }
 
 impl Storable for StableUser {
@@ -38,8 +79,96 @@ impl Storable for StableUser {
         Decode!(bytes.as_ref(), Self).unwrap()
     }
 
-    const BOUND: Bound = Bound::Bounded {
-        max_size: USER_VALUE_SIZE,
-        is_fixed_size: false,
-    };
+    // unbounded to hold per-token claims. Maps stored with the bounded layout are migrated on load
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEDGER_A: &str = "ryjl3-tyaaa-aaaaa-aaaba-cai";
+    const LEDGER_B: &str = "mxzaz-hqaaa-aaaar-qaada-cai";
+
+    #[test]
+    fn test_decode_user_stored_before_per_token_claims() {
+        // StableUser as stored before per-token claims, proof-of-work and SIWS
+        #[derive(CandidType)]
+        struct LegacyStableUser {
+            last_claimed_at: u64,
+        }
+        let bytes = Encode!(&LegacyStableUser { last_claimed_at: 100 }).unwrap();
+
+        let user = StableUser::from_bytes(Cow::Owned(bytes));
+        assert_eq!(user.last_claimed_at, 100);
+        assert!(user.token_claims.is_none());
+        assert!(user.pow_challenge.is_none());
+        assert!(user.siws_address.is_none());
+        // every token cools down from the last claim
+        assert_eq!(user.last_token_claim(LEDGER_A), 100);
+        assert_eq!(user.last_token_claim(LEDGER_B), 100);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut user = StableUser {
+            pow_challenge: Some(PowChallenge {
+                nonce: 7,
+                difficulty: 12,
+                expires_at: 500,
+            }),
+            siws_address: Some("So11111111111111111111111111111111111111112".to_string()),
+            ..Default::default()
+        };
+        user.reserve_token_claim(LEDGER_A, 200);
+
+        let decoded = StableUser::from_bytes(user.to_bytes());
+        assert_eq!(decoded.last_claimed_at, 200);
+        assert_eq!(decoded.token_claims, user.token_claims);
+        assert_eq!(decoded.pow_challenge.map(|challenge| challenge.nonce), Some(7));
+        assert_eq!(decoded.siws_address, user.siws_address);
+    }
+
+    #[test]
+    fn test_token_cooldowns() {
+        let mut user = StableUser::default();
+        assert_eq!(user.next_token_claim_at(LEDGER_A, 60), 60_000_000_000);
+
+        user.reserve_token_claim(LEDGER_A, 1_000_000_000);
+        assert_eq!(user.next_token_claim_at(LEDGER_A, 60), 61_000_000_000);
+        // tokens cool down independently once claims are tracked per token
+        assert_eq!(user.next_token_claim_at(LEDGER_B, 60), 60_000_000_000);
+    }
+
+    #[test]
+    fn test_release_failed_claims() {
+        let mut user = StableUser {
+            last_claimed_at: 100,
+            ..Default::default()
+        };
+        // both claims succeed
+        assert_eq!(user.reserve_token_claim(LEDGER_A, 200), None);
+        assert_eq!(user.reserve_token_claim(LEDGER_B, 200), None);
+
+        // next claim: A transfers, B fails and is released back to its previous claim
+        assert_eq!(user.reserve_token_claim(LEDGER_A, 300), Some(200));
+        let previous_claim = user.reserve_token_claim(LEDGER_B, 300);
+        user.release_token_claim(LEDGER_B, previous_claim);
+        assert_eq!(user.last_token_claim(LEDGER_A), 300);
+        assert_eq!(user.last_token_claim(LEDGER_B), 200);
+    }
+
+    #[test]
+    fn test_release_first_claim_falls_back_to_last_claimed_at() {
+        let mut user = StableUser {
+            last_claimed_at: 100,
+            ..Default::default()
+        };
+        let previous_claim = user.reserve_token_claim(LEDGER_A, 200);
+        user.release_token_claim(LEDGER_A, previous_claim);
+        // claim.rs restores last_claimed_at when nothing was claimed
+        user.last_claimed_at = 100;
+        assert_eq!(user.last_token_claim(LEDGER_A), 100);
+        assert_eq!(user.last_token_claim(LEDGER_B), 100);
+    }
 }
@@ -1,4 +1,3 @@
-use kong_lib::ic::get_time::get_time;
 use kong_lib::ic::id::caller_principal_id;
 
 use super::stable_user::{StableUser, StableUserId};
@@ -15,7 +14,7 @@ fn get() -> Option<StableUser> {
 }
 
 // this will get a user or return a default user if the user is not found
-pub async fn get_user() -> Result<StableUser, String> {
+pub fn get_user() -> Result<StableUser, String> {
     if is_caller_anonymous() {
         Err("Anonymous user")?
     }
@@ -33,16 +32,14 @@ pub async fn get_user() -> Result<StableUser, String> {
     })
 }
 
-// update the user's last_claimed_at with now
-pub fn update_user_token_claim() -> Result<u64, String> {
-    match get() {
-        Some(mut user) => {
-            user.last_claimed_at = get_time();
-            USER_MAP.with(|m| {
-                m.borrow_mut().insert(StableUserId(caller_principal_id()), user.clone());
-            });
-            Ok(user.last_claimed_at)
-        }
-        None => Err("User not found".to_string()),
+// update the caller's user
+pub fn update_user(user: StableUser) -> Result<(), String> {
+    if is_caller_anonymous() {
+        Err("Anonymous user")?
     }
+
+    USER_MAP.with(|m| {
+        m.borrow_mut().insert(StableUserId(caller_principal_id()), user);
+    });
+    Ok(())
 }
@@ -1,3 +1,4 @@
 pub mod json_helpers;
 pub mod math_helpers;
 pub mod nat_helpers;
+pub mod pool_math;
@@ -0,0 +1,283 @@
+//! Constant product pool math shared by kong_backend and kong_sim. Works on the pool reserves and token decimals
+//! so callers can use their own pool and token types
+use candid::Nat;
+
+use super::nat_helpers::{nat_add, nat_divide, nat_is_zero, nat_multiply, nat_sqrt, nat_subtract, nat_to_decimal_precision, nat_zero};
+
+/// Reserves of a pool, reserve = balance + lp_fee, and the decimals of its tokens
+#[derive(Debug, Clone)]
+pub struct PoolReserves {
+    pub reserve_0: Nat,
+    pub decimals_0: u8,
+    pub reserve_1: Nat,
+    pub decimals_1: u8,
+}
+
+impl PoolReserves {
+    pub fn new(balance_0: &Nat, lp_fee_0: &Nat, decimals_0: u8, balance_1: &Nat, lp_fee_1: &Nat, decimals_1: u8) -> Self {
+        PoolReserves {
+            reserve_0: nat_add(balance_0, lp_fee_0),
+            decimals_0,
+            reserve_1: nat_add(balance_1, lp_fee_1),
+            decimals_1,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        nat_is_zero(&self.reserve_0) || nat_is_zero(&self.reserve_1)
+    }
+}
+
+/// LP fee in basis points after the user's fee discount
+/// user_fee_level is 0 = 100% fee (no discount), 100 = 0% fee (max discount)
+fn user_lp_fee_bps(lp_fee_bps: u8, user_fee_level: Option<u8>) -> Result<Nat, String> {
+    // user_lp_fee_pct = 100 - user.fee_level
+    let user_lp_fee_pct = nat_subtract(&Nat::from(100_u8), &Nat::from(user_fee_level.unwrap_or(0_u8))).unwrap_or(Nat::from(100_u8));
+    // user_lp_fee_bps = (lp_fee_bps * user_lp_fee_pct) / 100 - user's fee level in bps with discount
+    Ok(nat_divide(&nat_multiply(&user_lp_fee_pct, &Nat::from(lp_fee_bps)), &Nat::from(100_u8)).ok_or("Invalid LP fee")?)
+}
+
+/// receive amount and LP fee of paying pay_amount into a pool, both in the receive token precision
+fn swap_amount(
+    pay_amount: &Nat,
+    pay_reserve: &Nat,
+    pay_decimals: u8,
+    receive_reserve: &Nat,
+    receive_decimals: u8,
+    lp_fee_bps: u8,
+    user_fee_level: Option<u8>,
+) -> Result<(Nat, Nat), String> {
+    // convert pay_amount and pool reserves to the max_decimals precision
+    let max_decimals = std::cmp::max(pay_decimals, receive_decimals);
+    let pay_reserve_in_max_decimals = nat_to_decimal_precision(pay_reserve, pay_decimals, max_decimals);
+    let receive_reserve_in_max_decimals = nat_to_decimal_precision(receive_reserve, receive_decimals, max_decimals);
+    let pay_amount_in_max_decimals = nat_to_decimal_precision(pay_amount, pay_decimals, max_decimals);
+
+    // receive_amount = (pay_amount * receive_reserve) / (pay_reserve + pay_amount)
+    let numerator_in_max_decimals = nat_multiply(&pay_amount_in_max_decimals, &receive_reserve_in_max_decimals);
+    let denominator_in_max_decimals = nat_add(&pay_reserve_in_max_decimals, &pay_amount_in_max_decimals);
+    let receive_amount_in_max_decimals =
+        nat_divide(&numerator_in_max_decimals, &denominator_in_max_decimals).ok_or("Invalid receive amount")?;
+
+    // lp_fee = (receive_amount * user_lp_fee_bps) / 10_000
+    let user_lp_fee_bps = user_lp_fee_bps(lp_fee_bps, user_fee_level)?;
+    let numerator_in_max_decimals = nat_multiply(&receive_amount_in_max_decimals, &user_lp_fee_bps);
+    let lp_fee_in_max_decimals = nat_divide(&numerator_in_max_decimals, &Nat::from(10_000_u128)).ok_or("Invalid LP fee")?;
+
+    // convert receive_amount and lp_fee from max_decimals to the receive token precision
+    let receive_amount = nat_to_decimal_precision(&receive_amount_in_max_decimals, max_decimals, receive_decimals);
+    let lp_fee = nat_to_decimal_precision(&lp_fee_in_max_decimals, max_decimals, receive_decimals);
+
+    Ok((receive_amount, lp_fee))
+}
+
+/// Swap amount_0 of token_0 for token_1. lp_fee_bps is the pool's LP fee or the overwrite of a multi-leg swap
+///
+/// returns (amount_1, lp_fee) in token_1, amount_1 does not include any fees
+pub fn swap_amount_0(reserves: &PoolReserves, amount_0: &Nat, lp_fee_bps: u8, user_fee_level: Option<u8>) -> Result<(Nat, Nat), String> {
+    swap_amount(
+        amount_0,
+        &reserves.reserve_0,
+        reserves.decimals_0,
+        &reserves.reserve_1,
+        reserves.decimals_1,
+        lp_fee_bps,
+        user_fee_level,
+    )
+}
+
+/// Swap amount_1 of token_1 for token_0
+///
+/// returns (amount_0, lp_fee) in token_0, amount_0 does not include any fees
+pub fn swap_amount_1(reserves: &PoolReserves, amount_1: &Nat, lp_fee_bps: u8, user_fee_level: Option<u8>) -> Result<(Nat, Nat), String> {
+    swap_amount(
+        amount_1,
+        &reserves.reserve_1,
+        reserves.decimals_1,
+        &reserves.reserve_0,
+        reserves.decimals_0,
+        lp_fee_bps,
+        user_fee_level,
+    )
+}
+
+/// Splits the LP fee of a swap between the LPs and Kong
+///
+/// returns (lp_fee, kong_fee)
+pub fn split_lp_fee(lp_fee: &Nat, lp_fee_bps: u8, kong_fee_bps: u8) -> (Nat, Nat) {
+    // kong_fee = lp_fee * kong_fee_bps / lp_fee_bps
+    // lp_fee = lp_fee - kong_fee
+    let numerator = nat_multiply(lp_fee, &Nat::from(kong_fee_bps));
+    let kong_fee = nat_divide(&numerator, &Nat::from(lp_fee_bps)).unwrap_or(nat_zero());
+    let lp_fee = nat_subtract(lp_fee, &kong_fee).unwrap_or(nat_zero());
+    (lp_fee, kong_fee)
+}
+
+/// Calculates the ratio of amounts (amount_0 and amount_1) to be added to the pool to maintain constant K
+/// and the LP token amount for the user
+///
+/// returns (amount_0, amount_1, add_lp_token_amount)
+pub fn add_liquidity_amounts(
+    reserves: &PoolReserves,
+    lp_token_decimals: u8,
+    lp_total_supply: &Nat,
+    amount_0: &Nat,
+    amount_1: &Nat,
+) -> Result<(Nat, Nat, Nat), String> {
+    let (reserve_0, decimals_0) = (&reserves.reserve_0, reserves.decimals_0);
+    let (reserve_1, decimals_1) = (&reserves.reserve_1, reserves.decimals_1);
+
+    if reserves.is_empty() {
+        // new pool as there are no balances - take user amounts as initial ratio
+        // initialize LP tokens as sqrt(amount_0 * amount_1)
+        // convert the amounts to the same decimal precision as the LP token
+        let amount_0_in_lp_token_decimals = nat_to_decimal_precision(amount_0, decimals_0, lp_token_decimals);
+        let amount_1_in_lp_token_decimals = nat_to_decimal_precision(amount_1, decimals_1, lp_token_decimals);
+        let add_lp_token_amount = nat_sqrt(&nat_multiply(&amount_0_in_lp_token_decimals, &amount_1_in_lp_token_decimals));
+        return Ok((amount_0.clone(), amount_1.clone(), add_lp_token_amount));
+    }
+
+    // add_lp_token_amount = lp_total_supply * amount / reserve
+    let lp_token_amount = |amount: &Nat, reserve: &Nat, decimals: u8| -> Result<Nat, String> {
+        let amount_in_lp_token_decimals = nat_to_decimal_precision(amount, decimals, lp_token_decimals);
+        let reserve_in_lp_token_decimals = nat_to_decimal_precision(reserve, decimals, lp_token_decimals);
+        let numerator_in_lp_token_decimals = nat_multiply(lp_total_supply, &amount_in_lp_token_decimals);
+        Ok(nat_divide(&numerator_in_lp_token_decimals, &reserve_in_lp_token_decimals).ok_or("Invalid LP token amount")?)
+    };
+
+    // amount_0 * reserve_1 = amount_1 * reserve_0 for constant K
+    // if the ratio of the user amounts is the same as the pool ratio, then the amounts are correct
+    // rarely happens as there are rounding precision errors
+    if nat_multiply(amount_0, reserve_1) == nat_multiply(amount_1, reserve_0) {
+        let add_lp_token_amount = lp_token_amount(amount_0, reserve_0, decimals_0)?;
+        return Ok((amount_0.clone(), amount_1.clone(), add_lp_token_amount));
+    }
+
+    // determine if the ratio of the user amounts is same or greater than the pool ratio (reserve_1 / reserve_0)
+    // using amount_0 to calculate the amount_1 that should be added to the pool
+    // amount_1 = amount_0 * reserve_1 / reserve_0
+    // convert amount_0 and reserve_0 to token_1 decimal precision
+    let amount_0_in_token_1_decimals = nat_to_decimal_precision(amount_0, decimals_0, decimals_1);
+    let reserve_0_in_token_1_decimals = nat_to_decimal_precision(reserve_0, decimals_0, decimals_1);
+    // amount_0 * reserve_1 - do the multiplication first before divison to avoid loss of precision
+    let numerator_in_token_1_decimals = nat_multiply(&amount_0_in_token_1_decimals, reserve_1);
+    let amount_1_in_token_1_decimals =
+        nat_divide(&numerator_in_token_1_decimals, &reserve_0_in_token_1_decimals).ok_or("Invalid amount_1")?;
+    // if amount_1 is equal or greater than calculated by the pool ratio, then use amount_0 and amount_1
+    if *amount_1 >= amount_1_in_token_1_decimals {
+        let add_lp_token_amount = lp_token_amount(amount_0, reserve_0, decimals_0)?;
+        return Ok((amount_0.clone(), amount_1_in_token_1_decimals, add_lp_token_amount));
+    }
+
+    // using amount_1 to calculate the amount_0 that should be added to the pool
+    // amount_0 = amount_1 * reserve_0 / reserve_1
+    let amount_1_in_token_0_decimals = nat_to_decimal_precision(amount_1, decimals_1, decimals_0);
+    let reserve_1_in_token_0_decimals = nat_to_decimal_precision(reserve_1, decimals_1, decimals_0);
+    let numerator_in_token_0_decimals = nat_multiply(&amount_1_in_token_0_decimals, reserve_0);
+    let amount_0_in_token_0_decimals =
+        nat_divide(&numerator_in_token_0_decimals, &reserve_1_in_token_0_decimals).ok_or("Invalid amount_0")?;
+    if *amount_0 >= amount_0_in_token_0_decimals {
+        let add_lp_token_amount = lp_token_amount(amount_1, reserve_1, decimals_1)?;
+        return Ok((amount_0_in_token_0_decimals, amount_1.clone(), add_lp_token_amount));
+    }
+
+    // pool ratio must have changed from initial calculation and amount_0 and amount_1 are not enough now
+    Err("Incorrect ratio of amount_0 and amount_1".to_string())
+}
+
+/// Payout of removing remove_lp_token_amount from the pool, split in balance and LP fees
+///
+/// returns (amount_0, lp_fee_0, amount_1, lp_fee_1)
+pub fn remove_liquidity_amounts(
+    balance_0: &Nat,
+    lp_fee_0: &Nat,
+    balance_1: &Nat,
+    lp_fee_1: &Nat,
+    lp_total_supply: &Nat,
+    remove_lp_token_amount: &Nat,
+) -> Result<(Nat, Nat, Nat, Nat), String> {
+    // calculate user's payout in token_0
+    // we split the calculations for balance and fees
+    // amount_0 = balance_0 * remove_lp_token_amount / lp_total_supply
+    let numerator = nat_multiply(balance_0, remove_lp_token_amount);
+    let payout_amount_0 = nat_divide(&numerator, lp_total_supply).ok_or("Invalid LP token amount_0")?;
+    // payout_lp_fee_0 = lp_fee_0 * remove_lp_token_amount / lp_total_supply
+    let numerator = nat_multiply(lp_fee_0, remove_lp_token_amount);
+    let payout_lp_fee_0 = nat_divide(&numerator, lp_total_supply).ok_or("Invalid LP lp_fee_0")?;
+
+    // calculate user's payout in token_1
+    // amount_1 = balance_1 * remove_lp_token_amount / lp_total_supply
+    let numerator = nat_multiply(balance_1, remove_lp_token_amount);
+    let payout_amount_1 = nat_divide(&numerator, lp_total_supply).ok_or("Invalid LP token amount_1")?;
+    // payout_lp_fee_1 = lp_fee_1 * remove_lp_token_amount / lp_total_supply
+    let numerator = nat_multiply(lp_fee_1, remove_lp_token_amount);
+    let payout_lp_fee_1 = nat_divide(&numerator, lp_total_supply).ok_or("Invalid LP lp_fee_1")?;
+
+    Ok((payout_amount_0, payout_lp_fee_0, payout_amount_1, payout_lp_fee_1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 10_000 ICP (8 decimals) / 10_000 ckUSDT (6 decimals)
+    fn reserves() -> PoolReserves {
+        PoolReserves::new(
+            &Nat::from(1_000_000_000_000_u64),
+            &nat_zero(),
+            8,
+            &Nat::from(10_000_000_000_u64),
+            &nat_zero(),
+            6,
+        )
+    }
+
+    #[test]
+    fn swap_amount_0_keeps_constant_product() {
+        let (amount_1, lp_fee) = swap_amount_0(&reserves(), &Nat::from(100_000_000_u64), 30, None).unwrap();
+        // 1 ICP -> 10_000 * 1 / 10_001 ckUSDT
+        assert_eq!(amount_1, Nat::from(999_900_u64));
+        // 0.3% LP fee
+        assert_eq!(lp_fee, Nat::from(2_999_u64));
+    }
+
+    #[test]
+    fn swap_amount_1_applies_user_fee_discount() {
+        let (_, lp_fee) = swap_amount_1(&reserves(), &Nat::from(1_000_000_u64), 30, None).unwrap();
+        let (_, discounted_lp_fee) = swap_amount_1(&reserves(), &Nat::from(1_000_000_u64), 30, Some(50)).unwrap();
+        assert_eq!(discounted_lp_fee, nat_divide(&lp_fee, &Nat::from(2_u8)).unwrap());
+    }
+
+    #[test]
+    fn split_lp_fee_with_kong() {
+        let (lp_fee, kong_fee) = split_lp_fee(&Nat::from(3_000_u64), 30, 8);
+        // kong_fee = lp_fee * 8 / 30
+        assert_eq!(kong_fee, Nat::from(800_u64));
+        assert_eq!(lp_fee, Nat::from(2_200_u64));
+    }
+
+    #[test]
+    fn add_and_remove_liquidity_round_trip() {
+        let reserves = reserves();
+        let lp_total_supply = Nat::from(1_000_000_000_000_u64);
+        // amount_1 is more than the pool ratio, only 1 ckUSDT is used for 1 ICP
+        let (amount_0, amount_1, lp_amount) = add_liquidity_amounts(
+            &reserves,
+            8,
+            &lp_total_supply,
+            &Nat::from(100_000_000_u64),
+            &Nat::from(20_000_000_u64),
+        )
+        .unwrap();
+        assert_eq!(amount_1, Nat::from(1_000_000_u64));
+        assert_eq!(lp_amount, Nat::from(100_000_000_u64));
+
+        let balance_0 = nat_add(&reserves.reserve_0, &amount_0);
+        let balance_1 = nat_add(&reserves.reserve_1, &amount_1);
+        let lp_total_supply = nat_add(&lp_total_supply, &lp_amount);
+        let (payout_0, _, payout_1, _) =
+            remove_liquidity_amounts(&balance_0, &nat_zero(), &balance_1, &nat_zero(), &lp_total_supply, &lp_amount).unwrap();
+        assert_eq!(payout_0, amount_0);
+        assert_eq!(payout_1, amount_1);
+    }
+}
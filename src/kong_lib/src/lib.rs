@@ -18,6 +18,7 @@ pub mod stable_pool;
 pub mod stable_request;
 pub mod stable_token;
 pub mod stable_transfer;
+pub mod stable_trusted_origin;
 pub mod stable_tx;
 pub mod stable_user;
 pub mod swap;
@@ -0,0 +1,3 @@
+#[allow(clippy::module_inception)]
+pub mod stable_trusted_origin;
+pub mod trusted_origin_store;
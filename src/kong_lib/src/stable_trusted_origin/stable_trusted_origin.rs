@@ -0,0 +1,50 @@
+use candid::CandidType;
+use ic_stable_structures::{storable::Bound, Storable};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// list of ICRC-28 trusted origins returned by icrc28_trusted_origins()
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct StableTrustedOrigins {
+    pub origins: Vec<String>,
+}
+
+impl Storable for StableTrustedOrigins {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        serde_cbor::to_vec(self).expect("Failed to encode trusted origins").into()
+    }
+
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
+        serde_cbor::from_slice(&bytes).expect("Failed to decode trusted origins")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+#[derive(CandidType, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrustedOriginAction {
+    Add,
+    Remove,
+}
+
+/// audit log entry for every change to the trusted origins
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct StableTrustedOriginLog {
+    pub log_id: u64,
+    pub action: TrustedOriginAction,
+    pub origin: String,
+    pub principal_id: String, // caller who made the change
+    pub ts: u64,
+}
+
+impl Storable for StableTrustedOriginLog {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        serde_cbor::to_vec(self).expect("Failed to encode trusted origin log").into()
+    }
+
+    fn from_bytes(bytes: Cow<'_, [u8]>) -> Self {
+        serde_cbor::from_slice(&bytes).expect("Failed to decode trusted origin log")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
@@ -0,0 +1,142 @@
+use ic_stable_structures::{Memory, StableBTreeMap, StableCell};
+
+use super::stable_trusted_origin::{StableTrustedOriginLog, StableTrustedOrigins, TrustedOriginAction};
+
+pub const MAX_TRUSTED_ORIGIN_LOGS: usize = 100;
+
+/// Stable memories used by the trusted origin store, allocated by the canister's memory manager
+pub struct TrustedOriginMemories<M: Memory> {
+    pub origins: M,
+    pub log: M,
+}
+
+/// ICRC-28 trusted origins and the audit log of changes to them. Callers pass in the caller's principal id
+/// and the current time so the store does not depend on the IC runtime
+pub struct TrustedOriginStore<M: Memory> {
+    origins: StableCell<StableTrustedOrigins, M>,
+    log: StableBTreeMap<u64, StableTrustedOriginLog, M>,
+}
+
+impl<M: Memory> TrustedOriginStore<M> {
+    /// default_origins are used on first install and on upgrade of a canister without trusted origins in stable memory
+    pub fn init(memories: TrustedOriginMemories<M>, default_origins: Vec<String>) -> Self {
+        Self {
+            origins: StableCell::init(memories.origins, StableTrustedOrigins { origins: default_origins })
+                .expect("Failed to initialize trusted origins"),
+            log: StableBTreeMap::init(memories.log),
+        }
+    }
+
+    pub fn get(&self) -> Vec<String> {
+        self.origins.get().origins.clone()
+    }
+
+    /// add origin to the trusted origins. returns the updated list
+    pub fn add(&mut self, origin: &str, principal_id: &str, ts: u64) -> Result<Vec<String>, String> {
+        let origin = validate_origin(origin)?;
+        let mut origins = self.get();
+        if origins.contains(&origin) {
+            return Err(format!("Origin {} already trusted", origin));
+        }
+        origins.push(origin.clone());
+        self.set(origins.clone())?;
+        self.insert_log(TrustedOriginAction::Add, &origin, principal_id, ts);
+        Ok(origins)
+    }
+
+    /// remove origin from the trusted origins. returns the updated list
+    pub fn remove(&mut self, origin: &str, principal_id: &str, ts: u64) -> Result<Vec<String>, String> {
+        let origin = origin.trim().trim_end_matches('/').to_string();
+        let mut origins = self.get();
+        let len = origins.len();
+        origins.retain(|o| *o != origin);
+        if origins.len() == len {
+            return Err(format!("Origin {} not trusted", origin));
+        }
+        self.set(origins.clone())?;
+        self.insert_log(TrustedOriginAction::Remove, &origin, principal_id, ts);
+        Ok(origins)
+    }
+
+    /// returns the latest num audit log entries, newest first. num is capped at MAX_TRUSTED_ORIGIN_LOGS
+    pub fn get_logs(&self, num: Option<usize>) -> Vec<StableTrustedOriginLog> {
+        let num = num.map_or(MAX_TRUSTED_ORIGIN_LOGS, |n| n.min(MAX_TRUSTED_ORIGIN_LOGS));
+        self.log.iter().rev().take(num).map(|(_, v)| v).collect()
+    }
+
+    fn set(&mut self, origins: Vec<String>) -> Result<(), String> {
+        self.origins
+            .set(StableTrustedOrigins { origins })
+            .map_err(|_| "Failed to update trusted origins".to_string())?;
+        Ok(())
+    }
+
+    fn insert_log(&mut self, action: TrustedOriginAction, origin: &str, principal_id: &str, ts: u64) {
+        let log_id = self.log.last_key_value().map_or(1, |(k, _)| k + 1);
+        let log = StableTrustedOriginLog {
+            log_id,
+            action,
+            origin: origin.to_string(),
+            principal_id: principal_id.to_string(),
+            ts,
+        };
+        self.log.insert(log_id, log);
+    }
+}
+
+/// origin must be scheme://host[:port] without path, query or trailing slash
+pub fn validate_origin(origin: &str) -> Result<String, String> {
+    let origin = origin.trim().trim_end_matches('/');
+    let host = origin
+        .strip_prefix("https://")
+        .or_else(|| origin.strip_prefix("http://"))
+        .ok_or(format!("Invalid origin {}. Must start with https:// or http://", origin))?;
+    if host.is_empty() || host.contains(['/', '?', '#', ' ']) {
+        return Err(format!("Invalid origin {}", origin));
+    }
+    Ok(origin.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_stable_structures::DefaultMemoryImpl;
+
+    fn store() -> TrustedOriginStore<DefaultMemoryImpl> {
+        TrustedOriginStore::init(
+            TrustedOriginMemories {
+                origins: DefaultMemoryImpl::default(),
+                log: DefaultMemoryImpl::default(),
+            },
+            vec!["https://kongswap.io".to_string()],
+        )
+    }
+
+    #[test]
+    fn test_validate_origin() {
+        assert_eq!(validate_origin("https://kongswap.io/").unwrap(), "https://kongswap.io");
+        assert_eq!(validate_origin(" http://localhost:5173 ").unwrap(), "http://localhost:5173");
+        assert!(validate_origin("kongswap.io").is_err());
+        assert!(validate_origin("https://").is_err());
+        assert!(validate_origin("https://kongswap.io/swap").is_err());
+        assert!(validate_origin("https://kongswap.io?ref=1").is_err());
+    }
+
+    #[test]
+    fn test_add_and_remove_are_logged() {
+        let mut store = store();
+        assert_eq!(store.add("https://dev.kongswap.io/", "admin", 1).unwrap().len(), 2);
+        assert!(store.add("https://dev.kongswap.io", "admin", 2).is_err());
+        assert_eq!(
+            store.remove("https://kongswap.io", "admin", 3).unwrap(),
+            vec!["https://dev.kongswap.io"]
+        );
+        assert!(store.remove("https://kongswap.io", "admin", 4).is_err());
+
+        let logs = store.get_logs(None);
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].action, TrustedOriginAction::Remove);
+        assert_eq!(logs[0].ts, 3);
+        assert_eq!(logs[1].origin, "https://dev.kongswap.io");
+    }
+}
@@ -1,6 +1,8 @@
 use candid::{CandidType, Nat};
 use serde::{Deserialize, Serialize};
 
+use crate::helpers::nat_helpers::{nat_add, nat_subtract, nat_zero};
+
 #[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
 pub struct SwapCalc {
     pub pool_id: u32,
@@ -12,3 +14,11 @@ pub struct SwapCalc {
     pub lp_fee: Nat,         // will be in receive_token
     pub gas_fee: Nat,        // will be in receive_token
 }
+
+impl SwapCalc {
+    /// this is the net amount the user will receive after the fees and gas are taken off
+    pub fn receive_amount_with_fees_and_gas(&self) -> Nat {
+        let total_fees = nat_add(&self.lp_fee, &self.gas_fee);
+        nat_subtract(&self.receive_amount, &total_fees).unwrap_or(nat_zero())
+    }
+}
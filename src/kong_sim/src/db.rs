@@ -0,0 +1,91 @@
+//! Loads the pools and transactions from the kong_admin database, using the raw_json column of each table
+use kong_lib::stable_lp_token::stable_lp_token::StableLPToken;
+use kong_lib::stable_pool::stable_pool::StablePool;
+use kong_lib::stable_token::stable_token::StableToken;
+use kong_lib::stable_tx::stable_tx::StableTx;
+use serde_json::Value;
+use tokio_postgres::{Client, NoTls};
+
+use super::snapshot::PoolSnapshot;
+
+pub async fn connect(db_url: &str) -> Result<Client, Box<dyn std::error::Error>> {
+    let (client, connection) = tokio_postgres::connect(db_url, NoTls).await?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            eprintln!("Database connection error: {}", e);
+        }
+    });
+    Ok(client)
+}
+
+async fn query_raw_json(db_client: &Client, table: &str, order_by: &str) -> Result<Vec<Value>, Box<dyn std::error::Error>> {
+    let rows = db_client
+        .query(&format!("SELECT raw_json FROM {} ORDER BY {}", table, order_by), &[])
+        .await?;
+    Ok(rows.iter().map(|row| row.get::<_, Value>(0)).collect())
+}
+
+/// raw_json of pools and lp_tokens is wrapped in the type name, e.g. {"StablePool": {...}}
+fn unwrap_raw_json(raw_json: Value, name: &str) -> Result<Value, String> {
+    match raw_json {
+        Value::Object(mut object) => object.remove(name).ok_or(format!("raw_json is not a {}", name)),
+        _ => Err(format!("raw_json is not a {}", name)),
+    }
+}
+
+/// Current state of the pools in the kong_admin database
+pub async fn load_snapshot(db_client: &Client) -> Result<PoolSnapshot, Box<dyn std::error::Error>> {
+    let mut tokens = Vec::new();
+    for raw_json in query_raw_json(db_client, "tokens", "token_id").await? {
+        tokens.push(serde_json::from_value::<StableToken>(raw_json)?);
+    }
+    let mut pools = Vec::new();
+    for raw_json in query_raw_json(db_client, "pools", "pool_id").await? {
+        pools.push(serde_json::from_value::<StablePool>(unwrap_raw_json(raw_json, "StablePool")?)?);
+    }
+    let mut lp_tokens = Vec::new();
+    for raw_json in query_raw_json(db_client, "lp_tokens", "lp_token_id").await? {
+        lp_tokens.push(serde_json::from_value::<StableLPToken>(unwrap_raw_json(
+            raw_json,
+            "StableLPToken",
+        )?)?);
+    }
+    Ok(PoolSnapshot::new(tokens, pools, lp_tokens, None))
+}
+
+/// Transactions in the kong_admin database ordered by tx_id. raw_json does not store the pool_id of the swap legs,
+/// it is looked up from the snapshot by the legs' token ids
+pub async fn load_txs(db_client: &Client, snapshot: &PoolSnapshot) -> Result<Vec<StableTx>, Box<dyn std::error::Error>> {
+    let mut txs = Vec::new();
+    for raw_json in query_raw_json(db_client, "txs", "tx_id").await? {
+        let Value::Object(object) = raw_json else {
+            Err("raw_json is not a tx")?
+        };
+        let Some((name, mut tx)) = object.into_iter().next() else {
+            Err("raw_json is not a tx")?
+        };
+        let variant = match name.as_str() {
+            "AddPoolTx" => "AddPool",
+            "AddLiquidityTx" => "AddLiquidity",
+            "RemoveLiquidityTx" => "RemoveLiquidity",
+            "SwapTx" => {
+                if let Some(Value::Array(legs)) = tx.get_mut("txs") {
+                    for leg in legs.iter_mut() {
+                        let pay_token_id = leg["pay_token_id"].as_u64().unwrap_or_default() as u32;
+                        let receive_token_id = leg["receive_token_id"].as_u64().unwrap_or_default() as u32;
+                        let pool_id = snapshot
+                            .pool_by_token_ids(pay_token_id, receive_token_id)
+                            .map_or(0, |pool| pool.pool_id);
+                        leg["pool_id"] = Value::from(pool_id);
+                    }
+                }
+                "Swap"
+            }
+            _ => continue, // SendTx doesn't change the pools
+        };
+        let mut object = serde_json::Map::new();
+        object.insert(variant.to_string(), tx);
+        txs.push(serde_json::from_value::<StableTx>(Value::Object(object))?);
+    }
+    Ok(txs)
+}
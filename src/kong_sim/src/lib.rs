@@ -0,0 +1,8 @@
+//! Offline AMM simulator for backtesting liquidity strategies against Kong's pool math.
+//! Pools are loaded from a kong_admin snapshot, the kong_backend backup files or the kong_admin database,
+//! then historical transactions are replayed with simulated positions to measure fee income and impermanent loss.
+#[cfg(feature = "postgres")]
+pub mod db;
+pub mod report;
+pub mod simulator;
+pub mod snapshot;
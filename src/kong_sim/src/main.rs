@@ -0,0 +1,116 @@
+use candid::Nat;
+use kong_lib::stable_token::token::Token;
+use kong_lib::stable_tx::stable_tx::StableTx;
+use kong_sim::simulator::Simulator;
+use kong_sim::snapshot::{load_backup_txs, load_backups, load_kong_admin_snapshot, to_f64, PoolSnapshot};
+use std::env;
+use std::path::Path;
+
+const BACKUPS_DIR: &str = "./backups";
+
+const USAGE: &str = "Usage: kong_sim [--snapshot <file> | --backups <dir> | --db <url>] [--txs <dir>] --pool <SYMBOL_0>_<SYMBOL_1>
+                [--amount_0 <amount>] [--amount_1 <amount>] [--from_ts <ts>] [--to_ts <ts>]";
+
+fn arg_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|i| args.get(i + 1))
+        .map(|arg| arg.as_str())
+}
+
+fn arg_u64(args: &[String], name: &str) -> Result<Option<u64>, String> {
+    arg_value(args, name)
+        .map(|value| value.parse::<u64>().map_err(|_| format!("Invalid {}: {}", name, value)))
+        .transpose()
+}
+
+/// decimal amount in token units, e.g. 1.5, to the token's smallest units
+fn parse_amount(amount: &str, decimals: u8) -> Result<Nat, String> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if fraction.len() > decimals as usize {
+        Err(format!("Amount {} has more than {} decimals", amount, decimals))?
+    }
+    let digits = format!("{}{}{}", whole, fraction, "0".repeat(decimals as usize - fraction.len()));
+    digits.parse::<Nat>().map_err(|_| format!("Invalid amount: {}", amount))
+}
+
+#[cfg(feature = "postgres")]
+fn load_from_db(db_url: &str) -> Result<(PoolSnapshot, Vec<StableTx>), Box<dyn std::error::Error>> {
+    tokio::runtime::Runtime::new()?.block_on(async {
+        let db_client = kong_sim::db::connect(db_url).await?;
+        let snapshot = kong_sim::db::load_snapshot(&db_client).await?;
+        let txs = kong_sim::db::load_txs(&db_client, &snapshot).await?;
+        Ok((snapshot, txs))
+    })
+}
+
+#[cfg(not(feature = "postgres"))]
+fn load_from_db(_db_url: &str) -> Result<(PoolSnapshot, Vec<StableTx>), Box<dyn std::error::Error>> {
+    Err("kong_sim was built without the postgres feature")?
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = env::args().collect::<Vec<String>>();
+    if args.contains(&"--help".to_string()) {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    let (mut snapshot, txs) = if let Some(db_url) = arg_value(&args, "--db") {
+        load_from_db(db_url)?
+    } else {
+        let txs_dir = arg_value(&args, "--txs").or(arg_value(&args, "--backups")).unwrap_or(BACKUPS_DIR);
+        let snapshot = match arg_value(&args, "--snapshot") {
+            Some(file) => load_kong_admin_snapshot(Path::new(file))?,
+            None => load_backups(Path::new(arg_value(&args, "--backups").unwrap_or(BACKUPS_DIR)))?,
+        };
+        (snapshot, load_backup_txs(Path::new(txs_dir))?)
+    };
+    let from_ts = arg_u64(&args, "--from_ts")?;
+    let to_ts = arg_u64(&args, "--to_ts")?;
+
+    // the snapshot is the state at its time (or now). rewind to start the simulation earlier
+    if let Some(from_ts) = from_ts {
+        if snapshot.ts.is_none_or(|ts| from_ts < ts) {
+            snapshot.rewind(&txs, from_ts)?;
+        }
+    }
+    // only replay txs after the snapshot
+    let from_ts = from_ts.or(snapshot.ts);
+
+    let symbol = arg_value(&args, "--pool").ok_or(USAGE)?;
+    let pool = snapshot.pool_by_symbol(symbol).ok_or(format!("Pool {} not found", symbol))?;
+    let pool_id = pool.pool_id;
+    let decimals_0 = snapshot.token(pool.token_id_0)?.decimals();
+    let decimals_1 = snapshot.token(pool.token_id_1)?.decimals();
+    let amount_0 = parse_amount(arg_value(&args, "--amount_0").unwrap_or("0"), decimals_0)?;
+    let amount_1 = parse_amount(arg_value(&args, "--amount_1").unwrap_or("0"), decimals_1)?;
+
+    let mut simulator = Simulator::new(snapshot);
+    let position = simulator.add_liquidity(pool_id, &amount_0, &amount_1)?;
+    let summary = simulator.replay(&txs, from_ts, to_ts);
+
+    println!(
+        "replayed {} swaps, {} liquidity changes. skipped {}, receive amount mismatches {}",
+        summary.swaps, summary.liquidity_changes, summary.skipped, summary.receive_mismatches
+    );
+    for error in summary.errors.iter() {
+        eprintln!("{}", error);
+    }
+    if let Some(stats) = simulator.pool_stats(pool_id) {
+        println!(
+            "{}: {} swaps, volume {} / {}, LP fees {} / {}, Kong fees {} / {}",
+            symbol,
+            stats.swaps,
+            to_f64(&stats.volume_0, decimals_0).unwrap_or_default(),
+            to_f64(&stats.volume_1, decimals_1).unwrap_or_default(),
+            to_f64(&stats.lp_fee_0, decimals_0).unwrap_or_default(),
+            to_f64(&stats.lp_fee_1, decimals_1).unwrap_or_default(),
+            to_f64(&stats.kong_fee_0, decimals_0).unwrap_or_default(),
+            to_f64(&stats.kong_fee_1, decimals_1).unwrap_or_default(),
+        );
+    }
+    println!("{}", simulator.position_report(position)?);
+
+    Ok(())
+}
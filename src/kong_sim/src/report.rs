@@ -0,0 +1,96 @@
+use std::fmt;
+
+/// LP position PnL. Values are in token_1 units
+#[derive(Debug, Clone)]
+pub struct PositionReport {
+    pub pool_symbol: String,
+    pub deposited_0: f64,
+    pub deposited_1: f64,
+    pub amount_0: f64, // current share of the pool including LP fees
+    pub amount_1: f64,
+    pub fees_0: f64, // LP fees earned
+    pub fees_1: f64,
+    pub entry_price: f64,
+    pub price: f64,
+    pub entry_value: f64,          // deposits at the entry price
+    pub hodl_value: f64,           // deposits at the current price
+    pub lp_value: f64,             // position at the current price
+    pub fee_income: f64,           // LP fees earned at the current price
+    pub impermanent_loss_pct: f64, // position without fee income vs holding, negative is a loss
+    pub pnl: f64,                  // lp_value - entry_value
+}
+
+impl PositionReport {
+    pub fn new(
+        pool_symbol: String,
+        (deposited_0, deposited_1): (f64, f64),
+        (amount_0, amount_1): (f64, f64),
+        (fees_0, fees_1): (f64, f64),
+        entry_price: f64,
+        price: f64,
+    ) -> Self {
+        let entry_value = deposited_0 * entry_price + deposited_1;
+        let hodl_value = deposited_0 * price + deposited_1;
+        let lp_value = amount_0 * price + amount_1;
+        let fee_income = fees_0 * price + fees_1;
+        let impermanent_loss_pct = if hodl_value > 0_f64 {
+            ((lp_value - fee_income) / hodl_value - 1_f64) * 100_f64
+        } else {
+            0_f64
+        };
+        PositionReport {
+            pool_symbol,
+            deposited_0,
+            deposited_1,
+            amount_0,
+            amount_1,
+            fees_0,
+            fees_1,
+            entry_price,
+            price,
+            entry_value,
+            hodl_value,
+            lp_value,
+            fee_income,
+            impermanent_loss_pct,
+            pnl: lp_value - entry_value,
+        }
+    }
+}
+
+impl fmt::Display for PositionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "pool: {}", self.pool_symbol)?;
+        writeln!(f, "deposited: {} / {}", self.deposited_0, self.deposited_1)?;
+        writeln!(f, "position: {} / {}", self.amount_0, self.amount_1)?;
+        writeln!(f, "fees earned: {} / {}", self.fees_0, self.fees_1)?;
+        writeln!(f, "price: {} -> {}", self.entry_price, self.price)?;
+        writeln!(f, "entry value: {}", self.entry_value)?;
+        writeln!(f, "hodl value: {}", self.hodl_value)?;
+        writeln!(f, "LP value: {}", self.lp_value)?;
+        writeln!(f, "fee income: {}", self.fee_income)?;
+        writeln!(f, "impermanent loss: {:.4}%", self.impermanent_loss_pct)?;
+        write!(f, "PnL: {}", self.pnl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn impermanent_loss_of_price_doubling() {
+        // 1 token_0 + 1 token_1 at price 1. price doubles, constant product gives sqrt(0.5) token_0 and sqrt(2) token_1
+        let report = PositionReport::new(
+            "A_B".to_string(),
+            (1_f64, 1_f64),
+            (0.5_f64.sqrt(), 2_f64.sqrt()),
+            (0_f64, 0_f64),
+            1_f64,
+            2_f64,
+        );
+        assert_eq!(report.hodl_value, 3_f64);
+        // IL of a 2x price move is 2 * sqrt(2) / 3 - 1 = -5.72%
+        assert!((report.impermanent_loss_pct + 5.719).abs() < 0.001);
+    }
+}
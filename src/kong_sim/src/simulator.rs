@@ -0,0 +1,414 @@
+use candid::Nat;
+use kong_lib::helpers::nat_helpers::{nat_add, nat_divide, nat_is_zero, nat_multiply, nat_subtract, nat_zero};
+use kong_lib::helpers::pool_math::{
+    add_liquidity_amounts, remove_liquidity_amounts, split_lp_fee, swap_amount_0, swap_amount_1, PoolReserves,
+};
+use kong_lib::stable_pool::stable_pool::StablePool;
+use kong_lib::stable_token::stable_token::StableToken;
+use kong_lib::stable_token::token::Token;
+use kong_lib::stable_tx::stable_tx::StableTx;
+use kong_lib::stable_tx::status_tx::StatusTx;
+use kong_lib::stable_tx::swap_tx::SwapTx;
+use kong_lib::swap::swap_calc::SwapCalc;
+use std::collections::BTreeMap;
+
+use super::report::PositionReport;
+use super::snapshot::{to_f64, PoolSnapshot};
+
+fn pool_reserves(pool: &StablePool, token_0: &StableToken, token_1: &StableToken) -> PoolReserves {
+    PoolReserves::new(
+        &pool.balance_0,
+        &pool.lp_fee_0,
+        token_0.decimals(),
+        &pool.balance_1,
+        &pool.lp_fee_1,
+        token_1.decimals(),
+    )
+}
+
+/// A simulated LP position
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub pool_id: u32,
+    pub lp_token_amount: Nat,
+    pub deposited_0: Nat,
+    pub deposited_1: Nat,
+    pub entry_price: f64, // price of token_0 in token_1 when the position was opened
+    pub fees_0: Nat,      // LP fees earned, the position's share of the LP fee of each swap
+    pub fees_1: Nat,
+}
+
+/// Totals of the replayed swaps of a pool
+#[derive(Debug, Clone, Default)]
+pub struct PoolStats {
+    pub swaps: u64,
+    pub volume_0: Nat, // amounts paid in token_0
+    pub volume_1: Nat, // amounts paid in token_1
+    pub lp_fee_0: Nat,
+    pub lp_fee_1: Nat,
+    pub kong_fee_0: Nat,
+    pub kong_fee_1: Nat,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReplaySummary {
+    pub swaps: u64,
+    pub liquidity_changes: u64,
+    pub skipped: u64,            // failed txs, txs outside the time range and pools not in the snapshot
+    pub receive_mismatches: u64, // swaps where the replayed receive amount differs from the historical one
+    pub errors: Vec<String>,
+}
+
+/// Runs swaps and liquidity changes against a copy of the pools using kong_backend's pool math
+pub struct Simulator {
+    state: PoolSnapshot,
+    positions: Vec<Position>,
+    pool_stats: BTreeMap<u32, PoolStats>,
+}
+
+impl Simulator {
+    pub fn new(snapshot: PoolSnapshot) -> Self {
+        Simulator {
+            state: snapshot,
+            positions: Vec::new(),
+            pool_stats: BTreeMap::new(),
+        }
+    }
+
+    pub fn state(&self) -> &PoolSnapshot {
+        &self.state
+    }
+
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    pub fn pool_stats(&self, pool_id: u32) -> Option<&PoolStats> {
+        self.pool_stats.get(&pool_id)
+    }
+
+    fn pool_and_tokens(&self, pool_id: u32) -> Result<(&StablePool, &StableToken, &StableToken), String> {
+        let pool = self.state.pools.get(&pool_id).ok_or(format!("pool_id={} not found", pool_id))?;
+        Ok((pool, self.state.token(pool.token_id_0)?, self.state.token(pool.token_id_1)?))
+    }
+
+    fn lp_total_supply(&self, pool: &StablePool) -> Nat {
+        self.state.lp_total_supply.get(&pool.lp_token_id).cloned().unwrap_or(nat_zero())
+    }
+
+    /// calculates a swap leg on the current pool state without updating it
+    fn swap_leg(
+        &self,
+        pool_id: u32,
+        pay_token_id: u32,
+        pay_amount: &Nat,
+        user_fee_level: Option<u8>,
+        use_lp_fee: Option<u8>,
+        use_gas_fee: Option<&Nat>,
+    ) -> Result<SwapCalc, String> {
+        let (pool, token_0, token_1) = self.pool_and_tokens(pool_id)?;
+        let reserves = pool_reserves(pool, token_0, token_1);
+        let receive_token = if pay_token_id == pool.token_id_0 {
+            token_1
+        } else if pay_token_id == pool.token_id_1 {
+            token_0
+        } else {
+            Err(format!("token_id={} not in pool_id={}", pay_token_id, pool_id))?
+        };
+
+        if reserves.is_empty() {
+            return Ok(SwapCalc {
+                pool_id,
+                pay_token_id,
+                pay_amount: nat_zero(),
+                receive_token_id: receive_token.token_id(),
+                receive_amount: nat_zero(),
+                lp_fee: nat_zero(),
+                gas_fee: nat_zero(),
+            });
+        }
+
+        let lp_fee_bps = use_lp_fee.unwrap_or(pool.lp_fee_bps);
+        let (receive_amount, lp_fee, receive_reserve) = if pay_token_id == pool.token_id_0 {
+            let (amount_1, lp_fee) = swap_amount_0(&reserves, pay_amount, lp_fee_bps, user_fee_level)?;
+            (amount_1, lp_fee, &reserves.reserve_1)
+        } else {
+            let (amount_0, lp_fee) = swap_amount_1(&reserves, pay_amount, lp_fee_bps, user_fee_level)?;
+            (amount_0, lp_fee, &reserves.reserve_0)
+        };
+        if receive_amount > *receive_reserve {
+            Err(format!("Insufficient {} in pool", receive_token.symbol()))?
+        }
+
+        Ok(SwapCalc {
+            pool_id,
+            pay_token_id,
+            pay_amount: pay_amount.clone(),
+            receive_token_id: receive_token.token_id(),
+            receive_amount,
+            lp_fee,
+            gas_fee: use_gas_fee.map_or_else(|| receive_token.fee(), |fee| fee.clone()),
+        })
+    }
+
+    /// updates the pools with the swap legs, the fee stats and the fees earned by the positions
+    fn apply_swaps(&mut self, swaps: &[SwapCalc]) -> Result<(), String> {
+        for swap in swaps {
+            let pool = self
+                .state
+                .pools
+                .get_mut(&swap.pool_id)
+                .ok_or(format!("pool_id={} not found", swap.pool_id))?;
+            // same as kong_backend swap update_liquidity_pool()
+            let (lp_fee, kong_fee) = split_lp_fee(&swap.lp_fee, pool.lp_fee_bps, pool.kong_fee_bps);
+            let receives_token_1 = swap.receive_token_id == pool.token_id_1;
+            if receives_token_1 {
+                // user pays token_0 and receives token_1
+                pool.balance_0 = nat_add(&pool.balance_0, &swap.pay_amount);
+                pool.balance_1 = nat_subtract(&pool.balance_1, &swap.receive_amount).ok_or("Insufficient liquid balance_1 in pool")?;
+                pool.lp_fee_1 = nat_add(&pool.lp_fee_1, &lp_fee);
+                pool.kong_fee_1 = nat_add(&pool.kong_fee_1, &kong_fee);
+            } else {
+                // user pays token_1 and receives token_0
+                pool.balance_1 = nat_add(&pool.balance_1, &swap.pay_amount);
+                pool.balance_0 = nat_subtract(&pool.balance_0, &swap.receive_amount).ok_or("Insufficient liquid balance_0 in pool")?;
+                pool.lp_fee_0 = nat_add(&pool.lp_fee_0, &lp_fee);
+                pool.kong_fee_0 = nat_add(&pool.kong_fee_0, &kong_fee);
+            }
+            let lp_total_supply = self.state.lp_total_supply.get(&pool.lp_token_id).cloned().unwrap_or(nat_zero());
+
+            let stats = self.pool_stats.entry(swap.pool_id).or_default();
+            stats.swaps += 1;
+            if receives_token_1 {
+                stats.volume_0 = nat_add(&stats.volume_0, &swap.pay_amount);
+                stats.lp_fee_1 = nat_add(&stats.lp_fee_1, &lp_fee);
+                stats.kong_fee_1 = nat_add(&stats.kong_fee_1, &kong_fee);
+            } else {
+                stats.volume_1 = nat_add(&stats.volume_1, &swap.pay_amount);
+                stats.lp_fee_0 = nat_add(&stats.lp_fee_0, &lp_fee);
+                stats.kong_fee_0 = nat_add(&stats.kong_fee_0, &kong_fee);
+            }
+
+            for position in self.positions.iter_mut().filter(|position| position.pool_id == swap.pool_id) {
+                // position's share of the LP fee = lp_fee * lp_token_amount / lp_total_supply
+                let fee = nat_divide(&nat_multiply(&lp_fee, &position.lp_token_amount), &lp_total_supply).unwrap_or(nat_zero());
+                if receives_token_1 {
+                    position.fees_1 = nat_add(&position.fees_1, &fee);
+                } else {
+                    position.fees_0 = nat_add(&position.fees_0, &fee);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Swaps pay_amount through the pool of the two tokens. Returns the receive amount after LP fee and gas
+    pub fn swap(&mut self, pay_token_id: u32, pay_amount: &Nat, receive_token_id: u32, user_fee_level: Option<u8>) -> Result<Nat, String> {
+        let pool_id = self
+            .state
+            .pool_by_token_ids(pay_token_id, receive_token_id)
+            .ok_or(format!("No pool for token_id={} and token_id={}", pay_token_id, receive_token_id))?
+            .pool_id;
+        let swap = self.swap_leg(pool_id, pay_token_id, pay_amount, user_fee_level, None, None)?;
+        let receive_amount = swap.receive_amount_with_fees_and_gas();
+        if nat_is_zero(&receive_amount) {
+            Err("Receive amount is zero".to_string())?
+        }
+        self.apply_swaps(&[swap])?;
+        Ok(receive_amount)
+    }
+
+    /// replays a historical swap along the same route. Returns the receive amount after LP fees and gas
+    fn replay_swap(&mut self, tx: &SwapTx) -> Result<Nat, String> {
+        let legs = tx.txs.len();
+        let mut pay_amount = tx.pay_amount.clone();
+        let mut swaps = Vec::with_capacity(legs);
+        for (i, leg) in tx.txs.iter().enumerate() {
+            let pool = match self.state.pools.get(&leg.pool_id) {
+                Some(pool)
+                    if [pool.token_id_0, pool.token_id_1] == [leg.pay_token_id, leg.receive_token_id]
+                        || [pool.token_id_1, pool.token_id_0] == [leg.pay_token_id, leg.receive_token_id] =>
+                {
+                    pool
+                }
+                _ => self.state.pool_by_token_ids(leg.pay_token_id, leg.receive_token_id).ok_or(format!(
+                    "No pool for token_id={} and token_id={}",
+                    leg.pay_token_id, leg.receive_token_id
+                ))?,
+            };
+            // multi-leg swaps split the LP fee between the legs and only the last leg pays gas, same as kong_backend swap_amounts()
+            let (use_lp_fee, use_gas_fee) = if legs == 1 {
+                (None, None)
+            } else if i < legs - 1 {
+                (Some((pool.lp_fee_bps + 1) / legs as u8), Some(nat_zero()))
+            } else {
+                (Some((pool.lp_fee_bps + 1) / legs as u8), None)
+            };
+            let swap = self.swap_leg(pool.pool_id, leg.pay_token_id, &pay_amount, None, use_lp_fee, use_gas_fee.as_ref())?;
+            pay_amount = swap.receive_amount_with_fees_and_gas();
+            swaps.push(swap);
+        }
+        // all legs are calculated on the state before the swap, as in kong_backend
+        self.apply_swaps(&swaps)?;
+        Ok(pay_amount)
+    }
+
+    /// Adds liquidity to a pool, amount_0 and amount_1 are adjusted to the pool ratio. Returns (amount_0, amount_1, add_lp_token_amount)
+    fn add_liquidity_to_pool(&mut self, pool_id: u32, amount_0: &Nat, amount_1: &Nat) -> Result<(Nat, Nat, Nat), String> {
+        let (pool, token_0, token_1) = self.pool_and_tokens(pool_id)?;
+        let lp_token = self.state.token(pool.lp_token_id)?;
+        let lp_total_supply = self.lp_total_supply(pool);
+        let (amount_0, amount_1, add_lp_token_amount) = add_liquidity_amounts(
+            &pool_reserves(pool, token_0, token_1),
+            lp_token.decimals(),
+            &lp_total_supply,
+            amount_0,
+            amount_1,
+        )?;
+
+        let pool = self.state.pools.get_mut(&pool_id).ok_or(format!("pool_id={} not found", pool_id))?;
+        pool.balance_0 = nat_add(&pool.balance_0, &amount_0);
+        pool.balance_1 = nat_add(&pool.balance_1, &amount_1);
+        let lp_token_id = pool.lp_token_id;
+        let lp_total_supply = self.state.lp_total_supply.entry(lp_token_id).or_default();
+        *lp_total_supply = nat_add(lp_total_supply, &add_lp_token_amount);
+        Ok((amount_0, amount_1, add_lp_token_amount))
+    }
+
+    /// Removes liquidity from a pool. Returns (amount_0, lp_fee_0, amount_1, lp_fee_1)
+    fn remove_liquidity_from_pool(&mut self, pool_id: u32, remove_lp_token_amount: &Nat) -> Result<(Nat, Nat, Nat, Nat), String> {
+        let (pool, _, _) = self.pool_and_tokens(pool_id)?;
+        let lp_total_supply = self.lp_total_supply(pool);
+        let (amount_0, lp_fee_0, amount_1, lp_fee_1) = remove_liquidity_amounts(
+            &pool.balance_0,
+            &pool.lp_fee_0,
+            &pool.balance_1,
+            &pool.lp_fee_1,
+            &lp_total_supply,
+            remove_lp_token_amount,
+        )?;
+
+        let pool = self.state.pools.get_mut(&pool_id).ok_or(format!("pool_id={} not found", pool_id))?;
+        pool.balance_0 = nat_subtract(&pool.balance_0, &amount_0).unwrap_or(nat_zero());
+        pool.lp_fee_0 = nat_subtract(&pool.lp_fee_0, &lp_fee_0).unwrap_or(nat_zero());
+        pool.balance_1 = nat_subtract(&pool.balance_1, &amount_1).unwrap_or(nat_zero());
+        pool.lp_fee_1 = nat_subtract(&pool.lp_fee_1, &lp_fee_1).unwrap_or(nat_zero());
+        let lp_token_id = pool.lp_token_id;
+        let lp_total_supply = self.state.lp_total_supply.entry(lp_token_id).or_default();
+        *lp_total_supply = nat_subtract(lp_total_supply, remove_lp_token_amount).unwrap_or(nat_zero());
+        Ok((amount_0, lp_fee_0, amount_1, lp_fee_1))
+    }
+
+    /// Opens a simulated LP position. Returns the index of the position
+    pub fn add_liquidity(&mut self, pool_id: u32, amount_0: &Nat, amount_1: &Nat) -> Result<usize, String> {
+        let (pool, _, _) = self.pool_and_tokens(pool_id)?;
+        let entry_price = self.state.price(pool).ok_or(format!("pool_id={} has no price", pool_id))?;
+        let (deposited_0, deposited_1, lp_token_amount) = self.add_liquidity_to_pool(pool_id, amount_0, amount_1)?;
+        self.positions.push(Position {
+            pool_id,
+            lp_token_amount,
+            deposited_0,
+            deposited_1,
+            entry_price,
+            fees_0: nat_zero(),
+            fees_1: nat_zero(),
+        });
+        Ok(self.positions.len() - 1)
+    }
+
+    /// Closes a simulated LP position. Returns (amount_0, lp_fee_0, amount_1, lp_fee_1) paid out
+    pub fn remove_liquidity(&mut self, position: usize) -> Result<(Nat, Nat, Nat, Nat), String> {
+        let Position {
+            pool_id, lp_token_amount, ..
+        } = self
+            .positions
+            .get(position)
+            .ok_or(format!("position={} not found", position))?
+            .clone();
+        let payout = self.remove_liquidity_from_pool(pool_id, &lp_token_amount)?;
+        self.positions[position].lp_token_amount = nat_zero();
+        Ok(payout)
+    }
+
+    /// Replays the successful transactions with from_ts < ts <= to_ts in order. Swaps are re-calculated on the simulated state,
+    /// liquidity changes use the historical amounts. Historical fee discounts are not known, swaps are replayed at the pool fee
+    pub fn replay(&mut self, txs: &[StableTx], from_ts: Option<u64>, to_ts: Option<u64>) -> ReplaySummary {
+        let mut summary = ReplaySummary::default();
+        let in_range = |ts: u64| from_ts.is_none_or(|from_ts| ts > from_ts) && to_ts.is_none_or(|to_ts| ts <= to_ts);
+
+        for tx in txs {
+            let result = match tx {
+                StableTx::Swap(tx) if tx.status == StatusTx::Success && in_range(tx.ts) => self.replay_swap(tx).map(|receive_amount| {
+                    summary.swaps += 1;
+                    if receive_amount != tx.receive_amount {
+                        summary.receive_mismatches += 1;
+                    }
+                }),
+                StableTx::AddPool(tx)
+                    if tx.status == StatusTx::Success && in_range(tx.ts) && self.state.pools.contains_key(&tx.pool_id) =>
+                {
+                    self.add_liquidity_to_pool(tx.pool_id, &tx.amount_0, &tx.amount_1)
+                        .map(|_| summary.liquidity_changes += 1)
+                }
+                StableTx::AddLiquidity(tx)
+                    if tx.status == StatusTx::Success && in_range(tx.ts) && self.state.pools.contains_key(&tx.pool_id) =>
+                {
+                    self.add_liquidity_to_pool(tx.pool_id, &tx.amount_0, &tx.amount_1)
+                        .map(|_| summary.liquidity_changes += 1)
+                }
+                StableTx::RemoveLiquidity(tx)
+                    if tx.status == StatusTx::Success && in_range(tx.ts) && self.state.pools.contains_key(&tx.pool_id) =>
+                {
+                    self.remove_liquidity_from_pool(tx.pool_id, &tx.remove_lp_token_amount)
+                        .map(|_| summary.liquidity_changes += 1)
+                }
+                StableTx::Send(_) => Ok(()),
+                _ => {
+                    summary.skipped += 1;
+                    Ok(())
+                }
+            };
+            if let Err(e) = result {
+                summary.errors.push(e);
+            }
+        }
+
+        summary
+    }
+
+    /// Value of a position at the current pool price
+    pub fn position_report(&self, position: usize) -> Result<PositionReport, String> {
+        let position = self.positions.get(position).ok_or(format!("position={} not found", position))?;
+        let (pool, token_0, token_1) = self.pool_and_tokens(position.pool_id)?;
+        let lp_total_supply = self.lp_total_supply(pool);
+        let (amount_0, lp_fee_0, amount_1, lp_fee_1) = if nat_is_zero(&position.lp_token_amount) {
+            (nat_zero(), nat_zero(), nat_zero(), nat_zero())
+        } else {
+            remove_liquidity_amounts(
+                &pool.balance_0,
+                &pool.lp_fee_0,
+                &pool.balance_1,
+                &pool.lp_fee_1,
+                &lp_total_supply,
+                &position.lp_token_amount,
+            )?
+        };
+        let price = self.state.price(pool).ok_or(format!("pool_id={} has no price", pool.pool_id))?;
+
+        let amount_0 = to_f64(&nat_add(&amount_0, &lp_fee_0), token_0.decimals()).ok_or("Invalid amount_0")?;
+        let amount_1 = to_f64(&nat_add(&amount_1, &lp_fee_1), token_1.decimals()).ok_or("Invalid amount_1")?;
+        let deposited_0 = to_f64(&position.deposited_0, token_0.decimals()).ok_or("Invalid deposited_0")?;
+        let deposited_1 = to_f64(&position.deposited_1, token_1.decimals()).ok_or("Invalid deposited_1")?;
+        let fees_0 = to_f64(&position.fees_0, token_0.decimals()).ok_or("Invalid fees_0")?;
+        let fees_1 = to_f64(&position.fees_1, token_1.decimals()).ok_or("Invalid fees_1")?;
+
+        Ok(PositionReport::new(
+            format!("{}_{}", token_0.symbol(), token_1.symbol()),
+            (deposited_0, deposited_1),
+            (amount_0, amount_1),
+            (fees_0, fees_1),
+            position.entry_price,
+            price,
+        ))
+    }
+}
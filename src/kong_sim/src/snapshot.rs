@@ -0,0 +1,232 @@
+use candid::Nat;
+use kong_lib::helpers::nat_helpers::{nat_add, nat_subtract, nat_zero};
+use kong_lib::helpers::pool_math::split_lp_fee;
+use kong_lib::stable_lp_token::stable_lp_token::{StableLPToken, StableLPTokenId};
+use kong_lib::stable_pool::stable_pool::{StablePool, StablePoolId};
+use kong_lib::stable_token::stable_token::{StableToken, StableTokenId};
+use kong_lib::stable_token::token::Token;
+use kong_lib::stable_tx::stable_tx::{StableTx, StableTxId};
+use kong_lib::stable_tx::status_tx::StatusTx;
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// State of the pools at a point in time, the starting point of a simulation
+#[derive(Debug, Clone, Default)]
+pub struct PoolSnapshot {
+    pub tokens: BTreeMap<u32, StableToken>,
+    pub pools: BTreeMap<u32, StablePool>,
+    pub lp_total_supply: BTreeMap<u32, Nat>, // by LP token_id
+    pub ts: Option<u64>,                     // time of the snapshot, None if unknown
+}
+
+impl PoolSnapshot {
+    pub fn new(tokens: Vec<StableToken>, pools: Vec<StablePool>, lp_tokens: Vec<StableLPToken>, ts: Option<u64>) -> Self {
+        let mut lp_total_supply: BTreeMap<u32, Nat> = BTreeMap::new();
+        for lp_token in lp_tokens {
+            *lp_total_supply.entry(lp_token.token_id).or_default() += lp_token.amount;
+        }
+        PoolSnapshot {
+            tokens: tokens.into_iter().map(|token| (token.token_id(), token)).collect(),
+            pools: pools.into_iter().map(|pool| (pool.pool_id, pool)).collect(),
+            lp_total_supply,
+            ts,
+        }
+    }
+
+    pub fn token(&self, token_id: u32) -> Result<&StableToken, String> {
+        self.tokens.get(&token_id).ok_or(format!("token_id={} not found", token_id))
+    }
+
+    /// pool by symbol, either "ICP_ckUSDT" or "ICP/ckUSDT"
+    pub fn pool_by_symbol(&self, symbol: &str) -> Option<&StablePool> {
+        let (symbol_0, symbol_1) = symbol.split_once('_').or(symbol.split_once('/'))?;
+        self.pools.values().find(|pool| {
+            !pool.is_removed
+                && self.tokens.get(&pool.token_id_0).is_some_and(|token| token.symbol() == symbol_0)
+                && self.tokens.get(&pool.token_id_1).is_some_and(|token| token.symbol() == symbol_1)
+        })
+    }
+
+    /// pool for a swap between the two tokens, in either direction
+    pub fn pool_by_token_ids(&self, token_id_a: u32, token_id_b: u32) -> Option<&StablePool> {
+        self.pools.values().find(|pool| {
+            !pool.is_removed
+                && ((pool.token_id_0 == token_id_a && pool.token_id_1 == token_id_b)
+                    || (pool.token_id_0 == token_id_b && pool.token_id_1 == token_id_a))
+        })
+    }
+
+    /// price of token_0 in token_1 using the pool reserves (balance + lp_fee)
+    pub fn price(&self, pool: &StablePool) -> Option<f64> {
+        let decimals_0 = self.tokens.get(&pool.token_id_0)?.decimals();
+        let decimals_1 = self.tokens.get(&pool.token_id_1)?.decimals();
+        let reserve_0 = to_f64(&(pool.balance_0.clone() + pool.lp_fee_0.clone()), decimals_0)?;
+        let reserve_1 = to_f64(&(pool.balance_1.clone() + pool.lp_fee_1.clone()), decimals_1)?;
+        if reserve_0 == 0_f64 {
+            None?
+        }
+        Some(reserve_1 / reserve_0)
+    }
+
+    /// Undoes the successful transactions after ts, newest first, to get the state of the pools at ts.
+    /// Used to start a simulation in the past from the current state (backups or database)
+    pub fn rewind(&mut self, txs: &[StableTx], ts: u64) -> Result<(), String> {
+        for tx in txs.iter().rev() {
+            match tx {
+                StableTx::Swap(tx) if tx.status == StatusTx::Success && tx.ts > ts => {
+                    for swap in tx.txs.iter().rev() {
+                        let pool = self
+                            .pools
+                            .get_mut(&swap.pool_id)
+                            .ok_or(format!("pool_id={} not found", swap.pool_id))?;
+                        // inverse of Simulator::apply_swaps()
+                        let (lp_fee, kong_fee) = split_lp_fee(&swap.lp_fee, pool.lp_fee_bps, pool.kong_fee_bps);
+                        if swap.receive_token_id == pool.token_id_1 {
+                            pool.balance_0 =
+                                nat_subtract(&pool.balance_0, &swap.pay_amount).ok_or(format!("tx_id={} balance_0 underflow", tx.tx_id))?;
+                            pool.balance_1 = nat_add(&pool.balance_1, &swap.receive_amount);
+                            pool.lp_fee_1 =
+                                nat_subtract(&pool.lp_fee_1, &lp_fee).ok_or(format!("tx_id={} lp_fee_1 underflow", tx.tx_id))?;
+                            pool.kong_fee_1 = nat_subtract(&pool.kong_fee_1, &kong_fee).unwrap_or(nat_zero());
+                        } else {
+                            pool.balance_1 =
+                                nat_subtract(&pool.balance_1, &swap.pay_amount).ok_or(format!("tx_id={} balance_1 underflow", tx.tx_id))?;
+                            pool.balance_0 = nat_add(&pool.balance_0, &swap.receive_amount);
+                            pool.lp_fee_0 =
+                                nat_subtract(&pool.lp_fee_0, &lp_fee).ok_or(format!("tx_id={} lp_fee_0 underflow", tx.tx_id))?;
+                            pool.kong_fee_0 = nat_subtract(&pool.kong_fee_0, &kong_fee).unwrap_or(nat_zero());
+                        }
+                    }
+                }
+                StableTx::AddPool(tx) if tx.status == StatusTx::Success && tx.ts > ts => {
+                    self.rewind_liquidity(
+                        tx.pool_id,
+                        (&tx.amount_0, &nat_zero(), &tx.amount_1, &nat_zero()),
+                        &tx.add_lp_token_amount,
+                        true,
+                    )?;
+                }
+                StableTx::AddLiquidity(tx) if tx.status == StatusTx::Success && tx.ts > ts => {
+                    self.rewind_liquidity(
+                        tx.pool_id,
+                        (&tx.amount_0, &nat_zero(), &tx.amount_1, &nat_zero()),
+                        &tx.add_lp_token_amount,
+                        true,
+                    )?;
+                }
+                StableTx::RemoveLiquidity(tx) if tx.status == StatusTx::Success && tx.ts > ts => {
+                    self.rewind_liquidity(
+                        tx.pool_id,
+                        (&tx.amount_0, &tx.lp_fee_0, &tx.amount_1, &tx.lp_fee_1),
+                        &tx.remove_lp_token_amount,
+                        false,
+                    )?;
+                }
+                _ => (),
+            }
+        }
+        self.ts = Some(ts);
+        Ok(())
+    }
+
+    fn rewind_liquidity(
+        &mut self,
+        pool_id: u32,
+        (amount_0, lp_fee_0, amount_1, lp_fee_1): (&Nat, &Nat, &Nat, &Nat),
+        lp_token_amount: &Nat,
+        is_add: bool,
+    ) -> Result<(), String> {
+        let pool = self.pools.get_mut(&pool_id).ok_or(format!("pool_id={} not found", pool_id))?;
+        let lp_total_supply = self.lp_total_supply.entry(pool.lp_token_id).or_default();
+        if is_add {
+            pool.balance_0 = nat_subtract(&pool.balance_0, amount_0).unwrap_or(nat_zero());
+            pool.balance_1 = nat_subtract(&pool.balance_1, amount_1).unwrap_or(nat_zero());
+            *lp_total_supply = nat_subtract(lp_total_supply, lp_token_amount).unwrap_or(nat_zero());
+        } else {
+            pool.balance_0 = nat_add(&pool.balance_0, amount_0);
+            pool.lp_fee_0 = nat_add(&pool.lp_fee_0, lp_fee_0);
+            pool.balance_1 = nat_add(&pool.balance_1, amount_1);
+            pool.lp_fee_1 = nat_add(&pool.lp_fee_1, lp_fee_1);
+            *lp_total_supply = nat_add(lp_total_supply, lp_token_amount);
+        }
+        Ok(())
+    }
+}
+
+/// amount in token units
+pub fn to_f64(amount: &Nat, decimals: u8) -> Option<f64> {
+    use num_traits::ToPrimitive;
+    Some(amount.0.to_f64()? / 10_f64.powi(decimals as i32))
+}
+
+#[derive(Deserialize)]
+struct KongAdminSnapshot {
+    created_at: u64,
+    state: KongAdminSnapshotState,
+}
+
+#[derive(Deserialize)]
+struct KongAdminSnapshotState {
+    tokens: Vec<StableToken>,
+    pools: Vec<StablePool>,
+    lp_tokens: Vec<StableLPToken>,
+}
+
+/// Loads a snapshot created by `kong_admin --snapshot`. The checksum is verified by kong_admin on restore, not here
+pub fn load_kong_admin_snapshot(path: &Path) -> Result<PoolSnapshot, Box<dyn std::error::Error>> {
+    let reader = BufReader::new(File::open(path)?);
+    let snapshot: KongAdminSnapshot = serde_json::from_reader(reader)?;
+    Ok(PoolSnapshot::new(
+        snapshot.state.tokens,
+        snapshot.state.pools,
+        snapshot.state.lp_tokens,
+        Some(snapshot.created_at),
+    ))
+}
+
+/// reads and merges all the backup files of a map, e.g. pools.*.json
+fn load_backup_files<K: Ord + DeserializeOwned, V: DeserializeOwned>(
+    dir_path: &Path,
+    pattern: &str,
+) -> Result<BTreeMap<K, V>, Box<dyn std::error::Error>> {
+    let re_pattern = Regex::new(pattern)?;
+    let mut files = fs::read_dir(dir_path)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_str().is_some_and(|file_name| re_pattern.is_match(file_name)))
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut map = BTreeMap::new();
+    for file in files {
+        let reader = BufReader::new(File::open(file)?);
+        let file_map: BTreeMap<K, V> = serde_json::from_reader(reader)?;
+        map.extend(file_map);
+    }
+    Ok(map)
+}
+
+/// Loads the kong_backend backup JSON files (tokens.*.json, pools.*.json and lp_tokens.*.json) as used by kong_admin
+pub fn load_backups(dir_path: &Path) -> Result<PoolSnapshot, Box<dyn std::error::Error>> {
+    let tokens: BTreeMap<StableTokenId, StableToken> = load_backup_files(dir_path, r"^tokens.*.json$")?;
+    let pools: BTreeMap<StablePoolId, StablePool> = load_backup_files(dir_path, r"^pools.*.json$")?;
+    let lp_tokens: BTreeMap<StableLPTokenId, StableLPToken> = load_backup_files(dir_path, r"^lp_tokens.*.json$")?;
+    Ok(PoolSnapshot::new(
+        tokens.into_values().collect(),
+        pools.into_values().collect(),
+        lp_tokens.into_values().collect(),
+        None,
+    ))
+}
+
+/// Loads the transactions from the kong_backend backup JSON files (txs.*.json), ordered by tx_id
+pub fn load_backup_txs(dir_path: &Path) -> Result<Vec<StableTx>, Box<dyn std::error::Error>> {
+    let txs: BTreeMap<StableTxId, StableTx> = load_backup_files(dir_path, r"^txs.*.json$")?;
+    Ok(txs.into_values().collect())
+}
@@ -121,22 +121,26 @@ export interface CreateMarketParams {
 
 export async function createMarket(params: CreateMarketParams) {
   const actor = predictionActor({ anon: false, requiresSigning: false });
-  const result = await actor.create_market(
-    params.question,
-    params.category,
-    params.rules,
-    params.outcomes,
-    params.resolutionMethod,
-    params.endTimeSpec,
-    params.image_url ? [params.image_url] : [], // Pass as optional array
-    params.uses_time_weighting !== undefined
-      ? [params.uses_time_weighting]
-      : [],
-    params.time_weight_alpha !== undefined ? [params.time_weight_alpha] : [],
-    params.token_id !== undefined && params.token_id !== null
-      ? [String(params.token_id)]
-      : [],
-  );
+  const result = await actor.create_market({
+    question: params.question,
+    category: params.category,
+    rules: params.rules,
+    outcomes: params.outcomes,
+    resolution_method: params.resolutionMethod,
+    end_time: params.endTimeSpec,
+    image_url: params.image_url ? [params.image_url] : [], // Pass as optional array
+    uses_time_weighting:
+      params.uses_time_weighting !== undefined
+        ? [params.uses_time_weighting]
+        : [],
+    time_weight_alpha:
+      params.time_weight_alpha !== undefined ? [params.time_weight_alpha] : [],
+    token_id:
+      params.token_id !== undefined && params.token_id !== null
+        ? [String(params.token_id)]
+        : [],
+    payout_model: [],
+  });
 
   notificationsStore.add({
     title: "Market Created",
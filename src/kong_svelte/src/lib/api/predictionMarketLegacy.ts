@@ -39,39 +39,6 @@ export async function getUserHistory(principal: string) {
   }
 }
 
-export interface CreateMarketParams {
-  question: string;
-  category: any; // MarketCategory type from candid
-  rules: string;
-  outcomes: string[];
-  resolutionMethod: any; // ResolutionMethod type from candid
-  endTimeSpec: any; // MarketEndTime type from candid
-  image_url?: string; // Optional image URL
-  uses_time_weighting?: boolean; // Optional: Whether to use time-weighted distribution
-  time_weight_alpha?: number; // Optional: Decay parameter for time-weighting
-  token_id?: string; // Optional: Token type to use for this market
-}
-
-export async function createMarket(params: CreateMarketParams) {
-  const actor = predictionActorLegacy({anon: false, requiresSigning: false});
-  const result = await actor.create_market(
-    params.question,
-    params.category,
-    params.rules,
-    params.outcomes,
-    params.resolutionMethod,
-    params.endTimeSpec,
-    params.image_url ? [params.image_url] : []
-  );
-
-  notificationsStore.add({
-    title: "Market Created",
-    message: `Market "${params.question}" has been created`,
-    type: "success",
-  });
-  return result;
-}
-
 export async function placeBet(
   token: Kong.Token,
   marketId: bigint,
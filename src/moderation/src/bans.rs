@@ -0,0 +1,40 @@
+use candid::Principal;
+use ic_stable_structures::storable::Bound;
+use ic_stable_structures::Storable;
+use std::borrow::Cow;
+
+// Encodings match the banned users stores of trollbox and comments so existing bans are kept
+
+/// Storable wrapper for a banned principal
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PrincipalStorable(pub Principal);
+
+impl Storable for PrincipalStorable {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.0.as_slice().to_vec())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(Principal::from_slice(&bytes))
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// Storable wrapper for the ban expiry time in nanoseconds
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExpiryTimeStorable(pub u64);
+
+impl Storable for ExpiryTimeStorable {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.0.to_le_bytes().to_vec())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        let mut data = [0u8; 8];
+        data.copy_from_slice(&bytes[0..8]);
+        Self(u64::from_le_bytes(data))
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
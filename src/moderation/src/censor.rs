@@ -0,0 +1,99 @@
+use rustrict::Censor;
+
+use crate::types::WordLists;
+
+pub const CENSOR_REPLACEMENT: char = '🦍';
+
+/// Reject text containing a blocked word and replace profanity and censored words
+pub fn censor_text(text: &str, word_lists: &WordLists) -> Result<String, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+
+    if word_lists.blocked.iter().any(|word| !find_word(&lower, word).is_empty()) {
+        return Err("Content contains a blocked word".to_string());
+    }
+
+    let mut censored = chars;
+    for word in &word_lists.censored {
+        for start in find_word(&lower, word) {
+            let len = word.chars().count();
+            censored[start..start + len].fill(CENSOR_REPLACEMENT);
+        }
+    }
+    let censored: String = censored.into_iter().collect();
+
+    if !word_lists.builtin_filter {
+        return Ok(censored);
+    }
+    let (censored, _) = Censor::from_str(&censored)
+        .with_censor_replacement(CENSOR_REPLACEMENT)
+        .censor_and_analyze();
+    Ok(censored)
+}
+
+/// Lowercase, trim and dedupe a word list, rejecting empty or overlong words
+pub fn normalize_words(words: Vec<String>) -> Result<Vec<String>, String> {
+    use crate::types::{MAX_WORDS_PER_LIST, MAX_WORD_LENGTH};
+
+    if words.len() > MAX_WORDS_PER_LIST {
+        return Err(format!("A word list can have at most {} words", MAX_WORDS_PER_LIST));
+    }
+    let mut words: Vec<String> = words.into_iter().map(|w| w.trim().to_lowercase()).collect();
+    if let Some(word) = words.iter().find(|w| w.is_empty() || w.chars().count() > MAX_WORD_LENGTH) {
+        return Err(format!(
+            "Invalid word \"{}\". Words must be between 1 and {} characters",
+            word, MAX_WORD_LENGTH
+        ));
+    }
+    words.sort();
+    words.dedup();
+    Ok(words)
+}
+
+// Start indexes of whole-word matches of a lowercase word in lowercase chars
+fn find_word(text: &[char], word: &str) -> Vec<usize> {
+    let word: Vec<char> = word.chars().collect();
+    if word.is_empty() || word.len() > text.len() {
+        return Vec::new();
+    }
+    let is_boundary = |i: Option<&char>| i.is_none_or(|c| !c.is_alphanumeric());
+    (0..=text.len() - word.len())
+        .filter(|&start| text[start..start + word.len()] == word[..])
+        .filter(|&start| is_boundary(start.checked_sub(1).and_then(|i| text.get(i))) && is_boundary(text.get(start + word.len())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lists(blocked: &[&str], censored: &[&str]) -> WordLists {
+        WordLists {
+            builtin_filter: false,
+            blocked: blocked.iter().map(|w| w.to_string()).collect(),
+            censored: censored.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_blocked_words() {
+        let lists = lists(&["rugpull"], &[]);
+        assert!(censor_text("this is a RugPull!", &lists).is_err());
+        assert_eq!(censor_text("rugpulls are bad", &lists).unwrap(), "rugpulls are bad");
+    }
+
+    #[test]
+    fn test_censored_words() {
+        let lists = lists(&[], &["moon"]);
+        assert_eq!(censor_text("to the Moon, moonshot", &lists).unwrap(), "to the 🦍🦍🦍🦍, moonshot");
+    }
+
+    #[test]
+    fn test_normalize_words() {
+        assert_eq!(
+            normalize_words(vec![" Scam ".to_string(), "scam".to_string(), "bot".to_string()]).unwrap(),
+            vec!["bot".to_string(), "scam".to_string()]
+        );
+        assert!(normalize_words(vec!["  ".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,14 @@
+//! Moderation shared by the trollbox and comments canisters
+//!
+//! Content is identified by the canister's own u64 ID (message or comment). The store keeps user
+//! reports, the review queue, appeals, bans, runtime word lists and an audit log of moderator
+//! actions in stable memory. Callers pass in the caller principal and current time so the logic
+//! does not depend on the IC runtime.
+
+pub mod bans;
+pub mod censor;
+pub mod store;
+pub mod types;
+
+pub use store::{ModerationMemories, ModerationStore, NewReport};
+pub use types::*;
@@ -0,0 +1,663 @@
+use candid::Principal;
+use ic_stable_structures::storable::Bound;
+use ic_stable_structures::{Memory, StableBTreeMap, StableCell, Storable};
+use std::borrow::Cow;
+
+use crate::bans::{ExpiryTimeStorable, PrincipalStorable};
+use crate::censor::{censor_text, normalize_words};
+use crate::types::*;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+const NANOS_PER_DAY: u64 = 24 * 60 * 60 * NANOS_PER_SEC;
+
+/// Stable memories used by the moderation store, allocated by the canister's memory manager
+pub struct ModerationMemories<M: Memory> {
+    pub bans: M,
+    pub reports: M,
+    pub contents: M,
+    pub appeals: M,
+    pub log: M,
+    pub settings: M,
+    pub pending_reports: M,
+    pub pending_contents: M,
+}
+
+/// Report to file against a message or comment
+pub struct NewReport {
+    pub content_id: u64,
+    pub author: Principal,
+    pub snapshot: String,
+    pub reporter: Principal,
+    pub reason: ReportReason,
+    pub note: Option<String>,
+}
+
+/// Pending report of a reporter. Encoded as the length-prefixed principal followed by the big-endian
+/// report ID, so the pending reports of a reporter are adjacent
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct ReporterReportKey {
+    reporter: Principal,
+    report_id: u64,
+}
+
+impl Storable for ReporterReportKey {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let principal = self.reporter.as_slice();
+        let mut bytes = Vec::with_capacity(1 + principal.len() + 8);
+        bytes.push(principal.len() as u8);
+        bytes.extend_from_slice(principal);
+        bytes.extend_from_slice(&self.report_id.to_be_bytes());
+        Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        let len = bytes[0] as usize;
+        Self {
+            reporter: Principal::from_slice(&bytes[1..1 + len]),
+            report_id: u64::from_be_bytes(bytes[1 + len..].try_into().expect("Invalid reporter report key")),
+        }
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 1 + 29 + 8,
+        is_fixed_size: false,
+    };
+}
+
+pub struct ModerationStore<M: Memory> {
+    bans: StableBTreeMap<PrincipalStorable, ExpiryTimeStorable, M>,
+    reports: StableBTreeMap<u64, Report, M>,
+    contents: StableBTreeMap<u64, ModeratedContent, M>,
+    pending_reports: StableBTreeMap<ReporterReportKey, (), M>, // pending reports by reporter
+    pending_contents: StableBTreeMap<u64, (), M>,              // content with pending reports, the review queue
+    appeals: StableBTreeMap<u64, Appeal, M>,
+    log: StableBTreeMap<u64, ModerationLogEntry, M>,
+    settings: StableCell<ModerationSettings, M>,
+}
+
+impl<M: Memory> ModerationStore<M> {
+    pub fn init(memories: ModerationMemories<M>) -> Self {
+        Self {
+            bans: StableBTreeMap::init(memories.bans),
+            reports: StableBTreeMap::init(memories.reports),
+            contents: StableBTreeMap::init(memories.contents),
+            pending_reports: StableBTreeMap::init(memories.pending_reports),
+            pending_contents: StableBTreeMap::init(memories.pending_contents),
+            appeals: StableBTreeMap::init(memories.appeals),
+            log: StableBTreeMap::init(memories.log),
+            settings: StableCell::init(memories.settings, ModerationSettings::default()).expect("Failed to initialize moderation settings"),
+        }
+    }
+
+    //
+    // Settings and word lists
+    //
+
+    pub fn settings(&self) -> ModerationSettings {
+        self.settings.get().clone()
+    }
+
+    pub fn set_config(&mut self, config: ModerationConfig, moderator: Principal, now: u64) -> Result<(), String> {
+        let settings = ModerationSettings { config, ..self.settings() };
+        self.settings
+            .set(settings)
+            .map_err(|_| "Failed to update moderation config".to_string())?;
+        self.log(Some(moderator), ModerationAction::UpdateConfig, None, None, None, now);
+        Ok(())
+    }
+
+    pub fn set_word_lists(&mut self, word_lists: WordLists, moderator: Principal, now: u64) -> Result<WordLists, String> {
+        let word_lists = WordLists {
+            builtin_filter: word_lists.builtin_filter,
+            blocked: normalize_words(word_lists.blocked)?,
+            censored: normalize_words(word_lists.censored)?,
+        };
+        let settings = ModerationSettings {
+            word_lists: word_lists.clone(),
+            ..self.settings()
+        };
+        self.settings.set(settings).map_err(|_| "Failed to update word lists".to_string())?;
+        self.log(Some(moderator), ModerationAction::UpdateWordLists, None, None, None, now);
+        Ok(word_lists)
+    }
+
+    /// Apply the word lists to new content
+    pub fn censor(&self, text: &str) -> Result<String, String> {
+        censor_text(text, &self.settings.get().word_lists)
+    }
+
+    //
+    // Bans
+    //
+
+    pub fn ban(&mut self, user: Principal, days: u64, moderator: Principal, now: u64) {
+        let expiry = now.saturating_add(days.saturating_mul(NANOS_PER_DAY));
+        self.bans.insert(PrincipalStorable(user), ExpiryTimeStorable(expiry));
+        self.log(Some(moderator), ModerationAction::Ban { days }, None, Some(user), None, now);
+    }
+
+    pub fn unban(&mut self, user: Principal, moderator: Principal, now: u64) -> bool {
+        let was_banned = self.bans.remove(&PrincipalStorable(user)).is_some();
+        if was_banned {
+            self.log(Some(moderator), ModerationAction::Unban, None, Some(user), None, now);
+        }
+        was_banned
+    }
+
+    /// Remaining ban time in seconds, removing the ban once it has expired
+    pub fn ban_remaining_secs(&mut self, user: &Principal, now: u64) -> Option<u64> {
+        let key = PrincipalStorable(*user);
+        let ExpiryTimeStorable(expiry) = self.bans.get(&key)?;
+        if now > expiry {
+            self.bans.remove(&key);
+            return None;
+        }
+        Some((expiry - now) / NANOS_PER_SEC)
+    }
+
+    //
+    // Reports and review
+    //
+
+    pub fn report(&mut self, new_report: NewReport, now: u64) -> Result<Report, String> {
+        if new_report.reporter == new_report.author {
+            return Err("You cannot report your own content".to_string());
+        }
+        let note = validate_note(new_report.note)?;
+
+        let mut content = self.contents.get(&new_report.content_id).unwrap_or_else(|| ModeratedContent {
+            content_id: new_report.content_id,
+            author: new_report.author,
+            snapshot: truncate(&new_report.snapshot, MAX_SNAPSHOT_LENGTH),
+            status: ContentStatus::Visible,
+            auto_hidden: false,
+            report_ids: Vec::new(),
+            pending_reports: 0,
+            reason: None,
+            reviewed_by: None,
+            updated_at: now,
+        });
+        if content.status == ContentStatus::Removed {
+            return Err("This content has already been removed".to_string());
+        }
+        if content
+            .report_ids
+            .iter()
+            .filter_map(|id| self.reports.get(id))
+            .any(|report| report.reporter == new_report.reporter && report.status == ReportStatus::Pending)
+        {
+            return Err("You have already reported this content".to_string());
+        }
+
+        let config = self.settings.get().config.clone();
+        let max_pending = config.max_pending_reports_per_user as usize;
+        let pending = self
+            .pending_reports
+            .range(
+                ReporterReportKey {
+                    reporter: new_report.reporter,
+                    report_id: 0,
+                }..,
+            )
+            .take_while(|(key, _)| key.reporter == new_report.reporter)
+            .take(max_pending)
+            .count();
+        if pending >= max_pending {
+            return Err("You have too many reports waiting for review. Please try again later".to_string());
+        }
+
+        let id = self.reports.last_key_value().map_or(0, |(id, _)| id + 1);
+        let report = Report {
+            id,
+            content_id: new_report.content_id,
+            reporter: new_report.reporter,
+            reason: new_report.reason,
+            note,
+            status: ReportStatus::Pending,
+            created_at: now,
+        };
+        self.reports.insert(id, report.clone());
+        self.pending_reports.insert(
+            ReporterReportKey {
+                reporter: report.reporter,
+                report_id: id,
+            },
+            (),
+        );
+        self.pending_contents.insert(new_report.content_id, ());
+
+        content.report_ids.push(id);
+        content.pending_reports += 1;
+        content.updated_at = now;
+        let auto_hide = config.auto_hide_threshold > 0
+            && content.status == ContentStatus::Visible
+            && content.pending_reports >= config.auto_hide_threshold;
+        if auto_hide {
+            content.status = ContentStatus::Hidden;
+            content.auto_hidden = true;
+        }
+        self.contents.insert(content.content_id, content.clone());
+        if auto_hide {
+            self.log(
+                None,
+                ModerationAction::AutoHide,
+                Some(content.content_id),
+                Some(content.author),
+                None,
+                now,
+            );
+        }
+
+        Ok(report)
+    }
+
+    pub fn content(&self, content_id: u64) -> Option<ModeratedContent> {
+        self.contents.get(&content_id)
+    }
+
+    /// Hidden and removed content is left out of public queries
+    pub fn is_hidden(&self, content_id: u64) -> bool {
+        self.contents
+            .get(&content_id)
+            .is_some_and(|content| content.status != ContentStatus::Visible)
+    }
+
+    /// Content with pending reports, oldest content first
+    pub fn review_queue(&self, cursor: Option<u64>, limit: Option<u64>) -> ReviewQueuePage {
+        let limit = page_size(limit);
+        let start = cursor.map_or(0, |cursor| cursor.saturating_add(1));
+        let items: Vec<ReviewItem> = self
+            .pending_contents
+            .range(start..)
+            .filter_map(|(content_id, _)| self.contents.get(&content_id))
+            .take(limit)
+            .map(|content| ReviewItem {
+                reports: content
+                    .report_ids
+                    .iter()
+                    .filter_map(|id| self.reports.get(id))
+                    .filter(|report| report.status == ReportStatus::Pending)
+                    .collect(),
+                content,
+            })
+            .collect();
+        let next_cursor = if items.len() == limit {
+            items.last().map(|item| item.content.content_id)
+        } else {
+            None
+        };
+        ReviewQueuePage { items, next_cursor }
+    }
+
+    /// Resolve the pending reports of content. The canister deletes removed content itself
+    pub fn review(
+        &mut self,
+        content_id: u64,
+        decision: ReviewDecision,
+        moderator: Principal,
+        note: Option<String>,
+        now: u64,
+    ) -> Result<ModeratedContent, String> {
+        let note = validate_note(note)?;
+        let mut content = self
+            .contents
+            .get(&content_id)
+            .ok_or_else(|| format!("No reports found for content {}", content_id))?;
+        if content.status == ContentStatus::Removed {
+            return Err(format!("Content {} has already been removed", content_id));
+        }
+
+        let (status, report_status, action) = match decision {
+            ReviewDecision::Dismiss => (ContentStatus::Visible, ReportStatus::Dismissed, ModerationAction::Dismiss),
+            ReviewDecision::Hide => (ContentStatus::Hidden, ReportStatus::Upheld, ModerationAction::Hide),
+            ReviewDecision::Remove => (ContentStatus::Removed, ReportStatus::Upheld, ModerationAction::Remove),
+        };
+        self.resolve_reports(&content, report_status);
+
+        content.auto_hidden = false;
+        content.reason = if status == ContentStatus::Visible { None } else { note.clone() };
+        content.status = status;
+        content.pending_reports = 0;
+        content.reviewed_by = Some(moderator);
+        content.updated_at = now;
+        self.contents.insert(content_id, content.clone());
+        self.log(Some(moderator), action, Some(content_id), Some(content.author), note, now);
+
+        Ok(content)
+    }
+
+    /// Record content deleted directly by a moderator so it is logged and can be appealed
+    pub fn record_removal(
+        &mut self,
+        content_id: u64,
+        author: Principal,
+        snapshot: &str,
+        moderator: Principal,
+        note: Option<String>,
+        now: u64,
+    ) {
+        let note = note.map(|note| truncate(&note, MAX_NOTE_LENGTH));
+        let mut content = self.contents.get(&content_id).unwrap_or_else(|| ModeratedContent {
+            content_id,
+            author,
+            snapshot: truncate(snapshot, MAX_SNAPSHOT_LENGTH),
+            status: ContentStatus::Visible,
+            auto_hidden: false,
+            report_ids: Vec::new(),
+            pending_reports: 0,
+            reason: None,
+            reviewed_by: None,
+            updated_at: now,
+        });
+        self.resolve_reports(&content, ReportStatus::Upheld);
+
+        content.status = ContentStatus::Removed;
+        content.auto_hidden = false;
+        content.pending_reports = 0;
+        content.reason = note.clone();
+        content.reviewed_by = Some(moderator);
+        content.updated_at = now;
+        self.contents.insert(content_id, content);
+        self.log(Some(moderator), ModerationAction::Remove, Some(content_id), Some(author), note, now);
+    }
+
+    fn resolve_reports(&mut self, content: &ModeratedContent, status: ReportStatus) {
+        for id in &content.report_ids {
+            if let Some(mut report) = self.reports.get(id) {
+                if report.status == ReportStatus::Pending {
+                    self.pending_reports.remove(&ReporterReportKey {
+                        reporter: report.reporter,
+                        report_id: *id,
+                    });
+                    report.status = status.clone();
+                    self.reports.insert(*id, report);
+                }
+            }
+        }
+        self.pending_contents.remove(&content.content_id);
+    }
+
+    //
+    // Appeals
+    //
+
+    pub fn appeal(&mut self, target: AppealTarget, appellant: Principal, reason: String, now: u64) -> Result<Appeal, String> {
+        let reason = reason.trim().to_string();
+        if reason.is_empty() || reason.len() > MAX_NOTE_LENGTH {
+            return Err(format!("Appeal reason must be between 1 and {} characters", MAX_NOTE_LENGTH));
+        }
+
+        match &target {
+            AppealTarget::Content(content_id) => {
+                let content = self
+                    .contents
+                    .get(content_id)
+                    .filter(|content| content.author == appellant)
+                    .ok_or("You can only appeal moderation of your own content")?;
+                if content.status == ContentStatus::Visible {
+                    return Err("This content is not hidden or removed".to_string());
+                }
+            }
+            AppealTarget::Ban => {
+                if self.ban_remaining_secs(&appellant, now).is_none() {
+                    return Err("You are not banned".to_string());
+                }
+            }
+        }
+
+        if self
+            .appeals
+            .iter()
+            .any(|(_, appeal)| appeal.appellant == appellant && appeal.target == target && appeal.status == AppealStatus::Pending)
+        {
+            return Err("You already have a pending appeal for this".to_string());
+        }
+
+        let id = self.appeals.last_key_value().map_or(0, |(id, _)| id + 1);
+        let appeal = Appeal {
+            id,
+            target,
+            appellant,
+            reason,
+            status: AppealStatus::Pending,
+            resolved_by: None,
+            resolution_note: None,
+            created_at: now,
+            resolved_at: None,
+        };
+        self.appeals.insert(id, appeal.clone());
+        Ok(appeal)
+    }
+
+    pub fn appeals(&self, status: Option<AppealStatus>, cursor: Option<u64>, limit: Option<u64>) -> AppealsPage {
+        let limit = page_size(limit);
+        let start = cursor.map_or(0, |cursor| cursor.saturating_add(1));
+        let appeals: Vec<Appeal> = self
+            .appeals
+            .range(start..)
+            .map(|(_, appeal)| appeal)
+            .filter(|appeal| status.as_ref().is_none_or(|status| appeal.status == *status))
+            .take(limit)
+            .collect();
+        let next_cursor = if appeals.len() == limit {
+            appeals.last().map(|appeal| appeal.id)
+        } else {
+            None
+        };
+        AppealsPage { appeals, next_cursor }
+    }
+
+    pub fn appeals_by(&self, appellant: &Principal) -> Vec<Appeal> {
+        self.appeals
+            .iter()
+            .map(|(_, appeal)| appeal)
+            .filter(|appeal| appeal.appellant == *appellant)
+            .collect()
+    }
+
+    /// Accepting a content appeal makes the content visible again, accepting a ban appeal lifts the ban
+    pub fn resolve_appeal(
+        &mut self,
+        appeal_id: u64,
+        accept: bool,
+        moderator: Principal,
+        note: Option<String>,
+        now: u64,
+    ) -> Result<Appeal, String> {
+        let note = validate_note(note)?;
+        let mut appeal = self
+            .appeals
+            .get(&appeal_id)
+            .ok_or_else(|| format!("Appeal {} not found", appeal_id))?;
+        if appeal.status != AppealStatus::Pending {
+            return Err(format!("Appeal {} has already been resolved", appeal_id));
+        }
+
+        let content_id = match appeal.target {
+            AppealTarget::Content(content_id) => Some(content_id),
+            AppealTarget::Ban => None,
+        };
+        if accept {
+            match content_id {
+                Some(content_id) => {
+                    if let Some(mut content) = self.contents.get(&content_id) {
+                        content.status = ContentStatus::Visible;
+                        content.auto_hidden = false;
+                        content.reason = None;
+                        content.reviewed_by = Some(moderator);
+                        content.updated_at = now;
+                        self.contents.insert(content_id, content);
+                    }
+                }
+                None => {
+                    self.bans.remove(&PrincipalStorable(appeal.appellant));
+                }
+            }
+        }
+
+        appeal.status = if accept { AppealStatus::Accepted } else { AppealStatus::Rejected };
+        appeal.resolved_by = Some(moderator);
+        appeal.resolution_note = note.clone();
+        appeal.resolved_at = Some(now);
+        self.appeals.insert(appeal_id, appeal.clone());
+
+        let action = if accept {
+            ModerationAction::AcceptAppeal { appeal_id }
+        } else {
+            ModerationAction::RejectAppeal { appeal_id }
+        };
+        self.log(Some(moderator), action, content_id, Some(appeal.appellant), note, now);
+
+        Ok(appeal)
+    }
+
+    //
+    // Audit log
+    //
+
+    pub fn log(
+        &mut self,
+        moderator: Option<Principal>,
+        action: ModerationAction,
+        content_id: Option<u64>,
+        user: Option<Principal>,
+        note: Option<String>,
+        now: u64,
+    ) -> u64 {
+        let id = self.log.last_key_value().map_or(0, |(id, _)| id + 1);
+        self.log.insert(
+            id,
+            ModerationLogEntry {
+                id,
+                moderator,
+                action,
+                content_id,
+                user,
+                note,
+                created_at: now,
+            },
+        );
+        id
+    }
+
+    /// Audit log, newest first
+    pub fn log_page(&self, cursor: Option<u64>, limit: Option<u64>) -> ModerationLogPage {
+        let limit = page_size(limit);
+        let end = cursor.unwrap_or(u64::MAX);
+        let entries: Vec<ModerationLogEntry> = self.log.range(..end).rev().take(limit).map(|(_, entry)| entry).collect();
+        let next_cursor = if entries.len() == limit {
+            entries.last().map(|entry| entry.id)
+        } else {
+            None
+        };
+        ModerationLogPage { entries, next_cursor }
+    }
+}
+
+fn page_size(limit: Option<u64>) -> usize {
+    limit.map_or(DEFAULT_PAGE_SIZE, |limit| (limit as usize).clamp(1, MAX_PAGE_SIZE))
+}
+
+fn validate_note(note: Option<String>) -> Result<Option<String>, String> {
+    match note.map(|note| note.trim().to_string()).filter(|note| !note.is_empty()) {
+        Some(note) if note.len() > MAX_NOTE_LENGTH => Err(format!("Note is too long. Maximum length is {}", MAX_NOTE_LENGTH)),
+        note => Ok(note),
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    text.chars().take(max_chars).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_stable_structures::DefaultMemoryImpl;
+
+    fn store() -> ModerationStore<DefaultMemoryImpl> {
+        ModerationStore::init(ModerationMemories {
+            bans: DefaultMemoryImpl::default(),
+            reports: DefaultMemoryImpl::default(),
+            contents: DefaultMemoryImpl::default(),
+            appeals: DefaultMemoryImpl::default(),
+            log: DefaultMemoryImpl::default(),
+            settings: DefaultMemoryImpl::default(),
+            pending_reports: DefaultMemoryImpl::default(),
+            pending_contents: DefaultMemoryImpl::default(),
+        })
+    }
+
+    fn user(id: u8) -> Principal {
+        Principal::from_slice(&[id])
+    }
+
+    fn report(store: &mut ModerationStore<DefaultMemoryImpl>, content_id: u64, reporter: u8) -> Result<Report, String> {
+        store.report(
+            NewReport {
+                content_id,
+                author: user(1),
+                snapshot: "gm".to_string(),
+                reporter: user(reporter),
+                reason: ReportReason::Spam,
+                note: None,
+            },
+            0,
+        )
+    }
+
+    #[test]
+    fn test_auto_hide_after_threshold() {
+        let mut store = store();
+        assert!(report(&mut store, 7, 1).is_err()); // own content
+        report(&mut store, 7, 2).unwrap();
+        assert!(report(&mut store, 7, 2).is_err()); // duplicate
+        report(&mut store, 7, 3).unwrap();
+        assert!(!store.is_hidden(7));
+        report(&mut store, 7, 4).unwrap();
+        assert!(store.is_hidden(7));
+        assert_eq!(store.review_queue(None, None).items[0].reports.len(), 3);
+        assert_eq!(store.log_page(None, None).entries[0].action, ModerationAction::AutoHide);
+    }
+
+    #[test]
+    fn test_max_pending_reports_per_user() {
+        let mut store = store();
+        let max_pending = store.settings().config.max_pending_reports_per_user as u64;
+        for content_id in 0..max_pending {
+            report(&mut store, content_id, 2).unwrap();
+        }
+        assert!(report(&mut store, max_pending, 2).is_err());
+        report(&mut store, max_pending, 3).unwrap();
+
+        // reviewed reports no longer count against the reporter
+        store.review(0, ReviewDecision::Dismiss, user(9), None, 1).unwrap();
+        report(&mut store, max_pending, 2).unwrap();
+        assert_eq!(store.review_queue(None, None).items.len() as u64, max_pending);
+    }
+
+    #[test]
+    fn test_review_and_appeal() {
+        let mut store = store();
+        report(&mut store, 7, 2).unwrap();
+        store.review(7, ReviewDecision::Hide, user(9), Some("spam".to_string()), 1).unwrap();
+        assert!(store.is_hidden(7));
+        assert!(store.review_queue(None, None).items.is_empty());
+
+        assert!(store.appeal(AppealTarget::Content(7), user(2), "not mine".to_string(), 2).is_err());
+        let appeal = store.appeal(AppealTarget::Content(7), user(1), "not spam".to_string(), 2).unwrap();
+        store.resolve_appeal(appeal.id, true, user(9), None, 3).unwrap();
+        assert!(!store.is_hidden(7));
+        assert!(store.resolve_appeal(appeal.id, true, user(9), None, 3).is_err());
+    }
+
+    #[test]
+    fn test_ban_appeal() {
+        let mut store = store();
+        assert!(store.appeal(AppealTarget::Ban, user(1), "why".to_string(), 0).is_err());
+        store.ban(user(1), 1, user(9), 0);
+        assert_eq!(store.ban_remaining_secs(&user(1), 0), Some(86_400));
+        let appeal = store.appeal(AppealTarget::Ban, user(1), "sorry".to_string(), 0).unwrap();
+        store.resolve_appeal(appeal.id, true, user(9), None, 1).unwrap();
+        assert_eq!(store.ban_remaining_secs(&user(1), 1), None);
+    }
+}
@@ -0,0 +1,213 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_stable_structures::storable::Bound;
+use ic_stable_structures::Storable;
+use std::borrow::Cow;
+
+pub const MAX_NOTE_LENGTH: usize = 500;
+pub const MAX_SNAPSHOT_LENGTH: usize = 1000;
+pub const MAX_WORDS_PER_LIST: usize = 500;
+pub const MAX_WORD_LENGTH: usize = 64;
+pub const MAX_PAGE_SIZE: usize = 100;
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+
+macro_rules! candid_storable {
+    ($t:ty) => {
+        impl Storable for $t {
+            fn to_bytes(&self) -> Cow<'_, [u8]> {
+                Cow::Owned(candid::encode_one(self).unwrap())
+            }
+
+            fn from_bytes(bytes: Cow<[u8]>) -> Self {
+                candid::decode_one(&bytes).unwrap()
+            }
+
+            const BOUND: Bound = Bound::Unbounded;
+        }
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize)]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    Scam,
+    Offensive,
+    OffTopic,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize)]
+pub enum ReportStatus {
+    Pending,
+    Upheld,    // Content was hidden or removed by a moderator
+    Dismissed, // Content was reviewed and kept
+}
+
+/// A user report of a message or comment
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct Report {
+    pub id: u64,
+    pub content_id: u64,
+    pub reporter: Principal,
+    pub reason: ReportReason,
+    pub note: Option<String>,
+    pub status: ReportStatus,
+    pub created_at: u64,
+}
+
+candid_storable!(Report);
+
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize)]
+pub enum ContentStatus {
+    Visible,
+    Hidden,  // Hidden from queries, automatically after too many reports or by a moderator
+    Removed, // Deleted by a moderator
+}
+
+/// Moderation state of a reported or moderated message or comment
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct ModeratedContent {
+    pub content_id: u64,
+    pub author: Principal,
+    pub snapshot: String, // Content at the time of the first report or removal
+    pub status: ContentStatus,
+    pub auto_hidden: bool,
+    pub report_ids: Vec<u64>,
+    pub pending_reports: u32,
+    pub reason: Option<String>, // Moderator note shown to the author when hidden or removed
+    pub reviewed_by: Option<Principal>,
+    pub updated_at: u64,
+}
+
+candid_storable!(ModeratedContent);
+
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct ReviewItem {
+    pub content: ModeratedContent,
+    pub reports: Vec<Report>,
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct ReviewQueuePage {
+    pub items: Vec<ReviewItem>,
+    pub next_cursor: Option<u64>, // Content ID to continue from
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize)]
+pub enum ReviewDecision {
+    Dismiss, // Keep the content and dismiss pending reports
+    Hide,
+    Remove,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize)]
+pub enum AppealTarget {
+    Content(u64),
+    Ban,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize)]
+pub enum AppealStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+/// A user's appeal against hidden or removed content or against a ban
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct Appeal {
+    pub id: u64,
+    pub target: AppealTarget,
+    pub appellant: Principal,
+    pub reason: String,
+    pub status: AppealStatus,
+    pub resolved_by: Option<Principal>,
+    pub resolution_note: Option<String>,
+    pub created_at: u64,
+    pub resolved_at: Option<u64>,
+}
+
+candid_storable!(Appeal);
+
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct AppealsPage {
+    pub appeals: Vec<Appeal>,
+    pub next_cursor: Option<u64>, // Appeal ID to continue from
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize)]
+pub enum ModerationAction {
+    AutoHide,
+    Hide,
+    Remove,
+    Dismiss,
+    Ban { days: u64 },
+    Unban,
+    AcceptAppeal { appeal_id: u64 },
+    RejectAppeal { appeal_id: u64 },
+    UpdateWordLists,
+    UpdateConfig,
+}
+
+/// Audit log entry of a moderator action
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct ModerationLogEntry {
+    pub id: u64,
+    pub moderator: Option<Principal>, // None for automatic actions
+    pub action: ModerationAction,
+    pub content_id: Option<u64>,
+    pub user: Option<Principal>,
+    pub note: Option<String>,
+    pub created_at: u64,
+}
+
+candid_storable!(ModerationLogEntry);
+
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct ModerationLogPage {
+    pub entries: Vec<ModerationLogEntry>,
+    pub next_cursor: Option<u64>, // Log ID to continue from, newest first
+}
+
+#[derive(Debug, Clone, PartialEq, CandidType, Deserialize)]
+pub struct ModerationConfig {
+    pub auto_hide_threshold: u32, // Pending reports before content is hidden, 0 disables auto-hide
+    pub max_pending_reports_per_user: u32,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            auto_hide_threshold: 3,
+            max_pending_reports_per_user: 20,
+        }
+    }
+}
+
+/// Word lists applied on top of the built-in profanity filter
+#[derive(Debug, Clone, PartialEq, Default, CandidType, Deserialize)]
+pub struct WordLists {
+    pub builtin_filter: bool,  // Censor profanity with rustrict
+    pub blocked: Vec<String>,  // Content with these words is rejected
+    pub censored: Vec<String>, // These words are replaced
+}
+
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct ModerationSettings {
+    pub config: ModerationConfig,
+    pub word_lists: WordLists,
+}
+
+impl Default for ModerationSettings {
+    fn default() -> Self {
+        Self {
+            config: ModerationConfig::default(),
+            word_lists: WordLists {
+                builtin_filter: true,
+                ..WordLists::default()
+            },
+        }
+    }
+}
+
+candid_storable!(ModerationSettings);
@@ -67,7 +67,13 @@ pub enum BetError {
     
     /// The market is in a state where betting is not allowed
     /// (e.g., Voided, Disputed, or Closed)
-    InvalidMarketStatus
+    InvalidMarketStatus,
+
+    /// The combo bet's legs are invalid (e.g., duplicate markets or mixed tokens)
+    InvalidCombo(String),
+
+    /// The reserve of an odds-locked market can't cover the payout locked by this bet
+    InsufficientReserve(String),
 }
 
 /// Represents a bet placed by a user on a prediction market
@@ -99,7 +105,12 @@ pub struct Bet {
     pub timestamp: Timestamp,
     
     /// All bets in a market use the same token type as specified in the market
-    pub token_id: TokenIdentifier
+    pub token_id: TokenIdentifier,
+
+    /// Pool odds of the chosen outcome right after the bet was placed (total pool / outcome pool)
+    /// Used by odds-locked payout models; absent for bets placed before it was recorded
+    #[serde(default)]
+    pub locked_odds: Option<f64>,
 }
 
 /// Implementation of the Storable trait for Bet
@@ -0,0 +1,528 @@
+//! # Combo Bets
+//!
+//! Combo bets combine positions on several markets into a single stake:
+//!
+//! - **Parlay**: every leg must win. The payout multiplier is the product of the legs'
+//!   implied odds (`1 / outcome_percentage`) at placement time.
+//! - **Conditional**: legs marked as conditions don't add to the odds. If a condition leg
+//!   loses, the combo is voided and the stake refunded, e.g. "Outcome B of market 2,
+//!   conditional on outcome A of market 1".
+//!
+//! Combo stakes are not added to the referenced market pools, so they don't change the
+//! parimutuel payouts of those markets. Odds are fixed at placement and paid out of the
+//! token's combo reserve (see `combo_reserve`), which caps the payout of each combo and the
+//! total exposure. The number of legs and the multiplier are capped as well, and every parlay
+//! leg's market pool must be at least the combo's potential payout, so moving the locked odds
+//! of a thin market costs more than the combo can win.
+//!
+//! A combo is settled only once every referenced market is resolved or voided. Pending combos
+//! are indexed by market, and settlement runs periodically alongside the expired-markets check
+//! for the markets that have resolved. It creates claims through the regular claims system:
+//! a winning claim for won combos and a refund claim for voided ones.
+
+use candid::{CandidType, Nat, Principal};
+use ic_cdk::{query, update};
+use ic_stable_structures::{storable::Bound, Storable};
+use icrc_ledger_types::icrc1::account::Account;
+use icrc_ledger_types::icrc2::transfer_from::{TransferFromArgs, TransferFromError};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+use super::bet::BetError;
+use super::combo_reserve::{check_combo_capacity, lock_combo_exposure, release_combo_exposure, ComboSettlement};
+use crate::canister::get_current_time;
+use crate::claims::claims_processing::{create_refund_claim, create_winning_claim};
+use crate::claims::claims_types::RefundReason;
+use crate::market::market::*;
+use crate::storage::{COMBO_BETS, COMBO_MARKET_INDEX, MARKETS};
+use crate::token::registry::{get_token_info, is_supported_token};
+use crate::transaction_recovery::record_failed_transaction;
+use crate::types::{MarketId, OutcomeIndex, Timestamp, TokenAmount, TokenIdentifier};
+
+/// Maximum number of legs in a combo bet
+pub const MAX_COMBO_LEGS: usize = 5;
+
+/// Maximum payout multiplier of a combo bet (before platform fees)
+pub const MAX_COMBO_MULTIPLIER: f64 = 100.0;
+
+/// A leg of a combo bet as submitted by the user
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ComboLegArgs {
+    /// Market referenced by this leg
+    pub market_id: MarketId,
+    /// Outcome that must win in that market
+    pub outcome_index: OutcomeIndex,
+    /// If true, the leg is a condition: losing it voids the combo instead of losing it
+    pub is_condition: bool,
+}
+
+/// A leg of a placed combo bet
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ComboLeg {
+    /// Market referenced by this leg
+    pub market_id: MarketId,
+    /// Outcome that must win in that market
+    pub outcome_index: OutcomeIndex,
+    /// Whether the leg is a condition (voids the combo if lost) rather than a parlay leg
+    pub is_condition: bool,
+    /// Implied probability of the outcome at placement time (its share of the market pool)
+    pub implied_probability: f64,
+}
+
+/// Settlement state of a combo bet
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ComboBetStatus {
+    /// Waiting for one or more referenced markets to resolve
+    Pending,
+    /// All legs won; a winning claim was created
+    Won { claim_id: u64 },
+    /// At least one parlay leg lost
+    Lost,
+    /// A referenced market was voided or a condition leg lost; the stake is refunded
+    /// (no claim if the stake did not cover the transfer fee)
+    Voided { claim_id: Option<u64> },
+}
+
+/// A combo (parlay or conditional) bet
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ComboBet {
+    /// Unique identifier of the combo bet
+    pub id: u64,
+    /// Principal ID of the user who placed the combo
+    pub user: Principal,
+    /// Legs of the combo
+    pub legs: Vec<ComboLeg>,
+    /// Amount staked on the combo
+    pub amount: TokenAmount,
+    /// Token used by the combo (shared by all referenced markets)
+    pub token_id: TokenIdentifier,
+    /// Payout multiplier fixed at placement time
+    pub multiplier: f64,
+    /// Payout if every leg wins, before platform fees
+    pub potential_payout: TokenAmount,
+    /// Time the combo was placed
+    pub timestamp: Timestamp,
+    /// Settlement state
+    pub status: ComboBetStatus,
+}
+
+impl Storable for ComboBet {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let mut buf = vec![];
+        ciborium::ser::into_writer(self, &mut buf).expect("Failed to serialize ComboBet");
+        Cow::Owned(buf)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        ciborium::de::from_reader(bytes.as_ref()).expect("Failed to deserialize ComboBet")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// Validates combo legs against the current market state and prices them
+///
+/// # Returns
+/// * `Result<(Vec<ComboLeg>, f64, TokenAmount), BetError>` - Priced legs, the combined multiplier
+///   and the potential payout of `amount`
+fn price_combo_legs(
+    legs: &[ComboLegArgs],
+    token_id: &TokenIdentifier,
+    amount: &TokenAmount,
+) -> Result<(Vec<ComboLeg>, f64, TokenAmount), BetError> {
+    let mut seen_markets = BTreeSet::new();
+    let mut priced_legs = Vec::with_capacity(legs.len());
+    let mut multiplier = 1.0;
+    let mut parlay_pools = Vec::new();
+
+    for leg in legs {
+        if !seen_markets.insert(leg.market_id.clone()) {
+            return Err(BetError::InvalidCombo(format!("Market {} is used in more than one leg", leg.market_id)));
+        }
+
+        let market = MARKETS
+            .with(|markets| markets.borrow().get(&leg.market_id))
+            .ok_or(BetError::MarketNotFound)?;
+
+        if market.status != MarketStatus::Active {
+            return Err(BetError::MarketNotActive);
+        }
+        if market.is_scalar() {
+            return Err(BetError::InvalidCombo(format!("Scalar market {} cannot be used in a combo", leg.market_id)));
+        }
+        if &market.token_id != token_id {
+            return Err(BetError::InvalidCombo("All combo legs must use the same token".to_string()));
+        }
+
+        let outcome_idx = leg.outcome_index.to_u64() as usize;
+        if outcome_idx >= market.outcomes.len() {
+            return Err(BetError::InvalidOutcome);
+        }
+
+        // Odds are only defined once the outcome has liquidity
+        let implied_probability = market.outcome_percentages[outcome_idx];
+        if implied_probability <= 0.0 {
+            return Err(BetError::InvalidCombo(format!(
+                "Outcome {} of market {} has no bets yet, so it has no implied odds",
+                leg.outcome_index, leg.market_id
+            )));
+        }
+
+        if !leg.is_condition {
+            multiplier *= 1.0 / implied_probability;
+            parlay_pools.push((leg.market_id.clone(), market.total_pool.clone()));
+        }
+
+        priced_legs.push(ComboLeg {
+            market_id: leg.market_id.clone(),
+            outcome_index: leg.outcome_index.clone(),
+            is_condition: leg.is_condition,
+            implied_probability,
+        });
+    }
+
+    let multiplier = multiplier.min(MAX_COMBO_MULTIPLIER);
+    let potential_payout = TokenAmount::from((amount.to_f64() * multiplier) as u64);
+
+    if let Some((market_id, _)) = parlay_pools.iter().find(|(_, pool)| *pool < potential_payout) {
+        return Err(BetError::InvalidCombo(format!(
+            "Market {} has too little liquidity for a combo paying {}",
+            market_id, potential_payout
+        )));
+    }
+
+    Ok((priced_legs, multiplier, potential_payout))
+}
+
+/// Places a combo (parlay or conditional) bet across several markets
+///
+/// Like `place_bet`, the stake is transferred with `icrc2_transfer_from`, so the user must
+/// first approve the prediction markets canister on the token ledger.
+///
+/// The combo's potential payout must fit in the token's combo reserve limits.
+///
+/// # Parameters
+/// * `legs` - Between 2 and `MAX_COMBO_LEGS` legs on distinct, active markets; at least one
+///   leg must not be a condition
+/// * `amount` - Amount of tokens to stake (raw token units including decimals)
+/// * `token_id` - Optional token identifier; if omitted, uses the token of the first leg's market
+///
+/// # Returns
+/// * `Result<u64, BetError>` - ID of the new combo bet, or the reason it was rejected
+#[update]
+pub async fn place_combo_bet(legs: Vec<ComboLegArgs>, amount: TokenAmount, token_id: Option<TokenIdentifier>) -> Result<u64, BetError> {
+    let user = ic_cdk::caller();
+
+    if legs.len() < 2 || legs.len() > MAX_COMBO_LEGS {
+        return Err(BetError::InvalidCombo(format!("A combo must have between 2 and {} legs", MAX_COMBO_LEGS)));
+    }
+    if legs.iter().all(|leg| leg.is_condition) {
+        return Err(BetError::InvalidCombo("A combo needs at least one leg that is not a condition".to_string()));
+    }
+
+    let token_id = match token_id {
+        Some(token_id) => token_id,
+        None => MARKETS
+            .with(|markets| markets.borrow().get(&legs[0].market_id))
+            .ok_or(BetError::MarketNotFound)?
+            .token_id,
+    };
+    if !is_supported_token(&token_id) {
+        return Err(BetError::TransferError(format!("Unsupported token: {}", token_id)));
+    }
+    let token_info = get_token_info(&token_id).ok_or_else(|| BetError::TransferError(format!("Token info not found for: {}", token_id)))?;
+    if amount <= token_info.transfer_fee {
+        return Err(BetError::InvalidCombo("Combo stake must exceed the token transfer fee".to_string()));
+    }
+
+    // Validate before taking the user's tokens
+    let (_, _, potential_payout) = price_combo_legs(&legs, &token_id, &amount)?;
+    check_combo_capacity(&token_id, &amount, &potential_payout)?;
+
+    let token_ledger = Principal::from_text(&token_id).map_err(|e| BetError::TransferError(format!("Invalid token ledger ID: {}", e)))?;
+    let args = TransferFromArgs {
+        spender_subaccount: None,
+        from: Account { owner: user, subaccount: None },
+        to: Account {
+            owner: ic_cdk::api::id(),
+            subaccount: None,
+        },
+        amount: amount.inner().clone(),
+        fee: None,
+        memo: None,
+        created_at_time: None,
+    };
+    match ic_cdk::call::<(TransferFromArgs,), (Result<Nat, TransferFromError>,)>(token_ledger, "icrc2_transfer_from", (args,)).await {
+        Ok((Ok(_block_index),)) => Ok(()),
+        Ok((Err(e),)) => Err(BetError::TransferError(format!(
+            "Transfer failed: {:?}. Make sure you have approved the prediction market canister to spend your tokens using icrc2_approve",
+            e
+        ))),
+        Err((code, msg)) => Err(BetError::TransferError(format!("Transfer failed: {} (code: {:?})", msg, code))),
+    }?;
+
+    // Re-price after the transfer since markets or the reserve may have moved in the meantime
+    let priced = price_combo_legs(&legs, &token_id, &amount)
+        .and_then(|priced| lock_combo_exposure(&token_id, &amount, &priced.2).map(|_| priced));
+    let (priced_legs, multiplier, potential_payout) = match priced {
+        Ok(priced) => priced,
+        Err(e) => {
+            ic_cdk::println!("Combo legs no longer valid after transfer ({:?}), refunding user {}", e, user);
+            if let Err(refund_error) = crate::token::transfer::transfer_token_fees_included(user, amount.clone(), &token_id).await {
+                record_failed_transaction(
+                    Some(legs[0].market_id.clone()),
+                    user,
+                    amount.clone(),
+                    token_id.clone(),
+                    refund_error.detailed_message(),
+                );
+            }
+            return Err(e);
+        }
+    };
+
+    let market_ids: Vec<u64> = priced_legs.iter().map(|leg| leg.market_id.to_u64()).collect();
+    let combo_id = COMBO_BETS.with(|combos| {
+        let mut combos = combos.borrow_mut();
+        let combo_id = combos.last_key_value().map_or(1, |(id, _)| id + 1);
+        combos.insert(
+            combo_id,
+            ComboBet {
+                id: combo_id,
+                user,
+                legs: priced_legs,
+                amount: amount.clone(),
+                token_id: token_id.clone(),
+                multiplier,
+                potential_payout: potential_payout.clone(),
+                timestamp: get_current_time(),
+                status: ComboBetStatus::Pending,
+            },
+        );
+        combo_id
+    });
+    COMBO_MARKET_INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        for market_id in market_ids {
+            index.insert((market_id, combo_id), ());
+        }
+    });
+
+    ic_cdk::println!(
+        "User {} placed combo bet {} of {} {} at {:.4}x (potential payout {})",
+        user,
+        combo_id,
+        amount,
+        token_info.symbol,
+        multiplier,
+        potential_payout
+    );
+
+    Ok(combo_id)
+}
+
+/// Outcome of a combo leg given the current state of its market
+enum LegState {
+    Pending,
+    Won,
+    Lost,
+    Voided,
+}
+
+fn leg_state(leg: &ComboLeg) -> LegState {
+    match MARKETS.with(|markets| markets.borrow().get(&leg.market_id)).map(|market| market.status) {
+        Some(MarketStatus::Closed(winners)) => {
+            if winners.iter().any(|winner| *winner == *leg.outcome_index.inner()) {
+                LegState::Won
+            } else {
+                LegState::Lost
+            }
+        }
+        Some(MarketStatus::Voided) | None => LegState::Voided,
+        Some(_) => LegState::Pending,
+    }
+}
+
+fn is_market_decided(market_id: u64) -> bool {
+    let status = MARKETS.with(|markets| markets.borrow().get(&MarketId::from(market_id))).map(|market| market.status);
+    !matches!(
+        status,
+        Some(MarketStatus::Active | MarketStatus::PendingActivation | MarketStatus::ExpiredUnresolved | MarketStatus::Disputed)
+    )
+}
+
+/// Indexes every pending combo bet by its markets
+///
+/// Called after upgrades so combos placed before the index existed are settled too.
+pub fn index_pending_combos() {
+    if !COMBO_MARKET_INDEX.with(|index| index.borrow().is_empty()) {
+        return;
+    }
+    COMBO_BETS.with(|combos| {
+        COMBO_MARKET_INDEX.with(|index| {
+            let mut index = index.borrow_mut();
+            for (combo_id, combo) in combos.borrow().iter() {
+                if combo.status == ComboBetStatus::Pending {
+                    for leg in &combo.legs {
+                        index.insert((leg.market_id.to_u64(), combo_id), ());
+                    }
+                }
+            }
+        })
+    });
+}
+
+/// Settles the pending combo bets of every market that resolved since the last run
+///
+/// Called periodically alongside the expired-markets check. Only markets with pending combos
+/// are visited, and each (market, combo) index entry is dropped once its market is decided,
+/// so a combo is settled when its last market resolves.
+///
+/// # Returns
+/// * `u64` - Number of combo bets settled
+pub fn settle_combo_bets() -> u64 {
+    let mut settled = 0;
+    let mut next_market = Some(0);
+
+    while let Some(from) = next_market {
+        let market_id = COMBO_MARKET_INDEX.with(|index| index.borrow().range((from, 0)..).next().map(|((market_id, _), _)| market_id));
+        let Some(market_id) = market_id else {
+            break;
+        };
+        next_market = market_id.checked_add(1);
+        if !is_market_decided(market_id) {
+            continue;
+        }
+
+        let combo_ids: Vec<u64> = COMBO_MARKET_INDEX.with(|index| {
+            index
+                .borrow()
+                .range((market_id, 0)..=(market_id, u64::MAX))
+                .map(|((_, combo_id), _)| combo_id)
+                .collect()
+        });
+        for combo_id in combo_ids {
+            COMBO_MARKET_INDEX.with(|index| index.borrow_mut().remove(&(market_id, combo_id)));
+            match settle_combo(combo_id) {
+                Some(true) => settled += 1,
+                Some(false) => {}
+                // Token no longer supported; keep the entry so a later run retries it
+                None => {
+                    COMBO_MARKET_INDEX.with(|index| index.borrow_mut().insert((market_id, combo_id), ()));
+                }
+            }
+        }
+    }
+
+    settled
+}
+
+/// Settles a combo bet if every referenced market is decided
+///
+/// # Returns
+/// * `Option<bool>` - Whether the combo was settled, or `None` if its winnings couldn't be paid yet
+fn settle_combo(combo_id: u64) -> Option<bool> {
+    let Some(mut combo) = COMBO_BETS.with(|combos| combos.borrow().get(&combo_id)) else {
+        return Some(false);
+    };
+    if combo.status != ComboBetStatus::Pending {
+        return Some(false);
+    }
+
+    let states: Vec<LegState> = combo.legs.iter().map(leg_state).collect();
+    if states.iter().any(|state| matches!(state, LegState::Pending)) {
+        return Some(false);
+    }
+
+    let voided_leg = combo
+        .legs
+        .iter()
+        .zip(&states)
+        .find(|(leg, state)| matches!(state, LegState::Voided) || (leg.is_condition && matches!(state, LegState::Lost)));
+    let lost = combo.legs.iter().zip(&states).any(|(leg, state)| !leg.is_condition && matches!(state, LegState::Lost));
+
+    combo.status = if let Some((leg, _)) = voided_leg {
+        ComboBetStatus::Voided {
+            claim_id: refund_combo(&combo, &leg.market_id),
+        }
+    } else if lost {
+        release_combo_exposure(&combo.token_id, &combo.amount, &combo.potential_payout, ComboSettlement::Lost);
+        ComboBetStatus::Lost
+    } else {
+        ComboBetStatus::Won {
+            claim_id: pay_combo(&combo)?,
+        }
+    };
+
+    ic_cdk::println!("Combo bet {} settled as {:?}", combo.id, combo.status);
+    COMBO_BETS.with(|combos| {
+        combos.borrow_mut().insert(combo.id, combo);
+    });
+    Some(true)
+}
+
+/// Creates a refund claim for a voided combo, unless the stake doesn't cover the transfer fee
+fn refund_combo(combo: &ComboBet, voided_market: &MarketId) -> Option<u64> {
+    let refundable = get_token_info(&combo.token_id).is_some_and(|token_info| combo.amount > token_info.transfer_fee);
+    // A stake too small to refund stays with the reserve
+    let settlement = if refundable { ComboSettlement::Voided } else { ComboSettlement::Lost };
+    release_combo_exposure(&combo.token_id, &combo.amount, &combo.potential_payout, settlement);
+    if !refundable {
+        return None;
+    }
+    Some(create_refund_claim(
+        combo.user,
+        voided_market.clone(),
+        combo.amount.clone(),
+        RefundReason::Other(format!("Combo bet {} voided", combo.id)),
+        combo.amount.clone(),
+        combo.token_id.clone(),
+    ))
+}
+
+/// Creates the winning claim for a combo, charging the platform fee on the profit
+fn pay_combo(combo: &ComboBet) -> Option<u64> {
+    let token_info = get_token_info(&combo.token_id)?;
+
+    let profit = combo.potential_payout.to_f64() - combo.amount.to_f64();
+    let platform_fee = (profit.max(0.0) * token_info.fee_percentage as f64 / 10000.0) as u64;
+    let winnings = combo.potential_payout.clone() - TokenAmount::from(platform_fee);
+    let settlement = ComboSettlement::Won { winnings: winnings.clone() };
+    release_combo_exposure(&combo.token_id, &combo.amount, &combo.potential_payout, settlement);
+
+    // Claims are tracked per market, so the combo is filed under its first parlay leg
+    let leg = combo.legs.iter().find(|leg| !leg.is_condition).unwrap_or(&combo.legs[0]);
+
+    Some(create_winning_claim(
+        combo.user,
+        leg.market_id.clone(),
+        combo.amount.clone(),
+        combo.legs.iter().filter(|leg| !leg.is_condition).map(|leg| leg.outcome_index.clone()).collect(),
+        winnings,
+        Some(TokenAmount::from(platform_fee)),
+        combo.token_id.clone(),
+        get_current_time(),
+    ))
+}
+
+/// Returns a combo bet by ID
+#[query]
+pub fn get_combo_bet(combo_id: u64) -> Option<ComboBet> {
+    COMBO_BETS.with(|combos| combos.borrow().get(&combo_id))
+}
+
+/// Returns all combo bets placed by a user, most recent first
+#[query]
+pub fn get_user_combo_bets(user: Principal) -> Vec<ComboBet> {
+    // Combo IDs increase with placement time, so reverse key order is most recent first
+    COMBO_BETS.with(|combos| {
+        combos
+            .borrow()
+            .iter()
+            .rev()
+            .filter(|(_, combo)| combo.user == user)
+            .map(|(_, combo)| combo)
+            .collect()
+    })
+}
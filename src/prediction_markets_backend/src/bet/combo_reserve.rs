@@ -0,0 +1,226 @@
+//! # Combo Reserve
+//!
+//! Combo bets pay fixed odds, so their winnings can't come out of a parimutuel pool. They are
+//! paid from a house reserve that admins fund per token, never from the stakes escrowed in
+//! market pools.
+//!
+//! Placing a combo locks its exposure (potential payout minus stake) in the reserve. A combo is
+//! rejected if its potential payout exceeds `max_combo_payout`, or if its exposure would push the
+//! total exposure above `max_total_exposure` or above the reserve balance. Both limits default to
+//! zero, so combos on a token are disabled until an admin funds the reserve and sets the limits.
+//!
+//! On settlement the exposure is released: stakes of lost combos go to the reserve, winnings of
+//! won combos (net of the stake) are taken from it, and voided combos are refunded.
+
+use candid::{CandidType, Nat, Principal};
+use ic_cdk::{query, update};
+use ic_stable_structures::{storable::Bound, Storable};
+use icrc_ledger_types::icrc1::account::Account;
+use icrc_ledger_types::icrc2::transfer_from::{TransferFromArgs, TransferFromError};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+use super::bet::BetError;
+use crate::controllers::admin::is_admin;
+use crate::storage::COMBO_RESERVES;
+use crate::token::registry::is_supported_token;
+use crate::token::transfer::transfer_token_fees_included;
+use crate::types::{TokenAmount, TokenIdentifier};
+
+/// House reserve backing the combo bets of one token
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ComboReserve {
+    /// Funds available to pay combo winnings, including the stakes of lost combos
+    pub balance: TokenAmount,
+    /// Winnings locked by pending combos (potential payout minus stake)
+    pub exposure: TokenAmount,
+    /// Stakes of pending combos
+    pub pending_stakes: TokenAmount,
+    /// Maximum potential payout of a single combo
+    pub max_combo_payout: TokenAmount,
+    /// Maximum total exposure of all pending combos
+    pub max_total_exposure: TokenAmount,
+}
+
+impl Storable for ComboReserve {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let mut buf = vec![];
+        ciborium::ser::into_writer(self, &mut buf).expect("Failed to serialize ComboReserve");
+        Cow::Owned(buf)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        ciborium::de::from_reader(bytes.as_ref()).expect("Failed to deserialize ComboReserve")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// How a combo was settled, as far as the reserve is concerned
+pub enum ComboSettlement {
+    /// The user is paid `winnings`, which include the stake
+    Won { winnings: TokenAmount },
+    /// The stake goes to the reserve
+    Lost,
+    /// The stake is refunded
+    Voided,
+}
+
+fn get_reserve(token_id: &TokenIdentifier) -> ComboReserve {
+    COMBO_RESERVES.with(|reserves| reserves.borrow().get(token_id)).unwrap_or_default()
+}
+
+fn check_capacity(reserve: &ComboReserve, stake: &TokenAmount, potential_payout: &TokenAmount) -> Result<(), BetError> {
+    if *potential_payout > reserve.max_combo_payout {
+        return Err(BetError::InvalidCombo(format!(
+            "Potential payout {} exceeds the maximum combo payout of {}",
+            potential_payout, reserve.max_combo_payout
+        )));
+    }
+    let exposure = reserve.exposure.clone() + (potential_payout.clone() - stake.clone());
+    if exposure > reserve.max_total_exposure || exposure > reserve.balance {
+        return Err(BetError::InvalidCombo(
+            "The combo reserve can't cover this combo right now".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the reserve can cover a combo without locking anything
+pub fn check_combo_capacity(token_id: &TokenIdentifier, stake: &TokenAmount, potential_payout: &TokenAmount) -> Result<(), BetError> {
+    check_capacity(&get_reserve(token_id), stake, potential_payout)
+}
+
+/// Locks the exposure of a new combo in the reserve
+pub fn lock_combo_exposure(token_id: &TokenIdentifier, stake: &TokenAmount, potential_payout: &TokenAmount) -> Result<(), BetError> {
+    let mut reserve = get_reserve(token_id);
+    check_capacity(&reserve, stake, potential_payout)?;
+    reserve.exposure += potential_payout.clone() - stake.clone();
+    reserve.pending_stakes += stake.clone();
+    COMBO_RESERVES.with(|reserves| reserves.borrow_mut().insert(token_id.clone(), reserve));
+    Ok(())
+}
+
+/// Releases the exposure of a settled combo and moves its stake and winnings through the reserve
+pub fn release_combo_exposure(
+    token_id: &TokenIdentifier,
+    stake: &TokenAmount,
+    potential_payout: &TokenAmount,
+    settlement: ComboSettlement,
+) {
+    let mut reserve = get_reserve(token_id);
+    reserve.exposure = reserve.exposure - (potential_payout.clone() - stake.clone());
+    reserve.pending_stakes = reserve.pending_stakes - stake.clone();
+    match settlement {
+        ComboSettlement::Won { winnings } => {
+            // Anything the user isn't paid, i.e. the platform fee on the profit, stays in the reserve
+            reserve.balance = reserve.balance + stake.clone() - winnings;
+        }
+        ComboSettlement::Lost => reserve.balance += stake.clone(),
+        ComboSettlement::Voided => {}
+    }
+    COMBO_RESERVES.with(|reserves| reserves.borrow_mut().insert(token_id.clone(), reserve));
+}
+
+/// Total held for combos of a token: the reserve balance plus the stakes of pending combos
+pub fn combo_reserve_holdings(token_id: &TokenIdentifier) -> (TokenAmount, TokenAmount) {
+    let reserve = get_reserve(token_id);
+    (reserve.balance, reserve.pending_stakes)
+}
+
+/// Returns the combo reserve of a token
+#[query]
+pub fn get_combo_reserve(token_id: TokenIdentifier) -> ComboReserve {
+    get_reserve(&token_id)
+}
+
+/// Adds funds to the combo reserve of a token
+///
+/// The admin must first approve the prediction markets canister on the token ledger, as the
+/// funds are transferred with `icrc2_transfer_from`.
+#[update]
+pub async fn fund_combo_reserve(token_id: TokenIdentifier, amount: TokenAmount) -> Result<ComboReserve, String> {
+    let admin = ic_cdk::caller();
+    if !is_admin(admin) {
+        return Err("Unauthorized: Only admins can fund the combo reserve".to_string());
+    }
+    if !is_supported_token(&token_id) {
+        return Err(format!("Unsupported token: {}", token_id));
+    }
+
+    let token_ledger = Principal::from_text(&token_id).map_err(|e| format!("Invalid token ledger ID: {}", e))?;
+    let args = TransferFromArgs {
+        spender_subaccount: None,
+        from: Account {
+            owner: admin,
+            subaccount: None,
+        },
+        to: Account {
+            owner: ic_cdk::api::id(),
+            subaccount: None,
+        },
+        amount: amount.inner().clone(),
+        fee: None,
+        memo: None,
+        created_at_time: None,
+    };
+    match ic_cdk::call::<(TransferFromArgs,), (Result<Nat, TransferFromError>,)>(token_ledger, "icrc2_transfer_from", (args,)).await {
+        Ok((Ok(_block_index),)) => Ok(()),
+        Ok((Err(e),)) => Err(format!("Transfer failed: {:?}", e)),
+        Err((code, msg)) => Err(format!("Transfer failed: {} (code: {:?})", msg, code)),
+    }?;
+
+    let mut reserve = get_reserve(&token_id);
+    reserve.balance += amount;
+    COMBO_RESERVES.with(|reserves| reserves.borrow_mut().insert(token_id.clone(), reserve.clone()));
+    Ok(reserve)
+}
+
+/// Withdraws funds from the combo reserve of a token to the calling admin
+///
+/// Only the part of the balance that isn't locked by pending combos can be withdrawn.
+#[update]
+pub async fn withdraw_combo_reserve(token_id: TokenIdentifier, amount: TokenAmount) -> Result<ComboReserve, String> {
+    let admin = ic_cdk::caller();
+    if !is_admin(admin) {
+        return Err("Unauthorized: Only admins can withdraw from the combo reserve".to_string());
+    }
+
+    let mut reserve = get_reserve(&token_id);
+    let available = reserve.balance.clone() - reserve.exposure.clone();
+    if amount > available {
+        return Err(format!("Only {} of the combo reserve is not locked by pending combos", available));
+    }
+    // Deduct before transferring so concurrent calls can't withdraw the same funds
+    reserve.balance = reserve.balance - amount.clone();
+    COMBO_RESERVES.with(|reserves| reserves.borrow_mut().insert(token_id.clone(), reserve));
+
+    if let Err(e) = transfer_token_fees_included(admin, amount.clone(), &token_id).await {
+        let mut reserve = get_reserve(&token_id);
+        reserve.balance += amount;
+        COMBO_RESERVES.with(|reserves| reserves.borrow_mut().insert(token_id.clone(), reserve));
+        return Err(e.detailed_message());
+    }
+    Ok(get_reserve(&token_id))
+}
+
+/// Sets the per-combo payout cap and the total exposure cap of a token's combo reserve
+#[update]
+pub fn set_combo_limits(
+    token_id: TokenIdentifier,
+    max_combo_payout: TokenAmount,
+    max_total_exposure: TokenAmount,
+) -> Result<ComboReserve, String> {
+    if !is_admin(ic_cdk::caller()) {
+        return Err("Unauthorized: Only admins can set combo limits".to_string());
+    }
+    if !is_supported_token(&token_id) {
+        return Err(format!("Unsupported token: {}", token_id));
+    }
+
+    let mut reserve = get_reserve(&token_id);
+    reserve.max_combo_payout = max_combo_payout;
+    reserve.max_total_exposure = max_total_exposure;
+    COMBO_RESERVES.with(|reserves| reserves.borrow_mut().insert(token_id, reserve.clone()));
+    Ok(reserve)
+}
@@ -1,5 +1,7 @@
 #[allow(clippy::module_inception)]
 pub mod bet;
+pub mod combo_bet;
+pub mod combo_reserve;
 pub mod get_market_bets;
 pub mod place_bet;
 pub mod latest_bets;
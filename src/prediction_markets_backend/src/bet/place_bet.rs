@@ -24,6 +24,7 @@ use super::bet::*;
 
 use crate::controllers::admin::is_admin;
 use crate::market::market::*;
+use crate::market::payout_model::check_odds_locked_bet;
 use crate::nat::StorableNat;
 use crate::stable_memory::*;
 use crate::storage::BETS;
@@ -158,6 +159,12 @@ async fn place_bet(
         MarketStatus::Voided => return Err(BetError::InvalidMarketStatus),
     }
 
+    // Odds-locked payouts are backed by the market's reserve, reject bets it can't cover
+    // before taking the user's tokens
+    if market.effective_payout_model().is_odds_locked() {
+        check_odds_locked_bet(&market, &outcome_index, &amount).map_err(BetError::InsufficientReserve)?;
+    }
+
     // Transfer tokens from user to the canister using icrc2_transfer_from
     // Create the transfer_from arguments
     let args = TransferFromArgs {
@@ -251,6 +258,14 @@ async fn place_bet(
 
     let bet_amount = amount.clone() - fee_amount.clone();
 
+    // Re-check the reserve against the latest pools, other bets may have landed during the transfer
+    if market.effective_payout_model().is_odds_locked() {
+        if let Err(e) = check_odds_locked_bet(&market, &outcome_index, &bet_amount) {
+            do_refund().await;
+            return Err(BetError::InsufficientReserve(e));
+        }
+    }
+
     // Update fee balance
     FEE_BALANCE.with(|fees| {
         let mut fees = fees.borrow_mut();
@@ -264,6 +279,9 @@ async fn place_bet(
     market.total_pool += bet_amount.clone();
     market.outcome_pools[outcome_idx] = market.outcome_pools[outcome_idx].clone() + bet_amount.clone();
 
+    // Odds of the chosen outcome after this bet, used by odds-locked payout models
+    let locked_odds = market.total_pool.to_f64() / market.outcome_pools[outcome_idx].to_f64();
+
     // Recalculate outcome percentages after adding the new bet
     // Formula: outcome_percentage[i] = outcome_pool[i] / total_pool
     market.outcome_percentages = market
@@ -328,6 +346,7 @@ async fn place_bet(
             outcome_index,                                     // Selected outcome
             timestamp: StorableNat::from(ic_cdk::api::time()), // Current time for time-weighting
             token_id: token_id.clone(),                        // Token type used for the bet
+            locked_odds: Some(locked_odds),                    // Odds at bet time for odds-locked payouts
         };
 
         // Get the next bet index for this market
@@ -387,6 +387,7 @@ pub fn estimate_bet_return(
                         current_time,
                         platform_fee_percentage: Some(PLATFORM_FEE_PERCENTAGE),
                         estimated_platform_fee: Some(TokenAmount::from(0u64)),
+                        payout_model: None,
                     }
                 }
             }
@@ -404,6 +405,7 @@ pub fn estimate_bet_return(
                 current_time,
                 platform_fee_percentage: Some(PLATFORM_FEE_PERCENTAGE),
                 estimated_platform_fee: Some(TokenAmount::from(0u64)),
+                payout_model: None,
             }
         }
     })
@@ -501,7 +503,8 @@ pub fn add_supported_token(token_info: TokenInfo) -> Result<(), String> {
     if !crate::controllers::admin::is_admin(ic_cdk::caller()) {
         return Err("Unauthorized: caller is not an admin".to_string());
     }
-    
+
+    validate_creator_fee_share(&token_info)?;
     add_token(token_info);
     Ok(())
 }
@@ -516,11 +519,20 @@ pub fn update_token_config(token_id: String, token_info: TokenInfo) -> Result<()
     if token_id != token_info.id {
         return Err("Token ID mismatch".to_string());
     }
-    
+
+    validate_creator_fee_share(&token_info)?;
     update_token(token_info);
     Ok(())
 }
 
+/// Checks that a token's creator fee share is a valid basis point value
+fn validate_creator_fee_share(token_info: &TokenInfo) -> Result<(), String> {
+    match token_info.creator_fee_share {
+        Some(share) if share > 10000 => Err("Creator fee share cannot exceed 10000 basis points".to_string()),
+        _ => Ok(()),
+    }
+}
+
 #[query]
 pub fn get_market_payout_records(market_id: u64) -> Vec<BetPayoutRecord> {
     // Convert market_id to our type system
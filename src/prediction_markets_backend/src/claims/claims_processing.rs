@@ -272,6 +272,32 @@ pub fn create_winning_claim(
     create_claim(user, market_id, claim_type, winnings_amount, token_id, timestamp)
 }
 
+/// Creates a claim for a market creator's share of the platform fee
+pub fn create_creator_fee_claim(
+    creator: Principal,
+    market_id: MarketId,
+    platform_fee: TokenAmount,
+    amount: TokenAmount,
+    token_id: TokenIdentifier,
+) -> u64 {
+    let claim_type = ClaimType::CreatorFee { platform_fee };
+
+    create_claim(creator, market_id, claim_type, amount, token_id, get_current_time())
+}
+
+/// Creates a claim returning subsidy liquidity to a market sponsor
+pub fn create_subsidy_claim(
+    sponsor: Principal,
+    market_id: MarketId,
+    contributed: TokenAmount,
+    amount: TokenAmount,
+    token_id: TokenIdentifier,
+) -> u64 {
+    let claim_type = ClaimType::LiquiditySubsidy { contributed };
+
+    create_claim(sponsor, market_id, claim_type, amount, token_id, get_current_time())
+}
+
 /// Creates a claim for a recovered failed transaction
 pub fn create_recovery_claim(
     user: Principal,
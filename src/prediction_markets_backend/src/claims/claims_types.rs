@@ -63,6 +63,16 @@ pub enum ClaimType {
         /// Reason for the refund
         reason: RefundReason,
     },
+    /// Creator's share of the platform fee of a resolved user-created market
+    CreatorFee {
+        /// Total platform fee charged on the market
+        platform_fee: TokenAmount,
+    },
+    /// Subsidy liquidity returned to a sponsor at resolution (or refunded if voided)
+    LiquiditySubsidy {
+        /// Total amount the sponsor contributed across all outcome pools
+        contributed: TokenAmount,
+    },
     /// Other types of claims (e.g., promotions, rewards)
     Other {
         /// Description of the claim
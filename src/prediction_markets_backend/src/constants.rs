@@ -17,3 +17,14 @@ pub fn calculate_percentage(amount: &StorableNat, percentage: u64) -> StorableNa
     let result = (amount.to_u64() * percentage) / hundred;
     StorableNat::from(result)
 }
+
+/// Canister ID of kong_backend, used to read pool prices for market resolution
+#[cfg(feature = "prod")]
+pub const KONG_BACKEND_CANISTER_ID: &str = "2ipq2-uqaaa-aaaar-qailq-cai"; // Mainnet kong_backend
+#[cfg(feature = "staging")]
+pub const KONG_BACKEND_CANISTER_ID: &str = "l4lgk-raaaa-aaaar-qahpq-cai"; // Staging kong_backend
+#[cfg(not(any(feature = "prod", feature = "staging")))]
+pub const KONG_BACKEND_CANISTER_ID: &str = "2ipq2-uqaaa-aaaar-qailq-cai"; // Local kong_backend, deployed with the mainnet ID
+
+/// Default share of the platform fee paid to creators of user-created markets (in basis points of the fee)
+pub const DEFAULT_CREATOR_FEE_SHARE_BPS: u64 = 2000; // 20% of the platform fee
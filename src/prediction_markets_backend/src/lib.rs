@@ -11,6 +11,11 @@
 //! - **Dual Approval Resolution**: User-created markets require agreement between creator and admin
 //! - **Transaction Recovery**: Robust handling of failed transactions with retry mechanisms
 //! - **Multi-select Markets**: Support for markets with multiple winning outcomes
+//! - **Scalar Markets**: Range markets with long/short positions settled linearly on a numeric value
+//! - **Kong Price Resolution**: Crypto price markets resolved automatically from Kong pool prices
+//! - **Combo Bets**: Parlay and conditional bets spanning several markets at fixed implied odds
+//! - **Creator Rewards**: Market creators earn a share of the platform fee and sponsors can seed subsidy liquidity
+//! - **Payout Models**: Creators choose the incentive curve (flat, exponential, linear decay, tiers, odds locked at bet)
 //!
 //! ## Resolution Flows
 //!
@@ -28,11 +33,13 @@ use candid::Principal;
 use ic_cdk_macros::{init, post_upgrade, pre_upgrade};
 
 use crate::bet::bet::*;
+use crate::bet::combo_bet::{ComboBet, ComboLegArgs};
+use crate::bet::combo_reserve::ComboReserve;
 use crate::canister::*;
-use crate::category::market_category::*;
 use crate::delegation::*;
 use crate::market::get_market_by_status::GetMarketsByStatusArgs;
 use crate::market::get_market_by_status::GetMarketsByStatusResult;
+use crate::market::create_market::{CreateMarketArgs, CreateScalarMarketArgs};
 use crate::market::market::*;
 use crate::market::update_expired_markets::*;
 // Import and re-export featured markets functionality
@@ -48,6 +55,7 @@ use crate::market::get_stats::StatsResult;
 // Standard types
 use crate::failed_transaction::FailedTransaction;
 use crate::resolution::resolution::*;
+use crate::resolution::resolve_scalar_market::ScalarResolutionArgs;
 use crate::token::registry::TokenInfo;
 use crate::user::user::*;
 // Claims system types
@@ -131,6 +139,7 @@ fn pre_upgrade() {
 fn post_upgrade() {
     // Restore state after upgrade
     stable_memory::restore();
+    bet::combo_bet::index_pending_combos();
 
     // Other post-upgrade initializations as needed
     update_expired_markets();
@@ -8,22 +8,30 @@
 //!
 //! - **Multi-token Support**: Markets can be created with different token types (KONG, ICP, etc.)
 //! - **Time-weighted Distribution**: Optional exponential weighting model that rewards earlier bets
+//! - **Payout Models**: Creators can choose another incentive curve (flat, linear decay,
+//!   early-bird tiers or odds locked at bet), see `market::payout_model`
 //! - **Flexible End Times**: Markets can end after a duration or at a specific date
+//! - **Scalar Markets**: Range markets with long/short positions settled on a numeric value
+//! - **Kong Price Markets**: Binary markets resolved automatically from a Kong pool price
 //! - **Governance Controls**: Admin-created markets are immediately active, while user-created
 //!   markets require activation (and later dual approval for resolution)
 //!
 //! The module maintains a global atomic counter to ensure each market receives a unique ID,
 //! even across canister upgrades.
 
+use candid::{CandidType, Deserialize};
 use ic_cdk::update;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use super::market::*;
+use super::payout_model::{BetWeighting, PayoutModel};
+use super::scalar::{scalar_outcomes, ScalarMarketConfig};
 use crate::token::registry::KONG_LEDGER_ID_LOCAL;
 use crate::token::registry::{is_supported_token, TokenIdentifier};
 
 use crate::category::market_category::*;
 use crate::controllers::admin::*;
+use crate::resolution::kong_price::KongPoolPriceSource;
 use crate::resolution::resolution::*;
 use crate::storage::MARKETS;
 use crate::types::{MarketId, Timestamp, TokenAmount, NANOS_PER_SECOND};
@@ -35,6 +43,33 @@ use crate::types::{MarketId, Timestamp, TokenAmount, NANOS_PER_SECOND};
 /// highest market ID found in stable storage plus one.
 pub static MARKET_ID: AtomicU64 = AtomicU64::new(0);
 
+/// Arguments for creating a categorical market
+#[derive(CandidType, Deserialize)]
+pub struct CreateMarketArgs {
+    /// The main question or title of the prediction market
+    pub question: String,
+    /// Market category for organization and filtering
+    pub category: MarketCategory,
+    /// Detailed rules and conditions for market resolution
+    pub rules: String,
+    /// Possible outcomes users can bet on (2-10 allowed)
+    pub outcomes: Vec<String>,
+    /// Method for determining the winning outcome
+    pub resolution_method: ResolutionMethod,
+    /// When the market closes for betting (duration or specific date)
+    pub end_time: MarketEndTime,
+    /// Optional URL to an image representing the market
+    pub image_url: Option<String>,
+    /// Whether to use time-weighted distribution (default: true)
+    pub uses_time_weighting: Option<bool>,
+    /// Decay parameter for time-weighting (default: 0.1)
+    pub time_weight_alpha: Option<f64>,
+    /// Token type to use for this market (default: KONG)
+    pub token_id: Option<TokenIdentifier>,
+    /// Incentive curve for sharing the profit between winners (overrides the time weighting fields)
+    pub payout_model: Option<PayoutModel>,
+}
+
 /// Creates a new prediction market with specified parameters
 ///
 /// This function allows users or admins to create new prediction markets. The markets
@@ -81,16 +116,7 @@ pub static MARKET_ID: AtomicU64 = AtomicU64::new(0);
 /// - Platform fee percentages
 ///
 /// # Parameters
-/// * `question` - The main question or title of the prediction market
-/// * `category` - Market category for organization and filtering
-/// * `rules` - Detailed rules and conditions for market resolution
-/// * `outcomes` - Possible outcomes users can bet on (2-10 allowed)
-/// * `resolution_method` - Method for determining the winning outcome
-/// * `end_time_secs` - When the market closes for betting (duration or specific date)
-/// * `image_url` - Optional URL to an image representing the market
-/// * `uses_time_weighting` - Whether to use time-weighted distribution (default: true)
-/// * `time_weight_alpha` - Decay parameter for time-weighting (default: 0.1)
-/// * `token_id` - Token type to use for this market (default: KONG)
+/// * `args` - Market configuration (see `CreateMarketArgs`)
 ///
 /// # Returns
 /// * `Result<MarketId, String>` - On success, returns the ID of the new market.
@@ -112,18 +138,122 @@ pub static MARKET_ID: AtomicU64 = AtomicU64::new(0);
 ///    - Admin creators: Market starts as `Active`
 ///    - User creators: Market starts as `Pending` (requires activation bet)
 #[update]
-pub fn create_market(
-    question: String,
-    category: MarketCategory,
-    rules: String,
-    outcomes: Vec<String>,
-    resolution_method: ResolutionMethod,
-    end_time_secs: MarketEndTime,
-    image_url: Option<String>,
-    uses_time_weighting: Option<bool>,
-    time_weight_alpha: Option<f64>,
-    token_id: Option<TokenIdentifier>,
-) -> Result<MarketId, String> {
+pub fn create_market(args: CreateMarketArgs) -> Result<MarketId, String> {
+    // Outcome validation - must have between 2-10 possible outcomes
+    if args.outcomes.len() < 2 {
+        return Err("Market must have at least 2 outcomes".to_string());
+    }
+    if args.outcomes.len() > 10 {
+        return Err("Market cannot have more than 10 outcomes".to_string());
+    }
+
+    // Kong price markets are binary: outcome 0 if the price condition holds, outcome 1 otherwise
+    if let ResolutionMethod::KongPrice { source, threshold, .. } = &args.resolution_method {
+        if args.outcomes.len() != 2 {
+            return Err("Kong price markets must have exactly 2 outcomes".to_string());
+        }
+        if !threshold.is_finite() || *threshold <= 0.0 {
+            return Err("Kong price threshold must be a positive number".to_string());
+        }
+        source.validate()?;
+    }
+
+    insert_new_market(args, MarketType::Categorical)
+}
+
+/// Arguments for creating a scalar (range) market
+#[derive(CandidType, Deserialize)]
+pub struct CreateScalarMarketArgs {
+    /// The question being predicted (e.g. "What will the ICP price be on June 1st?")
+    pub question: String,
+    /// Market category for organization and filtering
+    pub category: MarketCategory,
+    /// Detailed rules describing how the resolved value is determined
+    pub rules: String,
+    /// Value at (or below) which short positions receive the whole pool
+    pub lower_bound: f64,
+    /// Value at (or above) which long positions receive the whole pool
+    pub upper_bound: f64,
+    /// Optional display unit for the bounds (e.g. "USD")
+    pub unit: Option<String>,
+    /// Optional Kong pool whose price is used as the resolved value
+    pub resolution_source: Option<KongPoolPriceSource>,
+    /// When the market closes for betting
+    pub end_time: MarketEndTime,
+    /// Optional URL to an image representing the market
+    pub image_url: Option<String>,
+    /// Whether to time-weight bonuses within the gaining side (default: true)
+    pub uses_time_weighting: Option<bool>,
+    /// Decay parameter for time-weighting (default: 0.1)
+    pub time_weight_alpha: Option<f64>,
+    /// Token type to use for this market (default: KONG)
+    pub token_id: Option<TokenIdentifier>,
+    /// Incentive curve for sharing each side's gain (overrides the time weighting fields)
+    pub payout_model: Option<PayoutModel>,
+}
+
+/// Creates a new scalar prediction market
+///
+/// Scalar markets predict a numeric value between `lower_bound` and `upper_bound`.
+/// Users bet on the `Long` (index 0) or `Short` (index 1) outcome, and at resolution the
+/// pool is split linearly on the resolved value (see `market::scalar`). Scalar markets are
+/// resolved by admins through `resolve_scalar_market`, either with an explicit value or with
+/// the price read from `resolution_source`.
+///
+/// # Parameters
+/// * `args` - Scalar market configuration (see `CreateScalarMarketArgs`)
+///
+/// # Returns
+/// * `Result<MarketId, String>` - The ID of the new market, or an error explaining why creation failed
+#[update]
+pub fn create_scalar_market(args: CreateScalarMarketArgs) -> Result<MarketId, String> {
+    let config = ScalarMarketConfig {
+        lower_bound: args.lower_bound,
+        upper_bound: args.upper_bound,
+        unit: args.unit,
+        resolution_source: args.resolution_source,
+        resolved_value: None,
+    };
+    config.validate()?;
+    // Scalar positions are settled on the resolved value, there are no odds to lock
+    if matches!(args.payout_model, Some(PayoutModel::OddsLocked)) {
+        return Err("Scalar markets can't use odds-locked payouts".to_string());
+    }
+
+    insert_new_market(
+        CreateMarketArgs {
+            question: args.question,
+            category: args.category,
+            rules: args.rules,
+            outcomes: scalar_outcomes(),
+            resolution_method: ResolutionMethod::Admin,
+            end_time: args.end_time,
+            image_url: args.image_url,
+            uses_time_weighting: args.uses_time_weighting,
+            time_weight_alpha: args.time_weight_alpha,
+            token_id: args.token_id,
+            payout_model: args.payout_model,
+        },
+        MarketType::Scalar(config),
+    )
+}
+
+/// Validates the common market parameters and stores the new market
+fn insert_new_market(args: CreateMarketArgs, market_type: MarketType) -> Result<MarketId, String> {
+    let CreateMarketArgs {
+        question,
+        category,
+        rules,
+        outcomes,
+        resolution_method,
+        end_time: end_time_secs,
+        image_url,
+        uses_time_weighting,
+        time_weight_alpha,
+        token_id,
+        payout_model,
+    } = args;
+
     // Validate market parameters
     // These checks ensure the market is properly configured and can be displayed
     // and resolved correctly in the frontend application
@@ -133,14 +263,6 @@ pub fn create_market(
         return Err("Question cannot be empty".to_string());
     }
 
-    // Outcome validation - must have between 2-10 possible outcomes
-    if outcomes.len() < 2 {
-        return Err("Market must have at least 2 outcomes".to_string());
-    }
-    if outcomes.len() > 10 {
-        return Err("Market cannot have more than 10 outcomes".to_string());
-    }
-
     // Token validation - ensure the market uses a supported token type
     // If no token is specified, default to KONG tokens
     let token_id = token_id.unwrap_or_else(|| KONG_LEDGER_ID_LOCAL.to_string());
@@ -179,18 +301,27 @@ pub fn create_market(
         return Err("End time must be at least 1 minute in the future".to_string());
     }
 
-    // Use time weighting by default
-    let uses_time_weighting = uses_time_weighting.unwrap_or(true);
+    // An explicit payout model replaces the time weighting flags, which are kept in sync
+    // for clients that only read them
+    let (uses_time_weighting, time_weight_alpha) = if let Some(model) = &payout_model {
+        model.validate()?;
+        (model.is_weighted(), model.alpha())
+    } else {
+        // Use time weighting by default
+        let uses_time_weighting = uses_time_weighting.unwrap_or(true);
 
-    if !uses_time_weighting {
-        return Err("Only time-weighted markets are supported".to_string());
-    }
+        if !uses_time_weighting {
+            return Err("Only time-weighted markets are supported".to_string());
+        }
 
-    if let Some(time_weight_alpha) = time_weight_alpha {
-        if !(time_weight_alpha > 0.0 && time_weight_alpha <= 1.0) {
-            return Err("time_weight_alpha should be in interval: (0, 1]".to_string());
+        if let Some(time_weight_alpha) = time_weight_alpha {
+            if !(time_weight_alpha > 0.0 && time_weight_alpha <= 1.0) {
+                return Err("time_weight_alpha should be in interval: (0, 1]".to_string());
+            }
         }
-    }
+
+        (uses_time_weighting, time_weight_alpha)
+    };
 
     // Create new market with unique ID
     let market_id = MARKETS.with(|m| {
@@ -255,6 +386,14 @@ pub fn create_market(
                 // Resolution proposal for dual-approval markets
                 // Initially None; populated when resolution voting begins
                 resolution_proposal: None,
+
+                // Categorical or scalar outcome space
+                market_type,
+                liquidity_subsidies: Vec::new(),
+
+                // Incentive curve for sharing the profit between winners
+                // None keeps the exponential model described by the fields above
+                payout_model,
             },
         );
         market_id
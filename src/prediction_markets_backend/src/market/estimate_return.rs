@@ -1,8 +1,12 @@
 use crate::market::market::*;
 use crate::market::estimate_return_types::*;
+use crate::market::scalar::{calculate_scalar_position_payout, calculate_scalar_settlement, ScalarMarketConfig, LONG_OUTCOME_INDEX};
 use crate::nat::StorableNat;
-use crate::utils::time_weighting::*;
-use crate::utils::fee_utils::{calculate_platform_fee, calculate_amount_after_fee};
+use crate::market::payout_model::{
+    calculate_bonus_share, check_odds_locked_bet, locked_payout, locked_payout_fee, relative_bet_time, BetWeighting, PayoutModel,
+};
+use crate::token::registry::get_token_info;
+use crate::utils::time_weighting::calculate_weighted_contribution;
 use crate::constants::PLATFORM_FEE_PERCENTAGE;
 use crate::types::{TokenAmount, OutcomeIndex, Timestamp};
 
@@ -23,6 +27,11 @@ pub fn estimate_bet_return(
         return Err("Invalid outcome index".to_string());
     }
 
+    // Scalar markets settle linearly on a value rather than win/lose
+    if let Some(config) = market.scalar_config() {
+        return Ok(estimate_scalar_bet_return(market, config, outcome_index, bet_amount, current_time));
+    }
+
     // Calculate potential return based on current market state
     let outcome_idx = outcome_index.to_u64() as usize;
     
@@ -34,89 +43,47 @@ pub fn estimate_bet_return(
     
     // Calculate new total pool after this bet
     let new_total_pool = market.total_pool.clone() + bet_amount.clone();
-    
-    // Calculate potential returns for different scenarios
+
+    // Settlement charges the token's fee (basis points) on the profit from losing bets,
+    // the rest of the profit is the bonus pool shared between the winners
+    let fee_bps = get_token_info(&market.token_id)
+        .map(|info| info.fee_percentage)
+        .unwrap_or(PLATFORM_FEE_PERCENTAGE * 100);
+    let payout_model = market.effective_payout_model();
+    if payout_model.is_odds_locked() {
+        return estimate_odds_locked_bet_return(market, outcome_index, bet_amount, current_time, fee_bps);
+    }
+    let total_profit = new_total_pool.to_f64() - new_outcome_pool.to_f64();
+    let platform_fee = total_profit * fee_bps as f64 / 10000.0;
+    let bonus_pool = total_profit - platform_fee;
+
+    // Weight this bet and the existing bets on the outcome the same way finalize_market does
+    let weight = payout_model.bet_weight(market, &current_time);
+    let this_bet_weighted_contribution = calculate_weighted_contribution(bet_amount.to_f64(), weight);
+    let total_weighted_contribution: f64 = crate::storage::get_bets_for_market(&market.id)
+        .into_iter()
+        .filter(|bet| bet.outcome_index == outcome_index)
+        .map(|bet| {
+            let bet_weight = payout_model.bet_weight(market, &bet.timestamp);
+            calculate_weighted_contribution(bet.amount.to_f64(), bet_weight)
+        })
+        .sum::<f64>()
+        + this_bet_weighted_contribution;
+
+    let bonus_share = calculate_bonus_share(this_bet_weighted_contribution, total_weighted_contribution, bonus_pool);
     
     // Scenario 1: This outcome wins (and is the only winner)
-    let mut winning_return = EstimatedReturnScenario {
+    // Total reward = original bet + share of the bonus pool (at most the whole bonus pool)
+    let winning_return = EstimatedReturnScenario {
         scenario: "This outcome wins".to_string(),
         probability: market.outcome_percentages[outcome_idx],
         min_return: bet_amount.clone(), // Guaranteed to get at least the bet amount back
-        expected_return: StorableNat::from(0u64),
-        max_return: StorableNat::from(0u64),
-        time_weighted: false,
-        time_weight: None,
+        expected_return: StorableNat::from((bet_amount.to_f64() + bonus_share) as u64),
+        max_return: StorableNat::from((bet_amount.to_f64() + bonus_pool) as u64),
+        time_weighted: payout_model.is_weighted(),
+        time_weight: if payout_model.is_weighted() { Some(weight) } else { None },
     };
     
-    // Calculate expected return if this outcome wins
-    // In standard markets: (bet_amount / outcome_pool) * total_pool
-    let standard_return_ratio = bet_amount.to_f64() / new_outcome_pool.to_f64();
-    let standard_expected_return = (new_total_pool.to_u64() as f64 * standard_return_ratio) as u64;
-    
-    // For time-weighted markets, calculate the time-weighted return
-    if market.uses_time_weighting {
-        winning_return.time_weighted = true;
-        
-        // Calculate time weight for this bet
-        let alpha = get_market_alpha(market);
-        let weight = calculate_time_weight(
-            market.created_at.clone(),
-            market.end_time.clone(),
-            current_time.clone(),
-            alpha
-        );
-        winning_return.time_weight = Some(weight);
-        
-        // Get all existing bets for this outcome using our helper function
-        let existing_bets = crate::storage::get_bets_for_market(&market.id)
-            .into_iter()
-            .filter(|bet| bet.outcome_index == outcome_index)
-            .collect::<Vec<_>>();
-        
-        // Calculate weighted contributions for existing bets
-        let mut total_weighted_contribution = 0.0;
-        for bet in &existing_bets {
-            let bet_weight = calculate_time_weight(
-                market.created_at.clone(),
-                market.end_time.clone(),
-                bet.timestamp.clone(),
-                alpha
-            );
-            total_weighted_contribution += calculate_weighted_contribution(
-                bet.amount.to_f64(),
-                bet_weight
-            );
-        }
-        
-        // Add this new bet's weighted contribution
-        let this_bet_weighted_contribution = calculate_weighted_contribution(
-            bet_amount.to_f64(),
-            weight
-        );
-        total_weighted_contribution += this_bet_weighted_contribution;
-        
-        // Calculate the share of the bonus pool
-        let total_outcome_bets = new_outcome_pool.to_u64() as f64;
-        let bonus_pool = new_total_pool.to_u64() as f64 - total_outcome_bets;
-        
-        let bonus_share = if total_weighted_contribution > 0.0 {
-            this_bet_weighted_contribution / total_weighted_contribution * bonus_pool
-        } else {
-            0.0
-        };
-        
-        // Total reward = original bet + share of bonus pool
-        let time_weighted_return = bet_amount.to_u64() as f64 + bonus_share;
-        
-        winning_return.expected_return = StorableNat::from(time_weighted_return as u64);
-        winning_return.min_return = bet_amount.clone(); // Guaranteed original bet back
-        winning_return.max_return = StorableNat::from((bet_amount.to_u64() as f64 + bonus_pool) as u64); // Max possible (if only bet)
-    } else {
-        // Standard return calculation
-        winning_return.expected_return = StorableNat::from(standard_expected_return);
-        winning_return.max_return = new_total_pool.clone();
-    }
-    
     // Scenario 2: This outcome loses
     let losing_return = EstimatedReturnScenario {
         scenario: "This outcome loses".to_string(),
@@ -128,13 +95,8 @@ pub fn estimate_bet_return(
         time_weight: None,
     };
     
-    // Apply platform fee to winning scenario expected return
-    let raw_expected_return = TokenAmount::from(winning_return.expected_return.clone());
-    let platform_fee = calculate_platform_fee(&raw_expected_return);
-    let expected_return_after_fee = calculate_amount_after_fee(&raw_expected_return);
-    
-    // Update winning scenario with fee-adjusted return
-    winning_return.expected_return = StorableNat::from(expected_return_after_fee.to_u64());
+    // The bet's share of the platform fee, proportional to its stake as in settlement
+    let estimated_platform_fee = TokenAmount::from((platform_fee * bet_amount.to_f64() / new_outcome_pool.to_f64()) as u64);
     
     // Create the final estimate, including platform fee information
     let estimate = EstimatedReturn {
@@ -144,26 +106,174 @@ pub fn estimate_bet_return(
         current_market_pool: market.total_pool.clone(),
         current_outcome_pool: current_outcome_pool.clone(),
         scenarios: vec![winning_return, losing_return],
-        uses_time_weighting: market.uses_time_weighting,
-        time_weight_alpha: market.time_weight_alpha,
+        uses_time_weighting: payout_model.is_weighted(),
+        time_weight_alpha: payout_model.alpha(),
         current_time: current_time.clone(),
-        platform_fee_percentage: Some(PLATFORM_FEE_PERCENTAGE),
-        estimated_platform_fee: Some(platform_fee),
+        platform_fee_percentage: Some(fee_bps / 100),
+        estimated_platform_fee: Some(estimated_platform_fee),
+        payout_model: Some(payout_model),
     };
     
     Ok(estimate)
 }
 
+/// Estimate the return of a bet in an odds-locked market
+///
+/// The bet is paid the odds implied by the pools after it, less the platform fee on its
+/// profit, so the winning scenario is fixed. Fails if the market's reserve can't cover the bet.
+fn estimate_odds_locked_bet_return(
+    market: &Market,
+    outcome_index: OutcomeIndex,
+    bet_amount: TokenAmount,
+    current_time: Timestamp,
+    fee_bps: u64,
+) -> Result<EstimatedReturn, String> {
+    let outcome_idx = outcome_index.to_u64() as usize;
+    let odds = check_odds_locked_bet(market, &outcome_index, &bet_amount)?;
+    let payout = locked_payout(bet_amount.to_f64(), Some(odds));
+    let platform_fee = locked_payout_fee(bet_amount.to_f64(), payout, fee_bps);
+    let winning_amount = StorableNat::from((payout - platform_fee) as u64);
+
+    let winning_return = EstimatedReturnScenario {
+        scenario: "This outcome wins".to_string(),
+        probability: market.outcome_percentages[outcome_idx],
+        min_return: winning_amount.clone(),
+        expected_return: winning_amount.clone(),
+        max_return: winning_amount,
+        time_weighted: false,
+        time_weight: None,
+    };
+    let losing_return = EstimatedReturnScenario {
+        scenario: "This outcome loses".to_string(),
+        probability: 1.0 - market.outcome_percentages[outcome_idx],
+        min_return: StorableNat::from(0u64),
+        expected_return: StorableNat::from(0u64),
+        max_return: StorableNat::from(0u64),
+        time_weighted: false,
+        time_weight: None,
+    };
+
+    Ok(EstimatedReturn {
+        market_id: market.id.clone(),
+        outcome_index: outcome_index.clone(),
+        bet_amount: bet_amount.clone(),
+        current_market_pool: market.total_pool.clone(),
+        current_outcome_pool: market.outcome_pools[outcome_idx].clone(),
+        scenarios: vec![winning_return, losing_return],
+        uses_time_weighting: false,
+        time_weight_alpha: None,
+        current_time,
+        platform_fee_percentage: Some(fee_bps / 100),
+        estimated_platform_fee: Some(TokenAmount::from(platform_fee as u64)),
+        payout_model: Some(PayoutModel::OddsLocked),
+    })
+}
+
+/// Estimate the return of a long or short position in a scalar market
+///
+/// Returns one scenario per reference value: the lower bound, the value implied by the
+/// pools after this bet, and the upper bound. The bound scenarios carry the pool-implied
+/// weight of that side as probability; the implied-value scenario is the market consensus
+/// and carries a probability of 1.0.
+fn estimate_scalar_bet_return(
+    market: &Market,
+    config: &ScalarMarketConfig,
+    outcome_index: OutcomeIndex,
+    bet_amount: TokenAmount,
+    current_time: Timestamp,
+) -> EstimatedReturn {
+    let outcome_idx = outcome_index.to_u64() as usize;
+    let is_long = outcome_index.to_u64() == LONG_OUTCOME_INDEX;
+    let current_outcome_pool = market.outcome_pools[outcome_idx].clone();
+
+    // Pools after this bet
+    let amount = bet_amount.to_f64();
+    let mut long_pool = market.outcome_pools[0].to_f64();
+    let mut short_pool = market.outcome_pools[1].to_f64();
+    if is_long {
+        long_pool += amount;
+    } else {
+        short_pool += amount;
+    }
+    let side_pool = if is_long { long_pool } else { short_pool };
+
+    // Weighted contribution of this bet and of its side, including this bet
+    let payout_model = market.effective_payout_model();
+    let weight = payout_model.bet_weight(market, &current_time);
+    let this_bet_weighted_contribution = calculate_weighted_contribution(amount, weight);
+    let mut side_weighted_contribution = this_bet_weighted_contribution;
+    for bet in crate::storage::get_bets_for_market(&market.id) {
+        if bet.outcome_index == outcome_index {
+            let bet_weight = payout_model.bet_weight(market, &bet.timestamp);
+            side_weighted_contribution += calculate_weighted_contribution(bet.amount.to_f64(), bet_weight);
+        }
+    }
+
+    let total_pool = long_pool + short_pool;
+    let long_share = if total_pool > 0.0 { long_pool / total_pool } else { 0.5 };
+    let implied_value = config.implied_value(long_pool, short_pool);
+
+    // Scalar settlement charges the token's fee (basis points) on the transferred amount
+    let fee_bps = get_token_info(&market.token_id)
+        .map(|info| info.fee_percentage)
+        .unwrap_or(PLATFORM_FEE_PERCENTAGE * 100);
+
+    let mut best_fee = 0.0;
+    let mut scenario_at = |label: String, value: f64, probability: f64| {
+        let settlement = calculate_scalar_settlement(config.long_fraction(value), long_pool, short_pool, fee_bps);
+        let side_allocation = if is_long { settlement.long_allocation } else { settlement.short_allocation };
+        let (payout, bonus) = calculate_scalar_position_payout(
+            amount,
+            this_bet_weighted_contribution,
+            side_pool,
+            side_weighted_contribution,
+            side_allocation,
+        );
+        if bonus > 0.0 && settlement.transferred > settlement.platform_fee {
+            let fee = settlement.platform_fee * bonus / (settlement.transferred - settlement.platform_fee);
+            if fee > best_fee {
+                best_fee = fee;
+            }
+        }
+        EstimatedReturnScenario {
+            scenario: label,
+            probability,
+            min_return: StorableNat::from(payout as u64),
+            expected_return: StorableNat::from(payout as u64),
+            max_return: StorableNat::from(payout as u64),
+            time_weighted: payout_model.is_weighted(),
+            time_weight: if payout_model.is_weighted() { Some(weight) } else { None },
+        }
+    };
+
+    let scenarios = vec![
+        scenario_at(format!("Resolves at lower bound ({})", config.lower_bound), config.lower_bound, 1.0 - long_share),
+        scenario_at(format!("Resolves at implied value ({:.4})", implied_value), implied_value, 1.0),
+        scenario_at(format!("Resolves at upper bound ({})", config.upper_bound), config.upper_bound, long_share),
+    ];
+
+    EstimatedReturn {
+        market_id: market.id.clone(),
+        outcome_index: outcome_index.clone(),
+        bet_amount: bet_amount.clone(),
+        current_market_pool: market.total_pool.clone(),
+        current_outcome_pool,
+        scenarios,
+        uses_time_weighting: payout_model.is_weighted(),
+        time_weight_alpha: payout_model.alpha(),
+        current_time,
+        platform_fee_percentage: Some(fee_bps / 100),
+        estimated_platform_fee: Some(TokenAmount::from(best_fee as u64)),
+        payout_model: Some(payout_model),
+    }
+}
+
 /// Generate data points for visualizing the time weight curve
 pub fn generate_time_weight_curve(
     market: &Market,
     points: usize
 ) -> Result<Vec<TimeWeightPoint>, String> {
-    if !market.uses_time_weighting {
-        return Err("Market does not use time weighting".to_string());
-    }
-    
-    let alpha = get_market_alpha(market);
+    let payout_model = time_based_payout_model(market)?;
     let market_created_at = market.created_at.to_u64();
     let market_end_time = market.end_time.to_u64();
     
@@ -176,36 +286,31 @@ pub fn generate_time_weight_curve(
     
     let mut curve_points = Vec::with_capacity(points + 1);
     
-    // Add the starting point (t=0, weight=1.0)
+    // Add the starting point (t=0)
     curve_points.push(TimeWeightPoint {
         relative_time: 0.0,
         absolute_time: Timestamp::from(market_created_at),
-        weight: 1.0,
+        weight: payout_model.weight_at(0.0),
     });
     
     // Generate points along the curve
     for i in 1..points {
-        let time = market_created_at + (i as u64 * interval);
-        let relative_time = (time - market_created_at) as f64 / market_duration as f64;
-        let weight = calculate_time_weight(
-            Timestamp::from(market_created_at),
-            Timestamp::from(market_end_time),
-            Timestamp::from(time),
-            alpha
-        );
+        let time = Timestamp::from(market_created_at + (i as u64 * interval));
+        let relative_time = relative_bet_time(market, &time);
+        let weight = payout_model.bet_weight(market, &time);
         
         curve_points.push(TimeWeightPoint {
             relative_time,
-            absolute_time: Timestamp::from(time),
+            absolute_time: time,
             weight,
         });
     }
     
-    // Add the ending point (t=1, weight=alpha)
+    // Add the ending point (t=1)
     curve_points.push(TimeWeightPoint {
         relative_time: 1.0,
         absolute_time: Timestamp::from(market_end_time),
-        weight: alpha,
+        weight: payout_model.weight_at(1.0),
     });
     
     Ok(curve_points)
@@ -217,11 +322,7 @@ pub fn simulate_future_weight(
     bet_time: Timestamp,
     future_time: Timestamp
 ) -> Result<f64, String> {
-    if !market.uses_time_weighting {
-        return Err("Market does not use time weighting".to_string());
-    }
-    
-    let alpha = get_market_alpha(market);
+    let payout_model = time_based_payout_model(market)?;
     let market_created_at = market.created_at.to_u64();
     let market_end_time = market.end_time.to_u64();
     
@@ -233,14 +334,14 @@ pub fn simulate_future_weight(
         return Err("Bet time must be within market duration".to_string());
     }
     
-    let weight = calculate_time_weight(
-        TokenAmount::from(market_created_at).into(),
-        TokenAmount::from(market_end_time).into(),
-        bet_time.clone(),
-        alpha
-    );
-    
-    Ok(weight)
+    Ok(payout_model.bet_weight(market, &bet_time))
 }
 
-
+/// Payout model of a market whose bet weights depend on the bet time
+fn time_based_payout_model(market: &Market) -> Result<PayoutModel, String> {
+    match market.effective_payout_model() {
+        PayoutModel::Flat => Err("Market does not use time weighting".to_string()),
+        PayoutModel::OddsLocked => Err("Market pays the odds locked at bet time, not time weighting".to_string()),
+        model => Ok(model),
+    }
+}
@@ -1,6 +1,7 @@
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
 
+use crate::market::payout_model::PayoutModel;
 use crate::types::{MarketId, TokenAmount, OutcomeIndex, Timestamp, TokenIdentifier};
 
 use ic_stable_structures::{storable::Bound, Storable};
@@ -40,6 +41,7 @@ pub struct EstimatedReturn {
     pub current_time: Timestamp,
     pub platform_fee_percentage: Option<u64>,
     pub estimated_platform_fee: Option<TokenAmount>,
+    pub payout_model: Option<PayoutModel>,
 }
 
 /// Record of a bet payout, including time-weighting details if applicable
@@ -0,0 +1,230 @@
+//! # Liquidity Subsidies
+//!
+//! Market creators and sponsors can seed a market with subsidy liquidity to make it more
+//! attractive to bettors. A subsidy adds the same amount to every outcome pool, so it
+//! deepens the market without moving its odds.
+//!
+//! Subsidies are not bets: they don't earn time-weighted bonuses or count as bets.
+//! At resolution:
+//! - subsidy sitting in losing outcome pools becomes part of the profit paid to winners
+//!   (this is the incentive the sponsor pays for);
+//! - subsidy sitting in winning outcome pools is returned to the sponsors pro-rata to
+//!   their contributions, together with the distributable profit if nobody bet on a
+//!   winning outcome.
+//!
+//! If the market is voided, every sponsor is refunded their full contribution.
+
+use candid::{CandidType, Nat, Principal};
+use ic_cdk::update;
+use icrc_ledger_types::icrc1::account::Account;
+use icrc_ledger_types::icrc2::transfer_from::{TransferFromArgs, TransferFromError};
+use serde::{Deserialize, Serialize};
+
+use super::market::*;
+use crate::bet::bet::BetError;
+use crate::canister::get_current_time;
+use crate::claims::claims_processing::create_subsidy_claim;
+use crate::storage::MARKETS;
+use crate::token::registry::{get_token_info, TokenInfo};
+use crate::transaction_recovery::record_failed_transaction;
+use crate::types::{MarketId, Timestamp, TokenAmount};
+
+/// Subsidy liquidity contributed by a sponsor to a market
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LiquiditySubsidy {
+    /// Principal who provided the subsidy (the creator or any sponsor)
+    pub sponsor: Principal,
+
+    /// Amount added to each outcome pool
+    pub amount_per_outcome: TokenAmount,
+
+    /// Time the subsidy was added
+    pub timestamp: Timestamp,
+}
+
+impl Market {
+    /// Total subsidy sitting in each outcome pool
+    pub fn subsidy_per_outcome(&self) -> TokenAmount {
+        self.liquidity_subsidies
+            .iter()
+            .map(|subsidy| subsidy.amount_per_outcome.clone())
+            .sum()
+    }
+
+    /// Recalculates outcome percentages from the current outcome pools
+    pub fn update_outcome_percentages(&mut self) {
+        let total_pool = self.total_pool.to_f64();
+        self.outcome_percentages = self
+            .outcome_pools
+            .iter()
+            .map(|pool| if total_pool > 0.0 { pool.to_f64() / total_pool } else { 0.0 })
+            .collect();
+    }
+}
+
+/// Seeds every outcome pool of a market with subsidy liquidity
+///
+/// The caller transfers `amount_per_outcome * outcome count` tokens with `icrc2_transfer_from`,
+/// so they must approve the prediction markets canister on the market's token ledger first.
+/// Subsidies don't activate pending markets; the creator's activation bet is still required.
+///
+/// # Parameters
+/// * `market_id` - ID of the market to seed
+/// * `amount_per_outcome` - Amount added to each outcome pool
+///
+/// # Returns
+/// * `Result<(), BetError>` - Success or the reason the subsidy was rejected
+#[update]
+pub async fn seed_market_liquidity(market_id: MarketId, amount_per_outcome: TokenAmount) -> Result<(), BetError> {
+    let sponsor = ic_cdk::caller();
+
+    let market = MARKETS
+        .with(|markets| markets.borrow().get(&market_id))
+        .ok_or(BetError::MarketNotFound)?;
+    validate_subsidy_target(&market)?;
+
+    let token_info = get_token_info(&market.token_id)
+        .ok_or_else(|| BetError::TransferError(format!("Token info not found for: {}", market.token_id)))?;
+    if amount_per_outcome <= token_info.transfer_fee {
+        return Err(BetError::TransferError("Subsidy per outcome must exceed the token transfer fee".to_string()));
+    }
+    let total_amount = amount_per_outcome.clone() * market.outcomes.len() as u64;
+
+    let token_ledger =
+        Principal::from_text(&market.token_id).map_err(|e| BetError::TransferError(format!("Invalid token ledger ID: {}", e)))?;
+    let args = TransferFromArgs {
+        spender_subaccount: None,
+        from: Account {
+            owner: sponsor,
+            subaccount: None,
+        },
+        to: Account {
+            owner: ic_cdk::api::id(),
+            subaccount: None,
+        },
+        amount: total_amount.inner().clone(),
+        fee: None,
+        memo: None,
+        created_at_time: None,
+    };
+    match ic_cdk::call::<(TransferFromArgs,), (Result<Nat, TransferFromError>,)>(token_ledger, "icrc2_transfer_from", (args,)).await {
+        Ok((Ok(_block_index),)) => Ok(()),
+        Ok((Err(e),)) => Err(BetError::TransferError(format!(
+            "Transfer failed: {:?}. Make sure you have approved the prediction market canister to spend your tokens using icrc2_approve",
+            e
+        ))),
+        Err((code, msg)) => Err(BetError::TransferError(format!("Transfer failed: {} (code: {:?})", msg, code))),
+    }?;
+
+    // Re-read the market after the transfer to get the latest state
+    let mut market = match MARKETS.with(|markets| markets.borrow().get(&market_id)) {
+        Some(market) if validate_subsidy_target(&market).is_ok() => market,
+        _ => {
+            ic_cdk::println!("Market {} can no longer be seeded, refunding sponsor {}", market_id, sponsor);
+            if let Err(e) = crate::token::transfer::transfer_token_fees_included(sponsor, total_amount.clone(), &market.token_id).await {
+                record_failed_transaction(
+                    Some(market_id.clone()),
+                    sponsor,
+                    total_amount,
+                    market.token_id.clone(),
+                    e.detailed_message(),
+                );
+            }
+            return Err(BetError::MarketNotActive);
+        }
+    };
+
+    for pool in market.outcome_pools.iter_mut() {
+        *pool = pool.clone() + amount_per_outcome.clone();
+    }
+    market.total_pool += total_amount;
+    market.update_outcome_percentages();
+    market.liquidity_subsidies.push(LiquiditySubsidy {
+        sponsor,
+        amount_per_outcome: amount_per_outcome.clone(),
+        timestamp: get_current_time(),
+    });
+
+    MARKETS.with(|markets| {
+        markets.borrow_mut().insert(market_id.clone(), market);
+    });
+
+    ic_cdk::println!(
+        "Sponsor {} seeded market {} with {} {} per outcome",
+        sponsor,
+        market_id,
+        amount_per_outcome,
+        token_info.symbol
+    );
+
+    Ok(())
+}
+
+/// Checks that a market can receive subsidy liquidity
+fn validate_subsidy_target(market: &Market) -> Result<(), BetError> {
+    if !matches!(market.status, MarketStatus::Active | MarketStatus::PendingActivation) {
+        return Err(BetError::InvalidMarketStatus);
+    }
+    // Scalar pools are long/short positions, so an equal seed would still be settled as bets
+    if market.is_scalar() {
+        return Err(BetError::InvalidMarketStatus);
+    }
+    Ok(())
+}
+
+/// Creates claims returning subsidy liquidity to sponsors after a market is resolved
+///
+/// # Parameters
+/// * `market` - The resolved market
+/// * `winning_outcome_count` - Number of winning outcomes (their pools hold the returnable subsidy)
+/// * `unclaimed_profit` - Profit that no winning bet can receive (no bets on the winners),
+///   returned to sponsors as well
+/// * `token_info` - Token of the market
+///
+/// # Returns
+/// * `TokenAmount` - Total amount returned to sponsors
+pub fn return_liquidity_subsidies(
+    market: &Market,
+    winning_outcome_count: u64,
+    unclaimed_profit: TokenAmount,
+    token_info: &TokenInfo,
+) -> TokenAmount {
+    let subsidy_per_outcome = market.subsidy_per_outcome();
+    if subsidy_per_outcome.is_zero() {
+        return TokenAmount::from(0u64);
+    }
+
+    let returnable = subsidy_per_outcome.clone() * winning_outcome_count + unclaimed_profit;
+    let mut returned = TokenAmount::from(0u64);
+
+    for subsidy in &market.liquidity_subsidies {
+        let share = subsidy.amount_per_outcome.to_f64() / subsidy_per_outcome.to_f64();
+        let amount = TokenAmount::from((returnable.to_f64() * share) as u64);
+        if amount <= token_info.transfer_fee {
+            continue;
+        }
+
+        let contributed = subsidy.amount_per_outcome.clone() * market.outcomes.len() as u64;
+        let claim_id = create_subsidy_claim(subsidy.sponsor, market.id.clone(), contributed, amount.clone(), market.token_id.clone());
+        ic_cdk::println!("Created subsidy return claim {} for sponsor {} with amount {}", claim_id, subsidy.sponsor, amount);
+        returned += amount;
+    }
+
+    returned
+}
+
+/// Creates claims refunding every sponsor's full subsidy when a market is voided
+pub fn refund_liquidity_subsidies(market: &Market) {
+    let Some(token_info) = get_token_info(&market.token_id) else {
+        return;
+    };
+
+    for subsidy in &market.liquidity_subsidies {
+        let contributed = subsidy.amount_per_outcome.clone() * market.outcomes.len() as u64;
+        if contributed <= token_info.transfer_fee {
+            continue;
+        }
+        let claim_id = create_subsidy_claim(subsidy.sponsor, market.id.clone(), contributed.clone(), contributed.clone(), market.token_id.clone());
+        ic_cdk::println!("Created subsidy refund claim {} for sponsor {} with amount {}", claim_id, subsidy.sponsor, contributed);
+    }
+}
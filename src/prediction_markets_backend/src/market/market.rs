@@ -8,8 +8,9 @@
 //! - Admin-created markets can be directly resolved by any admin
 //! - User-created markets require dual approval between the creator and an admin
 //!
-//! The system supports both standard payout distributions and time-weighted distributions,
-//! where earlier bettors receive higher rewards through an exponential weighting model.
+//! The system supports both standard payout distributions and weighted distributions,
+//! where the market's payout model (e.g. exponential time weighting) decides how the
+//! profit is shared between winners.
 
 use candid::{CandidType, Nat, Principal};
 use ic_stable_structures::{storable::Bound, Storable};
@@ -19,6 +20,9 @@ use std::borrow::Cow;
 use crate::types::{MarketId, Timestamp, TokenAmount, PoolAmount, BetCount, TokenIdentifier, OutcomeIndex};
 
 use crate::category::market_category::*;
+use crate::market::liquidity::LiquiditySubsidy;
+use crate::market::payout_model::PayoutModel;
+use crate::market::scalar::ScalarMarketConfig;
 use crate::resolution::resolution::*;
 
 /// Represents the current status of a market
@@ -53,6 +57,21 @@ pub enum MarketStatus {
     Voided,
 }
 
+/// Describes the outcome space of a market
+///
+/// Categorical markets choose one or more winners among the discrete `outcomes`.
+/// Scalar markets predict a numeric value within a range; their two outcomes are the
+/// long and short positions and payouts are computed linearly on the resolved value.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub enum MarketType {
+    /// Discrete outcomes, resolved by selecting winning outcome indices
+    #[default]
+    Categorical,
+
+    /// Numeric range with long/short positions, resolved with a value
+    Scalar(ScalarMarketConfig),
+}
+
 /// Specifies how the market end time is determined
 /// 
 /// Markets can be configured to end either after a specific duration from creation
@@ -152,7 +171,36 @@ pub struct Market {
     
     /// Active resolution proposal for this market (if any)
     /// Contains detailed voting information for dual-approval resolution
-    pub resolution_proposal: Option<ResolutionProposalInfo>
+    pub resolution_proposal: Option<ResolutionProposalInfo>,
+
+    /// Outcome space of the market (categorical or scalar)
+    /// Markets created before scalar support default to categorical
+    #[serde(default)]
+    pub market_type: MarketType,
+
+    /// Subsidy liquidity seeded into every outcome pool by the creator or sponsors
+    #[serde(default)]
+    pub liquidity_subsidies: Vec<LiquiditySubsidy>,
+
+    /// Incentive curve used to share the bonus pool between winners
+    /// Markets created before payout models existed derive it from the time weighting fields
+    #[serde(default)]
+    pub payout_model: Option<PayoutModel>,
+}
+
+impl Market {
+    /// Returns the scalar configuration if this is a scalar market
+    pub fn scalar_config(&self) -> Option<&ScalarMarketConfig> {
+        match &self.market_type {
+            MarketType::Scalar(config) => Some(config),
+            MarketType::Categorical => None,
+        }
+    }
+
+    /// Whether this market settles on a numeric value instead of outcome indices
+    pub fn is_scalar(&self) -> bool {
+        self.scalar_config().is_some()
+    }
 }
 
 impl Storable for Market {
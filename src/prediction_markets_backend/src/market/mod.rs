@@ -16,3 +16,6 @@ pub mod bet;
 pub mod get_stats;
 pub mod update_expired_markets;
 pub mod featured;
+pub mod scalar;
+pub mod liquidity;
+pub mod payout_model;
@@ -0,0 +1,412 @@
+//! # Payout Models
+//!
+//! A market's payout model decides how the profit from losing bets is shared between the
+//! winners. Every winner gets their stake back plus a share of the bonus pool proportional
+//! to their weighted contribution:
+//!
+//! ```text
+//! reward_i = bet_i + (bet_i * weight_i / Σ bet_j * weight_j) * bonus_pool
+//! ```
+//!
+//! Only the weight differs between these models, so settlement (`finalize_market`,
+//! `finalize_scalar_market`) and quotes (`estimate_return`) share the `BetWeighting` trait
+//! and `calculate_bonus_share`, which keeps quotes consistent with settlement. `OddsLocked`
+//! pays fixed amounts instead and shares `locked_payout` between settlement and quotes.
+//!
+//! Available models (`t` is the bet time relative to the market duration, in [0, 1]):
+//! - `Flat`: plain parimutuel, every bet has weight 1
+//! - `Exponential`: `w(t) = α^t` (the original time weighting)
+//! - `LinearDecay`: `w(t) = 1 - (1 - floor) * t`
+//! - `Step`: early-bird tiers with a fixed weight per time window
+//! - `OddsLocked`: odds locked at bet. A winning bet is paid its stake times the outcome's
+//!   pool odds right after it was placed, less the platform fee on its profit. The market's
+//!   subsidy liquidity is the reserve backing these payouts: a bet is only accepted while the
+//!   pool covers every locked payout of its outcome (`check_odds_locked_bet`), and what the
+//!   winners are not paid goes back to the sponsors. Categorical markets only
+//!
+//! Markets created before payout models existed keep their `uses_time_weighting` and
+//! `time_weight_alpha` settings (see `Market::effective_payout_model`).
+
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::market::market::Market;
+use crate::types::{OutcomeIndex, Timestamp, TokenAmount};
+use crate::utils::time_weighting::DEFAULT_ALPHA;
+
+/// Maximum number of tiers in a step payout model
+pub const MAX_PAYOUT_TIERS: usize = 10;
+
+/// A time window of a step payout model
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PayoutTier {
+    /// End of the window as a fraction of the market duration, in (0, 1]
+    pub until: f64,
+
+    /// Weight of bets placed within the window
+    pub weight: f64,
+}
+
+/// Incentive curve used to share the bonus pool between winners
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum PayoutModel {
+    /// Plain parimutuel: the bonus pool is split pro-rata to stakes
+    Flat,
+
+    /// Exponential decay `w(t) = α^t`, from 1.0 at creation to α at the end time
+    Exponential {
+        /// Decay parameter in (0, 1]
+        alpha: f64,
+    },
+
+    /// Linear decay from 1.0 at creation to `floor` at the end time
+    LinearDecay {
+        /// Weight of a bet placed at the end time, in (0, 1]
+        floor: f64,
+    },
+
+    /// Early-bird tiers: bets get the weight of the first tier whose window contains them
+    Step {
+        /// Tiers ordered by `until`; the last tier must end at 1.0
+        tiers: Vec<PayoutTier>,
+    },
+
+    /// Odds locked at bet: winners are paid their stake times the odds when the bet was placed,
+    /// backed by the market's subsidy liquidity
+    OddsLocked,
+}
+
+/// Weighting of a bet's stake in the bonus pool distribution
+///
+/// Implemented by `PayoutModel` and used by both settlement and return estimates.
+pub trait BetWeighting {
+    /// Weight of a bet placed at `bet_time`
+    ///
+    /// # Parameters
+    /// * `market` - The market the bet belongs to
+    /// * `bet_time` - When the bet was placed
+    fn bet_weight(&self, market: &Market, bet_time: &Timestamp) -> f64;
+
+    /// Whether bets are weighted differently from a plain pro-rata split
+    fn is_weighted(&self) -> bool;
+}
+
+impl BetWeighting for PayoutModel {
+    fn bet_weight(&self, market: &Market, bet_time: &Timestamp) -> f64 {
+        self.weight_at(relative_bet_time(market, bet_time))
+    }
+
+    fn is_weighted(&self) -> bool {
+        !matches!(self, PayoutModel::Flat | PayoutModel::OddsLocked)
+    }
+}
+
+impl PayoutModel {
+    /// Validates the parameters of a payout model chosen at market creation
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            PayoutModel::Flat | PayoutModel::OddsLocked => Ok(()),
+            PayoutModel::Exponential { alpha } => {
+                if !(*alpha > 0.0 && *alpha <= 1.0) {
+                    return Err("Exponential alpha should be in interval: (0, 1]".to_string());
+                }
+                Ok(())
+            }
+            PayoutModel::LinearDecay { floor } => {
+                if !(*floor > 0.0 && *floor <= 1.0) {
+                    return Err("Linear decay floor should be in interval: (0, 1]".to_string());
+                }
+                Ok(())
+            }
+            PayoutModel::Step { tiers } => {
+                if tiers.is_empty() || tiers.len() > MAX_PAYOUT_TIERS {
+                    return Err(format!("Step payout model must have between 1 and {} tiers", MAX_PAYOUT_TIERS));
+                }
+                let mut previous_until = 0.0;
+                for tier in tiers {
+                    if !(tier.until > previous_until && tier.until <= 1.0) {
+                        return Err("Step tiers must end in increasing order within (0, 1]".to_string());
+                    }
+                    if !(tier.weight.is_finite() && tier.weight > 0.0) {
+                        return Err("Step tier weights must be positive numbers".to_string());
+                    }
+                    previous_until = tier.until;
+                }
+                if previous_until < 1.0 {
+                    return Err("The last step tier must end at 1.0".to_string());
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Weight of a bet placed at relative time `t` (in [0, 1])
+    pub fn weight_at(&self, t: f64) -> f64 {
+        match self {
+            // Odds-locked payouts don't share a bonus pool
+            PayoutModel::Flat | PayoutModel::OddsLocked => 1.0,
+            PayoutModel::Exponential { alpha } => alpha.powf(t),
+            PayoutModel::LinearDecay { floor } => 1.0 - (1.0 - floor) * t,
+            PayoutModel::Step { tiers } => tiers
+                .iter()
+                .find(|tier| t <= tier.until)
+                .or(tiers.last())
+                .map(|tier| tier.weight)
+                .unwrap_or(1.0),
+        }
+    }
+
+    /// Whether winners are paid the odds locked when they bet instead of sharing a bonus pool
+    pub fn is_odds_locked(&self) -> bool {
+        matches!(self, PayoutModel::OddsLocked)
+    }
+
+    /// Alpha of an exponential model (used for the legacy `time_weight_alpha` fields)
+    pub fn alpha(&self) -> Option<f64> {
+        match self {
+            PayoutModel::Exponential { alpha } => Some(*alpha),
+            _ => None,
+        }
+    }
+}
+
+impl Market {
+    /// Payout model used to settle this market
+    ///
+    /// Markets created before payout models existed map their time weighting settings to
+    /// `Exponential` (or `Flat` if time weighting was disabled).
+    pub fn effective_payout_model(&self) -> PayoutModel {
+        if let Some(model) = &self.payout_model {
+            return model.clone();
+        }
+        if self.uses_time_weighting {
+            PayoutModel::Exponential {
+                alpha: self.time_weight_alpha.unwrap_or(DEFAULT_ALPHA),
+            }
+        } else {
+            PayoutModel::Flat
+        }
+    }
+}
+
+/// Time of a bet relative to the market duration, clamped to [0, 1]
+pub fn relative_bet_time(market: &Market, bet_time: &Timestamp) -> f64 {
+    let created_at = market.created_at.to_u64();
+    let end_time = market.end_time.to_u64();
+    if end_time <= created_at {
+        return 0.0;
+    }
+    let elapsed = bet_time.to_u64().saturating_sub(created_at) as f64;
+    (elapsed / (end_time - created_at) as f64).clamp(0.0, 1.0)
+}
+
+/// Payout of a winning odds-locked bet before the platform fee
+///
+/// Bets without recorded odds are paid their stake back.
+pub fn locked_payout(bet_amount: f64, locked_odds: Option<f64>) -> f64 {
+    bet_amount * locked_odds.filter(|odds| odds.is_finite() && *odds >= 1.0).unwrap_or(1.0)
+}
+
+/// Platform fee charged on the profit of a winning odds-locked bet
+///
+/// The fee is in basis points of the profit, as for bets settled from the pool.
+pub fn locked_payout_fee(bet_amount: f64, payout: f64, fee_bps: u64) -> f64 {
+    (payout - bet_amount).max(0.0) * fee_bps as f64 / 10000.0
+}
+
+/// Settlement of the winning bets of an odds-locked market
+#[derive(Debug, Clone, PartialEq)]
+pub struct OddsLockedSettlement {
+    /// Payout after the platform fee, and that fee, per winning bet
+    pub payouts: Vec<(f64, f64)>,
+    /// Total platform fee charged on the winners' profit
+    pub platform_fee: f64,
+    /// Part of the pool left after paying the winners, returned to the sponsors
+    pub remainder: f64,
+}
+
+/// Settles the winning bets of an odds-locked market
+///
+/// Every winner is paid their locked payout less the fee on its profit. Bets on one outcome are
+/// always covered by the pool (see `check_odds_locked_bet`); if several outcomes win and their
+/// payouts exceed the pool, payouts and fees are scaled down pro-rata.
+///
+/// # Parameters
+/// * `winning_bets` - Amount and locked odds of each winning bet
+/// * `total_pool` - Total pool of the market, including subsidy liquidity
+/// * `fee_bps` - Platform fee in basis points of the profit
+pub fn settle_odds_locked(winning_bets: &[(f64, Option<f64>)], total_pool: f64, fee_bps: u64) -> OddsLockedSettlement {
+    let mut payouts: Vec<(f64, f64)> = winning_bets
+        .iter()
+        .map(|(amount, locked_odds)| {
+            let payout = locked_payout(*amount, *locked_odds);
+            let fee = locked_payout_fee(*amount, payout, fee_bps);
+            (payout - fee, fee)
+        })
+        .collect();
+
+    let total_owed: f64 = payouts.iter().map(|(payout, fee)| payout + fee).sum();
+    if total_owed > total_pool {
+        let scale = total_pool / total_owed;
+        for (payout, fee) in &mut payouts {
+            *payout *= scale;
+            *fee *= scale;
+        }
+    }
+
+    let platform_fee: f64 = payouts.iter().map(|(_, fee)| fee).sum();
+    let paid: f64 = payouts.iter().map(|(payout, _)| payout).sum();
+    OddsLockedSettlement {
+        payouts,
+        platform_fee,
+        remainder: (total_pool - paid - platform_fee).max(0.0),
+    }
+}
+
+/// Checks that an odds-locked market can cover a new bet, returning the odds locked for it
+///
+/// The market's subsidy liquidity is the reserve, so bets are only accepted once the market has
+/// been seeded. A bet is accepted if the pool after the bet covers the locked payouts of every
+/// bet on its outcome, so a single winning outcome can always be paid in full.
+///
+/// # Parameters
+/// * `market` - The market before the bet
+/// * `outcome_index` - Outcome the bet is placed on
+/// * `bet_amount` - Amount added to the outcome pool
+pub fn check_odds_locked_bet(market: &Market, outcome_index: &OutcomeIndex, bet_amount: &TokenAmount) -> Result<f64, String> {
+    if market.liquidity_subsidies.is_empty() {
+        return Err("Odds-locked markets need subsidy liquidity as a reserve before accepting bets".to_string());
+    }
+    let outcome_idx = outcome_index.to_u64() as usize;
+    let total_pool = market.total_pool.to_f64() + bet_amount.to_f64();
+    let outcome_pool = market.outcome_pools[outcome_idx].to_f64() + bet_amount.to_f64();
+    let odds = total_pool / outcome_pool;
+
+    let liability: f64 = crate::storage::get_bets_for_market(&market.id)
+        .into_iter()
+        .filter(|bet| bet.outcome_index == *outcome_index)
+        .map(|bet| locked_payout(bet.amount.to_f64(), bet.locked_odds))
+        .sum::<f64>()
+        + locked_payout(bet_amount.to_f64(), Some(odds));
+    check_locked_liability(liability, total_pool)?;
+    Ok(odds)
+}
+
+/// Checks that the locked payouts of an outcome are covered by the pool
+fn check_locked_liability(liability: f64, total_pool: f64) -> Result<(), String> {
+    if liability > total_pool {
+        return Err(format!(
+            "The market's reserve can't cover this bet: locked payouts of {:.0} would exceed the pool of {:.0}",
+            liability, total_pool
+        ));
+    }
+    Ok(())
+}
+
+/// Share of the bonus pool earned by a weighted contribution
+///
+/// # Parameters
+/// * `weighted_contribution` - The bet's stake multiplied by its weight
+/// * `total_weighted_contribution` - Sum of the weighted contributions of all winners
+/// * `bonus_pool` - Profit distributed on top of the returned stakes
+pub fn calculate_bonus_share(weighted_contribution: f64, total_weighted_contribution: f64, bonus_pool: f64) -> f64 {
+    if total_weighted_contribution > 0.0 {
+        weighted_contribution / total_weighted_contribution * bonus_pool
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiers() -> Vec<PayoutTier> {
+        vec![
+            PayoutTier { until: 0.25, weight: 3.0 },
+            PayoutTier { until: 0.5, weight: 2.0 },
+            PayoutTier { until: 1.0, weight: 1.0 },
+        ]
+    }
+
+    #[test]
+    fn test_validate_payout_models() {
+        assert!(PayoutModel::Flat.validate().is_ok());
+        assert!(PayoutModel::Exponential { alpha: 0.1 }.validate().is_ok());
+        assert!(PayoutModel::Exponential { alpha: 0.0 }.validate().is_err());
+        assert!(PayoutModel::LinearDecay { floor: 1.5 }.validate().is_err());
+        assert!(PayoutModel::Step { tiers: tiers() }.validate().is_ok());
+        assert!(PayoutModel::Step { tiers: vec![] }.validate().is_err());
+        assert!(PayoutModel::Step {
+            tiers: vec![PayoutTier { until: 0.5, weight: 1.0 }]
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_weight_curves() {
+        assert_eq!(PayoutModel::Flat.weight_at(0.7), 1.0);
+        assert!((PayoutModel::Exponential { alpha: 0.1 }.weight_at(1.0) - 0.1).abs() < 1e-12);
+        assert!((PayoutModel::LinearDecay { floor: 0.5 }.weight_at(0.5) - 0.75).abs() < 1e-12);
+
+        let step = PayoutModel::Step { tiers: tiers() };
+        assert_eq!(step.weight_at(0.0), 3.0);
+        assert_eq!(step.weight_at(0.3), 2.0);
+        assert_eq!(step.weight_at(1.0), 1.0);
+
+        assert_eq!(PayoutModel::OddsLocked.weight_at(0.9), 1.0);
+    }
+
+    #[test]
+    fn test_locked_payout() {
+        assert_eq!(locked_payout(100.0, Some(2.5)), 250.0);
+        // bets without usable odds get their stake back
+        assert_eq!(locked_payout(100.0, None), 100.0);
+        assert_eq!(locked_payout(100.0, Some(0.5)), 100.0);
+        assert_eq!(locked_payout(100.0, Some(f64::INFINITY)), 100.0);
+    }
+
+    #[test]
+    fn test_locked_payout_fee_is_charged_on_profit() {
+        assert_eq!(locked_payout_fee(100.0, 250.0, 200), 3.0);
+        assert_eq!(locked_payout_fee(100.0, 100.0, 200), 0.0);
+    }
+
+    #[test]
+    fn test_settle_odds_locked_pays_locked_odds_and_returns_the_rest() {
+        // 100 at 2.0 and 50 at 4.0 on the winner of a 1_000 pool, 10% fee on profit
+        let settlement = settle_odds_locked(&[(100.0, Some(2.0)), (50.0, Some(4.0))], 1_000.0, 1_000);
+        assert_eq!(settlement.payouts, vec![(190.0, 10.0), (185.0, 15.0)]);
+        assert_eq!(settlement.platform_fee, 25.0);
+        assert_eq!(settlement.remainder, 600.0);
+    }
+
+    #[test]
+    fn test_settle_odds_locked_scales_down_when_the_pool_is_short() {
+        // two winning outcomes each covered on its own, but not together
+        let settlement = settle_odds_locked(&[(100.0, Some(3.0)), (100.0, Some(3.0))], 300.0, 0);
+        assert_eq!(settlement.payouts, vec![(150.0, 0.0), (150.0, 0.0)]);
+        assert_eq!(settlement.remainder, 0.0);
+    }
+
+    #[test]
+    fn test_settle_odds_locked_without_winners_returns_the_pool() {
+        let settlement = settle_odds_locked(&[], 500.0, 200);
+        assert!(settlement.payouts.is_empty());
+        assert_eq!(settlement.platform_fee, 0.0);
+        assert_eq!(settlement.remainder, 500.0);
+    }
+
+    #[test]
+    fn test_locked_liability_must_be_covered_by_the_pool() {
+        assert!(check_locked_liability(1_000.0, 1_000.0).is_ok());
+        assert!(check_locked_liability(1_000.1, 1_000.0).is_err());
+    }
+
+    #[test]
+    fn test_bonus_share_is_pro_rata_to_weighted_contribution() {
+        assert_eq!(calculate_bonus_share(100.0, 400.0, 1000.0), 250.0);
+        assert_eq!(calculate_bonus_share(100.0, 0.0, 1000.0), 0.0);
+    }
+}
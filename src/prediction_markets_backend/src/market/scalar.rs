@@ -0,0 +1,271 @@
+//! # Scalar Markets
+//!
+//! Scalar (range) markets predict a numeric value instead of choosing between discrete
+//! outcomes, e.g. "What will the ICP price be on date X?". The creator fixes a lower and
+//! an upper bound; users take a **long** position (profits as the value approaches the
+//! upper bound) or a **short** position (profits as it approaches the lower bound).
+//!
+//! Positions are stored as two ordinary outcomes (`Long` at index 0, `Short` at index 1),
+//! so bet placement, pools, percentages and claims reuse the categorical machinery.
+//!
+//! ## Settlement
+//!
+//! At resolution the value `v` is clamped to `[lower, upper]` and the pool is split linearly:
+//!
+//! ```text
+//! long_fraction   = (v - lower) / (upper - lower)
+//! long_allocation = long_fraction * total_pool
+//! short_allocation = total_pool - long_allocation
+//! ```
+//!
+//! The platform fee is charged only on the amount that moves from the losing side to the
+//! gaining side. Within the gaining side every bet gets its stake back plus a share of the
+//! gain (time-weighted if the market uses time weighting); within the losing side every
+//! bet is paid out pro-rata to its stake. If one side has no bets there is no counterparty
+//! and both pools are returned as-is.
+
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::resolution::kong_price::KongPoolPriceSource;
+
+/// Outcome index used for long positions in scalar markets
+pub const LONG_OUTCOME_INDEX: u64 = 0;
+
+/// Outcome index used for short positions in scalar markets
+pub const SHORT_OUTCOME_INDEX: u64 = 1;
+
+/// Configuration of a scalar market's numeric range
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ScalarMarketConfig {
+    /// Value at (or below) which short positions receive the whole pool
+    pub lower_bound: f64,
+
+    /// Value at (or above) which long positions receive the whole pool
+    pub upper_bound: f64,
+
+    /// Optional display unit for the bounds and resolved value (e.g. "USD")
+    pub unit: Option<String>,
+
+    /// Optional Kong pool whose spot price is used as the resolved value
+    pub resolution_source: Option<KongPoolPriceSource>,
+
+    /// Value the market was settled on, set once the market is resolved
+    pub resolved_value: Option<f64>,
+}
+
+/// How the market pool is split between long and short positions for a resolved value
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalarSettlement {
+    /// Fraction of the pool allocated to long positions before fees, in [0, 1]
+    pub long_fraction: f64,
+
+    /// Total amount paid out to long positions (after the platform fee)
+    pub long_allocation: f64,
+
+    /// Total amount paid out to short positions (after the platform fee)
+    pub short_allocation: f64,
+
+    /// Amount moved from the losing side to the gaining side before fees
+    pub transferred: f64,
+
+    /// Platform fee charged on the transferred amount
+    pub platform_fee: f64,
+}
+
+impl ScalarMarketConfig {
+    /// Validates the bounds, unit and resolution source of a new scalar market
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.lower_bound.is_finite() || !self.upper_bound.is_finite() {
+            return Err("Scalar bounds must be finite numbers".to_string());
+        }
+        if self.lower_bound >= self.upper_bound {
+            return Err("Scalar lower bound must be less than the upper bound".to_string());
+        }
+        if let Some(unit) = &self.unit {
+            if unit.len() > 16 {
+                return Err("Scalar unit cannot be longer than 16 characters".to_string());
+            }
+        }
+        if let Some(source) = &self.resolution_source {
+            source.validate()?;
+        }
+        if self.resolved_value.is_some() {
+            return Err("A new scalar market cannot have a resolved value".to_string());
+        }
+        Ok(())
+    }
+
+    /// Fraction of the pool allocated to long positions for the given value
+    ///
+    /// The value is clamped to the market range, so anything at or below the lower bound
+    /// yields 0.0 and anything at or above the upper bound yields 1.0.
+    pub fn long_fraction(&self, value: f64) -> f64 {
+        let range = self.upper_bound - self.lower_bound;
+        if range <= 0.0 {
+            return 0.5;
+        }
+        ((value - self.lower_bound) / range).clamp(0.0, 1.0)
+    }
+
+    /// Value implied by the current pools, i.e. the value at which neither side gains
+    pub fn implied_value(&self, long_pool: f64, short_pool: f64) -> f64 {
+        let total = long_pool + short_pool;
+        if total <= 0.0 {
+            return (self.lower_bound + self.upper_bound) / 2.0;
+        }
+        self.lower_bound + (long_pool / total) * (self.upper_bound - self.lower_bound)
+    }
+}
+
+/// Outcome labels used for the two positions of a scalar market
+pub fn scalar_outcomes() -> Vec<String> {
+    vec!["Long".to_string(), "Short".to_string()]
+}
+
+/// Splits the market pool between the long and short sides
+///
+/// # Parameters
+/// * `long_fraction` - Fraction of the pool allocated to long positions (see `ScalarMarketConfig::long_fraction`)
+/// * `long_pool` - Total amount bet on long positions
+/// * `short_pool` - Total amount bet on short positions
+/// * `fee_percentage` - Platform fee in basis points, charged on the transferred amount
+pub fn calculate_scalar_settlement(long_fraction: f64, long_pool: f64, short_pool: f64, fee_percentage: u64) -> ScalarSettlement {
+    // Without a counterparty nothing can move between sides, so return both pools untouched
+    if long_pool <= 0.0 || short_pool <= 0.0 {
+        return ScalarSettlement {
+            long_fraction,
+            long_allocation: long_pool,
+            short_allocation: short_pool,
+            transferred: 0.0,
+            platform_fee: 0.0,
+        };
+    }
+
+    let total_pool = long_pool + short_pool;
+    let gross_long = long_fraction * total_pool;
+    let gross_short = total_pool - gross_long;
+
+    let transferred = (gross_long - long_pool).abs();
+    let platform_fee = transferred * fee_percentage as f64 / 10000.0;
+
+    // The fee is taken from the side that gains
+    let (long_allocation, short_allocation) = if gross_long > long_pool {
+        (gross_long - platform_fee, gross_short)
+    } else {
+        (gross_long, gross_short - platform_fee)
+    };
+
+    ScalarSettlement {
+        long_fraction,
+        long_allocation,
+        short_allocation,
+        transferred,
+        platform_fee,
+    }
+}
+
+/// Payout of a single bet given its side's allocation
+///
+/// # Parameters
+/// * `bet_amount` - Original amount of the bet
+/// * `weighted_contribution` - Bet amount multiplied by its time weight (equal to `bet_amount` without weighting)
+/// * `side_pool` - Total amount bet on the bet's side
+/// * `side_weighted_contribution` - Sum of weighted contributions on the bet's side
+/// * `side_allocation` - Amount allocated to the bet's side by `calculate_scalar_settlement`
+///
+/// # Returns
+/// * `(f64, f64)` - The total payout and the bonus above the original stake (zero when the side lost)
+pub fn calculate_scalar_position_payout(
+    bet_amount: f64,
+    weighted_contribution: f64,
+    side_pool: f64,
+    side_weighted_contribution: f64,
+    side_allocation: f64,
+) -> (f64, f64) {
+    if side_pool <= 0.0 {
+        return (0.0, 0.0);
+    }
+
+    if side_allocation >= side_pool {
+        // Gaining side: stake back plus a (weighted) share of the gain
+        let gain = side_allocation - side_pool;
+        let bonus = if side_weighted_contribution > 0.0 {
+            weighted_contribution / side_weighted_contribution * gain
+        } else {
+            0.0
+        };
+        (bet_amount + bonus, bonus)
+    } else {
+        // Losing side: what remains is shared pro-rata to stake
+        (bet_amount * side_allocation / side_pool, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ScalarMarketConfig {
+        ScalarMarketConfig {
+            lower_bound: 5.0,
+            upper_bound: 15.0,
+            unit: Some("USD".to_string()),
+            resolution_source: None,
+            resolved_value: None,
+        }
+    }
+
+    #[test]
+    fn test_long_fraction_is_clamped_and_linear() {
+        let config = config();
+        assert_eq!(config.long_fraction(0.0), 0.0);
+        assert_eq!(config.long_fraction(5.0), 0.0);
+        assert_eq!(config.long_fraction(7.5), 0.25);
+        assert_eq!(config.long_fraction(10.0), 0.5);
+        assert_eq!(config.long_fraction(15.0), 1.0);
+        assert_eq!(config.long_fraction(100.0), 1.0);
+    }
+
+    #[test]
+    fn test_validate_rejects_inverted_bounds() {
+        let mut config = config();
+        assert!(config.validate().is_ok());
+        config.lower_bound = 20.0;
+        assert!(config.validate().is_err());
+        config.lower_bound = f64::NAN;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_settlement_charges_fee_on_gaining_side() {
+        // 1000 long, 1000 short, resolved at 75% of the range with a 1% fee
+        let settlement = calculate_scalar_settlement(0.75, 1000.0, 1000.0, 100);
+        assert_eq!(settlement.transferred, 500.0);
+        assert_eq!(settlement.platform_fee, 5.0);
+        assert_eq!(settlement.long_allocation, 1495.0);
+        assert_eq!(settlement.short_allocation, 500.0);
+    }
+
+    #[test]
+    fn test_settlement_without_counterparty_returns_pools() {
+        let settlement = calculate_scalar_settlement(1.0, 1000.0, 0.0, 100);
+        assert_eq!(settlement.long_allocation, 1000.0);
+        assert_eq!(settlement.short_allocation, 0.0);
+        assert_eq!(settlement.platform_fee, 0.0);
+    }
+
+    #[test]
+    fn test_position_payouts_add_up_to_allocation() {
+        // Gaining side with two equally weighted bets of 300 and 700
+        let (a, _) = calculate_scalar_position_payout(300.0, 300.0, 1000.0, 1000.0, 1495.0);
+        let (b, _) = calculate_scalar_position_payout(700.0, 700.0, 1000.0, 1000.0, 1495.0);
+        assert!((a + b - 1495.0).abs() < 1e-9);
+        assert!(a > 300.0);
+
+        // Losing side keeps half of its stake
+        let (c, bonus) = calculate_scalar_position_payout(400.0, 400.0, 1000.0, 1000.0, 500.0);
+        assert_eq!(c, 200.0);
+        assert_eq!(bonus, 0.0);
+    }
+}
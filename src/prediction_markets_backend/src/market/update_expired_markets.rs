@@ -1,4 +1,6 @@
+use crate::bet::combo_bet::settle_combo_bets;
 use crate::market::market::*;
+use crate::resolution::resolve_via_kong_price::spawn_kong_price_resolution;
 use crate::storage::MARKETS;
 use crate::types::StorableNat;
 use ic_cdk::{heartbeat, update};
@@ -35,6 +37,15 @@ fn check_expired_markets() {
     if dropped_markets > 0 {
         ic_cdk::println!("Dropped {} markets", dropped_markets);
     }
+
+    // Settle ended Kong price markets from their pool prices
+    spawn_kong_price_resolution();
+
+    // Settle combo bets whose markets have all resolved
+    let settled_combos = settle_combo_bets();
+    if settled_combos > 0 {
+        ic_cdk::println!("Settled {} combo bets", settled_combos);
+    }
 }
 
 /// Manual trigger for checking expired markets
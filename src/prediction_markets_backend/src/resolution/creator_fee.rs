@@ -0,0 +1,58 @@
+//! # Creator Fee Share
+//!
+//! Creators of user-created markets receive a share of the platform fee charged at
+//! resolution, as a reward for attracting volume. The share is configured per token in
+//! basis points of the platform fee (`TokenInfo::creator_fee_share`). Admin-created markets
+//! don't pay a creator share.
+//!
+//! The creator's share is paid through a claim; only the remainder is burned or sent to
+//! the fee collector. A share that does not exceed the transfer fee is not claimed and
+//! goes to the fee collector with the rest of the platform fee.
+
+use crate::claims::claims_processing::create_creator_fee_claim;
+use crate::controllers::admin::is_admin;
+use crate::market::market::Market;
+use crate::token::registry::TokenInfo;
+use crate::types::TokenAmount;
+
+/// Portion of the platform fee owed to the market creator
+///
+/// # Parameters
+/// * `market` - The market being resolved
+/// * `platform_fee` - Total platform fee charged on the market
+/// * `token_info` - Token of the market, which holds the creator share configuration
+///
+/// # Returns
+/// * `TokenAmount` - The creator's share (zero for admin-created markets)
+pub fn creator_fee_amount(market: &Market, platform_fee: &TokenAmount, token_info: &TokenInfo) -> TokenAmount {
+    if is_admin(market.creator) {
+        return TokenAmount::from(0u64);
+    }
+    platform_fee.clone() * token_info.creator_fee_share_bps() / 10000
+}
+
+/// Creates the creator's fee share claim if it is worth transferring
+///
+/// # Returns
+/// * `Option<u64>` - The claim ID, or None if the share does not exceed the transfer fee
+pub fn pay_creator_fee(market: &Market, creator_fee: &TokenAmount, platform_fee: &TokenAmount, token_info: &TokenInfo) -> Option<u64> {
+    if *creator_fee <= token_info.transfer_fee {
+        return None;
+    }
+
+    let claim_id = create_creator_fee_claim(
+        market.creator,
+        market.id.clone(),
+        platform_fee.clone(),
+        creator_fee.clone(),
+        market.token_id.clone(),
+    );
+    ic_cdk::println!(
+        "Created creator fee claim {} for {} with amount {}",
+        claim_id,
+        market.creator,
+        creator_fee
+    );
+
+    Some(claim_id)
+}
@@ -6,7 +6,7 @@
 //!
 //! ## Distribution Models
 //!
-//! The system supports two distinct payout distribution approaches:
+//! The system supports three distinct payout distribution approaches:
 //!
 //! ### 1. Standard Distribution
 //!
@@ -56,19 +56,35 @@
 //!
 //! This implementation includes comprehensive safeguards to ensure rewards never exceed
 //! the total market pool, with dynamic bonus pool adjustments if necessary.
+//!
+//! ### 3. Odds-Locked Distribution
+//!
+//! Markets with the `OddsLocked` payout model pay each winning bet its stake times the odds
+//! recorded when it was placed, less the platform fee on its profit. The market's subsidy
+//! liquidity is the reserve backing these payouts: bets the pool can't cover are rejected when
+//! placed, and whatever is left of the pool after settlement is returned to the sponsors.
+//!
+//! ## Creator Fees and Subsidies
+//!
+//! For user-created markets, part of the platform fee is paid to the creator (see
+//! `creator_fee`). Subsidy liquidity in winning pools is returned to its sponsors pro-rata,
+//! while subsidy in losing pools is distributed to winners as profit (see `market::liquidity`).
 
 use candid::Principal;
 use num_traits::ToPrimitive;
 
+use super::creator_fee::{creator_fee_amount, pay_creator_fee};
 use super::resolution::*;
 use crate::canister::{get_current_time, record_market_payout};
 use crate::claims::claims_processing::create_winning_claim;
 use crate::market::estimate_return_types::BetPayoutRecord;
+use crate::market::liquidity::return_liquidity_subsidies;
 use crate::market::market::*;
 use crate::storage::BETS;
 use crate::token::registry::get_token_info;
 use crate::token::transfer::{handle_fee_transfer, handle_fee_transfer_failure, get_fee_account};
-use crate::utils::time_weighting::{calculate_time_weight, calculate_weighted_contribution, get_market_alpha};
+use crate::market::payout_model::{calculate_bonus_share, settle_odds_locked, BetWeighting};
+use crate::utils::time_weighting::calculate_weighted_contribution;
 
 // Import re-exported types from lib.rs
 use crate::types::BetDistributionDetail;
@@ -91,14 +107,14 @@ use crate::TokenAmount;
 /// 2. Calculating the total winning pool and platform fees
 /// 3. Processing the platform fee (burn or transfer)
 /// 4. Creating claims for winning bettors to claim their winnings using either:
-///    - Standard proportional distribution, or
-///    - Time-weighted distribution (if market.uses_time_weighting is true)
+///    - Standard proportional distribution,
+///    - Weighted distribution using the market's payout model (see `market::payout_model`), or
+///    - Fixed payouts at the odds locked when each bet was placed
 /// 5. Recording payout information for each winning bet
 ///
-/// For time-weighted markets, earlier bets receive higher payouts based on an
-/// exponential weighting model. This rewards users who committed to their
-/// predictions earlier, while ensuring all correct predictors receive at least
-/// their original bet amount back.
+/// For weighted payout models, bets receive a share of the profit according to their
+/// weight (e.g. earlier bets receive higher payouts with exponential time weighting),
+/// while all correct predictors receive at least their original bet amount back.
 ///
 /// # Parameters
 /// * `market` - Mutable reference to the market being finalized
@@ -115,6 +131,7 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
 
     // Initialize market resolution details structure to capture all resolution information
     let current_time = get_current_time();
+    let payout_model = market.effective_payout_model();
     let mut resolution_details = MarketResolutionDetails {
         market_id: market.id.clone(),
         winning_outcomes: winning_outcomes.clone(),
@@ -128,13 +145,18 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
         token_id: market.token_id.clone(),
         token_symbol: String::new(), // Will update later
         winning_bet_count: 0,        // Will update later
-        used_time_weighting: market.uses_time_weighting,
-        time_weight_alpha: market.time_weight_alpha,
+        used_time_weighting: payout_model.is_weighted(),
+        time_weight_alpha: payout_model.alpha(),
         // total_transfer_fees: TokenAmount::default(),  // Will update if applicable
         distributable_profit: TokenAmount::from(0), // Will update if applicable
         total_weighted_contribution: None,          // Will update if time-weighted
         distribution_details: Vec::new(),
         failed_transactions: Vec::new(),
+        scalar_resolved_value: None,
+        price_snapshot: None,
+        creator_fee_amount: None,
+        subsidy_returned_amount: None,
+        payout_model: Some(payout_model.clone()),
     };
 
     // Validate market state - allow both Active and ExpiredUnresolved markets to be finalized
@@ -142,6 +164,11 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
         return Err(ResolutionError::AlreadyResolved);
     }
 
+    // Scalar markets settle on a value, not on outcome indices (see finalize_scalar_market)
+    if market.is_scalar() {
+        return Err(ResolutionError::InvalidMethod);
+    }
+
     // Validate winning outcomes
     for outcome in &winning_outcomes {
         if outcome.to_u64() as usize >= market.outcomes.len() {
@@ -176,9 +203,26 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
 
     ic_cdk::println!("Total profit (losing bets): {}", total_profit);
 
+    // Odds-locked markets pay each winner a fixed payout, so the fee is charged on the profit of
+    // each winning bet instead of on the losing pools
+    let odds_locked_settlement = if payout_model.is_odds_locked() {
+        let winning_bets: Vec<_> = crate::storage::get_bets_for_market(&market.id)
+            .into_iter()
+            .filter(|bet| winning_outcomes.iter().any(|x| x == &bet.outcome_index))
+            .collect();
+        let stakes: Vec<(f64, Option<f64>)> = winning_bets.iter().map(|bet| (bet.amount.to_f64(), bet.locked_odds)).collect();
+        let settlement = settle_odds_locked(&stakes, market.total_pool.to_f64(), token_info.fee_percentage);
+        Some((winning_bets, settlement))
+    } else {
+        None
+    };
+
     // Calculate platform fee based on profit (1% for KONG, 2% for others)
     let fee_percentage = token_info.fee_percentage;
-    let platform_fee = total_profit.clone() * fee_percentage / 10000;
+    let platform_fee = match &odds_locked_settlement {
+        Some((_, settlement)) => TokenAmount::from(settlement.platform_fee as u64),
+        None => total_profit.clone() * fee_percentage / 10000,
+    };
 
     // Update platform fee in resolution details
     resolution_details.platform_fee_amount = platform_fee.clone();
@@ -199,9 +243,21 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
         token_info.symbol
     );
 
+    // Creators of user-created markets receive a share of the platform fee through a claim;
+    // only the remainder is burned or sent to the fee collector
+    let creator_fee = creator_fee_amount(market, &platform_fee, &token_info);
+    // a share too small to claim stays in the protocol fee
+    let protocol_fee = match pay_creator_fee(market, &creator_fee, &platform_fee, &token_info) {
+        Some(_) => {
+            resolution_details.creator_fee_amount = Some(creator_fee.clone());
+            platform_fee.clone() - creator_fee
+        }
+        None => platform_fee.clone(),
+    };
+
     // Process the platform fee (burn for KONG, transfer to fee collector for other tokens)
-    if platform_fee > token_info.transfer_fee {
-        match handle_fee_transfer(platform_fee.clone(), token_id).await {
+    if protocol_fee > token_info.transfer_fee {
+        match handle_fee_transfer(protocol_fee.clone(), token_id).await {
             Ok(Some(tx_id)) => {
                 // Store transaction ID in resolution details
                 // Convert Nat to u64 for storage in our resolution details
@@ -209,7 +265,7 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
 
                 ic_cdk::println!(
                     "Successfully burned platform fee of {} {} (Transaction ID: {})",
-                    platform_fee.to_f64() / 10f64.powf(token_info.decimals as f64),
+                    protocol_fee.to_f64() / 10f64.powf(token_info.decimals as f64),
                     token_info.symbol,
                     tx_id
                 );
@@ -217,7 +273,7 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
             Ok(None) => {
                 ic_cdk::println!(
                     "Successfully burned platform fee of {} {}",
-                    platform_fee.to_f64() / 10f64.powf(token_info.decimals as f64),
+                    protocol_fee.to_f64() / 10f64.powf(token_info.decimals as f64),
                     token_info.symbol
                 );
             }
@@ -229,13 +285,13 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
                 resolution_details.failed_transactions.push(FailedTransactionInfo {
                     market_id: Some(market.id.clone()),
                     user: get_fee_account(token_info.is_kong),
-                    amount: platform_fee.clone(),
+                    amount: protocol_fee.clone(),
                     token_id: Some(token_id.clone()),
                     error: error_msg.clone(),
                     timestamp: Some(get_current_time()),
                 });
 
-                handle_fee_transfer_failure(market.id.clone(), platform_fee.clone(), &token_info, e);
+                handle_fee_transfer_failure(market.id.clone(), protocol_fee.clone(), &token_info, e);
 
                 ic_cdk::println!("Error processing platform fee: {}. Continuing with distribution.", error_msg);
                 // Continue with distribution even if fee processing fails
@@ -245,7 +301,90 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
         ic_cdk::println!("Platform fee too small to process (less than transfer fee). Skipping fee transfer.");
     }
 
-    if total_winning_pool > 0u64 {
+    if let Some((winning_bets, settlement)) = odds_locked_settlement {
+        // Odds-Locked Distribution
+        //
+        // Each winner is paid their stake times the odds locked when the bet was placed, less
+        // the fee on its profit. The market's subsidy liquidity backs these payouts and gets
+        // whatever is left of the pool.
+        ic_cdk::println!("Using odds-locked distribution for {} winning bets", winning_bets.len());
+        resolution_details.winning_bet_count = winning_bets.len() as u64;
+        let total_paid: f64 = settlement.payouts.iter().map(|(payout, _)| payout).sum();
+        let total_staked: f64 = winning_bets.iter().map(|bet| bet.amount.to_f64()).sum();
+        resolution_details.distributable_profit = TokenAmount::from((total_paid - total_staked).max(0.0) as u64);
+
+        for (bet, (payout, fee)) in winning_bets.into_iter().zip(settlement.payouts) {
+            let gross_winnings = TokenAmount::from(payout as u64);
+            let bonus_amount = TokenAmount::from((payout - bet.amount.to_f64()).max(0.0) as u64);
+            let user_platform_fee = Some(TokenAmount::from(fee as u64));
+
+            let mut detail = BetDistributionDetail {
+                user: bet.user,
+                bet_amount: bet.amount.clone(),
+                time_weight: None,
+                weighted_contribution: None,
+                bonus_amount: bonus_amount.clone(),
+                total_payout: gross_winnings.clone(),
+                outcome_index: bet.outcome_index.clone(),
+                claim_id: None,
+            };
+
+            // Skip if winnings are less than transfer fee
+            if gross_winnings <= token_info.transfer_fee {
+                ic_cdk::println!(
+                    "Skipping claim - winnings {} less than fee {}",
+                    gross_winnings.to_u64(),
+                    token_info.transfer_fee.to_u64()
+                );
+                resolution_details.distribution_details.push(detail);
+                continue;
+            }
+
+            let claim_id = create_winning_claim(
+                bet.user,
+                market.id.clone(),
+                bet.amount.clone(),
+                vec![bet.outcome_index.clone()],
+                gross_winnings.clone(),
+                user_platform_fee.clone(),
+                token_id.clone(),
+                Timestamp::from(get_current_time()),
+            );
+            detail.claim_id = Some(claim_id);
+            resolution_details.distribution_details.push(detail);
+
+            ic_cdk::println!(
+                "Created claim {} for user {} with amount {} (locked odds {:?})",
+                claim_id,
+                bet.user.to_string(),
+                gross_winnings.to_u64(),
+                bet.locked_odds
+            );
+
+            record_market_payout(BetPayoutRecord {
+                market_id: market.id.clone(),
+                user: bet.user,
+                bet_amount: bet.amount.clone(),
+                payout_amount: gross_winnings,
+                timestamp: Timestamp::from(get_current_time()),
+                outcome_index: bet.outcome_index,
+                was_time_weighted: false,
+                time_weight: None,
+                original_contribution_returned: bet.amount,
+                bonus_amount: Some(bonus_amount),
+                platform_fee_amount: user_platform_fee,
+                token_id: token_id.clone(),
+                token_symbol: token_info.symbol.clone(),
+                platform_fee_percentage: token_info.fee_percentage,
+                transaction_id: None, // No transaction yet, user will claim
+            });
+        }
+
+        // The rest of the pool goes back to the sponsors whose liquidity backed the payouts
+        let remainder = TokenAmount::from(settlement.remainder as u64);
+        let returned = return_liquidity_subsidies(market, 0, remainder, &token_info);
+        resolution_details.subsidy_returned_amount = Some(returned);
+    } else if total_winning_pool > 0u64 {
         // Get all winning bets
         let winning_bets = BETS.with(|_bets| {
             // Get all bets for this market using our helper function
@@ -259,16 +398,15 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
         resolution_details.winning_bet_count = winning_bets.len() as u64;
         ic_cdk::println!("Found {} winning bets", winning_bets.len());
 
-        // Time-Weighted Distribution Model
+        // Weighted Distribution Model
         //
-        // When a market is configured to use time-weighted distribution (market.uses_time_weighting = true),
-        // the system applies an exponential weighting model that rewards earlier betting behavior.
-        // This model is designed to incentivize early market participation and improve price discovery.
-        if market.uses_time_weighting {
-            // The time-weighted distribution uses a sophisticated exponential decay function
-            // that calculates weights based on when bets were placed relative to market duration.
-            //
-            // The time weight formula is: weight = α^(t/T) where:
+        // When a market's payout model weights bets (the time-based models), the profit is
+        // shared according to each bet's weight instead of pro-rata to stakes. With the default
+        // exponential model this rewards earlier betting behavior, which incentivizes early
+        // market participation and improves price discovery.
+        if payout_model.is_weighted() {
+            // The weight of each bet comes from the market's payout model, e.g. for the
+            // exponential model: weight = α^(t/T) where:
             // - α is the decay parameter (typically 0.1, configurable per market)
             // - t is the time elapsed since market creation when the bet was placed
             // - T is the total market duration from creation to closing
@@ -277,14 +415,7 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
             // - Bets placed at market start receive full weight (1.0)
             // - Bets placed at market midpoint receive weight of ~0.32
             // - Bets placed at market end receive minimum weight (0.1)
-            let market_created_at = market.created_at.clone();
-            let market_end_time = market.end_time.clone();
-            let alpha = get_market_alpha(market);
-
-            // Update resolution details for time-weighted distribution
-            resolution_details.time_weight_alpha = Some(alpha);
-
-            ic_cdk::println!("Using time-weighted distribution with alpha: {}", alpha);
+            ic_cdk::println!("Using weighted distribution with payout model: {:?}", payout_model);
 
             // Calculate weighted contributions for each winning bet
             // Each bet's contribution is weighted by time - earlier bets get higher weights
@@ -295,18 +426,10 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
             for bet in &winning_bets {
                 let bet_amount = bet.amount.to_f64();
 
-                // Calculate time-based exponential weight
-                //
-                // This applies the core time-weighting function that determines how much
-                // additional reward a particular bet receives based on its timing.
+                // Calculate the bet's weight under the market's payout model
                 //
-                // The weight ranges from 1.0 (bet placed at market creation) to α (bet placed at market end)
-                let weight = calculate_time_weight(
-                    market_created_at.clone(), // When the market was created
-                    market_end_time.clone(),   // When the market closes for betting
-                    bet.timestamp.clone(),     // When this particular bet was placed
-                    alpha,                     // The exponential decay parameter
-                );
+                // This determines how much additional reward a particular bet receives based on its timing.
+                let weight = payout_model.bet_weight(market, &bet.timestamp);
 
                 // The weighted contribution combines bet amount with time weight
                 // This value represents the bet's share of the bonus pool
@@ -426,11 +549,7 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
 
             for (user, bet_amount, weight, weighted_contribution, outcome_index) in weighted_contributions {
                 // Calculate the share of the bonus pool
-                let bonus_share = if total_weighted_contribution > 0.0 {
-                    weighted_contribution / total_weighted_contribution * bonus_pool
-                } else {
-                    0.0
-                };
+                let bonus_share = calculate_bonus_share(weighted_contribution, total_weighted_contribution, bonus_pool);
 
                 // Total reward = original bet + share of bonus pool
                 // Note: Transfer fee is already accounted for in the bonus pool calculation
@@ -669,6 +788,19 @@ pub async fn finalize_market(market: &mut Market, winning_outcomes: Vec<OutcomeI
         }
     }
 
+    // Return the subsidy liquidity in the winning pools to sponsors. If nobody bet on a
+    // winning outcome the profit has no recipients, so it goes back to the sponsors as well.
+    // Odds-locked markets already returned what is left of the pool above
+    if !market.liquidity_subsidies.is_empty() && !payout_model.is_odds_locked() {
+        let unclaimed_profit = if resolution_details.winning_bet_count == 0 {
+            total_profit.clone() - platform_fee.clone()
+        } else {
+            TokenAmount::from(0u64)
+        };
+        let returned = return_liquidity_subsidies(market, winning_outcomes.len() as u64, unclaimed_profit, &token_info);
+        resolution_details.subsidy_returned_amount = Some(returned);
+    }
+
     // Update market status to Closed with the winning outcomes
     // This finalizes the market in the stable memory system and prevents
     // any further bets or resolutions on this market
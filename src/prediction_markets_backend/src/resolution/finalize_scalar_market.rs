@@ -0,0 +1,277 @@
+//! # Scalar Market Finalization
+//!
+//! This module settles scalar (range) markets on a resolved numeric value. The pool is
+//! split linearly between long and short positions (see `market::scalar`), the platform
+//! fee is processed on the amount moving between sides, and claims are created for every
+//! position with a payout using the same claims flow as categorical markets.
+
+use num_traits::ToPrimitive;
+
+use super::creator_fee::{creator_fee_amount, pay_creator_fee};
+use super::kong_price::KongPriceSnapshot;
+use super::resolution::*;
+use crate::canister::{get_current_time, record_market_payout};
+use crate::claims::claims_processing::create_winning_claim;
+use crate::market::estimate_return_types::BetPayoutRecord;
+use crate::bet::bet::Bet;
+use crate::market::market::*;
+use crate::market::scalar::{calculate_scalar_position_payout, calculate_scalar_settlement, LONG_OUTCOME_INDEX, SHORT_OUTCOME_INDEX};
+use crate::token::registry::get_token_info;
+use crate::token::transfer::{get_fee_account, handle_fee_transfer, handle_fee_transfer_failure};
+use crate::types::{BetDistributionDetail, FailedTransactionInfo, MarketResolutionDetails};
+use crate::market::payout_model::BetWeighting;
+use crate::utils::time_weighting::calculate_weighted_contribution;
+use crate::OutcomeIndex;
+use crate::TokenAmount;
+
+/// Finalizes a scalar market on the given value by creating claims for every position
+///
+/// # Parameters
+/// * `market` - Mutable reference to the scalar market being finalized
+/// * `resolved_value` - The value the market resolves to (clamped to the market range)
+/// * `price_snapshot` - Kong pool snapshot the value was read from, if any
+///
+/// # Returns
+/// * `Result<(), ResolutionError>` - Success or error reason if finalization fails
+pub async fn finalize_scalar_market(
+    market: &mut Market,
+    resolved_value: f64,
+    price_snapshot: Option<KongPriceSnapshot>,
+) -> Result<(), ResolutionError> {
+    ic_cdk::println!("Finalizing scalar market {} with value {}", market.id.to_u64(), resolved_value);
+
+    if !matches!(market.status, MarketStatus::Active | MarketStatus::ExpiredUnresolved) {
+        return Err(ResolutionError::AlreadyResolved);
+    }
+
+    if !resolved_value.is_finite() {
+        return Err(ResolutionError::InvalidOutcome);
+    }
+
+    let config = market.scalar_config().cloned().ok_or(ResolutionError::InvalidMethod)?;
+
+    let token_id = market.token_id.clone();
+    let token_info =
+        get_token_info(&token_id).ok_or(ResolutionError::TransferError(format!("Token info not found for ID: {}", token_id)))?;
+
+    let long_idx = LONG_OUTCOME_INDEX as usize;
+    let short_idx = SHORT_OUTCOME_INDEX as usize;
+    let long_pool = market.outcome_pools[long_idx].to_f64();
+    let short_pool = market.outcome_pools[short_idx].to_f64();
+
+    let long_fraction = config.long_fraction(resolved_value);
+    let settlement = calculate_scalar_settlement(long_fraction, long_pool, short_pool, token_info.fee_percentage);
+    let platform_fee = TokenAmount::from(settlement.platform_fee as u64);
+
+    ic_cdk::println!(
+        "Scalar settlement: long fraction {}, long allocation {}, short allocation {}, transferred {}, fee {}",
+        settlement.long_fraction,
+        settlement.long_allocation,
+        settlement.short_allocation,
+        settlement.transferred,
+        settlement.platform_fee
+    );
+
+    // Sides that receive anything count as winning outcomes for display and claims
+    let winning_outcomes: Vec<OutcomeIndex> = [
+        (LONG_OUTCOME_INDEX, settlement.long_allocation),
+        (SHORT_OUTCOME_INDEX, settlement.short_allocation),
+    ]
+    .iter()
+    .filter(|(_, allocation)| *allocation > 0.0)
+    .map(|(idx, _)| OutcomeIndex::from(*idx))
+    .collect();
+
+    // The gaining side's pool is reported as the winning pool
+    let gaining_pool = if settlement.long_allocation >= long_pool {
+        market.outcome_pools[long_idx].clone()
+    } else {
+        market.outcome_pools[short_idx].clone()
+    };
+
+    let payout_model = market.effective_payout_model();
+    let mut resolution_details = MarketResolutionDetails {
+        market_id: market.id.clone(),
+        winning_outcomes: winning_outcomes.clone(),
+        resolution_timestamp: get_current_time(),
+        total_market_pool: market.total_pool.clone(),
+        total_winning_pool: gaining_pool,
+        total_profit: TokenAmount::from(settlement.transferred as u64),
+        platform_fee_amount: platform_fee.clone(),
+        platform_fee_percentage: token_info.fee_percentage,
+        fee_transaction_id: None,
+        token_id: token_id.clone(),
+        token_symbol: token_info.symbol.clone(),
+        winning_bet_count: 0,
+        used_time_weighting: payout_model.is_weighted(),
+        time_weight_alpha: payout_model.alpha(),
+        distributable_profit: TokenAmount::from((settlement.transferred - settlement.platform_fee).max(0.0) as u64),
+        total_weighted_contribution: None,
+        distribution_details: Vec::new(),
+        failed_transactions: Vec::new(),
+        scalar_resolved_value: Some(resolved_value),
+        price_snapshot,
+        creator_fee_amount: None,
+        subsidy_returned_amount: None,
+        payout_model: Some(payout_model.clone()),
+    };
+
+    // Creators of user-created markets receive a share of the platform fee
+    let creator_fee = creator_fee_amount(market, &platform_fee, &token_info);
+    // a share too small to claim stays in the protocol fee
+    let protocol_fee = match pay_creator_fee(market, &creator_fee, &platform_fee, &token_info) {
+        Some(_) => {
+            resolution_details.creator_fee_amount = Some(creator_fee.clone());
+            platform_fee.clone() - creator_fee
+        }
+        None => platform_fee.clone(),
+    };
+
+    // Process the platform fee (burn for KONG, transfer to fee collector for other tokens)
+    if protocol_fee > token_info.transfer_fee {
+        match handle_fee_transfer(protocol_fee.clone(), &token_id).await {
+            Ok(Some(tx_id)) => {
+                resolution_details.fee_transaction_id = tx_id.0.to_u64();
+            }
+            Ok(None) => {}
+            Err(e) => {
+                let error_msg = format!("{:?}", e);
+                resolution_details.failed_transactions.push(FailedTransactionInfo {
+                    market_id: Some(market.id.clone()),
+                    user: get_fee_account(token_info.is_kong),
+                    amount: protocol_fee.clone(),
+                    token_id: Some(token_id.clone()),
+                    error: error_msg.clone(),
+                    timestamp: Some(get_current_time()),
+                });
+
+                handle_fee_transfer_failure(market.id.clone(), protocol_fee.clone(), &token_info, e);
+
+                ic_cdk::println!("Error processing platform fee: {}. Continuing with distribution.", error_msg);
+            }
+        }
+    } else {
+        ic_cdk::println!("Platform fee too small to process (less than transfer fee). Skipping fee transfer.");
+    }
+
+    let bets = crate::storage::get_bets_for_market(&market.id);
+
+    // Weighted contributions per side determine each bet's share of a side's gain
+    let weight_of = |bet: &Bet| payout_model.bet_weight(market, &bet.timestamp);
+    let mut side_weighted_contribution = [0.0f64; 2];
+    for bet in &bets {
+        let side = bet.outcome_index.to_u64() as usize;
+        if side < side_weighted_contribution.len() {
+            side_weighted_contribution[side] += calculate_weighted_contribution(bet.amount.to_f64(), weight_of(bet));
+        }
+    }
+    resolution_details.total_weighted_contribution = Some(side_weighted_contribution.iter().sum());
+
+    let side_pools = [long_pool, short_pool];
+    let side_allocations = [settlement.long_allocation, settlement.short_allocation];
+
+    for bet in bets {
+        let side = bet.outcome_index.to_u64() as usize;
+        if side >= side_pools.len() {
+            continue;
+        }
+
+        let weight = weight_of(&bet);
+        let weighted_contribution = calculate_weighted_contribution(bet.amount.to_f64(), weight);
+        let (payout, bonus) = calculate_scalar_position_payout(
+            bet.amount.to_f64(),
+            weighted_contribution,
+            side_pools[side],
+            side_weighted_contribution[side],
+            side_allocations[side],
+        );
+        let gross_winnings = TokenAmount::from(payout as u64);
+
+        // Fee share is proportional to the bet's share of the gaining side's bonus
+        let user_platform_fee = if bonus > 0.0 && settlement.transferred > settlement.platform_fee {
+            Some(TokenAmount::from((settlement.platform_fee * bonus / (settlement.transferred - settlement.platform_fee)) as u64))
+        } else {
+            None
+        };
+
+        let mut detail = BetDistributionDetail {
+            user: bet.user,
+            bet_amount: bet.amount.clone(),
+            time_weight: if payout_model.is_weighted() { Some(weight) } else { None },
+            weighted_contribution: Some(weighted_contribution),
+            bonus_amount: TokenAmount::from(bonus as u64),
+            total_payout: gross_winnings.clone(),
+            outcome_index: bet.outcome_index.clone(),
+            claim_id: None,
+        };
+
+        // Skip if the payout is less than the transfer fee
+        if gross_winnings <= token_info.transfer_fee {
+            ic_cdk::println!(
+                "Skipping claim - payout {} less than fee {}",
+                gross_winnings.to_u64(),
+                token_info.transfer_fee.to_u64()
+            );
+            resolution_details.distribution_details.push(detail);
+            continue;
+        }
+
+        let claim_id = create_winning_claim(
+            bet.user,
+            market.id.clone(),
+            bet.amount.clone(),
+            vec![bet.outcome_index.clone()],
+            gross_winnings.clone(),
+            user_platform_fee.clone(),
+            token_id.clone(),
+            get_current_time(),
+        );
+        detail.claim_id = Some(claim_id);
+        resolution_details.distribution_details.push(detail);
+        resolution_details.winning_bet_count += 1;
+
+        ic_cdk::println!(
+            "Created claim {} for user {} with amount {}",
+            claim_id,
+            bet.user.to_string(),
+            gross_winnings.to_u64()
+        );
+
+        record_market_payout(BetPayoutRecord {
+            market_id: market.id.clone(),
+            user: bet.user,
+            bet_amount: bet.amount.clone(),
+            payout_amount: gross_winnings,
+            timestamp: get_current_time(),
+            outcome_index: bet.outcome_index,
+            was_time_weighted: payout_model.is_weighted(),
+            time_weight: if payout_model.is_weighted() { Some(weight) } else { None },
+            original_contribution_returned: std::cmp::min(bet.amount.clone(), TokenAmount::from(payout as u64)),
+            bonus_amount: Some(TokenAmount::from(bonus as u64)),
+            platform_fee_amount: user_platform_fee,
+            token_id: token_id.clone(),
+            token_symbol: token_info.symbol.clone(),
+            platform_fee_percentage: token_info.fee_percentage,
+            transaction_id: None, // No transaction yet, user will claim
+        });
+    }
+
+    // Record the resolved value on the market and close it
+    if let MarketType::Scalar(config) = &mut market.market_type {
+        config.resolved_value = Some(resolved_value);
+    }
+    market.status = MarketStatus::Closed(winning_outcomes.into_iter().map(|x| x.inner().clone()).collect());
+
+    crate::storage::MARKET_RESOLUTION_DETAILS.with(|details| {
+        details.borrow_mut().insert(market.id.clone(), resolution_details.clone());
+    });
+
+    ic_cdk::println!(
+        "Scalar market {} finalized at {} with {} payouts",
+        market.id.to_u64(),
+        resolved_value,
+        resolution_details.winning_bet_count
+    );
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! # Kong Pool Prices
+//!
+//! This module reads spot prices from kong_backend pools so that markets can be settled
+//! from on-chain data rather than a manual admin decision. The price of a pool is quoted
+//! as the amount of the second token per unit of the first token (e.g. `ICP_ckUSDT`
+//! returns the ckUSDT price of one ICP).
+
+use candid::{CandidType, Nat, Principal};
+use serde::{Deserialize, Serialize};
+
+use crate::canister::get_current_time;
+use crate::constants::KONG_BACKEND_CANISTER_ID;
+use crate::types::{Timestamp, TokenAmount};
+
+/// Reference to a kong_backend pool used as a price source
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct KongPoolPriceSource {
+    /// Pool symbol as returned by kong_backend's `pools` query (e.g. "ICP_ckUSDT")
+    pub pool_symbol: String,
+}
+
+impl KongPoolPriceSource {
+    /// Checks that the pool symbol has the `TOKEN0_TOKEN1` shape used by kong_backend
+    pub fn validate(&self) -> Result<(), String> {
+        let mut tokens = self.pool_symbol.split('_');
+        match (tokens.next(), tokens.next(), tokens.next()) {
+            (Some(token_0), Some(token_1), None) if !token_0.is_empty() && !token_1.is_empty() => Ok(()),
+            _ => Err(format!("Invalid Kong pool symbol: {}", self.pool_symbol)),
+        }
+    }
+}
+
+/// How a pool price is compared against a market's threshold
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum PriceComparison {
+    /// The condition holds if the price is strictly above the threshold
+    Above,
+    /// The condition holds if the price is strictly below the threshold
+    Below,
+}
+
+impl PriceComparison {
+    /// Checks whether `price` satisfies the comparison against `threshold`
+    pub fn is_met(&self, price: f64, threshold: f64) -> bool {
+        match self {
+            PriceComparison::Above => price > threshold,
+            PriceComparison::Below => price < threshold,
+        }
+    }
+}
+
+/// Pool state captured when a market is resolved from a Kong price
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct KongPriceSnapshot {
+    /// Pool symbol the price was read from
+    pub pool_symbol: String,
+    /// Spot price of token_0 quoted in token_1
+    pub price: f64,
+    /// Pool balance of token_0 at the time of the snapshot
+    pub balance_0: TokenAmount,
+    /// Pool balance of token_1 at the time of the snapshot
+    pub balance_1: TokenAmount,
+    /// Time the snapshot was taken (nanoseconds)
+    pub timestamp: Timestamp,
+}
+
+/// Subset of kong_backend's `PoolReply` needed to read a price
+#[derive(CandidType, Deserialize, Clone, Debug)]
+struct KongPoolReply {
+    symbol: String,
+    address_0: String,
+    address_1: String,
+    balance_0: Nat,
+    balance_1: Nat,
+    price: f64,
+    is_removed: bool,
+}
+
+/// Fetches the current price of a Kong pool via an inter-canister call to kong_backend
+///
+/// # Parameters
+/// * `source` - The pool to read the price from
+///
+/// # Returns
+/// * `Result<KongPriceSnapshot, String>` - The price snapshot, or an error if the call failed,
+///   the pool does not exist or the pool has no liquidity
+pub async fn fetch_kong_pool_price(source: &KongPoolPriceSource) -> Result<KongPriceSnapshot, String> {
+    source.validate()?;
+
+    let kong_backend = Principal::from_text(KONG_BACKEND_CANISTER_ID).map_err(|e| format!("Invalid kong_backend ID: {}", e))?;
+
+    let pools = match ic_cdk::call::<(Option<String>,), (Result<Vec<KongPoolReply>, String>,)>(
+        kong_backend,
+        "pools",
+        (Some(source.pool_symbol.clone()),),
+    )
+    .await
+    {
+        Ok((Ok(pools),)) => pools,
+        Ok((Err(e),)) => return Err(format!("kong_backend pools query failed: {}", e)),
+        Err((code, msg)) => return Err(format!("kong_backend call failed: {} (code: {:?})", msg, code)),
+    };
+
+    // The query is a wildcard search, so pick the exact pool
+    let pool = pools
+        .into_iter()
+        .find(|pool| {
+            !pool.is_removed
+                && (pool.symbol == source.pool_symbol || format!("{}_{}", pool.address_0, pool.address_1) == source.pool_symbol)
+        })
+        .ok_or_else(|| format!("Kong pool {} not found", source.pool_symbol))?;
+
+    if !pool.price.is_finite() || pool.price <= 0.0 {
+        return Err(format!("Kong pool {} has no valid price", source.pool_symbol));
+    }
+
+    Ok(KongPriceSnapshot {
+        pool_symbol: pool.symbol,
+        price: pool.price,
+        balance_0: TokenAmount::from(pool.balance_0),
+        balance_1: TokenAmount::from(pool.balance_1),
+        timestamp: get_current_time(),
+    })
+}
@@ -1,4 +1,6 @@
 pub mod finalize_market;
+pub mod finalize_scalar_market;
+pub mod creator_fee;
 #[allow(clippy::module_inception)]
 pub mod resolution;
 
@@ -16,5 +18,8 @@ pub mod resolution_proposal_api;
 // Other resolution modules
 pub mod resolve_via_admin;
 pub mod resolve_via_oracle;
+pub mod resolve_scalar_market;
+pub mod resolve_via_kong_price;
+pub mod kong_price;
 pub mod transfer_kong;
 pub mod void_market;
@@ -8,6 +8,7 @@
 //! - Admin resolution (centralized by platform administrators)
 //! - Oracle-based resolution (using trusted external data providers)
 //! - Decentralized resolution (community-driven with staking requirements)
+//! - Kong price resolution (automatic, from a kong_backend pool price at the end time)
 //!
 //! The dual approval system requires both the market creator and an admin to agree on
 //! the outcome for user-created markets, while admin-created markets can be resolved
@@ -19,6 +20,7 @@ use std::collections::BTreeSet;
 use ic_stable_structures::{storable::Bound, Storable};
 use std::borrow::Cow;
 
+use super::kong_price::{KongPoolPriceSource, PriceComparison};
 use crate::types::{MarketId, Timestamp, OutcomeIndex};
 
 /// Represents a single vote in the dual resolution process
@@ -111,6 +113,19 @@ pub enum ResolutionMethod {
         /// Minimum amount of stake required to reach resolution consensus
         quorum: candid::Nat,
     },
+
+    /// Automatic resolution from a kong_backend pool price once the market has ended
+    /// Only valid for binary markets: outcome 0 wins if the condition holds, outcome 1 otherwise
+    KongPrice {
+        /// Pool whose spot price is compared against the threshold
+        source: KongPoolPriceSource,
+
+        /// Price threshold, quoted in the pool's second token
+        threshold: f64,
+
+        /// Whether the price must end above or below the threshold
+        comparison: PriceComparison,
+    },
 }
 
 /// Result type for market resolution operations
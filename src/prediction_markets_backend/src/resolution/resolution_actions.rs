@@ -104,6 +104,11 @@ pub async fn force_resolve_market(
         return ResolutionResult::Error(ResolutionError::InvalidMarketStatus);
     }
     
+    // Scalar markets are resolved with a value through resolve_scalar_market
+    if market.is_scalar() {
+        return ResolutionResult::Error(ResolutionError::InvalidMethod);
+    }
+
     // Validate outcome indices
     for outcome_index in &args.winning_outcomes {
         let idx = outcome_index.to_u64() as usize;
@@ -38,6 +38,11 @@ pub async fn propose_resolution(args: ResolutionArgs) -> ResolutionResult {
         return ResolutionResult::Error(ResolutionError::Unauthorized);
     }
 
+    // Scalar markets are resolved with a value through resolve_scalar_market
+    if market.is_scalar() {
+        return ResolutionResult::Error(ResolutionError::InvalidMethod);
+    }
+
     // Ensure each outcome index is valid for this market
     for outcome_index in &winning_outcomes {
         let idx = outcome_index.to_u64() as usize;
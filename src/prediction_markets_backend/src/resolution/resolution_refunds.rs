@@ -9,6 +9,7 @@
 use crate::canister::get_current_time;
 use crate::claims::claims_processing::create_refund_claim;
 use crate::claims::claims_types::RefundReason;
+use crate::market::liquidity::refund_liquidity_subsidies;
 use crate::market::market::*;
 use crate::resolution::resolution::ResolutionError;
 use crate::token::registry::get_token_info;
@@ -31,6 +32,9 @@ use crate::types::{MarketId, TokenAmount};
 pub fn create_refund_claims(market_id: &MarketId, market: &Market, reason: &str) -> Result<(), ResolutionError> {
     ic_cdk::println!("Creating refund claims for all bets in market {}: {}", market_id, reason);
 
+    // Refund subsidy liquidity to its sponsors
+    refund_liquidity_subsidies(market);
+
     // Get all bets for this market using our helper function
     let bets = crate::storage::get_bets_for_market(market_id);
 
@@ -100,6 +104,9 @@ pub fn create_refund_claims(market_id: &MarketId, market: &Market, reason: &str)
 pub async fn create_dispute_refund_claims(market_id: &MarketId, market: &Market) -> Result<(), ResolutionError> {
     ic_cdk::println!("Creating dispute refund claims for market {}", market_id);
 
+    // Subsidies are refunded in full, only the creator's deposit is burned
+    refund_liquidity_subsidies(market);
+
     // Get all bets for this market
     let bets = crate::storage::get_bets_for_market(market_id);
 
@@ -0,0 +1,99 @@
+//! # Scalar Market Resolution
+//!
+//! Scalar markets are resolved by an admin with a numeric value once the market has ended.
+//! The value is either supplied explicitly or, if the market has a Kong pool as its
+//! resolution source and no value is given, read from the pool's current price.
+
+use candid::{CandidType, Deserialize};
+use ic_cdk::update;
+
+use super::finalize_scalar_market::finalize_scalar_market;
+use super::kong_price::fetch_kong_pool_price;
+use super::resolution::*;
+use crate::controllers::admin::is_admin;
+use crate::market::market::*;
+use crate::storage::{MARKETS, RESOLUTION_PROPOSALS};
+use crate::types::MarketId;
+
+/// Arguments for resolving a scalar market
+#[derive(CandidType, Clone, Debug, Deserialize)]
+pub struct ScalarResolutionArgs {
+    /// ID of the scalar market to resolve
+    pub market_id: MarketId,
+    /// The resolved value; if omitted, the market's Kong pool price source is used
+    pub resolved_value: Option<f64>,
+}
+
+/// Resolves a scalar market on a numeric value (admin only)
+///
+/// # Parameters
+/// * `args` - Market ID and optional resolved value
+///
+/// # Returns
+/// * `ResolutionResult` - Success or error reason if the resolution fails
+///
+/// # Security
+/// Only admins can call this function.
+#[update]
+pub async fn resolve_scalar_market(args: ScalarResolutionArgs) -> ResolutionResult {
+    let caller = ic_cdk::caller();
+    if !is_admin(caller) {
+        return ResolutionResult::Error(ResolutionError::Unauthorized);
+    }
+
+    let mut market = match MARKETS.with(|markets| markets.borrow().get(&args.market_id)) {
+        Some(market) => market,
+        None => return ResolutionResult::Error(ResolutionError::MarketNotFound),
+    };
+
+    let config = match market.scalar_config() {
+        Some(config) => config.clone(),
+        None => return ResolutionResult::Error(ResolutionError::InvalidMethod),
+    };
+
+    if !matches!(market.status, MarketStatus::Active | MarketStatus::ExpiredUnresolved) {
+        return ResolutionResult::Error(ResolutionError::InvalidMarketStatus);
+    }
+
+    // Bets are accepted until the end time, so the market can't be resolved before it
+    if ic_cdk::api::time() < market.end_time.to_u64() {
+        return ResolutionResult::Error(ResolutionError::MarketStillOpen);
+    }
+
+    // Use the supplied value, or read it from the configured Kong pool
+    let (resolved_value, price_snapshot) = match (args.resolved_value, &config.resolution_source) {
+        (Some(value), _) => (value, None),
+        (None, Some(source)) => match fetch_kong_pool_price(source).await {
+            Ok(snapshot) => (snapshot.price, Some(snapshot)),
+            Err(e) => return ResolutionResult::Error(ResolutionError::TransferError(e)),
+        },
+        (None, None) => return ResolutionResult::Error(ResolutionError::InvalidOutcome),
+    };
+
+    // Re-read the market after the price call, its status may have changed in the meantime
+    if price_snapshot.is_some() {
+        market = match MARKETS.with(|markets| markets.borrow().get(&args.market_id)) {
+            Some(market) if matches!(market.status, MarketStatus::Active | MarketStatus::ExpiredUnresolved) => market,
+            Some(_) => return ResolutionResult::Error(ResolutionError::InvalidMarketStatus),
+            None => return ResolutionResult::Error(ResolutionError::MarketNotFound),
+        };
+    }
+
+    ic_cdk::println!("Admin {} is resolving scalar market {} at {}", caller, args.market_id, resolved_value);
+
+    if let Err(e) = finalize_scalar_market(&mut market, resolved_value, price_snapshot).await {
+        return ResolutionResult::Error(e);
+    }
+
+    market.resolved_by = Some(caller);
+
+    MARKETS.with(|markets| {
+        markets.borrow_mut().insert(args.market_id.clone(), market);
+    });
+
+    RESOLUTION_PROPOSALS.with(|proposals| {
+        proposals.borrow_mut().remove(&args.market_id);
+    });
+
+    ResolutionResult::Success
+}
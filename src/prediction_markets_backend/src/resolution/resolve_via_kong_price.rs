@@ -0,0 +1,173 @@
+//! # Kong Price Resolution
+//!
+//! Markets using `ResolutionMethod::KongPrice` are resolved without admin involvement.
+//! Alongside the expired-markets heartbeat, every ended price market is settled from its
+//! kong_backend pool: outcome 0 wins if the price meets the threshold condition, outcome 1
+//! otherwise. The pool snapshot used is stored in the market's resolution details.
+//!
+//! If kong_backend cannot be reached or the pool has no valid price, the market stays in
+//! `ExpiredUnresolved` and is retried on the next check; admins can still resolve it manually.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use ic_cdk::update;
+
+use super::finalize_market::finalize_market;
+use super::kong_price::{fetch_kong_pool_price, KongPriceSnapshot};
+use super::resolution::*;
+use crate::controllers::admin::is_admin;
+use crate::market::market::*;
+use crate::storage::{MARKETS, MARKET_RESOLUTION_DETAILS, RESOLUTION_PROPOSALS};
+use crate::types::{MarketId, OutcomeIndex};
+
+/// Start time of the running price resolution run (0 if none), so heartbeats don't start overlapping runs
+static PRICE_RESOLUTION_STARTED_AT: AtomicU64 = AtomicU64::new(0);
+
+/// A run older than this is considered abandoned (e.g. it trapped after an inter-canister call)
+const PRICE_RESOLUTION_TIMEOUT_NS: u64 = 10 * 60 * 1_000_000_000;
+
+/// Marks a run as started, returning false if another run is still in progress
+fn try_start_run(now: u64) -> bool {
+    let started_at = PRICE_RESOLUTION_STARTED_AT.load(Ordering::SeqCst);
+    if started_at != 0 && now.saturating_sub(started_at) < PRICE_RESOLUTION_TIMEOUT_NS {
+        return false;
+    }
+    PRICE_RESOLUTION_STARTED_AT.store(now, Ordering::SeqCst);
+    true
+}
+
+/// Starts a price resolution run in the background unless one is already running
+///
+/// Called from the expired-markets heartbeat.
+pub fn spawn_kong_price_resolution() {
+    let now = ic_cdk::api::time();
+    if !try_start_run(now) {
+        return;
+    }
+    ic_cdk::spawn(async move {
+        let resolved = resolve_kong_price_markets_impl(now).await;
+        if resolved > 0 {
+            ic_cdk::println!("Resolved {} markets from Kong pool prices", resolved);
+        }
+        PRICE_RESOLUTION_STARTED_AT.store(0, Ordering::SeqCst);
+    });
+}
+
+/// Manual trigger for resolving ended Kong price markets (admin only)
+///
+/// # Returns
+/// * `Result<u64, ResolutionError>` - Number of markets resolved (0 if a run is already in
+///   progress), or an error if the caller is not an admin
+#[update]
+pub async fn resolve_kong_price_markets() -> Result<u64, ResolutionError> {
+    if !is_admin(ic_cdk::caller()) {
+        return Err(ResolutionError::Unauthorized);
+    }
+    let now = ic_cdk::api::time();
+    if !try_start_run(now) {
+        ic_cdk::println!("Kong price resolution already in progress");
+        return Ok(0);
+    }
+
+    let resolved = resolve_kong_price_markets_impl(now).await;
+    PRICE_RESOLUTION_STARTED_AT.store(0, Ordering::SeqCst);
+
+    Ok(resolved)
+}
+
+/// Resolves every Kong price market that has ended and is not yet resolved
+async fn resolve_kong_price_markets_impl(now: u64) -> u64 {
+    let due_markets: Vec<MarketId> = MARKETS.with(|markets| {
+        markets
+            .borrow()
+            .iter()
+            .filter(|(_, market)| {
+                matches!(market.resolution_method, ResolutionMethod::KongPrice { .. })
+                    && matches!(market.status, MarketStatus::Active | MarketStatus::ExpiredUnresolved)
+                    && now >= market.end_time.to_u64()
+            })
+            .map(|(id, _)| id)
+            .collect()
+    });
+
+    // Markets on the same pool share one price read per run
+    let mut snapshots: BTreeMap<String, KongPriceSnapshot> = BTreeMap::new();
+    let mut resolved = 0;
+
+    for market_id in due_markets {
+        let Some(market) = MARKETS.with(|markets| markets.borrow().get(&market_id)) else {
+            continue;
+        };
+        let ResolutionMethod::KongPrice { source, .. } = &market.resolution_method else {
+            continue;
+        };
+
+        if !snapshots.contains_key(&source.pool_symbol) {
+            match fetch_kong_pool_price(source).await {
+                Ok(snapshot) => {
+                    snapshots.insert(source.pool_symbol.clone(), snapshot);
+                }
+                Err(e) => {
+                    ic_cdk::println!("Could not read Kong price for market {}: {}", market_id, e);
+                    continue;
+                }
+            }
+        }
+        let snapshot = snapshots[&source.pool_symbol].clone();
+
+        match resolve_from_snapshot(&market_id, snapshot).await {
+            Ok(()) => resolved += 1,
+            Err(e) => ic_cdk::println!("Failed to resolve market {} from Kong price: {:?}", market_id, e),
+        }
+    }
+
+    resolved
+}
+
+/// Finalizes a single Kong price market using the given pool snapshot
+async fn resolve_from_snapshot(market_id: &MarketId, snapshot: KongPriceSnapshot) -> Result<(), ResolutionError> {
+    // Re-read the market, it may have been resolved while prices were being fetched
+    let mut market = MARKETS
+        .with(|markets| markets.borrow().get(market_id))
+        .ok_or(ResolutionError::MarketNotFound)?;
+    if !matches!(market.status, MarketStatus::Active | MarketStatus::ExpiredUnresolved) {
+        return Err(ResolutionError::AlreadyResolved);
+    }
+
+    let (threshold, comparison) = match &market.resolution_method {
+        ResolutionMethod::KongPrice { threshold, comparison, .. } => (*threshold, comparison.clone()),
+        _ => return Err(ResolutionError::InvalidMethod),
+    };
+
+    let winning_outcome = if comparison.is_met(snapshot.price, threshold) { 0u64 } else { 1u64 };
+    ic_cdk::println!(
+        "Resolving market {} from {} price {} ({:?} {}): outcome {}",
+        market_id,
+        snapshot.pool_symbol,
+        snapshot.price,
+        comparison,
+        threshold,
+        winning_outcome
+    );
+
+    finalize_market(&mut market, vec![OutcomeIndex::from(winning_outcome)]).await?;
+
+    market.resolved_by = Some(ic_cdk::id());
+    MARKETS.with(|markets| {
+        markets.borrow_mut().insert(market_id.clone(), market);
+    });
+
+    // Attach the price snapshot to the stored resolution details
+    MARKET_RESOLUTION_DETAILS.with(|details| {
+        if let Some(resolution_details) = details.borrow_mut().get_mut(market_id) {
+            resolution_details.price_snapshot = Some(snapshot);
+        }
+    });
+
+    RESOLUTION_PROPOSALS.with(|proposals| {
+        proposals.borrow_mut().remove(market_id);
+    });
+
+    Ok(())
+}
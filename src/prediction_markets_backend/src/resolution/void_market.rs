@@ -113,6 +113,9 @@ pub async fn void_market(market_id: MarketId) -> Result<(), ResolutionError> {
         );
     }
 
+    // Refund subsidy liquidity to its sponsors
+    crate::market::liquidity::refund_liquidity_subsidies(&market);
+
     // Update market status to Voided - this happens even if some refunds failed
     // to prevent future resolution attempts on this market
     market.status = MarketStatus::Voided;
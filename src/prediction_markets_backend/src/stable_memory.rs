@@ -7,6 +7,7 @@
 //! The stable memory system stores:
 //! - Markets with their complete configurations and states
 //! - Bets placed by users on each market
+//! - Combo (parlay/conditional) bets spanning several markets, and the reserves backing them
 //! - Resolution proposals for the dual approval system
 //! - User delegations and oracle whitelist
 //!
@@ -26,6 +27,8 @@ use crate::{types::StorableNat, ClaimRecord};
 
 use super::delegation::*;
 
+use crate::bet::combo_bet::ComboBet;
+use crate::bet::combo_reserve::ComboReserve;
 use crate::failed_transaction::FailedTransaction;
 use crate::market::create_market::MARKET_ID;
 use crate::market::market::*;
@@ -147,6 +150,21 @@ thread_local! {
 
     pub static STABLE_FAILED_TRANSACTIONS: RefCell<StableBTreeMap<u64, FailedTransaction, Memory>> = RefCell::new(
         StableBTreeMap::init(MEMORY_MANAGER.with(|mm| mm.borrow().get(MemoryId::new(14))))
+    );
+
+    /// Stable BTree map for combo (parlay/conditional) bets indexed by combo ID
+    pub static STABLE_COMBO_BETS: RefCell<StableBTreeMap<u64, ComboBet, Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|mm| mm.borrow().get(MemoryId::new(15))))
+    );
+
+    /// Index of pending combo bets by the markets they reference, keyed by (market ID, combo ID)
+    pub static STABLE_COMBO_MARKET_INDEX: RefCell<StableBTreeMap<(u64, u64), (), Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|mm| mm.borrow().get(MemoryId::new(16))))
+    );
+
+    /// House reserves backing combo bet payouts, indexed by token
+    pub static STABLE_COMBO_RESERVES: RefCell<StableBTreeMap<TokenIdentifier, ComboReserve, Memory>> = RefCell::new(
+        StableBTreeMap::init(MEMORY_MANAGER.with(|mm| mm.borrow().get(MemoryId::new(17))))
     )
 }
 
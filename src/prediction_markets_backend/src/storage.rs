@@ -13,6 +13,9 @@ use std::collections::HashMap;
 // Re-export stable memory variables with the names expected in the rest of the codebase
 pub use crate::stable_memory::STABLE_MARKETS as MARKETS;
 pub use crate::stable_memory::STABLE_BETS as BETS;
+pub use crate::stable_memory::STABLE_COMBO_BETS as COMBO_BETS;
+pub use crate::stable_memory::STABLE_COMBO_MARKET_INDEX as COMBO_MARKET_INDEX;
+pub use crate::stable_memory::STABLE_COMBO_RESERVES as COMBO_RESERVES;
 pub use crate::stable_memory::FEE_BALANCE;
 pub use crate::stable_memory::STABLE_RESOLUTION_PROPOSALS as RESOLUTION_PROPOSALS;
 pub use crate::stable_memory::STABLE_DELEGATIONS as DELEGATIONS;
@@ -8,12 +8,14 @@ use candid::{CandidType, Deserialize, Nat};
 use serde::Serialize;
 use std::cell::RefCell;
 
+use crate::bet::combo_reserve::combo_reserve_holdings;
 use crate::canister::get_current_time;
 use crate::claims::claims_storage::CLAIMS;
 use crate::claims::claims_types::ClaimStatus;
 use crate::market::market::{Market, MarketStatus};
+use crate::market::scalar::{calculate_scalar_settlement, ScalarMarketConfig, LONG_OUTCOME_INDEX, SHORT_OUTCOME_INDEX};
 use crate::nat::StorableNat;
-use crate::storage::{BETS, MARKETS};
+use crate::storage::{BETS, MARKETS, MARKET_RESOLUTION_DETAILS};
 use crate::token::registry::{get_supported_token_identifiers, get_token_info, TokenInfo};
 use crate::types::{MarketId, Timestamp, TokenAmount, TokenIdentifier};
 use candid::Principal;
@@ -60,6 +62,10 @@ pub struct TokenBalanceBreakdown {
     pub platform_fees: TokenAmount,
     /// Platform fees collected from disputed penalties
     pub disputed_penalty_fees: TokenAmount,
+    /// Stakes of pending combo bets
+    pub combo_stakes: TokenAmount,
+    /// Combo reserve backing combo bet payouts
+    pub combo_reserve: TokenAmount,
 }
 
 /// Summary of all token balances
@@ -139,6 +145,8 @@ async fn calculate_single_token_balance(token_id: &TokenIdentifier) -> TokenBala
     let mut platform_fees = TokenAmount::from(0u64);
     let mut disputed_penalty_fees = TokenAmount::from(0u64);
     let pending_claims = calculate_pending_claims_total(token_id);
+    // Combo stakes are held outside the market pools, alongside the combo reserve
+    let (combo_reserve, combo_stakes) = combo_reserve_holdings(token_id);
 
     // Calculate totals from markets
     MARKETS.with(|markets| {
@@ -190,7 +198,9 @@ async fn calculate_single_token_balance(token_id: &TokenIdentifier) -> TokenBala
         + expired_markets.clone()
         // + platform_fees.clone()  // Don't include platform fees since they already withdrawn
         // + void_penalty_fees.clone() // Don't include void penalty fees since they already withdrawn
-        + pending_claims.clone();
+        + pending_claims.clone()
+        + combo_stakes.clone()
+        + combo_reserve.clone();
 
     // Calculate difference and determine if balance is sufficient
     let (difference, is_sufficient) = if actual_balance >= expected_balance {
@@ -219,6 +229,8 @@ async fn calculate_single_token_balance(token_id: &TokenIdentifier) -> TokenBala
             pending_claims,
             platform_fees,
             disputed_penalty_fees,
+            combo_stakes,
+            combo_reserve,
         },
         timestamp: get_current_time(),
     }
@@ -333,6 +345,21 @@ fn calculate_market_bet_total(market_id: &MarketId, token_id: &TokenIdentifier)
 
 /// Calculate platform fees for a market
 pub fn calculate_platform_fees_for_market(market: &Market, winning_outcomes: &Vec<Nat>, token_info: &TokenInfo) -> TokenAmount {
+    let fee_amount = match market.scalar_config() {
+        Some(config) => calculate_scalar_platform_fee(market, config, token_info),
+        None => calculate_categorical_platform_fee(market, winning_outcomes, token_info),
+    };
+
+    // The creator's share of the fee is paid out as a claim, so it's not a platform fee
+    let creator_fee = MARKET_RESOLUTION_DETAILS
+        .with(|details| details.borrow().get(&market.id).and_then(|d| d.creator_fee_amount.clone()))
+        .unwrap_or_else(|| TokenAmount::from(0u64));
+
+    TokenAmount::from(fee_amount - creator_fee)
+}
+
+/// Platform fee of a categorical market, charged on the losing pools
+fn calculate_categorical_platform_fee(market: &Market, winning_outcomes: &[Nat], token_info: &TokenInfo) -> TokenAmount {
     let to_u64 = |v: &Nat| -> u64 { v.0.to_u64_digits().first().cloned().unwrap_or(0) };
     let outcome_pools = market.outcome_pools.clone();
 
@@ -340,9 +367,19 @@ pub fn calculate_platform_fees_for_market(market: &Market, winning_outcomes: &Ve
     let winning_pool_total: StorableNat = winning_outcomes.iter().map(|i| outcome_pools[to_u64(i) as usize].clone()).sum();
     let lost_total: StorableNat = market_total - winning_pool_total;
 
-    let fee_amount = (lost_total * token_info.fee_percentage) / 10000;
+    (lost_total * token_info.fee_percentage) / 10000
+}
+
+/// Platform fee of a scalar market, charged on the amount moving between the long and short pools
+fn calculate_scalar_platform_fee(market: &Market, config: &ScalarMarketConfig, token_info: &TokenInfo) -> TokenAmount {
+    let Some(resolved_value) = config.resolved_value else {
+        return TokenAmount::from(0u64);
+    };
+    let long_pool = market.outcome_pools[LONG_OUTCOME_INDEX as usize].to_f64();
+    let short_pool = market.outcome_pools[SHORT_OUTCOME_INDEX as usize].to_f64();
 
-    TokenAmount::from(fee_amount)
+    let settlement = calculate_scalar_settlement(config.long_fraction(resolved_value), long_pool, short_pool, token_info.fee_percentage);
+    TokenAmount::from(settlement.platform_fee as u64)
 }
 
 /// Calculate total pending claims for a token
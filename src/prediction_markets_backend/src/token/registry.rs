@@ -21,6 +21,7 @@ use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 
+use crate::constants::DEFAULT_CREATOR_FEE_SHARE_BPS;
 use crate::types::{StorableNat, TokenAmount};
 
 /// Token identifier type, represented as a canister Principal ID in string form
@@ -72,6 +73,18 @@ pub struct TokenInfo {
     /// This defines the threshold that must be met before a market becomes active
     /// Examples: 3000 KONG (300_000_000_000 units), 25 ICP (2_500_000_000 units)
     pub activation_fee: TokenAmount,
+
+    /// Share of the platform fee paid to creators of user-created markets, in basis points
+    /// of the fee (2000 = 20% of the fee). Uses DEFAULT_CREATOR_FEE_SHARE_BPS if not set
+    #[serde(default)]
+    pub creator_fee_share: Option<u64>,
+}
+
+impl TokenInfo {
+    /// Creator fee share in basis points of the platform fee
+    pub fn creator_fee_share_bps(&self) -> u64 {
+        self.creator_fee_share.unwrap_or(DEFAULT_CREATOR_FEE_SHARE_BPS).min(10000)
+    }
 }
 
 impl Storable for TokenInfo {
@@ -107,6 +120,7 @@ pub fn init() {
                 is_kong: true,
                 transfer_fee: StorableNat::from(10_000u64),            // 0.0001 KONG
                 activation_fee: StorableNat::from(300_000_000_000u64), // 3000 KONG
+                creator_fee_share: None,
             },
         );
 
@@ -122,6 +136,7 @@ pub fn init() {
                 is_kong: true,
                 transfer_fee: StorableNat::from(10_000u64),            // 0.0001 KONG
                 activation_fee: StorableNat::from(300_000_000_000u64), // 3000 KONG
+                creator_fee_share: None,
             },
         );
 
@@ -137,6 +152,7 @@ pub fn init() {
                 is_kong: false,
                 transfer_fee: StorableNat::from(1_000u64),         // 0.001 USDT
                 activation_fee: StorableNat::from(100_000_000u64), // 100 ksUSDT
+                creator_fee_share: None,
             },
         );
 
@@ -152,6 +168,7 @@ pub fn init() {
                 is_kong: false,
                 transfer_fee: StorableNat::from(10_000u64),          // 0.0001 ICP
                 activation_fee: StorableNat::from(2_500_000_000u64), // 25 ICP
+                creator_fee_share: None,
             },
         );
 
@@ -167,6 +184,7 @@ pub fn init() {
                 is_kong: false,
                 transfer_fee: StorableNat::from(1_000u64),         // 0.001 USDT
                 activation_fee: StorableNat::from(100_000_000u64), // 100 ckUSDT
+                creator_fee_share: None,
             },
         );
 
@@ -182,6 +200,7 @@ pub fn init() {
                 is_kong: false,
                 transfer_fee: StorableNat::from(1_000u64),         // 0.001 USDC
                 activation_fee: StorableNat::from(100_000_000u64), // 100 ckUSDC
+                creator_fee_share: None,
             },
         );
 
@@ -197,6 +216,7 @@ pub fn init() {
                 is_kong: false,
                 transfer_fee: StorableNat::from(10u64),        // 0.0000001 BTC
                 activation_fee: StorableNat::from(100_000u64), // 0.001 ckBTC
+                creator_fee_share: None,
             },
         );
 
@@ -212,6 +232,7 @@ pub fn init() {
                 is_kong: false,
                 transfer_fee: StorableNat::from(10_000u64),              // 0.0001 DKP
                 activation_fee: StorableNat::from(7_000_000_000_000u64), // 70000 DKP
+                creator_fee_share: None,
             },
         );
 
@@ -227,6 +248,7 @@ pub fn init() {
                 is_kong: false,
                 transfer_fee: StorableNat::from(1_000u64),            // 0.00001 GLDT
                 activation_fee: StorableNat::from(10_000_000_000u64), // 100 GLDT
+                creator_fee_share: None,
             },
         );
 
@@ -6,6 +6,8 @@ use serde::Serialize;
 
 // Re-export StorableNat for convenience
 pub use crate::nat::StorableNat;
+use crate::market::payout_model::PayoutModel;
+use crate::resolution::kong_price::KongPriceSnapshot;
 use crate::token::registry::TokenInfo;
 
 use ic_stable_structures::{storable::Bound, Storable};
@@ -97,6 +99,21 @@ pub struct MarketResolutionDetails {
     pub distribution_details: Vec<BetDistributionDetail>,
     /// Any failed transactions that occurred during payout
     pub failed_transactions: Vec<FailedTransactionInfo>,
+    /// Value a scalar market was settled on (None for categorical markets)
+    #[serde(default)]
+    pub scalar_resolved_value: Option<f64>,
+    /// Kong pool price the market was resolved from (if resolved from a price source)
+    #[serde(default)]
+    pub price_snapshot: Option<KongPriceSnapshot>,
+    /// Part of the platform fee paid to the market creator (user-created markets only)
+    #[serde(default)]
+    pub creator_fee_amount: Option<TokenAmount>,
+    /// Subsidy liquidity returned to sponsors
+    #[serde(default)]
+    pub subsidy_returned_amount: Option<TokenAmount>,
+    /// Payout model the winnings were distributed with
+    #[serde(default)]
+    pub payout_model: Option<PayoutModel>,
 }
 
 impl Storable for MarketResolutionDetails {
@@ -5,8 +5,9 @@ use std::collections::HashMap;
 use super::user::*;
 
 use crate::market::market::*;
-use crate::utils::time_weighting::*;
-use crate::types::{TokenAmount, StorableNat, Timestamp};
+use crate::market::payout_model::{locked_payout, BetWeighting};
+use crate::utils::time_weighting::calculate_weighted_contribution;
+use crate::types::{TokenAmount, StorableNat};
 use crate::storage::{MARKETS, BETS, get_bets_for_market};
 
 /// Get betting history for a specific user
@@ -60,22 +61,13 @@ pub fn get_user_history(user: Principal) -> UserHistory {
                                 let mut winnings = StorableNat::from(0u64);
                                 
                                 if winning_outcomes.iter().any(|n| candid::Nat::from(bet.outcome_index.clone()) == *n) {
-                                    // Check if this is a time-weighted market
-                                    if market.uses_time_weighting {
-                                        // For time-weighted markets, calculate using time-weighted formula
-                                        let market_created_at = market.created_at.to_u64() as f64;
-                                        let market_end_time = market.end_time.to_u64() as f64;
-                                        let alpha = get_market_alpha(&market);
-                                        
-                                        // Calculate time-based weight for this bet
-                                        // Weight is used for other bets in the market pool, but not directly in this calculation
-                                        let _bet_weight = calculate_time_weight(
-                                            Timestamp::from(market_created_at as u64),
-                                            Timestamp::from(market_end_time as u64),
-                                            bet.timestamp.clone(),
-                                            alpha
-                                        );
-                                        
+                                    // Check if this market weights bets (time-weighted or another payout model)
+                                    let payout_model = market.effective_payout_model();
+                                    if payout_model.is_odds_locked() {
+                                        // Odds-locked markets pay the odds recorded with the bet
+                                        winnings = StorableNat::from(locked_payout(bet.amount.to_f64(), bet.locked_odds) as u64);
+                                    } else if payout_model.is_weighted() {
+                                        // For weighted markets, calculate using the market's payout model
                                         // Calculate total winning pool and weighted contributions
                                         let mut total_winning_pool = StorableNat::from(0u64);
                                         let mut weighted_contributions: Vec<(Principal, StorableNat, f64, f64)> = Vec::new();
@@ -89,12 +81,7 @@ pub fn get_user_history(user: Principal) -> UserHistory {
                                                 total_winning_pool += other_bet.amount.clone();
                                                 
                                                 // Calculate weight for this winning bet
-                                                let other_weight = calculate_time_weight(
-                                                    Timestamp::from(market_created_at as u64),
-                                                    Timestamp::from(market_end_time as u64),
-                                                    other_bet.timestamp.clone(),
-                                                    alpha
-                                                );
+                                                let other_weight = payout_model.bet_weight(&market, &other_bet.timestamp);
                                                 
                                                 let weighted_contribution = calculate_weighted_contribution(
                                                     other_bet.amount.to_f64(),
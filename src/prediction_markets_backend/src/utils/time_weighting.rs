@@ -14,6 +14,9 @@
 //! With α = 0.1, a bet placed at the start of the market receives 10x the weight
 //! of a bet placed at the end, significantly increasing the potential reward for
 //! early bettors who make correct predictions.
+//!
+//! This is the `Exponential` payout model; markets can choose other incentive curves,
+//! see `market::payout_model`.
 
 use crate::market::market::*;
 use crate::types::Timestamp;
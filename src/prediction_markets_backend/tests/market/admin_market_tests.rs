@@ -1,4 +1,4 @@
-use candid::{Principal, encode_args, decode_one, Nat};
+use candid::{Principal, encode_args, encode_one, decode_one, Nat};
 use crate::common::{setup_prediction_markets_canister, ADMIN_PRINCIPALS};
 
 #[derive(candid::CandidType)]
@@ -22,6 +22,20 @@ enum MarketEndTime {
     Duration(Nat),
 }
 
+#[derive(candid::CandidType)]
+struct CreateMarketArgs {
+    question: String,
+    category: MarketCategory,
+    rules: String,
+    outcomes: Vec<String>,
+    resolution_method: ResolutionMethod,
+    end_time: MarketEndTime,
+    image_url: Option<String>,
+    uses_time_weighting: Option<bool>,
+    time_weight_alpha: Option<f64>,
+    token_id: Option<String>,
+}
+
 /// Test for admin market creation with simple parameters using direct candid encoding
 #[test]
 fn test_admin_market_creation_simple() {
@@ -50,8 +64,8 @@ fn test_admin_market_creation_simple() {
     let time_weight_alpha = Some(0.1);
     let token_id = Option::<String>::None;
     
-    // Encode arguments using Candid's encode_args
-    let args = encode_args((
+    // Encode arguments using Candid's encode_one
+    let args = encode_one(CreateMarketArgs {
         question,
         category,
         rules,
@@ -62,7 +76,7 @@ fn test_admin_market_creation_simple() {
         uses_time_weighting,
         time_weight_alpha,
         token_id,
-    )).expect("Failed to encode market creation arguments");
+    }).expect("Failed to encode market creation arguments");
     
     println!("Market creation arguments encoded successfully");
     
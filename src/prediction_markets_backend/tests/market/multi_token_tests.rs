@@ -1,4 +1,4 @@
-use candid::{Principal, encode_args, decode_one, Nat};
+use candid::{Principal, encode_args, encode_one, decode_one, Nat};
 use crate::common::{setup_prediction_markets_canister, ADMIN_PRINCIPALS};
 
 #[derive(candid::CandidType)]
@@ -22,6 +22,20 @@ enum MarketEndTime {
     Duration(Nat),
 }
 
+#[derive(candid::CandidType)]
+struct CreateMarketArgs {
+    question: String,
+    category: MarketCategory,
+    rules: String,
+    outcomes: Vec<String>,
+    resolution_method: ResolutionMethod,
+    end_time: MarketEndTime,
+    image_url: Option<String>,
+    uses_time_weighting: Option<bool>,
+    time_weight_alpha: Option<f64>,
+    token_id: Option<String>,
+}
+
 /// Test for multi-token markets (both admin and user-created), including negative scenarios
 #[test]
 fn test_multi_token_markets() {
@@ -247,10 +261,10 @@ fn create_market_with_token(
     let time_weight_alpha = Some(0.1);     // Alpha value from memory
     
     // Encode arguments
-    let args = encode_args((
-        question.to_string(),
+    let args = encode_one(CreateMarketArgs {
+        question: question.to_string(),
         category,
-        rules.to_string(),
+        rules: rules.to_string(),
         outcomes,
         resolution_method,
         end_time,
@@ -258,7 +272,7 @@ fn create_market_with_token(
         uses_time_weighting,
         time_weight_alpha,
         token_id,
-    )).expect("Failed to encode market creation arguments");
+    }).expect("Failed to encode market creation arguments");
     
     // Make the call
     let result = pic.update_call(
@@ -112,6 +112,20 @@ enum MarketEndTime {
     Duration(Nat),
 }
 
+#[derive(candid::CandidType)]
+struct CreateMarketArgs {
+    question: String,
+    category: MarketCategory,
+    rules: String,
+    outcomes: Vec<String>,
+    resolution_method: ResolutionMethod,
+    end_time: MarketEndTime,
+    image_url: Option<String>,
+    uses_time_weighting: Option<bool>,
+    time_weight_alpha: Option<f64>,
+    token_id: Option<String>,
+}
+
 #[derive(candid::CandidType)]
 enum MarketStatus {
     Active,
@@ -154,8 +168,8 @@ fn test_user_market_creation_with_activation() {
     let time_weight_alpha = Some(0.1);
     let token_id = Option::<String>::None; // Default to KONG token
     
-    // Encode arguments using Candid's encode_args
-    let args = encode_args((
+    // Encode arguments using Candid's encode_one
+    let args = encode_one(CreateMarketArgs {
         question,
         category,
         rules,
@@ -166,7 +180,7 @@ fn test_user_market_creation_with_activation() {
         uses_time_weighting,
         time_weight_alpha,
         token_id,
-    )).expect("Failed to encode market creation arguments");
+    }).expect("Failed to encode market creation arguments");
     
     println!("Market creation arguments encoded successfully");
     
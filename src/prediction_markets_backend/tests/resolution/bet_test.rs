@@ -1,4 +1,4 @@
-use candid::{Principal, encode_args, decode_one, Nat};
+use candid::{Principal, encode_args, encode_one, decode_one, Nat};
 use serde::Deserialize;
 use num_traits::cast::ToPrimitive;
 use crate::common::{setup_complete_test_environment, TEST_USER_PRINCIPALS, ADMIN_PRINCIPALS};
@@ -104,6 +104,20 @@ enum MarketEndTime {
     Duration(Nat),
 }
 
+#[derive(candid::CandidType)]
+struct CreateMarketArgs {
+    question: String,
+    category: MarketCategory,
+    rules: String,
+    outcomes: Vec<String>,
+    resolution_method: ResolutionMethod,
+    end_time: MarketEndTime,
+    image_url: Option<String>,
+    uses_time_weighting: Option<bool>,
+    time_weight_alpha: Option<f64>,
+    token_id: Option<String>,
+}
+
 /// Helper function to query user token balance
 fn query_balance(pic: &PocketIc, token_canister_id: Principal, account_principal: Principal) -> u64 {
     // Create account structure
@@ -287,18 +301,18 @@ fn test_user_market_with_multiple_bets() {
     let token_id = Option::<String>::None; // Default to KONG token
     
     // Encode market creation arguments
-    let create_args = encode_args((
-        question.clone(),
+    let create_args = encode_one(CreateMarketArgs {
+        question: question.clone(),
         category,
-        rules.clone(),
-        outcomes.clone(), 
+        rules: rules.clone(),
+        outcomes: outcomes.clone(),
         resolution_method,
         end_time,
         image_url,
         uses_time_weighting,
         time_weight_alpha,
         token_id,
-    )).expect("Failed to encode market creation arguments");
+    }).expect("Failed to encode market creation arguments");
     
     // Alice creates the market (will be in pending activation status)
     let creation_result = pic.update_call(
@@ -0,0 +1,223 @@
+use anyhow::Result;
+use candid::Nat;
+use std::env;
+use std::sync::Arc;
+use tokio::time::Duration;
+
+use kong_sdk::agent::{create_agent, create_random_identity};
+use kong_sdk::kong_backend::KongBackend;
+use kong_sdk::kong_faucet::KongFaucet;
+use kong_sdk::network::Network;
+use kong_sdk::strategy::price_feed::{FixedPriceFeed, KongPriceFeed, PriceFeed};
+use kong_sdk::strategy::risk::RiskLimits;
+use kong_sdk::strategy::scheduler::Scheduler;
+use kong_sdk::strategy::state_store::StateStore;
+use kong_sdk::strategy::strategies::liquidity_cycle::LiquidityCycle;
+use kong_sdk::strategy::strategies::pool_arbitrage::PoolArbitrage;
+use kong_sdk::strategy::strategies::random_swaps::RandomSwaps;
+use kong_sdk::strategy::Strategy;
+
+const STATE_DIR: &str = "./bot_state";
+const INTERVAL: Duration = Duration::from_secs(5);
+
+/// where the bots run, either one of Kong Swap's networks or a local PocketIC instance
+struct Target {
+    replica_url: String,
+    is_mainnet: bool,
+    kong_backend: candid::Principal,
+    kong_faucet: Option<candid::Principal>,
+    #[cfg(feature = "pocket_ic")]
+    dry_run: Option<kong_sdk::dry_run::DryRun>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = env::args().collect::<Vec<String>>();
+    let target = target_from_args(&args).await?;
+
+    let bots: Vec<(Box<dyn Strategy>, RiskLimits)> = vec![
+        (
+            Box::new(RandomSwaps::new(
+                "ICP",
+                (10_000_000, 20_000_000), // 0.10 ICP to 0.20 ICP
+                "ckUSDT",
+                (500_000, 2_000_000), // 0.5 ckUSDT to 2 ckUSDT
+                INTERVAL,
+            )),
+            RiskLimits::default().with_max_order_amount("ICP", Nat::from(100_000_000_u64)),
+        ),
+        (
+            Box::new(RandomSwaps::new(
+                "ckUSDC",
+                (500_000, 2_000_000), // 0.5 ckUSDC to 2 ckUSDC
+                "ckUSDT",
+                (500_000, 2_000_000),
+                INTERVAL,
+            )),
+            RiskLimits::default(),
+        ),
+        (
+            Box::new(RandomSwaps::new(
+                "ckBTC",
+                (1_000, 5_000), // 0.00001 ckBTC to 0.00005 ckBTC
+                "ckUSDT",
+                (500_000, 2_000_000),
+                INTERVAL,
+            )),
+            RiskLimits::default(),
+        ),
+        (
+            Box::new(RandomSwaps::new(
+                "ckETH",
+                (200_000_000_000_000, 1_000_000_000_000_000), // 0.0002 ckETH to 0.001 ckETH
+                "ckUSDT",
+                (500_000, 2_000_000),
+                INTERVAL,
+            )),
+            RiskLimits::default(),
+        ),
+        (
+            Box::new(LiquidityCycle::new(
+                "ICP",
+                (50_000_000, 200_000_000), // 0.5 ICP to 2 ICP
+                "ckUSDT",
+                INTERVAL,
+            )),
+            RiskLimits::default(),
+        ),
+        (
+            // keep ckUSDC_ckUSDT at the peg, trading 10 ckUSDC or 10 ckUSDT when it is off by more than 0.5%
+            Box::new(PoolArbitrage::new(
+                "ckUSDC",
+                Nat::from(10_000_000_u64),
+                "ckUSDT",
+                Nat::from(10_000_000_u64),
+                0.5,
+                INTERVAL,
+            )),
+            RiskLimits::default().with_min_balance("ckUSDT", Nat::from(1_000_000_u64)),
+        ),
+    ];
+
+    let mut scheduler = Scheduler::new().with_state_store(StateStore::new(STATE_DIR)?);
+    for (strategy, risk_limits) in bots {
+        // need to create separate accounts for each bot
+        let identity = create_random_identity();
+        let agent = create_agent(&target.replica_url, identity, target.is_mainnet).await?;
+        let kong_backend = KongBackend::new(&agent, target.kong_backend).await;
+        fund(&target, &kong_backend, &strategy.tokens()).await?;
+
+        let price_feed: Arc<dyn PriceFeed> = if strategy.name().starts_with("pool_arbitrage") {
+            Arc::new(FixedPriceFeed::new().with_price("ckUSDC", "ckUSDT", 1_f64))
+        } else {
+            Arc::new(KongPriceFeed::new(&kong_backend))
+        };
+        scheduler.add(strategy, &kong_backend, price_feed, risk_limits);
+    }
+
+    scheduler.run().await?;
+
+    #[cfg(feature = "pocket_ic")]
+    if let Some(dry_run) = target.dry_run {
+        dry_run.stop().await;
+    }
+
+    Ok(())
+}
+
+fn network_from_args(args: &[String]) -> Network {
+    if args.contains(&"--prod".to_string()) {
+        Network::Prod
+    } else if args.contains(&"--staging".to_string()) {
+        Network::Staging
+    } else {
+        Network::Local
+    }
+}
+
+#[cfg(feature = "pocket_ic")]
+async fn target_from_args(args: &[String]) -> Result<Target> {
+    use kong_sdk::dry_run::{DryRun, DryRunConfig};
+
+    if !args.contains(&"--dry_run".to_string()) {
+        let network = network_from_args(args);
+        return Ok(Target {
+            replica_url: network.replica_url().to_string(),
+            is_mainnet: network.is_mainnet(),
+            kong_backend: network.kong_backend(),
+            kong_faucet: network.kong_faucet(),
+            dry_run: None,
+        });
+    }
+
+    let mut config = DryRunConfig::default()
+        .with_token("ckUSDC", 6, Nat::from(10_000_u64))
+        .with_token("ckBTC", 8, Nat::from(10_u64))
+        .with_token("ckETH", 18, Nat::from(2_000_000_000_000_u64))
+        .with_pool("ICP", Nat::from(1_000_000_000_000_u64), "ckUSDT", Nat::from(50_000_000_000_u64)) // 10,000 ICP at 5 ckUSDT
+        .with_pool("ckUSDC", Nat::from(50_000_000_000_u64), "ckUSDT", Nat::from(50_000_000_000_u64))
+        .with_pool("ckBTC", Nat::from(100_000_000_u64), "ckUSDT", Nat::from(100_000_000_000_u64)) // 1 ckBTC at 100,000 ckUSDT
+        .with_pool(
+            "ckETH",
+            Nat::from(20_000_000_000_000_000_000_u128),
+            "ckUSDT",
+            Nat::from(60_000_000_000_u64),
+        ); // 20 ckETH at 3,000 ckUSDT
+    if let Ok(kong_backend_wasm) = env::var("KONG_BACKEND_WASM") {
+        config.kong_backend_wasm = kong_backend_wasm.into();
+    }
+    if let Ok(icrc1_ledger_wasm) = env::var("ICRC1_LEDGER_WASM") {
+        config.icrc1_ledger_wasm = icrc1_ledger_wasm.into();
+    }
+    let dry_run = DryRun::start(&config).await?;
+    println!("Dry run on PocketIC at {}", dry_run.replica_url());
+    Ok(Target {
+        replica_url: dry_run.replica_url().to_string(),
+        is_mainnet: false,
+        kong_backend: dry_run.kong_backend(),
+        kong_faucet: None,
+        dry_run: Some(dry_run),
+    })
+}
+
+#[cfg(not(feature = "pocket_ic"))]
+async fn target_from_args(args: &[String]) -> Result<Target> {
+    if args.contains(&"--dry_run".to_string()) {
+        Err(anyhow::anyhow!("--dry_run requires the pocket_ic feature"))?
+    }
+    let network = network_from_args(args);
+    Ok(Target {
+        replica_url: network.replica_url().to_string(),
+        is_mainnet: network.is_mainnet(),
+        kong_backend: network.kong_backend(),
+        kong_faucet: network.kong_faucet(),
+    })
+}
+
+/// claims test tokens from the faucet, or mints them on a dry run
+async fn fund(target: &Target, kong_backend: &KongBackend, tokens: &[String]) -> Result<()> {
+    #[cfg(feature = "pocket_ic")]
+    if let Some(dry_run) = &target.dry_run {
+        let account =
+            icrc_ledger_types::icrc1::account::Account::from(kong_backend.agent().get_principal().map_err(|e| anyhow::anyhow!(e))?);
+        for symbol in tokens {
+            let amount = match symbol.as_str() {
+                "ICP" => Nat::from(10_000_000_000_u64),              // 100 ICP
+                "ckBTC" => Nat::from(10_000_000_u64),                // 0.1 ckBTC
+                "ckETH" => Nat::from(1_000_000_000_000_000_000_u64), // 1 ckETH
+                _ => Nat::from(1_000_000_000_u64),                   // 1,000 ckUSDT / ckUSDC
+            };
+            dry_run.mint(symbol, &account, &amount).await?;
+        }
+        return Ok(());
+    }
+    _ = tokens;
+
+    let kong_faucet = KongFaucet::new(
+        kong_backend.agent(),
+        target.kong_faucet.ok_or(anyhow::anyhow!("No faucet on this network"))?,
+    );
+    let faucet_claim = kong_faucet.claim().await?;
+    println!("Faucet claimed: {:?}", faucet_claim);
+    Ok(())
+}
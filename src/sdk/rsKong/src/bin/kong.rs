@@ -0,0 +1,376 @@
+use anyhow::Result;
+use candid::{Nat, Principal};
+use icrc_ledger_types::icrc1::account::Account;
+use std::env;
+use std::time::Duration;
+
+use kong_sdk::agent::{create_agent, create_anonymous_identity, create_identity_from_pem_file};
+use kong_sdk::kong_backend::add_liquidity::add_liquidity_args::AddLiquidityArgs;
+use kong_sdk::kong_backend::helpers::nat_helpers::{nat_from_decimal_str, nat_is_zero, nat_to_decimal_str};
+use kong_sdk::kong_backend::remove_liquidity::remove_liquidity_args::RemoveLiquidityArgs;
+use kong_sdk::kong_backend::requests::requests_reply::{Reply, RequestsReply};
+use kong_sdk::kong_backend::swap::swap_args::SwapArgs;
+use kong_sdk::kong_backend::tokens::token::Token;
+use kong_sdk::kong_backend::tokens::tokens_reply::TokensReply;
+use kong_sdk::kong_backend::user_balances::user_balances_reply::UserBalancesReply;
+use kong_sdk::kong_backend::KongBackend;
+use kong_sdk::kong_data::{KongData, TxsReply};
+use kong_sdk::kong_faucet::KongFaucet;
+use kong_sdk::ledger::icrc1_balance_of;
+use kong_sdk::network::Network;
+use kong_sdk::retry::{retry, RetryPolicy};
+
+const USAGE: &str =
+    "Usage: kong [--staging | --prod] [--identity <pem_file>] [--kong_backend <canister_id>] [--kong_data <canister_id>] <command>
+
+Commands:
+  tokens [symbol]                                  list tokens
+  pools [symbol]                                   list pools
+  txs [principal_id]                               recent transactions from kong_data
+  quote <amount> <pay_token> <receive_token>       quote a swap
+  swap <amount> <pay_token> <receive_token>        swap [--max_slippage <percent>] [--async]
+  add_liquidity <amount_0> <token_0> <token_1>     add liquidity, amount_1 is calculated from the pool price [--async]
+  remove_liquidity <lp_amount> <token_0> <token_1> remove liquidity [--async]
+  balances                                         token and LP balances of the identity
+  claims                                           unclaimed claims of the identity
+  claim <claim_id>                                 claim a claim
+  faucet                                           claim test tokens (local and staging only)
+
+Amounts are in token units, e.g. 1.5 ICP. Without --identity the anonymous identity is used, which is enough for queries";
+
+// flags followed by a value
+const VALUE_FLAGS: [&str; 4] = ["--identity", "--kong_backend", "--kong_data", "--max_slippage"];
+const ASYNC_MAX_WAIT: Duration = Duration::from_secs(60);
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let params = positional_args(&args);
+    let Some((command, params)) = params.split_first() else {
+        println!("{}", USAGE);
+        return Ok(());
+    };
+
+    let network = if args.contains(&"--prod".to_string()) {
+        Network::Prod
+    } else if args.contains(&"--staging".to_string()) {
+        Network::Staging
+    } else {
+        Network::Local
+    };
+    let identity = match flag_value(&args, "--identity") {
+        Some(pem_file) => create_identity_from_pem_file(pem_file)?,
+        None => create_anonymous_identity(),
+    };
+    let agent = create_agent(network.replica_url(), identity, network.is_mainnet()).await?;
+    let kong_backend_id = match flag_value(&args, "--kong_backend") {
+        Some(canister_id) => Principal::from_text(canister_id)?,
+        None => network.kong_backend(),
+    };
+    let kong_data_id = match flag_value(&args, "--kong_data") {
+        Some(canister_id) => Principal::from_text(canister_id)?,
+        None => network.kong_data(),
+    };
+    let principal_id = agent.get_principal().map_err(|e| anyhow::anyhow!(e))?;
+    let principal_text = principal_id.to_text();
+    let is_async = args.contains(&"--async".to_string());
+    let retry_policy = RetryPolicy::default();
+
+    let mut kong_backend = KongBackend::new(&agent, kong_backend_id).await;
+    match command.as_str() {
+        "tokens" => {
+            let tokens = retry(&retry_policy, || kong_backend.tokens(params.first().map(|s| s.as_str()))).await?;
+            for token in tokens.iter() {
+                println!(
+                    "{:<20} {:<30} decimals={} fee={}",
+                    token.symbol_with_chain(),
+                    token.address(),
+                    token.decimals(),
+                    nat_to_decimal_str(&token.fee(), token.decimals())
+                );
+            }
+        }
+        "pools" => {
+            kong_backend.load_tokens(&retry_policy).await?;
+            let pools = retry(&retry_policy, || kong_backend.pools(params.first().map(|s| s.as_str()))).await?;
+            for pool in pools.iter() {
+                println!(
+                    "{:<20} {} {} / {} {} price={} lp_fee={}bps",
+                    pool.symbol,
+                    amount_str(&kong_backend, &pool.symbol_0, &pool.balance_0),
+                    pool.symbol_0,
+                    amount_str(&kong_backend, &pool.symbol_1, &pool.balance_1),
+                    pool.symbol_1,
+                    pool.price,
+                    pool.lp_fee_bps
+                );
+            }
+        }
+        "txs" => {
+            kong_backend.load_tokens(&retry_policy).await?;
+            let kong_data = KongData::new(&agent, kong_data_id);
+            let principal_id = params.first().cloned();
+            let txs = retry(&retry_policy, || kong_data.txs(principal_id.clone(), None, None, Some(20))).await?;
+            for tx in txs.iter() {
+                match tx {
+                    TxsReply::AddPool(reply) => println!(
+                        "#{} AddPool {} {} {} and {} {}",
+                        reply.tx_id,
+                        reply.status,
+                        amount_str(&kong_backend, &reply.symbol_0, &reply.amount_0),
+                        reply.symbol_0,
+                        amount_str(&kong_backend, &reply.symbol_1, &reply.amount_1),
+                        reply.symbol_1
+                    ),
+                    TxsReply::AddLiquidity(reply) => println!(
+                        "#{} AddLiquidity {} {} {} and {} {}",
+                        reply.tx_id,
+                        reply.status,
+                        amount_str(&kong_backend, &reply.symbol_0, &reply.amount_0),
+                        reply.symbol_0,
+                        amount_str(&kong_backend, &reply.symbol_1, &reply.amount_1),
+                        reply.symbol_1
+                    ),
+                    TxsReply::RemoveLiquidity(reply) => println!(
+                        "#{} RemoveLiquidity {} {} {} and {} {}",
+                        reply.tx_id,
+                        reply.status,
+                        amount_str(&kong_backend, &reply.symbol_0, &reply.amount_0),
+                        reply.symbol_0,
+                        amount_str(&kong_backend, &reply.symbol_1, &reply.amount_1),
+                        reply.symbol_1
+                    ),
+                    TxsReply::Swap(reply) => println!(
+                        "#{} Swap {} {} {} to {} {}",
+                        reply.tx_id,
+                        reply.status,
+                        amount_str(&kong_backend, &reply.pay_symbol, &reply.pay_amount),
+                        reply.pay_symbol,
+                        amount_str(&kong_backend, &reply.receive_symbol, &reply.receive_amount),
+                        reply.receive_symbol
+                    ),
+                }
+            }
+        }
+        "quote" => {
+            let (pay_token, pay_amount, receive_token) = token_amount_args(&mut kong_backend, params, &retry_policy).await?;
+            let quote = retry(&retry_policy, || kong_backend.swap_amounts(&pay_token, &pay_amount, &receive_token)).await?;
+            println!(
+                "{} {} -> {} {} price={} slippage={}%",
+                amount_str(&kong_backend, &quote.pay_symbol, &quote.pay_amount),
+                quote.pay_symbol,
+                amount_str(&kong_backend, &quote.receive_symbol, &quote.receive_amount),
+                quote.receive_symbol,
+                quote.price,
+                quote.slippage
+            );
+        }
+        "swap" => {
+            let (pay_token, pay_amount, receive_token) = token_amount_args(&mut kong_backend, params, &retry_policy).await?;
+            let max_slippage = flag_value(&args, "--max_slippage").map(|s| s.parse::<f64>()).transpose()?;
+            let quote = retry(&retry_policy, || kong_backend.swap_amounts(&pay_token, &pay_amount, &receive_token)).await?;
+            let swap_args = SwapArgs {
+                pay_token,
+                pay_amount,
+                pay_tx_id: None,
+                receive_token,
+                receive_amount: Some(quote.receive_amount),
+                receive_address: None,
+                max_slippage,
+                referred_by: None,
+            };
+            let swap_reply = if is_async {
+                let request_id = kong_backend.swap_async(&swap_args).await?;
+                match wait_for_reply(&kong_backend, request_id).await? {
+                    Reply::Swap(reply) => reply,
+                    reply => Err(anyhow::anyhow!("Request #{} unexpected reply {:?}", request_id, reply))?,
+                }
+            } else {
+                kong_backend.swap(&swap_args).await?
+            };
+            println!(
+                "Swap #{} {} {} {} to {} {}",
+                swap_reply.request_id,
+                swap_reply.status,
+                amount_str(&kong_backend, &swap_reply.pay_symbol, &swap_reply.pay_amount),
+                swap_reply.pay_symbol,
+                amount_str(&kong_backend, &swap_reply.receive_symbol, &swap_reply.receive_amount),
+                swap_reply.receive_symbol
+            );
+        }
+        "add_liquidity" => {
+            let (token_0, amount_0, token_1) = token_amount_args(&mut kong_backend, params, &retry_policy).await?;
+            let amounts = retry(&retry_policy, || kong_backend.add_liquidity_amounts(&token_0, &amount_0, &token_1)).await?;
+            let add_liquidity_args = AddLiquidityArgs {
+                token_0,
+                amount_0: amounts.amount_0,
+                tx_id_0: None,
+                token_1,
+                amount_1: amounts.amount_1,
+                tx_id_1: None,
+            };
+            let add_liquidity_reply = if is_async {
+                let request_id = kong_backend.add_liquidity_async(&add_liquidity_args).await?;
+                match wait_for_reply(&kong_backend, request_id).await? {
+                    Reply::AddLiquidity(reply) => reply,
+                    reply => Err(anyhow::anyhow!("Request #{} unexpected reply {:?}", request_id, reply))?,
+                }
+            } else {
+                kong_backend.add_liquidity(&add_liquidity_args).await?
+            };
+            println!(
+                "Add Liquidity #{} {} {} {} and {} {} for {} {}",
+                add_liquidity_reply.request_id,
+                add_liquidity_reply.status,
+                amount_str(&kong_backend, &add_liquidity_reply.symbol_0, &add_liquidity_reply.amount_0),
+                add_liquidity_reply.symbol_0,
+                amount_str(&kong_backend, &add_liquidity_reply.symbol_1, &add_liquidity_reply.amount_1),
+                add_liquidity_reply.symbol_1,
+                amount_str(&kong_backend, &add_liquidity_reply.symbol, &add_liquidity_reply.add_lp_token_amount),
+                add_liquidity_reply.symbol
+            );
+        }
+        "remove_liquidity" => {
+            let [lp_amount, token_0, token_1] = params else {
+                Err(anyhow::anyhow!("remove_liquidity <lp_amount> <token_0> <token_1>"))?
+            };
+            kong_backend.load_tokens(&retry_policy).await?;
+            let lp_token = format!("{}_{}", token_0, token_1);
+            let remove_liquidity_args = RemoveLiquidityArgs {
+                token_0: token_0.to_string(),
+                token_1: token_1.to_string(),
+                remove_lp_token_amount: parse_amount(&kong_backend, &lp_token, lp_amount)?,
+            };
+            let remove_liquidity_reply = if is_async {
+                let request_id = kong_backend.remove_liquidity_async(&remove_liquidity_args).await?;
+                match wait_for_reply(&kong_backend, request_id).await? {
+                    Reply::RemoveLiquidity(reply) => reply,
+                    reply => Err(anyhow::anyhow!("Request #{} unexpected reply {:?}", request_id, reply))?,
+                }
+            } else {
+                kong_backend.remove_liquidity(&remove_liquidity_args).await?
+            };
+            println!(
+                "Remove Liquidity #{} {} {} {} and {} {}",
+                remove_liquidity_reply.request_id,
+                remove_liquidity_reply.status,
+                amount_str(&kong_backend, &remove_liquidity_reply.symbol_0, &remove_liquidity_reply.amount_0),
+                remove_liquidity_reply.symbol_0,
+                amount_str(&kong_backend, &remove_liquidity_reply.symbol_1, &remove_liquidity_reply.amount_1),
+                remove_liquidity_reply.symbol_1
+            );
+        }
+        "balances" => {
+            kong_backend.load_tokens(&retry_policy).await?;
+            println!("{}", principal_id);
+            let account = Account::from(principal_id);
+            let tokens = kong_backend.tokens(None).await?;
+            for token in tokens.iter().filter(|token| matches!(token, TokensReply::IC(_))) {
+                let ledger = Principal::from_text(token.address())?;
+                let balance = retry(&retry_policy, || icrc1_balance_of(&agent, &ledger, &account)).await?;
+                if !nat_is_zero(&balance) {
+                    println!("{:<20} {}", token.symbol(), nat_to_decimal_str(&balance, token.decimals()));
+                }
+            }
+            let user_balances = retry(&retry_policy, || kong_backend.user_balances(&principal_text)).await?;
+            for UserBalancesReply::LP(lp_balance) in user_balances.iter() {
+                println!(
+                    "{:<20} {} ({} {} and {} {}) ${}",
+                    lp_balance.symbol,
+                    lp_balance.balance,
+                    lp_balance.amount_0,
+                    lp_balance.symbol_0,
+                    lp_balance.amount_1,
+                    lp_balance.symbol_1,
+                    lp_balance.usd_balance
+                );
+            }
+        }
+        "claims" => {
+            let claims = retry(&retry_policy, || kong_backend.claims(&principal_text)).await?;
+            for claim in claims.iter() {
+                println!(
+                    "#{} {} {} {} {}",
+                    claim.claim_id,
+                    claim.status,
+                    amount_str(&kong_backend, &claim.symbol, &claim.amount),
+                    claim.symbol,
+                    claim.desc
+                );
+            }
+        }
+        "claim" => {
+            let claim_id = params.first().ok_or(anyhow::anyhow!("claim <claim_id>"))?.parse::<u64>()?;
+            let claim_reply = kong_backend.claim(claim_id).await?;
+            println!(
+                "Claim #{} {} {} {}",
+                claim_reply.claim_id,
+                claim_reply.status,
+                amount_str(&kong_backend, &claim_reply.symbol, &claim_reply.amount),
+                claim_reply.symbol
+            );
+        }
+        "faucet" => {
+            let kong_faucet_id = network.kong_faucet().ok_or(anyhow::anyhow!("No faucet on {:?}", network))?;
+            let kong_faucet = KongFaucet::new(&agent, kong_faucet_id);
+            println!("{}", kong_faucet.claim().await?);
+        }
+        _ => {
+            println!("{}", USAGE);
+        }
+    }
+
+    Ok(())
+}
+
+// arguments that are not flags or flag values
+fn positional_args(args: &[String]) -> Vec<String> {
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if VALUE_FLAGS.contains(&arg.as_str()) {
+            args.next();
+        } else if !arg.starts_with("--") {
+            positional.push(arg.clone());
+        }
+    }
+    positional
+}
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(|s| s.as_str())
+}
+
+fn parse_amount(kong_backend: &KongBackend, symbol: &str, amount: &str) -> Result<Nat> {
+    let token = kong_backend.token(symbol).ok_or(anyhow::anyhow!("Token {} not found", symbol))?;
+    nat_from_decimal_str(amount, token.decimals()).ok_or(anyhow::anyhow!("Invalid {} amount {}", symbol, amount))
+}
+
+// <amount> <token_0> <token_1> with the amount in token_0 units
+async fn token_amount_args(kong_backend: &mut KongBackend, params: &[String], retry_policy: &RetryPolicy) -> Result<(String, Nat, String)> {
+    let [amount, token_0, token_1] = params else {
+        Err(anyhow::anyhow!("Expected <amount> <token_0> <token_1>"))?
+    };
+    kong_backend.load_tokens(retry_policy).await?;
+    let amount = parse_amount(kong_backend, token_0, amount)?;
+    Ok((token_0.to_string(), amount, token_1.to_string()))
+}
+
+fn amount_str(kong_backend: &KongBackend, symbol: &str, amount: &Nat) -> String {
+    match kong_backend.token(symbol) {
+        Some(token) => nat_to_decimal_str(amount, token.decimals()),
+        None => amount.to_string(),
+    }
+}
+
+async fn wait_for_reply(kong_backend: &KongBackend, request_id: u64) -> Result<Reply> {
+    println!("Request #{} submitted", request_id);
+    let RequestsReply { statuses, reply, .. } = kong_backend.wait_for_request(request_id, ASYNC_MAX_WAIT).await?;
+    if statuses.last().is_some_and(|status| status == "Failed") {
+        Err(anyhow::anyhow!("Request #{} failed: {}", request_id, statuses.join(", ")))?
+    }
+    Ok(reply)
+}
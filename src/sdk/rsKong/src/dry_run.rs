@@ -0,0 +1,303 @@
+//! Local Kong Swap on PocketIC for dry runs of bots and strategies.
+//!
+//! Installs kong_backend and an ICRC-1 ledger per token on a PocketIC instance, adds the tokens and pools,
+//! then exposes the instance over HTTP so the bots' agents connect to it like to any replica. Requires the
+//! PocketIC server (POCKET_IC_BIN), the kong_backend wasm (`dfx build kong_backend`) and the ICRC-1 ledger wasm
+use anyhow::Result;
+use candid::{CandidType, Decode, Encode, Nat, Principal};
+use icrc_ledger_types::icrc1::account::Account;
+use icrc_ledger_types::icrc1::transfer::{TransferArg, TransferError};
+use icrc_ledger_types::icrc2::approve::{ApproveArgs, ApproveError};
+use pocket_ic::nonblocking::PocketIc;
+use pocket_ic::PocketIcBuilder;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::kong_backend::add_pool::add_pool_args::AddPoolArgs;
+use crate::kong_backend::add_pool::add_pool_reply::AddPoolReply;
+use crate::network::Network;
+
+const KONG_BACKEND_WASM: &str = "../../../target/wasm32-unknown-unknown/release/kong_backend.wasm";
+const ICRC1_LEDGER_WASM: &str = "../../../wasm/ic-icrc1-ledger.wasm.gz";
+const CYCLES: u128 = 10_000_000_000_000;
+// ckUSDT and ICP ids used by kong_backend's integration tests
+const CKUSDT_LEDGER: &str = "zdzgz-siaaa-aaaar-qaiba-cai";
+const ICP_LEDGER: &str = "nppha-riaaa-aaaal-ajf2q-cai";
+
+#[derive(Debug, Clone)]
+pub struct DryRunToken {
+    pub symbol: String,
+    pub decimals: u8,
+    pub fee: Nat,
+    pub canister_id: Option<Principal>, // a new canister id if None
+}
+
+#[derive(Debug, Clone)]
+pub struct DryRunPool {
+    pub token_0: String,
+    pub amount_0: Nat,
+    pub token_1: String, // must be ckUSDT or ICP
+    pub amount_1: Nat,
+}
+
+#[derive(Debug, Clone)]
+pub struct DryRunConfig {
+    pub kong_backend_wasm: PathBuf,
+    pub icrc1_ledger_wasm: PathBuf,
+    pub tokens: Vec<DryRunToken>, // added to kong_backend in order, ckUSDT and ICP must come first
+    pub pools: Vec<DryRunPool>,
+}
+
+impl Default for DryRunConfig {
+    /// ckUSDT and ICP with the wasm files at their default build locations
+    fn default() -> Self {
+        DryRunConfig {
+            kong_backend_wasm: PathBuf::from(KONG_BACKEND_WASM),
+            icrc1_ledger_wasm: PathBuf::from(ICRC1_LEDGER_WASM),
+            tokens: vec![
+                DryRunToken {
+                    symbol: "ckUSDT".to_string(),
+                    decimals: 6,
+                    fee: Nat::from(10_000_u64),
+                    canister_id: Principal::from_text(CKUSDT_LEDGER).ok(),
+                },
+                DryRunToken {
+                    symbol: "ICP".to_string(),
+                    decimals: 8,
+                    fee: Nat::from(10_000_u64),
+                    canister_id: Principal::from_text(ICP_LEDGER).ok(),
+                },
+            ],
+            pools: Vec::new(),
+        }
+    }
+}
+
+impl DryRunConfig {
+    pub fn with_token(mut self, symbol: &str, decimals: u8, fee: Nat) -> Self {
+        self.tokens.push(DryRunToken {
+            symbol: symbol.to_string(),
+            decimals,
+            fee,
+            canister_id: None,
+        });
+        self
+    }
+
+    pub fn with_pool(mut self, token_0: &str, amount_0: Nat, token_1: &str, amount_1: Nat) -> Self {
+        self.pools.push(DryRunPool {
+            token_0: token_0.to_string(),
+            amount_0,
+            token_1: token_1.to_string(),
+            amount_1,
+        });
+        self
+    }
+}
+
+// ICRC-1 ledger init args, see wasm/ic-icrc1-ledger.did
+#[allow(dead_code)]
+#[derive(CandidType)]
+enum MetadataValue {
+    Text(String),
+}
+
+#[derive(CandidType)]
+struct FeatureFlags {
+    icrc2: bool,
+}
+
+#[derive(CandidType)]
+struct ArchiveOptions {
+    num_blocks_to_archive: u64,
+    trigger_threshold: u64,
+    controller_id: Principal,
+}
+
+#[derive(CandidType)]
+struct InitArgs {
+    minting_account: Account,
+    transfer_fee: Nat,
+    decimals: Option<u8>,
+    token_symbol: String,
+    token_name: String,
+    metadata: Vec<(String, MetadataValue)>,
+    initial_balances: Vec<(Account, Nat)>,
+    feature_flags: Option<FeatureFlags>,
+    archive_options: ArchiveOptions,
+}
+
+#[derive(CandidType)]
+enum LedgerArg {
+    Init(InitArgs),
+}
+
+#[derive(CandidType)]
+struct AddTokenArgs {
+    token: String,
+}
+
+/// Kong Swap running on a local PocketIC instance
+pub struct DryRun {
+    pic: PocketIc,
+    url: String,
+    controller: Principal,
+    kong_backend: Principal,
+    ledgers: BTreeMap<String, Principal>,
+}
+
+impl DryRun {
+    pub async fn start(config: &DryRunConfig) -> Result<Self> {
+        let mut pic = PocketIcBuilder::new()
+            .with_fiduciary_subnet()
+            .with_application_subnet()
+            .build_async()
+            .await;
+        // the controller mints the tokens and adds the tokens and pools
+        let controller = Principal::self_authenticating(b"kong_sdk dry run controller");
+
+        let kong_backend = Network::Local.kong_backend();
+        pic.create_canister_with_id(Some(controller), None, kong_backend)
+            .await
+            .map_err(|e| anyhow::anyhow!("Could not create kong_backend: {}", e))?;
+        pic.add_cycles(kong_backend, CYCLES).await;
+        pic.install_canister(kong_backend, fs::read(&config.kong_backend_wasm)?, Encode!(&())?, Some(controller))
+            .await;
+
+        let icrc1_ledger_wasm = fs::read(&config.icrc1_ledger_wasm)?;
+        let mut ledgers = BTreeMap::new();
+        for token in config.tokens.iter() {
+            let ledger = match token.canister_id {
+                Some(canister_id) => pic
+                    .create_canister_with_id(Some(controller), None, canister_id)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Could not create {} ledger: {}", token.symbol, e))?,
+                None => pic.create_canister_with_settings(Some(controller), None).await,
+            };
+            pic.add_cycles(ledger, CYCLES).await;
+            let ledger_arg = LedgerArg::Init(InitArgs {
+                minting_account: Account::from(controller),
+                transfer_fee: token.fee.clone(),
+                decimals: Some(token.decimals),
+                token_symbol: token.symbol.clone(),
+                token_name: token.symbol.clone(),
+                metadata: vec![],
+                initial_balances: vec![],
+                feature_flags: Some(FeatureFlags { icrc2: true }),
+                archive_options: ArchiveOptions {
+                    num_blocks_to_archive: 1_000,
+                    trigger_threshold: 500,
+                    controller_id: controller,
+                },
+            });
+            pic.install_canister(ledger, icrc1_ledger_wasm.clone(), Encode!(&ledger_arg)?, Some(controller))
+                .await;
+            ledgers.insert(token.symbol.clone(), ledger);
+        }
+
+        let url = pic.make_live(None).await.to_string();
+        let dry_run = DryRun {
+            pic,
+            url,
+            controller,
+            kong_backend,
+            ledgers,
+        };
+
+        for token in config.tokens.iter() {
+            let args = AddTokenArgs {
+                token: format!("IC.{}", dry_run.ledger(&token.symbol)?),
+            };
+            dry_run
+                .update::<_, Result<candid::Reserved, String>>(kong_backend, "add_token", &args)
+                .await?
+                .map_err(|e| anyhow::anyhow!("add_token {} failed: {}", token.symbol, e))?;
+        }
+
+        for pool in config.pools.iter() {
+            dry_run.add_pool(pool).await?;
+        }
+
+        Ok(dry_run)
+    }
+
+    /// url of the PocketIC HTTP gateway, use with create_agent(url, identity, false)
+    pub fn replica_url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn kong_backend(&self) -> Principal {
+        self.kong_backend
+    }
+
+    pub fn ledger(&self, symbol: &str) -> Result<Principal> {
+        self.ledgers
+            .get(symbol)
+            .copied()
+            .ok_or(anyhow::anyhow!("Token {} not found", symbol))
+    }
+
+    async fn update<A: CandidType, R: CandidType + DeserializeOwned>(&self, canister_id: Principal, method: &str, arg: &A) -> Result<R> {
+        let result = self
+            .pic
+            .update_call(canister_id, self.controller, method, Encode!(arg)?)
+            .await
+            .map_err(|e| anyhow::anyhow!("{} failed: {}", method, e.reject_message))?;
+        Ok(Decode!(result.as_slice(), R)?)
+    }
+
+    /// mints amount of the token to account, e.g. to fund a bot
+    pub async fn mint(&self, symbol: &str, account: &Account, amount: &Nat) -> Result<Nat> {
+        let transfer_args = TransferArg {
+            from_subaccount: None,
+            to: *account,
+            amount: amount.clone(),
+            fee: None,
+            memo: None,
+            created_at_time: None,
+        };
+        self.update::<_, Result<Nat, TransferError>>(self.ledger(symbol)?, "icrc1_transfer", &transfer_args)
+            .await?
+            .map_err(|e| anyhow::anyhow!("Mint {} failed: {:?}", symbol, e))
+    }
+
+    async fn add_pool(&self, pool: &DryRunPool) -> Result<AddPoolReply> {
+        let kong_backend_account = Account::from(self.kong_backend);
+        for (symbol, amount) in [(&pool.token_0, &pool.amount_0), (&pool.token_1, &pool.amount_1)] {
+            // mint to the controller and approve kong_backend, with margin for the fees
+            let amount = amount.clone() * 2_u32;
+            self.mint(symbol, &Account::from(self.controller), &amount).await?;
+            let approve_args = ApproveArgs {
+                from_subaccount: None,
+                spender: kong_backend_account,
+                amount,
+                expected_allowance: None,
+                expires_at: None,
+                fee: None,
+                memo: None,
+                created_at_time: None,
+            };
+            self.update::<_, Result<Nat, ApproveError>>(self.ledger(symbol)?, "icrc2_approve", &approve_args)
+                .await?
+                .map_err(|e| anyhow::anyhow!("Approve {} failed: {:?}", symbol, e))?;
+        }
+        let add_pool_args = AddPoolArgs {
+            token_0: pool.token_0.clone(),
+            amount_0: pool.amount_0.clone(),
+            tx_id_0: None,
+            token_1: pool.token_1.clone(),
+            amount_1: pool.amount_1.clone(),
+            tx_id_1: None,
+            lp_fee_bps: None,
+        };
+        self.update::<_, Result<AddPoolReply, String>>(self.kong_backend, "add_pool", &add_pool_args)
+            .await?
+            .map_err(|e| anyhow::anyhow!("add_pool {}_{} failed: {}", pool.token_0, pool.token_1, e))
+    }
+
+    pub async fn stop(self) {
+        self.pic.drop().await;
+    }
+}
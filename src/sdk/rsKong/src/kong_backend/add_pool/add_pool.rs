@@ -0,0 +1,25 @@
+use anyhow::Result;
+use candid::{Decode, Encode};
+
+use super::add_pool_args::AddPoolArgs;
+use super::add_pool_reply::AddPoolReply;
+
+use crate::kong_backend::KongBackend;
+
+impl KongBackend {
+    /// token_1 must be ckUSDT or ICP. The tokens must have been added with add_token by a controller
+    pub async fn add_pool(&self, add_pool_args: &AddPoolArgs) -> Result<AddPoolReply> {
+        // icrc2_approve for token_0 and token_1
+        self.icrc2_approve(&add_pool_args.token_0, &add_pool_args.amount_0).await?;
+        self.icrc2_approve(&add_pool_args.token_1, &add_pool_args.amount_1).await?;
+
+        // kong_backend add_pool
+        let result = self
+            .agent
+            .update(&self.principal_id, "add_pool")
+            .with_arg(Encode!(&add_pool_args)?)
+            .await?;
+        let add_pool_result = Decode!(result.as_slice(), Result<AddPoolReply, String>)?;
+        add_pool_result.map_err(|e| anyhow::anyhow!(e))
+    }
+}
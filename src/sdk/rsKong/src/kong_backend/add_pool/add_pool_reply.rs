@@ -0,0 +1,27 @@
+use candid::{CandidType, Nat};
+use serde::{Deserialize, Serialize};
+
+use crate::kong_backend::transfers::transfers_reply::TransferIdReply;
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct AddPoolReply {
+    pub tx_id: u64,
+    pub pool_id: u32,
+    pub request_id: u64,
+    pub status: String,
+    pub name: String,
+    pub symbol: String,
+    pub chain_0: String,
+    pub symbol_0: String,
+    pub amount_0: Nat,
+    pub chain_1: String,
+    pub symbol_1: String,
+    pub amount_1: Nat,
+    pub lp_fee_bps: u8,
+    pub lp_token_symbol: String,
+    pub add_lp_token_amount: Nat,
+    pub transfer_ids: Vec<TransferIdReply>,
+    pub claim_ids: Vec<u64>,
+    pub is_removed: bool,
+    pub ts: u64,
+}
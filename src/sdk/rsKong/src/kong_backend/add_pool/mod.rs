@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod add_pool;
+pub mod add_pool_args;
+pub mod add_pool_reply;
@@ -0,0 +1,24 @@
+use anyhow::Result;
+use candid::{Decode, Encode};
+
+use super::claim_reply::ClaimReply;
+use super::claims_reply::ClaimsReply;
+
+use crate::kong_backend::KongBackend;
+
+impl KongBackend {
+    /// claims of principal_id that are not claimed yet
+    pub async fn claims(&self, principal_id: &str) -> Result<Vec<ClaimsReply>> {
+        let results = self
+            .agent
+            .query(&self.principal_id, "claims")
+            .with_arg(Encode!(&principal_id)?)
+            .await?;
+        Decode!(results.as_slice(), Result<Vec<ClaimsReply>, String>)?.map_err(|e| anyhow::anyhow!(e))
+    }
+
+    pub async fn claim(&self, claim_id: u64) -> Result<ClaimReply> {
+        let result = self.agent.update(&self.principal_id, "claim").with_arg(Encode!(&claim_id)?).await?;
+        Decode!(result.as_slice(), Result<ClaimReply, String>)?.map_err(|e| anyhow::anyhow!(e))
+    }
+}
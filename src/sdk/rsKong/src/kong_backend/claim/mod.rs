@@ -1 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod claim;
 pub mod claim_reply;
+pub mod claims_reply;
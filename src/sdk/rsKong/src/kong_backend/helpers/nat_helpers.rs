@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use candid::Nat;
+use num::BigUint;
 use num_traits::{ToPrimitive, Zero};
 
 pub fn nat_zero() -> Nat {
@@ -30,3 +31,29 @@ pub fn nat_divide_as_f64(numerator: &Nat, denominator: &Nat) -> Option<f64> {
     }
     numerator.0.to_f64().and_then(|n| denominator.0.to_f64().map(|d| n / d))
 }
+
+// parses a decimal amount like "1.25" into a Nat with decimals precision
+pub fn nat_from_decimal_str(amount: &str, decimals: u8) -> Option<Nat> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty() || fraction.len() > decimals as usize {
+        None?
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        None?
+    }
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals as usize);
+    BigUint::parse_bytes(digits.as_bytes(), 10).map(Nat)
+}
+
+// formats a Nat with decimals precision as a decimal amount, without trailing zeros
+pub fn nat_to_decimal_str(n: &Nat, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = format!("{:0>width$}", n.0.to_str_radix(10), width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
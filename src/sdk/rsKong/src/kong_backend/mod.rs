@@ -1,11 +1,19 @@
 use anyhow::Result;
-use candid::{Decode, Encode, Principal};
+use candid::{Decode, Encode, Nat, Principal};
 use ic_agent::Agent;
 use icrc_ledger_types::icrc1::account::Account;
+use std::time::Duration;
+use tokens::token::Token;
 use tokens::tokens_reply::TokensReply;
 
+use helpers::nat_helpers::nat_add;
+
+use crate::ledger::icrc2_approve;
+use crate::retry::RetryPolicy;
+
 pub mod add_liquidity;
 pub mod add_liquidity_amounts;
+pub mod add_pool;
 mod canister;
 pub mod claim;
 pub mod helpers;
@@ -17,9 +25,9 @@ pub mod swap;
 pub mod swap_amounts;
 pub mod tokens;
 pub mod transfers;
+pub mod user_balances;
 
-const KONG_BACKEND_STAGING: &str = "l4lgk-raaaa-aaaar-qahpq-cai";
-const KONG_BACKEND_PROD: &str = "2ipq2-uqaaa-aaaar-qailq-cai";
+const APPROVE_EXPIRY: Duration = Duration::from_secs(60);
 
 #[derive(Clone)]
 pub struct KongBackend {
@@ -30,29 +38,44 @@ pub struct KongBackend {
 }
 
 impl KongBackend {
-    pub async fn new(agent: &Agent, is_prod: bool) -> Self {
-        let principal_id = if is_prod {
-            Principal::from_text(KONG_BACKEND_PROD).unwrap()
-        } else {
-            Principal::from_text(KONG_BACKEND_STAGING).unwrap()
-        };
+    /// Client for the kong_backend canister at principal_id (see `Network::kong_backend`).
+    /// The list of tokens is loaded on creation, use `load_tokens` to retry or refresh it
+    pub async fn new(agent: &Agent, principal_id: Principal) -> Self {
         let mut instance = KongBackend {
             agent: agent.clone(),
             principal_id,
             account_id: Account::from(principal_id),
             tokens: Vec::new(),
         };
-        _ = instance.tokens(None).await; // populate tokens
+        _ = instance.load_tokens(&RetryPolicy::none()).await; // populate tokens
         instance
     }
 
-    #[allow(dead_code)]
+    pub fn principal_id(&self) -> Principal {
+        self.principal_id
+    }
+
+    pub fn agent(&self) -> &Agent {
+        &self.agent
+    }
+
     pub async fn icrc1_name(&self) -> Result<String> {
-        let icrc1_name = self
-            .agent
-            .query(&self.principal_id, "icrc1_name")
-            .with_arg(Encode!()?)
-            .await?;
+        let icrc1_name = self.agent.query(&self.principal_id, "icrc1_name").with_arg(Encode!()?).await?;
         Ok(Decode!(icrc1_name.as_slice(), String)?)
     }
+
+    /// icrc2_approve kong_backend to spend amount of the token plus the gas fee for the icrc2_transfer_from.
+    /// Note, the icrc2_approve transaction itself also requires a gas fee
+    async fn icrc2_approve(&self, symbol: &str, amount: &Nat) -> Result<Nat> {
+        let token = self.token(symbol).ok_or(anyhow::anyhow!("Token {} not found", symbol))?;
+        let ledger = Principal::from_text(token.address())?;
+        icrc2_approve(
+            &self.agent,
+            &ledger,
+            &self.account_id,
+            &nat_add(amount, &token.fee()),
+            APPROVE_EXPIRY,
+        )
+        .await
+    }
 }
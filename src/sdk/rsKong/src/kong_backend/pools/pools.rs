@@ -1,18 +1,13 @@
 use anyhow::Result;
 use candid::{Decode, Encode};
 
-use super::pools_reply::PoolsReply;
+use super::pools_reply::PoolReply;
 
 use crate::kong_backend::KongBackend;
 
 impl KongBackend {
-    #[allow(dead_code)]
-    pub async fn pools(&self, symbol: Option<&str>) -> Result<Vec<PoolsReply>> {
-        let results = self
-            .agent
-            .query(&self.principal_id, "pools")
-            .with_arg(Encode!(&symbol)?)
-            .await?;
-        Decode!(results.as_slice(), Result<Vec<PoolsReply>, String>)?.map_err(|e| anyhow::anyhow!(e))
+    pub async fn pools(&self, symbol: Option<&str>) -> Result<Vec<PoolReply>> {
+        let results = self.agent.query(&self.principal_id, "pools").with_arg(Encode!(&symbol)?).await?;
+        Decode!(results.as_slice(), Result<Vec<PoolReply>, String>)?.map_err(|e| anyhow::anyhow!(e))
     }
 }
@@ -18,7 +18,7 @@ impl KongBackend {
         remove_liquidity_result.map_err(|e| anyhow::anyhow!(e))
     }
 
-    #[allow(dead_code)]
+    /// returns the request_id, use wait_for_request() to get the RemoveLiquidityReply
     pub async fn remove_liquidity_async(&self, remove_liquidity_args: &RemoveLiquidityArgs) -> Result<u64> {
         // kong_backend remove_liquidity_async
         let result = self
@@ -6,7 +6,6 @@ use super::remove_liquidity_amounts_reply::RemoveLiquidityAmountsReply;
 use crate::kong_backend::KongBackend;
 
 impl KongBackend {
-    #[allow(dead_code)]
     pub async fn remove_liquidity_amounts(
         &self,
         token_0: &str,
@@ -2,9 +2,11 @@ use super::requests_reply::RequestsReply;
 use crate::kong_backend::KongBackend;
 use anyhow::Result;
 use candid::{Decode, Encode};
+use tokio::time::{timeout, Duration};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 impl KongBackend {
-    // the default swap() function
     pub async fn requests(&self, request_id: Option<u64>) -> Result<Vec<RequestsReply>> {
         let result = self
             .agent
@@ -14,4 +16,28 @@ impl KongBackend {
         let requests = Decode!(result.as_slice(), Result<Vec<RequestsReply>, String>)?;
         requests.map_err(|e| anyhow::anyhow!(e))
     }
+
+    /// polls requests(request_id) of a *_async call until its last status is Success or Failed.
+    /// Query errors are treated as transient and polled again until max_wait
+    pub async fn wait_for_request(&self, request_id: u64, max_wait: Duration) -> Result<RequestsReply> {
+        let result = timeout(max_wait, async {
+            loop {
+                if let Ok(requests) = self.requests(Some(request_id)).await {
+                    if let Some(request) = requests.into_iter().next() {
+                        if request
+                            .statuses
+                            .last()
+                            .is_some_and(|status| status == "Success" || status == "Failed")
+                        {
+                            return request;
+                        }
+                    }
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        })
+        .await;
+
+        result.map_err(|_| anyhow::anyhow!("Request #{} not finished after {:?}", request_id, max_wait))
+    }
 }
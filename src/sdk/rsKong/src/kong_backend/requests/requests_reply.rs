@@ -1,5 +1,7 @@
 use crate::kong_backend::add_liquidity::add_liquidity_args::AddLiquidityArgs;
 use crate::kong_backend::add_liquidity::add_liquidity_reply::AddLiquidityReply;
+use crate::kong_backend::add_pool::add_pool_args::AddPoolArgs;
+use crate::kong_backend::add_pool::add_pool_reply::AddPoolReply;
 use crate::kong_backend::claim::claim_reply::ClaimReply;
 use crate::kong_backend::remove_liquidity::remove_liquidity_args::RemoveLiquidityArgs;
 use crate::kong_backend::remove_liquidity::remove_liquidity_reply::RemoveLiquidityReply;
@@ -19,6 +21,7 @@ pub struct RequestsReply {
 
 #[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
 pub enum Request {
+    AddPool(AddPoolArgs),
     AddLiquidity(AddLiquidityArgs),
     RemoveLiquidity(RemoveLiquidityArgs),
     Swap(SwapArgs),
@@ -28,6 +31,7 @@ pub enum Request {
 #[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
 pub enum Reply {
     Pending,
+    AddPool(AddPoolReply),
     AddLiquidity(AddLiquidityReply),
     RemoveLiquidity(RemoveLiquidityReply),
     Swap(SwapReply),
@@ -1,7 +1,5 @@
-#![allow(dead_code)]
-
 use anyhow::Result;
-use candid::{Decode, Encode, Nat, Principal};
+use candid::{Decode, Encode, Principal};
 use icrc_ledger_types::icrc1::transfer::TransferArg;
 
 use super::swap_args::SwapArgs;
@@ -10,8 +8,10 @@ use super::swap_reply::SwapReply;
 use crate::kong_backend::tokens::token::Token;
 use crate::kong_backend::transfers::tx_id::TxId;
 use crate::kong_backend::KongBackend;
+use crate::ledger::icrc1_transfer;
 
 impl KongBackend {
+    // swap() using icrc1_transfer() and then passing the block index as pay_tx_id
     pub async fn swap_transfer(&self, swap_args: &SwapArgs) -> Result<SwapReply> {
         // icrc1_transfer for pay token
         let pay_token = self
@@ -29,22 +29,12 @@ impl KongBackend {
             memo: None,
             created_at_time: None,
         };
-        let result = self
-            .agent
-            .update(&pay_token_ledger, "icrc1_transfer")
-            .with_arg(Encode!(&transfer_args)?)
-            .await?;
-        let transfer_results = Decode!(result.as_slice(), Result<Nat, String>)?;
-        let pay_tx_id = TxId::BlockIndex(transfer_results.map_err(|e| anyhow::anyhow!(e))?);
+        let pay_tx_id = TxId::BlockIndex(icrc1_transfer(&self.agent, &pay_token_ledger, &transfer_args).await?);
 
         // kong_backend swap
         let mut swap_args = swap_args.clone();
         swap_args.pay_tx_id = Some(pay_tx_id); // pass the tx_id from the above icrc1_transfer
-        let result = self
-            .agent
-            .update(&self.principal_id, "swap")
-            .with_arg(Encode!(&swap_args)?)
-            .await?;
+        let result = self.agent.update(&self.principal_id, "swap").with_arg(Encode!(&swap_args)?).await?;
         let swap_result = Decode!(result.as_slice(), Result<SwapReply, String>)?;
         swap_result.map_err(|e| anyhow::anyhow!(e))
     }
@@ -66,13 +56,7 @@ impl KongBackend {
             memo: None,
             created_at_time: None,
         };
-        let result = self
-            .agent
-            .update(&pay_token_ledger, "icrc1_transfer")
-            .with_arg(Encode!(&transfer_args)?)
-            .await?;
-        let transfer_results = Decode!(result.as_slice(), Result<Nat, String>)?;
-        let pay_tx_id = TxId::BlockIndex(transfer_results.map_err(|e| anyhow::anyhow!(e))?);
+        let pay_tx_id = TxId::BlockIndex(icrc1_transfer(&self.agent, &pay_token_ledger, &transfer_args).await?);
 
         // kong_backend swap
         let mut swap_args = swap_args.clone();
@@ -4,16 +4,14 @@ use serde::Deserialize;
 #[derive(CandidType, Debug, Clone, Deserialize)]
 pub struct ICReply {
     pub token_id: u32,
-    pub pool_symbol: String,
-    pub name: String,
     pub chain: String,
-    pub symbol: String,
-    pub token: String,
     pub canister_id: String,
+    pub name: String,
+    pub symbol: String,
     pub decimals: u8,
     pub fee: Nat,
     pub icrc1: bool,
     pub icrc2: bool,
     pub icrc3: bool,
-    pub on_kong: bool,
+    pub is_removed: bool,
 }
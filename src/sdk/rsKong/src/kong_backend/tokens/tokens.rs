@@ -5,19 +5,20 @@ use super::token::Token;
 use super::tokens_reply::TokensReply;
 
 use crate::kong_backend::KongBackend;
+use crate::retry::{retry, RetryPolicy};
 
 impl KongBackend {
-    pub async fn tokens(&mut self, symbol: Option<&str>) -> Result<Vec<TokensReply>> {
-        let results = self
-            .agent
-            .query(&self.principal_id, "tokens")
-            .with_arg(Encode!(&symbol)?)
-            .await?;
-        let tokens = Decode!(results.as_slice(), Result<Vec<TokensReply>, String>)?.map_err(|e| anyhow::anyhow!(e));
-        if let Ok(ref t) = tokens {
-            self.tokens = t.clone();
-        }
-        tokens
+    pub async fn tokens(&self, symbol: Option<&str>) -> Result<Vec<TokensReply>> {
+        let results = self.agent.query(&self.principal_id, "tokens").with_arg(Encode!(&symbol)?).await?;
+        Decode!(results.as_slice(), Result<Vec<TokensReply>, String>)?.map_err(|e| anyhow::anyhow!(e))
+    }
+
+    /// loads the list of tokens used by token() and the approve+swap flows
+    pub async fn load_tokens(&mut self, retry_policy: &RetryPolicy) -> Result<()> {
+        let this = &*self;
+        let tokens = retry(retry_policy, || this.tokens(None)).await?;
+        self.tokens = tokens;
+        Ok(())
     }
 
     pub fn token(&self, symbol: &str) -> Option<&TokensReply> {
@@ -0,0 +1,3 @@
+#[allow(clippy::module_inception)]
+pub mod user_balances;
+pub mod user_balances_reply;
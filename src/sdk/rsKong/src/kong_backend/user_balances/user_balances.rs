@@ -0,0 +1,18 @@
+use anyhow::Result;
+use candid::{Decode, Encode};
+
+use super::user_balances_reply::UserBalancesReply;
+
+use crate::kong_backend::KongBackend;
+
+impl KongBackend {
+    /// LP token balances of principal_id
+    pub async fn user_balances(&self, principal_id: &str) -> Result<Vec<UserBalancesReply>> {
+        let results = self
+            .agent
+            .query(&self.principal_id, "user_balances")
+            .with_arg(Encode!(&principal_id)?)
+            .await?;
+        Decode!(results.as_slice(), Result<Vec<UserBalancesReply>, String>)?.map_err(|e| anyhow::anyhow!(e))
+    }
+}
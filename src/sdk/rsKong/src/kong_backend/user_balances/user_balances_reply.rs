@@ -0,0 +1,27 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub enum UserBalancesReply {
+    LP(LPBalancesReply),
+}
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct LPBalancesReply {
+    pub name: String,
+    pub symbol: String,
+    pub lp_token_id: u64,
+    pub balance: f64,
+    pub usd_balance: f64,
+    pub chain_0: String,
+    pub symbol_0: String,
+    pub address_0: String,
+    pub amount_0: f64,
+    pub usd_amount_0: f64,
+    pub chain_1: String,
+    pub symbol_1: String,
+    pub address_1: String,
+    pub amount_1: f64,
+    pub usd_amount_1: f64,
+    pub ts: u64,
+}
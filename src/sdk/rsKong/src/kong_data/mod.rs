@@ -0,0 +1,65 @@
+use anyhow::Result;
+use candid::{CandidType, Decode, Encode, Principal};
+use ic_agent::Agent;
+use serde::{Deserialize, Serialize};
+
+use crate::kong_backend::add_liquidity::add_liquidity_reply::AddLiquidityReply;
+use crate::kong_backend::add_pool::add_pool_reply::AddPoolReply;
+use crate::kong_backend::pools::pools_reply::PoolReply;
+use crate::kong_backend::remove_liquidity::remove_liquidity_reply::RemoveLiquidityReply;
+use crate::kong_backend::swap::swap_reply::SwapReply;
+use crate::kong_backend::tokens::tokens_reply::TokensReply;
+
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub enum TxsReply {
+    AddPool(AddPoolReply),
+    AddLiquidity(AddLiquidityReply),
+    RemoveLiquidity(RemoveLiquidityReply),
+    Swap(SwapReply),
+}
+
+/// Client for the kong_data canister, the read-only copy of kong_backend used for history and analytics
+#[derive(Clone)]
+pub struct KongData {
+    agent: Agent,
+    principal_id: Principal,
+}
+
+impl KongData {
+    pub fn new(agent: &Agent, principal_id: Principal) -> Self {
+        KongData {
+            agent: agent.clone(),
+            principal_id,
+        }
+    }
+
+    pub fn principal_id(&self) -> Principal {
+        self.principal_id
+    }
+
+    pub async fn tokens(&self, symbol: Option<&str>) -> Result<Vec<TokensReply>> {
+        let results = self.agent.query(&self.principal_id, "tokens").with_arg(Encode!(&symbol)?).await?;
+        Decode!(results.as_slice(), Result<Vec<TokensReply>, String>)?.map_err(|e| anyhow::anyhow!(e))
+    }
+
+    pub async fn pools(&self, symbol: Option<&str>) -> Result<Vec<PoolReply>> {
+        let results = self.agent.query(&self.principal_id, "pools").with_arg(Encode!(&symbol)?).await?;
+        Decode!(results.as_slice(), Result<Vec<PoolReply>, String>)?.map_err(|e| anyhow::anyhow!(e))
+    }
+
+    /// transactions filtered by principal id, transaction id or token id. num_txs limits the number returned
+    pub async fn txs(
+        &self,
+        principal_id: Option<String>,
+        tx_id: Option<u64>,
+        token_id: Option<u32>,
+        num_txs: Option<u16>,
+    ) -> Result<Vec<TxsReply>> {
+        let results = self
+            .agent
+            .query(&self.principal_id, "txs")
+            .with_arg(Encode!(&principal_id, &tx_id, &token_id, &num_txs)?)
+            .await?;
+        Decode!(results.as_slice(), Result<Vec<TxsReply>, String>)?.map_err(|e| anyhow::anyhow!(e))
+    }
+}
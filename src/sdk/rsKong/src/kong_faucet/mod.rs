@@ -2,18 +2,17 @@ use anyhow::Result;
 use candid::{Decode, Encode, Principal};
 use ic_agent::Agent;
 
-const KONG_FAUCET: &str = "ohr23-xqaaa-aaaar-qahqq-cai";
-
 pub struct KongFaucet {
     agent: Agent,
     principal: Principal,
 }
 
 impl KongFaucet {
-    pub fn new(agent: &Agent) -> Self {
+    /// Client for the testnet faucet at principal (see `Network::kong_faucet`)
+    pub fn new(agent: &Agent, principal: Principal) -> Self {
         KongFaucet {
             agent: agent.clone(),
-            principal: Principal::from_text(KONG_FAUCET).unwrap(),
+            principal,
         }
     }
 
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use candid::{Decode, Encode, Nat, Principal};
+use ic_agent::Agent;
+use icrc_ledger_types::icrc1::account::Account;
+use icrc_ledger_types::icrc1::transfer::{TransferArg, TransferError};
+use icrc_ledger_types::icrc2::approve::{ApproveArgs, ApproveError};
+use std::time::{Duration, SystemTime};
+
+/// ICRC-1/ICRC-2 ledger calls
+pub async fn icrc1_balance_of(agent: &Agent, ledger: &Principal, account: &Account) -> Result<Nat> {
+    let result = agent.query(ledger, "icrc1_balance_of").with_arg(Encode!(account)?).await?;
+    Ok(Decode!(result.as_slice(), Nat)?)
+}
+
+pub async fn icrc1_fee(agent: &Agent, ledger: &Principal) -> Result<Nat> {
+    let result = agent.query(ledger, "icrc1_fee").with_arg(Encode!()?).await?;
+    Ok(Decode!(result.as_slice(), Nat)?)
+}
+
+pub async fn icrc1_decimals(agent: &Agent, ledger: &Principal) -> Result<u8> {
+    let result = agent.query(ledger, "icrc1_decimals").with_arg(Encode!()?).await?;
+    Ok(Decode!(result.as_slice(), u8)?)
+}
+
+/// Returns the block index of the transfer
+pub async fn icrc1_transfer(agent: &Agent, ledger: &Principal, transfer_args: &TransferArg) -> Result<Nat> {
+    let result = agent.update(ledger, "icrc1_transfer").with_arg(Encode!(transfer_args)?).await?;
+    Decode!(result.as_slice(), Result<Nat, TransferError>)?.map_err(|e| anyhow::anyhow!("icrc1_transfer failed: {:?}", e))
+}
+
+/// Approves spender to transfer amount within expires_in. Returns the block index of the approval
+pub async fn icrc2_approve(agent: &Agent, ledger: &Principal, spender: &Account, amount: &Nat, expires_in: Duration) -> Result<Nat> {
+    let ts_now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?.as_nanos() as u64;
+    let approve_args = ApproveArgs {
+        from_subaccount: None,
+        spender: *spender,
+        amount: amount.clone(),
+        expected_allowance: None,
+        expires_at: Some(ts_now + expires_in.as_nanos() as u64),
+        fee: None,
+        memo: None,
+        created_at_time: None,
+    };
+    let result = agent.update(ledger, "icrc2_approve").with_arg(Encode!(&approve_args)?).await?;
+    Decode!(result.as_slice(), Result<Nat, ApproveError>)?.map_err(|e| anyhow::anyhow!("icrc2_approve failed: {:?}", e))
+}
@@ -0,0 +1,19 @@
+//! Kong Swap rust SDK
+//!
+//! Typed clients for the Kong Swap canisters (kong_backend, kong_data and the testnet faucet),
+//! ICRC ledger helpers and retry helpers. Canister ids are passed in explicitly, `Network`
+//! has the ids of Kong Swap's local, staging and production environments.
+//!
+//! `strategy` is a framework for market-making and arbitrage bots. With the `pocket_ic` feature,
+//! `dry_run` runs Kong Swap on a local PocketIC instance to test bots end-to-end.
+
+pub mod agent;
+#[cfg(feature = "pocket_ic")]
+pub mod dry_run;
+pub mod kong_backend;
+pub mod kong_data;
+pub mod kong_faucet;
+pub mod ledger;
+pub mod network;
+pub mod retry;
+pub mod strategy;
@@ -0,0 +1,53 @@
+use candid::Principal;
+
+const LOCAL_REPLICA: &str = "http://localhost:4943";
+const MAINNET_REPLICA: &str = "https://ic0.app";
+
+const KONG_BACKEND_STAGING: &str = "l4lgk-raaaa-aaaar-qahpq-cai";
+const KONG_BACKEND_PROD: &str = "2ipq2-uqaaa-aaaar-qailq-cai";
+const KONG_DATA_STAGING: &str = "6ukzc-hiaaa-aaaah-qpxqa-cai";
+const KONG_DATA_PROD: &str = "cbefx-hqaaa-aaaar-qakrq-cai";
+const KONG_FAUCET: &str = "ohr23-xqaaa-aaaar-qahqq-cai";
+
+/// Kong Swap environments. Canister ids follow canister_ids.all.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Local,   // local replica with the canisters deployed by dfx
+    Staging, // mainnet with test tokens
+    Prod,    // mainnet with real tokens
+}
+
+impl Network {
+    pub fn replica_url(&self) -> &'static str {
+        match self {
+            Network::Local => LOCAL_REPLICA,
+            Network::Staging | Network::Prod => MAINNET_REPLICA,
+        }
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        !matches!(self, Network::Local)
+    }
+
+    pub fn kong_backend(&self) -> Principal {
+        match self {
+            Network::Staging => Principal::from_text(KONG_BACKEND_STAGING).unwrap(),
+            Network::Local | Network::Prod => Principal::from_text(KONG_BACKEND_PROD).unwrap(),
+        }
+    }
+
+    pub fn kong_data(&self) -> Principal {
+        match self {
+            Network::Staging => Principal::from_text(KONG_DATA_STAGING).unwrap(),
+            Network::Local | Network::Prod => Principal::from_text(KONG_DATA_PROD).unwrap(),
+        }
+    }
+
+    /// testnet faucet, not available in production
+    pub fn kong_faucet(&self) -> Option<Principal> {
+        match self {
+            Network::Local | Network::Staging => Some(Principal::from_text(KONG_FAUCET).unwrap()),
+            Network::Prod => None,
+        }
+    }
+}
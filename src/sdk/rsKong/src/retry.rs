@@ -0,0 +1,53 @@
+use anyhow::Result;
+use std::future::Future;
+use std::time::Duration;
+
+/// Exponential backoff for queries and other idempotent calls. Don't retry update calls that
+/// move tokens (swap, add_liquidity, ...): a call that timed out may still have been executed
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// a single attempt
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+}
+
+/// Calls f until it succeeds or max_attempts is reached, doubling the delay after each failure
+pub async fn retry<T, F, Fut>(policy: &RetryPolicy, mut f: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut delay = policy.base_delay;
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Ok(result) => return Ok(result),
+            Err(e) if attempt >= policy.max_attempts => return Err(e),
+            Err(_) => {
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(policy.max_delay);
+                attempt += 1;
+            }
+        }
+    }
+}
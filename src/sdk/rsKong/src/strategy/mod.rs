@@ -0,0 +1,72 @@
+//! Framework for market-making and arbitrage bots.
+//!
+//! A `Strategy` is called on every tick with a `StrategyContext` (the kong_backend client, a price feed,
+//! the bot's token balances and its persisted state) and returns the orders to place. The `Scheduler`
+//! runs several strategies concurrently, checks every order against the strategy's `RiskLimits` before
+//! executing it and saves the strategy's state to a `StateStore` after each tick.
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::kong_backend::KongBackend;
+
+use order::{Fill, Order};
+use price_feed::PriceFeed;
+use risk::Inventory;
+
+pub mod order;
+pub mod price_feed;
+pub mod risk;
+pub mod scheduler;
+pub mod state_store;
+pub mod strategies;
+
+#[async_trait]
+pub trait Strategy: Send {
+    /// unique name of the strategy, also the key of its persisted state
+    fn name(&self) -> &str;
+
+    /// time between ticks
+    fn interval(&self) -> Duration;
+
+    /// symbols of the tokens the strategy trades, their balances are loaded into the inventory before each tick
+    fn tokens(&self) -> Vec<String>;
+
+    /// returns the orders to place. Orders are executed in sequence, an order failing the risk limits is skipped
+    async fn on_tick(&mut self, ctx: &mut StrategyContext<'_>) -> Result<Vec<Order>>;
+
+    /// called after each executed order, either with the fill or the error
+    fn on_fill(&mut self, _ctx: &mut StrategyContext<'_>, _order: &Order, _fill: &Result<Fill, String>) {}
+}
+
+pub struct StrategyContext<'a> {
+    pub kong_backend: &'a KongBackend,
+    pub price_feed: &'a dyn PriceFeed,
+    pub inventory: &'a Inventory,
+    state: &'a mut Value,
+}
+
+impl<'a> StrategyContext<'a> {
+    pub fn new(kong_backend: &'a KongBackend, price_feed: &'a dyn PriceFeed, inventory: &'a Inventory, state: &'a mut Value) -> Self {
+        StrategyContext {
+            kong_backend,
+            price_feed,
+            inventory,
+            state,
+        }
+    }
+
+    /// persisted state of the strategy, the default if there is none yet or it can't be parsed
+    pub fn state<T: DeserializeOwned + Default>(&self) -> T {
+        serde_json::from_value(self.state.clone()).unwrap_or_default()
+    }
+
+    /// replaces the persisted state, saved by the scheduler after the tick
+    pub fn set_state<T: Serialize>(&mut self, state: &T) -> Result<()> {
+        *self.state = serde_json::to_value(state)?;
+        Ok(())
+    }
+}
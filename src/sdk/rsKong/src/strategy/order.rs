@@ -0,0 +1,142 @@
+use anyhow::Result;
+use candid::Nat;
+
+use crate::kong_backend::add_liquidity::add_liquidity_args::AddLiquidityArgs;
+use crate::kong_backend::add_liquidity::add_liquidity_reply::AddLiquidityReply;
+use crate::kong_backend::remove_liquidity::remove_liquidity_args::RemoveLiquidityArgs;
+use crate::kong_backend::remove_liquidity::remove_liquidity_reply::RemoveLiquidityReply;
+use crate::kong_backend::swap::swap_args::SwapArgs;
+use crate::kong_backend::swap::swap_reply::SwapReply;
+use crate::kong_backend::KongBackend;
+
+/// Order placed by a strategy. Amounts are in the token's smallest units
+#[derive(Debug, Clone)]
+pub enum Order {
+    Swap {
+        pay_token: String,
+        pay_amount: Nat,
+        receive_token: String,
+        max_slippage: Option<f64>, // percent, the risk limits' max_slippage if None
+    },
+    AddLiquidity {
+        token_0: String,
+        amount_0: Nat,
+        token_1: String,
+        amount_1: Option<Nat>, // calculated from the pool price if None
+    },
+    RemoveLiquidity {
+        token_0: String,
+        token_1: String,
+        remove_lp_token_amount: Nat,
+    },
+}
+
+impl Order {
+    /// tokens and amounts paid into kong_backend by the order. quote the order first so both legs of an add are included
+    pub fn pay_amounts(&self) -> Vec<(&str, &Nat)> {
+        match self {
+            Order::Swap { pay_token, pay_amount, .. } => vec![(pay_token.as_str(), pay_amount)],
+            Order::AddLiquidity {
+                token_0,
+                amount_0,
+                token_1,
+                amount_1,
+            } => {
+                let mut pay_amounts = vec![(token_0.as_str(), amount_0)];
+                if let Some(amount_1) = amount_1 {
+                    pay_amounts.push((token_1.as_str(), amount_1));
+                }
+                pay_amounts
+            }
+            Order::RemoveLiquidity { .. } => vec![],
+        }
+    }
+
+    /// fills in the amounts calculated by kong_backend, ie. amount_1 of an add from add_liquidity_amounts
+    pub async fn quote(&self, kong_backend: &KongBackend) -> Result<Order> {
+        match self {
+            Order::AddLiquidity {
+                token_0,
+                amount_0,
+                token_1,
+                amount_1: None,
+            } => {
+                let add_liquidity_amounts = kong_backend.add_liquidity_amounts(token_0, amount_0, token_1).await?;
+                Ok(Order::AddLiquidity {
+                    token_0: token_0.to_string(),
+                    amount_0: add_liquidity_amounts.amount_0,
+                    token_1: token_1.to_string(),
+                    amount_1: Some(add_liquidity_amounts.amount_1),
+                })
+            }
+            _ => Ok(self.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Fill {
+    Swap(SwapReply),
+    AddLiquidity(AddLiquidityReply),
+    RemoveLiquidity(RemoveLiquidityReply),
+}
+
+/// Places the order on kong_backend. Swaps and adds without amount_1 are quoted first with swap_amounts / add_liquidity_amounts
+pub async fn execute(kong_backend: &KongBackend, order: &Order, max_slippage: f64) -> Result<Fill> {
+    match order {
+        Order::Swap {
+            pay_token,
+            pay_amount,
+            receive_token,
+            max_slippage: order_max_slippage,
+        } => {
+            let swap_amounts = kong_backend.swap_amounts(pay_token, pay_amount, receive_token).await?;
+            let swap_args = SwapArgs {
+                pay_token: pay_token.to_string(),
+                pay_amount: swap_amounts.pay_amount,
+                pay_tx_id: None,
+                receive_token: receive_token.to_string(),
+                receive_amount: Some(swap_amounts.receive_amount),
+                receive_address: None,
+                max_slippage: Some(order_max_slippage.unwrap_or(max_slippage)),
+                referred_by: None,
+            };
+            Ok(Fill::Swap(kong_backend.swap(&swap_args).await?))
+        }
+        Order::AddLiquidity {
+            token_0,
+            amount_0,
+            token_1,
+            amount_1,
+        } => {
+            let (amount_0, amount_1) = match amount_1 {
+                Some(amount_1) => (amount_0.clone(), amount_1.clone()),
+                None => {
+                    let add_liquidity_amounts = kong_backend.add_liquidity_amounts(token_0, amount_0, token_1).await?;
+                    (add_liquidity_amounts.amount_0, add_liquidity_amounts.amount_1)
+                }
+            };
+            let add_liquidity_args = AddLiquidityArgs {
+                token_0: token_0.to_string(),
+                amount_0,
+                tx_id_0: None,
+                token_1: token_1.to_string(),
+                amount_1,
+                tx_id_1: None,
+            };
+            Ok(Fill::AddLiquidity(kong_backend.add_liquidity(&add_liquidity_args).await?))
+        }
+        Order::RemoveLiquidity {
+            token_0,
+            token_1,
+            remove_lp_token_amount,
+        } => {
+            let remove_liquidity_args = RemoveLiquidityArgs {
+                token_0: token_0.to_string(),
+                token_1: token_1.to_string(),
+                remove_lp_token_amount: remove_lp_token_amount.clone(),
+            };
+            Ok(Fill::RemoveLiquidity(kong_backend.remove_liquidity(&remove_liquidity_args).await?))
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+
+use crate::kong_backend::KongBackend;
+
+/// Source of prices for strategies, e.g. Kong's pools or an external exchange
+#[async_trait]
+pub trait PriceFeed: Send + Sync {
+    /// price of 1 base token in quote tokens
+    async fn price(&self, base: &str, quote: &str) -> Result<f64>;
+}
+
+/// Prices of Kong's pools
+pub struct KongPriceFeed {
+    kong_backend: KongBackend,
+}
+
+impl KongPriceFeed {
+    pub fn new(kong_backend: &KongBackend) -> Self {
+        KongPriceFeed {
+            kong_backend: kong_backend.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceFeed for KongPriceFeed {
+    async fn price(&self, base: &str, quote: &str) -> Result<f64> {
+        let pools = self.kong_backend.pools(None).await?;
+        if let Some(pool) = pools.iter().find(|pool| pool.symbol_0 == base && pool.symbol_1 == quote) {
+            return Ok(pool.price);
+        }
+        match pools.iter().find(|pool| pool.symbol_0 == quote && pool.symbol_1 == base) {
+            Some(pool) if pool.price > 0_f64 => Ok(1_f64 / pool.price),
+            _ => Err(anyhow::anyhow!("No price for {}/{}", base, quote)),
+        }
+    }
+}
+
+/// Fixed prices, for testing strategies or pegged tokens
+#[derive(Default)]
+pub struct FixedPriceFeed {
+    prices: BTreeMap<(String, String), f64>,
+}
+
+impl FixedPriceFeed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_price(mut self, base: &str, quote: &str, price: f64) -> Self {
+        self.prices.insert((base.to_string(), quote.to_string()), price);
+        self
+    }
+}
+
+#[async_trait]
+impl PriceFeed for FixedPriceFeed {
+    async fn price(&self, base: &str, quote: &str) -> Result<f64> {
+        if let Some(price) = self.prices.get(&(base.to_string(), quote.to_string())) {
+            return Ok(*price);
+        }
+        match self.prices.get(&(quote.to_string(), base.to_string())) {
+            Some(price) if *price > 0_f64 => Ok(1_f64 / price),
+            _ => Err(anyhow::anyhow!("No price for {}/{}", base, quote)),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::Result;
+use candid::{Nat, Principal};
+use icrc_ledger_types::icrc1::account::Account;
+use std::collections::BTreeMap;
+
+use super::order::Order;
+
+use crate::kong_backend::helpers::nat_helpers::{nat_add, nat_zero};
+use crate::kong_backend::tokens::token::Token;
+use crate::kong_backend::KongBackend;
+use crate::ledger::icrc1_balance_of;
+
+/// Token balances of the bot's account, keyed by symbol
+#[derive(Debug, Clone, Default)]
+pub struct Inventory {
+    pub balances: BTreeMap<String, Nat>,
+}
+
+impl Inventory {
+    /// loads the ledger balances of the agent's principal for the tokens
+    pub async fn load(kong_backend: &KongBackend, symbols: &[String]) -> Result<Self> {
+        let principal_id = kong_backend.agent().get_principal().map_err(|e| anyhow::anyhow!(e))?;
+        let account = Account::from(principal_id);
+        let mut balances = BTreeMap::new();
+        for symbol in symbols {
+            let token = kong_backend.token(symbol).ok_or(anyhow::anyhow!("Token {} not found", symbol))?;
+            let ledger = Principal::from_text(token.address())?;
+            balances.insert(symbol.to_string(), icrc1_balance_of(kong_backend.agent(), &ledger, &account).await?);
+        }
+        Ok(Inventory { balances })
+    }
+
+    pub fn balance(&self, symbol: &str) -> Nat {
+        self.balances.get(symbol).cloned().unwrap_or(nat_zero())
+    }
+}
+
+/// Limits checked before each order. Tokens without a limit are not limited
+#[derive(Debug, Clone)]
+pub struct RiskLimits {
+    pub max_order_amount: BTreeMap<String, Nat>, // largest amount paid by a single order
+    pub min_balance: BTreeMap<String, Nat>,      // balance to keep after paying for an order, including the gas fee
+    pub max_orders_per_tick: usize,
+    pub max_slippage: f64, // percent, used for swaps without max_slippage and the most an order may set
+}
+
+impl Default for RiskLimits {
+    fn default() -> Self {
+        Self {
+            max_order_amount: BTreeMap::new(),
+            min_balance: BTreeMap::new(),
+            max_orders_per_tick: 10,
+            max_slippage: 2_f64,
+        }
+    }
+}
+
+impl RiskLimits {
+    pub fn with_max_order_amount(mut self, symbol: &str, amount: Nat) -> Self {
+        self.max_order_amount.insert(symbol.to_string(), amount);
+        self
+    }
+
+    pub fn with_min_balance(mut self, symbol: &str, amount: Nat) -> Self {
+        self.min_balance.insert(symbol.to_string(), amount);
+        self
+    }
+
+    /// checks an order against the limits and the inventory. fee is the gas fee of the token paid
+    pub fn check(&self, order: &Order, inventory: &Inventory, fee: impl Fn(&str) -> Nat) -> Result<(), String> {
+        if let Order::Swap {
+            max_slippage: Some(max_slippage),
+            ..
+        } = order
+        {
+            if *max_slippage > self.max_slippage {
+                Err(format!("Max slippage {}% above the limit of {}%", max_slippage, self.max_slippage))?
+            }
+        }
+        for (symbol, amount) in order.pay_amounts() {
+            if let Some(max_order_amount) = self.max_order_amount.get(symbol) {
+                if amount > max_order_amount {
+                    Err(format!("{} {} above the max order amount of {}", amount, symbol, max_order_amount))?
+                }
+            }
+            let required = nat_add(&nat_add(amount, &fee(symbol)), self.min_balance.get(symbol).unwrap_or(&nat_zero()));
+            let balance = inventory.balance(symbol);
+            if balance < required {
+                Err(format!("{} balance of {} below the {} required", symbol, balance, required))?
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swap(pay_amount: u64, max_slippage: Option<f64>) -> Order {
+        Order::Swap {
+            pay_token: "ICP".to_string(),
+            pay_amount: Nat::from(pay_amount),
+            receive_token: "ckUSDT".to_string(),
+            max_slippage,
+        }
+    }
+
+    #[test]
+    fn risk_limits_check() {
+        let risk_limits = RiskLimits::default()
+            .with_max_order_amount("ICP", Nat::from(1_000_u64))
+            .with_min_balance("ICP", Nat::from(500_u64));
+        let inventory = Inventory {
+            balances: BTreeMap::from([("ICP".to_string(), Nat::from(2_000_u64))]),
+        };
+        let fee = |_: &str| Nat::from(10_u64);
+
+        assert!(risk_limits.check(&swap(1_000, None), &inventory, fee).is_ok());
+        // above max order amount
+        assert!(risk_limits.check(&swap(1_001, None), &inventory, fee).is_err());
+        // 1_000 + 10 fee + 500 min balance > 1_200
+        let inventory = Inventory {
+            balances: BTreeMap::from([("ICP".to_string(), Nat::from(1_200_u64))]),
+        };
+        assert!(risk_limits.check(&swap(1_000, None), &inventory, fee).is_err());
+        assert!(risk_limits.check(&swap(100, Some(5_f64)), &inventory, fee).is_err());
+    }
+
+    #[test]
+    fn risk_limits_check_add_liquidity_legs() {
+        let risk_limits = RiskLimits::default().with_max_order_amount("ckUSDT", Nat::from(1_000_u64));
+        let inventory = Inventory {
+            balances: BTreeMap::from([
+                ("ICP".to_string(), Nat::from(2_000_u64)),
+                ("ckUSDT".to_string(), Nat::from(5_000_u64)),
+            ]),
+        };
+        let fee = |_: &str| Nat::from(10_u64);
+        let add_liquidity = |amount_1: u64| Order::AddLiquidity {
+            token_0: "ICP".to_string(),
+            amount_0: Nat::from(100_u64),
+            token_1: "ckUSDT".to_string(),
+            amount_1: Some(Nat::from(amount_1)),
+        };
+
+        assert!(risk_limits.check(&add_liquidity(1_000), &inventory, fee).is_ok());
+        // token_1 leg above max order amount
+        assert!(risk_limits.check(&add_liquidity(1_001), &inventory, fee).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+use super::order::execute;
+use super::price_feed::PriceFeed;
+use super::risk::{Inventory, RiskLimits};
+use super::state_store::StateStore;
+use super::{Strategy, StrategyContext};
+
+use crate::kong_backend::helpers::nat_helpers::nat_zero;
+use crate::kong_backend::tokens::token::Token;
+use crate::kong_backend::KongBackend;
+
+struct Bot {
+    strategy: Box<dyn Strategy>,
+    kong_backend: KongBackend,
+    price_feed: Arc<dyn PriceFeed>,
+    risk_limits: RiskLimits,
+}
+
+impl Bot {
+    async fn tick(&mut self, state: &mut Value) -> Result<()> {
+        let tokens = self.strategy.tokens();
+        let mut inventory = Inventory::load(&self.kong_backend, &tokens).await?;
+        let mut ctx = StrategyContext::new(&self.kong_backend, self.price_feed.as_ref(), &inventory, state);
+        let orders = self.strategy.on_tick(&mut ctx).await?;
+
+        for order in orders.iter().take(self.risk_limits.max_orders_per_tick) {
+            let fee = |symbol: &str| self.kong_backend.token(symbol).map_or(nat_zero(), |token| token.fee());
+            // quote first so the risk limits see every amount the order pays
+            let fill = match order.quote(&self.kong_backend).await {
+                Ok(quoted_order) => match self.risk_limits.check(&quoted_order, &inventory, fee) {
+                    Ok(()) => execute(&self.kong_backend, &quoted_order, self.risk_limits.max_slippage)
+                        .await
+                        .map_err(|e| e.to_string()),
+                    Err(e) => Err(format!("Risk limits: {}", e)),
+                },
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = &fill {
+                println!("{}: {:?} failed: {}", self.strategy.name(), order, e);
+            }
+            if fill.is_ok() {
+                inventory = Inventory::load(&self.kong_backend, &tokens).await?;
+            }
+            let mut ctx = StrategyContext::new(&self.kong_backend, self.price_feed.as_ref(), &inventory, state);
+            self.strategy.on_fill(&mut ctx, order, &fill);
+        }
+        Ok(())
+    }
+}
+
+/// Runs strategies concurrently, each on its own tokio task and interval, until Ctrl-C
+#[derive(Default)]
+pub struct Scheduler {
+    bots: Vec<Bot>,
+    state_store: Option<StateStore>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// persist the strategies' state, loaded when the scheduler starts and saved after every tick
+    pub fn with_state_store(mut self, state_store: StateStore) -> Self {
+        self.state_store = Some(state_store);
+        self
+    }
+
+    /// kong_backend must be created with the bot's own identity, strategies sharing an identity share balances
+    pub fn add(
+        &mut self,
+        strategy: Box<dyn Strategy>,
+        kong_backend: &KongBackend,
+        price_feed: Arc<dyn PriceFeed>,
+        risk_limits: RiskLimits,
+    ) {
+        self.bots.push(Bot {
+            strategy,
+            kong_backend: kong_backend.clone(),
+            price_feed,
+            risk_limits,
+        });
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let mut handles = Vec::new();
+        for mut bot in self.bots {
+            let state_store = self.state_store.clone();
+            let mut shutdown_rx = shutdown_rx.clone();
+            handles.push(tokio::spawn(async move {
+                let name = bot.strategy.name().to_string();
+                let mut state = match &state_store {
+                    Some(state_store) => state_store.load(&name)?,
+                    None => Value::Null,
+                };
+                loop {
+                    if let Err(e) = bot.tick(&mut state).await {
+                        println!("{}: {}", name, e);
+                    }
+                    if let Some(state_store) = &state_store {
+                        state_store.save(&name, &state)?;
+                    }
+                    tokio::select! {
+                        _ = tokio::time::sleep(bot.strategy.interval()) => {}
+                        _ = shutdown_rx.changed() => break,
+                    }
+                }
+                anyhow::Ok(())
+            }));
+        }
+
+        // stop after the current tick on Ctrl-C
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                _ = shutdown_tx.send(true);
+            }
+        });
+
+        for handle in handles {
+            handle.await??;
+        }
+        Ok(())
+    }
+}
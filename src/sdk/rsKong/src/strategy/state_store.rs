@@ -0,0 +1,40 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+/// Persists the state of each strategy as a JSON file <dir>/<strategy name>.json, so bots can be restarted
+#[derive(Debug, Clone)]
+pub struct StateStore {
+    dir: PathBuf,
+}
+
+impl StateStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(StateStore { dir })
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", name))
+    }
+
+    /// state of the strategy, Null if it was never saved
+    pub fn load(&self, name: &str) -> Result<Value> {
+        let path = self.path(name);
+        if !path.exists() {
+            return Ok(Value::Null);
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// writes to a temporary file first so a crash doesn't leave a partial state
+    pub fn save(&self, name: &str, state: &Value) -> Result<()> {
+        let path = self.path(name);
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(state)?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+}
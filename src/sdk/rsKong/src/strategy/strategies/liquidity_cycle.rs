@@ -0,0 +1,98 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use candid::Nat;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::kong_backend::helpers::nat_helpers::{nat_from_decimal_str, nat_to_decimal_str};
+use crate::strategy::order::{Fill, Order};
+use crate::strategy::{Strategy, StrategyContext};
+
+/// Adds a random amount of liquidity on one tick and removes it on the next
+pub struct LiquidityCycle {
+    name: String,
+    token_0: String,
+    range_0: (u64, u64), // random amount of token_0 to add, token_1 is calculated from the pool price
+    token_1: String,
+    interval: Duration,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct LiquidityCycleState {
+    lp_token_amount: Option<String>, // LP tokens added and not removed yet. String as candid Nat doesn't serialize to a JSON number
+    cycles: u64,
+}
+
+impl LiquidityCycle {
+    pub fn new(token_0: &str, range_0: (u64, u64), token_1: &str, interval: Duration) -> Self {
+        LiquidityCycle {
+            name: format!("liquidity_cycle_{}_{}", token_0, token_1),
+            token_0: token_0.to_string(),
+            range_0,
+            token_1: token_1.to_string(),
+            interval,
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for LiquidityCycle {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn tokens(&self) -> Vec<String> {
+        vec![self.token_0.clone(), self.token_1.clone()]
+    }
+
+    async fn on_tick(&mut self, ctx: &mut StrategyContext<'_>) -> Result<Vec<Order>> {
+        let state: LiquidityCycleState = ctx.state();
+        match state.lp_token_amount.as_deref().and_then(|amount| nat_from_decimal_str(amount, 0)) {
+            Some(remove_lp_token_amount) => Ok(vec![Order::RemoveLiquidity {
+                token_0: self.token_0.clone(),
+                token_1: self.token_1.clone(),
+                remove_lp_token_amount,
+            }]),
+            None => Ok(vec![Order::AddLiquidity {
+                token_0: self.token_0.clone(),
+                amount_0: Nat::from(rand::thread_rng().gen_range(self.range_0.0..self.range_0.1)),
+                token_1: self.token_1.clone(),
+                amount_1: None,
+            }]),
+        }
+    }
+
+    fn on_fill(&mut self, ctx: &mut StrategyContext<'_>, _order: &Order, fill: &Result<Fill, String>) {
+        let mut state: LiquidityCycleState = ctx.state();
+        match fill {
+            Ok(Fill::AddLiquidity(reply)) => {
+                println!(
+                    "{}: add liquidity #{} {} {} and {} {} for {} LP tokens",
+                    self.name, reply.request_id, reply.amount_0, reply.symbol_0, reply.amount_1, reply.symbol_1, reply.add_lp_token_amount
+                );
+                state.lp_token_amount = Some(nat_to_decimal_str(&reply.add_lp_token_amount, 0));
+            }
+            Ok(Fill::RemoveLiquidity(reply)) => {
+                println!(
+                    "{}: remove liquidity #{} {} LP tokens for {} {} and {} {}",
+                    self.name,
+                    reply.request_id,
+                    reply.remove_lp_token_amount,
+                    reply.amount_0,
+                    reply.symbol_0,
+                    reply.amount_1,
+                    reply.symbol_1
+                );
+                state.lp_token_amount = None;
+                state.cycles += 1;
+            }
+            _ => (),
+        }
+        _ = ctx.set_state(&state);
+    }
+}
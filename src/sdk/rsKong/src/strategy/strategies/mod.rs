@@ -0,0 +1,4 @@
+//! Ready made strategies, also examples of how to write one
+pub mod liquidity_cycle;
+pub mod pool_arbitrage;
+pub mod random_swaps;
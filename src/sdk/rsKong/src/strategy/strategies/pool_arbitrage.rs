@@ -0,0 +1,119 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use candid::Nat;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::strategy::order::{Fill, Order};
+use crate::strategy::{Strategy, StrategyContext};
+
+/// Trades a Kong pool back towards the price feed's price. When selling base_amount on the pool gets a price
+/// more than threshold_pct above the reference price it sells, when buying base with quote_amount costs a price
+/// more than threshold_pct below it buys. Prices are quoted for the order sizes, so spread and fees are included
+pub struct PoolArbitrage {
+    name: String,
+    base: String,
+    base_amount: Nat,
+    quote: String,
+    quote_amount: Nat,
+    threshold_pct: f64,
+    interval: Duration,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct PoolArbitrageState {
+    sell_price: f64, // quote received per base sold
+    buy_price: f64,  // quote paid per base bought
+    reference_price: f64,
+    trades: u64,
+}
+
+impl PoolArbitrage {
+    pub fn new(base: &str, base_amount: Nat, quote: &str, quote_amount: Nat, threshold_pct: f64, interval: Duration) -> Self {
+        PoolArbitrage {
+            name: format!("pool_arbitrage_{}_{}", base, quote),
+            base: base.to_string(),
+            base_amount,
+            quote: quote.to_string(),
+            quote_amount,
+            threshold_pct,
+            interval,
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for PoolArbitrage {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn tokens(&self) -> Vec<String> {
+        vec![self.base.clone(), self.quote.clone()]
+    }
+
+    async fn on_tick(&mut self, ctx: &mut StrategyContext<'_>) -> Result<Vec<Order>> {
+        // executable prices of both order sizes, so the spread and fees are included
+        let sell_price = ctx
+            .kong_backend
+            .swap_amounts(&self.base, &self.base_amount, &self.quote)
+            .await?
+            .price;
+        let buy_price = 1_f64
+            / ctx
+                .kong_backend
+                .swap_amounts(&self.quote, &self.quote_amount, &self.base)
+                .await?
+                .price;
+        let reference_price = ctx.price_feed.price(&self.base, &self.quote).await?;
+        let mut state: PoolArbitrageState = ctx.state();
+        state.sell_price = sell_price;
+        state.buy_price = buy_price;
+        state.reference_price = reference_price;
+        ctx.set_state(&state)?;
+
+        let order = if (sell_price / reference_price - 1_f64) * 100_f64 > self.threshold_pct {
+            Order::Swap {
+                pay_token: self.base.clone(),
+                pay_amount: self.base_amount.clone(),
+                receive_token: self.quote.clone(),
+                max_slippage: None,
+            }
+        } else if (buy_price / reference_price - 1_f64) * 100_f64 < -self.threshold_pct {
+            Order::Swap {
+                pay_token: self.quote.clone(),
+                pay_amount: self.quote_amount.clone(),
+                receive_token: self.base.clone(),
+                max_slippage: None,
+            }
+        } else {
+            return Ok(vec![]);
+        };
+        Ok(vec![order])
+    }
+
+    fn on_fill(&mut self, ctx: &mut StrategyContext<'_>, _order: &Order, fill: &Result<Fill, String>) {
+        if let Ok(Fill::Swap(reply)) = fill {
+            let mut state: PoolArbitrageState = ctx.state();
+            state.trades += 1;
+            println!(
+                "{}: pool sell {} buy {} vs reference {}, swap #{} {} {} to {} {}",
+                self.name,
+                state.sell_price,
+                state.buy_price,
+                state.reference_price,
+                reply.request_id,
+                reply.pay_amount,
+                reply.pay_symbol,
+                reply.receive_amount,
+                reply.receive_symbol
+            );
+            _ = ctx.set_state(&state);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use candid::Nat;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::strategy::order::{Fill, Order};
+use crate::strategy::{Strategy, StrategyContext};
+
+/// Swaps a random amount back and forth between two tokens, alternating direction every tick.
+/// Generates volume on test networks
+pub struct RandomSwaps {
+    name: String,
+    token_0: String,
+    range_0: (u64, u64), // random amount of token_0 to pay
+    token_1: String,
+    range_1: (u64, u64), // random amount of token_1 to pay
+    interval: Duration,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct RandomSwapsState {
+    pay_token_1: bool, // direction of the next swap
+    swaps: u64,
+    errors: u64,
+}
+
+impl RandomSwaps {
+    pub fn new(token_0: &str, range_0: (u64, u64), token_1: &str, range_1: (u64, u64), interval: Duration) -> Self {
+        RandomSwaps {
+            name: format!("random_swaps_{}_{}", token_0, token_1),
+            token_0: token_0.to_string(),
+            range_0,
+            token_1: token_1.to_string(),
+            range_1,
+            interval,
+        }
+    }
+}
+
+#[async_trait]
+impl Strategy for RandomSwaps {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn tokens(&self) -> Vec<String> {
+        vec![self.token_0.clone(), self.token_1.clone()]
+    }
+
+    async fn on_tick(&mut self, ctx: &mut StrategyContext<'_>) -> Result<Vec<Order>> {
+        let mut state: RandomSwapsState = ctx.state();
+        let ((pay_token, (min_amount, max_amount)), receive_token) = if state.pay_token_1 {
+            ((&self.token_1, self.range_1), &self.token_0)
+        } else {
+            ((&self.token_0, self.range_0), &self.token_1)
+        };
+        let pay_amount = Nat::from(rand::thread_rng().gen_range(min_amount..max_amount));
+        state.pay_token_1 = !state.pay_token_1;
+        ctx.set_state(&state)?;
+        Ok(vec![Order::Swap {
+            pay_token: pay_token.to_string(),
+            pay_amount,
+            receive_token: receive_token.to_string(),
+            max_slippage: None,
+        }])
+    }
+
+    fn on_fill(&mut self, ctx: &mut StrategyContext<'_>, _order: &Order, fill: &Result<Fill, String>) {
+        let mut state: RandomSwapsState = ctx.state();
+        match fill {
+            Ok(Fill::Swap(reply)) => {
+                state.swaps += 1;
+                println!(
+                    "{}: swap #{} {} {} to {} {}",
+                    self.name, reply.request_id, reply.pay_amount, reply.pay_symbol, reply.receive_amount, reply.receive_symbol
+                );
+            }
+            Ok(_) => (),
+            Err(_) => state.errors += 1,
+        }
+        _ = ctx.set_state(&state);
+    }
+}
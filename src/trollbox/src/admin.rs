@@ -1,8 +1,10 @@
 use ic_cdk::api::caller;
 use crate::state::*;
+use crate::rooms::remove_room_messages;
 use candid::Principal;
+use kong_lib::stable_trusted_origin::stable_trusted_origin::StableTrustedOriginLog;
 /// Check if the caller is an admin
-fn caller_is_admin() -> bool {
+pub(crate) fn caller_is_admin() -> bool {
     let caller_principal = caller();
     ADMINS.with(|admins| {
         admins.borrow().contains(&caller_principal)
@@ -10,7 +12,7 @@ fn caller_is_admin() -> bool {
 }
 
 /// Check if the caller is a controller of the canister
-fn caller_is_controller() -> bool {
+pub(crate) fn caller_is_controller() -> bool {
     let caller_principal = caller();
     ic_cdk::api::is_controller(&caller_principal)
 }
@@ -75,17 +77,20 @@ pub fn delete_message(message_id: u64) -> Result<(), String> {
     }
     
     // Check if the message exists
-    let message_exists = MESSAGE_STORE.with(|store| {
-        store.borrow().contains_key(&message_id)
+    let message = MESSAGE_STORE.with(|store| {
+        store.borrow().get(&message_id)
     });
     
-    if !message_exists {
+    let Some(message) = message else {
         return Err(format!("Message with ID {} not found", message_id));
-    }
+    };
+    
+    // Delete the message and remove it from its room
+    remove_room_messages(&message.room_id, &[message_id]);
     
-    // Delete the message
-    MESSAGE_STORE.with(|store| {
-        store.borrow_mut().remove(&message_id);
+    // Record the removal in the moderation log so the author can appeal
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().record_removal(message_id, message.principal, &message.message, caller(), None, ic_cdk::api::time());
     });
     
     Ok(())
@@ -99,17 +104,9 @@ pub fn ban_user(user_principal: candid::Principal, days: u64) -> Result<(), Stri
         return Err("Unauthorized: Only admins can ban users".to_string());
     }
     
-    // Convert days to nanoseconds and add to current time
-    let ban_duration_ns = days * 24 * 60 * 60 * 1_000_000_000;
-    let current_time = ic_cdk::api::time();
-    let ban_expiry = current_time + ban_duration_ns;
-    
-    // Add user to banned list (using stable storage)
-    BANNED_USERS_STORE.with(|banned_users| {
-        banned_users.borrow_mut().insert(
-            PrincipalStorable(user_principal),
-            ExpiryTimeStorable(ban_expiry)
-        );
+    // Add user to banned list and record the ban in the moderation log
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().ban(user_principal, days, caller(), ic_cdk::api::time());
     });
     
     Ok(())
@@ -123,9 +120,9 @@ pub fn unban_user(user_principal: candid::Principal) -> Result<(), String> {
         return Err("Unauthorized: Only admins can unban users".to_string());
     }
     
-    // Remove user from banned list (using stable storage)
-    BANNED_USERS_STORE.with(|banned_users| {
-        banned_users.borrow_mut().remove(&PrincipalStorable(user_principal));
+    // Remove user from banned list and record the unban in the moderation log
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().unban(user_principal, caller(), ic_cdk::api::time());
     });
     
     Ok(())
@@ -135,4 +132,40 @@ pub fn unban_user(user_principal: candid::Principal) -> Result<(), String> {
 #[ic_cdk::query]
 pub fn check_ban_status(user_principal: candid::Principal) -> Option<u64> {
     is_user_banned(&user_principal)
-} 
\ No newline at end of file
+}
+
+/// Allows admins or controllers to add a trusted origin returned by icrc28_trusted_origins
+#[ic_cdk::update]
+pub fn add_trusted_origin(origin: String) -> Result<Vec<String>, String> {
+    if !caller_is_admin() && !caller_is_controller() {
+        return Err("Unauthorized: Only admins or controllers can add trusted origins".to_string());
+    }
+
+    TRUSTED_ORIGINS.with(|store| {
+        store.borrow_mut().add(&origin, &caller().to_text(), ic_cdk::api::time())
+    })
+}
+
+/// Allows admins or controllers to remove a trusted origin
+#[ic_cdk::update]
+pub fn remove_trusted_origin(origin: String) -> Result<Vec<String>, String> {
+    if !caller_is_admin() && !caller_is_controller() {
+        return Err("Unauthorized: Only admins or controllers can remove trusted origins".to_string());
+    }
+
+    TRUSTED_ORIGINS.with(|store| {
+        store.borrow_mut().remove(&origin, &caller().to_text(), ic_cdk::api::time())
+    })
+}
+
+/// Returns the audit log of changes to the trusted origins, newest first
+#[ic_cdk::query]
+pub fn get_trusted_origins_log(limit: Option<u64>) -> Result<Vec<StableTrustedOriginLog>, String> {
+    if !caller_is_admin() && !caller_is_controller() {
+        return Err("Unauthorized: Only admins or controllers can view the trusted origins log".to_string());
+    }
+
+    Ok(TRUSTED_ORIGINS.with(|store| {
+        store.borrow().get_logs(limit.map(|l| l as usize))
+    }))
+}
@@ -9,6 +9,7 @@ use std::borrow::Cow;
 use icrc_ledger_types::icrc21::errors::ErrorInfo;
 use icrc_ledger_types::icrc21::requests::{ConsentMessageMetadata, ConsentMessageRequest};
 use icrc_ledger_types::icrc21::responses::{ConsentInfo, ConsentMessage};
+use crate::types::ReportReason;
 
 // We'll implement our own simple hash function since we don't have sha2
 fn hash_principals(principals: &[Principal]) -> Vec<u8> {
@@ -68,6 +69,42 @@ pub fn icrc21_canister_call_consent_message(consent_msg_request: ConsentMessageR
                 caller_principal
             ))
         },
+        "create_room_message" => {
+            let (room_id, message) = candid::decode_args::<(String, String)>(&consent_msg_request.arg)
+                .map_err(|e| ErrorInfo { 
+                    description: format!("Failed to decode message: {}", e) 
+                })?;
+
+            ConsentMessage::GenericDisplayMessage(format!(
+                "# Approve Trollbox Message\n\nRoom: {}\n\nMessage: {}\n\nFrom: {}",
+                room_id,
+                message,
+                caller_principal
+            ))
+        },
+        "join_room" => {
+            let room_id = decode_one::<String>(&consent_msg_request.arg)
+                .map_err(|e| ErrorInfo { 
+                    description: format!("Failed to decode room: {}", e) 
+                })?;
+
+            ConsentMessage::GenericDisplayMessage(format!(
+                "# Join Trollbox Room\n\nRoom: {}\n\nTrollbox will check your token or LP balance to verify you can join this room.",
+                room_id
+            ))
+        },
+        "report_message" => {
+            let (message_id, reason, _note) = candid::decode_args::<(u64, ReportReason, Option<String>)>(&consent_msg_request.arg)
+                .map_err(|e| ErrorInfo { 
+                    description: format!("Failed to decode report: {}", e) 
+                })?;
+
+            ConsentMessage::GenericDisplayMessage(format!(
+                "# Report Trollbox Message\n\nMessage ID: {}\n\nReason: {:?}\n\nModerators will review this message.",
+                message_id,
+                reason
+            ))
+        },
         // Add other method matches here as needed
         _ => ConsentMessage::GenericDisplayMessage(
             format!("Approve Trollbox to execute {}?", 
@@ -312,16 +349,10 @@ pub struct Icrc28TrustedOriginsResponse {
 }
 
 // list every base URL that users will authenticate to your app from
+// stored in stable memory and maintained by admins with add_trusted_origin / remove_trusted_origin
 #[query]
 fn icrc28_trusted_origins() -> Icrc28TrustedOriginsResponse {
-    let trusted_origins = vec![
-        format!("https://edoy4-liaaa-aaaar-qakha-cai.localhost:5173"), // svelte FE
-        format!("http://localhost:5173"),
-        String::from("https://kongswap.io"),
-        String::from("https://www.kongswap.io"),
-        String::from("https://edoy4-liaaa-aaaar-qakha-cai.icp0.io"),
-        String::from("https://dev.kongswap.io"),
-    ];
+    let trusted_origins = crate::state::TRUSTED_ORIGINS.with(|store| store.borrow().get());
 
     Icrc28TrustedOriginsResponse { trusted_origins }
 }
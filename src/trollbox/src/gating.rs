@@ -0,0 +1,67 @@
+use candid::{CandidType, Deserialize, Nat, Principal};
+use icrc_ledger_types::icrc1::account::Account;
+use crate::types::*;
+
+// Subset of kong_backend's UserBalancesReply needed to read LP balances
+#[derive(CandidType, Deserialize, Debug)]
+enum KongUserBalancesReply {
+    LP(KongLPBalance),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct KongLPBalance {
+    symbol: String,
+    balance: f64,
+}
+
+/// Check that a user holds enough of the token or LP position required by the room gate
+pub async fn check_room_gate(gate: &RoomGate, user: Principal) -> Result<(), String> {
+    match gate {
+        RoomGate::TokenBalance { ledger, min_balance } => {
+            let balance = icrc1_balance_of(*ledger, user).await?;
+            if balance < *min_balance {
+                return Err(format!("This room requires a balance of at least {} of token {}", min_balance, ledger));
+            }
+        }
+        RoomGate::LpBalance { symbol, min_balance } => {
+            let balance = kong_lp_balance(symbol, user).await?;
+            if balance < *min_balance {
+                return Err(format!("This room requires at least {} {} LP tokens", min_balance, symbol));
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn icrc1_balance_of(ledger: Principal, user: Principal) -> Result<Nat, String> {
+    let account = Account { owner: user, subaccount: None };
+    match ic_cdk::call::<(Account,), (Nat,)>(ledger, "icrc1_balance_of", (account,)).await {
+        Ok((balance,)) => Ok(balance),
+        Err((code, msg)) => Err(format!("Failed to get balance from {}: {} (code: {:?})", ledger, msg, code)),
+    }
+}
+
+async fn kong_lp_balance(symbol: &str, user: Principal) -> Result<f64, String> {
+    let kong_backend = Principal::from_text(KONG_BACKEND_CANISTER_ID)
+        .map_err(|e| format!("Invalid kong_backend ID: {}", e))?;
+
+    let balances = match ic_cdk::call::<(String,), (Result<Vec<KongUserBalancesReply>, String>,)>(
+        kong_backend,
+        "user_balances",
+        (user.to_text(),),
+    )
+    .await
+    {
+        Ok((Ok(balances),)) => balances,
+        // kong_backend returns an error for principals it has never seen, so they hold no LP tokens
+        Ok((Err(_),)) => return Ok(0.0),
+        Err((code, msg)) => return Err(format!("kong_backend call failed: {} (code: {:?})", msg, code)),
+    };
+
+    Ok(balances
+        .into_iter()
+        .map(|KongUserBalancesReply::LP(lp)| lp)
+        .filter(|lp| lp.symbol == symbol)
+        .map(|lp| lp.balance)
+        .sum())
+}
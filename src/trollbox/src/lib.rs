@@ -1,6 +1,7 @@
 use icrc_ledger_types::icrc21::requests::ConsentMessageRequest;
 use icrc_ledger_types::icrc21::responses::ConsentInfo;
 use icrc_ledger_types::icrc21::errors::ErrorInfo;
+use kong_lib::stable_trusted_origin::stable_trusted_origin::StableTrustedOriginLog;
 
 // Module declarations
 mod authentication;
@@ -9,6 +10,9 @@ mod state;
 mod queries;
 mod updates;
 mod admin;
+mod gating;
+mod rooms;
+mod reports;
 
 // Public exports
 pub use authentication::*;
@@ -16,6 +20,8 @@ pub use types::*;
 pub use queries::*;
 pub use updates::*;
 pub use admin::*;
+pub use rooms::*;
+pub use reports::*;
 
 // Initialize the canister
 #[ic_cdk::init]
@@ -24,6 +30,13 @@ fn init() {
     
     // Initialize the canister deployer as the first admin
     admin::init_admin();
+
+    rooms::init_rooms();
+}
+
+#[ic_cdk::post_upgrade]
+fn post_upgrade() {
+    rooms::init_rooms();
 }
 
 // Candid interface generation
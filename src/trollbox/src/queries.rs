@@ -25,8 +25,14 @@ pub fn get_messages(params: Option<PaginationParams>) -> MessagesPage {
             };
         }
 
-        // Collect all messages
-        let mut messages: Vec<Message> = store.iter().map(|(_, msg)| msg.clone()).collect();
+        // Collect all messages of the global room, leaving out messages hidden by moderation
+        let mut messages: Vec<Message> = MODERATION.with(|moderation| {
+            let moderation = moderation.borrow();
+            store.iter()
+                .map(|(_, msg)| msg)
+                .filter(|msg| msg.room_id == GLOBAL_ROOM_ID && !moderation.is_hidden(msg.id))
+                .collect()
+        });
         
         // Sort by newest first (using timestamp)
         messages.sort_by(|a, b| b.created_at.cmp(&a.created_at));
@@ -63,6 +69,9 @@ pub fn get_messages(params: Option<PaginationParams>) -> MessagesPage {
 
 #[ic_cdk::query]
 pub fn get_message(id: u64) -> Option<Message> {
+    if MODERATION.with(|moderation| moderation.borrow().is_hidden(id)) {
+        return None;
+    }
     MESSAGE_STORE.with(|store| {
         store.borrow().get(&id)
     })
@@ -0,0 +1,600 @@
+use candid::Principal;
+use ic_cdk::api::{caller, time};
+use crate::admin::{caller_is_admin, caller_is_controller};
+use crate::gating::check_room_gate;
+use crate::state::*;
+use crate::types::*;
+
+/// The global room, created on init and upgrade so existing messages keep working
+pub fn global_room() -> Room {
+    ROOM_STORE.with(|rooms| {
+        rooms.borrow().get(&GLOBAL_ROOM_ID.to_string())
+    }).unwrap_or_else(|| Room {
+        id: GLOBAL_ROOM_ID.to_string(),
+        name: "Global".to_string(),
+        kind: RoomKind::Global,
+        gate: None,
+        max_message_length: MAX_MESSAGE_LENGTH as u64,
+        max_messages: None,
+        retention_secs: None,
+        moderators: Vec::new(),
+        created_by: ic_cdk::api::id(),
+        created_at: time(),
+    })
+}
+
+/// Create the global room if missing and index messages stored before rooms existed
+pub fn init_rooms() {
+    let room = global_room();
+    ROOM_STORE.with(|rooms| {
+        rooms.borrow_mut().insert(room.id.clone(), room);
+    });
+
+    let keys: Vec<RoomMessageKey> = MESSAGE_STORE.with(|store| {
+        store.borrow()
+            .iter()
+            .map(|(id, msg)| RoomMessageKey { room_id: msg.room_id, message_id: id })
+            .collect()
+    });
+    let mut counts: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
+    ROOM_MESSAGE_INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        for key in keys {
+            *counts.entry(key.room_id.clone()).or_default() += 1;
+            index.insert(key, ());
+        }
+    });
+    ROOM_MESSAGE_COUNTS.with(|store| {
+        let mut store = store.borrow_mut();
+        let stale: Vec<String> = store.iter()
+            .map(|(room_id, _)| room_id)
+            .filter(|room_id| !counts.contains_key(room_id))
+            .collect();
+        for room_id in stale {
+            store.remove(&room_id);
+        }
+        for (room_id, count) in counts {
+            store.insert(room_id, count);
+        }
+    });
+}
+
+fn get_room_or_err(room_id: &str) -> Result<Room, String> {
+    ROOM_STORE.with(|rooms| {
+        rooms.borrow().get(&room_id.to_string())
+    }).ok_or_else(|| format!("Room {} not found", room_id))
+}
+
+/// Admins, controllers and the room's moderators can moderate a room
+pub fn can_moderate(room: &Room, principal: &Principal) -> bool {
+    caller_is_admin() || caller_is_controller() || room.moderators.contains(principal)
+}
+
+fn has_valid_membership(room: &Room, principal: Principal) -> bool {
+    if room.gate.is_none() {
+        return true;
+    }
+    let key = RoomMemberKey { room_id: room.id.clone(), principal };
+    ROOM_MEMBERS.with(|members| {
+        members.borrow().get(&key)
+    }).is_some_and(|verified_at| time().saturating_sub(verified_at) < ROOM_MEMBERSHIP_TTL_NS)
+}
+
+fn validate_room_args(args: &RoomArgs) -> Result<(), String> {
+    if args.id.is_empty() || args.id.len() > MAX_ROOM_ID_LENGTH {
+        return Err(format!("Room ID must be between 1 and {} characters", MAX_ROOM_ID_LENGTH));
+    }
+    if !args.id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_' || c == ':') {
+        return Err("Room ID can only contain lowercase letters, digits, '-', '_' and ':'".to_string());
+    }
+    let name = args.name.trim();
+    if name.is_empty() || name.len() > MAX_ROOM_NAME_LENGTH {
+        return Err(format!("Room name must be between 1 and {} characters", MAX_ROOM_NAME_LENGTH));
+    }
+    if let Some(max_length) = args.max_message_length {
+        if max_length == 0 || max_length > MAX_ROOM_MESSAGE_LENGTH as u64 {
+            return Err(format!("Maximum message length must be between 1 and {}", MAX_ROOM_MESSAGE_LENGTH));
+        }
+    }
+    if args.max_messages == Some(0) {
+        return Err("Maximum messages must be greater than 0".to_string());
+    }
+    if let Some(RoomGate::LpBalance { symbol, min_balance }) = &args.gate {
+        if symbol.is_empty() || !min_balance.is_finite() || *min_balance < 0.0 {
+            return Err("Invalid LP balance gate".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Allows admins to create a room
+#[ic_cdk::update]
+pub fn create_room(args: RoomArgs) -> Result<Room, String> {
+    if !caller_is_admin() && !caller_is_controller() {
+        return Err("Unauthorized: Only admins or controllers can create rooms".to_string());
+    }
+    validate_room_args(&args)?;
+
+    if ROOM_STORE.with(|rooms| rooms.borrow().contains_key(&args.id)) {
+        return Err(format!("Room {} already exists", args.id));
+    }
+
+    let room = Room {
+        id: args.id,
+        name: args.name.trim().to_string(),
+        kind: args.kind,
+        gate: args.gate,
+        max_message_length: args.max_message_length.unwrap_or(MAX_MESSAGE_LENGTH as u64),
+        max_messages: args.max_messages,
+        retention_secs: args.retention_secs,
+        moderators: Vec::new(),
+        created_by: caller(),
+        created_at: time(),
+    };
+
+    ROOM_STORE.with(|rooms| {
+        rooms.borrow_mut().insert(room.id.clone(), room.clone());
+    });
+
+    Ok(room)
+}
+
+/// Allows admins to change a room's name, gate and retention
+#[ic_cdk::update]
+pub fn update_room(args: RoomArgs) -> Result<Room, String> {
+    if !caller_is_admin() && !caller_is_controller() {
+        return Err("Unauthorized: Only admins or controllers can update rooms".to_string());
+    }
+    validate_room_args(&args)?;
+
+    let mut room = get_room_or_err(&args.id)?;
+    room.name = args.name.trim().to_string();
+    room.kind = args.kind;
+    room.gate = args.gate;
+    room.max_message_length = args.max_message_length.unwrap_or(room.max_message_length);
+    room.max_messages = args.max_messages;
+    room.retention_secs = args.retention_secs;
+
+    ROOM_STORE.with(|rooms| {
+        rooms.borrow_mut().insert(room.id.clone(), room.clone());
+    });
+    prune_room(&room, time());
+
+    Ok(room)
+}
+
+/// Allows admins to delete a room with all its messages
+#[ic_cdk::update]
+pub fn delete_room(room_id: String) -> Result<(), String> {
+    if !caller_is_admin() && !caller_is_controller() {
+        return Err("Unauthorized: Only admins or controllers can delete rooms".to_string());
+    }
+    if room_id == GLOBAL_ROOM_ID {
+        return Err("The global room cannot be deleted".to_string());
+    }
+    get_room_or_err(&room_id)?;
+
+    let message_ids = room_message_ids(&room_id);
+    remove_room_messages(&room_id, &message_ids);
+
+    let members: Vec<RoomMemberKey> = ROOM_MEMBERS.with(|members| {
+        members.borrow()
+            .range(RoomMemberKey { room_id: room_id.clone(), principal: Principal::from_slice(&[]) }..)
+            .take_while(|(key, _)| key.room_id == room_id)
+            .map(|(key, _)| key)
+            .collect()
+    });
+    ROOM_MEMBERS.with(|store| {
+        let mut store = store.borrow_mut();
+        for key in members {
+            store.remove(&key);
+        }
+    });
+
+    ROOM_STORE.with(|rooms| {
+        rooms.borrow_mut().remove(&room_id);
+    });
+
+    Ok(())
+}
+
+/// Allows admins to set the moderators of a room
+#[ic_cdk::update]
+pub fn set_room_moderators(room_id: String, moderators: Vec<candid::Principal>) -> Result<Room, String> {
+    if !caller_is_admin() && !caller_is_controller() {
+        return Err("Unauthorized: Only admins or controllers can set room moderators".to_string());
+    }
+    if moderators.len() > MAX_ROOM_MODERATORS {
+        return Err(format!("A room can have at most {} moderators", MAX_ROOM_MODERATORS));
+    }
+
+    let mut room = get_room_or_err(&room_id)?;
+    let mut moderators = moderators;
+    moderators.sort();
+    moderators.dedup();
+    room.moderators = moderators;
+
+    ROOM_STORE.with(|rooms| {
+        rooms.borrow_mut().insert(room.id.clone(), room.clone());
+    });
+
+    Ok(room)
+}
+
+#[ic_cdk::query]
+pub fn get_rooms() -> Vec<Room> {
+    ROOM_STORE.with(|rooms| {
+        rooms.borrow().iter().map(|(_, room)| room).collect()
+    })
+}
+
+#[ic_cdk::query]
+pub fn get_room(room_id: String) -> Option<Room> {
+    ROOM_STORE.with(|rooms| {
+        rooms.borrow().get(&room_id)
+    })
+}
+
+/// Verify the caller's holding for a gated room. Returns when the membership has to be renewed
+#[ic_cdk::update]
+pub async fn join_room(room_id: String) -> Result<Option<u64>, String> {
+    let room = get_room_or_err(&room_id)?;
+    verify_membership(&room, caller()).await
+}
+
+async fn verify_membership(room: &Room, principal: Principal) -> Result<Option<u64>, String> {
+    let Some(gate) = &room.gate else {
+        return Ok(None);
+    };
+    check_room_gate(gate, principal).await?;
+
+    let verified_at = time();
+    ROOM_MEMBERS.with(|members| {
+        members.borrow_mut().insert(RoomMemberKey { room_id: room.id.clone(), principal }, verified_at);
+    });
+
+    Ok(Some(verified_at + ROOM_MEMBERSHIP_TTL_NS))
+}
+
+/// Post a message to a room. Gated rooms re-check the caller's holding when the membership has expired
+#[ic_cdk::update]
+pub async fn create_room_message(room_id: String, content: String) -> Result<Message, String> {
+    let room = get_room_or_err(&room_id)?;
+
+    if !has_valid_membership(&room, caller()) {
+        verify_membership(&room, caller()).await?;
+    }
+
+    // Room may have been changed or deleted while the gate was checked
+    let room = get_room_or_err(&room_id)?;
+    post_message(&room, content)
+}
+
+/// Get messages of a room, newest first. The cursor is the ID of the last message of the previous page
+#[ic_cdk::query]
+pub fn get_room_messages(room_id: String, params: Option<PaginationParams>) -> Result<MessagesPage, String> {
+    let room = get_room_or_err(&room_id)?;
+    let caller = caller();
+    if !has_valid_membership(&room, caller) && !can_moderate(&room, &caller) {
+        return Err("Join this room to read its messages".to_string());
+    }
+
+    let params = params.unwrap_or(PaginationParams {
+        cursor: None,
+        limit: None,
+    });
+    let limit = params.limit
+        .map(|l| l.try_into().unwrap_or(DEFAULT_PAGE_SIZE))
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .min(MAX_MESSAGES_STORED);
+    let end = params.cursor.unwrap_or(u64::MAX);
+    let min_created_at = retention_cutoff(&room, time());
+
+    let message_ids: Vec<u64> = ROOM_MESSAGE_INDEX.with(|index| {
+        index.borrow()
+            .range(RoomMessageKey { room_id: room_id.clone(), message_id: 0 }..RoomMessageKey { room_id: room_id.clone(), message_id: end })
+            .rev()
+            .take(limit)
+            .map(|(key, _)| key.message_id)
+            .collect()
+    });
+
+    let messages: Vec<Message> = MESSAGE_STORE.with(|store| {
+        let store = store.borrow();
+        message_ids.iter()
+            .filter_map(|id| store.get(id))
+            .filter(|msg| msg.created_at >= min_created_at)
+            .collect()
+    });
+
+    let next_cursor = if message_ids.len() == limit && messages.len() == limit {
+        messages.last().map(|msg| msg.id)
+    } else {
+        None
+    };
+
+    // Leave out messages hidden by reports or moderators
+    let messages: Vec<Message> = MODERATION.with(|moderation| {
+        let moderation = moderation.borrow();
+        messages.into_iter().filter(|msg| !moderation.is_hidden(msg.id)).collect()
+    });
+
+    Ok(MessagesPage {
+        messages,
+        next_cursor,
+    })
+}
+
+/// Allows room moderators and admins to delete a message in a room
+#[ic_cdk::update]
+pub fn delete_room_message(room_id: String, message_id: u64) -> Result<(), String> {
+    let room = get_room_or_err(&room_id)?;
+    if !can_moderate(&room, &caller()) {
+        return Err("Unauthorized: Only moderators of this room can delete messages".to_string());
+    }
+
+    let message = MESSAGE_STORE.with(|store| {
+        store.borrow().get(&message_id)
+    }).filter(|msg| msg.room_id == room_id);
+    let Some(message) = message else {
+        return Err(format!("Message with ID {} not found in room {}", message_id, room_id));
+    };
+
+    remove_room_messages(&room_id, &[message_id]);
+
+    // Record the removal in the moderation log so the author can appeal
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().record_removal(message_id, message.principal, &message.message, caller(), None, time());
+    });
+    Ok(())
+}
+
+/// Validate and store a message in a room, applying bans, rate limits and the room's retention
+pub fn post_message(room: &Room, content: String) -> Result<Message, String> {
+    let caller = caller();
+    let current_time = time();
+
+    // Check if user is banned
+    if let Some(remaining_ban_seconds) = is_user_banned(&caller) {
+        return Err(format!(
+            "You are banned from posting for {} more {} until your ban expires.",
+            remaining_ban_seconds,
+            if remaining_ban_seconds == 1 { "second" } else { "seconds" }
+        ));
+    }
+
+    // Check if user is sending messages too quickly
+    let can_send = LAST_MESSAGE_TIME.with(|last_time_map| {
+        let mut map = last_time_map.borrow_mut();
+        if let Some(last_time) = map.get(&caller) {
+            if current_time - last_time < MIN_MESSAGE_INTERVAL_NS {
+                false
+            } else {
+                map.insert(caller, current_time);
+                true
+            }
+        } else {
+            map.insert(caller, current_time);
+            true
+        }
+    });
+
+    if !can_send {
+        return Err(format!(
+            "You're sending messages too quickly. Please wait at least {} seconds between messages.",
+            MIN_MESSAGE_INTERVAL_NS / 1_000_000_000
+        ));
+    }
+
+    let censored_content = validate_message(&content, room.max_message_length as usize)?;
+
+    let id = next_message_id();
+    let message = Message {
+        id,
+        message: censored_content,
+        principal: caller,
+        created_at: current_time,
+        room_id: room.id.clone(),
+    };
+
+    insert_room_message(&message);
+    prune_room(room, current_time);
+
+    Ok(message)
+}
+
+/// Store a message and add it to the index and message count of its room
+fn insert_room_message(message: &Message) {
+    MESSAGE_STORE.with(|store| {
+        store.borrow_mut().insert(message.id, message.clone());
+    });
+    ROOM_MESSAGE_INDEX.with(|index| {
+        index.borrow_mut().insert(RoomMessageKey { room_id: message.room_id.clone(), message_id: message.id }, ());
+    });
+    ROOM_MESSAGE_COUNTS.with(|counts| {
+        let mut counts = counts.borrow_mut();
+        let count = counts.get(&message.room_id).unwrap_or(0);
+        counts.insert(message.room_id.clone(), count + 1);
+    });
+}
+
+fn retention_cutoff(room: &Room, now: u64) -> u64 {
+    room.retention_secs
+        .map(|secs| now.saturating_sub(secs.saturating_mul(1_000_000_000)))
+        .unwrap_or(0)
+}
+
+fn room_message_ids(room_id: &str) -> Vec<u64> {
+    ROOM_MESSAGE_INDEX.with(|index| {
+        index.borrow()
+            .range(RoomMessageKey { room_id: room_id.to_string(), message_id: 0 }..)
+            .take_while(|(key, _)| key.room_id == room_id)
+            .map(|(key, _)| key.message_id)
+            .collect()
+    })
+}
+
+/// Remove messages beyond the room's maximum count or older than its retention period
+fn prune_room(room: &Room, now: u64) {
+    let message_count = ROOM_MESSAGE_COUNTS.with(|counts| counts.borrow().get(&room.id)).unwrap_or(0);
+    let over_limit = room.max_messages
+        .map(|max| message_count.saturating_sub(max) as usize)
+        .unwrap_or(0);
+    let min_created_at = retention_cutoff(room, now);
+
+    // Message IDs are increasing with time, so the oldest and expired messages are at the start
+    let to_remove: Vec<u64> = ROOM_MESSAGE_INDEX.with(|index| {
+        MESSAGE_STORE.with(|store| {
+            let store = store.borrow();
+            index.borrow()
+                .range(RoomMessageKey { room_id: room.id.clone(), message_id: 0 }..)
+                .take_while(|(key, _)| key.room_id == room.id)
+                .map(|(key, _)| key.message_id)
+                .enumerate()
+                .take_while(|(i, id)| *i < over_limit || store.get(id).is_none_or(|msg| msg.created_at < min_created_at))
+                .map(|(_, id)| id)
+                .collect()
+        })
+    });
+
+    if !to_remove.is_empty() {
+        remove_room_messages(&room.id, &to_remove);
+    }
+}
+
+pub(crate) fn remove_room_messages(room_id: &str, message_ids: &[u64]) {
+    MESSAGE_STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        for id in message_ids {
+            store.remove(id);
+        }
+    });
+    let removed = ROOM_MESSAGE_INDEX.with(|index| {
+        let mut index = index.borrow_mut();
+        message_ids.iter()
+            .filter(|id| index.remove(&RoomMessageKey { room_id: room_id.to_string(), message_id: **id }).is_some())
+            .count() as u64
+    });
+    ROOM_MESSAGE_COUNTS.with(|counts| {
+        let mut counts = counts.borrow_mut();
+        match counts.get(&room_id.to_string()).unwrap_or(0).saturating_sub(removed) {
+            0 => counts.remove(&room_id.to_string()),
+            count => counts.insert(room_id.to_string(), count),
+        };
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: u64 = 1_000_000_000;
+
+    fn room(id: &str, max_messages: Option<u64>, retention_secs: Option<u64>) -> Room {
+        Room {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: RoomKind::Pool(id.to_string()),
+            gate: None,
+            max_message_length: MAX_MESSAGE_LENGTH as u64,
+            max_messages,
+            retention_secs,
+            moderators: Vec::new(),
+            created_by: Principal::anonymous(),
+            created_at: 0,
+        }
+    }
+
+    fn post(room_id: &str, created_at: u64) -> u64 {
+        let id = next_message_id();
+        insert_room_message(&Message {
+            id,
+            message: format!("message {}", id),
+            principal: Principal::anonymous(),
+            created_at,
+            room_id: room_id.to_string(),
+        });
+        id
+    }
+
+    fn message_count(room_id: &str) -> Option<u64> {
+        ROOM_MESSAGE_COUNTS.with(|counts| counts.borrow().get(&room_id.to_string()))
+    }
+
+    // The stored count always matches the index
+    fn assert_count_in_sync(room_id: &str) {
+        let indexed = room_message_ids(room_id).len() as u64;
+        assert_eq!(message_count(room_id).unwrap_or(0), indexed);
+    }
+
+    #[test]
+    fn test_prune_room_keeps_max_messages() {
+        let room = room("ICP_ckUSDT", Some(3), None);
+        let other = post("other", 0);
+        let ids: Vec<u64> = (0..5).map(|i| post(&room.id, i * SECOND)).collect();
+        assert_eq!(message_count(&room.id), Some(5));
+
+        prune_room(&room, 10 * SECOND);
+        assert_eq!(room_message_ids(&room.id), ids[2..].to_vec());
+        assert_count_in_sync(&room.id);
+        assert!(MESSAGE_STORE.with(|store| store.borrow().get(&ids[0]).is_none()));
+
+        // other rooms are not affected
+        assert_eq!(room_message_ids("other"), vec![other]);
+        assert_count_in_sync("other");
+    }
+
+    #[test]
+    fn test_prune_room_drops_expired_messages() {
+        let room = room("market-1", None, Some(60));
+        let old = post(&room.id, 0);
+        let recent = post(&room.id, 100 * SECOND);
+
+        prune_room(&room, 120 * SECOND);
+        assert_eq!(room_message_ids(&room.id), vec![recent]);
+        assert!(MESSAGE_STORE.with(|store| store.borrow().get(&old).is_none()));
+        assert_count_in_sync(&room.id);
+
+        // once every message expired the room has no count left
+        prune_room(&room, 1_000 * SECOND);
+        assert!(room_message_ids(&room.id).is_empty());
+        assert_eq!(message_count(&room.id), None);
+    }
+
+    #[test]
+    fn test_remove_room_messages_keeps_count_in_sync() {
+        let first = post("pool", 0);
+        let second = post("pool", 0);
+        let elsewhere = post("other", 0);
+
+        // IDs of another room or already removed are not counted
+        remove_room_messages("pool", &[first, elsewhere]);
+        assert_eq!(message_count("pool"), Some(1));
+        remove_room_messages("pool", &[first]);
+        assert_eq!(message_count("pool"), Some(1));
+        assert_count_in_sync("pool");
+
+        remove_room_messages("pool", &[second]);
+        assert_eq!(message_count("pool"), None);
+    }
+
+    #[test]
+    fn test_init_rooms_rebuilds_message_counts() {
+        ROOM_STORE.with(|rooms| rooms.borrow_mut().insert(GLOBAL_ROOM_ID.to_string(), room(GLOBAL_ROOM_ID, None, None)));
+        post(GLOBAL_ROOM_ID, 0);
+        post(GLOBAL_ROOM_ID, 0);
+        post("pool", 0);
+        // stale counts, e.g. from before the index existed
+        ROOM_MESSAGE_COUNTS.with(|counts| {
+            let mut counts = counts.borrow_mut();
+            counts.insert(GLOBAL_ROOM_ID.to_string(), 7);
+            counts.insert("deleted".to_string(), 3);
+        });
+
+        init_rooms();
+        assert_eq!(message_count(GLOBAL_ROOM_ID), Some(2));
+        assert_eq!(message_count("pool"), Some(1));
+        assert_eq!(message_count("deleted"), None);
+        assert_count_in_sync(GLOBAL_ROOM_ID);
+    }
+}
@@ -1,50 +1,17 @@
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
-use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, Storable};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell};
 use std::cell::RefCell;
-use crate::types::Message;
+use crate::types::{Message, Room, RoomMemberKey, RoomMessageKey, DEFAULT_TRUSTED_ORIGINS};
 use regex::Regex;
 use lazy_static::lazy_static;
-use rustrict::{CensorStr, Type, Censor};
 use std::collections::{HashMap, HashSet};
 use candid::Principal;
-use std::borrow::Cow;
+use moderation::{ModerationMemories, ModerationStore};
+use kong_lib::stable_trusted_origin::trusted_origin_store::{TrustedOriginMemories, TrustedOriginStore};
 
 // Type aliases
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
-// Define a storable wrapper for Principal and u64 for stable storage
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct PrincipalStorable(pub Principal);
-
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct ExpiryTimeStorable(pub u64);
-
-impl Storable for PrincipalStorable {
-    fn to_bytes(&self) -> Cow<[u8]> {
-        Cow::Owned(self.0.as_slice().to_vec())
-    }
-
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        Self(Principal::from_slice(&bytes))
-    }
-
-    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
-}
-
-impl Storable for ExpiryTimeStorable {
-    fn to_bytes(&self) -> Cow<[u8]> {
-        Cow::Owned(self.0.to_le_bytes().to_vec())
-    }
-
-    fn from_bytes(bytes: Cow<[u8]>) -> Self {
-        let mut data = [0u8; 8];
-        data.copy_from_slice(&bytes[0..8]);
-        Self(u64::from_le_bytes(data))
-    }
-
-    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
-}
-
 // Spam prevention constants
 pub const MIN_MESSAGE_INTERVAL_NS: u64 = 5_000_000_000; // 5 seconds in nanoseconds
 
@@ -59,14 +26,73 @@ thread_local! {
         )
     );
 
-    pub static BANNED_USERS_STORE: RefCell<StableBTreeMap<PrincipalStorable, ExpiryTimeStorable, Memory>> = RefCell::new(
+    // Reports, review queue, appeals, bans, word lists and moderation audit log
+    pub static MODERATION: RefCell<ModerationStore<Memory>> = RefCell::new(
+        MEMORY_MANAGER.with(|m| {
+            let m = m.borrow();
+            ModerationStore::init(ModerationMemories {
+                bans: m.get(MemoryId::new(1)), // Banned users store from before moderation was shared
+                reports: m.get(MemoryId::new(9)),
+                contents: m.get(MemoryId::new(10)),
+                appeals: m.get(MemoryId::new(11)),
+                log: m.get(MemoryId::new(12)),
+                settings: m.get(MemoryId::new(13)),
+                pending_reports: m.get(MemoryId::new(16)),
+                pending_contents: m.get(MemoryId::new(17)),
+            })
+        })
+    );
+
+    // ICRC-28 trusted origins, initialized with DEFAULT_TRUSTED_ORIGINS, and the audit log of changes to them
+    pub static TRUSTED_ORIGINS: RefCell<TrustedOriginStore<Memory>> = RefCell::new(
+        MEMORY_MANAGER.with(|m| {
+            let m = m.borrow();
+            TrustedOriginStore::init(
+                TrustedOriginMemories {
+                    origins: m.get(MemoryId::new(4)), // 3 is used by authentication
+                    log: m.get(MemoryId::new(5)),
+                },
+                DEFAULT_TRUSTED_ORIGINS.iter().map(|o| o.to_string()).collect(),
+            )
+        })
+    );
+
+    // Chat rooms by room ID
+    pub static ROOM_STORE: RefCell<StableBTreeMap<String, Room, Memory>> = RefCell::new(
         StableBTreeMap::init(
-            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(1))) // Use a different memory ID
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(6)))
+        )
+    );
+
+    // Message IDs per room, used for pagination and retention
+    pub static ROOM_MESSAGE_INDEX: RefCell<StableBTreeMap<RoomMessageKey, (), Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(7)))
+        )
+    );
+
+    // Number of messages per room, kept in sync with ROOM_MESSAGE_INDEX
+    pub static ROOM_MESSAGE_COUNTS: RefCell<StableBTreeMap<String, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(14)))
+        )
+    );
+
+    // Next message ID, never reused even if the newest message is deleted
+    pub static NEXT_MESSAGE_ID: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(15))),
+            0
+        ).expect("Failed to initialize next message ID")
+    );
+
+    // Members of gated rooms and when their holding was last verified
+    pub static ROOM_MEMBERS: RefCell<StableBTreeMap<RoomMemberKey, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(8)))
         )
     );
 
-    pub static MESSAGE_COUNTER: RefCell<u64> = RefCell::new(0);
-    
     // Track last message timestamp per user for spam prevention
     pub static LAST_MESSAGE_TIME: RefCell<HashMap<Principal, u64>> = RefCell::new(HashMap::new());
     
@@ -88,15 +114,13 @@ lazy_static! {
 }
 
 // Helper function to validate message content
-pub fn validate_message(message: &str) -> Result<String, String> {
-    use crate::types::MAX_MESSAGE_LENGTH;
-    
+pub fn validate_message(message: &str, max_length: usize) -> Result<String, String> {
     let trimmed = message.trim();
     if trimmed.is_empty() {
         return Err("Message cannot be empty".to_string());
     }
-    if message.len() > MAX_MESSAGE_LENGTH {
-        return Err(format!("Message too long. Maximum length is {}", MAX_MESSAGE_LENGTH));
+    if message.len() > max_length {
+        return Err(format!("Message too long. Maximum length is {}", max_length));
     }
 
     // Check for HTML tags
@@ -109,32 +133,27 @@ pub fn validate_message(message: &str) -> Result<String, String> {
         return Err("CSS styling is not allowed".to_string());
     }
 
-    // Replace inappropriate words with "gorilla"
-    let (censored, _) = Censor::from_str(message)
-        .with_censor_replacement('🦍')
-        .censor_and_analyze();
-    
-    Ok(censored)
+    // Reject blocked words and replace inappropriate words with "gorilla"
+    MODERATION.with(|moderation| moderation.borrow().censor(message))
 }
 
-// Helper function to check if a user is banned
+// Helper function to check if a user is banned, returns the remaining ban time in seconds
 pub fn is_user_banned(principal: &Principal) -> Option<u64> {
-    BANNED_USERS_STORE.with(|banned_users| {
-        let store = banned_users.borrow();
-        if let Some(ExpiryTimeStorable(expiry_time)) = store.get(&PrincipalStorable(principal.clone())) {
-            let current_time = ic_cdk::api::time();
-            
-            // If ban has expired, remove the user from the banned list
-            if current_time > expiry_time {
-                drop(store); // Release the borrowed reference before mutating
-                banned_users.borrow_mut().remove(&PrincipalStorable(principal.clone()));
-                None
-            } else {
-                // Return remaining ban time in seconds
-                Some((expiry_time - current_time) / 1_000_000_000)
-            }
-        } else {
-            None
-        }
+    MODERATION.with(|moderation| {
+        moderation.borrow_mut().ban_remaining_secs(principal, ic_cdk::api::time())
     })
-} 
\ No newline at end of file
+}
+
+// Helper function to get the next message ID, kept in stable memory so IDs of deleted messages are never reused
+pub fn next_message_id() -> u64 {
+    // Messages stored before the counter existed are taken into account
+    let last_stored = MESSAGE_STORE.with(|store| {
+        store.borrow().last_key_value().map(|(id, _)| id + 1).unwrap_or(0)
+    });
+    NEXT_MESSAGE_ID.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let id = (*cell.get()).max(last_stored);
+        cell.set(id + 1).expect("Failed to update next message ID");
+        id
+    })
+}
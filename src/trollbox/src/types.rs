@@ -1,13 +1,28 @@
-use candid::{CandidType, Deserialize, Principal};
+use candid::{CandidType, Deserialize, Nat, Principal};
 use ic_stable_structures::Storable;
 use serde::Serialize;
 
+// Moderation types shared with the comments canister
+pub use moderation::{
+    Appeal, AppealStatus, AppealTarget, AppealsPage, ContentStatus, ModeratedContent, ModerationAction, ModerationConfig,
+    ModerationLogEntry, ModerationLogPage, Report, ReportReason, ReportStatus, ReviewDecision, ReviewItem, ReviewQueuePage, WordLists,
+};
+
 // Constants
 pub const MAX_MESSAGE_LENGTH: usize = 280; // Twitter-style limit
 pub const MAX_MESSAGES_STORED: usize = 100; // Keep last 100 messages
 pub const MAX_USERNAME_LENGTH: usize = 32;
 pub const DEFAULT_PAGE_SIZE: usize = 20;
 
+// Room constants
+pub const GLOBAL_ROOM_ID: &str = "global";
+pub const MAX_ROOM_ID_LENGTH: usize = 64;
+pub const MAX_ROOM_NAME_LENGTH: usize = 64;
+pub const MAX_ROOM_MESSAGE_LENGTH: usize = 1000;
+pub const MAX_ROOM_MODERATORS: usize = 20;
+pub const ROOM_MEMBERSHIP_TTL_NS: u64 = 24 * 60 * 60 * 1_000_000_000; // Gated memberships are re-checked daily
+pub const KONG_BACKEND_CANISTER_ID: &str = "2ipq2-uqaaa-aaaar-qailq-cai";
+
 // Message structure
 #[derive(Debug, Clone, CandidType, Deserialize, Serialize)]
 pub struct Message {
@@ -15,6 +30,12 @@ pub struct Message {
     pub message: String,
     pub principal: Principal,
     pub created_at: u64,
+    #[serde(default = "default_room_id")] // Messages stored before rooms belong to the global room
+    pub room_id: String,
+}
+
+fn default_room_id() -> String {
+    GLOBAL_ROOM_ID.to_string()
 }
 
 impl Storable for Message { 
@@ -30,6 +51,109 @@ impl Storable for Message {
     const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
 }
 
+// What a room is about
+#[derive(Debug, Clone, PartialEq, CandidType, Deserialize)]
+pub enum RoomKind {
+    Global,
+    Token(Principal), // ICRC-1 ledger of the token
+    Pool(String),     // Kong pool symbol, e.g. "ICP_ckUSDT"
+    Market(u64),      // Prediction market ID
+}
+
+// Minimum holding required to join a room
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub enum RoomGate {
+    // ICRC-1 balance checked with icrc1_balance_of, in the token's smallest unit
+    TokenBalance { ledger: Principal, min_balance: Nat },
+    // LP balance of a Kong pool checked with kong_backend user_balances
+    LpBalance { symbol: String, min_balance: f64 },
+}
+
+// Chat room with its own gate, retention and moderators
+#[derive(Debug, Clone, CandidType, Deserialize)]
+pub struct Room {
+    pub id: String,
+    pub name: String,
+    pub kind: RoomKind,
+    pub gate: Option<RoomGate>,
+    pub max_message_length: u64,
+    pub max_messages: Option<u64>,   // Keep only the latest messages
+    pub retention_secs: Option<u64>, // Drop messages older than this
+    pub moderators: Vec<Principal>,
+    pub created_by: Principal,
+    pub created_at: u64,
+}
+
+impl Storable for Room {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        std::borrow::Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RoomArgs {
+    pub id: String,
+    pub name: String,
+    pub kind: RoomKind,
+    pub gate: Option<RoomGate>,
+    pub max_message_length: Option<u64>,
+    pub max_messages: Option<u64>,
+    pub retention_secs: Option<u64>,
+}
+
+// Index of messages per room
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, CandidType, Deserialize)]
+pub struct RoomMessageKey {
+    pub room_id: String,
+    pub message_id: u64,
+}
+
+// Encoded as length-prefixed room ID followed by the big-endian message ID, so the keys of a room
+// are contiguous and ordered by message ID
+impl Storable for RoomMessageKey {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        let mut bytes = Vec::with_capacity(4 + self.room_id.len() + 8);
+        bytes.extend_from_slice(&(self.room_id.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(self.room_id.as_bytes());
+        bytes.extend_from_slice(&self.message_id.to_be_bytes());
+        std::borrow::Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        let len = u32::from_be_bytes(bytes[..4].try_into().unwrap()) as usize;
+        let room_id = String::from_utf8(bytes[4..4 + len].to_vec()).unwrap();
+        let message_id = u64::from_be_bytes(bytes[4 + len..].try_into().unwrap());
+        RoomMessageKey { room_id, message_id }
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
+// Member of a gated room, mapped to the time the gate was last verified
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, CandidType, Deserialize)]
+pub struct RoomMemberKey {
+    pub room_id: String,
+    pub principal: Principal,
+}
+
+impl Storable for RoomMemberKey {
+    fn to_bytes(&self) -> std::borrow::Cow<[u8]> {
+        std::borrow::Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: ic_stable_structures::storable::Bound = ic_stable_structures::storable::Bound::Unbounded;
+}
+
 #[derive(CandidType, Deserialize)]
 pub struct PaginationParams {
     pub cursor: Option<u64>,  // Timestamp in nanoseconds
@@ -40,4 +164,61 @@ pub struct PaginationParams {
 pub struct MessagesPage {
     pub messages: Vec<Message>,
     pub next_cursor: Option<u64>,
-} 
\ No newline at end of file
+}
+
+// ICRC-28 trusted origins, defaults used until changed with add_trusted_origin / remove_trusted_origin
+pub const DEFAULT_TRUSTED_ORIGINS: [&str; 6] = [
+    "https://edoy4-liaaa-aaaar-qakha-cai.localhost:5173", // svelte FE
+    "http://localhost:5173",
+    "https://kongswap.io",
+    "https://www.kongswap.io",
+    "https://edoy4-liaaa-aaaar-qakha-cai.icp0.io",
+    "https://dev.kongswap.io",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+
+    fn key(room_id: &str, message_id: u64) -> RoomMessageKey {
+        RoomMessageKey { room_id: room_id.to_string(), message_id }
+    }
+
+    #[test]
+    fn test_room_message_key_round_trip() {
+        for key in [key("global", 0), key("", 7), key("token:ryjl3-tyaaa-aaaaa-aaaba-cai", u64::MAX), key("pool:ICP_🦍", 256)] {
+            assert_eq!(RoomMessageKey::from_bytes(key.to_bytes()), key);
+        }
+    }
+
+    #[test]
+    fn test_room_message_key_bytes_ordered_by_message_id() {
+        let ids = [0, 1, 255, 256, 65_536, u64::MAX];
+        for pair in ids.windows(2) {
+            assert!(key("pool", pair[0]).to_bytes() < key("pool", pair[1]).to_bytes());
+        }
+    }
+
+    #[test]
+    fn test_room_message_keys_of_a_room_are_contiguous() {
+        let mut index: StableBTreeMap<RoomMessageKey, (), _> = StableBTreeMap::init(DefaultMemoryImpl::default());
+        // rooms that share a prefix, with message IDs interleaved between them
+        for (i, room_id) in ["a", "ab", "b", "a", "", "ab", "a"].iter().enumerate() {
+            index.insert(key(room_id, 300 - i as u64 * 50), ());
+        }
+
+        let room_ids = |room_id: &str| -> Vec<u64> {
+            index
+                .range(key(room_id, 0)..)
+                .take_while(|(key, _)| key.room_id == room_id)
+                .map(|(key, _)| key.message_id)
+                .collect()
+        };
+        assert_eq!(room_ids("a"), vec![0, 150, 300]);
+        assert_eq!(room_ids("ab"), vec![50, 250]);
+        assert_eq!(room_ids("b"), vec![200]);
+        assert_eq!(room_ids(""), vec![100]);
+        assert!(room_ids("c").is_empty());
+    }
+}